@@ -0,0 +1,397 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{instruction::Instruction, system_program};
+use clap::{Subcommand, ValueEnum};
+use message_gateway_client::state::{HashAlgorithm, IntegerEncoding, SignerRegistryType};
+use message_gateway_client::{accounts, instruction, ix, pda};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Registry {
+    Via,
+    Chain,
+    Project,
+}
+
+impl From<Registry> for SignerRegistryType {
+    fn from(registry: Registry) -> Self {
+        match registry {
+            Registry::Via => SignerRegistryType::VIA,
+            Registry::Chain => SignerRegistryType::Chain,
+            Registry::Project => SignerRegistryType::Project,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Hash {
+    Keccak256,
+    Sha256,
+    Blake3,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Encoding {
+    Le,
+    Be,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create the gateway for --gateway-chain-id, with the signer as authority
+    InitGateway,
+    
+    /// Create another instance of the gateway for --gateway-chain-id (blue/green, canary)
+    InitGatewayInstance {
+        /// Non-zero instance id (0 is the gateway created by init-gateway)
+        #[arg(long)]
+        instance_id: u16,
+    },
+    
+    /// Create the replay counter of a source chain
+    InitCounter {
+        #[arg(long)]
+        source_chain_id: u64,
+    },
+    
+    /// Create a signer registry
+    InitRegistry {
+        #[arg(long, value_enum)]
+        registry: Registry,
+        /// Chain the registry covers (the gateway's own chain for VIA)
+        #[arg(long)]
+        chain_id: u64,
+        /// Comma-separated signer pubkeys
+        #[arg(long, value_delimiter = ',', required = true)]
+        signers: Vec<Pubkey>,
+        #[arg(long)]
+        threshold: u8,
+    },
+    
+    /// Create the configuration of a counterpart chain
+    InitChainConfig {
+        #[arg(long)]
+        chain_id: u64,
+        #[arg(long, value_enum, default_value = "keccak256")]
+        hash: Hash,
+        #[arg(long, value_enum, default_value = "le")]
+        encoding: Encoding,
+    },
+    
+    /// Whether send_message accepts a counterpart chain as destination, and its send limits
+    SetDestinationConfig {
+        #[arg(long)]
+        chain_id: u64,
+        #[arg(long, action = clap::ArgAction::Set)]
+        enabled: bool,
+        #[arg(long)]
+        min_confirmations: u16,
+        #[arg(long)]
+        max_confirmations: u16,
+        /// Largest on-chain data in bytes
+        #[arg(long)]
+        max_payload_size: u32,
+    },
+    
+    /// Add one signer to a registry
+    AddSigner {
+        #[arg(long, value_enum)]
+        registry: Registry,
+        #[arg(long)]
+        chain_id: u64,
+        #[arg(long)]
+        signer: Pubkey,
+    },
+    
+    /// Remove one signer from a registry
+    RemoveSigner {
+        #[arg(long, value_enum)]
+        registry: Registry,
+        #[arg(long)]
+        chain_id: u64,
+        #[arg(long)]
+        signer: Pubkey,
+    },
+    
+    /// Replace a registry's whole signer set and threshold in one instruction
+    RotateSigners {
+        #[arg(long, value_enum)]
+        registry: Registry,
+        #[arg(long)]
+        chain_id: u64,
+        #[arg(long, value_delimiter = ',', required = true)]
+        signers: Vec<Pubkey>,
+        #[arg(long)]
+        threshold: u8,
+    },
+    
+    /// Change a registry's signature threshold
+    SetThreshold {
+        #[arg(long, value_enum)]
+        registry: Registry,
+        #[arg(long)]
+        chain_id: u64,
+        #[arg(long)]
+        threshold: u8,
+    },
+    
+    /// Enable or disable a registry
+    SetRegistryEnabled {
+        #[arg(long, value_enum)]
+        registry: Registry,
+        #[arg(long)]
+        chain_id: u64,
+        #[arg(long, action = clap::ArgAction::Set)]
+        enabled: bool,
+    },
+    
+    /// Evaluate a registry's threshold without enforcing it until a unix timestamp,
+    /// enforcing `enforced` signatures meanwhile (a past timestamp ends the trial)
+    SetRegistryShadow {
+        #[arg(long, value_enum)]
+        registry: Registry,
+        #[arg(long)]
+        chain_id: u64,
+        #[arg(long)]
+        enforced: u8,
+        #[arg(long)]
+        until: i64,
+    },
+    
+    /// Stop sending and processing messages
+    Pause,
+    
+    /// Resume sending and processing messages
+    Unpause,
+    
+    /// Accepted protocol version range of inbound messages
+    SetProtocolVersions {
+        #[arg(long)]
+        min: u8,
+        #[arg(long)]
+        max: u8,
+    },
+    
+    /// Account receiving send fees
+    SetTreasury {
+        #[arg(long)]
+        treasury: Pubkey,
+    },
+    
+    /// Lamport fee charged by send_message for messages to a destination chain
+    SetFeeConfig {
+        #[arg(long)]
+        dest_chain_id: u64,
+        #[arg(long)]
+        base_fee: u64,
+        #[arg(long)]
+        fee_per_byte: u64,
+    },
+    
+    /// Refund paid from the fee vault to relayers of messages from a chain
+    SetRelayerRefund {
+        #[arg(long)]
+        chain_id: u64,
+        /// Flat lamports per message
+        #[arg(long)]
+        refund: u64,
+        /// Share of the route's send fee, in basis points
+        #[arg(long)]
+        fee_share_bps: u16,
+    },
+    
+    /// Move accumulated send fees out of the fee vault
+    WithdrawFees {
+        #[arg(long)]
+        amount: u64,
+        #[arg(long)]
+        recipient: Pubkey,
+    },
+    
+    /// Allow messages whose source and destination are this gateway's chain
+    SetLoopback {
+        #[arg(long, action = clap::ArgAction::Set)]
+        enabled: bool,
+    },
+    
+    /// Verify every TX2 attestation instead of stopping at the thresholds
+    SetFullSignatureVerification {
+        #[arg(long, action = clap::ArgAction::Set)]
+        enabled: bool,
+    },
+    
+    /// Also compare the signature bytes of matched precompile instructions
+    SetStrictPrecompileChecks {
+        #[arg(long, action = clap::ArgAction::Set)]
+        enabled: bool,
+    },
+}
+
+/// Accounts of the registry admin instructions, which all share one layout
+macro_rules! registry_accounts {
+    ($accounts:ident, $authority:expr, $gateway:expr, $registry:expr, $chain_id:expr) => {
+        accounts::$accounts {
+            signer_registry: pda::signer_registry($registry.into(), $chain_id).0,
+            gateway: $gateway,
+            authority: *$authority,
+        }
+    };
+}
+
+impl Command {
+    /// Gateway instructions the command sends
+    pub fn instructions(&self, authority: &Pubkey, gateway_chain_id: u64) -> Vec<Instruction> {
+        let gateway = pda::gateway(gateway_chain_id).0;
+        let built = match *self {
+            Command::InitGateway => ix::initialize_gateway(authority, gateway_chain_id),
+            Command::InitGatewayInstance { instance_id } => {
+                ix::initialize_gateway_instance(authority, gateway_chain_id, instance_id)
+            }
+            Command::InitCounter { source_chain_id } => ix::build(
+                accounts::InitializeCounter {
+                    counter_pda: pda::counter(source_chain_id).0,
+                    authority: *authority,
+                    gateway,
+                    system_program: system_program::ID,
+                },
+                instruction::InitializeCounter { source_chain_id },
+            ),
+            Command::InitRegistry { registry, chain_id, ref signers, threshold } => ix::initialize_signer_registry(
+                authority,
+                gateway_chain_id,
+                registry.into(),
+                chain_id,
+                signers.clone(),
+                threshold,
+            ),
+            Command::InitChainConfig { chain_id, hash, encoding } => ix::build(
+                accounts::InitializeChainConfig {
+                    chain_config: pda::chain_config(chain_id).0,
+                    gateway,
+                    authority: *authority,
+                    system_program: system_program::ID,
+                },
+                instruction::InitializeChainConfig {
+                    chain_id,
+                    hash_algorithm: match hash {
+                        Hash::Keccak256 => HashAlgorithm::Keccak256,
+                        Hash::Sha256 => HashAlgorithm::Sha256,
+                        Hash::Blake3 => HashAlgorithm::Blake3,
+                    },
+                    integer_encoding: match encoding {
+                        Encoding::Le => IntegerEncoding::LittleEndian,
+                        Encoding::Be => IntegerEncoding::BigEndian,
+                    },
+                },
+            ),
+            Command::SetDestinationConfig {
+                chain_id,
+                enabled,
+                min_confirmations,
+                max_confirmations,
+                max_payload_size,
+            } => ix::build(
+                accounts::SetDestinationConfig {
+                    chain_config: pda::chain_config(chain_id).0,
+                    gateway,
+                    authority: *authority,
+                },
+                instruction::SetDestinationConfig {
+                    chain_id,
+                    enabled,
+                    min_confirmations,
+                    max_confirmations,
+                    max_payload_size,
+                },
+            ),
+            Command::AddSigner { registry, chain_id, signer } => ix::build(
+                registry_accounts!(AddSigner, authority, gateway, registry, chain_id),
+                instruction::AddSigner { registry_type: registry.into(), chain_id, new_signer: signer },
+            ),
+            Command::RemoveSigner { registry, chain_id, signer } => ix::build(
+                registry_accounts!(RemoveSigner, authority, gateway, registry, chain_id),
+                instruction::RemoveSigner { registry_type: registry.into(), chain_id, signer_to_remove: signer },
+            ),
+            Command::RotateSigners { registry, chain_id, ref signers, threshold } => ix::build(
+                registry_accounts!(UpdateSigners, authority, gateway, registry, chain_id),
+                instruction::UpdateSigners {
+                    registry_type: registry.into(),
+                    chain_id,
+                    new_signers: signers.clone(),
+                    new_required_signatures: threshold,
+                },
+            ),
+            Command::SetThreshold { registry, chain_id, threshold } => ix::build(
+                registry_accounts!(UpdateThreshold, authority, gateway, registry, chain_id),
+                instruction::UpdateThreshold { registry_type: registry.into(), chain_id, new_threshold: threshold },
+            ),
+            Command::SetRegistryEnabled { registry, chain_id, enabled } => ix::build(
+                registry_accounts!(SetRegistryEnabled, authority, gateway, registry, chain_id),
+                instruction::SetRegistryEnabled { registry_type: registry.into(), chain_id, enabled },
+            ),
+            Command::SetRegistryShadow { registry, chain_id, enforced, until } => ix::build(
+                registry_accounts!(SetRegistryShadow, authority, gateway, registry, chain_id),
+                instruction::SetRegistryShadow {
+                    registry_type: registry.into(),
+                    chain_id,
+                    enforced_signatures: enforced,
+                    shadow_until: until,
+                },
+            ),
+            Command::Pause => ix::set_system_enabled(authority, gateway_chain_id, false),
+            Command::Unpause => ix::set_system_enabled(authority, gateway_chain_id, true),
+            Command::SetProtocolVersions { min, max } => ix::build(
+                accounts::SetProtocolVersions { gateway, authority: *authority },
+                instruction::SetProtocolVersions { min_protocol_version: min, max_protocol_version: max },
+            ),
+            Command::SetTreasury { treasury } => ix::build(
+                accounts::SetTreasury { gateway, authority: *authority },
+                instruction::SetTreasury { treasury },
+            ),
+            Command::SetFeeConfig { dest_chain_id, base_fee, fee_per_byte } => ix::build(
+                accounts::SetFeeConfig {
+                    fee_config: pda::fee_config(dest_chain_id).0,
+                    fee_vault: pda::fee_vault().0,
+                    gateway,
+                    authority: *authority,
+                    system_program: system_program::ID,
+                },
+                instruction::SetFeeConfig { dest_chain_id, base_fee, fee_per_byte },
+            ),
+            Command::SetRelayerRefund { chain_id, refund, fee_share_bps } => ix::build(
+                accounts::SetRelayerRefund {
+                    fee_config: pda::fee_config(chain_id).0,
+                    gateway,
+                    authority: *authority,
+                    system_program: system_program::ID,
+                },
+                instruction::SetRelayerRefund {
+                    chain_id,
+                    relayer_refund: refund,
+                    relayer_fee_share_bps: fee_share_bps,
+                },
+            ),
+            Command::WithdrawFees { amount, recipient } => ix::build(
+                accounts::WithdrawFees {
+                    fee_vault: pda::fee_vault().0,
+                    gateway,
+                    authority: *authority,
+                    recipient,
+                    system_program: system_program::ID,
+                },
+                instruction::WithdrawFees { amount },
+            ),
+            Command::SetLoopback { enabled } => ix::build(
+                accounts::SetLoopbackEnabled { gateway, authority: *authority },
+                instruction::SetLoopbackEnabled { enabled },
+            ),
+            Command::SetFullSignatureVerification { enabled } => ix::build(
+                accounts::SetFullSignatureVerification { gateway, authority: *authority },
+                instruction::SetFullSignatureVerification { enabled },
+            ),
+            Command::SetStrictPrecompileChecks { enabled } => ix::build(
+                accounts::SetStrictPrecompileChecks { gateway, authority: *authority },
+                instruction::SetStrictPrecompileChecks { enabled },
+            ),
+        };
+        vec![built]
+    }
+}
\ No newline at end of file
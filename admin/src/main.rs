@@ -0,0 +1,72 @@
+//! Admin CLI for the Via Labs V4 message gateway
+//!
+//! Every command builds its gateway instruction through `message-gateway-client`, signs it
+//! with the authority keypair and sends it; with `--dry-run` it prints the instructions and
+//! the unsigned transaction (base64, for multisig review or simulation) instead
+
+mod command;
+mod output;
+
+use anchor_lang::prelude::Pubkey;
+use clap::Parser;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::{read_keypair_file, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::command::Command;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Parser)]
+#[command(name = "via-admin", about = "Administer a Via Labs V4 message gateway")]
+struct Cli {
+    /// RPC endpoint
+    #[arg(long, short = 'u', global = true, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    
+    /// Gateway authority keypair (default: Solana CLI keypair)
+    #[arg(long, short = 'k', global = true)]
+    keypair: Option<String>,
+    
+    /// Chain id the gateway was initialized with
+    #[arg(long, global = true, default_value_t = 0)]
+    gateway_chain_id: u64,
+    
+    /// Print the transaction instead of sending it
+    #[arg(long, global = true)]
+    dry_run: bool,
+    
+    /// Authority to build a dry run for without its keypair (e.g. a multisig)
+    #[arg(long, global = true, requires = "dry_run")]
+    authority: Option<Pubkey>,
+    
+    #[command(subcommand)]
+    command: Command,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    if let Some(authority) = cli.authority {
+        let instructions = cli.command.instructions(&authority, cli.gateway_chain_id);
+        output::print_dry_run(&Transaction::new_with_payer(&instructions, Some(&authority)));
+        return Ok(());
+    }
+    
+    let keypair_path = cli.keypair.clone().unwrap_or_else(|| {
+        format!("{}/.config/solana/id.json", std::env::var("HOME").unwrap_or_default())
+    });
+    let authority = read_keypair_file(&keypair_path)?;
+    let instructions = cli.command.instructions(&authority.pubkey(), cli.gateway_chain_id);
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(&authority.pubkey()));
+    if cli.dry_run {
+        output::print_dry_run(&transaction);
+        return Ok(());
+    }
+    
+    let client = RpcClient::new_with_commitment(cli.url, CommitmentConfig::confirmed());
+    transaction.sign(&[&authority], client.get_latest_blockhash()?);
+    let signature = client.send_and_confirm_transaction(&transaction)?;
+    println!("{signature}");
+    Ok(())
+}
\ No newline at end of file
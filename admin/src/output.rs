@@ -0,0 +1,23 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use solana_sdk::transaction::Transaction;
+
+/// Instructions with their accounts and data, then the unsigned transaction
+pub fn print_dry_run(transaction: &Transaction) {
+    let message = &transaction.message;
+    for (index, instruction) in message.instructions.iter().enumerate() {
+        println!("instruction {index}: program {}", message.account_keys[instruction.program_id_index as usize]);
+        for &account in &instruction.accounts {
+            let account = account as usize;
+            println!(
+                "  {} {}{}",
+                message.account_keys[account],
+                if message.is_signer(account) { "signer " } else { "" },
+                if message.is_maybe_writable(account, None) { "writable" } else { "readonly" },
+            );
+        }
+        let data: String = instruction.data.iter().map(|b| format!("{b:02x}")).collect();
+        println!("  data {data}");
+    }
+    println!("unsigned transaction (base64, blockhash unset):");
+    println!("{}", STANDARD.encode(message.serialize()));
+}
\ No newline at end of file
@@ -0,0 +1,44 @@
+//! Gateway account decoding and, with the `rpc` feature, fetching
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::{AccountDeserialize, ZeroCopy};
+
+use crate::ClientError;
+
+/// Borsh account (discriminator checked)
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T, ClientError> {
+    let mut data = data;
+    Ok(T::try_deserialize(&mut data)?)
+}
+
+/// Zero-copy account such as `RegistryBundle` (discriminator checked)
+pub fn decode_zero_copy<T: ZeroCopy>(data: &[u8]) -> Result<T, ClientError> {
+    let disc = T::DISCRIMINATOR;
+    let body = data
+        .strip_prefix(disc)
+        .filter(|body| body.len() >= std::mem::size_of::<T>())
+        .ok_or(ClientError::Decode(ErrorCode::AccountDiscriminatorMismatch.into()))?;
+    Ok(bytemuck::pod_read_unaligned(&body[..std::mem::size_of::<T>()]))
+}
+
+/// Fetch and decode a Borsh account
+#[cfg(feature = "rpc")]
+pub fn fetch<T: AccountDeserialize>(
+    client: &solana_client::rpc_client::RpcClient,
+    address: &anchor_lang::prelude::Pubkey,
+) -> Result<T, ClientError> {
+    fetch_optional(client, address)?.ok_or(ClientError::AccountNotFound(*address))
+}
+
+/// Fetch and decode a Borsh account that may not exist, e.g. a TxId PDA to tell
+/// whether TX1 landed (or TX2 already closed it)
+#[cfg(feature = "rpc")]
+pub fn fetch_optional<T: AccountDeserialize>(
+    client: &solana_client::rpc_client::RpcClient,
+    address: &anchor_lang::prelude::Pubkey,
+) -> Result<Option<T>, ClientError> {
+    let account = client
+        .get_account_with_commitment(address, client.commitment())?
+        .value;
+    account.map(|account| decode(&account.data)).transpose()
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+
+/// Errors of the client helpers
+#[derive(Debug)]
+pub enum ClientError {
+    /// Account data is not the expected gateway account
+    Decode(anchor_lang::error::Error),
+    
+    /// No account exists at the address
+    AccountNotFound(Pubkey),
+    
+    /// Envelope cannot be hashed (fails `MessageEnvelope::validate`)
+    InvalidEnvelope(anchor_lang::error::Error),
+    
+    /// RPC request failed
+    #[cfg(feature = "rpc")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Decode(error) => write!(f, "account decode failed: {error}"),
+            ClientError::AccountNotFound(address) => write!(f, "account {address} not found"),
+            ClientError::InvalidEnvelope(error) => write!(f, "invalid envelope: {error}"),
+            #[cfg(feature = "rpc")]
+            ClientError::Rpc(error) => write!(f, "rpc request failed: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<anchor_lang::error::Error> for ClientError {
+    fn from(error: anchor_lang::error::Error) -> Self {
+        ClientError::Decode(error)
+    }
+}
+
+#[cfg(feature = "rpc")]
+impl From<solana_client::client_error::ClientError> for ClientError {
+    fn from(error: solana_client::client_error::ClientError) -> Self {
+        ClientError::Rpc(Box::new(error))
+    }
+}
\ No newline at end of file
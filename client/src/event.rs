@@ -0,0 +1,50 @@
+//! Gateway events from transaction logs
+//!
+//! `emit!` writes each event as a `Program data: <base64>` line holding its discriminator
+//! followed by the Borsh body. Only lines logged while the gateway itself is executing
+//! are read, so an event-shaped log from a CPI'd or sibling program is never mistaken
+//! for a gateway event
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// Event of type `E` from the bytes of one `Program data:` line
+pub fn decode_event<E: AnchorDeserialize + Discriminator>(data: &[u8]) -> Option<E> {
+    let body = data.strip_prefix(E::DISCRIMINATOR)?;
+    E::try_from_slice(body).ok()
+}
+
+/// Every event of type `E` the gateway emitted, in log order
+pub fn parse_events<E: AnchorDeserialize + Discriminator>(logs: &[String]) -> Vec<E> {
+    gateway_data(logs)
+        .filter_map(|data| decode_event(&data))
+        .collect()
+}
+
+/// Decoded `Program data:` payloads logged by the gateway
+pub fn gateway_data(logs: &[String]) -> impl Iterator<Item = Vec<u8>> + '_ {
+    let gateway = message_gateway_v4::ID.to_string();
+    let mut stack: Vec<bool> = Vec::new();
+    
+    logs.iter().filter_map(move |line| {
+        if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            let program = words.next().unwrap_or_default();
+            match words.next() {
+                Some("invoke") => stack.push(program == gateway),
+                Some("success") | Some("failed:") => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+        
+        let data = line.strip_prefix(PROGRAM_DATA)?;
+        if !stack.last().copied().unwrap_or(false) {
+            return None;
+        }
+        STANDARD.decode(data).ok()
+    })
+}
\ No newline at end of file
@@ -0,0 +1,208 @@
+//! Signed-message fixtures (feature `fixtures`)
+//!
+//! Given validator keypairs and an envelope, produces everything a TX1/TX2 pair carries:
+//! the attestations, the signing hash, the Ed25519 precompile instructions and both
+//! gateway instructions. `Corruption` variants break one thing each, so integrators and
+//! auditors can check that the gateway (or their own verifier) rejects them
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use message_gateway_v4::state::{HashScheme, MessageEnvelope, MessageSignature, SignatureScheme};
+use message_gateway_v4::txbuilder::{
+    create_tx_pda_instruction, ed25519_verify_instructions, process_message_instruction, InboundRoute,
+};
+use message_gateway_v4::utils::ed25519::{ed25519_instruction, ED25519_OFFSETS_ENTRY_SIZE, ED25519_OFFSETS_START};
+use message_gateway_v4::utils::hash::create_message_hash_for_signing;
+use solana_sdk::signature::Signer;
+
+pub use solana_sdk::signature::Keypair;
+
+use crate::ClientError;
+
+/// One deliberately broken variant of a signed message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Corruption {
+    /// Validators sign, and precompiles verify, a different hash (TX1: InvalidSignature)
+    WrongHash,
+    /// The envelope is changed after signing (TX1: InvalidSignature)
+    TamperedEnvelope,
+    /// The first signature has a bit flipped, so its precompile fails in the runtime
+    TamperedSignature,
+    /// The first attestation has no precompile instruction; TX1 accepts the others, TX2 does
+    /// not count it (Insufficient*Signatures once its layer falls short)
+    MissingPrecompile,
+    /// The first attestation is listed twice (TX2: DuplicateSigner)
+    DuplicateSigner,
+    /// Precompile entries read their key and message from another instruction, which the
+    /// gateway ignores (TX1: InvalidSignature)
+    ForeignOffsets,
+}
+
+impl Corruption {
+    /// Every variant, for table-driven tests
+    pub const ALL: [Corruption; 6] = [
+        Corruption::WrongHash,
+        Corruption::TamperedEnvelope,
+        Corruption::TamperedSignature,
+        Corruption::MissingPrecompile,
+        Corruption::DuplicateSigner,
+        Corruption::ForeignOffsets,
+    ];
+}
+
+/// TX1/TX2 arguments and instructions of one signed message
+#[derive(Clone, Debug)]
+pub struct Fixture {
+    pub envelope: MessageEnvelope,
+    pub signatures: Vec<MessageSignature>,
+    /// Hash the validators signed (`create_message_hash_for_signing`)
+    pub message_hash: [u8; 32],
+    /// Ed25519 precompile instructions, placed before the gateway instruction in both TXs
+    pub precompiles: Vec<Instruction>,
+    /// `create_tx_pda`
+    pub tx1: Instruction,
+    /// `process_message`
+    pub tx2: Instruction,
+}
+
+impl Fixture {
+    /// TX1 instructions in order (without compute budget)
+    pub fn tx1_instructions(&self) -> Vec<Instruction> {
+        [self.precompiles.as_slice(), std::slice::from_ref(&self.tx1)].concat()
+    }
+    
+    /// TX2 instructions in order (without compute budget)
+    pub fn tx2_instructions(&self) -> Vec<Instruction> {
+        [self.precompiles.as_slice(), std::slice::from_ref(&self.tx2)].concat()
+    }
+}
+
+/// Fixture generator for one relayer and inbound route
+pub struct Fixtures {
+    relayer: Pubkey,
+    route: InboundRoute,
+    hash_scheme: HashScheme,
+}
+
+impl Fixtures {
+    /// Generator signing with the default hash scheme and no ChainConfig
+    pub fn new(relayer: Pubkey, route: InboundRoute) -> Self {
+        Self {
+            relayer,
+            route,
+            hash_scheme: HashScheme::default(),
+        }
+    }
+    
+    /// Sign with the scheme of a route that has a ChainConfig
+    pub fn with_hash_scheme(mut self, hash_scheme: HashScheme) -> Self {
+        self.hash_scheme = hash_scheme;
+        self
+    }
+    
+    /// Valid fixture: every signer attests `envelope`
+    pub fn sign(&self, envelope: MessageEnvelope, signers: &[&Keypair]) -> Result<Fixture, ClientError> {
+        let message_hash = self.message_hash(&envelope)?;
+        self.assemble(envelope, attest(signers, &message_hash))
+    }
+    
+    /// Valid fixture from attestations made elsewhere (e.g. `mock_validator::MockValidatorSet`)
+    /// Only Ed25519 attestations get a precompile instruction
+    pub fn assemble(
+        &self,
+        envelope: MessageEnvelope,
+        signatures: Vec<MessageSignature>,
+    ) -> Result<Fixture, ClientError> {
+        let message_hash = self.message_hash(&envelope)?;
+        let precompiles = ed25519_verify_instructions(&signatures, &message_hash);
+        Ok(self.fixture(envelope, signatures, message_hash, precompiles))
+    }
+    
+    /// Fixture broken by `corruption`; `message_hash` stays the one the gateway will compute
+    pub fn corrupt(
+        &self,
+        envelope: MessageEnvelope,
+        signers: &[&Keypair],
+        corruption: Corruption,
+    ) -> Result<Fixture, ClientError> {
+        let mut fixture = self.sign(envelope, signers)?;
+        match corruption {
+            Corruption::WrongHash => {
+                let mut other = fixture.message_hash;
+                other[0] ^= 0xff;
+                fixture.signatures = attest(signers, &other);
+                fixture.precompiles = ed25519_verify_instructions(&fixture.signatures, &other);
+            }
+            Corruption::TamperedEnvelope => {
+                fixture.envelope.on_chain_data.push(0);
+                fixture.message_hash = self.message_hash(&fixture.envelope)?;
+            }
+            Corruption::TamperedSignature => {
+                if let Some(first) = fixture.signatures.first_mut() {
+                    first.signature[0] ^= 1;
+                }
+                fixture.precompiles = ed25519_verify_instructions(&fixture.signatures, &fixture.message_hash);
+            }
+            Corruption::MissingPrecompile => {
+                if !fixture.precompiles.is_empty() {
+                    fixture.precompiles.remove(0);
+                }
+            }
+            Corruption::DuplicateSigner => {
+                if let Some(first) = fixture.signatures.first().cloned() {
+                    fixture.signatures.push(first);
+                }
+            }
+            Corruption::ForeignOffsets => {
+                let entries: Vec<(Pubkey, [u8; 64])> =
+                    fixture.signatures.iter().map(|s| (s.signer, s.signature)).collect();
+                let mut precompile = ed25519_instruction(&entries, &fixture.message_hash);
+                for entry in 0..entries.len() {
+                    let start = ED25519_OFFSETS_START + entry * ED25519_OFFSETS_ENTRY_SIZE;
+                    // signature, public key and message instruction indices -> instruction 0
+                    for field in [2, 6, 12] {
+                        precompile.data[start + field..start + field + 2].copy_from_slice(&0u16.to_le_bytes());
+                    }
+                }
+                fixture.precompiles = vec![precompile];
+            }
+        }
+        Ok(self.fixture(fixture.envelope, fixture.signatures, fixture.message_hash, fixture.precompiles))
+    }
+    
+    fn message_hash(&self, envelope: &MessageEnvelope) -> Result<[u8; 32], ClientError> {
+        create_message_hash_for_signing(self.hash_scheme, self.route.gateway_chain_id, envelope)
+            .map_err(ClientError::InvalidEnvelope)
+    }
+    
+    fn fixture(
+        &self,
+        envelope: MessageEnvelope,
+        signatures: Vec<MessageSignature>,
+        message_hash: [u8; 32],
+        precompiles: Vec<Instruction>,
+    ) -> Fixture {
+        let tx1 = create_tx_pda_instruction(&self.relayer, &envelope, &signatures);
+        let tx2 = process_message_instruction(&self.relayer, &self.route, &envelope, &signatures);
+        Fixture {
+            envelope,
+            signatures,
+            message_hash,
+            precompiles,
+            tx1,
+            tx2,
+        }
+    }
+}
+
+/// Ed25519 attestations of `message_hash` by each signer
+fn attest(signers: &[&Keypair], message_hash: &[u8; 32]) -> Vec<MessageSignature> {
+    signers
+        .iter()
+        .map(|signer| MessageSignature {
+            signature: signer.sign_message(message_hash).into(),
+            signer: signer.pubkey(),
+            scheme: SignatureScheme::Ed25519,
+        })
+        .collect()
+}
\ No newline at end of file
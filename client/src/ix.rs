@@ -0,0 +1,231 @@
+//! Instruction builders
+//!
+//! Every gateway instruction can be built from the program's generated structs, which
+//! carry the discriminator and the account order:
+//! `ix::build(accounts::SetTreasury { .. }, instruction::SetTreasury { .. })`
+//! The functions below also derive the PDAs for the common relayer and admin flows; TX1/TX2
+//! with precompile instructions and lookup tables are assembled by `txbuilder`
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::{instruction::Instruction, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use message_gateway_v4::state::{MessageEnvelope, MessageSignature, SignerRegistryType};
+use message_gateway_v4::{accounts, instruction};
+
+use crate::pda;
+
+pub use message_gateway_v4::txbuilder::{
+    create_tx_pda_instruction, create_tx_pda_instruction_for_instance,
+    create_tx_pda_instruction_with_payer, deliver_message_instruction, process_message_instruction,
+};
+
+/// Gateway instruction from its accounts and arguments
+pub fn build(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    build_with_remaining(accounts, args, Vec::new())
+}
+
+/// Gateway instruction whose handler also reads `remaining` accounts
+pub fn build_with_remaining(
+    accounts: impl ToAccountMetas,
+    args: impl InstructionData,
+    remaining: Vec<AccountMeta>,
+) -> Instruction {
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(remaining);
+    Instruction::new_with_bytes(message_gateway_v4::ID, &args.data(), metas)
+}
+
+/// `initialize_gateway` for `chain_id`
+pub fn initialize_gateway(authority: &Pubkey, chain_id: u64) -> Instruction {
+    build(
+        accounts::InitializeGateway {
+            gateway: pda::gateway(chain_id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeGateway { chain_id },
+    )
+}
+
+/// `initialize_gateway_instance`: instance `instance_id` of the gateway on `chain_id`, next
+/// to the default one
+pub fn initialize_gateway_instance(
+    authority: &Pubkey,
+    chain_id: u64,
+    instance_id: u16,
+) -> Instruction {
+    build(
+        accounts::InitializeGatewayInstance {
+            gateway: pda::gateway_for_instance(chain_id, instance_id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeGatewayInstance { chain_id, instance_id },
+    )
+}
+
+/// `initialize_signer_registry` on the gateway of `gateway_chain_id`
+pub fn initialize_signer_registry(
+    authority: &Pubkey,
+    gateway_chain_id: u64,
+    registry_type: SignerRegistryType,
+    chain_id: u64,
+    initial_signers: Vec<Pubkey>,
+    required_signatures: u8,
+) -> Instruction {
+    build(
+        accounts::InitializeSignerRegistry {
+            signer_registry: pda::signer_registry(registry_type, chain_id).0,
+            gateway: pda::gateway(gateway_chain_id).0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeSignerRegistry {
+            registry_type,
+            chain_id,
+            initial_signers,
+            required_signatures,
+        },
+    )
+}
+
+/// `set_system_enabled` on the gateway of `chain_id`
+pub fn set_system_enabled(authority: &Pubkey, chain_id: u64, enabled: bool) -> Instruction {
+    build(
+        accounts::SetSystemEnabled {
+            gateway: pda::gateway(chain_id).0,
+            authority: *authority,
+        },
+        instruction::SetSystemEnabled { enabled },
+    )
+}
+
+/// TX1 with the TxId PDA rent advanced from the relayer's pool
+pub fn create_tx_pda_pooled(
+    relayer: &Pubkey,
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+) -> Instruction {
+    build(
+        accounts::CreateTxPdaPooled {
+            tx_id_pda: pda::tx_id(envelope.source_chain_id, envelope.tx_id).0,
+            gateway: pda::gateway(envelope.dest_chain_id).0,
+            counter_pda: pda::counter(envelope.source_chain_id).0,
+            relayer_pool: pda::relayer_pool(relayer).0,
+            chain_config: pda::chain_config(envelope.source_chain_id).0,
+            relayer: *relayer,
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateTxPdaPooled {
+            envelope: envelope.clone(),
+            signatures: signatures.to_vec(),
+        },
+    )
+}
+
+/// `deposit_relayer_pool` into the relayer's pool on the gateway of `chain_id`
+pub fn deposit_relayer_pool(relayer: &Pubkey, chain_id: u64, amount: u64) -> Instruction {
+    build(
+        accounts::DepositRelayerPool {
+            gateway: pda::gateway(chain_id).0,
+            relayer_pool: pda::relayer_pool(relayer).0,
+            relayer: *relayer,
+            system_program: system_program::ID,
+        },
+        instruction::DepositRelayerPool { amount },
+    )
+}
+
+/// `withdraw_relayer_pool` from the relayer's pool on the gateway of `chain_id`
+pub fn withdraw_relayer_pool(relayer: &Pubkey, chain_id: u64, amount: u64) -> Instruction {
+    build(
+        accounts::WithdrawRelayerPool {
+            gateway: pda::gateway(chain_id).0,
+            relayer_pool: pda::relayer_pool(relayer).0,
+            relayer: *relayer,
+        },
+        instruction::WithdrawRelayerPool { amount },
+    )
+}
+
+/// `gc_batch` over `(tx_id_pda, refund)` pairs; the refund account is the PDA's recorded
+/// relayer, or its relayer pool when TX1 was pooled
+pub fn gc_batch(keeper: &Pubkey, pairs: &[(Pubkey, Pubkey)]) -> Instruction {
+    let remaining = pairs
+        .iter()
+        .flat_map(|(tx_id_pda, refund)| {
+            [AccountMeta::new(*tx_id_pda, false), AccountMeta::new(*refund, false)]
+        })
+        .collect();
+    build_with_remaining(
+        accounts::GcBatch { keeper: *keeper },
+        instruction::GcBatch {},
+        remaining,
+    )
+}
+
+/// `verify_hash_consistency` of `envelope` against a counterpart's `cross_chain_hash`, under
+/// the route's hash scheme; simulate it and decode the return data as
+/// `utils::hash::HashConsistency`
+pub fn verify_hash_consistency(
+    envelope: MessageEnvelope,
+    cross_chain_hash: [u8; 32],
+) -> Instruction {
+    build(
+        accounts::VerifyHash {
+            gateway: pda::gateway(envelope.dest_chain_id).0,
+            chain_config: pda::chain_config(envelope.source_chain_id).0,
+        },
+        instruction::VerifyHashConsistency { envelope, cross_chain_hash },
+    )
+}
+
+/// `estimate_relay_cost`; simulate it and decode the return data as
+/// `utils::cost::RelayCostEstimate`
+pub fn estimate_relay_cost(payload_size: u32, signature_count: u8) -> Instruction {
+    build(
+        accounts::EstimateRelayCost { rent: sysvar::rent::ID },
+        instruction::EstimateRelayCost { payload_size, signature_count },
+    )
+}
+
+/// `export_snapshot` page `page` of the gateway on `chain_id`, over the chain configs of
+/// `chain_ids` and the `registries`, put in canonical order; simulate it for pages
+/// 0..page_count, decode each return data as `utils::snapshot::SnapshotPage` and rebuild
+/// the snapshot with `utils::snapshot::assemble_snapshot`
+pub fn export_snapshot(
+    chain_id: u64,
+    page: u16,
+    chain_ids: &[u64],
+    registries: &[(SignerRegistryType, u64)],
+) -> Instruction {
+    let mut chain_ids = chain_ids.to_vec();
+    chain_ids.sort_unstable();
+    chain_ids.dedup();
+    let mut registries: Vec<(u8, SignerRegistryType, u64)> = registries
+        .iter()
+        .map(|&(registry_type, chain_id)| {
+            let discriminant = registry_type.discriminant().expect("verifier id within MAX_VERIFIER_ID");
+            (discriminant, registry_type, chain_id)
+        })
+        .collect();
+    registries.sort_unstable_by_key(|&(discriminant, _, chain_id)| (discriminant, chain_id));
+    registries.dedup_by_key(|&mut (discriminant, _, chain_id)| (discriminant, chain_id));
+    
+    let remaining = chain_ids
+        .iter()
+        .map(|&chain_id| pda::chain_config(chain_id).0)
+        .chain(
+            registries
+                .iter()
+                .map(|&(_, registry_type, chain_id)| pda::signer_registry(registry_type, chain_id).0),
+        )
+        .map(|address| AccountMeta::new_readonly(address, false))
+        .collect();
+    build_with_remaining(
+        accounts::ExportSnapshot { gateway: pda::gateway(chain_id).0 },
+        instruction::ExportSnapshot { page },
+        remaining,
+    )
+}
\ No newline at end of file
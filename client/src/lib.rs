@@ -0,0 +1,27 @@
+//! Rust client for the Via Labs V4 message gateway
+//!
+//! - `pda`: addresses of every gateway PDA
+//! - `ix`: instructions from the program's typed `accounts::*` / `instruction::*` structs,
+//!   plus ready-made builders for the common relayer and admin flows
+//! - `account`: decoding (and, with the `rpc` feature, fetching) of gateway accounts
+//! - `event`: gateway events from transaction logs
+//! - `fixture` (feature `fixtures`): signed TX1/TX2 test messages, valid and corrupted
+//! - `mock_validator` (feature `fixtures`): ephemeral VIA/Chain/Project validator sets
+//!
+//! The program's own types are re-exported, so relayers and tests depend on this crate
+//! alone and never hand-encode Anchor discriminators
+
+pub mod account;
+pub mod error;
+pub mod event;
+#[cfg(feature = "fixtures")]
+pub mod fixture;
+pub mod ix;
+#[cfg(feature = "fixtures")]
+pub mod mock_validator;
+pub mod pda;
+
+pub use error::ClientError;
+pub use message_gateway_v4::{
+    accounts, constants, errors, events, instruction, state, txbuilder, utils, ID,
+};
\ No newline at end of file
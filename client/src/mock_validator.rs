@@ -0,0 +1,209 @@
+//! Mock validator set (feature `fixtures`)
+//!
+//! Ephemeral signer registries holding Ed25519 and secp256k1 keys, which attest message
+//! hashes the way live validators do under the three-layer policy: enough VIA signers
+//! (registry of the destination gateway), Chain signers (registry of the source chain) and,
+//! when the route has one, Project signers to meet each registry's threshold
+//! Keys shared by two layers sign once, as the gateway counts one attestation for both
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{instruction::Instruction, keccak};
+use message_gateway_v4::state::{HashScheme, MessageEnvelope, MessageSignature, SignatureScheme, SignerRegistryType};
+use message_gateway_v4::utils::hash::create_message_hash_for_signing;
+use message_gateway_v4::utils::signature::secp256k1_signer_id;
+use solana_sdk::signature::{Keypair, Signer};
+
+use crate::{ix, ClientError};
+
+/// Validator key of either supported scheme
+pub enum MockKey {
+    Ed25519(Keypair),
+    Secp256k1(libsecp256k1::SecretKey),
+}
+
+impl MockKey {
+    /// Fresh Ed25519 key
+    pub fn ed25519() -> Self {
+        MockKey::Ed25519(Keypair::new())
+    }
+    
+    /// secp256k1 key derived from `seed` (deterministic, so fixtures can be regenerated)
+    pub fn secp256k1(seed: &[u8]) -> Self {
+        let mut counter = 0u8;
+        loop {
+            let candidate = keccak::hashv(&[seed, &[counter]]).to_bytes();
+            if let Ok(secret) = libsecp256k1::SecretKey::parse(&candidate) {
+                return MockKey::Secp256k1(secret);
+            }
+            counter += 1;
+        }
+    }
+    
+    /// Identity the key is registered under (keccak256 of the uncompressed key for secp256k1)
+    pub fn signer(&self) -> Pubkey {
+        match self {
+            MockKey::Ed25519(keypair) => keypair.pubkey(),
+            MockKey::Secp256k1(secret) => {
+                let uncompressed = libsecp256k1::PublicKey::from_secret_key(secret).serialize();
+                secp256k1_signer_id(uncompressed[1..].try_into().unwrap())
+            }
+        }
+    }
+    
+    /// Attestation of `message_hash`
+    pub fn sign(&self, message_hash: &[u8; 32]) -> MessageSignature {
+        match self {
+            MockKey::Ed25519(keypair) => MessageSignature {
+                signature: keypair.sign_message(message_hash).into(),
+                signer: keypair.pubkey(),
+                scheme: SignatureScheme::Ed25519,
+            },
+            MockKey::Secp256k1(secret) => {
+                let (signature, recovery_id) =
+                    libsecp256k1::sign(&libsecp256k1::Message::parse(message_hash), secret);
+                MessageSignature {
+                    signature: signature.serialize(),
+                    signer: self.signer(),
+                    scheme: SignatureScheme::Secp256k1 { recovery_id: recovery_id.serialize() },
+                }
+            }
+        }
+    }
+}
+
+/// One signer registry and the keys behind it
+pub struct MockRegistry {
+    pub registry_type: SignerRegistryType,
+    pub chain_id: u64,
+    pub keys: Vec<MockKey>,
+    pub required_signatures: u8,
+}
+
+impl MockRegistry {
+    /// Registered signer identities, in key order
+    pub fn signers(&self) -> Vec<Pubkey> {
+        self.keys.iter().map(MockKey::signer).collect()
+    }
+}
+
+/// Validator registries of one gateway
+pub struct MockValidatorSet {
+    pub gateway_chain_id: u64,
+    pub registries: Vec<MockRegistry>,
+}
+
+impl MockValidatorSet {
+    pub fn new(gateway_chain_id: u64) -> Self {
+        Self {
+            gateway_chain_id,
+            registries: Vec::new(),
+        }
+    }
+    
+    /// Add a registry of `ed25519` fresh Ed25519 keys followed by `secp256k1` secp256k1 keys
+    pub fn with_registry(
+        mut self,
+        registry_type: SignerRegistryType,
+        chain_id: u64,
+        ed25519: usize,
+        secp256k1: usize,
+        required_signatures: u8,
+    ) -> Self {
+        let mut keys: Vec<MockKey> = (0..ed25519).map(|_| MockKey::ed25519()).collect();
+        for index in 0..secp256k1 {
+            let seed = [
+                &[registry_type.discriminant().expect("verifier id within MAX_VERIFIER_ID")][..],
+                &chain_id.to_le_bytes(),
+                &(index as u64).to_le_bytes(),
+            ]
+            .concat();
+            keys.push(MockKey::secp256k1(&seed));
+        }
+        self.registries.push(MockRegistry {
+            registry_type,
+            chain_id,
+            keys,
+            required_signatures,
+        });
+        self
+    }
+    
+    /// Add `key` to an existing registry as well, so one validator serves two layers
+    pub fn share_key(mut self, from: (SignerRegistryType, u64, usize), to: (SignerRegistryType, u64)) -> Self {
+        let key = match &self.registry(from.0, from.1).expect("source registry").keys[from.2] {
+            MockKey::Ed25519(keypair) => MockKey::Ed25519(keypair.insecure_clone()),
+            MockKey::Secp256k1(secret) => MockKey::Secp256k1(*secret),
+        };
+        if let Some(registry) = self.registry_mut(to.0, to.1) {
+            registry.keys.push(key);
+        }
+        self
+    }
+    
+    pub fn registry(&self, registry_type: SignerRegistryType, chain_id: u64) -> Option<&MockRegistry> {
+        self.registries
+            .iter()
+            .find(|r| r.registry_type == registry_type && r.chain_id == chain_id)
+    }
+    
+    fn registry_mut(&mut self, registry_type: SignerRegistryType, chain_id: u64) -> Option<&mut MockRegistry> {
+        self.registries
+            .iter_mut()
+            .find(|r| r.registry_type == registry_type && r.chain_id == chain_id)
+    }
+    
+    /// `initialize_signer_registry` for every registry, in the order they were added
+    pub fn initialize_instructions(&self, authority: &Pubkey) -> Vec<Instruction> {
+        self.registries
+            .iter()
+            .map(|r| {
+                ix::initialize_signer_registry(
+                    authority,
+                    self.gateway_chain_id,
+                    r.registry_type,
+                    r.chain_id,
+                    r.signers(),
+                    r.required_signatures,
+                )
+            })
+            .collect()
+    }
+    
+    /// Signing hash of `envelope` and the attestations meeting every layer's threshold
+    /// `project` names the project registry's chain id when the route has one
+    pub fn attest(
+        &self,
+        hash_scheme: HashScheme,
+        envelope: &MessageEnvelope,
+        project: Option<u64>,
+    ) -> Result<([u8; 32], Vec<MessageSignature>), ClientError> {
+        let message_hash = create_message_hash_for_signing(hash_scheme, self.gateway_chain_id, envelope)
+            .map_err(ClientError::InvalidEnvelope)?;
+        
+        let layers = [
+            Some((SignerRegistryType::VIA, self.gateway_chain_id)),
+            Some((SignerRegistryType::Chain, envelope.source_chain_id)),
+            project.map(|chain_id| (SignerRegistryType::Project, chain_id)),
+        ];
+        let mut signatures: Vec<MessageSignature> = Vec::new();
+        for (registry_type, chain_id) in layers.into_iter().flatten() {
+            let Some(registry) = self.registry(registry_type, chain_id) else {
+                continue;
+            };
+            // Attestations already given by keys this registry shares with an earlier layer
+            let signers = registry.signers();
+            let mut counted = signatures.iter().filter(|s| signers.contains(&s.signer)).count();
+            for (key, signer) in registry.keys.iter().zip(&signers) {
+                if counted >= registry.required_signatures as usize {
+                    break;
+                }
+                if signatures.iter().any(|s| s.signer == *signer) {
+                    continue;
+                }
+                signatures.push(key.sign(&message_hash));
+                counted += 1;
+            }
+        }
+        Ok((message_hash, signatures))
+    }
+}
\ No newline at end of file
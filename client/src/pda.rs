@@ -0,0 +1,311 @@
+//! Gateway PDA addresses with their bumps, derived with the seeds the program checks
+//! Every gateway-owned PDA but the delivery authority ends with the gateway instance's seed
+//! suffix, which is empty for the default instance; helpers without an `_for_instance`
+//! variant derive the default instance's address
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::constants::*;
+use message_gateway_v4::state::{instance_seed, Channel, SignerRegistryType};
+use message_gateway_v4::utils::caip::caip2_seed;
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &message_gateway_v4::ID)
+}
+
+/// Gateway configuration of `chain_id`
+pub fn gateway(chain_id: u64) -> (Pubkey, u8) {
+    gateway_for_instance(chain_id, DEFAULT_INSTANCE_ID)
+}
+
+/// Gateway configuration of instance `instance_id` on `chain_id`
+pub fn gateway_for_instance(chain_id: u64, instance_id: u16) -> (Pubkey, u8) {
+    find(&[GATEWAY_SEED, &chain_id.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+/// Highest tx_id seen from a source chain
+pub fn counter(source_chain_id: u64) -> (Pubkey, u8) {
+    counter_for_instance(source_chain_id, DEFAULT_INSTANCE_ID)
+}
+
+/// Highest tx_id seen from a source chain by gateway instance `instance_id`
+pub fn counter_for_instance(source_chain_id: u64, instance_id: u16) -> (Pubkey, u8) {
+    find(&[COUNTER_SEED, &source_chain_id.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+/// TxId PDA created by TX1 and closed by TX2
+pub fn tx_id(source_chain_id: u64, tx_id: u128) -> (Pubkey, u8) {
+    tx_id_for_instance(source_chain_id, tx_id, DEFAULT_INSTANCE_ID)
+}
+
+/// TxId PDA of a message delivered to gateway instance `instance_id`
+pub fn tx_id_for_instance(source_chain_id: u64, tx_id: u128, instance_id: u16) -> (Pubkey, u8) {
+    find(&[TX_SEED, &source_chain_id.to_le_bytes(), &tx_id.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+/// Signer registry of one layer (VIA registries are keyed by the gateway's chain)
+pub fn signer_registry(registry_type: SignerRegistryType, chain_id: u64) -> (Pubkey, u8) {
+    signer_registry_for_instance(registry_type, chain_id, DEFAULT_INSTANCE_ID)
+}
+
+/// Signer registry of one layer of gateway instance `instance_id`
+pub fn signer_registry_for_instance(
+    registry_type: SignerRegistryType,
+    chain_id: u64,
+    instance_id: u16,
+) -> (Pubkey, u8) {
+    find(&[
+        SIGNER_REGISTRY_SEED,
+        &registry_type.discriminant().expect("verifier id within MAX_VERIFIER_ID").to_le_bytes(),
+        &chain_id.to_le_bytes(),
+        &instance_seed(instance_id),
+    ])
+}
+
+/// Per-chain route configuration
+pub fn chain_config(chain_id: u64) -> (Pubkey, u8) {
+    chain_config_for_instance(chain_id, DEFAULT_INSTANCE_ID)
+}
+
+/// Per-chain route configuration of gateway instance `instance_id`
+pub fn chain_config_for_instance(chain_id: u64, instance_id: u16) -> (Pubkey, u8) {
+    find(&[CHAIN_CONFIG_SEED, &chain_id.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+/// CAIP-2 alias of a chain config
+pub fn chain_alias(caip2_id: &str) -> (Pubkey, u8) {
+    find(&[CHAIN_ALIAS_SEED, &caip2_seed(caip2_id)])
+}
+
+/// Channel between a local program and an app on a remote chain
+pub fn channel(remote_chain_id: u64, local_app: &Pubkey, remote_app: &[u8]) -> (Pubkey, u8) {
+    find(&[
+        CHANNEL_SEED,
+        &remote_chain_id.to_le_bytes(),
+        local_app.as_ref(),
+        &Channel::remote_app_seed(remote_app),
+    ])
+}
+
+/// Pending cross-chain query
+pub fn query(requester: &Pubkey, query_id: u128) -> (Pubkey, u8) {
+    find(&[QUERY_SEED, requester.as_ref(), &query_id.to_le_bytes()])
+}
+
+/// V3 route mapping of a legacy chain id
+pub fn v3_mapping(v3_chain_id: u64) -> (Pubkey, u8) {
+    find(&[V3_MAPPING_SEED, &v3_chain_id.to_le_bytes()])
+}
+
+/// Replay receipt of a translated V3 message
+pub fn v3_receipt(v3_source_chain_id: u64, tx_id: u128) -> (Pubkey, u8) {
+    find(&[V3_RECEIPT_SEED, &v3_source_chain_id.to_le_bytes(), &tx_id.to_le_bytes()])
+}
+
+/// Token bridge peer on a remote chain
+pub fn bridge_peer(chain_id: u64) -> (Pubkey, u8) {
+    bridge_peer_for_instance(chain_id, DEFAULT_INSTANCE_ID)
+}
+
+/// Token bridge peer on a remote chain of gateway instance `instance_id`
+pub fn bridge_peer_for_instance(chain_id: u64, instance_id: u16) -> (Pubkey, u8) {
+    find(&[BRIDGE_PEER_SEED, &chain_id.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+/// Wrapped asset record of a remote token
+pub fn wrapped_asset(source_chain_id: u64, source_token: &[u8; 32]) -> (Pubkey, u8) {
+    find(&[WRAPPED_ASSET_SEED, &source_chain_id.to_le_bytes(), source_token])
+}
+
+/// Mint of a wrapped remote token
+pub fn wrapped_mint(source_chain_id: u64, source_token: &[u8; 32]) -> (Pubkey, u8) {
+    find(&[WRAPPED_MINT_SEED, &source_chain_id.to_le_bytes(), source_token])
+}
+
+/// Listing of a remote token awaiting its wrapped mint
+pub fn wrapped_listing(source_chain_id: u64, source_token: &[u8; 32]) -> (Pubkey, u8) {
+    find(&[WRAPPED_LISTING_SEED, &source_chain_id.to_le_bytes(), source_token])
+}
+
+/// Vault holding native SOL bridged out
+pub fn sol_vault() -> (Pubkey, u8) {
+    sol_vault_for_instance(DEFAULT_INSTANCE_ID)
+}
+
+/// Vault holding native SOL bridged out through gateway instance `instance_id`
+pub fn sol_vault_for_instance(instance_id: u16) -> (Pubkey, u8) {
+    find(&[SOL_VAULT_SEED, &instance_seed(instance_id)])
+}
+
+/// Pending token rescue of a mint
+pub fn rescue(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[RESCUE_SEED, mint.as_ref()])
+}
+
+/// Rate limit of a mint
+pub fn rate_limit(mint: &Pubkey) -> (Pubkey, u8) {
+    rate_limit_for_instance(mint, DEFAULT_INSTANCE_ID)
+}
+
+/// Rate limit of a mint on gateway instance `instance_id`
+pub fn rate_limit_for_instance(mint: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    find(&[RATE_LIMIT_SEED, mint.as_ref(), &instance_seed(instance_id)])
+}
+
+/// Token bridge configuration of a mint
+pub fn token_config(mint: &Pubkey) -> (Pubkey, u8) {
+    token_config_for_instance(mint, DEFAULT_INSTANCE_ID)
+}
+
+/// Token bridge configuration of a mint on gateway instance `instance_id`
+pub fn token_config_for_instance(mint: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    find(&[TOKEN_CONFIG_SEED, mint.as_ref(), &instance_seed(instance_id)])
+}
+
+/// Registered NFT collection
+pub fn nft_collection(collection_mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[NFT_COLLECTION_SEED, collection_mint.as_ref()])
+}
+
+/// Wrapped NFT record of a wrapped mint
+pub fn wrapped_nft(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[WRAPPED_NFT_SEED, mint.as_ref()])
+}
+
+/// Mint of a wrapped remote NFT
+pub fn wrapped_nft_mint(source_chain_id: u64, collection_hash: &[u8; 32], token_id: &[u8; 32]) -> (Pubkey, u8) {
+    find(&[WRAPPED_NFT_MINT_SEED, &source_chain_id.to_le_bytes(), collection_hash, token_id])
+}
+
+/// Registered compressed NFT tree
+pub fn compressed_nft_tree(merkle_tree: &Pubkey) -> (Pubkey, u8) {
+    find(&[COMPRESSED_NFT_TREE_SEED, merkle_tree.as_ref()])
+}
+
+/// Wrapped compressed NFT record
+pub fn wrapped_cnft(source_chain_id: u64, collection_hash: &[u8; 32], token_id: &[u8; 32]) -> (Pubkey, u8) {
+    find(&[WRAPPED_CNFT_SEED, &source_chain_id.to_le_bytes(), collection_hash, token_id])
+}
+
+/// Dead letter of an undeliverable inbound transfer
+pub fn dead_letter(source_chain_id: u64, tx_id: u128) -> (Pubkey, u8) {
+    find(&[DEAD_LETTER_SEED, &source_chain_id.to_le_bytes(), &tx_id.to_le_bytes()])
+}
+
+/// Claimable inbound transfer
+pub fn token_claim(source_chain_id: u64, tx_id: u128) -> (Pubkey, u8) {
+    find(&[TOKEN_CLAIM_SEED, &source_chain_id.to_le_bytes(), &tx_id.to_le_bytes()])
+}
+
+/// Liquidity provider bond
+pub fn liquidity_provider(owner: &Pubkey) -> (Pubkey, u8) {
+    find(&[LIQUIDITY_PROVIDER_SEED, owner.as_ref()])
+}
+
+/// Fast fill of an inbound transfer fronted by a liquidity provider
+pub fn fast_fill(source_chain_id: u64, tx_id: u128) -> (Pubkey, u8) {
+    find(&[FAST_FILL_SEED, &source_chain_id.to_le_bytes(), &tx_id.to_le_bytes()])
+}
+
+/// Token bridge blocklist
+pub fn blocklist() -> (Pubkey, u8) {
+    blocklist_for_instance(DEFAULT_INSTANCE_ID)
+}
+
+/// Token bridge blocklist of gateway instance `instance_id`
+pub fn blocklist_for_instance(instance_id: u16) -> (Pubkey, u8) {
+    find(&[BLOCKLIST_SEED, &instance_seed(instance_id)])
+}
+
+/// Vesting schedule of an inbound grant
+pub fn vesting(source_chain_id: u64, tx_id: u128) -> (Pubkey, u8) {
+    find(&[VESTING_SEED, &source_chain_id.to_le_bytes(), &tx_id.to_le_bytes()])
+}
+
+/// Custody extension of a mint
+pub fn custody_extension(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[CUSTODY_EXTENSION_SEED, mint.as_ref()])
+}
+
+/// Authority signing for a mint's custody extension
+pub fn custody_authority(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[CUSTODY_AUTHORITY_SEED, mint.as_ref()])
+}
+
+/// Registry bundle of an inbound route, with or without the project layer
+pub fn registry_bundle(source_chain_id: u64, with_project: bool) -> (Pubkey, u8) {
+    registry_bundle_for_instance(source_chain_id, with_project, DEFAULT_INSTANCE_ID)
+}
+
+/// Registry bundle of an inbound route of gateway instance `instance_id`
+pub fn registry_bundle_for_instance(
+    source_chain_id: u64,
+    with_project: bool,
+    instance_id: u16,
+) -> (Pubkey, u8) {
+    find(&[
+        REGISTRY_BUNDLE_SEED,
+        &source_chain_id.to_le_bytes(),
+        &[with_project as u8],
+        &instance_seed(instance_id),
+    ])
+}
+
+/// Authority of the message history tree
+pub fn message_history() -> (Pubkey, u8) {
+    message_history_for_instance(DEFAULT_INSTANCE_ID)
+}
+
+/// Authority of gateway instance `instance_id`'s message history tree
+pub fn message_history_for_instance(instance_id: u16) -> (Pubkey, u8) {
+    find(&[MESSAGE_HISTORY_SEED, &instance_seed(instance_id)])
+}
+
+/// Chunked payload buffer of an inbound message
+pub fn message_buffer(source_chain_id: u64, tx_id: u128) -> (Pubkey, u8) {
+    message_buffer_for_instance(source_chain_id, tx_id, DEFAULT_INSTANCE_ID)
+}
+
+/// Chunked payload buffer of a message delivered to gateway instance `instance_id`
+pub fn message_buffer_for_instance(source_chain_id: u64, tx_id: u128, instance_id: u16) -> (Pubkey, u8) {
+    find(&[
+        MESSAGE_BUFFER_SEED,
+        &source_chain_id.to_le_bytes(),
+        &tx_id.to_le_bytes(),
+        &instance_seed(instance_id),
+    ])
+}
+
+/// Relayer pool advancing TxId PDA rent
+pub fn relayer_pool(relayer: &Pubkey) -> (Pubkey, u8) {
+    relayer_pool_for_instance(relayer, DEFAULT_INSTANCE_ID)
+}
+
+/// Relayer pool advancing TxId PDA rent on gateway instance `instance_id`
+pub fn relayer_pool_for_instance(relayer: &Pubkey, instance_id: u16) -> (Pubkey, u8) {
+    find(&[RELAYER_POOL_SEED, relayer.as_ref(), &instance_seed(instance_id)])
+}
+
+/// Authority signing `on_message_received` deliveries into `recipient_program`
+pub fn delivery_authority(recipient_program: &Pubkey) -> (Pubkey, u8) {
+    find(&[DELIVERY_AUTHORITY_SEED, recipient_program.as_ref()])
+}
+
+/// Lamport fee schedule of messages to `dest_chain_id`
+pub fn fee_config(dest_chain_id: u64) -> (Pubkey, u8) {
+    fee_config_for_instance(dest_chain_id, DEFAULT_INSTANCE_ID)
+}
+
+/// Lamport fee schedule of messages to `dest_chain_id` from gateway instance `instance_id`
+pub fn fee_config_for_instance(dest_chain_id: u64, instance_id: u16) -> (Pubkey, u8) {
+    find(&[FEE_CONFIG_SEED, &dest_chain_id.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+/// Vault collecting the lamport send fees
+pub fn fee_vault() -> (Pubkey, u8) {
+    fee_vault_for_instance(DEFAULT_INSTANCE_ID)
+}
+
+/// Vault collecting gateway instance `instance_id`'s lamport send fees
+pub fn fee_vault_for_instance(instance_id: u16) -> (Pubkey, u8) {
+    find(&[FEE_VAULT_SEED, &instance_seed(instance_id)])
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+//! Client PDAs, instruction encoding, account decoding and event parsing against the program
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountSerialize, Discriminator, Event};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use message_gateway_client::events::RelayerPoolUpdated;
+use message_gateway_client::state::{RegistryBundle, RelayerPool, TxIdPDA};
+use message_gateway_client::{account, event, instruction, ix, pda, txbuilder, ID};
+use message_gateway_v4::test_vectors::HASH_VECTORS;
+
+fn pool_updated(balance: u64) -> RelayerPoolUpdated {
+    RelayerPoolUpdated {
+        relayer: Pubkey::new_unique(),
+        balance,
+        rent_outstanding: 0,
+    }
+}
+
+fn data_line(event: &impl Event) -> String {
+    format!("Program data: {}", STANDARD.encode(event.data()))
+}
+
+#[test]
+fn pdas_match_txbuilder_accounts() {
+    let envelope = HASH_VECTORS[0].envelope();
+    let relayer = Pubkey::new_unique();
+    let tx1 = txbuilder::create_tx_pda_instruction(&relayer, &envelope, &[]);
+    
+    assert_eq!(tx1.accounts[0].pubkey, pda::tx_id(envelope.source_chain_id, envelope.tx_id).0);
+    assert_eq!(tx1.accounts[1].pubkey, pda::gateway(envelope.dest_chain_id).0);
+    assert_eq!(tx1.accounts[2].pubkey, pda::counter(envelope.source_chain_id).0);
+    assert_eq!(tx1.accounts[3].pubkey, pda::chain_config(envelope.source_chain_id).0);
+}
+
+#[test]
+fn builders_encode_discriminator_and_accounts() {
+    let relayer = Pubkey::new_unique();
+    let deposit = ix::deposit_relayer_pool(&relayer, 1, 42);
+    
+    assert_eq!(deposit.program_id, ID);
+    assert_eq!(&deposit.data[..8], instruction::DepositRelayerPool::DISCRIMINATOR);
+    assert_eq!(&deposit.data[8..], &42u64.to_le_bytes());
+    assert_eq!(deposit.accounts[0].pubkey, pda::gateway(1).0);
+    assert_eq!(deposit.accounts[1].pubkey, pda::relayer_pool(&relayer).0);
+    assert!(deposit.accounts[2].is_signer);
+    
+    let pairs = [(Pubkey::new_unique(), relayer), (Pubkey::new_unique(), relayer)];
+    let gc = ix::gc_batch(&relayer, &pairs);
+    assert_eq!(gc.accounts.len(), 1 + 2 * pairs.len());
+    assert_eq!(gc.accounts[3].pubkey, pairs[1].0);
+}
+
+#[test]
+fn decodes_accounts_and_rejects_other_types() {
+    let pool = RelayerPool {
+        relayer: Pubkey::new_unique(),
+        balance: 7,
+        rent_outstanding: 3,
+        bump: 254,
+    };
+    let mut data = Vec::new();
+    pool.try_serialize(&mut data).unwrap();
+    
+    let decoded: RelayerPool = account::decode(&data).unwrap();
+    assert_eq!((decoded.relayer, decoded.balance, decoded.bump), (pool.relayer, 7, 254));
+    assert!(account::decode::<TxIdPDA>(&data).is_err());
+}
+
+#[test]
+fn decodes_zero_copy_accounts() {
+    let mut bundle: RegistryBundle = bytemuck::Zeroable::zeroed();
+    bundle.registry_epoch = 9;
+    bundle.source_chain_id = 137;
+    let mut data = RegistryBundle::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(&bundle));
+    
+    let decoded: RegistryBundle = account::decode_zero_copy(&data).unwrap();
+    assert_eq!((decoded.registry_epoch, decoded.source_chain_id), (9, 137));
+    assert!(account::decode_zero_copy::<RegistryBundle>(&data[..data.len() - 1]).is_err());
+    assert!(account::decode_zero_copy::<RegistryBundle>(&data[1..]).is_err());
+}
+
+#[test]
+fn parses_only_gateway_events() {
+    let other = Pubkey::new_unique();
+    let logs = vec![
+        format!("Program {ID} invoke [1]"),
+        data_line(&pool_updated(1)),
+        format!("Program {other} invoke [2]"),
+        data_line(&pool_updated(2)),
+        format!("Program {other} success"),
+        data_line(&pool_updated(3)),
+        format!("Program {ID} success"),
+        data_line(&pool_updated(4)),
+    ];
+    
+    let balances: Vec<u64> = event::parse_events::<RelayerPoolUpdated>(&logs)
+        .iter()
+        .map(|e| e.balance)
+        .collect();
+    assert_eq!(balances, vec![1, 3]);
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+//! Signed-message fixtures: valid ones match their precompiles, corrupted ones do not
+#![cfg(feature = "fixtures")]
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use message_gateway_client::fixture::{Corruption, Fixture, Fixtures, Keypair};
+use message_gateway_client::instruction::{CreateTxPda, ProcessMessage};
+use message_gateway_client::txbuilder::InboundRoute;
+use message_gateway_client::utils::signature::parse_ed25519_instruction;
+use message_gateway_v4::test_vectors::HASH_VECTORS;
+use solana_sdk::signature::Signature;
+
+fn generator() -> Fixtures {
+    let route = InboundRoute {
+        gateway_chain_id: HASH_VECTORS[0].dest_chain_id,
+        ..Default::default()
+    };
+    Fixtures::new(Pubkey::new_unique(), route)
+}
+
+/// Signatures with a precompile entry over the fixture's message hash
+fn matched(fixture: &Fixture) -> usize {
+    fixture
+        .signatures
+        .iter()
+        .filter(|s| {
+            fixture.precompiles.iter().any(|ix| {
+                parse_ed25519_instruction(ix, &s.signature, &s.signer, &fixture.message_hash, true) == Some(true)
+            })
+        })
+        .count()
+}
+
+/// Signatures that verify over the fixture's message hash
+fn verified(fixture: &Fixture) -> usize {
+    fixture
+        .signatures
+        .iter()
+        .filter(|s| Signature::from(s.signature).verify(s.signer.as_ref(), &fixture.message_hash))
+        .count()
+}
+
+#[test]
+fn signed_fixture_carries_matching_tx1_and_tx2() {
+    let validators = [Keypair::new(), Keypair::new(), Keypair::new()];
+    let signers: Vec<&Keypair> = validators.iter().collect();
+    let fixture = generator().sign(HASH_VECTORS[0].envelope(), &signers).unwrap();
+    
+    assert_eq!((matched(&fixture), verified(&fixture)), (3, 3));
+    assert_eq!(fixture.tx1_instructions().len(), 4);
+    assert_eq!(&fixture.tx1.data[..8], CreateTxPda::DISCRIMINATOR);
+    assert_eq!(&fixture.tx2.data[..8], ProcessMessage::DISCRIMINATOR);
+    
+    let args = CreateTxPda::deserialize(&mut &fixture.tx1.data[8..]).unwrap();
+    assert_eq!(args.envelope, fixture.envelope);
+    assert_eq!(args.signatures.len(), 3);
+}
+
+#[test]
+fn each_corruption_breaks_its_fixture() {
+    let validators = [Keypair::new(), Keypair::new()];
+    let signers: Vec<&Keypair> = validators.iter().collect();
+    
+    for corruption in Corruption::ALL {
+        let fixture = generator().corrupt(HASH_VECTORS[0].envelope(), &signers, corruption).unwrap();
+        // (attestations with a matching precompile entry, attestations that verify)
+        let expected = match corruption {
+            Corruption::WrongHash | Corruption::TamperedEnvelope => (0, 0),
+            Corruption::TamperedSignature => (2, 1),
+            Corruption::MissingPrecompile => (1, 2),
+            Corruption::DuplicateSigner => (3, 3),
+            Corruption::ForeignOffsets => (0, 2),
+        };
+        assert_eq!((matched(&fixture), verified(&fixture)), expected, "{corruption:?}");
+        assert_eq!(fixture.envelope == HASH_VECTORS[0].envelope(), corruption != Corruption::TamperedEnvelope);
+    }
+}
@@ -0,0 +1,82 @@
+//! Mock validator sets attest like live validators under the three-layer policy
+#![cfg(feature = "fixtures")]
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_client::fixture::Fixtures;
+use message_gateway_client::mock_validator::{MockKey, MockValidatorSet};
+use message_gateway_client::state::{HashScheme, MessageSignature, SignatureScheme, SignerRegistryType};
+use message_gateway_client::txbuilder::InboundRoute;
+use message_gateway_client::utils::signature::verify_secp256k1_signature;
+use message_gateway_v4::test_vectors::HASH_VECTORS;
+use solana_sdk::signature::Signature;
+
+fn verifies(attestation: &MessageSignature, message_hash: &[u8; 32]) -> bool {
+    match attestation.scheme {
+        SignatureScheme::Ed25519 => {
+            Signature::from(attestation.signature).verify(attestation.signer.as_ref(), message_hash)
+        }
+        SignatureScheme::Secp256k1 { recovery_id } => {
+            verify_secp256k1_signature(&attestation.signature, recovery_id, &attestation.signer, message_hash).unwrap()
+        }
+    }
+}
+
+/// VIA {3 Ed25519, 2 of 3}, Chain {1 Ed25519 + 2 secp256k1, 3 of 3} and a Project registry
+/// sharing the first VIA key, 2 of 2
+fn validator_set(gateway_chain_id: u64, source_chain_id: u64) -> MockValidatorSet {
+    MockValidatorSet::new(gateway_chain_id)
+        .with_registry(SignerRegistryType::VIA, gateway_chain_id, 3, 0, 2)
+        .with_registry(SignerRegistryType::Chain, source_chain_id, 1, 2, 3)
+        .with_registry(SignerRegistryType::Project, source_chain_id, 1, 0, 2)
+        .share_key((SignerRegistryType::VIA, gateway_chain_id, 0), (SignerRegistryType::Project, source_chain_id))
+}
+
+#[test]
+fn attestations_meet_every_layer_threshold() {
+    let envelope = HASH_VECTORS[0].envelope();
+    let set = validator_set(envelope.dest_chain_id, envelope.source_chain_id);
+    let (message_hash, signatures) =
+        set.attest(HashScheme::default(), &envelope, Some(envelope.source_chain_id)).unwrap();
+    
+    assert!(signatures.iter().all(|s| verifies(s, &message_hash)));
+    for registry in &set.registries {
+        let members = registry.signers();
+        let counted = signatures.iter().filter(|s| members.contains(&s.signer)).count();
+        assert!(counted >= registry.required_signatures as usize, "{:?}", registry.registry_type);
+    }
+    
+    // The shared key signs once for VIA and Project: 2 VIA + 3 Chain + 1 more Project
+    assert_eq!(signatures.len(), 6);
+    let mut signers: Vec<Pubkey> = signatures.iter().map(|s| s.signer).collect();
+    signers.sort();
+    signers.dedup();
+    assert_eq!(signers.len(), signatures.len());
+}
+
+#[test]
+fn project_layer_is_only_signed_when_routed() {
+    let envelope = HASH_VECTORS[0].envelope();
+    let set = validator_set(envelope.dest_chain_id, envelope.source_chain_id);
+    let (_, signatures) = set.attest(HashScheme::default(), &envelope, None).unwrap();
+    assert_eq!(signatures.len(), 5);
+}
+
+#[test]
+fn secp256k1_keys_are_deterministic() {
+    assert_eq!(MockKey::secp256k1(b"seed").signer(), MockKey::secp256k1(b"seed").signer());
+    assert_ne!(MockKey::secp256k1(b"seed").signer(), MockKey::secp256k1(b"other").signer());
+}
+
+#[test]
+fn attestations_assemble_into_a_fixture() {
+    let envelope = HASH_VECTORS[0].envelope();
+    let set = validator_set(envelope.dest_chain_id, envelope.source_chain_id);
+    let (message_hash, signatures) = set.attest(HashScheme::default(), &envelope, None).unwrap();
+    
+    let route = InboundRoute { gateway_chain_id: envelope.dest_chain_id, ..Default::default() };
+    let fixture = Fixtures::new(Pubkey::new_unique(), route).assemble(envelope, signatures).unwrap();
+    assert_eq!(fixture.message_hash, message_hash);
+    // Only the Ed25519 attestations (2 VIA + 1 Chain) need precompile instructions
+    assert_eq!(fixture.precompiles.len(), 3);
+    assert_eq!(set.initialize_instructions(&Pubkey::new_unique()).len(), 3);
+}
@@ -0,0 +1,116 @@
+use crate::error::CoreError;
+use crate::hash::{keccak256, IntegerEncoding};
+
+/// Current envelope format version
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// Protocol version bound into the domain separator
+pub const PROTOCOL_VERSION: u8 = 4;
+
+pub const MAX_SENDER_SIZE: usize = 64;
+pub const MAX_RECIPIENT_SIZE: usize = 64;
+pub const MAX_ON_CHAIN_DATA_SIZE: usize = 1024;
+pub const MAX_OFF_CHAIN_DATA_SIZE: usize = 1024;
+pub const MAX_DA_POINTER_SIZE: usize = 64;
+
+/// Cross-chain message as validators sign it; same fields as the gateway's `MessageEnvelope`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Envelope {
+    /// Envelope format version
+    pub version: u8,
+    /// Transaction ID from source chain
+    pub tx_id: u128,
+    /// Chain the message originates from
+    pub source_chain_id: u64,
+    /// Chain the message is delivered to
+    pub dest_chain_id: u64,
+    /// Sender address on the source chain
+    pub sender: Vec<u8>,
+    /// Recipient address on the destination chain
+    pub recipient: Vec<u8>,
+    /// Payload delivered on-chain
+    pub on_chain_data: Vec<u8>,
+    /// Payload for off-chain consumers (signed, not executed)
+    pub off_chain_data: Vec<u8>,
+    /// keccak256 commitment to `off_chain_data` (zero = derive from `off_chain_data`)
+    pub off_chain_data_hash: [u8; 32],
+    /// Application-level message type (0 = generic)
+    pub message_type: u8,
+    /// Unix timestamp after which the message can no longer be processed (0 = none)
+    pub deadline: i64,
+    /// Channel sequence number (0 = not routed through a channel)
+    pub sequence: u64,
+    /// Protocol version of the validator software that produced the message
+    pub protocol_version: u8,
+    /// Data-availability pointer (IPFS CID or Arweave transaction ID bytes, empty = none)
+    pub da_pointer: Vec<u8>,
+}
+
+impl Envelope {
+    /// Validate version and field sizes, as the gateway does before hashing
+    pub fn validate(&self) -> Result<(), CoreError> {
+        let checks = [
+            (self.version == ENVELOPE_VERSION, CoreError::UnsupportedEnvelopeVersion),
+            (self.sender.len() <= MAX_SENDER_SIZE, CoreError::SenderTooLong),
+            (self.recipient.len() <= MAX_RECIPIENT_SIZE, CoreError::RecipientTooLong),
+            (self.on_chain_data.len() <= MAX_ON_CHAIN_DATA_SIZE, CoreError::OnChainDataTooLarge),
+            (self.off_chain_data.len() <= MAX_OFF_CHAIN_DATA_SIZE, CoreError::OffChainDataTooLarge),
+            (self.da_pointer.len() <= MAX_DA_POINTER_SIZE, CoreError::DaPointerTooLong),
+        ];
+        match checks.into_iter().find(|(ok, _)| !ok) {
+            Some((_, error)) => Err(error),
+            None => Ok(()),
+        }
+    }
+    
+    /// Commitment to the off-chain payload that validators sign over
+    /// Commit-only envelopes carry just the hash; if the data is also present it must match
+    pub fn resolved_off_chain_data_hash(&self) -> Result<[u8; 32], CoreError> {
+        let data_hash = keccak256(&[&self.off_chain_data]);
+        if self.off_chain_data_hash == [0u8; 32] {
+            return Ok(data_hash);
+        }
+        if !self.off_chain_data.is_empty() && data_hash != self.off_chain_data_hash {
+            return Err(CoreError::OffChainDataHashMismatch);
+        }
+        Ok(self.off_chain_data_hash)
+    }
+    
+    /// Cross-chain hash pre-image: integers in the route's byte order, variable fields
+    /// with a u32 length prefix, the off-chain payload as its keccak256 commitment
+    pub fn encode_preimage(&self, integer_encoding: IntegerEncoding) -> Result<Vec<u8>, CoreError> {
+        self.with_preimage(integer_encoding, |parts| parts.concat())
+    }
+    
+    /// Hand the pre-image to `f` as ordered slices, without concatenating it
+    pub(crate) fn with_preimage<R>(
+        &self,
+        integer_encoding: IntegerEncoding,
+        f: impl FnOnce(&[&[u8]]) -> R,
+    ) -> Result<R, CoreError> {
+        self.validate()?;
+        let off_chain_data_hash = self.resolved_off_chain_data_hash()?;
+        let u64_bytes = |value: u64| integer_encoding.u64_bytes(value);
+        let length = |data: &[u8]| integer_encoding.u32_bytes(data.len() as u32);
+        
+        Ok(f(&[
+            &[self.version],
+            &integer_encoding.u128_bytes(self.tx_id),
+            &u64_bytes(self.source_chain_id),
+            &u64_bytes(self.dest_chain_id),
+            &length(&self.sender),
+            &self.sender,
+            &length(&self.recipient),
+            &self.recipient,
+            &length(&self.on_chain_data),
+            &self.on_chain_data,
+            &off_chain_data_hash,
+            &[self.message_type],
+            &u64_bytes(self.deadline as u64),
+            &u64_bytes(self.sequence),
+            &[self.protocol_version],
+            &length(&self.da_pointer),
+            &self.da_pointer,
+        ]))
+    }
+}
\ No newline at end of file
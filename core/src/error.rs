@@ -0,0 +1,31 @@
+use std::fmt;
+
+/// Envelope rejected before hashing, mirroring the gateway's errors
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CoreError {
+    UnsupportedEnvelopeVersion,
+    SenderTooLong,
+    RecipientTooLong,
+    OnChainDataTooLarge,
+    OffChainDataTooLarge,
+    DaPointerTooLong,
+    /// `off_chain_data_hash` does not commit to the `off_chain_data` carried alongside it
+    OffChainDataHashMismatch,
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            CoreError::UnsupportedEnvelopeVersion => "unsupported envelope version",
+            CoreError::SenderTooLong => "sender too long",
+            CoreError::RecipientTooLong => "recipient too long",
+            CoreError::OnChainDataTooLarge => "on-chain data too large",
+            CoreError::OffChainDataTooLarge => "off-chain data too large",
+            CoreError::DaPointerTooLong => "data-availability pointer too long",
+            CoreError::OffChainDataHashMismatch => "off-chain data hash mismatch",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for CoreError {}
\ No newline at end of file
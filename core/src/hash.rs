@@ -0,0 +1,192 @@
+use sha2::Sha256;
+use sha3::{Digest, Keccak256};
+
+use crate::envelope::Envelope;
+use crate::error::CoreError;
+
+/// Tag hashed into every domain separator
+pub const DOMAIN_SEPARATOR_TAG: &[u8] = b"ViaLabsMessageGateway";
+
+/// EIP-191 style prefix of the signing hash
+pub const SIGNED_HASH_PREFIX: &[u8] = b"\x19\x01";
+
+/// Gateway instance whose domain separator carries no instance id
+pub const DEFAULT_INSTANCE_ID: u16 = 0;
+
+/// Hash algorithm for the message pre-image
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum HashAlgorithm {
+    /// Ethereum-compatible keccak256 (default)
+    #[default]
+    Keccak256,
+    Sha256,
+    Blake3,
+}
+
+/// Byte order for integers in the hash pre-image
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum IntegerEncoding {
+    /// Solana native format (default)
+    #[default]
+    LittleEndian,
+    /// EVM abi.encodePacked / Cosmos compatible format
+    BigEndian,
+}
+
+impl IntegerEncoding {
+    pub fn u16_bytes(self, value: u16) -> [u8; 2] {
+        match self {
+            IntegerEncoding::LittleEndian => value.to_le_bytes(),
+            IntegerEncoding::BigEndian => value.to_be_bytes(),
+        }
+    }
+    
+    pub fn u32_bytes(self, value: u32) -> [u8; 4] {
+        match self {
+            IntegerEncoding::LittleEndian => value.to_le_bytes(),
+            IntegerEncoding::BigEndian => value.to_be_bytes(),
+        }
+    }
+    
+    pub fn u64_bytes(self, value: u64) -> [u8; 8] {
+        match self {
+            IntegerEncoding::LittleEndian => value.to_le_bytes(),
+            IntegerEncoding::BigEndian => value.to_be_bytes(),
+        }
+    }
+    
+    pub fn u128_bytes(self, value: u128) -> [u8; 16] {
+        match self {
+            IntegerEncoding::LittleEndian => value.to_le_bytes(),
+            IntegerEncoding::BigEndian => value.to_be_bytes(),
+        }
+    }
+}
+
+/// Complete hashing configuration for a chain route
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct HashScheme {
+    pub algorithm: HashAlgorithm,
+    pub integer_encoding: IntegerEncoding,
+}
+
+pub(crate) fn keccak256(parts: &[&[u8]]) -> [u8; 32] {
+    hash_with_algorithm(HashAlgorithm::Keccak256, parts)
+}
+
+/// Hash a sequence of byte slices with the route's configured algorithm
+pub fn hash_with_algorithm(algorithm: HashAlgorithm, parts: &[&[u8]]) -> [u8; 32] {
+    match algorithm {
+        HashAlgorithm::Keccak256 => digest::<Keccak256>(parts),
+        HashAlgorithm::Sha256 => digest::<Sha256>(parts),
+        HashAlgorithm::Blake3 => {
+            let mut hasher = blake3::Hasher::new();
+            parts.iter().for_each(|part| {
+                hasher.update(part);
+            });
+            *hasher.finalize().as_bytes()
+        }
+    }
+}
+
+fn digest<D: Digest>(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = D::new();
+    parts.iter().for_each(|part| hasher.update(part));
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&hasher.finalize()[..32]);
+    hash
+}
+
+/// Cross-chain hash of an envelope under the route's scheme
+pub fn create_cross_chain_hash(hash_scheme: HashScheme, envelope: &Envelope) -> Result<[u8; 32], CoreError> {
+    envelope.with_preimage(hash_scheme.integer_encoding, |parts| {
+        hash_with_algorithm(hash_scheme.algorithm, parts)
+    })
+}
+
+/// Domain separator binding signatures to a single gateway deployment
+/// H(tag || program_id || gateway_chain_id (u64) || instance_id (u16))
+/// The instance id is omitted for the default instance. The protocol version is not part of
+/// the domain: it is signed in the envelope, so a build bump keeps older messages verifiable
+pub fn create_domain_separator(
+    hash_scheme: HashScheme,
+    program_id: &[u8; 32],
+    gateway_chain_id: u64,
+    instance_id: u16,
+) -> [u8; 32] {
+    create_domain_separator_with(
+        |parts| hash_with_algorithm(hash_scheme.algorithm, parts),
+        hash_scheme.integer_encoding,
+        program_id,
+        gateway_chain_id,
+        instance_id,
+    )
+}
+
+/// Domain separator with the pre-image hashed by `hash`
+/// The gateway program passes its Solana syscall hasher, so both sides share one layout
+pub fn create_domain_separator_with(
+    hash: impl FnOnce(&[&[u8]]) -> [u8; 32],
+    integer_encoding: IntegerEncoding,
+    program_id: &[u8; 32],
+    gateway_chain_id: u64,
+    instance_id: u16,
+) -> [u8; 32] {
+    let instance_id_bytes = integer_encoding.u16_bytes(instance_id);
+    let instance_id_bytes: &[u8] = if instance_id == DEFAULT_INSTANCE_ID {
+        &[]
+    } else {
+        &instance_id_bytes
+    };
+    hash(&[
+        DOMAIN_SEPARATOR_TAG,
+        program_id,
+        &integer_encoding.u64_bytes(gateway_chain_id),
+        instance_id_bytes,
+    ])
+}
+
+/// Signing hash H(0x1901 || domain_separator || cross_chain_hash) with H supplied by `hash`
+pub fn create_signing_hash_with(
+    hash: impl FnOnce(&[&[u8]]) -> [u8; 32],
+    domain_separator: &[u8; 32],
+    cross_chain_hash: &[u8; 32],
+) -> [u8; 32] {
+    hash(&[SIGNED_HASH_PREFIX, domain_separator, cross_chain_hash])
+}
+
+/// Hash validators sign for the default gateway instance of `program_id` on
+/// `gateway_chain_id`: H(0x1901 || domain_separator || cross_chain_hash)
+pub fn create_message_hash_for_signing(
+    hash_scheme: HashScheme,
+    program_id: &[u8; 32],
+    gateway_chain_id: u64,
+    envelope: &Envelope,
+) -> Result<[u8; 32], CoreError> {
+    create_instance_message_hash_for_signing(
+        hash_scheme,
+        program_id,
+        gateway_chain_id,
+        DEFAULT_INSTANCE_ID,
+        envelope,
+    )
+}
+
+/// Hash validators sign for gateway instance `instance_id` of `program_id` on
+/// `gateway_chain_id`
+pub fn create_instance_message_hash_for_signing(
+    hash_scheme: HashScheme,
+    program_id: &[u8; 32],
+    gateway_chain_id: u64,
+    instance_id: u16,
+    envelope: &Envelope,
+) -> Result<[u8; 32], CoreError> {
+    let message_hash = create_cross_chain_hash(hash_scheme, envelope)?;
+    let domain_separator =
+        create_domain_separator(hash_scheme, program_id, gateway_chain_id, instance_id);
+    Ok(create_signing_hash_with(
+        |parts| hash_with_algorithm(hash_scheme.algorithm, parts),
+        &domain_separator,
+        &message_hash,
+    ))
+}
\ No newline at end of file
@@ -0,0 +1,17 @@
+//! Via Labs V4 message envelope encoding and hashing
+//!
+//! Produces the same cross-chain pre-images and hashes as the gateway program
+//! (`message_gateway_v4::utils::hash`) with no Anchor or solana-program dependency, so
+//! off-chain validators and EVM-side tooling sign exactly what the gateway verifies
+//! The program takes its domain separator and signing hash layouts from this crate
+//! (`create_domain_separator_with`, `create_signing_hash_with`), hashing through Solana
+//! syscalls; `tests/core_parity.rs` in the program keeps the cross-chain hashes
+//! byte-identical over the recorded hash snapshots
+
+pub mod envelope;
+pub mod error;
+pub mod hash;
+
+pub use envelope::*;
+pub use error::CoreError;
+pub use hash::*;
\ No newline at end of file
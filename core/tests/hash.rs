@@ -0,0 +1,182 @@
+//! Domain separator and signing hash vectors
+//!
+//! The expected digests were not produced by this crate: they come from a standalone
+//! implementation of the documented layouts (Python `hashlib.sha256` and a reference
+//! Keccak-f[1600] checked against the published keccak256("") and keccak256("abc")
+//! digests), so a change to either layout fails here instead of drifting unnoticed
+
+use via_gateway_core::{
+    create_cross_chain_hash, create_domain_separator, create_instance_message_hash_for_signing,
+    create_message_hash_for_signing, create_signing_hash_with, hash_with_algorithm, Envelope,
+    HashAlgorithm, HashScheme, IntegerEncoding, ENVELOPE_VERSION, PROTOCOL_VERSION,
+};
+
+const GATEWAY_CHAIN: u64 = 0x0102_0304_0506_0708;
+const INSTANCE: u16 = 0x0203;
+
+struct Vector {
+    scheme: HashScheme,
+    cross_chain_hash: &'static str,
+    /// Domain separator and signing hash of the default instance, then of `INSTANCE`
+    domain_separators: [&'static str; 2],
+    signing_hashes: [&'static str; 2],
+    /// Signing hash for `INSTANCE` of the same envelope attested at protocol version 3
+    previous_protocol_signing_hash: &'static str,
+}
+
+const fn scheme(algorithm: HashAlgorithm, integer_encoding: IntegerEncoding) -> HashScheme {
+    HashScheme { algorithm, integer_encoding }
+}
+
+const VECTORS: &[Vector] = &[
+    Vector {
+        scheme: scheme(HashAlgorithm::Keccak256, IntegerEncoding::LittleEndian),
+        cross_chain_hash: "229b8648df8aac5e39ebb3d8c055cad012e91619c907653309fbe7cccd496278",
+        domain_separators: [
+            "2204d9a9ece37614d500ae304352af9e38bf005b81139d11dbda2c9fda4fa68a",
+            "442ebb1655dfa0a232cf5f93e8dd11a907855c0370bdff68429c81c117ed6570",
+        ],
+        signing_hashes: [
+            "0f614617056a0bf6ac1bfdd04fe407f85b8a8bb16896a7720e47f6b2cefe6f25",
+            "da356102f68c0745351905f484f7b7f997186ff8984bfe497ffcd7850af29a0e",
+        ],
+        previous_protocol_signing_hash: "9d4a61250c901dc8c7168459a3b878dda0e8d14cc2351aec5eb954f92765cb95",
+    },
+    Vector {
+        scheme: scheme(HashAlgorithm::Keccak256, IntegerEncoding::BigEndian),
+        cross_chain_hash: "0699ab7ebfed68d4098000eae9d2f908ab506967ba7090e8f0739a21baeed770",
+        domain_separators: [
+            "26e6a2a9cd0131835f77eb1b491f08b9b48f9556c2fb0e44f07b940a9dee64e6",
+            "820449d04d81994c50462e512788782771398707e105a104199b0ba17f1e6b1e",
+        ],
+        signing_hashes: [
+            "39d365d489c71bafdee916bb71caafe061f9523a6ff0a8c519370b242b458991",
+            "5d2dcbecca489ff7b1cea054c4d80d093f3f03e14accf5fbe05ed73bc5c9edfb",
+        ],
+        previous_protocol_signing_hash: "c510fb678ac56edf9ec29d00595dfe69c86d34840f099382ba96098bc76f6ada",
+    },
+    Vector {
+        scheme: scheme(HashAlgorithm::Sha256, IntegerEncoding::LittleEndian),
+        cross_chain_hash: "eec0c940290fe6af742e99d416d8e44906622b35402b5a043986c95a68023628",
+        domain_separators: [
+            "8697051dd27c415b242d01ea69190aab800ecd179fffabe7aa725abcc6594a90",
+            "6e27a071d03166eb830b10167f726c65c850833cff0f37cffd09474aacc17bcb",
+        ],
+        signing_hashes: [
+            "2923428d4d7616328d406563631fc0ec0b89205b1c6f2b8bb154f26992177c16",
+            "6e467fb276a961769a3c3648ad98059e8d343f5ac965d2f033f83dc35c7967d6",
+        ],
+        previous_protocol_signing_hash: "271791b8bf5574ef5f5c14441c04dfefc9f2e2757a748a302265431173b1432b",
+    },
+    Vector {
+        scheme: scheme(HashAlgorithm::Sha256, IntegerEncoding::BigEndian),
+        cross_chain_hash: "5b36543c7feb16902e416e94d81c1d9d4404d8702df3ba936e060e69b4f3d616",
+        domain_separators: [
+            "f1a063e70aae5e432e4145147b500f54b6e66dec1da79125086e73309299641a",
+            "7f2322c6a840ef5a3897b0a475ada13da1b724e58d87e1f43432c77e1a6ddfaf",
+        ],
+        signing_hashes: [
+            "bccd3dba66162633c95e3e48dd6a676b882d77bcf13729dfff5f940bbdf4eeb3",
+            "f1405614556700b0bb4a929fbaa43095b8770f5fc5810b196485a5b30915407a",
+        ],
+        previous_protocol_signing_hash: "eab52ef9195e36edb757bf227c318cd06ab4df8d82237783062cdc54a664059b",
+    },
+];
+
+fn hex(s: &str) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).unwrap();
+    }
+    bytes
+}
+
+fn program_id() -> [u8; 32] {
+    core::array::from_fn(|i| i as u8)
+}
+
+fn envelope() -> Envelope {
+    Envelope {
+        version: ENVELOPE_VERSION,
+        tx_id: 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10,
+        source_chain_id: 2,
+        dest_chain_id: GATEWAY_CHAIN,
+        sender: vec![0x11; 20],
+        recipient: vec![0x22; 32],
+        on_chain_data: b"hello".to_vec(),
+        off_chain_data: b"world".to_vec(),
+        off_chain_data_hash: [0u8; 32],
+        message_type: 0,
+        deadline: 1_700_000_000,
+        sequence: 7,
+        protocol_version: PROTOCOL_VERSION,
+        da_pointer: Vec::new(),
+    }
+}
+
+#[test]
+fn domain_separator_vectors() {
+    for v in VECTORS {
+        for (instance_id, expected) in [0, INSTANCE].into_iter().zip(v.domain_separators) {
+            let domain_separator =
+                create_domain_separator(v.scheme, &program_id(), GATEWAY_CHAIN, instance_id);
+            assert_eq!(domain_separator, hex(expected), "{:?} instance {}", v.scheme, instance_id);
+        }
+    }
+}
+
+#[test]
+fn signing_hash_vectors() {
+    let envelope = envelope();
+    for v in VECTORS {
+        let cross_chain_hash = create_cross_chain_hash(v.scheme, &envelope).unwrap();
+        assert_eq!(cross_chain_hash, hex(v.cross_chain_hash), "{:?}", v.scheme);
+        
+        for (instance_id, expected) in [0, INSTANCE].into_iter().zip(v.signing_hashes) {
+            let signing_hash = create_instance_message_hash_for_signing(
+                v.scheme,
+                &program_id(),
+                GATEWAY_CHAIN,
+                instance_id,
+                &envelope,
+            )
+            .unwrap();
+            assert_eq!(signing_hash, hex(expected), "{:?} instance {}", v.scheme, instance_id);
+        }
+        
+        let default_instance =
+            create_message_hash_for_signing(v.scheme, &program_id(), GATEWAY_CHAIN, &envelope);
+        assert_eq!(default_instance.unwrap(), hex(v.signing_hashes[0]));
+    }
+}
+
+#[test]
+fn previous_protocol_version_messages_keep_their_signing_hash() {
+    // Validators signed these under version 3; a build at PROTOCOL_VERSION must still
+    // reproduce them, so the build's own version cannot enter the domain
+    let envelope = Envelope { protocol_version: 3, ..envelope() };
+    for v in VECTORS {
+        let signing_hash = create_instance_message_hash_for_signing(
+            v.scheme,
+            &program_id(),
+            GATEWAY_CHAIN,
+            INSTANCE,
+            &envelope,
+        )
+        .unwrap();
+        assert_eq!(signing_hash, hex(v.previous_protocol_signing_hash), "{:?}", v.scheme);
+        assert_ne!(signing_hash, hex(v.signing_hashes[1]), "{:?}", v.scheme);
+    }
+}
+
+#[test]
+fn signing_hash_composes_its_parts() {
+    for v in VECTORS {
+        let signing_hash = create_signing_hash_with(
+            |parts| hash_with_algorithm(v.scheme.algorithm, parts),
+            &hex(v.domain_separators[1]),
+            &hex(v.cross_chain_hash),
+        );
+        assert_eq!(signing_hash, hex(v.signing_hashes[1]), "{:?}", v.scheme);
+    }
+}
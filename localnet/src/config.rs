@@ -0,0 +1,61 @@
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::Result;
+
+/// Harness settings, read from `VIA_LOCALNET_*` environment variables
+pub struct Config {
+    /// `VIA_LOCALNET_PROGRAM`: built program (default `target/deploy/message_gateway_v4.so`)
+    pub program: PathBuf,
+    /// `VIA_LOCALNET_RELAYER`: relayer binary (default `target/debug/via-relayer`)
+    pub relayer: PathBuf,
+    /// `VIA_LOCALNET_VALIDATOR`: test validator binary (default `solana-test-validator`)
+    pub validator: PathBuf,
+    /// `VIA_LOCALNET_RPC_PORT` (default 8899)
+    pub rpc_port: u16,
+    /// `VIA_LOCALNET_MESSAGES`: inbound messages the source chain produces (default 10)
+    pub messages: usize,
+    /// `VIA_LOCALNET_POOL_TOP_UP`: passed to the relayer; non-zero relays through its pool
+    pub pool_top_up: u64,
+    /// `VIA_LOCALNET_TIMEOUT_SECS`: how long each stage may take (default 120)
+    pub timeout: Duration,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self> {
+        Ok(Self {
+            program: var("PROGRAM")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("target/deploy/message_gateway_v4.so")),
+            relayer: var("RELAYER")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("target/debug/via-relayer")),
+            validator: var("VALIDATOR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("solana-test-validator")),
+            rpc_port: parse_or("RPC_PORT", 8899)?,
+            messages: parse_or("MESSAGES", 10)?,
+            pool_top_up: parse_or("POOL_TOP_UP", 0)?,
+            timeout: Duration::from_secs(parse_or("TIMEOUT_SECS", 120)?),
+        })
+    }
+    
+    pub fn rpc_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.rpc_port)
+    }
+}
+
+fn var(name: &str) -> Option<String> {
+    env::var(format!("VIA_LOCALNET_{name}")).ok()
+}
+
+fn parse_or<T: std::str::FromStr>(name: &str, default: T) -> Result<T>
+where
+    T::Err: std::error::Error + 'static,
+{
+    match var(name) {
+        Some(value) => Ok(value.parse()?),
+        None => Ok(default),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,131 @@
+//! End-to-end invariants, checked against the validator's state and the gateway's logs
+
+use std::collections::HashMap;
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_client::events::{MessageProcessed, TxPdaCreated};
+use message_gateway_client::state::{CounterPDA, MessageEnvelope, RelayerPool};
+use message_gateway_client::{account, event, pda, ID};
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+
+use crate::Result;
+
+/// Gateway events of every successful transaction so far, oldest first
+#[derive(Default)]
+pub struct GatewayEvents {
+    pub created: Vec<TxPdaCreated>,
+    pub processed: Vec<MessageProcessed>,
+}
+
+impl GatewayEvents {
+    pub fn fetch(client: &RpcClient) -> Result<Self> {
+        let mut signatures: Vec<Signature> = Vec::new();
+        let mut before = None;
+        loop {
+            let page = client.get_signatures_for_address_with_config(
+                &ID,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: None,
+                    limit: None,
+                    commitment: Some(client.commitment()),
+                },
+            )?;
+            if page.is_empty() {
+                break;
+            }
+            for status in page {
+                let signature: Signature = status.signature.parse()?;
+                before = Some(signature);
+                if status.err.is_none() {
+                    signatures.push(signature);
+                }
+            }
+        }
+        
+        let mut events = Self::default();
+        for signature in signatures.iter().rev() {
+            let transaction = client.get_transaction_with_config(
+                signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(client.commitment()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )?;
+            let logs = match transaction.transaction.meta.map(|meta| meta.log_messages) {
+                Some(OptionSerializer::Some(logs)) => logs,
+                _ => continue,
+            };
+            events.created.extend(event::parse_events::<TxPdaCreated>(&logs));
+            events.processed.extend(event::parse_events::<MessageProcessed>(&logs));
+        }
+        Ok(events)
+    }
+    
+    /// MessageProcessed events per tx_id
+    pub fn processed_counts(&self) -> HashMap<u128, usize> {
+        let mut counts = HashMap::new();
+        for processed in &self.processed {
+            *counts.entry(processed.tx_id).or_default() += 1;
+        }
+        counts
+    }
+}
+
+/// Every message was opened by one TX1 and delivered by one TX2 from `relayer`, and nothing
+/// else was delivered
+pub fn delivered_exactly_once(events: &GatewayEvents, sent: &[MessageEnvelope], relayer: &Pubkey) -> Result<()> {
+    let counts = events.processed_counts();
+    for envelope in sent {
+        let processed = counts.get(&envelope.tx_id).copied().unwrap_or(0);
+        if processed != 1 {
+            return Err(format!("tx_id={} delivered {processed} times", envelope.tx_id).into());
+        }
+        let created = events.created.iter().filter(|e| e.tx_id == envelope.tx_id).count();
+        if created != 1 {
+            return Err(format!("tx_id={} opened {created} TxId PDAs", envelope.tx_id).into());
+        }
+    }
+    if counts.len() != sent.len() {
+        return Err(format!("{} messages delivered, {} sent", counts.len(), sent.len()).into());
+    }
+    for processed in &events.processed {
+        if processed.relayer != *relayer {
+            return Err(format!("tx_id={} delivered by {}", processed.tx_id, processed.relayer).into());
+        }
+    }
+    Ok(())
+}
+
+/// TX2 closed every TxId PDA and the source chain's counter saw the highest tx_id
+pub fn state_settled(client: &RpcClient, sent: &[MessageEnvelope], relayer: &Pubkey) -> Result<()> {
+    for envelope in sent {
+        let address = pda::tx_id(envelope.source_chain_id, envelope.tx_id).0;
+        if client.get_account_with_commitment(&address, client.commitment())?.value.is_some() {
+            return Err(format!("TxId PDA of tx_id={} still open", envelope.tx_id).into());
+        }
+    }
+    
+    if let Some(last) = sent.iter().max_by_key(|envelope| envelope.tx_id) {
+        let counter: CounterPDA = account::fetch(client, &pda::counter(last.source_chain_id).0)?;
+        if counter.source_chain_id != last.source_chain_id || counter.highest_tx_id_seen != last.tx_id {
+            return Err(format!(
+                "counter of chain {} at tx_id={}, expected {}",
+                counter.source_chain_id, counter.highest_tx_id_seen, last.tx_id
+            )
+            .into());
+        }
+    }
+    
+    // Pooled relays return every advanced rent to the pool
+    let pool: Option<RelayerPool> = account::fetch_optional(client, &pda::relayer_pool(relayer).0)?;
+    if let Some(pool) = pool.filter(|pool| pool.rent_outstanding != 0) {
+        return Err(format!("relayer pool still lends {} lamports", pool.rent_outstanding).into());
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,233 @@
+//! Localnet end-to-end harness for the Via Labs V4 message gateway
+//!
+//! 1. Starts `solana-test-validator` with the built gateway preloaded
+//! 2. Initializes the gateway, a VIA and an EVM Chain validator set (`mock_validator`) and
+//!    the EVM chain's ChainConfig (keccak256, big-endian, 20-byte addresses)
+//! 3. Starts the reference relayer on a feed file the simulated EVM chain publishes to
+//! 4. Sends `VIA_LOCALNET_MESSAGES` attested messages from the EVM chain and one message
+//!    back to it, then republishes the first message as a replay
+//! 5. Checks exactly-once delivery, closed TxId PDAs, the source chain's counter, a settled
+//!    relayer pool and the outbound delivery against the validator's state and logs
+//!
+//! Exits non-zero on the first broken invariant. Build the program and the relayer first
+//! (see `config` for the paths and knobs)
+
+mod config;
+mod invariants;
+mod process;
+mod source_chain;
+
+use std::env;
+use std::fs;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::system_program;
+use message_gateway_client::mock_validator::MockValidatorSet;
+use message_gateway_client::state::{AddressFormat, MessageEnvelope, SignerRegistryType};
+use message_gateway_client::{accounts, constants::PROTOCOL_VERSION, instruction, ix, pda, ID};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::signature::{write_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+use crate::config::Config;
+use crate::invariants::GatewayEvents;
+use crate::source_chain::{hex, EvmChain, EVM_HASH_SCHEME};
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Chain id the localnet gateway is initialized with
+const SOLANA_CHAIN_ID: u64 = 900;
+/// The simulated source chain (Hardhat's default chain id)
+const EVM_CHAIN_ID: u64 = 31337;
+/// Sending contract on the EVM chain
+const EVM_APP: [u8; 20] = [0xEE; 20];
+
+fn main() -> Result<()> {
+    let config = Config::from_env()?;
+    let workdir = env::temp_dir().join(format!("via-localnet-{}", std::process::id()));
+    fs::create_dir_all(&workdir)?;
+    
+    let _validator = process::start_validator(&config, &workdir.join("ledger"), &ID)?;
+    let client = RpcClient::new_with_commitment(config.rpc_url(), CommitmentConfig::confirmed());
+    println!("validator up at {}", config.rpc_url());
+    
+    let authority = Keypair::new();
+    let relayer = Keypair::new();
+    fund(&client, &authority.pubkey())?;
+    fund(&client, &relayer.pubkey())?;
+    let relayer_keypair = workdir.join("relayer.json");
+    write_keypair_file(&relayer, &relayer_keypair)?;
+    
+    let validators = MockValidatorSet::new(SOLANA_CHAIN_ID)
+        .with_registry(SignerRegistryType::VIA, SOLANA_CHAIN_ID, 3, 0, 2)
+        .with_registry(SignerRegistryType::Chain, EVM_CHAIN_ID, 0, 3, 2);
+    let mut setup = vec![ix::initialize_gateway(&authority.pubkey(), SOLANA_CHAIN_ID)];
+    setup.extend(validators.initialize_instructions(&authority.pubkey()));
+    setup.extend(evm_chain_config(&authority.pubkey()));
+    for instruction in setup {
+        submit(&client, &authority, &[instruction])?;
+    }
+    println!("gateway {SOLANA_CHAIN_ID} initialized with EVM chain {EVM_CHAIN_ID}");
+    
+    let feed = workdir.join("feed.jsonl");
+    fs::write(&feed, "")?;
+    let relayer_process = process::start_relayer(&config, &relayer_keypair, &feed, SOLANA_CHAIN_ID)?;
+    let mut evm = EvmChain::new(EVM_CHAIN_ID, SOLANA_CHAIN_ID, feed);
+    
+    // Inbound: the EVM chain sends, the validators attest, the relayer delivers
+    let recipient = Keypair::new().pubkey();
+    let mut sent: Vec<MessageEnvelope> = Vec::new();
+    for index in 0..config.messages {
+        let envelope = evm.send(EVM_APP, &recipient, format!("localnet message {index}").as_bytes());
+        evm.publish(&validators, &envelope)?;
+        sent.push(envelope);
+    }
+    wait_for(&config, "inbound delivery", || {
+        Ok(GatewayEvents::fetch(&client)?.processed.len() >= sent.len())
+    })?;
+    
+    // Replay: the same attested message again must not be delivered twice
+    if let Some(first) = sent.first() {
+        evm.publish(&validators, first)?;
+        thread::sleep(Duration::from_secs(5));
+    }
+    
+    let events = GatewayEvents::fetch(&client)?;
+    invariants::delivered_exactly_once(&events, &sent, &relayer.pubkey())?;
+    invariants::state_settled(&client, &sent, &relayer.pubkey())?;
+    println!("{} inbound messages delivered exactly once, replay rejected", sent.len());
+    
+    // Outbound: a Solana app sends to the EVM chain, the relayer picks it up
+    let outbound = outbound_envelope(&authority.pubkey());
+    submit(&client, &authority, &[send_message(&authority.pubkey(), outbound.clone())])?;
+    let started = Instant::now();
+    loop {
+        let remaining = config.timeout.saturating_sub(started.elapsed());
+        match relayer_process.outbound.recv_timeout(remaining) {
+            Ok(envelope) => {
+                if evm.receive(envelope) {
+                    break;
+                }
+            }
+            Err(RecvTimeoutError::Timeout) => return Err("timed out waiting for outbound delivery".into()),
+            Err(RecvTimeoutError::Disconnected) => return Err("relayer exited".into()),
+        }
+    }
+    let received = &evm.inbox[0];
+    // tx_ids above u64::MAX do not survive a serde_json::Value, so match on the payload
+    if received["on_chain_data"].as_str() != Some(hex(&outbound.on_chain_data).as_str()) {
+        return Err(format!("EVM chain received {received}, expected tx_id={}", outbound.tx_id).into());
+    }
+    println!("outbound tx_id={} received on EVM chain {EVM_CHAIN_ID}", outbound.tx_id);
+    
+    let _ = fs::remove_dir_all(&workdir);
+    println!("localnet end-to-end: ok");
+    Ok(())
+}
+
+fn fund(client: &RpcClient, address: &Pubkey) -> Result<()> {
+    let signature = client.request_airdrop(address, 100 * LAMPORTS_PER_SOL)?;
+    while !client.confirm_transaction(&signature)? {
+        thread::sleep(Duration::from_millis(200));
+    }
+    Ok(())
+}
+
+fn submit(client: &RpcClient, payer: &Keypair, instructions: &[Instruction]) -> Result<()> {
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        client.get_latest_blockhash()?,
+    );
+    client.send_and_confirm_transaction(&transaction)?;
+    Ok(())
+}
+
+/// Poll `done` until it holds or the stage times out
+fn wait_for(config: &Config, stage: &str, mut done: impl FnMut() -> Result<bool>) -> Result<()> {
+    let started = Instant::now();
+    while !done()? {
+        if started.elapsed() > config.timeout {
+            return Err(format!("timed out waiting for {stage}").into());
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+    Ok(())
+}
+
+/// ChainConfig of the EVM chain: its route scheme and 20-byte addresses
+fn evm_chain_config(authority: &Pubkey) -> [Instruction; 2] {
+    let chain_config = pda::chain_config(EVM_CHAIN_ID).0;
+    let gateway = pda::gateway(SOLANA_CHAIN_ID).0;
+    [
+        ix::build(
+            accounts::InitializeChainConfig {
+                chain_config,
+                gateway,
+                authority: *authority,
+                system_program: system_program::ID,
+            },
+            instruction::InitializeChainConfig {
+                chain_id: EVM_CHAIN_ID,
+                hash_algorithm: EVM_HASH_SCHEME.algorithm,
+                integer_encoding: EVM_HASH_SCHEME.integer_encoding,
+            },
+        ),
+        ix::build(
+            accounts::SetRemoteAddressConfig {
+                chain_config,
+                gateway,
+                authority: *authority,
+            },
+            instruction::SetRemoteAddressConfig {
+                chain_id: EVM_CHAIN_ID,
+                address_format: AddressFormat::Evm,
+                remote_gateway: Vec::new(),
+            },
+        ),
+    ]
+}
+
+fn outbound_envelope(sender: &Pubkey) -> MessageEnvelope {
+    MessageEnvelope {
+        version: MessageEnvelope::CURRENT_VERSION,
+        tx_id: (SOLANA_CHAIN_ID as u128) << 64 | 1,
+        source_chain_id: SOLANA_CHAIN_ID,
+        dest_chain_id: EVM_CHAIN_ID,
+        sender: sender.to_bytes().to_vec(),
+        recipient: EVM_APP.to_vec(),
+        on_chain_data: b"localnet reply".to_vec(),
+        off_chain_data: Vec::new(),
+        off_chain_data_hash: [0; 32],
+        message_type: MessageEnvelope::MESSAGE_TYPE_GENERIC,
+        deadline: 0,
+        sequence: 0,
+        protocol_version: PROTOCOL_VERSION,
+        da_pointer: Vec::new(),
+    }
+}
+
+fn send_message(sender: &Pubkey, envelope: MessageEnvelope) -> Instruction {
+    ix::build(
+        accounts::SendMessage {
+            gateway: pda::gateway(SOLANA_CHAIN_ID).0,
+            sender: *sender,
+            chain_config: Some(pda::chain_config(EVM_CHAIN_ID).0),
+            fee_config: pda::fee_config(EVM_CHAIN_ID).0,
+            channel: None,
+            price_update: None,
+            fee_payer: None,
+            sol_vault: None,
+            fee_vault: None,
+            system_program: None,
+        },
+        instruction::SendMessage { envelope, confirmations: 1 },
+    )
+}
\ No newline at end of file
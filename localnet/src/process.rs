@@ -0,0 +1,85 @@
+//! Child processes the harness owns: the test validator and the relayer
+//! Both are killed when their guard drops, so a failed run leaves nothing behind
+
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anchor_lang::prelude::Pubkey;
+use solana_client::rpc_client::RpcClient;
+
+use crate::config::Config;
+use crate::Result;
+
+pub struct Guard(Child);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// `solana-test-validator` with the gateway preloaded at its program ID, on a fresh ledger
+pub fn start_validator(config: &Config, ledger: &Path, program_id: &Pubkey) -> Result<Guard> {
+    let child = Command::new(&config.validator)
+        .arg("--reset")
+        .arg("--quiet")
+        .arg("--ledger")
+        .arg(ledger)
+        .arg("--rpc-port")
+        .arg(config.rpc_port.to_string())
+        .arg("--bpf-program")
+        .arg(program_id.to_string())
+        .arg(&config.program)
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(|error| format!("cannot start {}: {error}", config.validator.display()))?;
+    let guard = Guard(child);
+    
+    let client = RpcClient::new(config.rpc_url());
+    let started = Instant::now();
+    while client.get_health().is_err() {
+        if started.elapsed() > config.timeout {
+            return Err("test validator did not become healthy".into());
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+    Ok(guard)
+}
+
+/// Relayer process and the outbound envelopes it prints, one JSON value per line
+pub struct Relayer {
+    _guard: Guard,
+    pub outbound: Receiver<serde_json::Value>,
+}
+
+/// The reference relayer, relaying from `feed` with `keypair` as fee payer
+pub fn start_relayer(config: &Config, keypair: &Path, feed: &PathBuf, gateway_chain_id: u64) -> Result<Relayer> {
+    let mut child = Command::new(&config.relayer)
+        .env("VIA_RELAYER_RPC_URL", config.rpc_url())
+        .env("VIA_RELAYER_KEYPAIR", keypair)
+        .env("VIA_RELAYER_GATEWAY_CHAIN_ID", gateway_chain_id.to_string())
+        .env("VIA_RELAYER_FEED", feed)
+        .env("VIA_RELAYER_POLL_MS", "500")
+        .env("VIA_RELAYER_POOL_TOP_UP", config.pool_top_up.to_string())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("cannot start {}: {error}", config.relayer.display()))?;
+    
+    let stdout = child.stdout.take().ok_or("relayer stdout not captured")?;
+    let (sender, outbound) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(std::result::Result::ok) {
+            if let Ok(value) = serde_json::from_str(&line) {
+                if sender.send(value).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    Ok(Relayer { _guard: Guard(child), outbound })
+}
\ No newline at end of file
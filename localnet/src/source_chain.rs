@@ -0,0 +1,160 @@
+//! Simulated EVM-like source chain
+//!
+//! Produces outbound envelopes the way an EVM gateway would (20-byte senders, sequential
+//! nonces folded into the tx_id, ABI-style payloads), has them attested by its mock
+//! validator set and publishes them to the relayer's JSON-lines feed. It also receives the
+//! envelopes the Solana gateway sends back to it
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_client::constants::PROTOCOL_VERSION;
+use message_gateway_client::mock_validator::MockValidatorSet;
+use message_gateway_client::state::{HashAlgorithm, HashScheme, IntegerEncoding, MessageEnvelope, SignatureScheme};
+use serde::Serialize;
+
+use crate::Result;
+
+/// Route scheme of the EVM chain's ChainConfig: keccak256 over big-endian integers
+pub const EVM_HASH_SCHEME: HashScheme = HashScheme {
+    algorithm: HashAlgorithm::Keccak256,
+    integer_encoding: IntegerEncoding::BigEndian,
+};
+
+/// One line of the relayer's feed (see the relayer's `feed` module)
+#[derive(Serialize)]
+struct FeedLine {
+    envelope: EnvelopeLine,
+    signatures: Vec<SignatureLine>,
+}
+
+#[derive(Serialize)]
+struct EnvelopeLine {
+    version: u8,
+    tx_id: u128,
+    source_chain_id: u64,
+    dest_chain_id: u64,
+    sender: String,
+    recipient: String,
+    on_chain_data: String,
+    off_chain_data: String,
+    off_chain_data_hash: String,
+    message_type: u8,
+    deadline: i64,
+    sequence: u64,
+    protocol_version: u8,
+    da_pointer: String,
+}
+
+#[derive(Serialize)]
+struct SignatureLine {
+    signer: String,
+    signature: String,
+    recovery_id: Option<u8>,
+}
+
+pub struct EvmChain {
+    pub chain_id: u64,
+    /// Solana gateway the chain sends to
+    pub dest_chain_id: u64,
+    nonce: u64,
+    feed: PathBuf,
+    /// Envelopes received from the Solana gateway
+    pub inbox: Vec<serde_json::Value>,
+}
+
+impl EvmChain {
+    pub fn new(chain_id: u64, dest_chain_id: u64, feed: PathBuf) -> Self {
+        Self {
+            chain_id,
+            dest_chain_id,
+            nonce: 0,
+            feed,
+            inbox: Vec::new(),
+        }
+    }
+    
+    /// Next outbound envelope from `sender` (a 20-byte contract address) to `recipient`
+    pub fn send(&mut self, sender: [u8; 20], recipient: &Pubkey, payload: &[u8]) -> MessageEnvelope {
+        self.nonce += 1;
+        // abi.encode(uint256 nonce, bytes payload), as a dApp on the source chain would
+        let mut on_chain_data = [[0u8; 24].as_slice(), &self.nonce.to_be_bytes()].concat();
+        on_chain_data.extend_from_slice(&[0u8; 31]);
+        on_chain_data.push(0x40);
+        on_chain_data.extend_from_slice(&[0u8; 24]);
+        on_chain_data.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+        on_chain_data.extend_from_slice(payload);
+        on_chain_data.resize(on_chain_data.len().next_multiple_of(32), 0);
+        
+        MessageEnvelope {
+            version: MessageEnvelope::CURRENT_VERSION,
+            tx_id: (self.chain_id as u128) << 64 | self.nonce as u128,
+            source_chain_id: self.chain_id,
+            dest_chain_id: self.dest_chain_id,
+            sender: sender.to_vec(),
+            recipient: recipient.to_bytes().to_vec(),
+            on_chain_data,
+            off_chain_data: Vec::new(),
+            off_chain_data_hash: [0; 32],
+            message_type: MessageEnvelope::MESSAGE_TYPE_GENERIC,
+            deadline: 0,
+            sequence: 0,
+            protocol_version: PROTOCOL_VERSION,
+            da_pointer: Vec::new(),
+        }
+    }
+    
+    /// Have the validators attest `envelope` and append it to the relayer's feed
+    pub fn publish(&self, validators: &MockValidatorSet, envelope: &MessageEnvelope) -> Result<()> {
+        let (_, signatures) = validators.attest(EVM_HASH_SCHEME, envelope, None)?;
+        let line = FeedLine {
+            envelope: EnvelopeLine {
+                version: envelope.version,
+                tx_id: envelope.tx_id,
+                source_chain_id: envelope.source_chain_id,
+                dest_chain_id: envelope.dest_chain_id,
+                sender: hex(&envelope.sender),
+                recipient: hex(&envelope.recipient),
+                on_chain_data: hex(&envelope.on_chain_data),
+                off_chain_data: hex(&envelope.off_chain_data),
+                off_chain_data_hash: hex(&envelope.off_chain_data_hash),
+                message_type: envelope.message_type,
+                deadline: envelope.deadline,
+                sequence: envelope.sequence,
+                protocol_version: envelope.protocol_version,
+                da_pointer: hex(&envelope.da_pointer),
+            },
+            signatures: signatures
+                .iter()
+                .map(|s| SignatureLine {
+                    signer: s.signer.to_string(),
+                    signature: hex(&s.signature),
+                    recovery_id: match s.scheme {
+                        SignatureScheme::Ed25519 => None,
+                        SignatureScheme::Secp256k1 { recovery_id } => Some(recovery_id),
+                    },
+                })
+                .collect(),
+        };
+        
+        let mut feed = OpenOptions::new().create(true).append(true).open(&self.feed)?;
+        writeln!(feed, "{}", serde_json::to_string(&line)?)?;
+        Ok(())
+    }
+    
+    /// Accept an envelope the relayer picked up from the Solana gateway, if addressed here
+    pub fn receive(&mut self, envelope: serde_json::Value) -> bool {
+        let addressed_here = envelope["dest_chain_id"].as_u64() == Some(self.chain_id);
+        if addressed_here {
+            self.inbox.push(envelope);
+        }
+        addressed_here
+    }
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    let digits: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("0x{digits}")
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+//! `parse_ed25519_instruction` over arbitrary precompile data
+//!
+//! Besides panics and out-of-bounds slicing, an accepted entry must have the expected key
+//! and message somewhere in the data (and the signature too when strict), and every entry
+//! laid out by `ed25519_instruction` must be accepted
+#![no_main]
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use message_gateway_v4::constants::MAX_SIGNATURES_PER_MESSAGE;
+use message_gateway_v4::utils::ed25519::ed25519_instruction;
+use message_gateway_v4::utils::signature::parse_ed25519_instruction;
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    data: Vec<u8>,
+    signature: [u8; 64],
+    signer: [u8; 32],
+    message: [u8; 32],
+    strict: bool,
+    /// Entries for the round-trip through the shared builder
+    entries: Vec<([u8; 32], [u8; 64])>,
+}
+
+fn contains(data: &[u8], needle: &[u8]) -> bool {
+    data.windows(needle.len()).any(|window| window == needle)
+}
+
+fuzz_target!(|input: Input| {
+    let signer = Pubkey::new_from_array(input.signer);
+    let ix = Instruction::new_with_bytes(ed25519_program::ID, &input.data, Vec::new());
+    if parse_ed25519_instruction(&ix, &input.signature, &signer, &input.message, input.strict) == Some(true) {
+        assert!(contains(&input.data, &input.signer));
+        assert!(contains(&input.data, &input.message));
+        if input.strict {
+            assert!(contains(&input.data, &input.signature));
+        }
+    }
+    
+    let entries: Vec<(Pubkey, [u8; 64])> = input
+        .entries
+        .iter()
+        .take(MAX_SIGNATURES_PER_MESSAGE)
+        .map(|(key, signature)| (Pubkey::new_from_array(*key), *signature))
+        .collect();
+    let packed = ed25519_instruction(&entries, &input.message);
+    for (key, signature) in &entries {
+        assert_eq!(parse_ed25519_instruction(&packed, signature, key, &input.message, true), Some(true));
+    }
+});
\ No newline at end of file
@@ -0,0 +1,106 @@
+//! Attestation matching through the instructions sysvar over arbitrary transactions
+//!
+//! The transaction mixes raw instructions with precompiles built by `ed25519_instruction`
+//! and then corrupted, so both the rejection and the acceptance paths are reached
+//! Every match `match_ed25519_instructions` records (TX1) must be found again by
+//! `verify_ed25519_signature` (TX2), with the recorded offset as a hint and without it
+#![no_main]
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::{ed25519_program, instruction::BorrowedInstruction, sysvar::{self, instructions}};
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use message_gateway_v4::constants::MAX_SIGNATURES_PER_MESSAGE;
+use message_gateway_v4::state::{MessageSignature, SignatureScheme};
+use message_gateway_v4::utils::ed25519::ed25519_instruction;
+use message_gateway_v4::utils::signature::{match_ed25519_instructions, verify_ed25519_signature};
+
+#[derive(Arbitrary, Debug)]
+enum Ix {
+    /// Arbitrary data for the precompile or another program
+    Raw { precompile: bool, data: Vec<u8> },
+    /// Precompile over the attestations at these indices, with one byte flipped
+    Packed { attestations: Vec<u8>, corrupt: Option<(u16, u8)> },
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    attestations: Vec<([u8; 32], [u8; 64])>,
+    message_hash: [u8; 32],
+    transaction: Vec<Ix>,
+    current_index: u16,
+}
+
+fuzz_target!(|input: Input| {
+    let signatures: Vec<MessageSignature> = input
+        .attestations
+        .iter()
+        .take(MAX_SIGNATURES_PER_MESSAGE)
+        .map(|(signer, signature)| MessageSignature {
+            signature: *signature,
+            signer: Pubkey::new_from_array(*signer),
+            scheme: SignatureScheme::Ed25519,
+        })
+        .collect();
+    
+    let mut payloads: Vec<(Pubkey, Vec<u8>)> = input
+        .transaction
+        .iter()
+        .map(|ix| match ix {
+            Ix::Raw { precompile, data } => {
+                let program_id = if *precompile { ed25519_program::ID } else { Pubkey::default() };
+                (program_id, data.clone())
+            }
+            Ix::Packed { attestations, corrupt } => {
+                let entries: Vec<(Pubkey, [u8; 64])> = attestations
+                    .iter()
+                    .filter_map(|&index| signatures.get(index as usize))
+                    .map(|signature| (signature.signer, signature.signature))
+                    .collect();
+                let mut data = ed25519_instruction(&entries, &input.message_hash).data;
+                if let Some((at, mask)) = corrupt {
+                    let len = data.len();
+                    data[*at as usize % len] ^= mask;
+                }
+                (ed25519_program::ID, data)
+            }
+        })
+        .collect();
+    // The gateway instruction the scan runs from
+    payloads.push((message_gateway_v4::ID, Vec::new()));
+    
+    let borrowed: Vec<BorrowedInstruction> = payloads
+        .iter()
+        .map(|(program_id, data)| BorrowedInstruction { program_id, accounts: Vec::new(), data })
+        .collect();
+    let mut data = instructions::construct_instructions_data(&borrowed);
+    let current_index = input.current_index % (payloads.len() as u16 + 1);
+    instructions::store_current_index_checked(&mut data, current_index).unwrap();
+    
+    let key = instructions::ID;
+    let owner = sysvar::ID;
+    let mut lamports = 0;
+    let account = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+    
+    let Ok(offsets) = match_ed25519_instructions(&signatures, &input.message_hash, &account) else {
+        return;
+    };
+    for (signature, &offset) in signatures.iter().zip(offsets.iter()) {
+        if offset == 0 {
+            continue;
+        }
+        for hint in [offset, 0] {
+            let found = verify_ed25519_signature(
+                &signature.signature,
+                &signature.signer,
+                &input.message_hash,
+                &account,
+                hint,
+                false,
+            );
+            if input.message_hash != [0; 32] {
+                assert!(matches!(found, Ok(true)));
+            }
+        }
+    }
+});
\ No newline at end of file
@@ -3,12 +3,65 @@ pub const GATEWAY_SEED: &[u8] = b"gateway";
 pub const COUNTER_SEED: &[u8] = b"counter";
 pub const TX_SEED: &[u8] = b"tx";
 pub const SIGNER_REGISTRY_SEED: &[u8] = b"signer_registry";
+pub const CHAIN_CONFIG_SEED: &[u8] = b"chain_config";
+pub const CHAIN_ALIAS_SEED: &[u8] = b"chain_alias";
+pub const CHANNEL_SEED: &[u8] = b"channel";
+pub const QUERY_SEED: &[u8] = b"query";
+pub const V3_MAPPING_SEED: &[u8] = b"v3_mapping";
+pub const V3_RECEIPT_SEED: &[u8] = b"v3_receipt";
+pub const BRIDGE_PEER_SEED: &[u8] = b"bridge_peer";
+pub const WRAPPED_ASSET_SEED: &[u8] = b"wrapped_asset";
+pub const WRAPPED_MINT_SEED: &[u8] = b"wrapped_mint";
+pub const SOL_VAULT_SEED: &[u8] = b"sol_vault";
+pub const RESCUE_SEED: &[u8] = b"rescue";
+pub const RATE_LIMIT_SEED: &[u8] = b"rate_limit";
+pub const TOKEN_CONFIG_SEED: &[u8] = b"token_config";
+pub const NFT_COLLECTION_SEED: &[u8] = b"nft_collection";
+pub const WRAPPED_NFT_SEED: &[u8] = b"wrapped_nft";
+pub const WRAPPED_NFT_MINT_SEED: &[u8] = b"wrapped_nft_mint";
+pub const DEAD_LETTER_SEED: &[u8] = b"dead_letter";
+pub const TOKEN_CLAIM_SEED: &[u8] = b"token_claim";
+pub const LIQUIDITY_PROVIDER_SEED: &[u8] = b"liquidity_provider";
+pub const FAST_FILL_SEED: &[u8] = b"fast_fill";
+pub const BLOCKLIST_SEED: &[u8] = b"blocklist";
+pub const WRAPPED_LISTING_SEED: &[u8] = b"wrapped_listing";
+pub const VESTING_SEED: &[u8] = b"vesting";
+pub const COMPRESSED_NFT_TREE_SEED: &[u8] = b"cnft_tree";
+pub const WRAPPED_CNFT_SEED: &[u8] = b"wrapped_cnft";
+pub const CUSTODY_EXTENSION_SEED: &[u8] = b"custody_ext";
+pub const CUSTODY_AUTHORITY_SEED: &[u8] = b"custody_authority";
+pub const REGISTRY_BUNDLE_SEED: &[u8] = b"registry_bundle";
+pub const MESSAGE_HISTORY_SEED: &[u8] = b"message_history";
+pub const MESSAGE_BUFFER_SEED: &[u8] = b"message_buffer";
+pub const RELAYER_POOL_SEED: &[u8] = b"relayer_pool";
+pub const DELIVERY_AUTHORITY_SEED: &[u8] = via_receiver_interface::DELIVERY_AUTHORITY_SEED;
+pub const FEE_CONFIG_SEED: &[u8] = b"fee_config";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+
+/// Domain separation for signed message hashes (EIP-712 style)
+pub const DOMAIN_SEPARATOR_TAG: &[u8] = via_gateway_core::DOMAIN_SEPARATOR_TAG;
+pub const SIGNED_HASH_PREFIX: &[u8] = via_gateway_core::SIGNED_HASH_PREFIX;
+pub const PROTOCOL_VERSION: u8 = via_gateway_core::PROTOCOL_VERSION;
+
+/// Version of this program build, stored in every gateway it creates
+/// Bump it with any change to an account layout
+pub const PROGRAM_VERSION: u16 = 2;
+/// Oldest program version whose gateway layout this build still reads correctly
+pub const MIN_COMPATIBLE_PROGRAM_VERSION: u16 = 2;
+
+/// Gateway instance created by `initialize_gateway`; its PDAs carry no instance seed
+pub const DEFAULT_INSTANCE_ID: u16 = via_gateway_core::DEFAULT_INSTANCE_ID;
 
 /// Maximum sizes for DOS protection
 pub const MAX_RECIPIENT_SIZE: usize = 64;
 pub const MAX_SENDER_SIZE: usize = 64;
 pub const MAX_ON_CHAIN_DATA_SIZE: usize = 1024;
 pub const MAX_OFF_CHAIN_DATA_SIZE: usize = 1024;
+pub const MAX_DA_POINTER_SIZE: usize = 64;
+
+/// Largest payload assembled from chunks in a MessageBuffer (the buffer account must fit
+/// the 10 KiB an account can be created with)
+pub const MAX_BUFFERED_DATA_SIZE: usize = 8192;
 
 /// Signature validation constants
 pub const MAX_SIGNATURES_PER_MESSAGE: usize = 8;
@@ -16,6 +69,60 @@ pub const MIN_SIGNATURES_REQUIRED: usize = 2;
 pub const ED25519_SIGNATURE_SIZE: usize = 64;
 pub const ED25519_PUBKEY_SIZE: usize = 32;
 
+/// Recent-tx bloom filter in CounterPDA: size, hash count and insertions before it is
+/// cleared (keeps the false-positive rate below ~0.02%)
+pub const RECENT_TX_FILTER_BYTES: usize = 256;
+pub const RECENT_TX_FILTER_HASHES: usize = 4;
+pub const RECENT_TX_FILTER_CAPACITY: u16 = 64;
+
+/// Lifetime of a TxId PDA whose TX2 never arrived, after which keepers may close it
+pub const TX_ID_PDA_TTL_SECONDS: i64 = 7 * 24 * 60 * 60;
+
+/// Largest number of TxId PDAs one gc_batch closes (each takes two accounts)
+pub const MAX_GC_BATCH: usize = 24;
+
 /// Signer registry constants
 pub const MAX_SIGNERS_PER_REGISTRY: usize = 10;
-pub const MIN_THRESHOLD: u8 = 1;
\ No newline at end of file
+pub const MIN_THRESHOLD: u8 = 1;
+
+/// Default staleness bound for Pyth price updates (seconds)
+pub const DEFAULT_MAX_PRICE_AGE: u64 = 60;
+
+/// Emergency escrow rescue: delay before execution and reason length
+pub const RESCUE_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+pub const MAX_RESCUE_REASON_LEN: usize = 128;
+
+/// Longest registry shadow trial, so a raised threshold cannot stay unenforced indefinitely
+pub const MAX_SHADOW_TRIAL_SECONDS: i64 = 30 * 24 * 60 * 60;
+
+/// Delay between proposing a wrapped-asset listing and creating its mint
+pub const WRAPPED_LISTING_TIMELOCK_SECONDS: i64 = 48 * 60 * 60;
+
+/// Basis-point denominator for bridge fees
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Wrapped mints use the source asset's decimals capped at this value
+pub const MAX_WRAPPED_DECIMALS: u8 = 9;
+
+/// Largest number of recipient/amount pairs in a batched token transfer
+pub const MAX_BATCH_TRANSFERS: usize = 16;
+
+/// Compliance blocklist capacity (wallets and mints)
+pub const MAX_BLOCKLIST_ENTRIES: usize = 128;
+
+/// Fast transfers: minimum LP bond and maximum LP fee on a fronted transfer
+pub const MIN_LP_BOND_LAMPORTS: u64 = 1_000_000_000;
+pub const MAX_FAST_FILL_FEE_BPS: u64 = 100;
+
+/// Extra verifier sets (DVN-style) a project can require
+pub const MAX_EXTRA_VERIFIERS: usize = 4;
+pub const MAX_VERIFIER_ID: u8 = u8::MAX - 3;
+
+/// Verification stack depth: VIA, Chain, Project and the extra verifier sets
+pub const MAX_VERIFICATION_MODULES: usize = 3 + MAX_EXTRA_VERIFIERS;
+
+/// CAIP-2 chain identifier limits (namespace:reference)
+pub const MIN_CAIP2_NAMESPACE_LEN: usize = 3;
+pub const MAX_CAIP2_NAMESPACE_LEN: usize = 8;
+pub const MAX_CAIP2_REFERENCE_LEN: usize = 32;
+pub const MAX_CAIP2_ID_LEN: usize = MAX_CAIP2_NAMESPACE_LEN + 1 + MAX_CAIP2_REFERENCE_LEN;
\ No newline at end of file
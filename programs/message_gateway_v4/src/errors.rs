@@ -98,4 +98,346 @@ pub enum GatewayError {
     
     #[msg("Gateway is disabled")]
     GatewayDisabled,
+    
+    #[msg("Unsupported message envelope version")]
+    UnsupportedEnvelopeVersion,
+    
+    #[msg("Message deadline has passed")]
+    MessageExpired,
+    
+    #[msg("Envelope source chain does not match gateway")]
+    InvalidSourceChain,
+    
+    #[msg("Envelope sender does not match signer")]
+    SenderMismatch,
+    
+    #[msg("Invalid CAIP-2 chain identifier")]
+    InvalidCaip2Id,
+    
+    #[msg("CAIP-2 identifier already set for this chain")]
+    Caip2AlreadySet,
+    
+    #[msg("CAIP-2 identifier does not match chain config")]
+    Caip2Mismatch,
+    
+    #[msg("Invalid message payload")]
+    InvalidPayload,
+    
+    #[msg("Unsupported payload version")]
+    UnsupportedPayloadVersion,
+    
+    #[msg("Extra verifier signature threshold not met")]
+    InsufficientVerifierSignatures,
+    
+    #[msg("Required verifier registry missing or invalid")]
+    MissingVerifierRegistry,
+    
+    #[msg("Too many extra verifiers configured")]
+    TooManyVerifiers,
+    
+    #[msg("Channel does not match message")]
+    ChannelMismatch,
+    
+    #[msg("Channel is closed")]
+    ChannelClosed,
+    
+    #[msg("Channel account required for sequenced message")]
+    ChannelRequired,
+    
+    #[msg("Unexpected channel sequence number")]
+    InvalidSequence,
+    
+    #[msg("Channel trust requirements not met")]
+    ChannelTrustNotMet,
+    
+    #[msg("Protocol version not supported by this gateway")]
+    UnsupportedProtocolVersion,
+    
+    #[msg("Invalid protocol version range")]
+    InvalidProtocolVersionRange,
+    
+    #[msg("Query response does not match the pending query")]
+    QueryMismatch,
+    
+    #[msg("Address is not valid for the chain's address format")]
+    InvalidRemoteAddress,
+    
+    #[msg("Off-chain data does not match its committed hash")]
+    OffChainDataHashMismatch,
+    
+    #[msg("Data-availability pointer too long")]
+    DaPointerTooLong,
+    
+    #[msg("Loopback routes are disabled on this gateway")]
+    LoopbackDisabled,
+    
+    #[msg("Invalid or unverified Pyth price update")]
+    InvalidPriceFeed,
+    
+    #[msg("Price update is too old")]
+    StalePrice,
+    
+    #[msg("Fee conversion overflow")]
+    FeeCalculationOverflow,
+    
+    #[msg("Fee payment accounts missing")]
+    MissingFeeAccounts,
+    
+    #[msg("V3 source chain is not mapped to a V4 route")]
+    V3RouteNotMapped,
+    
+    #[msg("Token amount must be greater than zero")]
+    InvalidAmount,
+    
+    #[msg("Token message was not sent by the registered bridge peer")]
+    UntrustedBridgePeer,
+    
+    #[msg("Token in payload does not match the provided mint")]
+    TokenMismatch,
+    
+    #[msg("Token account is not owned by the payload recipient")]
+    InvalidTokenRecipient,
+    
+    #[msg("Value transfer requires the SOL vault, fee payer and system program")]
+    MissingValueAccounts,
+    
+    #[msg("SOL vault balance is insufficient for this release")]
+    InsufficientVaultBalance,
+    
+    #[msg("Rescue reason is too long")]
+    RescueReasonTooLong,
+    
+    #[msg("Rescue timelock has not elapsed")]
+    RescueTimelockActive,
+    
+    #[msg("Rate limit window must be positive")]
+    InvalidRateLimit,
+    
+    #[msg("Transfer exceeds the token's rolling volume limit")]
+    RateLimitExceeded,
+    
+    #[msg("Rebasing tokens cannot be deposited into escrow")]
+    RebasingTokenBlocked,
+    
+    #[msg("Escrow accounting would go out of range")]
+    EscrowAccountingMismatch,
+    
+    #[msg("Mint is not a non-fungible token")]
+    InvalidNft,
+    
+    #[msg("NFT is not a verified member of a registered collection")]
+    NftCollectionNotVerified,
+    
+    #[msg("NFT does not match the transfer payload")]
+    NftMismatch,
+    
+    #[msg("Wrapped NFT is already minted")]
+    NftAlreadyMinted,
+    
+    #[msg("NFT metadata is missing or does not match the payload")]
+    MissingNftMetadata,
+    
+    #[msg("Token bridging is paused for this mint")]
+    TokenPaused,
+    
+    #[msg("Transfer amount is below the token's minimum")]
+    TransferBelowMinimum,
+    
+    #[msg("Transfer amount is above the token's maximum")]
+    TransferAboveMaximum,
+    
+    #[msg("Invalid token configuration")]
+    InvalidTokenConfig,
+    
+    #[msg("Mint is not configured for this bridge path")]
+    TokenKindMismatch,
+    
+    #[msg("Amount cannot be represented in the token's bridge decimals")]
+    AmountNotRepresentable,
+    
+    #[msg("Payload version is supported; process the message normally")]
+    PayloadVersionSupported,
+    
+    #[msg("Signer is neither the owner nor the delegate of the token account")]
+    UnauthorizedDepositor,
+    
+    #[msg("Delegate approval does not cover the deposit amount")]
+    InsufficientDelegation,
+    
+    #[msg("Transfer can be released directly; only blocked transfers can be parked")]
+    ReleaseNotBlocked,
+    
+    #[msg("Escrow account is required for native token claims")]
+    MissingEscrow,
+    
+    #[msg("Message does not match the parked transfer")]
+    ClaimMismatch,
+    
+    #[msg("Liquidity provider bond is below the minimum")]
+    InsufficientBond,
+    
+    #[msg("Liquidity provider has open fills")]
+    OpenFastFills,
+    
+    #[msg("Fast-fill fee exceeds the maximum")]
+    FastFillFeeTooHigh,
+    
+    #[msg("Fast fill is not in the required state")]
+    InvalidFastFillStatus,
+    
+    #[msg("Message was revoked after being fronted")]
+    FastFillRevoked,
+    
+    #[msg("Transfer was fronted by a liquidity provider")]
+    FastFillExists,
+    
+    #[msg("Address or mint is on the compliance blocklist")]
+    AddressBlocked,
+    
+    #[msg("Blocklist is full")]
+    BlocklistFull,
+    
+    #[msg("Address is already blocked or is not on the blocklist")]
+    InvalidBlocklistEntry,
+    
+    #[msg("Treasury token account is required when a bridge fee applies")]
+    MissingTreasuryAccount,
+    
+    #[msg("Wrapped-asset listing timelock has not elapsed")]
+    ListingTimelockActive,
+    
+    #[msg("Wrapped asset is delisted")]
+    WrappedAssetDelisted,
+    
+    #[msg("Wrapped asset is already listed")]
+    WrappedAssetListed,
+    
+    #[msg("Mint uses claim-based delivery; park the transfer for the recipient to claim")]
+    ClaimDeliveryRequired,
+    
+    #[msg("Batch must contain between one and the maximum number of transfers")]
+    InvalidBatchSize,
+    
+    #[msg("Batched deposits do not support tokens with transfer fees")]
+    BatchTransferFeeUnsupported,
+    
+    #[msg("Missing or invalid token account for a batch recipient")]
+    InvalidBatchAccount,
+    
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    
+    #[msg("No vested tokens to claim")]
+    NothingVested,
+    
+    #[msg("Invalid or unregistered compressed NFT tree")]
+    InvalidCompressedNftTree,
+    
+    #[msg("Token escrow still holds bridged tokens")]
+    EscrowNotEmpty,
+    
+    #[msg("Issuer program does not match the token's issuer route")]
+    InvalidIssuerProgram,
+    
+    #[msg("Issuer burn or mint did not move the expected amount")]
+    IssuerTransferMismatch,
+    
+    #[msg("Custody extension does not match the mint or project")]
+    InvalidCustodyExtension,
+    
+    #[msg("Custody extension did not move the expected amount")]
+    CustodyTransferMismatch,
+    
+    #[msg("Malformed compact message encoding")]
+    InvalidCompactEncoding,
+    
+    #[msg("tx_id recently passed TX1 for this source chain")]
+    RecentTxIdReplay,
+    
+    #[msg("Registry bundle predates the latest signer registry change")]
+    StaleRegistryBundle,
+    
+    #[msg("Registry bundle does not match the route or its registries")]
+    InvalidRegistryBundle,
+    
+    #[msg("Message history is enabled but its tree accounts are missing or wrong")]
+    MissingMessageHistory,
+    
+    #[msg("Message chunk is out of order or overflows the buffer")]
+    InvalidMessageChunk,
+    
+    #[msg("Message buffer is incomplete or does not match its payload hash")]
+    MessageBufferIncomplete,
+    
+    #[msg("gc_batch takes up to MAX_GC_BATCH TxId PDA and relayer account pairs")]
+    InvalidGcBatch,
+    
+    #[msg("TxId PDA has not expired yet")]
+    TxIdPdaNotExpired,
+    
+    #[msg("Relayer pool balance does not cover the TxId PDA rent")]
+    RelayerPoolInsufficient,
+    
+    #[msg("TxId PDA rent was advanced by a relayer pool that is missing or does not match")]
+    InvalidRelayerPool,
+    
+    #[msg("bootstrap_test_env takes project signers exactly when the project registry account is given")]
+    InvalidBootstrapProject,
+    
+    #[msg("Counter PDA has not been initialized for a source chain")]
+    CounterNotInitialized,
+    
+    #[msg("Counter PDA belongs to a different source chain")]
+    CounterChainMismatch,
+    
+    #[msg("TxId PDA already exists: TX1 for this tx_id has already been processed")]
+    AlreadyProcessed,
+    
+    #[msg("Signer registry account does not exist or holds no registry")]
+    RegistryNotFound,
+    
+    #[msg("Ed25519 precompile instruction has a malformed offsets table")]
+    Ed25519InstructionMalformed,
+    
+    #[msg("TxId PDA rent was funded by a payer that is missing or does not match")]
+    InvalidRentPayer,
+    
+    #[msg("assert_invariants takes gateway accounts it can audit, each token config followed by its escrow")]
+    InvalidInvariantAccount,
+    
+    #[msg("Gateway account was written by a program version this build cannot read")]
+    IncompatibleVersion,
+    
+    #[msg("export_snapshot takes chain configs ascending by chain id, then signer registries ascending by type and chain id")]
+    InvalidSnapshotAccount,
+    
+    #[msg("Snapshot page is out of range, or pages do not reassemble into one valid snapshot")]
+    InvalidSnapshotPage,
+    
+    #[msg("Shadow trial must enforce fewer signatures than the registry threshold but no fewer than before its last change (0 only for a new registry), and end within MAX_SHADOW_TRIAL_SECONDS")]
+    InvalidShadowTrial,
+    
+    #[msg("Additional gateway instances need a non-default instance id")]
+    InvalidGatewayInstance,
+    
+    #[msg("Recipient program does not match the message recipient")]
+    InvalidRecipientProgram,
+    
+    #[msg("Fee vault balance is insufficient for this withdrawal")]
+    InsufficientFeeVaultBalance,
+    
+    #[msg("Relayer fee share cannot exceed 10000 basis points")]
+    InvalidRelayerRefund,
+    
+    #[msg("Destination chain is disabled for sending")]
+    DestinationChainDisabled,
+    
+    #[msg("Requested confirmations are outside the destination chain's range")]
+    ConfirmationsOutOfRange,
+    
+    #[msg("On-chain data exceeds the destination chain's maximum payload size")]
+    PayloadTooLargeForDestination,
+    
+    #[msg("Destination config needs min_confirmations <= max_confirmations and a payload size up to MAX_ON_CHAIN_DATA_SIZE")]
+    InvalidDestinationConfig,
 }
\ No newline at end of file
@@ -1,14 +1,18 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{
+    AddressFormat, ChannelOrdering, HashAlgorithm, IntegerEncoding, MessageEnvelope,
+    SignerRegistryType, TokenKind, TokenSettings,
+};
+
 /// Event emitted when a message is sent
 #[event]
 pub struct SendRequested {
-    pub tx_id: u128,
-    pub sender: [u8; 32],
-    pub recipient: Vec<u8>,
-    pub dest_chain_id: u64,
-    pub chain_data: Vec<u8>,
+    pub envelope: MessageEnvelope,
     pub confirmations: u16,
+    /// Lamports the sender paid for the message: the destination's FeeConfig fee plus the
+    /// USD send fee at the current price (0 on send paths without fees)
+    pub fee_paid: u64,
     // pub timestamp: i64, 
 }
 
@@ -24,7 +28,11 @@ pub struct TxPdaCreated {
 pub struct MessageProcessed {
     pub tx_id: u128,
     pub source_chain_id: u64,
+    pub off_chain_data_hash: [u8; 32],
+    pub da_pointer: Vec<u8>,
     pub relayer: Pubkey,
+    /// Lamports refunded to the relayer from the fee vault (0 without a refund)
+    pub relayer_refund: u64,
     // pub processed_at: i64,
 }
 
@@ -41,4 +49,802 @@ pub struct CounterInitialized {
     pub counter_pda: Pubkey,
     pub authority: Pubkey,
     pub gateway: Pubkey,
+}
+
+/// Event emitted when a chain configuration is created or updated
+#[event]
+pub struct ChainConfigUpdated {
+    pub chain_id: u64,
+    pub hash_algorithm: HashAlgorithm,
+    pub integer_encoding: IntegerEncoding,
+}
+
+/// Event emitted when a CAIP-2 identifier is mapped to a chain
+#[event]
+pub struct ChainAliasSet {
+    pub chain_id: u64,
+    pub caip2_id: String,
+}
+
+/// Event emitted when a CAIP-2 identifier mapping is removed
+#[event]
+pub struct ChainAliasCleared {
+    pub chain_id: u64,
+    pub caip2_id: String,
+}
+
+/// Event emitted when a channel is opened
+#[event]
+pub struct ChannelOpened {
+    pub channel: Pubkey,
+    pub remote_chain_id: u64,
+    pub local_app: Pubkey,
+    pub remote_app: Vec<u8>,
+    pub ordering: ChannelOrdering,
+}
+
+/// Event emitted when a channel is closed
+#[event]
+pub struct ChannelClosed {
+    pub channel: Pubkey,
+    pub remote_chain_id: u64,
+    pub local_app: Pubkey,
+}
+
+/// Event emitted when the supported protocol version range changes
+#[event]
+pub struct ProtocolVersionsUpdated {
+    pub min_protocol_version: u8,
+    pub max_protocol_version: u8,
+}
+
+/// Event emitted when a cross-chain query is sent
+#[event]
+pub struct QueryRequested {
+    pub query: Pubkey,
+    pub query_id: u128,
+    pub requester: Pubkey,
+    pub dest_chain_id: u64,
+    pub callback_program: Pubkey,
+}
+
+/// Event emitted when a query response is delivered to the callback program
+#[event]
+pub struct QueryFulfilled {
+    pub query: Pubkey,
+    pub query_id: u128,
+    pub source_chain_id: u64,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when a message is delivered to its recipient program by deliver_message
+#[event]
+pub struct MessageDelivered {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub recipient_program: Pubkey,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when an unanswered query is cancelled
+#[event]
+pub struct QueryCancelled {
+    pub query: Pubkey,
+    pub query_id: u128,
+}
+
+/// Event emitted when a chain's address family or remote gateway changes
+#[event]
+pub struct RemoteAddressConfigUpdated {
+    pub chain_id: u64,
+    pub address_format: AddressFormat,
+    pub remote_gateway: Vec<u8>,
+}
+
+/// Event emitted by verify_hash with the hashes computed on-chain
+#[event]
+pub struct HashVerified {
+    pub tx_id: u128,
+    pub cross_chain_hash: [u8; 32],
+    pub signing_hash: [u8; 32],
+}
+
+/// Event emitted when loopback test mode is toggled
+#[event]
+pub struct LoopbackModeChanged {
+    pub enabled: bool,
+}
+
+/// Event emitted when full TX2 signature verification is toggled
+#[event]
+pub struct SignatureVerificationModeChanged {
+    pub full_verification: bool,
+}
+
+/// Event emitted when strict Ed25519 precompile checks are toggled
+#[event]
+pub struct PrecompileCheckModeChanged {
+    pub strict: bool,
+}
+
+/// Event emitted when a chain's USD send fee changes
+#[event]
+pub struct SendFeeUpdated {
+    pub chain_id: u64,
+    pub send_fee_usd: u64,
+}
+
+/// Event emitted when the fee price feed changes
+#[event]
+pub struct PriceFeedUpdated {
+    pub price_feed_id: [u8; 32],
+    pub max_price_age: u64,
+}
+
+/// Event emitted when the token bridge fee treasury changes
+#[event]
+pub struct TreasuryUpdated {
+    pub treasury: Pubkey,
+}
+
+/// Event emitted when a send fee is paid
+#[event]
+pub struct SendFeePaid {
+    pub payer: Pubkey,
+    pub dest_chain_id: u64,
+    pub fee_usd: u64,
+    pub lamports: u64,
+}
+
+/// Event emitted when a V3 chain mapping is created or updated
+#[event]
+pub struct V3MappingUpdated {
+    pub v3_chain_id: u64,
+    pub v4_chain_id: u64,
+    pub message_type: u8,
+    pub enabled: bool,
+}
+
+/// Event emitted when a V3 message is accepted and re-wrapped as a V4 envelope
+#[event]
+pub struct V3MessageTranslated {
+    pub v3_source_chain_id: u64,
+    pub legacy_hash: [u8; 32],
+    pub envelope: MessageEnvelope,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when a remote token bridge is registered
+#[event]
+pub struct BridgePeerUpdated {
+    pub chain_id: u64,
+    pub remote_bridge: Vec<u8>,
+}
+
+/// Event emitted when tokens are locked in escrow for a cross-chain transfer
+#[event]
+pub struct TokensDeposited {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub depositor: Pubkey,
+    /// Delegate that submitted the deposit under the depositor's approval, if any
+    pub delegate: Option<Pubkey>,
+    pub amount: u64,
+    /// Amount credited to escrow after Token-2022 transfer fees
+    pub received: u64,
+    /// Bridge fee withheld from the received amount; the rest is bridged
+    pub fee: u64,
+    pub dest_chain_id: u64,
+    pub recipient: Vec<u8>,
+}
+
+/// Event emitted when tokens are released from escrow for an inbound transfer
+#[event]
+pub struct TokensReleased {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    /// Bridge fee paid to the treasury out of `amount`
+    pub fee: u64,
+    /// Amount credited to the recipient after Token-2022 transfer fees
+    pub received: u64,
+    pub source_chain_id: u64,
+    pub relayer: Pubkey,
+    /// Lamports refunded to the relayer from the fee vault (0 without a refund)
+    pub relayer_refund: u64,
+}
+
+/// Event emitted when tokens for several recipients are locked in one batch message
+#[event]
+pub struct TokenBatchDeposited {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub depositor: Pubkey,
+    /// Delegate that submitted the deposit under the depositor's approval, if any
+    pub delegate: Option<Pubkey>,
+    pub transfers: u8,
+    /// Total debited from the depositor across all transfers
+    pub amount: u64,
+    /// Total bridge fee paid to the treasury
+    pub fee: u64,
+    pub dest_chain_id: u64,
+}
+
+/// Event emitted when every transfer of an inbound batch message is delivered
+#[event]
+pub struct TokenBatchReleased {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub transfers: u8,
+    /// Total released across all transfers, fee included
+    pub amount: u64,
+    /// Total bridge fee paid to the treasury out of `amount`
+    pub fee: u64,
+    pub source_chain_id: u64,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when the authority proposes a wrapped-asset listing
+#[event]
+pub struct WrappedAssetListingProposed {
+    pub source_chain_id: u64,
+    pub source_token: [u8; 32],
+    pub decimals: u8,
+    pub execute_after: i64,
+    pub proposed_by: Pubkey,
+}
+
+/// Event emitted when a pending wrapped-asset listing is cancelled
+#[event]
+pub struct WrappedAssetListingCancelled {
+    pub source_chain_id: u64,
+    pub source_token: [u8; 32],
+    pub cancelled_by: Pubkey,
+}
+
+/// Event emitted when a wrapped asset is delisted
+#[event]
+pub struct WrappedAssetDelisted {
+    pub source_chain_id: u64,
+    pub source_token: [u8; 32],
+    pub mint: Pubkey,
+    pub delisted_by: Pubkey,
+}
+
+/// Event emitted when a wrapped mint is created for a foreign asset
+#[event]
+pub struct WrappedAssetCreated {
+    pub source_chain_id: u64,
+    pub source_token: [u8; 32],
+    pub mint: Pubkey,
+    pub decimals: u8,
+}
+
+/// Event emitted when wrapped tokens are minted for an inbound transfer
+#[event]
+pub struct WrappedTokensMinted {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    /// Bridge fee minted to the treasury out of `amount`
+    pub fee: u64,
+    pub source_chain_id: u64,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when wrapped tokens are burned to return to their source chain
+#[event]
+pub struct WrappedTokensBurned {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+    /// Bridge fee kept by the gateway on top of the burned amount
+    pub fee: u64,
+    pub dest_chain_id: u64,
+    pub recipient: Vec<u8>,
+}
+
+/// Event emitted when lamports attached to a message are locked in the SOL vault
+#[event]
+pub struct ValueLocked {
+    pub tx_id: u128,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub dest_chain_id: u64,
+    pub recipient: Vec<u8>,
+}
+
+/// Event emitted when lamports are released from the SOL vault for an inbound transfer
+#[event]
+pub struct ValueReleased {
+    pub tx_id: u128,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub source_chain_id: u64,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when an emergency escrow rescue is queued
+#[event]
+pub struct RescueRequested {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub escrow_balance: u64,
+    pub reason: String,
+    pub requested_at: i64,
+    pub execute_after: i64,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when a queued rescue moves tokens out of escrow
+#[event]
+pub struct RescueExecuted {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining_escrow_balance: u64,
+    pub reason: String,
+    pub requested_at: i64,
+    pub executed_at: i64,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when a queued rescue is cancelled
+#[event]
+pub struct RescueCancelled {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when a mint's inbound volume cap is set
+#[event]
+pub struct RateLimitUpdated {
+    pub mint: Pubkey,
+    pub window_seconds: u64,
+    pub max_amount: u64,
+}
+
+/// Event emitted when a wallet or mint is added to or removed from the compliance blocklist
+#[event]
+pub struct BlocklistUpdated {
+    pub entry: Pubkey,
+    pub blocked: bool,
+    pub updated_by: Pubkey,
+}
+
+/// Event emitted when a mint's bridge configuration is set
+#[event]
+pub struct TokenConfigUpdated {
+    pub mint: Pubkey,
+    pub kind: TokenKind,
+    pub governance: Pubkey,
+    pub settings: TokenSettings,
+    pub updated_by: Pubkey,
+}
+
+/// Event emitted when a project custody extension takes over a mint's token legs
+#[event]
+pub struct CustodyExtensionAttached {
+    pub mint: Pubkey,
+    pub project_registry: Pubkey,
+    pub program: Pubkey,
+    pub attached_by: Pubkey,
+}
+
+/// Event emitted when a mint returns from a custody extension to the built-in escrow
+#[event]
+pub struct CustodyExtensionDetached {
+    pub mint: Pubkey,
+    pub program: Pubkey,
+    pub detached_by: Pubkey,
+}
+
+/// Event emitted when a custody extension locks tokens for a cross-chain transfer
+#[event]
+pub struct CustodyLocked {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub program: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub dest_chain_id: u64,
+    pub recipient: Vec<u8>,
+}
+
+/// Event emitted when a custody extension releases an inbound transfer
+#[event]
+pub struct CustodyReleased {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub mint: Pubkey,
+    pub program: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when a mint is routed through its issuer's program or back to escrow
+#[event]
+pub struct IssuerRouteUpdated {
+    pub mint: Pubkey,
+    /// Default pubkey when the mint returned to escrow bridging
+    pub issuer_program: Pubkey,
+    pub updated_by: Pubkey,
+}
+
+/// Event emitted when issuer-routed tokens are burned for a cross-chain transfer
+#[event]
+pub struct IssuerBurnDeposited {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub dest_chain_id: u64,
+    pub recipient: Vec<u8>,
+}
+
+/// Event emitted when the issuer mints an inbound issuer-routed transfer
+#[event]
+pub struct IssuerTransferCompleted {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when a Metaplex collection is allowed to bridge out
+#[event]
+pub struct NftCollectionRegistered {
+    pub collection_mint: Pubkey,
+}
+
+/// Event emitted when a Solana-native NFT is locked in escrow
+#[event]
+pub struct NftLocked {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub collection_mint: Pubkey,
+    pub owner: Pubkey,
+    pub dest_chain_id: u64,
+    pub recipient: Vec<u8>,
+}
+
+/// Event emitted when a Solana-native NFT is released from escrow
+#[event]
+pub struct NftUnlocked {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub source_chain_id: u64,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when a wrapped NFT is minted for a foreign NFT
+#[event]
+pub struct WrappedNftMinted {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub source_chain_id: u64,
+    pub token_id: [u8; 32],
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when a wrapped NFT is burned to return to its source chain
+#[event]
+pub struct WrappedNftBurned {
+    pub tx_id: u128,
+    pub mint: Pubkey,
+    pub holder: Pubkey,
+    pub dest_chain_id: u64,
+    pub recipient: Vec<u8>,
+}
+
+/// Event emitted when a Bubblegum tree is registered for wrapped compressed NFTs
+#[event]
+pub struct CompressedNftTreeRegistered {
+    pub merkle_tree: Pubkey,
+}
+
+/// Event emitted when a Solana-native compressed NFT is transferred to the gateway
+#[event]
+pub struct CompressedNftLocked {
+    pub tx_id: u128,
+    pub asset_id: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub collection_mint: Pubkey,
+    pub owner: Pubkey,
+    pub dest_chain_id: u64,
+    pub recipient: Vec<u8>,
+}
+
+/// Event emitted when a Solana-native compressed NFT is released by the gateway
+#[event]
+pub struct CompressedNftUnlocked {
+    pub tx_id: u128,
+    pub asset_id: Pubkey,
+    pub recipient: Pubkey,
+    pub source_chain_id: u64,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when a compressed NFT is minted for a foreign NFT
+#[event]
+pub struct WrappedCompressedNftMinted {
+    pub tx_id: u128,
+    pub asset_id: Pubkey,
+    pub merkle_tree: Pubkey,
+    pub recipient: Pubkey,
+    pub source_chain_id: u64,
+    pub token_id: [u8; 32],
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when a wrapped compressed NFT is burned to return to its source chain
+#[event]
+pub struct WrappedCompressedNftBurned {
+    pub tx_id: u128,
+    pub asset_id: Pubkey,
+    pub holder: Pubkey,
+    pub dest_chain_id: u64,
+    pub recipient: Vec<u8>,
+}
+
+/// Event emitted when a wrapped mint receives its attested name, symbol and URI
+#[event]
+pub struct WrappedAssetMetadataSet {
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+/// Event emitted when a token transfer with an unsupported payload version is parked
+#[event]
+pub struct TokenTransferDeadLettered {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub payload_version: u8,
+    pub relayer: Pubkey,
+    /// Lamports refunded to the relayer from the fee vault (0 without a refund)
+    pub relayer_refund: u64,
+}
+
+/// Event emitted when a mint's escrow balance is reconciled against its recorded liabilities
+#[event]
+pub struct EscrowReconciled {
+    pub mint: Pubkey,
+    pub escrow: Pubkey,
+    pub escrow_balance: u64,
+    pub escrowed_amount: u64,
+    /// Balance above the recorded liabilities (donations, rounding)
+    pub surplus: u64,
+    /// Recorded liabilities not backed by the balance; non-zero means insolvent
+    pub shortfall: u64,
+    pub timestamp: i64,
+}
+
+/// Consistency check run by assert_invariants
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InvariantCheck {
+    /// Gateway chain id set and protocol version range well-formed
+    GatewayConfig,
+    /// Signer registry stored at its canonical PDA
+    RegistryAddress,
+    /// Enabled registry threshold reachable by its distinct signers
+    RegistryThreshold,
+    /// Counter initialized for the source chain its seeds name
+    CounterChainId,
+    /// Chain config at its canonical PDA with fields within their bounds
+    ChainConfigBounds,
+    /// Relayer pool lamports cover its rent plus its recorded balance
+    RelayerPoolBalance,
+    /// Escrow token balance covers the amount owed to remote chains
+    EscrowBalance,
+}
+
+/// Account that failed an invariant check
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvariantViolation {
+    pub account: Pubkey,
+    pub check: InvariantCheck,
+}
+
+/// Event emitted by assert_invariants with every check that failed (none = healthy)
+#[event]
+pub struct InvariantsAsserted {
+    pub chain_id: u64,
+    pub checks: u32,
+    pub violations: Vec<InvariantViolation>,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an undeliverable inbound token transfer is parked as a claim
+#[event]
+pub struct TokenTransferParked {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    /// Parked because the mint was paused
+    pub paused: bool,
+    /// Parked because the recipient or mint is blocklisted
+    pub blocked: bool,
+    pub relayer: Pubkey,
+}
+
+/// Event emitted when the recipient claims a parked token transfer
+#[event]
+pub struct TokenClaimed {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    /// Bridge fee paid to the treasury out of `amount`
+    pub fee: u64,
+}
+
+/// Event emitted when a vesting grant is delivered into a vesting schedule
+#[event]
+pub struct VestingScheduleCreated {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub start_time: i64,
+    pub cliff_seconds: u64,
+    pub duration_seconds: u64,
+    pub period_seconds: u64,
+}
+
+/// Event emitted when the recipient claims vested tokens
+#[event]
+pub struct VestedTokensClaimed {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    /// Bridge fee paid to the treasury out of `amount`
+    pub fee: u64,
+    /// Total claimed from the schedule so far
+    pub claimed: u64,
+    /// Whether the grant is fully claimed and the schedule closed
+    pub complete: bool,
+}
+
+/// Event emitted when a parked token transfer is sent back to its source-chain sender
+#[event]
+pub struct TokenTransferReturned {
+    pub tx_id: u128,
+    pub return_tx_id: u128,
+    pub source_chain_id: u64,
+    pub mint: Pubkey,
+    pub sender: Vec<u8>,
+    pub wire_amount: u64,
+}
+
+/// Event emitted when a liquidity provider's bond changes
+#[event]
+pub struct LiquidityBondUpdated {
+    pub liquidity_provider: Pubkey,
+    pub bond: u64,
+}
+
+/// Event emitted when a relayer deposits into or withdraws from its rent pool
+#[event]
+pub struct RelayerPoolUpdated {
+    pub relayer: Pubkey,
+    pub balance: u64,
+    pub rent_outstanding: u64,
+}
+
+/// Event emitted when a liquidity provider fronts an inbound token transfer
+#[event]
+pub struct TransferFronted {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub liquidity_provider: Pubkey,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+/// Event emitted when TX2 settles a fronted transfer
+#[event]
+pub struct FastFillSettled {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub liquidity_provider: Pubkey,
+    /// False when TX2 carried a different envelope and the LP forfeited the fill
+    pub repaid: bool,
+}
+
+/// Event emitted when the authority revokes a fronted message and slashes the LP
+#[event]
+pub struct FastFillRevoked {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub liquidity_provider: Pubkey,
+    pub slashed: u64,
+    pub slash_destination: Pubkey,
+}
+
+/// Event emitted when the gateway starts recording processed messages in a new tree
+#[event]
+pub struct MessageHistoryTreeSet {
+    pub merkle_tree: Pubkey,
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+}
+
+/// Event emitted when a registry shadow trial is started or ended
+#[event]
+pub struct RegistryShadowSet {
+    pub registry_type: SignerRegistryType,
+    pub chain_id: u64,
+    /// Threshold evaluated during the trial and enforced after it
+    pub required_signatures: u8,
+    /// Threshold enforced during the trial
+    pub enforced_signatures: u8,
+    /// End of the trial (0 = ended)
+    pub shadow_until: i64,
+}
+
+/// Event emitted when a message passes the enforced thresholds but misses a threshold on
+/// shadow trial; `layer` is the module's position in the [VIA, Chain, Project?, Verifier...]
+/// stack
+#[event]
+pub struct ShadowThresholdMissed {
+    pub message_hash: [u8; 32],
+    pub layer: u8,
+    pub signatures: u8,
+    pub required_signatures: u8,
+}
+
+/// Event emitted when the lamport fee schedule of a destination chain is set
+#[event]
+pub struct FeeConfigUpdated {
+    pub dest_chain_id: u64,
+    pub base_fee: u64,
+    pub fee_per_byte: u64,
+}
+
+/// Event emitted when accumulated send fees are withdrawn from the fee vault
+#[event]
+pub struct FeesWithdrawn {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+/// Event emitted when the relayer refund for messages from a chain is set
+#[event]
+pub struct RelayerRefundUpdated {
+    pub chain_id: u64,
+    pub relayer_refund: u64,
+    pub relayer_fee_share_bps: u16,
+}
+
+/// Event emitted when a chain's send limits change
+#[event]
+pub struct DestinationConfigUpdated {
+    pub chain_id: u64,
+    pub enabled: bool,
+    pub min_confirmations: u16,
+    pub max_confirmations: u16,
+    pub max_payload_size: u32,
 }
\ No newline at end of file
@@ -2,7 +2,10 @@ use anchor_lang::prelude::*;
 
 use crate::constants::*;
 use crate::errors::GatewayError;
-use crate::events::SystemStatusChanged;
+use crate::events::{
+    LoopbackModeChanged, PrecompileCheckModeChanged, PriceFeedUpdated, ProtocolVersionsUpdated,
+    SignatureVerificationModeChanged, SystemStatusChanged, TreasuryUpdated,
+};
 use crate::state::MessageGateway;
 
 pub fn set_system_enabled(ctx: Context<SetSystemEnabled>, enabled: bool) -> Result<()> {
@@ -19,8 +22,208 @@ pub fn set_system_enabled(ctx: Context<SetSystemEnabled>, enabled: bool) -> Resu
 pub struct SetSystemEnabled<'info> {
     #[account(
         mut,
-        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref()],
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
         bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Update the accepted envelope protocol version range (admin only)
+/// Widen the range before rolling out new validator software, then raise the
+/// minimum once every validator has upgraded
+pub fn set_protocol_versions(
+    ctx: Context<SetProtocolVersions>,
+    min_protocol_version: u8,
+    max_protocol_version: u8,
+) -> Result<()> {
+    require!(
+        min_protocol_version > 0 && min_protocol_version <= max_protocol_version,
+        GatewayError::InvalidProtocolVersionRange
+    );
+    
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.min_protocol_version = min_protocol_version;
+    gateway.max_protocol_version = max_protocol_version;
+    
+    emit!(ProtocolVersionsUpdated {
+        min_protocol_version,
+        max_protocol_version,
+    });
+    
+    msg!(
+        "Supported protocol versions: {}..={}",
+        min_protocol_version,
+        max_protocol_version
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolVersions<'info> {
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Toggle loopback test mode (admin only)
+/// When enabled, send_message output for this gateway's own chain_id can be fed
+/// straight into create_tx_pda/process_message on the same cluster
+pub fn set_loopback_enabled(ctx: Context<SetLoopbackEnabled>, enabled: bool) -> Result<()> {
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.loopback_enabled = enabled;
+    
+    emit!(LoopbackModeChanged { enabled });
+    
+    msg!("Loopback mode {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetLoopbackEnabled<'info> {
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Toggle full TX2 signature verification (admin only)
+/// Off by default: validation stops once every layer threshold is met, skipping the
+/// signatures a message carries beyond them; turn it on to verify (and so reject
+/// invalid) surplus attestations too
+pub fn set_full_signature_verification(
+    ctx: Context<SetFullSignatureVerification>,
+    enabled: bool,
+) -> Result<()> {
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.full_signature_verification = enabled;
+    
+    emit!(SignatureVerificationModeChanged {
+        full_verification: enabled,
+    });
+    
+    msg!(
+        "Full signature verification {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFullSignatureVerification<'info> {
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Toggle strict Ed25519 precompile checks (admin only)
+/// Off by default: a precompile entry whose key and message match the attestation proves
+/// it, since the runtime verified its signature; on, the signature bytes must match too
+pub fn set_strict_precompile_checks(
+    ctx: Context<SetStrictPrecompileChecks>,
+    enabled: bool,
+) -> Result<()> {
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.strict_precompile_checks = enabled;
+    
+    emit!(PrecompileCheckModeChanged { strict: enabled });
+    
+    msg!(
+        "Strict precompile checks {}",
+        if enabled { "enabled" } else { "disabled" }
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetStrictPrecompileChecks<'info> {
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Set the Pyth SOL/USD feed and staleness bound used for USD send fees (admin only)
+pub fn set_price_feed(
+    ctx: Context<SetPriceFeed>,
+    price_feed_id: [u8; 32],
+    max_price_age: u64,
+) -> Result<()> {
+    require!(max_price_age > 0, GatewayError::StalePrice);
+    
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.price_feed_id = price_feed_id;
+    gateway.max_price_age = max_price_age;
+    
+    emit!(PriceFeedUpdated {
+        price_feed_id,
+        max_price_age,
+    });
+    
+    msg!("Price feed updated (max age {}s)", max_price_age);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.treasury = treasury;
+    
+    emit!(TreasuryUpdated { treasury });
+    
+    msg!("Treasury set to {}", treasury);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
         has_one = authority @ GatewayError::UnauthorizedAuthority
     )]
     pub gateway: Account<'info, MessageGateway>,
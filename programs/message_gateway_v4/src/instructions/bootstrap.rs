@@ -0,0 +1,155 @@
+//! Test-only bootstrap (feature `test-env`): gateway, source-chain counter and signer
+//! registries in one transaction, so devnet and test deployments skip the ordered setup
+//! sequence. Never build a mainnet program with this feature
+
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::CounterInitialized;
+use crate::instructions::initialize::configure_gateway;
+use crate::instructions::signer_registry::configure_signer_registry;
+use crate::state::{CounterPDA, MessageGateway, SignerRegistry, SignerRegistryType};
+
+pub fn handler(
+    ctx: Context<BootstrapTestEnv>,
+    chain_id: u64,
+    source_chain_id: u64,
+    via_signers: Vec<Pubkey>,
+    chain_signers: Vec<Pubkey>,
+    project_signers: Vec<Pubkey>,
+    required_signatures: u8,
+) -> Result<()> {
+    require!(chain_id > 0 && source_chain_id > 0, GatewayError::InvalidChainId);
+    // Project signers and the project registry account come together or not at all
+    require!(
+        project_signers.is_empty() == ctx.accounts.project_registry.is_none(),
+        GatewayError::InvalidBootstrapProject
+    );
+    
+    let authority = ctx.accounts.authority.key();
+    configure_gateway(
+        &mut ctx.accounts.gateway,
+        authority,
+        chain_id,
+        DEFAULT_INSTANCE_ID,
+        ctx.bumps.gateway,
+        ctx.program_id,
+    );
+    
+    let counter = &mut ctx.accounts.counter_pda;
+    counter.source_chain_id = source_chain_id;
+    counter.highest_tx_id_seen = 0;
+    counter.bump = ctx.bumps.counter_pda;
+    emit!(CounterInitialized {
+        source_chain_id,
+        counter_pda: ctx.accounts.counter_pda.key(),
+        authority,
+        gateway: ctx.accounts.gateway.key(),
+    });
+    
+    configure_signer_registry(
+        &mut ctx.accounts.via_registry,
+        SignerRegistryType::VIA,
+        chain_id,
+        authority,
+        via_signers,
+        required_signatures,
+        ctx.bumps.via_registry,
+    )?;
+    configure_signer_registry(
+        &mut ctx.accounts.chain_registry,
+        SignerRegistryType::Chain,
+        source_chain_id,
+        authority,
+        chain_signers,
+        required_signatures,
+        ctx.bumps.chain_registry,
+    )?;
+    if let Some(project_registry) = ctx.accounts.project_registry.as_mut() {
+        configure_signer_registry(
+            project_registry,
+            SignerRegistryType::Project,
+            source_chain_id,
+            authority,
+            project_signers,
+            required_signatures,
+            ctx.bumps.project_registry.unwrap_or_default(),
+        )?;
+    }
+    
+    msg!(
+        "Test environment bootstrapped: gateway chain {}, source chain {}, project registry: {}",
+        chain_id,
+        source_chain_id,
+        ctx.accounts.project_registry.is_some()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u64, source_chain_id: u64)]
+pub struct BootstrapTestEnv<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MessageGateway::SIZE,
+        seeds = [GATEWAY_SEED, chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CounterPDA::SIZE,
+        seeds = [COUNTER_SEED, source_chain_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub counter_pda: Box<Account<'info, CounterPDA>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = SignerRegistry::space(MAX_SIGNERS_PER_REGISTRY),
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            &chain_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub via_registry: Box<Account<'info, SignerRegistry>>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = SignerRegistry::space(MAX_SIGNERS_PER_REGISTRY),
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            &source_chain_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub chain_registry: Box<Account<'info, SignerRegistry>>,
+    
+    /// Only for routes that also require project signatures
+    #[account(
+        init,
+        payer = authority,
+        space = SignerRegistry::space(MAX_SIGNERS_PER_REGISTRY),
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &SignerRegistryType::Project.discriminant()?.to_le_bytes(),
+            &source_chain_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub project_registry: Option<Box<Account<'info, SignerRegistry>>>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
\ No newline at end of file
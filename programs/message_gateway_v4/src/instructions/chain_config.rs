@@ -0,0 +1,434 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{
+    ChainAliasCleared, ChainAliasSet, ChainConfigUpdated, DestinationConfigUpdated,
+    RemoteAddressConfigUpdated, SendFeeUpdated,
+};
+use crate::state::{
+    AddressFormat, ChainAlias, ChainConfig, HashAlgorithm, IntegerEncoding, MessageGateway,
+};
+use crate::utils::address::decode_remote_address;
+use crate::utils::caip::{caip2_seed, validate_caip2};
+
+/// Initialize the configuration for a counterpart chain (admin only)
+/// The chain becomes a send_message destination with no confirmation or extra payload limits
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct InitializeChainConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ChainConfig::SIZE,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_chain_config(
+    ctx: Context<InitializeChainConfig>,
+    chain_id: u64,
+    hash_algorithm: HashAlgorithm,
+    integer_encoding: IntegerEncoding,
+) -> Result<()> {
+    require!(chain_id > 0, GatewayError::InvalidChainId);
+    
+    let chain_config = &mut ctx.accounts.chain_config;
+    chain_config.chain_id = chain_id;
+    chain_config.hash_algorithm = hash_algorithm;
+    chain_config.integer_encoding = integer_encoding;
+    chain_config.bump = ctx.bumps.chain_config;
+    chain_config.caip2_id = String::new();
+    chain_config.address_format = AddressFormat::Raw;
+    chain_config.remote_gateway = Vec::new();
+    chain_config.send_fee_usd = 0;
+    chain_config.enabled = true;
+    chain_config.min_confirmations = 0;
+    chain_config.max_confirmations = u16::MAX;
+    chain_config.max_payload_size = MAX_ON_CHAIN_DATA_SIZE as u32;
+    
+    emit!(ChainConfigUpdated {
+        chain_id,
+        hash_algorithm,
+        integer_encoding,
+    });
+    
+    msg!(
+        "Chain config initialized for chain {} ({:?}, {:?})",
+        chain_id,
+        hash_algorithm,
+        integer_encoding
+    );
+    Ok(())
+}
+
+/// Update the hash algorithm for a counterpart chain (admin only)
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct SetHashAlgorithm<'info> {
+    #[account(
+        mut,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_hash_algorithm(
+    ctx: Context<SetHashAlgorithm>,
+    chain_id: u64,
+    hash_algorithm: HashAlgorithm,
+) -> Result<()> {
+    let chain_config = &mut ctx.accounts.chain_config;
+    
+    msg!(
+        "Updating hash algorithm for chain {}: {:?} -> {:?}",
+        chain_id,
+        chain_config.hash_algorithm,
+        hash_algorithm
+    );
+    
+    chain_config.hash_algorithm = hash_algorithm;
+    
+    emit!(ChainConfigUpdated {
+        chain_id,
+        hash_algorithm,
+        integer_encoding: chain_config.integer_encoding,
+    });
+    
+    Ok(())
+}
+
+/// Update the hash pre-image integer byte order for a counterpart chain (admin only)
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct SetIntegerEncoding<'info> {
+    #[account(
+        mut,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_integer_encoding(
+    ctx: Context<SetIntegerEncoding>,
+    chain_id: u64,
+    integer_encoding: IntegerEncoding,
+) -> Result<()> {
+    let chain_config = &mut ctx.accounts.chain_config;
+    
+    msg!(
+        "Updating integer encoding for chain {}: {:?} -> {:?}",
+        chain_id,
+        chain_config.integer_encoding,
+        integer_encoding
+    );
+    
+    chain_config.integer_encoding = integer_encoding;
+    
+    emit!(ChainConfigUpdated {
+        chain_id,
+        hash_algorithm: chain_config.hash_algorithm,
+        integer_encoding,
+    });
+    
+    Ok(())
+}
+
+/// Map a CAIP-2 identifier to a chain (admin only)
+/// Creating the alias PDA fails if another chain already claimed the identifier
+#[derive(Accounts)]
+#[instruction(chain_id: u64, caip2_id: String)]
+pub struct SetChainAlias<'info> {
+    #[account(
+        mut,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ChainAlias::SIZE,
+        seeds = [CHAIN_ALIAS_SEED, caip2_seed(&caip2_id).as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub chain_alias: Account<'info, ChainAlias>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_chain_alias(
+    ctx: Context<SetChainAlias>,
+    chain_id: u64,
+    caip2_id: String,
+) -> Result<()> {
+    validate_caip2(&caip2_id)?;
+    
+    let chain_config = &mut ctx.accounts.chain_config;
+    require!(chain_config.caip2_id.is_empty(), GatewayError::Caip2AlreadySet);
+    chain_config.caip2_id = caip2_id.clone();
+    
+    let chain_alias = &mut ctx.accounts.chain_alias;
+    chain_alias.chain_id = chain_id;
+    chain_alias.caip2_id = caip2_id.clone();
+    chain_alias.bump = ctx.bumps.chain_alias;
+    
+    emit!(ChainAliasSet {
+        chain_id,
+        caip2_id: caip2_id.clone(),
+    });
+    
+    msg!("Chain {} mapped to {}", chain_id, caip2_id);
+    Ok(())
+}
+
+/// Remove the CAIP-2 mapping for a chain (admin only)
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct ClearChainAlias<'info> {
+    #[account(
+        mut,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            CHAIN_ALIAS_SEED,
+            caip2_seed(&chain_config.caip2_id).as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_alias.bump,
+        constraint = chain_alias.chain_id == chain_id @ GatewayError::Caip2Mismatch
+    )]
+    pub chain_alias: Account<'info, ChainAlias>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn clear_chain_alias(ctx: Context<ClearChainAlias>, chain_id: u64) -> Result<()> {
+    let chain_config = &mut ctx.accounts.chain_config;
+    let caip2_id = std::mem::take(&mut chain_config.caip2_id);
+    
+    emit!(ChainAliasCleared {
+        chain_id,
+        caip2_id: caip2_id.clone(),
+    });
+    
+    msg!("Chain {} unmapped from {}", chain_id, caip2_id);
+    Ok(())
+}
+
+/// Set the address family and remote gateway address for a counterpart chain (admin only)
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct SetRemoteAddressConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_remote_address_config(
+    ctx: Context<SetRemoteAddressConfig>,
+    chain_id: u64,
+    address_format: AddressFormat,
+    remote_gateway: Vec<u8>,
+) -> Result<()> {
+    // Empty remote gateway leaves it unset; otherwise store the canonical form
+    let remote_gateway = if remote_gateway.is_empty() {
+        remote_gateway
+    } else {
+        decode_remote_address(address_format, &remote_gateway)?
+    };
+    
+    let chain_config = &mut ctx.accounts.chain_config;
+    chain_config.address_format = address_format;
+    chain_config.remote_gateway = remote_gateway.clone();
+    
+    emit!(RemoteAddressConfigUpdated {
+        chain_id,
+        address_format,
+        remote_gateway,
+    });
+    
+    msg!("Address format for chain {} set to {:?}", chain_id, address_format);
+    Ok(())
+}
+
+/// Set the USD send fee for messages to a counterpart chain (admin only)
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct SetSendFee<'info> {
+    #[account(
+        mut,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_send_fee(ctx: Context<SetSendFee>, chain_id: u64, send_fee_usd: u64) -> Result<()> {
+    let chain_config = &mut ctx.accounts.chain_config;
+    
+    msg!(
+        "Updating send fee for chain {}: {} -> {} micro-USD",
+        chain_id,
+        chain_config.send_fee_usd,
+        send_fee_usd
+    );
+    
+    chain_config.send_fee_usd = send_fee_usd;
+    
+    emit!(SendFeeUpdated {
+        chain_id,
+        send_fee_usd,
+    });
+    
+    Ok(())
+}
+
+/// Enable or disable a counterpart chain as a send_message destination and set its send
+/// limits (admin only)
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct SetDestinationConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CHAIN_CONFIG_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Account<'info, ChainConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_destination_config(
+    ctx: Context<SetDestinationConfig>,
+    chain_id: u64,
+    enabled: bool,
+    min_confirmations: u16,
+    max_confirmations: u16,
+    max_payload_size: u32,
+) -> Result<()> {
+    require!(
+        min_confirmations <= max_confirmations
+            && max_payload_size as usize <= MAX_ON_CHAIN_DATA_SIZE,
+        GatewayError::InvalidDestinationConfig
+    );
+    
+    let chain_config = &mut ctx.accounts.chain_config;
+    chain_config.enabled = enabled;
+    chain_config.min_confirmations = min_confirmations;
+    chain_config.max_confirmations = max_confirmations;
+    chain_config.max_payload_size = max_payload_size;
+    
+    emit!(DestinationConfigUpdated {
+        chain_id,
+        enabled,
+        min_confirmations,
+        max_confirmations,
+        max_payload_size,
+    });
+    
+    msg!(
+        "Destination {} {}: {}..={} confirmations, payload up to {} bytes",
+        chain_id,
+        if enabled { "enabled" } else { "disabled" },
+        min_confirmations,
+        max_confirmations,
+        max_payload_size
+    );
+    Ok(())
+}
\ No newline at end of file
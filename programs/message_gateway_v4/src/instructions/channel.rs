@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{ChannelClosed, ChannelOpened};
+use crate::state::{Channel, ChannelOrdering, MessageGateway};
+
+/// Open a channel between a local application and a remote application
+/// The local application must sign; it becomes the channel owner
+#[derive(Accounts)]
+#[instruction(remote_chain_id: u64, remote_app: Vec<u8>)]
+pub struct OpenChannel<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Channel::SIZE,
+        seeds = [
+            CHANNEL_SEED,
+            remote_chain_id.to_le_bytes().as_ref(),
+            local_app.key().as_ref(),
+            Channel::remote_app_seed(&remote_app).as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub channel: Account<'info, Channel>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        constraint = gateway.system_enabled @ GatewayError::SystemDisabled
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// Local application identity (program PDA or wallet) owning the channel
+    pub local_app: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_channel(
+    ctx: Context<OpenChannel>,
+    remote_chain_id: u64,
+    remote_app: Vec<u8>,
+    ordering: ChannelOrdering,
+    require_project_registry: bool,
+) -> Result<()> {
+    require!(remote_chain_id > 0, GatewayError::InvalidChainId);
+    require!(!remote_app.is_empty(), GatewayError::EmptyRecipient);
+    require!(remote_app.len() <= MAX_SENDER_SIZE, GatewayError::SenderTooLong);
+    
+    let channel = &mut ctx.accounts.channel;
+    channel.remote_chain_id = remote_chain_id;
+    channel.local_app = ctx.accounts.local_app.key();
+    channel.remote_app = remote_app.clone();
+    channel.ordering = ordering;
+    channel.next_sequence_send = Channel::INITIAL_SEQUENCE;
+    channel.next_sequence_recv = Channel::INITIAL_SEQUENCE;
+    channel.require_project_registry = require_project_registry;
+    channel.open = true;
+    channel.bump = ctx.bumps.channel;
+    
+    emit!(ChannelOpened {
+        channel: channel.key(),
+        remote_chain_id,
+        local_app: channel.local_app,
+        remote_app,
+        ordering,
+    });
+    
+    msg!(
+        "Channel opened: remote_chain={}, local_app={}, ordering={:?}",
+        remote_chain_id,
+        channel.local_app,
+        ordering
+    );
+    Ok(())
+}
+
+/// Close a channel (local application only); closed channels reject all messages
+#[derive(Accounts)]
+pub struct CloseChannel<'info> {
+    #[account(
+        mut,
+        has_one = local_app @ GatewayError::UnauthorizedAuthority
+    )]
+    pub channel: Account<'info, Channel>,
+    
+    pub local_app: Signer<'info>,
+}
+
+pub fn close_channel(ctx: Context<CloseChannel>) -> Result<()> {
+    let channel = &mut ctx.accounts.channel;
+    require!(channel.open, GatewayError::ChannelClosed);
+    channel.open = false;
+    
+    emit!(ChannelClosed {
+        channel: channel.key(),
+        remote_chain_id: channel.remote_chain_id,
+        local_app: channel.local_app,
+    });
+    
+    msg!("Channel {} closed", channel.key());
+    Ok(())
+}
@@ -0,0 +1,882 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{
+    CompressedNftLocked, CompressedNftTreeRegistered, CompressedNftUnlocked, SendRequested,
+    WrappedCompressedNftBurned, WrappedCompressedNftMinted,
+};
+use crate::instructions::process_message::{
+    delivery_accounts, extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::instructions::token_bridge::{bridge_envelope, check_inbound_bridge_message};
+use crate::payloads::{NftMetadata, NftTransferPayload, PAYLOAD_KIND_NFT_TRANSFER};
+use crate::state::{
+    BridgePeer, ChainConfig, CompressedNftTree, MessageEnvelope, MessageGateway, MessageSignature,
+    NftCollection, RelayerPool, SignerRegistry, TxIdPDA, WrappedCompressedNft,
+};
+use crate::utils::{
+    address::validate_remote_address,
+    bubblegum::{
+        compressed_asset_id, next_leaf_nonce, BubblegumCpi, CompressedLeaf, MetadataArgs,
+        TokenProgramVersion, TokenStandard, ACCOUNT_COMPRESSION_PROGRAM_ID,
+        BUBBLEGUM_PROGRAM_ID, NOOP_PROGRAM_ID,
+    },
+};
+
+/// Register a Bubblegum tree for minting wrapped compressed NFTs (admin only)
+/// The gateway PDA must already be the tree's creator or delegate
+#[derive(Accounts)]
+pub struct RegisterCompressedNftTree<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CompressedNftTree::SIZE,
+        seeds = [COMPRESSED_NFT_TREE_SEED, merkle_tree.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub compressed_nft_tree: Account<'info, CompressedNftTree>,
+    
+    /// CHECK: Merkle tree account; must be owned by the account-compression program
+    #[account(owner = ACCOUNT_COMPRESSION_PROGRAM_ID @ GatewayError::InvalidCompressedNftTree)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_compressed_nft_tree(ctx: Context<RegisterCompressedNftTree>) -> Result<()> {
+    let compressed_nft_tree = &mut ctx.accounts.compressed_nft_tree;
+    compressed_nft_tree.merkle_tree = ctx.accounts.merkle_tree.key();
+    compressed_nft_tree.bump = ctx.bumps.compressed_nft_tree;
+    
+    emit!(CompressedNftTreeRegistered {
+        merkle_tree: compressed_nft_tree.merkle_tree,
+    });
+    
+    msg!("Compressed NFT tree {} registered", compressed_nft_tree.merkle_tree);
+    Ok(())
+}
+
+/// Transfer a Solana-native compressed NFT to the gateway and send it to a remote chain
+/// `metadata` is the leaf's full metadata: its hashes are recomputed and proven by
+/// Bubblegum, so the collection check below holds for the leaf actually moved
+/// remaining_accounts: the leaf's Merkle proof path
+#[derive(Accounts)]
+#[instruction(tx_id: u128, dest_chain_id: u64)]
+pub struct LockCompressedNft<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// NFT bridge on the destination chain
+    #[account(
+        seeds = [BRIDGE_PEER_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// Optional destination chain config validating the recipient address format
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Option<Account<'info, ChainConfig>>,
+    
+    #[account(
+        seeds = [
+            NFT_COLLECTION_SEED,
+            nft_collection.collection_mint.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = nft_collection.bump
+    )]
+    pub nft_collection: Account<'info, NftCollection>,
+    
+    pub owner: Signer<'info>,
+    
+    /// CHECK: Leaf delegate (the owner if none is set); verified by Bubblegum
+    pub leaf_delegate: UncheckedAccount<'info>,
+    
+    /// CHECK: Bubblegum TreeConfig of the tree
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = bubblegum_program.key(),
+        bump
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Merkle tree holding the leaf; verified by the account-compression program
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account-compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn lock_compressed_nft<'info>(
+    ctx: Context<'_, '_, '_, 'info, LockCompressedNft<'info>>,
+    tx_id: u128,
+    dest_chain_id: u64,
+    recipient: Vec<u8>,
+    metadata: MetadataArgs,
+    leaf: CompressedLeaf,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(tx_id > 0, GatewayError::InvalidTxId);
+    require!(
+        gateway.allows_route(gateway.chain_id, dest_chain_id),
+        GatewayError::LoopbackDisabled
+    );
+    
+    // Beneficiary must be a valid address for the destination chain family
+    if let Some(chain_config) = ctx.accounts.chain_config.as_ref() {
+        validate_remote_address(chain_config.address_format, &recipient)?;
+    }
+    
+    // Only verified members of a registered collection can leave Solana
+    let collection_mint = ctx.accounts.nft_collection.collection_mint;
+    require!(
+        metadata
+            .collection
+            .is_some_and(|collection| collection.verified && collection.key == collection_mint),
+        GatewayError::NftCollectionNotVerified
+    );
+    require!(
+        leaf.data_hash == metadata.data_hash()? && leaf.creator_hash == metadata.creator_hash(),
+        GatewayError::NftMismatch
+    );
+    
+    bubblegum_cpi(
+        &ctx.accounts.bubblegum_program,
+        &ctx.accounts.tree_config,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.log_wrapper,
+        &ctx.accounts.compression_program,
+        &ctx.accounts.system_program,
+    )
+    .transfer(
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.leaf_delegate.to_account_info(),
+        &gateway.to_account_info(),
+        &leaf,
+        ctx.remaining_accounts,
+        &[],
+    )?;
+    
+    let nft_metadata = NftMetadata {
+        name: metadata.name,
+        symbol: metadata.symbol,
+        uri: metadata.uri,
+    };
+    let merkle_tree = ctx.accounts.merkle_tree.key();
+    let asset_id = compressed_asset_id(&merkle_tree, leaf.nonce);
+    let payload = NftTransferPayload {
+        collection: collection_mint.to_bytes().to_vec(),
+        token_id: asset_id.to_bytes(),
+        metadata_uri_hash: nft_metadata.uri_hash(),
+        recipient: recipient.clone(),
+    };
+    
+    let envelope = bridge_envelope(
+        gateway,
+        &ctx.accounts.bridge_peer,
+        tx_id,
+        dest_chain_id,
+        PAYLOAD_KIND_NFT_TRANSFER,
+        payload.encode()?,
+        nft_metadata.encode()?,
+    )?;
+    
+    emit!(CompressedNftLocked {
+        tx_id,
+        asset_id,
+        merkle_tree,
+        collection_mint,
+        owner: ctx.accounts.owner.key(),
+        dest_chain_id,
+        recipient,
+    });
+    
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("Compressed NFT {} locked: tx_id={}, dest_chain={}", asset_id, tx_id, dest_chain_id);
+    Ok(())
+}
+
+/// TX2 for Solana-native compressed NFTs returning home: verifies the message like
+/// process_message, then transfers the leaf from the gateway to the beneficiary
+/// remaining_accounts: the project's extra verifier registries, the message history accounts
+/// while history is enabled, then the leaf's Merkle proof path
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct UnlockCompressedNft<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// NFT bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// CHECK: Beneficiary wallet; must match the payload recipient
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// CHECK: Bubblegum TreeConfig of the tree
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = bubblegum_program.key(),
+        bump
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Merkle tree holding the leaf; the asset id derived from it must match the payload
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    /// CHECK: Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account-compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn unlock_compressed_nft<'info>(
+    ctx: Context<'_, '_, '_, 'info, UnlockCompressedNft<'info>>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+    leaf: CompressedLeaf,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    let verifier_count = extra_verifier_count(ctx.accounts.project_registry.as_deref());
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        verifier_count,
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_NFT_TRANSFER)?;
+    let payload = NftTransferPayload::decode(&envelope.on_chain_data)?;
+    
+    let asset_id = compressed_asset_id(&ctx.accounts.merkle_tree.key(), leaf.nonce);
+    require!(payload.token_id == asset_id.to_bytes(), GatewayError::NftMismatch);
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        Pubkey::try_from(payload.recipient.as_slice())
+            .map_err(|_| GatewayError::InvalidRemoteAddress)?,
+        GatewayError::InvalidTokenRecipient
+    );
+    
+    // Proof nodes follow the extra verifier registries and the history accounts
+    let proof = delivery_accounts(&ctx.accounts.gateway, ctx.remaining_accounts, verifier_count);
+    
+    // The gateway PDA owns the leaf and signs the release
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    let gateway_info = gateway.to_account_info();
+    bubblegum_cpi(
+        &ctx.accounts.bubblegum_program,
+        &ctx.accounts.tree_config,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.log_wrapper,
+        &ctx.accounts.compression_program,
+        &ctx.accounts.system_program,
+    )
+    .transfer(
+        &gateway_info,
+        &gateway_info,
+        &ctx.accounts.recipient.to_account_info(),
+        &leaf,
+        proof,
+        &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]],
+    )?;
+    
+    emit!(CompressedNftUnlocked {
+        tx_id: envelope.tx_id,
+        asset_id,
+        recipient: ctx.accounts.recipient.key(),
+        source_chain_id: envelope.source_chain_id,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Compressed NFT {} unlocked and TxId PDA closed for tx_id={}", asset_id, envelope.tx_id);
+    Ok(())
+}
+
+/// TX2 for foreign NFTs delivered as compressed NFTs: verifies the message like
+/// process_message, then mints a leaf to the beneficiary in a registered tree
+/// The metadata relayed in `off_chain_data` is required on every arrival, since nothing
+/// but the leaf hash is stored on-chain
+/// `collection_hash` (keccak256 of the source collection) and `token_id` must match the payload
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope, signatures: Vec<MessageSignature>, collection_hash: [u8; 32], token_id: [u8; 32])]
+pub struct MintWrappedCompressedNft<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// NFT bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    #[account(
+        seeds = [COMPRESSED_NFT_TREE_SEED, merkle_tree.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = compressed_nft_tree.bump
+    )]
+    pub compressed_nft_tree: Account<'info, CompressedNftTree>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + WrappedCompressedNft::SIZE,
+        seeds = [
+            WRAPPED_CNFT_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            collection_hash.as_ref(),
+            token_id.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub wrapped_cnft: Account<'info, WrappedCompressedNft>,
+    
+    /// CHECK: Beneficiary wallet; must match the payload recipient
+    pub recipient: UncheckedAccount<'info>,
+    
+    /// CHECK: Bubblegum TreeConfig of the tree; its mint count gives the new leaf's nonce
+    #[account(
+        mut,
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = bubblegum_program.key(),
+        bump
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Registered Merkle tree the leaf is minted into
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    /// CHECK: Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account-compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn mint_wrapped_compressed_nft(
+    ctx: Context<MintWrappedCompressedNft>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+    collection_hash: [u8; 32],
+    token_id: [u8; 32],
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_NFT_TRANSFER)?;
+    let payload = NftTransferPayload::decode(&envelope.on_chain_data)?;
+    require!(
+        keccak::hash(&payload.collection).to_bytes() == collection_hash
+            && payload.token_id == token_id,
+        GatewayError::NftMismatch
+    );
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        Pubkey::try_from(payload.recipient.as_slice())
+            .map_err(|_| GatewayError::InvalidRemoteAddress)?,
+        GatewayError::InvalidTokenRecipient
+    );
+    
+    // At most one leaf exists per foreign NFT
+    require!(!ctx.accounts.wrapped_cnft.minted, GatewayError::NftAlreadyMinted);
+    
+    require!(!envelope.off_chain_data.is_empty(), GatewayError::MissingNftMetadata);
+    let nft_metadata = NftMetadata::decode(&envelope.off_chain_data)?;
+    require!(
+        nft_metadata.uri_hash() == payload.metadata_uri_hash,
+        GatewayError::MissingNftMetadata
+    );
+    
+    let merkle_tree = ctx.accounts.merkle_tree.key();
+    let asset_id = compressed_asset_id(&merkle_tree, next_leaf_nonce(&ctx.accounts.tree_config)?);
+    
+    // The gateway PDA is the tree's creator or delegate and signs the mint
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    bubblegum_cpi(
+        &ctx.accounts.bubblegum_program,
+        &ctx.accounts.tree_config,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.log_wrapper,
+        &ctx.accounts.compression_program,
+        &ctx.accounts.system_program,
+    )
+    .mint_v1(
+        &ctx.accounts.recipient.to_account_info(),
+        &ctx.accounts.relayer.to_account_info(),
+        &gateway.to_account_info(),
+        &MetadataArgs {
+            name: nft_metadata.name,
+            symbol: nft_metadata.symbol,
+            uri: nft_metadata.uri,
+            seller_fee_basis_points: 0,
+            primary_sale_happened: false,
+            is_mutable: false,
+            edition_nonce: None,
+            token_standard: Some(TokenStandard::NonFungible),
+            collection: None,
+            uses: None,
+            token_program_version: TokenProgramVersion::Original,
+            creators: Vec::new(),
+        },
+        &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]],
+    )?;
+    
+    let wrapped_cnft = &mut ctx.accounts.wrapped_cnft;
+    wrapped_cnft.source_chain_id = envelope.source_chain_id;
+    wrapped_cnft.source_collection = payload.collection;
+    wrapped_cnft.collection_hash = collection_hash;
+    wrapped_cnft.token_id = token_id;
+    wrapped_cnft.metadata_uri_hash = payload.metadata_uri_hash;
+    wrapped_cnft.merkle_tree = merkle_tree;
+    wrapped_cnft.asset_id = asset_id;
+    wrapped_cnft.minted = true;
+    wrapped_cnft.bump = ctx.bumps.wrapped_cnft;
+    
+    emit!(WrappedCompressedNftMinted {
+        tx_id: envelope.tx_id,
+        asset_id,
+        merkle_tree,
+        recipient: ctx.accounts.recipient.key(),
+        source_chain_id: envelope.source_chain_id,
+        token_id,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Wrapped compressed NFT minted and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
+
+/// Burn a wrapped compressed NFT and send the original back to its source chain
+/// remaining_accounts: the leaf's Merkle proof path
+#[derive(Accounts)]
+pub struct BurnWrappedCompressedNft<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        mut,
+        seeds = [
+            WRAPPED_CNFT_SEED,
+            wrapped_cnft.source_chain_id.to_le_bytes().as_ref(),
+            wrapped_cnft.collection_hash.as_ref(),
+            wrapped_cnft.token_id.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = wrapped_cnft.bump,
+        has_one = merkle_tree @ GatewayError::NftMismatch
+    )]
+    pub wrapped_cnft: Account<'info, WrappedCompressedNft>,
+    
+    /// NFT bridge on the source chain
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            wrapped_cnft.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// Optional source chain config validating the recipient address format
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            wrapped_cnft.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Option<Account<'info, ChainConfig>>,
+    
+    pub holder: Signer<'info>,
+    
+    /// CHECK: Leaf delegate (the holder if none is set); verified by Bubblegum
+    pub leaf_delegate: UncheckedAccount<'info>,
+    
+    /// CHECK: Bubblegum TreeConfig of the tree
+    #[account(
+        seeds = [merkle_tree.key().as_ref()],
+        seeds::program = bubblegum_program.key(),
+        bump
+    )]
+    pub tree_config: UncheckedAccount<'info>,
+    
+    /// CHECK: Merkle tree holding the leaf; must be the one recorded at mint
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: Bubblegum program
+    #[account(address = BUBBLEGUM_PROGRAM_ID)]
+    pub bubblegum_program: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub log_wrapper: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account-compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn burn_wrapped_compressed_nft<'info>(
+    ctx: Context<'_, '_, '_, 'info, BurnWrappedCompressedNft<'info>>,
+    tx_id: u128,
+    recipient: Vec<u8>,
+    leaf: CompressedLeaf,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    let wrapped_cnft = &ctx.accounts.wrapped_cnft;
+    let dest_chain_id = wrapped_cnft.source_chain_id;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(tx_id > 0, GatewayError::InvalidTxId);
+    require!(wrapped_cnft.minted, GatewayError::NftMismatch);
+    require!(
+        compressed_asset_id(&wrapped_cnft.merkle_tree, leaf.nonce) == wrapped_cnft.asset_id,
+        GatewayError::NftMismatch
+    );
+    
+    // Beneficiary must be a valid address for the source chain family
+    if let Some(chain_config) = ctx.accounts.chain_config.as_ref() {
+        validate_remote_address(chain_config.address_format, &recipient)?;
+    }
+    
+    bubblegum_cpi(
+        &ctx.accounts.bubblegum_program,
+        &ctx.accounts.tree_config,
+        &ctx.accounts.merkle_tree,
+        &ctx.accounts.log_wrapper,
+        &ctx.accounts.compression_program,
+        &ctx.accounts.system_program,
+    )
+    .burn(
+        &ctx.accounts.holder.to_account_info(),
+        &ctx.accounts.leaf_delegate.to_account_info(),
+        &leaf,
+        ctx.remaining_accounts,
+    )?;
+    
+    let payload = NftTransferPayload {
+        collection: wrapped_cnft.source_collection.clone(),
+        token_id: wrapped_cnft.token_id,
+        metadata_uri_hash: wrapped_cnft.metadata_uri_hash,
+        recipient: recipient.clone(),
+    };
+    let envelope = bridge_envelope(
+        gateway,
+        &ctx.accounts.bridge_peer,
+        tx_id,
+        dest_chain_id,
+        PAYLOAD_KIND_NFT_TRANSFER,
+        payload.encode()?,
+        Vec::new(),
+    )?;
+    
+    let asset_id = wrapped_cnft.asset_id;
+    ctx.accounts.wrapped_cnft.minted = false;
+    
+    emit!(WrappedCompressedNftBurned {
+        tx_id,
+        asset_id,
+        holder: ctx.accounts.holder.key(),
+        dest_chain_id,
+        recipient,
+    });
+    
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("Wrapped compressed NFT burned: tx_id={}, dest_chain={}", tx_id, dest_chain_id);
+    Ok(())
+}
+
+/// Bubblegum CPI context from an instruction's tree and program accounts
+fn bubblegum_cpi<'a, 'info>(
+    bubblegum_program: &'a UncheckedAccount<'info>,
+    tree_config: &'a UncheckedAccount<'info>,
+    merkle_tree: &'a UncheckedAccount<'info>,
+    log_wrapper: &'a UncheckedAccount<'info>,
+    compression_program: &'a UncheckedAccount<'info>,
+    system_program: &'a Program<'info, System>,
+) -> BubblegumCpi<'a, 'info> {
+    BubblegumCpi {
+        bubblegum_program,
+        tree_config,
+        merkle_tree,
+        log_wrapper,
+        compression_program,
+        system_program,
+    }
+}
\ No newline at end of file
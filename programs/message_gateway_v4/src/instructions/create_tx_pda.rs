@@ -3,58 +3,209 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::GatewayError;
 use crate::events::TxPdaCreated;
-use crate::state::{CounterPDA, TxIdPDA, MessageSignature};
-use crate::utils::{hash::create_message_hash_for_signing, signature::validate_signatures_tx1};
+use crate::instructions::relayer_pool::{advance_tx_id_rent, fund_tx_id_rent};
+use crate::state::{
+    ChainConfig, CounterPDA, MessageBuffer, MessageEnvelope, MessageGateway, RelayerPool, TxIdPDA,
+    MessageSignature, RentRefundKind,
+};
+use crate::utils::{
+    compact::decode_compact_message_lazy,
+    hash::{create_envelope_digest, create_message_hash_for_signing_with_payload},
+    signature::{validate_signatures_tx1, SignatureSource},
+};
 
 pub fn handler(
     ctx: Context<CreateTxPda>,
-    tx_id: u128,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    record_tx_id(
+        &ctx.accounts.gateway,
+        &mut ctx.accounts.tx_id_pda,
+        ctx.bumps.tx_id_pda,
+        &mut ctx.accounts.counter_pda,
+        ctx.bumps.counter_pda,
+        ctx.accounts.relayer.key(),
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.instructions,
+        &envelope,
+        &envelope.on_chain_data,
+        &signatures,
+    )?;
+    fund_tx_id_rent(
+        &mut ctx.accounts.tx_id_pda,
+        ctx.accounts.payer.as_ref(),
+        &ctx.accounts.relayer,
+        &ctx.accounts.system_program,
+    )
+}
+
+/// TX1 with compact-encoded message arguments (see `utils::compact`)
+pub fn handler_compact(
+    ctx: Context<CreateTxPdaCompact>,
     source_chain_id: u64,
-    dest_chain_id: u64,
-    sender: Vec<u8>,
-    recipient: Vec<u8>,
-    on_chain_data: Vec<u8>,
-    off_chain_data: Vec<u8>,
+    tx_id: u128,
+    message: Vec<u8>,
+) -> Result<()> {
+    let (envelope, signatures) = decode_compact_message_lazy(source_chain_id, tx_id, &message)?;
+    record_tx_id(
+        &ctx.accounts.gateway,
+        &mut ctx.accounts.tx_id_pda,
+        ctx.bumps.tx_id_pda,
+        &mut ctx.accounts.counter_pda,
+        ctx.bumps.counter_pda,
+        ctx.accounts.relayer.key(),
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.instructions,
+        &envelope,
+        &envelope.on_chain_data,
+        &signatures,
+    )?;
+    fund_tx_id_rent(
+        &mut ctx.accounts.tx_id_pda,
+        ctx.accounts.payer.as_ref(),
+        &ctx.accounts.relayer,
+        &ctx.accounts.system_program,
+    )
+}
+
+/// TX1 for a chunked message: the payload is taken from the completed MessageBuffer
+/// (`envelope.on_chain_data` must be empty), which is closed to the relayer
+/// TX2 then uses process_message_by_hash, which never needs the payload
+pub fn handler_buffered(
+    ctx: Context<CreateTxPdaBuffered>,
+    envelope: MessageEnvelope,
     signatures: Vec<MessageSignature>,
 ) -> Result<()> {
-    // Input validation for DOS protection
-    require!(sender.len() <= MAX_SENDER_SIZE, GatewayError::SenderTooLong);
-    require!(recipient.len() <= MAX_RECIPIENT_SIZE, GatewayError::RecipientTooLong);
-    require!(on_chain_data.len() <= MAX_ON_CHAIN_DATA_SIZE, GatewayError::OnChainDataTooLarge);
-    require!(off_chain_data.len() <= MAX_OFF_CHAIN_DATA_SIZE, GatewayError::OffChainDataTooLarge);
-    
-    // Create message hash for signature validation
-    let message_hash = create_message_hash_for_signing(
-        tx_id,
-        source_chain_id,
-        dest_chain_id,
-        &sender,
-        &recipient,
-        &on_chain_data,
-        &off_chain_data,
+    require!(envelope.on_chain_data.is_empty(), GatewayError::InvalidPayload);
+    let payload = ctx.accounts.message_buffer.payload()?;
+    record_tx_id(
+        &ctx.accounts.gateway,
+        &mut ctx.accounts.tx_id_pda,
+        ctx.bumps.tx_id_pda,
+        &mut ctx.accounts.counter_pda,
+        ctx.bumps.counter_pda,
+        ctx.accounts.relayer.key(),
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.instructions,
+        &envelope,
+        payload,
+        &signatures,
     )?;
+    fund_tx_id_rent(
+        &mut ctx.accounts.tx_id_pda,
+        ctx.accounts.payer.as_ref(),
+        &ctx.accounts.relayer,
+        &ctx.accounts.system_program,
+    )
+}
+
+/// TX1 whose TxId PDA rent is advanced from the relayer's pool: the relayer pays the
+/// rent up front and is reimbursed from the pool in the same instruction
+pub fn handler_pooled(
+    ctx: Context<CreateTxPdaPooled>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    record_tx_id(
+        &ctx.accounts.gateway,
+        &mut ctx.accounts.tx_id_pda,
+        ctx.bumps.tx_id_pda,
+        &mut ctx.accounts.counter_pda,
+        ctx.bumps.counter_pda,
+        ctx.accounts.relayer.key(),
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.instructions,
+        &envelope,
+        &envelope.on_chain_data,
+        &signatures,
+    )?;
+    advance_tx_id_rent(
+        &mut ctx.accounts.tx_id_pda,
+        &mut ctx.accounts.relayer_pool,
+        &ctx.accounts.relayer.to_account_info(),
+    )
+}
+
+/// Verify TX1 signatures and record the TxId PDA and source chain counter
+/// `on_chain_data` is the payload the signatures cover
+#[allow(clippy::too_many_arguments)]
+fn record_tx_id(
+    gateway: &MessageGateway,
+    tx_pda: &mut TxIdPDA,
+    tx_pda_bump: u8,
+    counter: &mut CounterPDA,
+    counter_bump: u8,
+    relayer: Pubkey,
+    chain_config: Option<&ChainConfig>,
+    instructions: &AccountInfo,
+    envelope: &MessageEnvelope,
+    on_chain_data: &[u8],
+    signatures: &dyn SignatureSource,
+) -> Result<()> {
+    // Envelope version and DOS size validation
+    envelope.validate()?;
     
-    // TX1 basic signature validation (cryptographic verification only)
-    validate_signatures_tx1(&signatures, &message_hash, &ctx.accounts.instructions)?;
-    // Initialize TxId PDA (proves this tx_id hasn't been processed)
-    let tx_pda = &mut ctx.accounts.tx_id_pda;
-    tx_pda.tx_id = tx_id;
-    tx_pda.bump = ctx.bumps.tx_id_pda;
+    let tx_id = envelope.tx_id;
+    let source_chain_id = envelope.source_chain_id;
+    // 0 marks a counter that is not initialized yet, and is no destination either
+    envelope.validate_chain_ids()?;
+    
+    // A paused gateway or a message for another chain would only lock the relayer's rent
+    // until TX2 fails the same checks
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(
+        envelope.dest_chain_id == gateway.chain_id,
+        GatewayError::InvalidDestChain
+    );
+    require!(
+        gateway.allows_route(source_chain_id, envelope.dest_chain_id),
+        GatewayError::LoopbackDisabled
+    );
     
-    // Initialize counter if new, otherwise it already exists
-    let counter = &mut ctx.accounts.counter_pda;
+    // The TxId PDA is created if needed so an open one fails with a gateway error code
+    require!(!tx_pda.is_recorded(), GatewayError::AlreadyProcessed);
+    
+    // Initialize counter on first use, otherwise it must be this source chain's
     if counter.source_chain_id == 0 {
-        // New counter - initialize
         counter.source_chain_id = source_chain_id;
-        counter.bump = ctx.bumps.counter_pda;
+        counter.bump = counter_bump;
         counter.highest_tx_id_seen = 0;
     }
+    counter.check_source_chain(source_chain_id)?;
+    
+    // Cheap replay pre-check before any signature work: a tx_id that recently passed TX1
+    // (even if TX2 already closed its PDA) is rejected; a false positive clears once the
+    // filter rotates
+    require!(!counter.maybe_recent(tx_id), GatewayError::RecentTxIdReplay);
+    
+    // Create message hash for signature validation
+    // (domain is the destination gateway's chain, checked above to be this gateway's)
+    let hash_scheme = ChainConfig::route_hash_scheme(chain_config);
+    let message_hash = create_message_hash_for_signing_with_payload(
+        hash_scheme,
+        envelope.dest_chain_id,
+        gateway.instance_id,
+        envelope,
+        on_chain_data,
+    )?;
+    
+    // TX1 basic signature validation (cryptographic verification only)
+    let ed25519_offsets = validate_signatures_tx1(signatures, &message_hash, instructions)?;
+    // Initialize TxId PDA (proves this tx_id hasn't been processed)
+    tx_pda.message_hash = message_hash;
+    tx_pda.envelope_digest = create_envelope_digest(envelope)?;
+    tx_pda.ed25519_offsets = ed25519_offsets;
+    tx_pda.rent_refund = relayer;
+    tx_pda.rent_refund_kind = RentRefundKind::Relayer;
+    tx_pda.expires_at = TxIdPDA::expiry(Clock::get()?.unix_timestamp, envelope.deadline);
+    tx_pda.bump = tx_pda_bump;
     
     // Update Counter PDA with highest tx_id seen
-    let counter = &mut ctx.accounts.counter_pda;
     if tx_id > counter.highest_tx_id_seen {
         counter.highest_tx_id_seen = tx_id;
     }
+    counter.record_recent(tx_id);
     
     emit!(TxPdaCreated {
         tx_id,
@@ -66,33 +217,271 @@ pub fn handler(
 }
 
 #[derive(Accounts)]
-#[instruction(tx_id: u128, source_chain_id: u64, dest_chain_id: u64, sender: Vec<u8>, recipient: Vec<u8>, on_chain_data: Vec<u8>, off_chain_data: Vec<u8>, signatures: Vec<MessageSignature>)]
+#[instruction(envelope: MessageEnvelope)]
 pub struct CreateTxPda<'info> {
     #[account(
-        init,
+        init_if_needed,
+        payer = relayer,
+        space = 8 + TxIdPDA::SIZE,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Destination gateway: TX1 is refused while it is paused or for another chain
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + CounterPDA::SIZE,
+        seeds = [
+            COUNTER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub counter_pda: Account<'info, CounterPDA>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// Optional wallet funding the TxId PDA rent in place of the relayer: the relayer
+    /// fronts the rent and is reimbursed in the same instruction, and closing the PDA
+    /// refunds this wallet
+    #[account(mut)]
+    pub payer: Option<Signer<'info>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(source_chain_id: u64, tx_id: u128)]
+pub struct CreateTxPdaCompact<'info> {
+    #[account(
+        init_if_needed,
         payer = relayer,
         space = 8 + TxIdPDA::SIZE,
         seeds = [
             TX_SEED,
             source_chain_id.to_le_bytes().as_ref(),
-            &tx_id.to_le_bytes()
+            &tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
         ],
         bump
     )]
     pub tx_id_pda: Account<'info, TxIdPDA>,
     
+    /// Destination gateway: TX1 is refused while it is paused or for another chain
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
     #[account(
         init_if_needed,
         payer = relayer,
         space = 8 + CounterPDA::SIZE,
         seeds = [
             COUNTER_SEED,
-            source_chain_id.to_le_bytes().as_ref()
+            source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
         ],
         bump
     )]
     pub counter_pda: Account<'info, CounterPDA>,
     
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, source_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// Optional wallet funding the TxId PDA rent in place of the relayer: the relayer
+    /// fronts the rent and is reimbursed in the same instruction, and closing the PDA
+    /// refunds this wallet
+    #[account(mut)]
+    pub payer: Option<Signer<'info>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts of `create_tx_pda` plus the completed buffer holding the payload
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct CreateTxPdaBuffered<'info> {
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + TxIdPDA::SIZE,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Destination gateway: TX1 is refused while it is paused or for another chain
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + CounterPDA::SIZE,
+        seeds = [
+            COUNTER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub counter_pda: Account<'info, CounterPDA>,
+    
+    /// Buffer holding the message payload; closed once its signatures are verified
+    #[account(
+        mut,
+        seeds = [
+            MESSAGE_BUFFER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = message_buffer.bump,
+        has_one = relayer @ GatewayError::UnauthorizedAuthority,
+        close = relayer
+    )]
+    pub message_buffer: Account<'info, MessageBuffer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// Optional wallet funding the TxId PDA rent in place of the relayer: the relayer
+    /// fronts the rent and is reimbursed in the same instruction, and closing the PDA
+    /// refunds this wallet
+    #[account(mut)]
+    pub payer: Option<Signer<'info>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts of `create_tx_pda` plus the relayer's rent pool
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct CreateTxPdaPooled<'info> {
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + TxIdPDA::SIZE,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Destination gateway: TX1 is refused while it is paused or for another chain
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + CounterPDA::SIZE,
+        seeds = [
+            COUNTER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub counter_pda: Account<'info, CounterPDA>,
+    
+    /// Pool reimbursing the TxId PDA rent; credited back when the PDA closes
+    #[account(
+        mut,
+        seeds = [RELAYER_POOL_SEED, relayer.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = relayer_pool.bump,
+        has_one = relayer @ GatewayError::UnauthorizedAuthority
+    )]
+    pub relayer_pool: Account<'info, RelayerPool>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
     #[account(mut)]
     pub relayer: Signer<'info>,
     
@@ -0,0 +1,571 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{
+    CustodyExtensionAttached, CustodyExtensionDetached, CustodyLocked, CustodyReleased, SendRequested,
+};
+use crate::instructions::process_message::{
+    delivery_accounts, extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::instructions::token_bridge::{
+    bridge_envelope, check_inbound_bridge_message, enforce_blocklist, enforce_rate_limit,
+};
+use crate::payloads::{TokenTransferPayload, PAYLOAD_KIND_TOKEN_TRANSFER};
+use crate::state::{
+    BridgePeer, ChainConfig, CustodyExtension, MessageEnvelope, MessageGateway, MessageSignature,
+    RelayerPool, SignerRegistry, SignerRegistryType, TokenConfig, TokenKind, TxIdPDA,
+};
+use crate::utils::{
+    address::validate_remote_address,
+    custody::{CustodyExtensionCpi, CustodyLockArgs, CustodyReleaseArgs},
+    token::received_amount,
+};
+
+/// Attach a project's escrow/mint program as the custody of a native mint
+/// Signed by the project registry's authority, which must also manage the token
+/// (gateway authority or the token's governance); the built-in escrow must be empty
+#[derive(Accounts)]
+pub struct AttachCustodyExtension<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, token_config.mint.as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.can_manage(&authority.key(), &gateway.authority)
+            @ GatewayError::UnauthorizedAuthority
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CustodyExtension::SIZE,
+        seeds = [CUSTODY_EXTENSION_SEED, token_config.mint.as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub custody_extension: Account<'info, CustodyExtension>,
+    
+    /// Project signer registry taking ownership of the mint's custody
+    #[account(
+        has_one = authority @ GatewayError::UnauthorizedAuthority,
+        constraint = project_registry.registry_type == SignerRegistryType::Project
+            @ GatewayError::InvalidSignerRegistryType
+    )]
+    pub project_registry: Account<'info, SignerRegistry>,
+    
+    /// CHECK: Project extension program; only required to be executable
+    #[account(executable)]
+    pub extension_program: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn attach_custody_extension(ctx: Context<AttachCustodyExtension>) -> Result<()> {
+    let token_config = &mut ctx.accounts.token_config;
+    require!(token_config.kind == TokenKind::Native, GatewayError::TokenKindMismatch);
+    require!(token_config.escrowed_amount == 0, GatewayError::EscrowNotEmpty);
+    token_config.kind = TokenKind::ProjectCustody;
+    
+    let custody_extension = &mut ctx.accounts.custody_extension;
+    custody_extension.mint = token_config.mint;
+    custody_extension.project_registry = ctx.accounts.project_registry.key();
+    custody_extension.program = ctx.accounts.extension_program.key();
+    custody_extension.bump = ctx.bumps.custody_extension;
+    custody_extension.authority_bump = Pubkey::find_program_address(
+        &[CUSTODY_AUTHORITY_SEED, custody_extension.mint.as_ref(), &ctx.accounts.gateway.instance_seed()],
+        ctx.program_id,
+    )
+    .1;
+    
+    emit!(CustodyExtensionAttached {
+        mint: custody_extension.mint,
+        project_registry: custody_extension.project_registry,
+        program: custody_extension.program,
+        attached_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!(
+        "Custody extension {} attached to mint {}",
+        custody_extension.program,
+        custody_extension.mint
+    );
+    Ok(())
+}
+
+/// Detach a mint's custody extension and return it to the built-in escrow
+/// (gateway authority or the token's governance)
+/// Tokens held by the extension stay there; the gateway escrow starts empty
+#[derive(Accounts)]
+pub struct DetachCustodyExtension<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, token_config.mint.as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.can_manage(&authority.key(), &gateway.authority)
+            @ GatewayError::UnauthorizedAuthority
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [CUSTODY_EXTENSION_SEED, token_config.mint.as_ref(), gateway.instance_seed().as_ref()],
+        bump = custody_extension.bump
+    )]
+    pub custody_extension: Account<'info, CustodyExtension>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn detach_custody_extension(ctx: Context<DetachCustodyExtension>) -> Result<()> {
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.kind = TokenKind::Native;
+    
+    emit!(CustodyExtensionDetached {
+        mint: token_config.mint,
+        program: ctx.accounts.custody_extension.program,
+        detached_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Custody extension detached from mint {}", token_config.mint);
+    Ok(())
+}
+
+/// Lock tokens in a project custody extension and send the transfer message
+/// The gateway calls the extension's `custody_lock` (signed by the mint's custody authority
+/// PDA) and checks the depositor's balance dropped by exactly `amount`
+/// remaining_accounts: the extension's own accounts
+#[derive(Accounts)]
+#[instruction(tx_id: u128, dest_chain_id: u64)]
+pub struct DepositViaCustodyExtension<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// Token bridge on the destination chain
+    #[account(
+        seeds = [BRIDGE_PEER_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// Optional destination chain config validating the recipient address format
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Option<Account<'info, ChainConfig>>,
+    
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        seeds = [CUSTODY_EXTENSION_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = custody_extension.bump
+    )]
+    pub custody_extension: Account<'info, CustodyExtension>,
+    
+    /// CHECK: Signer-only PDA proving custody calls come from the gateway
+    #[account(
+        seeds = [CUSTODY_AUTHORITY_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = custody_extension.authority_bump
+    )]
+    pub custody_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub depositor: Signer<'info>,
+    
+    /// CHECK: Extension program attached to the mint
+    #[account(
+        executable,
+        address = custody_extension.program @ GatewayError::InvalidCustodyExtension
+    )]
+    pub extension_program: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_via_custody_extension<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositViaCustodyExtension<'info>>,
+    tx_id: u128,
+    dest_chain_id: u64,
+    recipient: Vec<u8>,
+    amount: u64,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(tx_id > 0, GatewayError::InvalidTxId);
+    require!(
+        gateway.allows_route(gateway.chain_id, dest_chain_id),
+        GatewayError::LoopbackDisabled
+    );
+    
+    // Beneficiary must be a valid address for the destination chain family
+    if let Some(chain_config) = ctx.accounts.chain_config.as_ref() {
+        validate_remote_address(chain_config.address_format, &recipient)?;
+    }
+    
+    enforce_blocklist(
+        &ctx.accounts.blocklist,
+        &[
+            ctx.accounts.depositor_token_account.owner,
+            ctx.accounts.depositor.key(),
+            ctx.accounts.mint.key(),
+        ],
+    )?;
+    let token_config = &ctx.accounts.token_config;
+    token_config.check_outbound(TokenKind::ProjectCustody, amount)?;
+    
+    // The extension locks exact amounts, so the amount must already be representable
+    let decimals = ctx.accounts.mint.decimals;
+    let wire = token_config.to_wire_amount(amount, decimals)?;
+    
+    let mint = ctx.accounts.mint.key();
+    let authority_bump = [ctx.accounts.custody_extension.authority_bump];
+    let instance_seed = ctx.accounts.gateway.instance_seed();
+    let authority_seeds: &[&[u8]] = &[CUSTODY_AUTHORITY_SEED, mint.as_ref(), &instance_seed, &authority_bump];
+    
+    let balance_before = ctx.accounts.depositor_token_account.amount;
+    CustodyExtensionCpi {
+        program: &ctx.accounts.extension_program.to_account_info(),
+        custody_authority: &ctx.accounts.custody_authority.to_account_info(),
+        mint: &ctx.accounts.mint.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+    }
+    .lock(
+        &ctx.accounts.depositor_token_account.to_account_info(),
+        &ctx.accounts.depositor.to_account_info(),
+        CustodyLockArgs { tx_id, dest_chain_id, amount },
+        ctx.remaining_accounts,
+        &[authority_seeds],
+    )?;
+    
+    ctx.accounts.depositor_token_account.reload()?;
+    let debited = balance_before.checked_sub(ctx.accounts.depositor_token_account.amount);
+    require!(debited == Some(amount), GatewayError::CustodyTransferMismatch);
+    
+    let depositor = ctx.accounts.depositor_token_account.owner;
+    let payload = TokenTransferPayload {
+        token: mint.to_bytes(),
+        amount: wire,
+        sender: depositor.to_bytes().to_vec(),
+        recipient: recipient.clone(),
+    };
+    
+    let envelope = bridge_envelope(
+        gateway,
+        &ctx.accounts.bridge_peer,
+        tx_id,
+        dest_chain_id,
+        PAYLOAD_KIND_TOKEN_TRANSFER,
+        payload.encode()?,
+        Vec::new(),
+    )?;
+    
+    emit!(CustodyLocked {
+        tx_id,
+        mint,
+        program: ctx.accounts.custody_extension.program,
+        depositor,
+        amount,
+        dest_chain_id,
+        recipient,
+    });
+    
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("Custody lock: tx_id={}, amount={}, dest_chain={}", tx_id, amount, dest_chain_id);
+    Ok(())
+}
+
+/// TX2 for project-custody mints: verifies the message like release_tokens, with the
+/// extension's project registry as a mandatory signature layer, then calls the extension's
+/// `custody_release` and checks the recipient was credited exactly the payload amount
+/// remaining_accounts: the project's extra verifier registries, the message history accounts
+/// while history is enabled, then the extension's accounts
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct ReleaseViaCustodyExtension<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Project signer registry that owns the extension; always required
+    #[account(address = custody_extension.project_registry @ GatewayError::InvalidCustodyExtension)]
+    pub project_registry: Account<'info, SignerRegistry>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        seeds = [CUSTODY_EXTENSION_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = custody_extension.bump
+    )]
+    pub custody_extension: Account<'info, CustodyExtension>,
+    
+    /// CHECK: Signer-only PDA proving custody calls come from the gateway
+    #[account(
+        seeds = [CUSTODY_AUTHORITY_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = custody_extension.authority_bump
+    )]
+    pub custody_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    /// CHECK: Mint's TokenRateLimit PDA; may be uninitialized (no cap), validated in enforce_rate_limit
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub rate_limit: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Extension program attached to the mint
+    #[account(
+        executable,
+        address = custody_extension.program @ GatewayError::InvalidCustodyExtension
+    )]
+    pub extension_program: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn release_via_custody_extension<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReleaseViaCustodyExtension<'info>>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        Some(&*ctx.accounts.project_registry),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    let verifier_count = extra_verifier_count(Some(&*ctx.accounts.project_registry));
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        verifier_count,
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_TOKEN_TRANSFER)?;
+    let payload = TokenTransferPayload::decode(&envelope.on_chain_data)?;
+    let mint = ctx.accounts.mint.key();
+    require!(payload.token == mint.to_bytes(), GatewayError::TokenMismatch);
+    
+    let recipient = payload.recipient_pubkey()?;
+    require_keys_eq!(
+        ctx.accounts.recipient_token_account.owner,
+        recipient,
+        GatewayError::InvalidTokenRecipient
+    );
+    enforce_blocklist(&ctx.accounts.blocklist, &[recipient, mint])?;
+    
+    let token_config = &ctx.accounts.token_config;
+    token_config.check_path(TokenKind::ProjectCustody)?;
+    let amount = token_config.from_wire_amount(payload.amount, ctx.accounts.mint.decimals)?;
+    enforce_rate_limit(&ctx.accounts.rate_limit, amount)?;
+    
+    // Extension accounts follow the extra verifier registries and the history accounts
+    let extension_accounts =
+        delivery_accounts(&ctx.accounts.gateway, ctx.remaining_accounts, verifier_count);
+    
+    let authority_bump = [ctx.accounts.custody_extension.authority_bump];
+    let instance_seed = ctx.accounts.gateway.instance_seed();
+    let authority_seeds: &[&[u8]] = &[CUSTODY_AUTHORITY_SEED, mint.as_ref(), &instance_seed, &authority_bump];
+    
+    let balance_before = ctx.accounts.recipient_token_account.amount;
+    CustodyExtensionCpi {
+        program: &ctx.accounts.extension_program.to_account_info(),
+        custody_authority: &ctx.accounts.custody_authority.to_account_info(),
+        mint: &ctx.accounts.mint.to_account_info(),
+        token_program: &ctx.accounts.token_program.to_account_info(),
+    }
+    .release(
+        &ctx.accounts.recipient_token_account.to_account_info(),
+        CustodyReleaseArgs {
+            tx_id: envelope.tx_id,
+            source_chain_id: envelope.source_chain_id,
+            amount,
+        },
+        extension_accounts,
+        &[authority_seeds],
+    )?;
+    
+    ctx.accounts.recipient_token_account.reload()?;
+    let received = received_amount(balance_before, ctx.accounts.recipient_token_account.amount)?;
+    require!(received == amount, GatewayError::CustodyTransferMismatch);
+    
+    emit!(CustodyReleased {
+        tx_id: envelope.tx_id,
+        source_chain_id: envelope.source_chain_id,
+        mint,
+        program: ctx.accounts.custody_extension.program,
+        recipient,
+        amount,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Custody release completed and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,254 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::MessageDelivered;
+use crate::instructions::process_message::{
+    delivery_accounts, extra_verifier_count, route_processed_message, verify_inbound_message,
+    DeliveryTail,
+};
+use crate::payloads::{ViaMessage, PAYLOAD_KIND_PROGRAM_DELIVERY};
+use crate::state::{
+    ChainConfig, Channel, FeeConfig, MessageEnvelope, MessageGateway, MessageSignature,
+    RelayerPool, SignerRegistry, TxIdPDA,
+};
+
+/// TX2 for program deliveries: verifies the message like process_message, then invokes
+/// `on_message_received` on the recipient program with the recipient's delivery authority
+/// PDA as signer
+/// remaining_accounts: the project's extra verifier registries, the message history
+/// accounts while history is enabled, then the recipient's accounts (passed through)
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct DeliverMessage<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Box<Account<'info, TxIdPDA>>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Box<Account<'info, SignerRegistry>>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Box<Account<'info, SignerRegistry>>,
+    
+    /// Channel the message is routed through (required when envelope.sequence != 0)
+    #[account(
+        mut,
+        seeds = [
+            CHANNEL_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            envelope.recipient.as_slice(),
+            Channel::remote_app_seed(&envelope.sender).as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = channel.bump
+    )]
+    pub channel: Option<Account<'info, Channel>>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Box<Account<'info, SignerRegistry>>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// Source chain fee config for the relayer refund, with the fee vault below
+    #[account(
+        seeds = [
+            FEE_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = fee_config.bump
+    )]
+    pub fee_config: Option<Box<Account<'info, FeeConfig>>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<SystemAccount<'info>>,
+    
+    /// CHECK: Program the message is addressed to, bound to `envelope.recipient`
+    #[account(
+        executable,
+        constraint = envelope.recipient.as_slice() == recipient_program.key().as_ref()
+            @ GatewayError::InvalidRecipientProgram
+    )]
+    pub recipient_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Signs the delivery CPI; scoped to the recipient so one recipient cannot
+    /// forward it as a delivery to another
+    #[account(
+        seeds = [DELIVERY_AUTHORITY_SEED, recipient_program.key().as_ref()],
+        bump
+    )]
+    pub delivery_authority: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deliver_message<'info>(
+    ctx: Context<'_, '_, '_, 'info, DeliverMessage<'info>>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    let project_registry = ctx.accounts.project_registry.as_deref().map(|registry| &**registry);
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        project_registry,
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    require!(
+        envelope.message_type == PAYLOAD_KIND_PROGRAM_DELIVERY,
+        GatewayError::InvalidPayload
+    );
+    
+    let verifier_count = extra_verifier_count(project_registry);
+    let relayer_refund = DeliveryTail {
+        gateway: &ctx.accounts.gateway,
+        tx_id_pda: &ctx.accounts.tx_id_pda,
+        relayer_pool: ctx.accounts.relayer_pool.as_deref_mut(),
+        payer: ctx.accounts.payer.as_deref(),
+        fee_config: ctx.accounts.fee_config.as_deref().map(|config| &**config),
+        fee_vault: ctx.accounts.fee_vault.as_ref(),
+        fee_vault_bump: ctx.bumps.fee_vault,
+        relayer: &ctx.accounts.relayer,
+        system_program: &ctx.accounts.system_program,
+    }
+    .finalize_delivery(
+        ctx.remaining_accounts,
+        verifier_count,
+        &envelope,
+        envelope.on_chain_data.len(),
+    )?;
+    
+    route_processed_message(
+        ctx.accounts.channel.as_deref_mut(),
+        project_registry.is_some(),
+        ctx.accounts.relayer.key(),
+        relayer_refund,
+        &envelope,
+    )?;
+    
+    // Recipient accounts follow the extra verifier registries and the history accounts
+    let recipient_accounts =
+        delivery_accounts(&ctx.accounts.gateway, ctx.remaining_accounts, verifier_count);
+    
+    let authority_info = ctx.accounts.delivery_authority.to_account_info();
+    let mut account_metas = vec![AccountMeta::new_readonly(authority_info.key(), true)];
+    let mut account_infos = vec![authority_info];
+    for account in recipient_accounts {
+        account_metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(ctx.accounts.recipient_program.to_account_info());
+    
+    let recipient_program = ctx.accounts.recipient_program.key();
+    let message = ViaMessage {
+        tx_id: envelope.tx_id,
+        source_chain_id: envelope.source_chain_id,
+        sender: envelope.sender,
+        on_chain_data: envelope.on_chain_data,
+    };
+    let instruction = Instruction {
+        program_id: recipient_program,
+        accounts: account_metas,
+        data: message
+            .instruction_data()
+            .map_err(|_| GatewayError::InvalidPayload)?,
+    };
+    
+    invoke_signed(
+        &instruction,
+        &account_infos,
+        &[&[
+            DELIVERY_AUTHORITY_SEED,
+            recipient_program.as_ref(),
+            &[ctx.bumps.delivery_authority],
+        ]],
+    )?;
+    
+    emit!(MessageDelivered {
+        tx_id: message.tx_id,
+        source_chain_id: message.source_chain_id,
+        recipient_program,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Message delivered to {} for tx_id={}", recipient_program, message.tx_id);
+    Ok(())
+}
\ No newline at end of file
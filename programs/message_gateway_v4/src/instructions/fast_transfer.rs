@@ -0,0 +1,546 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{FastFillRevoked, FastFillSettled, LiquidityBondUpdated, TransferFronted};
+use crate::instructions::token_bridge::{
+    decode_inbound_transfer, enforce_blocklist, expected_payload_token,
+};
+use crate::state::{
+    BridgePeer, ChainConfig, FastFill, FastFillStatus, LiquidityProvider, MessageEnvelope,
+    MessageGateway, TokenConfig, TxIdPDA, WrappedAsset,
+};
+use crate::utils::{
+    hash::create_instance_message_hash_for_signing, token::transfer_checked_with_hook,
+};
+
+/// Add lamports to a liquidity provider's bond, registering the LP on first use
+#[derive(Accounts)]
+pub struct DepositLpBond<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + LiquidityProvider::SIZE,
+        seeds = [LIQUIDITY_PROVIDER_SEED, owner.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deposit_lp_bond(ctx: Context<DepositLpBond>, amount: u64) -> Result<()> {
+    require!(amount > 0, GatewayError::InvalidAmount);
+    
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.liquidity_provider.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    liquidity_provider.owner = ctx.accounts.owner.key();
+    liquidity_provider.bond = liquidity_provider
+        .bond
+        .checked_add(amount)
+        .ok_or(GatewayError::InvalidAmount)?;
+    liquidity_provider.bump = ctx.bumps.liquidity_provider;
+    
+    emit!(LiquidityBondUpdated {
+        liquidity_provider: liquidity_provider.owner,
+        bond: liquidity_provider.bond,
+    });
+    
+    msg!("LP {} bond: {}", liquidity_provider.owner, liquidity_provider.bond);
+    Ok(())
+}
+
+/// Withdraw lamports from a liquidity provider's bond (LP only, no open fills)
+#[derive(Accounts)]
+pub struct WithdrawLpBond<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_PROVIDER_SEED, owner.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = liquidity_provider.bump,
+        has_one = owner @ GatewayError::UnauthorizedAuthority
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn withdraw_lp_bond(ctx: Context<WithdrawLpBond>, amount: u64) -> Result<()> {
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    require!(liquidity_provider.open_fills == 0, GatewayError::OpenFastFills);
+    require!(
+        amount > 0 && amount <= liquidity_provider.bond,
+        GatewayError::InvalidAmount
+    );
+    
+    liquidity_provider.bond -= amount;
+    move_bond_lamports(
+        &liquidity_provider.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        amount,
+    )?;
+    
+    emit!(LiquidityBondUpdated {
+        liquidity_provider: liquidity_provider.owner,
+        bond: liquidity_provider.bond,
+    });
+    
+    msg!("LP {} bond: {}", liquidity_provider.owner, liquidity_provider.bond);
+    Ok(())
+}
+
+/// Front an inbound token transfer after TX1: the LP pays the recipient from its own
+/// tokens, minus a fee, and is repaid the full amount when TX2 lands
+/// The envelope must hash to the signing hash TX1 verified; the LP carries the risk that
+/// TX2 never validates, and its bond that the authority revokes the message
+/// remaining_accounts: transfer-hook accounts for Token-2022 mints with a hook
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct FrontTransfer<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA proving TX1 for the message
+    #[account(
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    /// Registry entry mapping the payload token to `mint`; required for wrapped mints
+    #[account(
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            wrapped_asset.source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = wrapped_asset.bump,
+        has_one = mint @ GatewayError::TokenMismatch
+    )]
+    pub wrapped_asset: Option<Account<'info, WrappedAsset>>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_PROVIDER_SEED, lp.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = liquidity_provider.bump
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    
+    #[account(
+        init,
+        payer = lp,
+        space = 8 + FastFill::SIZE,
+        seeds = [
+            FAST_FILL_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub fast_fill: Account<'info, FastFill>,
+    
+    /// LP token account funding the fill and repaid by TX2
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = lp,
+        token::token_program = token_program
+    )]
+    pub lp_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Beneficiary token account; its owner must match the payload recipient
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub lp: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn front_transfer<'info>(
+    ctx: Context<'_, '_, '_, 'info, FrontTransfer<'info>>,
+    envelope: MessageEnvelope,
+    fee: u64,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(
+        envelope.dest_chain_id == gateway.chain_id,
+        GatewayError::InvalidDestChain
+    );
+    envelope.validate()?;
+    
+    // Only the envelope whose signatures TX1 verified may be fronted; its payload is
+    // bound by the signing hash, so a forged amount or recipient cannot be paid out
+    let chain_config = ChainConfig::load_route(&ctx.accounts.chain_config)?;
+    let message_hash = create_instance_message_hash_for_signing(
+        ChainConfig::route_hash_scheme(chain_config.as_ref()),
+        gateway.chain_id,
+        gateway.instance_id,
+        &envelope,
+    )?;
+    require!(
+        message_hash == ctx.accounts.tx_id_pda.message_hash,
+        GatewayError::InvalidMessageHash
+    );
+    require!(
+        ctx.accounts.liquidity_provider.bond >= MIN_LP_BOND_LAMPORTS,
+        GatewayError::InsufficientBond
+    );
+    
+    let payload = decode_inbound_transfer(&envelope, &ctx.accounts.bridge_peer)?;
+    let token_config = &ctx.accounts.token_config;
+    require!(
+        payload.token == expected_payload_token(token_config, ctx.accounts.wrapped_asset.as_deref())?,
+        GatewayError::TokenMismatch
+    );
+    token_config.check_path(token_config.kind)?;
+    require!(
+        !token_config.settings.claim_delivery,
+        GatewayError::ClaimDeliveryRequired
+    );
+    
+    let recipient = payload.recipient_pubkey()?;
+    enforce_blocklist(
+        &ctx.accounts.blocklist,
+        &[recipient, ctx.accounts.lp.key(), ctx.accounts.mint.key()],
+    )?;
+    require_keys_eq!(
+        ctx.accounts.recipient_token_account.owner,
+        recipient,
+        GatewayError::InvalidTokenRecipient
+    );
+    
+    let decimals = ctx.accounts.mint.decimals;
+    let amount = token_config.from_wire_amount(payload.amount, decimals)?;
+    
+    // TX2 withholds any release-side bridge fee from the LP's repayment, so the
+    // recipient gets what release_tokens would have delivered, minus the LP fee
+    let delivered = amount - token_config.release_fee(amount);
+    let max_fee = (delivered as u128 * MAX_FAST_FILL_FEE_BPS as u128 / BPS_DENOMINATOR as u128) as u64;
+    require!(fee <= max_fee, GatewayError::FastFillFeeTooHigh);
+    
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.lp_token_account.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.recipient_token_account.to_account_info(),
+        &ctx.accounts.lp.to_account_info(),
+        ctx.remaining_accounts,
+        &[],
+        delivered - fee,
+        decimals,
+    )?;
+    
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    liquidity_provider.open_fills = liquidity_provider
+        .open_fills
+        .checked_add(1)
+        .ok_or(GatewayError::InvalidAmount)?;
+    
+    let fast_fill = &mut ctx.accounts.fast_fill;
+    fast_fill.source_chain_id = envelope.source_chain_id;
+    fast_fill.tx_id = envelope.tx_id;
+    fast_fill.envelope_digest = FastFill::envelope_digest(&envelope)?;
+    fast_fill.liquidity_provider = liquidity_provider.owner;
+    fast_fill.repayment_account = ctx.accounts.lp_token_account.key();
+    fast_fill.mint = ctx.accounts.mint.key();
+    fast_fill.recipient = recipient;
+    fast_fill.amount = amount;
+    fast_fill.fee = fee;
+    fast_fill.status = FastFillStatus::Pending;
+    fast_fill.filled_at = Clock::get()?.unix_timestamp;
+    fast_fill.bump = ctx.bumps.fast_fill;
+    
+    emit!(TransferFronted {
+        tx_id: envelope.tx_id,
+        source_chain_id: envelope.source_chain_id,
+        liquidity_provider: fast_fill.liquidity_provider,
+        mint: fast_fill.mint,
+        recipient,
+        amount,
+        fee,
+    });
+    
+    msg!("Transfer fronted for tx_id={}, amount={}, fee={}", envelope.tx_id, amount, fee);
+    Ok(())
+}
+
+/// Revoke a fronted message and slash the LP's bond (admin only)
+/// A revoked fill also blocks the message's TX2, which would otherwise repay the LP, so it
+/// stays open for good; it no longer counts against the LP's open fills
+#[derive(Accounts)]
+pub struct RevokeFastFill<'info> {
+    #[account(
+        mut,
+        seeds = [
+            FAST_FILL_SEED,
+            fast_fill.source_chain_id.to_le_bytes().as_ref(),
+            &fast_fill.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = fast_fill.bump
+    )]
+    pub fast_fill: Account<'info, FastFill>,
+    
+    #[account(
+        mut,
+        seeds = [
+            LIQUIDITY_PROVIDER_SEED,
+            fast_fill.liquidity_provider.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = liquidity_provider.bump
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    
+    /// CHECK: Receives the slashed lamports (e.g. a treasury or the affected party)
+    #[account(mut)]
+    pub slash_destination: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn revoke_fast_fill(ctx: Context<RevokeFastFill>, slash_amount: u64) -> Result<()> {
+    let fast_fill = &mut ctx.accounts.fast_fill;
+    require!(
+        fast_fill.status == FastFillStatus::Pending,
+        GatewayError::InvalidFastFillStatus
+    );
+    fast_fill.status = FastFillStatus::Revoked;
+    
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    let slashed = slash_amount.min(liquidity_provider.bond);
+    liquidity_provider.bond -= slashed;
+    liquidity_provider.slashed = liquidity_provider.slashed.saturating_add(slashed);
+    liquidity_provider.open_fills = liquidity_provider.open_fills.saturating_sub(1);
+    move_bond_lamports(
+        &liquidity_provider.to_account_info(),
+        &ctx.accounts.slash_destination.to_account_info(),
+        slashed,
+    )?;
+    
+    emit!(FastFillRevoked {
+        tx_id: fast_fill.tx_id,
+        source_chain_id: fast_fill.source_chain_id,
+        liquidity_provider: fast_fill.liquidity_provider,
+        slashed,
+        slash_destination: ctx.accounts.slash_destination.key(),
+    });
+    
+    msg!("Fast fill revoked for tx_id={}, slashed={}", fast_fill.tx_id, slashed);
+    Ok(())
+}
+
+/// Close a settled fill and reclaim its rent (LP only)
+/// Revoked fills cannot be closed: settle_fast_fill would then see an empty PDA and let
+/// TX2 deliver the revoked message
+#[derive(Accounts)]
+pub struct CloseFastFill<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            FAST_FILL_SEED,
+            fast_fill.source_chain_id.to_le_bytes().as_ref(),
+            &fast_fill.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = fast_fill.bump,
+        constraint = fast_fill.liquidity_provider == owner.key() @ GatewayError::UnauthorizedAuthority,
+        constraint = fast_fill.status.is_settled() @ GatewayError::InvalidFastFillStatus
+    )]
+    pub fast_fill: Account<'info, FastFill>,
+    
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_PROVIDER_SEED, owner.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = liquidity_provider.bump
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn close_fast_fill(ctx: Context<CloseFastFill>) -> Result<()> {
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    liquidity_provider.open_fills = liquidity_provider.open_fills.saturating_sub(1);
+    
+    msg!("Fast fill closed for tx_id={}", ctx.accounts.fast_fill.tx_id);
+    Ok(())
+}
+
+/// Settle a transfer's FastFill during TX2 and return the token account to deliver to
+/// The FastFill PDA is always passed, so a fronted transfer cannot be delivered twice;
+/// an uninitialized PDA means the transfer was not fronted and goes to the recipient
+pub(crate) fn settle_fast_fill<'info>(
+    fast_fill: &AccountInfo<'info>,
+    envelope: &MessageEnvelope,
+) -> Result<Option<Pubkey>> {
+    if fast_fill.data_is_empty() {
+        return Ok(None);
+    }
+    
+    require_keys_eq!(*fast_fill.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    
+    let mut fill = FastFill::try_deserialize(&mut &fast_fill.try_borrow_data()?[..])?;
+    require!(fill.status != FastFillStatus::Revoked, GatewayError::FastFillRevoked);
+    require!(
+        fill.status == FastFillStatus::Pending,
+        GatewayError::InvalidFastFillStatus
+    );
+    
+    // A fill of a different envelope under the same tx_id is forfeited
+    let repaid = fill.envelope_digest == FastFill::envelope_digest(envelope)?;
+    fill.status = if repaid { FastFillStatus::Repaid } else { FastFillStatus::Forfeited };
+    
+    emit!(FastFillSettled {
+        tx_id: fill.tx_id,
+        source_chain_id: fill.source_chain_id,
+        liquidity_provider: fill.liquidity_provider,
+        repaid,
+    });
+    
+    let repayment_account = fill.repayment_account;
+    fill.try_serialize(&mut &mut fast_fill.try_borrow_mut_data()?[..])?;
+    Ok(repaid.then_some(repayment_account))
+}
+
+/// Check TX2's delivery account: the LP repayment account for a fronted transfer,
+/// otherwise a token account owned by the payload recipient
+/// Mints in claim-delivery mode never push to the recipient; their transfers are parked
+pub(crate) fn check_delivery_account<'info>(
+    fast_fill: &AccountInfo<'info>,
+    envelope: &MessageEnvelope,
+    delivery_account: &InterfaceAccount<TokenAccount>,
+    recipient: Pubkey,
+    token_config: &TokenConfig,
+) -> Result<()> {
+    match settle_fast_fill(fast_fill, envelope)? {
+        Some(repayment_account) => require_keys_eq!(
+            delivery_account.key(),
+            repayment_account,
+            GatewayError::InvalidTokenRecipient
+        ),
+        None => {
+            require!(
+                !token_config.settings.claim_delivery,
+                GatewayError::ClaimDeliveryRequired
+            );
+            require_keys_eq!(
+                delivery_account.owner,
+                recipient,
+                GatewayError::InvalidTokenRecipient
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Move bonded lamports out of a program-owned LiquidityProvider account
+fn move_bond_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(GatewayError::InvalidAmount)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(GatewayError::InvalidAmount)?;
+    Ok(())
+}
\ No newline at end of file
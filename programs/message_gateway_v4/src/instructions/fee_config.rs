@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{FeeConfigUpdated, FeesWithdrawn, RelayerRefundUpdated};
+use crate::state::{FeeConfig, MessageGateway};
+
+/// Set the lamport fee schedule for messages to a destination chain, creating it on first
+/// use (admin only); zero fees make the route free again
+/// The authority also tops the fee vault up to rent exemption, so fees smaller than the
+/// rent-exempt minimum can be paid into it
+#[derive(Accounts)]
+#[instruction(dest_chain_id: u64)]
+pub struct SetFeeConfig<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeConfig::SIZE,
+        seeds = [FEE_CONFIG_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_config: Account<'info, FeeConfig>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_fee_config(
+    ctx: Context<SetFeeConfig>,
+    dest_chain_id: u64,
+    base_fee: u64,
+    fee_per_byte: u64,
+) -> Result<()> {
+    require!(dest_chain_id > 0, GatewayError::InvalidChainId);
+    
+    let shortfall = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(ctx.accounts.fee_vault.lamports());
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    
+    let fee_config = &mut ctx.accounts.fee_config;
+    fee_config.dest_chain_id = dest_chain_id;
+    fee_config.base_fee = base_fee;
+    fee_config.fee_per_byte = fee_per_byte;
+    fee_config.bump = ctx.bumps.fee_config;
+    
+    emit!(FeeConfigUpdated {
+        dest_chain_id,
+        base_fee,
+        fee_per_byte,
+    });
+    
+    msg!(
+        "Fee config for chain {}: base {} + {} per byte (lamports)",
+        dest_chain_id,
+        base_fee,
+        fee_per_byte
+    );
+    Ok(())
+}
+
+/// Set the refund process_message and deliver_message pay relayers of messages from a chain
+/// out of the fee vault, creating the chain's fee config on first use (admin only)
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct SetRelayerRefund<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + FeeConfig::SIZE,
+        seeds = [FEE_CONFIG_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_config: Account<'info, FeeConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_relayer_refund(
+    ctx: Context<SetRelayerRefund>,
+    chain_id: u64,
+    relayer_refund: u64,
+    relayer_fee_share_bps: u16,
+) -> Result<()> {
+    require!(chain_id > 0, GatewayError::InvalidChainId);
+    require!(
+        relayer_fee_share_bps as u64 <= BPS_DENOMINATOR,
+        GatewayError::InvalidRelayerRefund
+    );
+    
+    let fee_config = &mut ctx.accounts.fee_config;
+    fee_config.dest_chain_id = chain_id;
+    fee_config.relayer_refund = relayer_refund;
+    fee_config.relayer_fee_share_bps = relayer_fee_share_bps;
+    fee_config.bump = ctx.bumps.fee_config;
+    
+    emit!(RelayerRefundUpdated {
+        chain_id,
+        relayer_refund,
+        relayer_fee_share_bps,
+    });
+    
+    msg!(
+        "Relayer refund for chain {}: {} lamports + {} bps of the send fee",
+        chain_id,
+        relayer_refund,
+        relayer_fee_share_bps
+    );
+    Ok(())
+}
+
+/// Withdraw accumulated send fees from the fee vault (admin only)
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_vault: SystemAccount<'info>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Any wallet chosen by the admin to receive the fees
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+    require!(amount > 0, GatewayError::InvalidAmount);
+    
+    // The vault is a system account and must stay rent exempt
+    let vault = &ctx.accounts.fee_vault;
+    let available = vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(amount <= available, GatewayError::InsufficientFeeVaultBalance);
+    
+    let instance_seed = ctx.accounts.gateway.instance_seed();
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
+            },
+            &[&[FEE_VAULT_SEED, &instance_seed, &[ctx.bumps.fee_vault]]],
+        ),
+        amount,
+    )?;
+    
+    emit!(FeesWithdrawn {
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+    });
+    
+    msg!("Withdrew {} lamports of send fees", amount);
+    Ok(())
+}
+
+/// Pay a relayer its refund for a message carrying `payload_size` bytes of on-chain data
+/// from the fee vault; returns the lamports paid
+/// No refund without the source chain's fee config and the vault, and the payout is capped
+/// at the vault balance above its rent-exempt minimum so an empty vault never blocks
+/// processing; `instance_seed` is the gateway instance's vault seed suffix
+pub(crate) fn pay_relayer_refund<'info>(
+    fee_config: Option<&FeeConfig>,
+    fee_vault: Option<&SystemAccount<'info>>,
+    fee_vault_bump: Option<u8>,
+    instance_seed: &[u8],
+    relayer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    payload_size: usize,
+) -> Result<u64> {
+    let (Some(fee_config), Some(fee_vault), Some(bump)) = (fee_config, fee_vault, fee_vault_bump) else {
+        return Ok(0);
+    };
+    
+    let owed = fee_config.relayer_refund_for(payload_size)?;
+    let available = fee_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    let amount = owed.min(available);
+    if amount == 0 {
+        return Ok(0);
+    }
+    
+    transfer(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            Transfer {
+                from: fee_vault.to_account_info(),
+                to: relayer.to_account_info(),
+            },
+            &[&[FEE_VAULT_SEED, instance_seed, &[bump]]],
+        ),
+        amount,
+    )?;
+    
+    Ok(amount)
+}
\ No newline at end of file
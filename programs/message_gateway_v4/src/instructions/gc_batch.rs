@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_GC_BATCH;
+use crate::errors::GatewayError;
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::state::{RelayerPool, RentRefundKind, TxIdPDA};
+
+/// Close expired TxId PDAs whose TX2 never arrived, refunding each one's rent to the
+/// relayer or payer that paid it, or to the relayer pool that advanced it (permissionless;
+/// keepers pay only the transaction fee)
+/// remaining_accounts: [tx_id_pda, refund] pairs, at most MAX_GC_BATCH of them, where refund is
+/// the PDA's relayer or payer wallet, or the relayer pool that advanced its rent
+#[derive(Accounts)]
+pub struct GcBatch<'info> {
+    pub keeper: Signer<'info>,
+}
+
+pub fn gc_batch<'info>(ctx: Context<'_, '_, 'info, 'info, GcBatch<'info>>) -> Result<()> {
+    let accounts = ctx.remaining_accounts;
+    require!(
+        !accounts.is_empty() && accounts.len() % 2 == 0 && accounts.len() / 2 <= MAX_GC_BATCH,
+        GatewayError::InvalidGcBatch
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    let mut closed = 0usize;
+    for pair in accounts.chunks_exact(2) {
+        let (tx_id_pda, refund) = (&pair[0], &pair[1]);
+        
+        // Closed by TX2 (or an earlier batch) since the keeper listed it
+        if tx_id_pda.owner != &crate::ID {
+            continue;
+        }
+        
+        let tx_id_pda = Account::<TxIdPDA>::try_from(tx_id_pda)
+            .map_err(|_| GatewayError::InvalidGcBatch)?;
+        require!(tx_id_pda.is_expired(now), GatewayError::TxIdPdaNotExpired);
+        require!(refund.is_writable, GatewayError::InvalidGcBatch);
+        
+        if tx_id_pda.rent_refund_kind != RentRefundKind::Pool {
+            require_keys_eq!(refund.key(), tx_id_pda.rent_refund, GatewayError::InvalidGcBatch);
+        } else {
+            let mut relayer_pool = Account::<RelayerPool>::try_from(refund)
+                .map_err(|_| GatewayError::InvalidRelayerPool)?;
+            settle_tx_id_rent(&tx_id_pda, Some(&mut relayer_pool), None)?;
+            relayer_pool.exit(&crate::ID)?;
+        }
+        
+        tx_id_pda.close(refund.clone())?;
+        closed += 1;
+    }
+    
+    msg!("Garbage-collected {} expired TxId PDAs", closed);
+    Ok(())
+}
\ No newline at end of file
@@ -1,21 +1,81 @@
 use anchor_lang::prelude::*;
 
 use crate::constants::*;
-use crate::state::MessageGateway;
+use crate::errors::GatewayError;
+use crate::state::{instance_seed, MessageGateway};
 
 pub fn handler(ctx: Context<InitializeGateway>, chain_id: u64) -> Result<()> {
-    let gateway = &mut ctx.accounts.gateway;
+    // Envelopes with chain id 0 are rejected, so a gateway on chain 0 could never route
+    require!(chain_id > 0, GatewayError::InvalidChainId);
     
-    // Set gateway configuration
-    gateway.authority = ctx.accounts.authority.key();
-    gateway.chain_id = chain_id;
-    gateway.system_enabled = true;
-    gateway.bump = ctx.bumps.gateway;
+    configure_gateway(
+        &mut ctx.accounts.gateway,
+        ctx.accounts.authority.key(),
+        chain_id,
+        DEFAULT_INSTANCE_ID,
+        ctx.bumps.gateway,
+        ctx.program_id,
+    );
     
     msg!("Gateway initialized for chain: {:?}", chain_id);
     Ok(())
 }
 
+/// Create another gateway instance on `chain_id` next to the default one, with its own
+/// registries, counters and signing domain
+pub fn initialize_instance(
+    ctx: Context<InitializeGatewayInstance>,
+    chain_id: u64,
+    instance_id: u16,
+) -> Result<()> {
+    require!(chain_id > 0, GatewayError::InvalidChainId);
+    require!(instance_id != DEFAULT_INSTANCE_ID, GatewayError::InvalidGatewayInstance);
+    
+    configure_gateway(
+        &mut ctx.accounts.gateway,
+        ctx.accounts.authority.key(),
+        chain_id,
+        instance_id,
+        ctx.bumps.gateway,
+        ctx.program_id,
+    );
+    
+    msg!("Gateway instance {} initialized for chain: {:?}", instance_id, chain_id);
+    Ok(())
+}
+
+/// Default configuration of a freshly created gateway, shared with `bootstrap_test_env`
+pub(crate) fn configure_gateway(
+    gateway: &mut MessageGateway,
+    authority: Pubkey,
+    chain_id: u64,
+    instance_id: u16,
+    bump: u8,
+    program_id: &Pubkey,
+) {
+    // Set gateway configuration
+    gateway.authority = authority;
+    gateway.chain_id = chain_id;
+    gateway.system_enabled = true;
+    gateway.bump = bump;
+    gateway.min_protocol_version = PROTOCOL_VERSION;
+    gateway.max_protocol_version = PROTOCOL_VERSION;
+    gateway.loopback_enabled = false;
+    gateway.price_feed_id = [0u8; 32];
+    gateway.max_price_age = DEFAULT_MAX_PRICE_AGE;
+    gateway.treasury = authority;
+    gateway.registry_epoch = 0;
+    gateway.message_history_tree = Pubkey::default();
+    gateway.message_history_bump = 0;
+    // Found once here; every later instruction re-derives the vault with the cached bump
+    gateway.sol_vault_bump =
+        Pubkey::find_program_address(&[SOL_VAULT_SEED, &instance_seed(instance_id)], program_id).1;
+    gateway.full_signature_verification = false;
+    gateway.strict_precompile_checks = false;
+    gateway.program_version = PROGRAM_VERSION;
+    gateway.instance_id = instance_id;
+}
+
 #[derive(Accounts)]
 #[instruction(chain_id: u64)]
 pub struct InitializeGateway<'info> {
@@ -31,5 +91,23 @@ pub struct InitializeGateway<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: u64, instance_id: u16)]
+pub struct InitializeGatewayInstance<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MessageGateway::SIZE,
+        seeds = [GATEWAY_SEED, chain_id.to_le_bytes().as_ref(), instance_seed(instance_id).as_ref()],
+        bump
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
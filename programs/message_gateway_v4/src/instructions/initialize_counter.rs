@@ -55,7 +55,8 @@ pub struct InitializeCounter<'info> {
         space = 8 + CounterPDA::SIZE,
         seeds = [
             COUNTER_SEED,
-            source_chain_id.to_le_bytes().as_ref()
+            source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
         ],
         bump
     )]
@@ -71,8 +72,9 @@ pub struct InitializeCounter<'info> {
     // Gateway account to verify authority
     // Using the destination chain gateway (Solana = 1)
     #[account(
-        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref()],
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
         bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
         constraint = gateway.system_enabled @ GatewayError::GatewayDisabled
     )]
     pub gateway: Account<'info, MessageGateway>,
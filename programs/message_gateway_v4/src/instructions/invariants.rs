@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{InvariantCheck, InvariantViolation, InvariantsAsserted};
+use crate::state::{
+    ChainConfig, CounterPDA, MessageGateway, RelayerPool, SignerRegistry, TokenConfig,
+};
+
+/// Check the gateway and the accounts in remaining_accounts for cross-account consistency
+/// and emit an `InvariantsAsserted` report (permissionless, read-only)
+/// remaining_accounts: signer registries, counters, chain configs, relayer pools and token
+/// configs, recognised by their discriminator; each token config is followed by its escrow
+/// A violation is reported, not raised, so monitors can simulate the instruction and read
+/// the report; only accounts it cannot audit make it fail
+#[derive(Accounts)]
+pub struct AssertInvariants<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+}
+
+pub fn assert_invariants<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AssertInvariants<'info>>,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    let instance_seed = gateway.instance_seed();
+    let rent = Rent::get()?;
+    let mut report = Report::default();
+    
+    report.check(
+        gateway.key(),
+        InvariantCheck::GatewayConfig,
+        gateway.chain_id != 0
+            && gateway.min_protocol_version > 0
+            && gateway.min_protocol_version <= gateway.max_protocol_version,
+    );
+    
+    let mut accounts = ctx.remaining_accounts.iter();
+    while let Some(account_info) = accounts.next() {
+        require_keys_eq!(
+            *account_info.owner,
+            crate::ID,
+            GatewayError::InvalidInvariantAccount
+        );
+        let key = account_info.key();
+        let data = account_info.try_borrow_data()?;
+        
+        if data.starts_with(SignerRegistry::DISCRIMINATOR) {
+            let registry = SignerRegistry::try_deserialize(&mut &data[..])?;
+            let seeds: [&[u8]; 5] = [
+                SIGNER_REGISTRY_SEED,
+                &registry.registry_type.discriminant()?.to_le_bytes(),
+                &registry.chain_id.to_le_bytes(),
+                &instance_seed,
+                &[registry.bump],
+            ];
+            report.check(key, InvariantCheck::RegistryAddress, is_address(&seeds, &key));
+            report.check(key, InvariantCheck::RegistryThreshold, registry_threshold_holds(&registry));
+        } else if data.starts_with(CounterPDA::DISCRIMINATOR) {
+            let counter = CounterPDA::try_deserialize(&mut &data[..])?;
+            let seeds: [&[u8]; 4] = [
+                COUNTER_SEED,
+                &counter.source_chain_id.to_le_bytes(),
+                &instance_seed,
+                &[counter.bump],
+            ];
+            report.check(
+                key,
+                InvariantCheck::CounterChainId,
+                counter.source_chain_id != 0 && is_address(&seeds, &key),
+            );
+        } else if data.starts_with(ChainConfig::DISCRIMINATOR) {
+            let chain_config = ChainConfig::try_deserialize(&mut &data[..])?;
+            let seeds: [&[u8]; 4] = [
+                CHAIN_CONFIG_SEED,
+                &chain_config.chain_id.to_le_bytes(),
+                &instance_seed,
+                &[chain_config.bump],
+            ];
+            report.check(
+                key,
+                InvariantCheck::ChainConfigBounds,
+                chain_config.chain_id != 0
+                    && chain_config.caip2_id.len() <= MAX_CAIP2_ID_LEN
+                    && chain_config.remote_gateway.len() <= MAX_SENDER_SIZE
+                    && chain_config.min_confirmations <= chain_config.max_confirmations
+                    && chain_config.max_payload_size as usize <= MAX_ON_CHAIN_DATA_SIZE
+                    && is_address(&seeds, &key),
+            );
+        } else if data.starts_with(RelayerPool::DISCRIMINATOR) {
+            let relayer_pool = RelayerPool::try_deserialize(&mut &data[..])?;
+            // The pool holds its balance on top of its own rent; lent rent sits in TxId PDAs
+            let backing = rent
+                .minimum_balance(account_info.data_len())
+                .saturating_add(relayer_pool.balance);
+            report.check(
+                key,
+                InvariantCheck::RelayerPoolBalance,
+                account_info.lamports() >= backing,
+            );
+        } else if data.starts_with(TokenConfig::DISCRIMINATOR) {
+            let token_config = TokenConfig::try_deserialize(&mut &data[..])?;
+            let escrow_info = accounts.next().ok_or(GatewayError::InvalidInvariantAccount)?;
+            let escrow = InterfaceAccount::<TokenAccount>::try_from(escrow_info)
+                .map_err(|_| GatewayError::InvalidInvariantAccount)?;
+            require!(
+                escrow.mint == token_config.mint && escrow.owner == gateway.key(),
+                GatewayError::InvalidInvariantAccount
+            );
+            report.check(
+                key,
+                InvariantCheck::EscrowBalance,
+                escrow.amount >= token_config.escrowed_amount,
+            );
+        } else {
+            return err!(GatewayError::InvalidInvariantAccount);
+        }
+    }
+    
+    msg!(
+        "Invariants: {} checks, {} violations",
+        report.checks,
+        report.violations.len()
+    );
+    emit!(InvariantsAsserted {
+        chain_id: gateway.chain_id,
+        checks: report.checks,
+        violations: report.violations,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Checks run so far and the ones that failed
+#[derive(Default)]
+struct Report {
+    checks: u32,
+    violations: Vec<InvariantViolation>,
+}
+
+impl Report {
+    fn check(&mut self, account: Pubkey, check: InvariantCheck, holds: bool) {
+        self.checks += 1;
+        if !holds {
+            self.violations.push(InvariantViolation { account, check });
+        }
+    }
+}
+
+/// Whether `key` is the PDA of `seeds` (bump included)
+fn is_address(seeds: &[&[u8]], key: &Pubkey) -> bool {
+    Pubkey::create_program_address(seeds, &crate::ID).is_ok_and(|address| address == *key)
+}
+
+/// An enabled registry must be able to reach its threshold with distinct signers
+fn registry_threshold_holds(registry: &SignerRegistry) -> bool {
+    let mut signers = registry.signers.clone();
+    signers.sort_unstable();
+    signers.dedup();
+    
+    registry.extra_verifiers.len() <= MAX_EXTRA_VERIFIERS
+        && signers.len() == registry.signers.len()
+        && (!registry.enabled
+            || (registry.required_signatures >= SignerRegistry::MIN_REQUIRED_SIGNATURES
+                && registry.required_signatures as usize <= signers.len()))
+}
\ No newline at end of file
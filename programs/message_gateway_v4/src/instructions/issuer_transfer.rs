@@ -0,0 +1,465 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{IssuerBurnDeposited, IssuerRouteUpdated, IssuerTransferCompleted, SendRequested};
+use crate::instructions::process_message::{
+    delivery_accounts, extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::instructions::token_bridge::{
+    bridge_envelope, check_inbound_bridge_message, enforce_blocklist, enforce_rate_limit,
+};
+use crate::payloads::{TokenTransferPayload, PAYLOAD_KIND_ISSUER_TRANSFER};
+use crate::state::{
+    BridgePeer, ChainConfig, MessageEnvelope, MessageGateway, MessageSignature, RelayerPool,
+    SignerRegistry, TokenConfig, TokenKind, TxIdPDA,
+};
+use crate::utils::{address::validate_remote_address, token::received_amount};
+
+/// Route a configured mint through its issuer's burn/mint attestation program, or back to
+/// escrow (admin only)
+/// Switching requires an empty escrow liability so no locked tokens are stranded
+#[derive(Accounts)]
+pub struct SetIssuerRoute<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, token_config.mint.as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Set `issuer_program` to the default pubkey to return the mint to escrow bridging
+pub fn set_issuer_route(ctx: Context<SetIssuerRoute>, issuer_program: Pubkey) -> Result<()> {
+    let token_config = &mut ctx.accounts.token_config;
+    require!(
+        matches!(token_config.kind, TokenKind::Native | TokenKind::IssuerBurnMint),
+        GatewayError::TokenKindMismatch
+    );
+    require!(token_config.escrowed_amount == 0, GatewayError::EscrowNotEmpty);
+    
+    token_config.issuer_program = issuer_program;
+    token_config.kind = if issuer_program == Pubkey::default() {
+        TokenKind::Native
+    } else {
+        TokenKind::IssuerBurnMint
+    };
+    
+    emit!(IssuerRouteUpdated {
+        mint: token_config.mint,
+        issuer_program,
+        updated_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Issuer route for mint {}: {}", token_config.mint, issuer_program);
+    Ok(())
+}
+
+/// Burn issuer-routed tokens through the issuer's program and send the transfer message
+/// The burn instruction is built by the caller (`issuer_data` plus remaining_accounts, in
+/// the issuer's order); the gateway checks the mint supply and the depositor's balance
+/// both dropped by exactly `amount`
+#[derive(Accounts)]
+#[instruction(tx_id: u128, dest_chain_id: u64)]
+pub struct DepositForIssuerBurn<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// Token bridge on the destination chain
+    #[account(
+        seeds = [BRIDGE_PEER_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// Optional destination chain config validating the recipient address format
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Option<Account<'info, ChainConfig>>,
+    
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub depositor: Signer<'info>,
+    
+    /// CHECK: Issuer attestation program configured for the mint
+    #[account(
+        executable,
+        address = token_config.issuer_program @ GatewayError::InvalidIssuerProgram
+    )]
+    pub issuer_program: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_for_issuer_burn<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositForIssuerBurn<'info>>,
+    tx_id: u128,
+    dest_chain_id: u64,
+    recipient: Vec<u8>,
+    amount: u64,
+    issuer_data: Vec<u8>,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(tx_id > 0, GatewayError::InvalidTxId);
+    require!(
+        gateway.allows_route(gateway.chain_id, dest_chain_id),
+        GatewayError::LoopbackDisabled
+    );
+    
+    // Beneficiary must be a valid address for the destination chain family
+    if let Some(chain_config) = ctx.accounts.chain_config.as_ref() {
+        validate_remote_address(chain_config.address_format, &recipient)?;
+    }
+    
+    enforce_blocklist(
+        &ctx.accounts.blocklist,
+        &[
+            ctx.accounts.depositor_token_account.owner,
+            ctx.accounts.depositor.key(),
+            ctx.accounts.mint.key(),
+        ],
+    )?;
+    let token_config = &ctx.accounts.token_config;
+    token_config.check_outbound(TokenKind::IssuerBurnMint, amount)?;
+    
+    // The issuer burns exact amounts, so the amount must already be representable
+    let decimals = ctx.accounts.mint.decimals;
+    let wire = token_config.to_wire_amount(amount, decimals)?;
+    
+    let supply_before = ctx.accounts.mint.supply;
+    let balance_before = ctx.accounts.depositor_token_account.amount;
+    invoke_issuer(&ctx.accounts.issuer_program, issuer_data, ctx.remaining_accounts)?;
+    
+    ctx.accounts.mint.reload()?;
+    ctx.accounts.depositor_token_account.reload()?;
+    let burned = supply_before.checked_sub(ctx.accounts.mint.supply);
+    let debited = balance_before.checked_sub(ctx.accounts.depositor_token_account.amount);
+    require!(
+        burned == Some(amount) && debited == Some(amount),
+        GatewayError::IssuerTransferMismatch
+    );
+    
+    let mint = ctx.accounts.mint.key();
+    let depositor = ctx.accounts.depositor_token_account.owner;
+    let payload = TokenTransferPayload {
+        token: mint.to_bytes(),
+        amount: wire,
+        sender: depositor.to_bytes().to_vec(),
+        recipient: recipient.clone(),
+    };
+    
+    let envelope = bridge_envelope(
+        gateway,
+        &ctx.accounts.bridge_peer,
+        tx_id,
+        dest_chain_id,
+        PAYLOAD_KIND_ISSUER_TRANSFER,
+        payload.encode()?,
+        Vec::new(),
+    )?;
+    
+    emit!(IssuerBurnDeposited {
+        tx_id,
+        mint,
+        depositor,
+        amount,
+        dest_chain_id,
+        recipient,
+    });
+    
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("Issuer burn deposited: tx_id={}, amount={}, dest_chain={}", tx_id, amount, dest_chain_id);
+    Ok(())
+}
+
+/// TX2 for issuer-routed transfers: verifies the message like release_tokens, then runs the
+/// issuer's mint instruction (`issuer_data`, with its accounts after the verifier registries)
+/// and checks the recipient was credited exactly the payload amount
+/// remaining_accounts: the project's extra verifier registries, the message history accounts
+/// while history is enabled, then the issuer's accounts
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct CompleteIssuerTransfer<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    /// CHECK: Mint's TokenRateLimit PDA; may be uninitialized (no cap), validated in enforce_rate_limit
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub rate_limit: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Issuer attestation program configured for the mint
+    #[account(
+        executable,
+        address = token_config.issuer_program @ GatewayError::InvalidIssuerProgram
+    )]
+    pub issuer_program: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn complete_issuer_transfer<'info>(
+    ctx: Context<'_, '_, '_, 'info, CompleteIssuerTransfer<'info>>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+    issuer_data: Vec<u8>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    let verifier_count = extra_verifier_count(ctx.accounts.project_registry.as_deref());
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        verifier_count,
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_ISSUER_TRANSFER)?;
+    let payload = TokenTransferPayload::decode(&envelope.on_chain_data)?;
+    let mint = ctx.accounts.mint.key();
+    require!(payload.token == mint.to_bytes(), GatewayError::TokenMismatch);
+    
+    let recipient = payload.recipient_pubkey()?;
+    require_keys_eq!(
+        ctx.accounts.recipient_token_account.owner,
+        recipient,
+        GatewayError::InvalidTokenRecipient
+    );
+    enforce_blocklist(&ctx.accounts.blocklist, &[recipient, mint])?;
+    
+    let token_config = &ctx.accounts.token_config;
+    token_config.check_path(TokenKind::IssuerBurnMint)?;
+    let amount = token_config.from_wire_amount(payload.amount, ctx.accounts.mint.decimals)?;
+    enforce_rate_limit(&ctx.accounts.rate_limit, amount)?;
+    
+    // Issuer accounts follow the extra verifier registries and the history accounts
+    let issuer_accounts =
+        delivery_accounts(&ctx.accounts.gateway, ctx.remaining_accounts, verifier_count);
+    
+    let balance_before = ctx.accounts.recipient_token_account.amount;
+    invoke_issuer(&ctx.accounts.issuer_program, issuer_data, issuer_accounts)?;
+    
+    ctx.accounts.recipient_token_account.reload()?;
+    let received = received_amount(balance_before, ctx.accounts.recipient_token_account.amount)?;
+    require!(received == amount, GatewayError::IssuerTransferMismatch);
+    
+    emit!(IssuerTransferCompleted {
+        tx_id: envelope.tx_id,
+        source_chain_id: envelope.source_chain_id,
+        mint,
+        recipient,
+        amount,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Issuer transfer completed and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
+
+/// Invoke the issuer program with caller-built instruction data and accounts
+/// The gateway adds no PDA signature, so the issuer can only use authority the transaction's
+/// own signers already granted
+fn invoke_issuer<'info>(
+    issuer_program: &AccountInfo<'info>,
+    data: Vec<u8>,
+    accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let metas = accounts
+        .iter()
+        .map(|account| {
+            if account.is_writable {
+                AccountMeta::new(account.key(), account.is_signer)
+            } else {
+                AccountMeta::new_readonly(account.key(), account.is_signer)
+            }
+        })
+        .collect();
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(issuer_program.clone());
+    
+    let instruction = Instruction {
+        program_id: issuer_program.key(),
+        accounts: metas,
+        data,
+    };
+    invoke(&instruction, &account_infos)?;
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{V3MappingUpdated, V3MessageTranslated};
+use crate::state::{
+    LegacyV3Message, MessageGateway, MessageSignature, SignerRegistry, V3Receipt, V3RouteMapping,
+};
+use crate::utils::{
+    hash::create_legacy_v3_hash,
+    signature::{validate_three_layer_signatures, VerificationPolicy},
+    verification::load_verifier_registries,
+};
+
+/// Create or update the V4 mapping for a V3 source chain (admin only)
+#[derive(Accounts)]
+#[instruction(v3_chain_id: u64)]
+pub struct SetV3Mapping<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + V3RouteMapping::SIZE,
+        seeds = [V3_MAPPING_SEED, v3_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub mapping: Account<'info, V3RouteMapping>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_v3_mapping(
+    ctx: Context<SetV3Mapping>,
+    v3_chain_id: u64,
+    v4_chain_id: u64,
+    message_type: u8,
+    enabled: bool,
+) -> Result<()> {
+    require!(v3_chain_id > 0 && v4_chain_id > 0, GatewayError::InvalidChainId);
+    
+    let mapping = &mut ctx.accounts.mapping;
+    mapping.v3_chain_id = v3_chain_id;
+    mapping.v4_chain_id = v4_chain_id;
+    mapping.message_type = message_type;
+    mapping.enabled = enabled;
+    mapping.bump = ctx.bumps.mapping;
+    
+    emit!(V3MappingUpdated {
+        v3_chain_id,
+        v4_chain_id,
+        message_type,
+        enabled,
+    });
+    
+    msg!(
+        "V3 chain {} mapped to V4 chain {} (enabled={})",
+        v3_chain_id,
+        v4_chain_id,
+        enabled
+    );
+    Ok(())
+}
+
+/// Accept a V3-format message with V3 attestations and re-wrap it as a V4 envelope
+/// Signatures are checked over the legacy hash against the V4 registries of the mapped
+/// route; a permanent receipt PDA provides replay protection in a single transaction
+#[derive(Accounts)]
+#[instruction(message: LegacyV3Message)]
+pub struct TranslateV3Message<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        seeds = [
+            V3_MAPPING_SEED,
+            message.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = mapping.bump
+    )]
+    pub mapping: Account<'info, V3RouteMapping>,
+    
+    /// Replay marker; creation fails if the V3 message was already translated
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + V3Receipt::SIZE,
+        seeds = [
+            V3_RECEIPT_SEED,
+            message.source_chain_id.to_le_bytes().as_ref(),
+            &message.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub receipt: Account<'info, V3Receipt>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            message.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry of the mapped V4 source chain
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            mapping.v4_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    /// Extra verifier registries it requires follow in remaining_accounts
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn translate_v3_message(
+    ctx: Context<TranslateV3Message>,
+    message: LegacyV3Message,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    let mapping = &ctx.accounts.mapping;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(mapping.enabled, GatewayError::V3RouteNotMapped);
+    require!(
+        message.dest_chain_id == gateway.chain_id,
+        GatewayError::InvalidDestChain
+    );
+    message.validate()?;
+    
+    // V3 validators signed the bare legacy hash
+    let legacy_hash = create_legacy_v3_hash(&message)?;
+    
+    let extra_verifier_ids = ctx
+        .accounts
+        .project_registry
+        .as_ref()
+        .map(|registry| registry.extra_verifiers.as_slice())
+        .unwrap_or_default();
+    let extra_verifiers = load_verifier_registries(
+        ctx.remaining_accounts,
+        extra_verifier_ids,
+        mapping.v4_chain_id,
+        &gateway.instance_seed(),
+    )?;
+    
+    validate_three_layer_signatures(
+        &signatures,
+        &legacy_hash,
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        &extra_verifiers,
+        VerificationPolicy::for_gateway(&ctx.accounts.gateway, Clock::get()?.unix_timestamp),
+        &ctx.accounts.instructions,
+    )?;
+    
+    let receipt = &mut ctx.accounts.receipt;
+    receipt.tx_id = message.tx_id;
+    receipt.bump = ctx.bumps.receipt;
+    
+    let envelope = message.to_envelope(mapping);
+    
+    emit!(V3MessageTranslated {
+        v3_source_chain_id: message.source_chain_id,
+        legacy_hash,
+        envelope,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("V3 message translated for tx_id={}", message.tx_id);
+    Ok(())
+}
\ No newline at end of file
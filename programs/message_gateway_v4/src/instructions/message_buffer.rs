@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{GATEWAY_SEED, MAX_BUFFERED_DATA_SIZE, MESSAGE_BUFFER_SEED};
+use crate::errors::GatewayError;
+use crate::state::{MessageBuffer, MessageGateway};
+
+/// Open a buffer for the payload of an inbound message too large for one transaction
+/// The relayer then writes it with create_message_chunk and submits TX1 with
+/// create_tx_pda_buffered, which checks it against `payload_hash`
+#[derive(Accounts)]
+#[instruction(source_chain_id: u64, tx_id: u128, total_size: u32)]
+pub struct CreateMessageBuffer<'info> {
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + MessageBuffer::size(total_size),
+        seeds = [
+            MESSAGE_BUFFER_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            &tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub message_buffer: Account<'info, MessageBuffer>,
+    
+    /// Gateway instance the buffered message is delivered to; scopes the buffer address
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_message_buffer(
+    ctx: Context<CreateMessageBuffer>,
+    source_chain_id: u64,
+    tx_id: u128,
+    total_size: u32,
+    payload_hash: [u8; 32],
+) -> Result<()> {
+    require!(
+        total_size as usize <= MAX_BUFFERED_DATA_SIZE,
+        GatewayError::OnChainDataTooLarge
+    );
+    
+    let message_buffer = &mut ctx.accounts.message_buffer;
+    message_buffer.relayer = ctx.accounts.relayer.key();
+    message_buffer.source_chain_id = source_chain_id;
+    message_buffer.tx_id = tx_id;
+    message_buffer.payload_hash = payload_hash;
+    message_buffer.total_size = total_size;
+    message_buffer.bump = ctx.bumps.message_buffer;
+    message_buffer.data = Vec::with_capacity(total_size as usize);
+    
+    msg!(
+        "Message buffer opened for tx_id={} from chain {} ({} bytes)",
+        tx_id,
+        source_chain_id,
+        total_size
+    );
+    Ok(())
+}
+
+/// Write the next payload chunk into a message buffer (buffer relayer only)
+#[derive(Accounts)]
+#[instruction(source_chain_id: u64, tx_id: u128)]
+pub struct CreateMessageChunk<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MESSAGE_BUFFER_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            &tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = message_buffer.bump,
+        has_one = relayer @ GatewayError::UnauthorizedAuthority
+    )]
+    pub message_buffer: Account<'info, MessageBuffer>,
+    
+    /// Gateway instance the buffered message is delivered to; scopes the buffer address
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
+    pub relayer: Signer<'info>,
+}
+
+pub fn create_message_chunk(
+    ctx: Context<CreateMessageChunk>,
+    _source_chain_id: u64,
+    tx_id: u128,
+    offset: u32,
+    chunk: Vec<u8>,
+) -> Result<()> {
+    let message_buffer = &mut ctx.accounts.message_buffer;
+    message_buffer.append_chunk(offset, &chunk)?;
+    
+    msg!(
+        "Message chunk written for tx_id={} ({}/{} bytes)",
+        tx_id,
+        message_buffer.data.len(),
+        message_buffer.total_size
+    );
+    Ok(())
+}
+
+/// Close an abandoned message buffer, returning its rent (buffer relayer only)
+#[derive(Accounts)]
+#[instruction(source_chain_id: u64, tx_id: u128)]
+pub struct CloseMessageBuffer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            MESSAGE_BUFFER_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            &tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = message_buffer.bump,
+        has_one = relayer @ GatewayError::UnauthorizedAuthority,
+        close = relayer
+    )]
+    pub message_buffer: Account<'info, MessageBuffer>,
+    
+    /// Gateway instance the buffered message is delivered to; scopes the buffer address
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+}
+
+pub fn close_message_buffer(
+    _ctx: Context<CloseMessageBuffer>,
+    _source_chain_id: u64,
+    tx_id: u128,
+) -> Result<()> {
+    msg!("Message buffer closed for tx_id={}", tx_id);
+    Ok(())
+}
\ No newline at end of file
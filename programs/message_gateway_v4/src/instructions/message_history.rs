@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::MessageHistoryTreeSet;
+use crate::state::MessageGateway;
+use crate::utils::{
+    bubblegum::{ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID},
+    history::MessageHistoryCpi,
+};
+
+/// Initialize a concurrent Merkle tree and record every processed message in it from
+/// now on (admin only)
+/// The tree account must already be allocated for `max_depth` / `max_buffer_size` and
+/// owned by the account-compression program; calling again rotates to a fresh tree,
+/// leaving the previous one readable
+pub fn set_message_history_tree(
+    ctx: Context<SetMessageHistoryTree>,
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    let bump = ctx.bumps.history_authority;
+    let history = MessageHistoryCpi {
+        merkle_tree: &ctx.accounts.merkle_tree,
+        history_authority: &ctx.accounts.history_authority,
+        compression_program: &ctx.accounts.compression_program,
+        noop_program: &ctx.accounts.noop_program,
+    };
+    history.init_empty(max_depth, max_buffer_size, &ctx.accounts.gateway.instance_seed(), bump)?;
+    
+    let merkle_tree = ctx.accounts.merkle_tree.key();
+    let gateway = &mut ctx.accounts.gateway;
+    gateway.message_history_tree = merkle_tree;
+    gateway.message_history_bump = bump;
+    
+    emit!(MessageHistoryTreeSet {
+        merkle_tree,
+        max_depth,
+        max_buffer_size,
+    });
+    
+    msg!(
+        "Message history tree set to {} (depth {}, buffer {})",
+        merkle_tree,
+        max_depth,
+        max_buffer_size
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMessageHistoryTree<'info> {
+    #[account(
+        mut,
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// CHECK: Allocated, uninitialized tree account; initialized by the account-compression program
+    #[account(mut, owner = ACCOUNT_COMPRESSION_PROGRAM_ID @ GatewayError::MissingMessageHistory)]
+    pub merkle_tree: UncheckedAccount<'info>,
+    
+    /// CHECK: PDA set as tree authority; signs appends
+    #[account(seeds = [MESSAGE_HISTORY_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub history_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL account-compression program
+    #[account(address = ACCOUNT_COMPRESSION_PROGRAM_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+    
+    /// CHECK: SPL noop program
+    #[account(address = NOOP_PROGRAM_ID)]
+    pub noop_program: UncheckedAccount<'info>,
+    
+    pub authority: Signer<'info>,
+}
\ No newline at end of file
@@ -1,17 +1,98 @@
 pub mod admin;
+#[cfg(feature = "test-env")]
+pub mod bootstrap;
+pub mod chain_config;
+pub mod channel;
+pub mod compressed_nft;
 pub mod create_tx_pda;
+pub mod custody_extension;
+pub mod delivery;
+pub mod fast_transfer;
+pub mod fee_config;
+pub mod gc_batch;
 pub mod initialize;
 pub mod initialize_counter;
+pub mod invariants;
+pub mod issuer_transfer;
+pub mod legacy;
+pub mod message_buffer;
+pub mod message_history;
+pub mod nft_bridge;
 pub mod process_message;
+pub mod query;
+pub mod registry_bundle;
+pub mod relay_cost;
+pub mod relayer_pool;
+pub mod rescue;
 pub mod send_message;
 pub mod signer_registry;
+pub mod snapshot;
+pub mod token_batch;
+pub mod token_bridge;
+pub mod token_claim;
+pub mod value_transfer;
+pub mod verify_hash;
+pub mod vesting;
+pub mod wrapped;
 
 // Public re-exports (Context structs needed by external code)
-pub use admin::SetSystemEnabled;
-pub use create_tx_pda::CreateTxPda;
-pub use initialize::InitializeGateway;
+pub use admin::{
+    SetSystemEnabled,
+    SetProtocolVersions,
+    SetLoopbackEnabled,
+    SetFullSignatureVerification,
+    SetStrictPrecompileChecks,
+    SetPriceFeed,
+    SetTreasury,
+};
+pub use chain_config::{
+    InitializeChainConfig,
+    SetHashAlgorithm,
+    SetIntegerEncoding,
+    SetChainAlias,
+    ClearChainAlias,
+    SetRemoteAddressConfig,
+    SetSendFee,
+    SetDestinationConfig,
+};
+#[cfg(feature = "test-env")]
+pub use bootstrap::BootstrapTestEnv;
+pub use channel::{OpenChannel, CloseChannel};
+pub use compressed_nft::{
+    RegisterCompressedNftTree,
+    LockCompressedNft,
+    UnlockCompressedNft,
+    MintWrappedCompressedNft,
+    BurnWrappedCompressedNft,
+};
+pub use create_tx_pda::{CreateTxPda, CreateTxPdaCompact, CreateTxPdaBuffered, CreateTxPdaPooled};
+pub use custody_extension::{
+    AttachCustodyExtension, DetachCustodyExtension, DepositViaCustodyExtension, ReleaseViaCustodyExtension,
+};
+pub use delivery::DeliverMessage;
+pub use fast_transfer::{
+    DepositLpBond,
+    WithdrawLpBond,
+    FrontTransfer,
+    RevokeFastFill,
+    CloseFastFill,
+};
+pub use fee_config::{SetFeeConfig, SetRelayerRefund, WithdrawFees};
+pub use gc_batch::GcBatch;
+pub use initialize::{InitializeGateway, InitializeGatewayInstance};
 pub use initialize_counter::InitializeCounter;
-pub use process_message::ProcessMessage;
+pub use invariants::AssertInvariants;
+pub use issuer_transfer::{SetIssuerRoute, DepositForIssuerBurn, CompleteIssuerTransfer};
+pub use legacy::{SetV3Mapping, TranslateV3Message};
+pub use message_buffer::{CreateMessageBuffer, CreateMessageChunk, CloseMessageBuffer};
+pub use message_history::SetMessageHistoryTree;
+pub use nft_bridge::{RegisterNftCollection, LockNft, UnlockNft, MintWrappedNft, BurnWrappedNft};
+pub use process_message::{ProcessMessage, ProcessMessageCompact, ProcessMessageBundled};
+pub use query::{SendQuery, FulfillQuery, CancelQuery};
+pub use registry_bundle::{CreateRegistryBundle, SyncRegistryBundle};
+pub use relay_cost::EstimateRelayCost;
+pub use relayer_pool::{DepositRelayerPool, WithdrawRelayerPool};
+pub use rescue::{RequestRescue, RescueTokens, CancelRescue};
 pub use send_message::SendMessage;
 pub use signer_registry::{
     InitializeSignerRegistry,
@@ -20,13 +101,86 @@ pub use signer_registry::{
     RemoveSigner,
     UpdateThreshold,
     SetRegistryEnabled,
+    SetRegistryShadow,
+    SetExtraVerifiers,
+};
+pub use snapshot::ExportSnapshot;
+pub use token_bridge::{
+    SetBridgePeer,
+    SetTokenConfig,
+    UpdateTokenConfig,
+    SetBlocklistEntry,
+    ReconcileEscrow,
+    SetRateLimit,
+    DepositTokens,
+    ReleaseTokens,
+    DeadLetterTokenTransfer,
+};
+pub use token_batch::ReleaseTokenBatch;
+pub use token_claim::{ParkTokenTransfer, ClaimTokenTransfer, ReturnTokenTransfer};
+pub use value_transfer::ReleaseValue;
+pub use verify_hash::VerifyHash;
+pub use vesting::{CreateVestingSchedule, ClaimVestedTokens};
+pub use wrapped::{
+    ProposeWrappedAsset,
+    CancelWrappedAssetListing,
+    CreateWrappedMint,
+    DelistWrappedAsset,
+    AttestWrappedAsset,
+    BurnWrapped,
+    MintWrapped,
 };
 
 // Crate-internal re-exports (client account symbols needed by #[program] macro)
 pub(crate) use admin::__client_accounts_set_system_enabled;
+pub(crate) use admin::__client_accounts_set_protocol_versions;
+pub(crate) use admin::__client_accounts_set_loopback_enabled;
+pub(crate) use admin::__client_accounts_set_full_signature_verification;
+pub(crate) use admin::__client_accounts_set_strict_precompile_checks;
+pub(crate) use admin::__client_accounts_set_price_feed;
+pub(crate) use admin::__client_accounts_set_treasury;
+#[cfg(feature = "cpi")]
+pub(crate) use admin::{
+    __cpi_client_accounts_set_full_signature_verification,
+    __cpi_client_accounts_set_loopback_enabled,
+    __cpi_client_accounts_set_price_feed,
+    __cpi_client_accounts_set_protocol_versions,
+    __cpi_client_accounts_set_strict_precompile_checks,
+    __cpi_client_accounts_set_system_enabled,
+    __cpi_client_accounts_set_treasury,
+};
+#[cfg(feature = "test-env")]
+pub(crate) use bootstrap::*;
+pub(crate) use chain_config::*;
+pub(crate) use channel::*;
+pub(crate) use compressed_nft::*;
 pub(crate) use create_tx_pda::*;
+pub(crate) use custody_extension::*;
+pub(crate) use delivery::*;
+pub(crate) use fast_transfer::*;
+pub(crate) use fee_config::*;
+pub(crate) use gc_batch::*;
 pub(crate) use initialize::*;
 pub(crate) use initialize_counter::*;
+pub(crate) use invariants::*;
+pub(crate) use issuer_transfer::*;
+pub(crate) use legacy::*;
+pub(crate) use message_buffer::*;
+pub(crate) use message_history::*;
+pub(crate) use nft_bridge::*;
 pub(crate) use process_message::*;
+pub(crate) use query::*;
+pub(crate) use registry_bundle::*;
+pub(crate) use relay_cost::*;
+pub(crate) use relayer_pool::*;
+pub(crate) use rescue::*;
 pub(crate) use send_message::*;
-pub(crate) use signer_registry::*;
\ No newline at end of file
+pub(crate) use signer_registry::*;
+pub(crate) use snapshot::*;
+pub(crate) use token_batch::*;
+pub(crate) use token_bridge::*;
+pub(crate) use token_claim::*;
+pub(crate) use value_transfer::*;
+pub(crate) use verify_hash::*;
+pub(crate) use vesting::*;
+pub(crate) use wrapped::*;
\ No newline at end of file
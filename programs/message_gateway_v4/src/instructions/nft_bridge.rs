@@ -0,0 +1,821 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::{
+    create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
+    Metadata, MetadataAccount,
+};
+use anchor_spl::token::{
+    burn, mint_to, transfer_checked, Burn, Mint, MintTo, Token, TokenAccount, TransferChecked,
+};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{
+    NftCollectionRegistered, NftLocked, NftUnlocked, SendRequested, WrappedNftBurned,
+    WrappedNftMinted,
+};
+use crate::instructions::process_message::{
+    extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::instructions::token_bridge::{bridge_envelope, check_inbound_bridge_message};
+use crate::payloads::{NftMetadata, NftTransferPayload, PAYLOAD_KIND_NFT_TRANSFER};
+use crate::state::{
+    BridgePeer, ChainConfig, MessageEnvelope, MessageGateway, MessageSignature, NftCollection,
+    RelayerPool, SignerRegistry, TxIdPDA, WrappedNft,
+};
+use crate::utils::address::validate_remote_address;
+
+/// Allow verified members of a Metaplex collection to be bridged out (admin only)
+#[derive(Accounts)]
+pub struct RegisterNftCollection<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + NftCollection::SIZE,
+        seeds = [NFT_COLLECTION_SEED, collection_mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub nft_collection: Account<'info, NftCollection>,
+    
+    pub collection_mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_nft_collection(ctx: Context<RegisterNftCollection>) -> Result<()> {
+    let nft_collection = &mut ctx.accounts.nft_collection;
+    nft_collection.collection_mint = ctx.accounts.collection_mint.key();
+    nft_collection.bump = ctx.bumps.nft_collection;
+    
+    emit!(NftCollectionRegistered {
+        collection_mint: nft_collection.collection_mint,
+    });
+    
+    msg!("NFT collection {} registered", nft_collection.collection_mint);
+    Ok(())
+}
+
+/// Lock a Solana-native Metaplex NFT in escrow and send it to a remote chain
+/// The NFT must be a verified member of a registered collection; its metadata is
+/// relayed in `off_chain_data`
+#[derive(Accounts)]
+#[instruction(tx_id: u128, dest_chain_id: u64)]
+pub struct LockNft<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// NFT bridge on the destination chain
+    #[account(
+        seeds = [BRIDGE_PEER_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// Optional destination chain config validating the recipient address format
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Option<Account<'info, ChainConfig>>,
+    
+    #[account(
+        seeds = [
+            NFT_COLLECTION_SEED,
+            nft_collection.collection_mint.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = nft_collection.bump
+    )]
+    pub nft_collection: Account<'info, NftCollection>,
+    
+    #[account(constraint = mint.decimals == 0 && mint.supply == 1 @ GatewayError::InvalidNft)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: Account<'info, MetadataAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = owner
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = gateway
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    
+    pub token_metadata_program: Program<'info, Metadata>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn lock_nft(
+    ctx: Context<LockNft>,
+    tx_id: u128,
+    dest_chain_id: u64,
+    recipient: Vec<u8>,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(tx_id > 0, GatewayError::InvalidTxId);
+    require!(
+        gateway.allows_route(gateway.chain_id, dest_chain_id),
+        GatewayError::LoopbackDisabled
+    );
+    
+    // Beneficiary must be a valid address for the destination chain family
+    if let Some(chain_config) = ctx.accounts.chain_config.as_ref() {
+        validate_remote_address(chain_config.address_format, &recipient)?;
+    }
+    
+    // Only verified members of a registered collection can leave Solana
+    let metadata = &ctx.accounts.metadata;
+    let collection_mint = ctx.accounts.nft_collection.collection_mint;
+    require!(
+        metadata
+            .collection
+            .as_ref()
+            .is_some_and(|collection| collection.verified && collection.key == collection_mint),
+        GatewayError::NftCollectionNotVerified
+    );
+    
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.owner_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        1,
+        0,
+    )?;
+    
+    // Metaplex strings are stored null-padded
+    let nft_metadata = NftMetadata {
+        name: metadata.name.trim_end_matches('\0').to_string(),
+        symbol: metadata.symbol.trim_end_matches('\0').to_string(),
+        uri: metadata.uri.trim_end_matches('\0').to_string(),
+    };
+    let mint = ctx.accounts.mint.key();
+    let payload = NftTransferPayload {
+        collection: collection_mint.to_bytes().to_vec(),
+        token_id: mint.to_bytes(),
+        metadata_uri_hash: nft_metadata.uri_hash(),
+        recipient: recipient.clone(),
+    };
+    
+    let envelope = bridge_envelope(
+        gateway,
+        &ctx.accounts.bridge_peer,
+        tx_id,
+        dest_chain_id,
+        PAYLOAD_KIND_NFT_TRANSFER,
+        payload.encode()?,
+        nft_metadata.encode()?,
+    )?;
+    
+    emit!(NftLocked {
+        tx_id,
+        mint,
+        collection_mint,
+        owner: ctx.accounts.owner.key(),
+        dest_chain_id,
+        recipient,
+    });
+    
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("NFT {} locked: tx_id={}, dest_chain={}", mint, tx_id, dest_chain_id);
+    Ok(())
+}
+
+/// TX2 for Solana-native NFTs returning home: verifies the message like
+/// process_message, then releases the NFT from escrow to the beneficiary
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct UnlockNft<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// NFT bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = gateway
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    
+    /// CHECK: Beneficiary wallet; must match the payload recipient
+    pub recipient: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn unlock_nft(
+    ctx: Context<UnlockNft>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_NFT_TRANSFER)?;
+    let payload = NftTransferPayload::decode(&envelope.on_chain_data)?;
+    
+    let mint = ctx.accounts.mint.key();
+    require!(payload.token_id == mint.to_bytes(), GatewayError::NftMismatch);
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        nft_recipient(&payload)?,
+        GatewayError::InvalidTokenRecipient
+    );
+    
+    // The gateway PDA owns the escrow and signs the release
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: gateway.to_account_info(),
+            },
+            &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]],
+        ),
+        1,
+        0,
+    )?;
+    
+    emit!(NftUnlocked {
+        tx_id: envelope.tx_id,
+        mint,
+        recipient: ctx.accounts.recipient.key(),
+        source_chain_id: envelope.source_chain_id,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("NFT {} unlocked and TxId PDA closed for tx_id={}", mint, envelope.tx_id);
+    Ok(())
+}
+
+/// TX2 for foreign NFTs: verifies the message like process_message, then mints the
+/// wrapped NFT (creating its mint and Metaplex metadata on first arrival)
+/// `collection_hash` (keccak256 of the source collection) and `token_id` must match the payload
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope, signatures: Vec<MessageSignature>, collection_hash: [u8; 32], token_id: [u8; 32])]
+pub struct MintWrappedNft<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// NFT bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        seeds = [
+            WRAPPED_NFT_MINT_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            collection_hash.as_ref(),
+            token_id.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump,
+        mint::decimals = 0,
+        mint::authority = gateway
+    )]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + WrappedNft::SIZE,
+        seeds = [WRAPPED_NFT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub wrapped_nft: Account<'info, WrappedNft>,
+    
+    /// CHECK: Metaplex metadata PDA of the wrapped mint; created by CPI on first arrival
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    /// CHECK: Beneficiary wallet; must match the payload recipient
+    pub recipient: UncheckedAccount<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    
+    pub token_metadata_program: Program<'info, Metadata>,
+    
+    pub system_program: Program<'info, System>,
+    
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn mint_wrapped_nft(
+    ctx: Context<MintWrappedNft>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+    collection_hash: [u8; 32],
+    token_id: [u8; 32],
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_NFT_TRANSFER)?;
+    let payload = NftTransferPayload::decode(&envelope.on_chain_data)?;
+    require!(
+        keccak::hash(&payload.collection).to_bytes() == collection_hash
+            && payload.token_id == token_id,
+        GatewayError::NftMismatch
+    );
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        nft_recipient(&payload)?,
+        GatewayError::InvalidTokenRecipient
+    );
+    
+    // At most one wrapped token exists per foreign NFT
+    require!(ctx.accounts.mint.supply == 0, GatewayError::NftAlreadyMinted);
+    
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    let gateway_seeds: &[&[u8]] = &[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]];
+    
+    // First arrival: create Metaplex metadata from the relayed, hash-bound metadata
+    if ctx.accounts.metadata.data_is_empty() {
+        require!(!envelope.off_chain_data.is_empty(), GatewayError::MissingNftMetadata);
+        let nft_metadata = NftMetadata::decode(&envelope.off_chain_data)?;
+        require!(
+            nft_metadata.uri_hash() == payload.metadata_uri_hash,
+            GatewayError::MissingNftMetadata
+        );
+        
+        create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: ctx.accounts.metadata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    mint_authority: gateway.to_account_info(),
+                    payer: ctx.accounts.relayer.to_account_info(),
+                    update_authority: gateway.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                },
+                &[gateway_seeds],
+            ),
+            DataV2 {
+                name: nft_metadata.name,
+                symbol: nft_metadata.symbol,
+                uri: nft_metadata.uri,
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            true,
+            true,
+            None,
+        )?;
+        
+        let wrapped_nft = &mut ctx.accounts.wrapped_nft;
+        wrapped_nft.source_chain_id = envelope.source_chain_id;
+        wrapped_nft.source_collection = payload.collection.clone();
+        wrapped_nft.token_id = token_id;
+        wrapped_nft.metadata_uri_hash = payload.metadata_uri_hash;
+        wrapped_nft.mint = ctx.accounts.mint.key();
+        wrapped_nft.bump = ctx.bumps.wrapped_nft;
+    }
+    
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: gateway.to_account_info(),
+            },
+            &[gateway_seeds],
+        ),
+        1,
+    )?;
+    
+    emit!(WrappedNftMinted {
+        tx_id: envelope.tx_id,
+        mint: ctx.accounts.mint.key(),
+        recipient: ctx.accounts.recipient.key(),
+        source_chain_id: envelope.source_chain_id,
+        token_id,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Wrapped NFT minted and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
+
+/// Burn a wrapped NFT and send the original back to its source chain
+#[derive(Accounts)]
+pub struct BurnWrappedNft<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        seeds = [WRAPPED_NFT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = wrapped_nft.bump,
+        has_one = mint @ GatewayError::NftMismatch
+    )]
+    pub wrapped_nft: Account<'info, WrappedNft>,
+    
+    /// NFT bridge on the source chain
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            wrapped_nft.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// Optional source chain config validating the recipient address format
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            wrapped_nft.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Option<Account<'info, ChainConfig>>,
+    
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = holder
+    )]
+    pub holder_token_account: Account<'info, TokenAccount>,
+    
+    pub holder: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn burn_wrapped_nft(
+    ctx: Context<BurnWrappedNft>,
+    tx_id: u128,
+    recipient: Vec<u8>,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    let wrapped_nft = &ctx.accounts.wrapped_nft;
+    let dest_chain_id = wrapped_nft.source_chain_id;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(tx_id > 0, GatewayError::InvalidTxId);
+    
+    // Beneficiary must be a valid address for the source chain family
+    if let Some(chain_config) = ctx.accounts.chain_config.as_ref() {
+        validate_remote_address(chain_config.address_format, &recipient)?;
+    }
+    
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+    
+    let payload = NftTransferPayload {
+        collection: wrapped_nft.source_collection.clone(),
+        token_id: wrapped_nft.token_id,
+        metadata_uri_hash: wrapped_nft.metadata_uri_hash,
+        recipient: recipient.clone(),
+    };
+    let envelope = bridge_envelope(
+        gateway,
+        &ctx.accounts.bridge_peer,
+        tx_id,
+        dest_chain_id,
+        PAYLOAD_KIND_NFT_TRANSFER,
+        payload.encode()?,
+        Vec::new(),
+    )?;
+    
+    emit!(WrappedNftBurned {
+        tx_id,
+        mint: wrapped_nft.mint,
+        holder: ctx.accounts.holder.key(),
+        dest_chain_id,
+        recipient,
+    });
+    
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("Wrapped NFT burned: tx_id={}, dest_chain={}", tx_id, dest_chain_id);
+    Ok(())
+}
+
+/// Solana beneficiary of an inbound NFT transfer
+fn nft_recipient(payload: &NftTransferPayload) -> Result<Pubkey> {
+    Pubkey::try_from(payload.recipient.as_slice())
+        .map_err(|_| GatewayError::InvalidRemoteAddress.into())
+}
\ No newline at end of file
@@ -3,152 +3,878 @@ use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::GatewayError;
 use crate::events::MessageProcessed;
-use crate::state::{MessageGateway, TxIdPDA, SignerRegistry, MessageSignature};
+use crate::instructions::fee_config::pay_relayer_refund;
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::payloads::{
+    PAYLOAD_KIND_ASSET_METADATA, PAYLOAD_KIND_ISSUER_TRANSFER, PAYLOAD_KIND_NFT_TRANSFER,
+    PAYLOAD_KIND_PROGRAM_DELIVERY, PAYLOAD_KIND_QUERY_RESPONSE, PAYLOAD_KIND_TOKEN_BATCH,
+    PAYLOAD_KIND_TOKEN_RETURN, PAYLOAD_KIND_TOKEN_TRANSFER, PAYLOAD_KIND_TOKEN_VESTING,
+    PAYLOAD_KIND_VALUE_TRANSFER,
+};
+use crate::state::{
+    ChainConfig, Channel, FeeConfig, MessageEnvelope, MessageGateway, TxIdPDA, SignerRegistry,
+    MessageSignature, RegistryBundle, RelayerPool, ValidationResult,
+};
 use crate::utils::{
-    hash::create_message_hash_for_signing,
-    signature::validate_three_layer_signatures
+    address::validate_remote_address,
+    compact::decode_compact_message_lazy,
+    hash::{create_envelope_digest, create_instance_message_hash_for_signing},
+    history::{record_processed_message, MESSAGE_HISTORY_ACCOUNTS},
+    logging::verbose_msg,
+    signature::{
+        validate_layered_signatures, validate_signature_count, SignatureSource, VerificationPolicy,
+    },
+    verification::{load_verifier_registries, VerificationModule},
 };
 
 pub fn handler(
     ctx: Context<ProcessMessage>,
-    tx_id: u128,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    let project_registry = ctx.accounts.project_registry.as_deref().map(|registry| &**registry);
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        project_registry,
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    let relayer_refund = DeliveryTail {
+        gateway: &ctx.accounts.gateway,
+        tx_id_pda: &ctx.accounts.tx_id_pda,
+        relayer_pool: ctx.accounts.relayer_pool.as_deref_mut(),
+        payer: ctx.accounts.payer.as_deref(),
+        fee_config: ctx.accounts.fee_config.as_deref().map(|config| &**config),
+        fee_vault: ctx.accounts.fee_vault.as_ref(),
+        fee_vault_bump: ctx.bumps.fee_vault,
+        relayer: &ctx.accounts.relayer,
+        system_program: &ctx.accounts.system_program,
+    }
+    .finalize_delivery(
+        ctx.remaining_accounts,
+        extra_verifier_count(project_registry),
+        &envelope,
+        envelope.on_chain_data.len(),
+    )?;
+    
+    complete_processing(
+        ctx.accounts.channel.as_deref_mut(),
+        project_registry.is_some(),
+        ctx.accounts.relayer.key(),
+        relayer_refund,
+        &envelope,
+    )
+}
+
+/// TX2 with compact-encoded message arguments (see `utils::compact`)
+pub fn handler_compact(
+    ctx: Context<ProcessMessageCompact>,
     source_chain_id: u64,
-    dest_chain_id: u64,
-    sender: Vec<u8>,
-    recipient: Vec<u8>,
-    on_chain_data: Vec<u8>,
-    off_chain_data: Vec<u8>,
+    tx_id: u128,
+    message: Vec<u8>,
+) -> Result<()> {
+    let (envelope, signatures) = decode_compact_message_lazy(source_chain_id, tx_id, &message)?;
+    let project_registry = ctx.accounts.project_registry.as_deref().map(|registry| &**registry);
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        project_registry,
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    let relayer_refund = DeliveryTail {
+        gateway: &ctx.accounts.gateway,
+        tx_id_pda: &ctx.accounts.tx_id_pda,
+        relayer_pool: ctx.accounts.relayer_pool.as_deref_mut(),
+        payer: ctx.accounts.payer.as_deref(),
+        fee_config: ctx.accounts.fee_config.as_deref().map(|config| &**config),
+        fee_vault: ctx.accounts.fee_vault.as_ref(),
+        fee_vault_bump: ctx.bumps.fee_vault,
+        relayer: &ctx.accounts.relayer,
+        system_program: &ctx.accounts.system_program,
+    }
+    .finalize_delivery(
+        ctx.remaining_accounts,
+        extra_verifier_count(project_registry),
+        &envelope,
+        envelope.on_chain_data.len(),
+    )?;
+    
+    complete_processing(
+        ctx.accounts.channel.as_deref_mut(),
+        project_registry.is_some(),
+        ctx.accounts.relayer.key(),
+        relayer_refund,
+        &envelope,
+    )
+}
+
+/// Slim TX2: `envelope.on_chain_data` may be omitted (it is ignored), since the payload is
+/// bound by the signing hash TX1 verified and stored in the TxId PDA; every other field is
+/// checked against the envelope digest TX1 recorded
+pub fn handler_by_hash(
+    ctx: Context<ProcessMessage>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    let tx_id_pda = &ctx.accounts.tx_id_pda;
+    let project_registry = ctx.accounts.project_registry.as_deref().map(|registry| &**registry);
+    check_inbound_envelope(
+        &ctx.accounts.gateway,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &envelope,
+        signatures.len(),
+    )?;
+    require!(
+        create_envelope_digest(&envelope)? == tx_id_pda.envelope_digest,
+        GatewayError::InvalidMessageHash
+    );
+    
+    let (project_layer, extra_verifier_ids) =
+        project_layer(project_registry);
+    verify_inbound_signatures(
+        &ctx.accounts.gateway,
+        &**ctx.accounts.via_registry,
+        &**ctx.accounts.chain_registry,
+        project_layer,
+        extra_verifier_ids,
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+        &tx_id_pda.message_hash,
+        &tx_id_pda.ed25519_offsets,
+    )?;
+    
+    // The envelope's on_chain_data is not bound here, so it cannot size the refund
+    let relayer_refund = DeliveryTail {
+        gateway: &ctx.accounts.gateway,
+        tx_id_pda: &ctx.accounts.tx_id_pda,
+        relayer_pool: ctx.accounts.relayer_pool.as_deref_mut(),
+        payer: ctx.accounts.payer.as_deref(),
+        fee_config: ctx.accounts.fee_config.as_deref().map(|config| &**config),
+        fee_vault: ctx.accounts.fee_vault.as_ref(),
+        fee_vault_bump: ctx.bumps.fee_vault,
+        relayer: &ctx.accounts.relayer,
+        system_program: &ctx.accounts.system_program,
+    }
+    .finalize_delivery(
+        ctx.remaining_accounts,
+        extra_verifier_count(project_registry),
+        &envelope,
+        0,
+    )?;
+    
+    complete_processing(
+        ctx.accounts.channel.as_deref_mut(),
+        project_registry.is_some(),
+        ctx.accounts.relayer.key(),
+        relayer_refund,
+        &envelope,
+    )
+}
+
+/// TX2 validated against a registry bundle instead of the three registry accounts
+/// The bundle must have been synced at the gateway's current registry epoch
+pub fn handler_bundled(
+    ctx: Context<ProcessMessageBundled>,
+    envelope: MessageEnvelope,
     signatures: Vec<MessageSignature>,
 ) -> Result<()> {
     let gateway = &ctx.accounts.gateway;
+    let chain_config = ChainConfig::load_route(&ctx.accounts.chain_config)?;
+    check_inbound_envelope(gateway, chain_config.as_ref(), &envelope, signatures.len())?;
     
-    // Validate system is enabled
-    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    let bundle = ctx.accounts.registry_bundle.load()?;
+    require!(
+        bundle.is_current(gateway.registry_epoch),
+        GatewayError::StaleRegistryBundle
+    );
+    require!(
+        bundle.source_chain_id == envelope.source_chain_id,
+        GatewayError::InvalidRegistryBundle
+    );
     
-    // Validate destination chain matches gateway
+    let hash_scheme = ChainConfig::route_hash_scheme(chain_config.as_ref());
+    let message_hash = create_instance_message_hash_for_signing(
+        hash_scheme,
+        gateway.chain_id,
+        gateway.instance_id,
+        &envelope,
+    )?;
+    
+    verify_inbound_signatures(
+        gateway,
+        &bundle.via,
+        &bundle.chain,
+        bundle.project_layer().map(|layer| layer as &dyn VerificationModule),
+        bundle.extra_verifier_ids(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+        &message_hash,
+        &ctx.accounts.tx_id_pda.ed25519_offsets,
+    )?;
+    let has_project_layer = bundle.project_layer().is_some();
+    let extra_verifier_count = bundle.extra_verifier_ids().len();
+    drop(bundle);
+    
+    let relayer_refund = DeliveryTail {
+        gateway,
+        tx_id_pda: &ctx.accounts.tx_id_pda,
+        relayer_pool: ctx.accounts.relayer_pool.as_deref_mut(),
+        payer: ctx.accounts.payer.as_deref(),
+        fee_config: ctx.accounts.fee_config.as_deref().map(|config| &**config),
+        fee_vault: ctx.accounts.fee_vault.as_ref(),
+        fee_vault_bump: ctx.bumps.fee_vault,
+        relayer: &ctx.accounts.relayer,
+        system_program: &ctx.accounts.system_program,
+    }
+    .finalize_delivery(
+        ctx.remaining_accounts,
+        extra_verifier_count,
+        &envelope,
+        envelope.on_chain_data.len(),
+    )?;
+    
+    complete_processing(
+        ctx.accounts.channel.as_deref_mut(),
+        has_project_layer,
+        ctx.accounts.relayer.key(),
+        relayer_refund,
+        &envelope,
+    )
+}
+
+/// Record the message in the gateway's history tree (no-op while history is disabled)
+/// The history accounts follow the extra verifier registries in remaining_accounts
+pub(crate) fn record_history(
+    gateway: &MessageGateway,
+    tx_id_pda: &TxIdPDA,
+    remaining_accounts: &[AccountInfo],
+    extra_verifier_count: usize,
+    envelope: &MessageEnvelope,
+) -> Result<()> {
+    let history_accounts = remaining_accounts.get(extra_verifier_count..).unwrap_or_default();
+    record_processed_message(gateway, history_accounts, envelope, &tx_id_pda.message_hash)
+}
+
+/// Accounts of the TX2 tail every delivery path runs once its message is verified
+pub(crate) struct DeliveryTail<'a, 'info> {
+    pub gateway: &'a MessageGateway,
+    pub tx_id_pda: &'a Account<'info, TxIdPDA>,
+    pub relayer_pool: Option<&'a mut Account<'info, RelayerPool>>,
+    pub payer: Option<&'a AccountInfo<'info>>,
+    pub fee_config: Option<&'a FeeConfig>,
+    pub fee_vault: Option<&'a SystemAccount<'info>>,
+    pub fee_vault_bump: Option<u8>,
+    pub relayer: &'a Signer<'info>,
+    pub system_program: &'a Program<'info, System>,
+}
+
+impl DeliveryTail<'_, '_> {
+    /// Record the message in the history tree, return the TxId PDA rent to the pool or
+    /// payer that funded it, then pay the relayer refund for `payload_size` bytes of
+    /// on-chain data; returns the refund paid
+    pub fn finalize_delivery(
+        self,
+        remaining_accounts: &[AccountInfo],
+        extra_verifier_count: usize,
+        envelope: &MessageEnvelope,
+        payload_size: usize,
+    ) -> Result<u64> {
+        record_history(
+            self.gateway,
+            self.tx_id_pda,
+            remaining_accounts,
+            extra_verifier_count,
+            envelope,
+        )?;
+        settle_tx_id_rent(self.tx_id_pda, self.relayer_pool, self.payer)?;
+        pay_relayer_refund(
+            self.fee_config,
+            self.fee_vault,
+            self.fee_vault_bump,
+            &self.gateway.instance_seed(),
+            self.relayer,
+            self.system_program,
+            payload_size,
+        )
+    }
+}
+
+/// Number of extra verifier registries a live project registry requires
+pub(crate) fn extra_verifier_count(project_registry: Option<&SignerRegistry>) -> usize {
+    project_registry.map_or(0, |registry| registry.extra_verifiers.len())
+}
+
+/// Application accounts in remaining_accounts: those after the extra verifier registries
+/// and, while history is enabled, the message history accounts
+pub(crate) fn delivery_accounts<'a, 'info>(
+    gateway: &MessageGateway,
+    remaining_accounts: &'a [AccountInfo<'info>],
+    extra_verifier_count: usize,
+) -> &'a [AccountInfo<'info>] {
+    let history_count = if gateway.message_history_enabled() {
+        MESSAGE_HISTORY_ACCOUNTS
+    } else {
+        0
+    };
+    remaining_accounts
+        .get(extra_verifier_count + history_count..)
+        .unwrap_or_default()
+}
+
+/// Post-verification part of process_message: delivery-path restrictions, then channel
+/// routing and the MessageProcessed event
+fn complete_processing(
+    channel: Option<&mut Channel>,
+    has_project_layer: bool,
+    relayer: Pubkey,
+    relayer_refund: u64,
+    envelope: &MessageEnvelope,
+) -> Result<()> {
+    // Query responses, token/value transfers, vesting grants, returns, asset attestations
+    // and program deliveries have dedicated delivery paths (fulfill_query, release_tokens,
+    // release_token_batch, create_vesting_schedule, complete_issuer_transfer,
+    // return_token_transfer, release_value, attest_wrapped_asset, deliver_message) so the
+    // callback, release or CPI cannot be skipped
     require!(
-        dest_chain_id == gateway.chain_id,
-        GatewayError::InvalidDestChain
+        envelope.message_type != PAYLOAD_KIND_QUERY_RESPONSE
+            && envelope.message_type != PAYLOAD_KIND_TOKEN_TRANSFER
+            && envelope.message_type != PAYLOAD_KIND_TOKEN_BATCH
+            && envelope.message_type != PAYLOAD_KIND_TOKEN_RETURN
+            && envelope.message_type != PAYLOAD_KIND_TOKEN_VESTING
+            && envelope.message_type != PAYLOAD_KIND_ISSUER_TRANSFER
+            && envelope.message_type != PAYLOAD_KIND_VALUE_TRANSFER
+            && envelope.message_type != PAYLOAD_KIND_ASSET_METADATA
+            && envelope.message_type != PAYLOAD_KIND_PROGRAM_DELIVERY,
+        GatewayError::InvalidPayload
     );
     
-    // DOS protection: validate input sizes
+    // NFT transfers addressed to the gateway belong to the built-in NFT bridge
+    // (unlock_nft, mint_wrapped_nft); other NFT applications keep using this path
     require!(
-        sender.len() <= MAX_SENDER_SIZE,
-        GatewayError::SenderTooLong
+        envelope.message_type != PAYLOAD_KIND_NFT_TRANSFER
+            || envelope.recipient.as_slice() != crate::ID.as_ref(),
+        GatewayError::InvalidPayload
     );
+    
+    route_processed_message(channel, has_project_layer, relayer, relayer_refund, envelope)
+}
+
+/// Channel routing and the MessageProcessed event, shared with deliver_message
+pub(crate) fn route_processed_message(
+    channel: Option<&mut Channel>,
+    has_project_layer: bool,
+    relayer: Pubkey,
+    relayer_refund: u64,
+    envelope: &MessageEnvelope,
+) -> Result<()> {
+    let tx_id = envelope.tx_id;
+    let source_chain_id = envelope.source_chain_id;
+    
+    // Channel routing: binding, trust settings and ordering
+    if envelope.uses_channel() {
+        let channel = channel.ok_or(GatewayError::ChannelRequired)?;
+        channel.route_inbound(envelope, has_project_layer)?;
+    }
+    
+    // Emit event for successful processing
+    emit!(MessageProcessed {
+        tx_id,
+        source_chain_id,
+        off_chain_data_hash: envelope.resolved_off_chain_data_hash()?,
+        da_pointer: envelope.da_pointer.clone(),
+        relayer,
+        relayer_refund,
+       // processed_at: Clock::get()?.unix_timestamp,
+    });
+    
+    // Note: The TxId PDA will be closed automatically by Anchor's close constraint
+    // This reclaims rent (~0.002 SOL) back to relayer, or to the pool or payer that funded it
+    
+    msg!("Message processed and TxId PDA closed for tx_id={}", tx_id);
+    Ok(())
+}
+
+/// TX2 checks shared by every delivery path: system status, destination, envelope
+/// validity, expiry, protocol version, replay PDA and layered signatures
+/// Extra verifier registries are read from the front of `remaining_accounts`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn verify_inbound_message(
+    gateway: &MessageGateway,
+    tx_id_pda: &TxIdPDA,
+    chain_config: Option<&ChainConfig>,
+    via_registry: &SignerRegistry,
+    chain_registry: &SignerRegistry,
+    project_registry: Option<&SignerRegistry>,
+    remaining_accounts: &[AccountInfo],
+    instructions: &AccountInfo,
+    envelope: &MessageEnvelope,
+    signatures: &dyn SignatureSource,
+) -> Result<ValidationResult> {
+    check_inbound_envelope(gateway, chain_config, envelope, signatures.len())?;
+    
+    // Create message hash for signature validation (same route scheme as TX1)
+    let hash_scheme = ChainConfig::route_hash_scheme(chain_config);
+    let message_hash = create_instance_message_hash_for_signing(
+        hash_scheme,
+        gateway.chain_id,
+        gateway.instance_id,
+        envelope,
+    )?;
+    
+    let (project_layer, extra_verifier_ids) = project_layer(project_registry);
+    verify_inbound_signatures(
+        gateway,
+        via_registry,
+        chain_registry,
+        project_layer,
+        extra_verifier_ids,
+        remaining_accounts,
+        instructions,
+        envelope,
+        signatures,
+        &message_hash,
+        &tx_id_pda.ed25519_offsets,
+    )
+}
+
+/// Envelope checks of TX2 that do not depend on the signing hash
+fn check_inbound_envelope(
+    gateway: &MessageGateway,
+    chain_config: Option<&ChainConfig>,
+    envelope: &MessageEnvelope,
+    signature_count: usize,
+) -> Result<()> {
+    precheck_inbound(gateway, envelope, signature_count)?;
+    
+    // Sender must be a valid address for the source chain family
+    if let Some(chain_config) = chain_config {
+        validate_remote_address(chain_config.address_format, &envelope.sender)?;
+    }
+    
+    // Reject messages whose deadline has passed (first syscall on the path)
     require!(
-        recipient.len() <= MAX_RECIPIENT_SIZE,
-        GatewayError::RecipientTooLong
+        !envelope.is_expired(Clock::get()?.unix_timestamp),
+        GatewayError::MessageExpired
     );
+    
+    Ok(())
+}
+
+/// Field comparisons that reject most bad TX2s; they run before any syscall, hashing
+/// or instructions-sysvar scan so rejected relays cost as little compute as possible
+/// Ordered by cost and by how often relays fail them in practice
+/// The tx_id needs no check here: the TxId PDA's seeds bind it to the envelope
+pub fn precheck_inbound(
+    gateway: &MessageGateway,
+    envelope: &MessageEnvelope,
+    signature_count: usize,
+) -> Result<()> {
+    // Validate system is enabled
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    
+    // Validate destination chain matches gateway
     require!(
-        on_chain_data.len() <= MAX_ON_CHAIN_DATA_SIZE,
-        GatewayError::OnChainDataTooLarge
+        envelope.dest_chain_id == gateway.chain_id,
+        GatewayError::InvalidDestChain
     );
+    envelope.validate_chain_ids()?;
+    
     require!(
-        off_chain_data.len() <= MAX_OFF_CHAIN_DATA_SIZE,
-        GatewayError::OffChainDataTooLarge
+        gateway.allows_route(envelope.source_chain_id, envelope.dest_chain_id),
+        GatewayError::LoopbackDisabled
     );
     
-    // Verify TxId PDA exists (proves TX1 succeeded)
+    // Protocol version must be inside the range currently accepted by the gateway
     require!(
-        ctx.accounts.tx_id_pda.tx_id == tx_id,
-        GatewayError::InvalidTxId
+        gateway.supports_protocol_version(envelope.protocol_version),
+        GatewayError::UnsupportedProtocolVersion
     );
     
-    // Create message hash for signature validation
-    let message_hash = create_message_hash_for_signing(
-        tx_id,
-        source_chain_id,
-        dest_chain_id,
-        &sender,
-        &recipient,
-        &on_chain_data,
-        &off_chain_data,
+    // Attestation count bounds, before any signature is looked at
+    validate_signature_count(signature_count)?;
+    
+    // Envelope version and DOS size validation
+    envelope.validate()
+}
+
+/// Project layer of a live project registry and the extra verifier sets it requires
+fn project_layer(
+    project_registry: Option<&SignerRegistry>,
+) -> (Option<&dyn VerificationModule>, &[u8]) {
+    match project_registry {
+        Some(registry) => (Some(registry), registry.extra_verifiers.as_slice()),
+        None => (None, &[]),
+    }
+}
+
+/// Layered signature validation of `message_hash`, including the project's extra verifier sets
+#[allow(clippy::too_many_arguments)]
+fn verify_inbound_signatures(
+    gateway: &MessageGateway,
+    via_registry: &dyn VerificationModule,
+    chain_registry: &dyn VerificationModule,
+    project_registry: Option<&dyn VerificationModule>,
+    extra_verifier_ids: &[u8],
+    remaining_accounts: &[AccountInfo],
+    instructions: &AccountInfo,
+    envelope: &MessageEnvelope,
+    signatures: &dyn SignatureSource,
+    message_hash: &[u8; 32],
+    ed25519_offsets: &[u8; MAX_SIGNATURES_PER_MESSAGE],
+) -> Result<ValidationResult> {
+    // Extra verifier sets the project opted into (passed via remaining_accounts)
+    let extra_verifiers = load_verifier_registries(
+        remaining_accounts,
+        extra_verifier_ids,
+        envelope.source_chain_id,
+        &gateway.instance_seed(),
     )?;
     
     // THREE-LAYER SIGNATURE VALIDATION - Production Security
-    let validation_result = validate_three_layer_signatures(
-        &signatures,
-        &message_hash,
-        &ctx.accounts.via_registry,
-        &ctx.accounts.chain_registry,
-        ctx.accounts.project_registry.as_ref().map(|acc| acc.as_ref()),
-        &ctx.accounts.instructions,
+    let validation_result = validate_layered_signatures(
+        signatures,
+        message_hash,
+        via_registry,
+        chain_registry,
+        project_registry,
+        &extra_verifiers,
+        ed25519_offsets,
+        VerificationPolicy::for_gateway(gateway, Clock::get()?.unix_timestamp),
+        instructions,
     )?;
     
-    msg!(
+    verbose_msg!(
         "Message signature validation passed: VIA={}, Chain={}, Project={}, tx_id={}",
         validation_result.via_signatures,
         validation_result.chain_signatures,
         validation_result.project_signatures,
-        tx_id
+        envelope.tx_id
     );
     
-    // TODO: Future enhancements:
-    // - CPI to recipient program for message delivery
-    // - Gas refund processing via gas handler
+    Ok(validation_result)
+}
+
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct ProcessMessage<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
     
-    // Emit event for successful processing
-    emit!(MessageProcessed {
-        tx_id,
-        source_chain_id,
-        relayer: ctx.accounts.relayer.key(),
-       // processed_at: Clock::get()?.unix_timestamp,
-    });
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer, // Close and return rent to relayer (or its pool or payer)
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Box<Account<'info, TxIdPDA>>,
     
-    // Note: The TxId PDA will be closed automatically by Anchor's close constraint
-    // This reclaims rent (~0.002 SOL) back to relayer
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
     
-    msg!("Message processed and TxId PDA closed for tx_id={}", tx_id);
-    Ok(())
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Box<Account<'info, SignerRegistry>>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Box<Account<'info, SignerRegistry>>,
+    
+    /// Channel the message is routed through (required when envelope.sequence != 0)
+    #[account(
+        mut,
+        seeds = [
+            CHANNEL_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            envelope.recipient.as_slice(),
+            Channel::remote_app_seed(&envelope.sender).as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = channel.bump
+    )]
+    pub channel: Option<Account<'info, Channel>>,
+    
+    /// Optional project signer registry for application-level validation
+    /// Extra verifier registries it requires follow in remaining_accounts, then the
+    /// message history accounts while history is enabled
+    pub project_registry: Option<Box<Account<'info, SignerRegistry>>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent (required when TX1 used
+    /// create_tx_pda_pooled); the rent is returned to it instead of the relayer
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent (required when TX1 named a payer); the rent
+    /// is returned to it instead of the relayer
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// Source chain fee config and the fee vault, passed by relayers claiming the chain's
+    /// relayer refund (see set_relayer_refund)
+    #[account(
+        seeds = [
+            FEE_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = fee_config.bump
+    )]
+    pub fee_config: Option<Box<Account<'info, FeeConfig>>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<SystemAccount<'info>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
+/// Accounts of process_message_compact; the PDA seeds come from the Borsh-encoded
+/// `source_chain_id` and `tx_id` arguments, and the destination must be this gateway
+/// A passed channel is checked by `route_inbound` against the decoded envelope
 #[derive(Accounts)]
-#[instruction(tx_id: u128, source_chain_id: u64, dest_chain_id: u64, sender: Vec<u8>, recipient: Vec<u8>, on_chain_data: Vec<u8>, off_chain_data: Vec<u8>, signatures: Vec<MessageSignature>)]
-pub struct ProcessMessage<'info> {
+#[instruction(source_chain_id: u64, tx_id: u128)]
+pub struct ProcessMessageCompact<'info> {
     #[account(
-        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref()],
-        bump = gateway.bump
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
     )]
-    pub gateway: Account<'info, MessageGateway>,
+    pub gateway: Box<Account<'info, MessageGateway>>,
     
     /// TxId PDA that will be closed atomically
     #[account(
         mut,
-        close = relayer, // Close and return rent to relayer
+        close = relayer,
         seeds = [
             TX_SEED,
             source_chain_id.to_le_bytes().as_ref(),
-            &tx_id.to_le_bytes()
+            &tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
         ],
         bump = tx_id_pda.bump
     )]
-    pub tx_id_pda: Account<'info, TxIdPDA>,
+    pub tx_id_pda: Box<Account<'info, TxIdPDA>>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, source_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
     
     /// VIA signer registry for VIA-level validation
     #[account(
         seeds = [
             SIGNER_REGISTRY_SEED,
-            &crate::state::SignerRegistryType::VIA.discriminant().to_le_bytes(),
-            dest_chain_id.to_le_bytes().as_ref()
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            gateway.chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
         ],
         bump = via_registry.bump
     )]
-    pub via_registry: Account<'info, SignerRegistry>,
+    pub via_registry: Box<Account<'info, SignerRegistry>>,
     
     /// Chain signer registry for source chain validation
     #[account(
         seeds = [
             SIGNER_REGISTRY_SEED,
-            &crate::state::SignerRegistryType::Chain.discriminant().to_le_bytes(),
-            source_chain_id.to_le_bytes().as_ref()
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
         ],
         bump = chain_registry.bump
     )]
-    pub chain_registry: Account<'info, SignerRegistry>,
+    pub chain_registry: Box<Account<'info, SignerRegistry>>,
+    
+    /// Channel the message is routed through (required when the sequence is non-zero)
+    #[account(mut)]
+    pub channel: Option<Account<'info, Channel>>,
     
     /// Optional project signer registry for application-level validation
-    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    pub project_registry: Option<Box<Account<'info, SignerRegistry>>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// Source chain fee config for the relayer refund, with the fee vault below
+    #[account(
+        seeds = [FEE_CONFIG_SEED, source_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = fee_config.bump
+    )]
+    pub fee_config: Option<Box<Account<'info, FeeConfig>>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<SystemAccount<'info>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts of process_message_bundled: one registry bundle replaces the VIA, Chain and
+/// Project registry accounts; extra verifier registries (then the message history
+/// accounts) still follow in remaining_accounts
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct ProcessMessageBundled<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Box<Account<'info, MessageGateway>>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Box<Account<'info, TxIdPDA>>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// Snapshot of the route's signer registries (with or without the project layer)
+    #[account(
+        seeds = [
+            REGISTRY_BUNDLE_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &[registry_bundle.load()?.has_project],
+            gateway.instance_seed().as_ref()
+        ],
+        bump = registry_bundle.load()?.bump
+    )]
+    pub registry_bundle: AccountLoader<'info, RegistryBundle>,
+    
+    /// Channel the message is routed through (required when envelope.sequence != 0)
+    #[account(
+        mut,
+        seeds = [
+            CHANNEL_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            envelope.recipient.as_slice(),
+            Channel::remote_app_seed(&envelope.sender).as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = channel.bump
+    )]
+    pub channel: Option<Account<'info, Channel>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// Source chain fee config for the relayer refund, with the fee vault below
+    #[account(
+        seeds = [
+            FEE_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = fee_config.bump
+    )]
+    pub fee_config: Option<Box<Account<'info, FeeConfig>>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<SystemAccount<'info>>,
     
     #[account(mut)]
     pub relayer: Signer<'info>,
@@ -0,0 +1,379 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{QueryCancelled, QueryFulfilled, QueryRequested, SendRequested};
+use crate::instructions::process_message::{
+    delivery_accounts, extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::payloads::{
+    QueryCallback, QueryResponsePayload, PAYLOAD_KIND_QUERY_REQUEST, PAYLOAD_KIND_QUERY_RESPONSE,
+};
+use crate::state::{
+    ChainConfig, MessageEnvelope, MessageGateway, MessageSignature, PendingQuery, RelayerPool,
+    SignerRegistry, TxIdPDA,
+};
+
+/// Send a read-only query to a remote chain
+/// The response is delivered by validators through fulfill_query into `callback_program`
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct SendQuery<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PendingQuery::SIZE,
+        seeds = [
+            QUERY_SEED,
+            requester.key().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub query: Account<'info, PendingQuery>,
+    
+    /// Requesting application (program PDA or wallet); must be the envelope sender
+    pub requester: Signer<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn send_query(
+    ctx: Context<SendQuery>,
+    envelope: MessageEnvelope,
+    callback_program: Pubkey,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    
+    // Validate system is enabled
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    
+    // Validate inputs
+    require!(!envelope.recipient.is_empty(), GatewayError::EmptyRecipient);
+    require!(!envelope.on_chain_data.is_empty(), GatewayError::EmptyChainData);
+    require!(
+        envelope.message_type == PAYLOAD_KIND_QUERY_REQUEST,
+        GatewayError::InvalidPayload
+    );
+    
+    // Envelope must originate from this gateway and the requester
+    require!(
+        envelope.source_chain_id == gateway.chain_id,
+        GatewayError::InvalidSourceChain
+    );
+    require!(
+        gateway.allows_route(envelope.source_chain_id, envelope.dest_chain_id),
+        GatewayError::LoopbackDisabled
+    );
+    require!(
+        envelope.sender.as_slice() == ctx.accounts.requester.key().as_ref(),
+        GatewayError::SenderMismatch
+    );
+    
+    // Queries are not sequenced through channels
+    require!(!envelope.uses_channel(), GatewayError::InvalidSequence);
+    
+    // Envelope version and DOS size validation
+    envelope.validate()?;
+    
+    let query = &mut ctx.accounts.query;
+    query.requester = ctx.accounts.requester.key();
+    query.query_id = envelope.tx_id;
+    query.dest_chain_id = envelope.dest_chain_id;
+    query.responder_hash = PendingQuery::responder_hash(&envelope.recipient);
+    query.callback_program = callback_program;
+    query.payer = ctx.accounts.payer.key();
+    query.bump = ctx.bumps.query;
+    
+    emit!(QueryRequested {
+        query: query.key(),
+        query_id: query.query_id,
+        requester: query.requester,
+        dest_chain_id: query.dest_chain_id,
+        callback_program,
+    });
+    
+    let query_id = envelope.tx_id;
+    let dest_chain_id = envelope.dest_chain_id;
+    
+    // Relayers pick the request up like any other message
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("Query sent: query_id={}, dest_chain={}", query_id, dest_chain_id);
+    Ok(())
+}
+
+/// TX2 for query responses: verifies the signed response like process_message,
+/// then invokes the requester's callback program and closes the pending query
+/// remaining_accounts: the project's extra verifier registries, the message history accounts
+/// while history is enabled, then the callback's accounts
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct FulfillQuery<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Query being answered; the response recipient is the requester
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            QUERY_SEED,
+            envelope.recipient.as_slice(),
+            &query.query_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = query.bump,
+        has_one = payer @ GatewayError::QueryMismatch,
+        has_one = callback_program @ GatewayError::QueryMismatch
+    )]
+    pub query: Account<'info, PendingQuery>,
+    
+    /// CHECK: Rent refund destination, bound to the query by has_one
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+    
+    /// CHECK: Callback program, bound to the query by has_one
+    #[account(executable)]
+    pub callback_program: UncheckedAccount<'info>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub rent_payer: Option<SystemAccount<'info>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn fulfill_query<'info>(
+    ctx: Context<'_, '_, '_, 'info, FulfillQuery<'info>>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    let verifier_count = extra_verifier_count(ctx.accounts.project_registry.as_deref());
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        verifier_count,
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.rent_payer.as_deref(),
+    )?;
+    
+    require!(
+        envelope.message_type == PAYLOAD_KIND_QUERY_RESPONSE,
+        GatewayError::InvalidPayload
+    );
+    require!(!envelope.uses_channel(), GatewayError::InvalidSequence);
+    
+    // Response must come from the queried address on the queried chain
+    let query = &ctx.accounts.query;
+    let response = QueryResponsePayload::decode(&envelope.on_chain_data)?;
+    require!(
+        response.query_id == query.query_id
+            && envelope.source_chain_id == query.dest_chain_id
+            && PendingQuery::responder_hash(&envelope.sender) == query.responder_hash,
+        GatewayError::QueryMismatch
+    );
+    
+    // Callback accounts follow the extra verifier registries and the history accounts
+    let callback_accounts =
+        delivery_accounts(&ctx.accounts.gateway, ctx.remaining_accounts, verifier_count);
+    
+    let query_info = query.to_account_info();
+    let mut account_metas = vec![AccountMeta::new_readonly(query_info.key(), true)];
+    let mut account_infos = vec![query_info.clone()];
+    for account in callback_accounts {
+        account_metas.push(if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(ctx.accounts.callback_program.to_account_info());
+    
+    let callback = QueryCallback {
+        query_id: response.query_id,
+        source_chain_id: envelope.source_chain_id,
+        result: response.result,
+    };
+    let instruction = Instruction {
+        program_id: query.callback_program,
+        accounts: account_metas,
+        data: callback.instruction_data()?,
+    };
+    
+    // The PendingQuery PDA signs so the callback can authenticate the gateway
+    let query_id_bytes = query.query_id.to_le_bytes();
+    let instance_seed = ctx.accounts.gateway.instance_seed();
+    invoke_signed(
+        &instruction,
+        &account_infos,
+        &[&[
+            QUERY_SEED,
+            query.requester.as_ref(),
+            &query_id_bytes,
+            &instance_seed,
+            &[query.bump],
+        ]],
+    )?;
+    
+    emit!(QueryFulfilled {
+        query: query.key(),
+        query_id: query.query_id,
+        source_chain_id: envelope.source_chain_id,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Query fulfilled and TxId PDA closed for query_id={}", query.query_id);
+    Ok(())
+}
+
+/// Cancel an unanswered query and reclaim its rent (requester only)
+#[derive(Accounts)]
+pub struct CancelQuery<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        mut,
+        close = payer,
+        seeds = [
+            QUERY_SEED,
+            requester.key().as_ref(),
+            &query.query_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = query.bump,
+        has_one = requester @ GatewayError::QueryMismatch,
+        has_one = payer @ GatewayError::QueryMismatch
+    )]
+    pub query: Account<'info, PendingQuery>,
+    
+    pub requester: Signer<'info>,
+    
+    /// CHECK: Rent refund destination, bound to the query by has_one
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+pub fn cancel_query(ctx: Context<CancelQuery>) -> Result<()> {
+    let query = &ctx.accounts.query;
+    
+    emit!(QueryCancelled {
+        query: query.key(),
+        query_id: query.query_id,
+    });
+    
+    msg!("Query cancelled: query_id={}", query.query_id);
+    Ok(())
+}
\ No newline at end of file
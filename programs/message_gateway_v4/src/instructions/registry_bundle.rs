@@ -0,0 +1,205 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{GATEWAY_SEED, REGISTRY_BUNDLE_SEED, SIGNER_REGISTRY_SEED},
+    errors::GatewayError,
+    state::{MessageGateway, RegistryBundle, SignerRegistry, SignerRegistryType},
+};
+
+/// Create the registry bundle of an inbound route (permissionless; contents are copied
+/// from the canonical registries)
+#[derive(Accounts)]
+#[instruction(source_chain_id: u64, with_project: bool)]
+pub struct CreateRegistryBundle<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = RegistryBundle::SIZE,
+        seeds = [
+            REGISTRY_BUNDLE_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            &[with_project as u8],
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub registry_bundle: AccountLoader<'info, RegistryBundle>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// VIA signer registry of this gateway's chain
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            gateway.chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry of the source chain
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Project signer registry of the source chain (required when `with_project`)
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &SignerRegistryType::Project.discriminant()?.to_le_bytes(),
+            source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = project_registry.bump
+    )]
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_registry_bundle(
+    ctx: Context<CreateRegistryBundle>,
+    source_chain_id: u64,
+    with_project: bool,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let mut bundle = accounts.registry_bundle.load_init()?;
+    bundle.bump = ctx.bumps.registry_bundle;
+    snapshot_registries(
+        &mut bundle,
+        &accounts.gateway,
+        source_chain_id,
+        with_project,
+        &accounts.via_registry,
+        &accounts.chain_registry,
+        accounts.project_registry.as_ref(),
+    )
+}
+
+/// Re-sync a registry bundle after a registry change advanced the gateway's registry
+/// epoch (permissionless)
+#[derive(Accounts)]
+#[instruction(source_chain_id: u64, with_project: bool)]
+pub struct SyncRegistryBundle<'info> {
+    #[account(
+        mut,
+        seeds = [
+            REGISTRY_BUNDLE_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            &[with_project as u8],
+            gateway.instance_seed().as_ref()
+        ],
+        bump = registry_bundle.load()?.bump
+    )]
+    pub registry_bundle: AccountLoader<'info, RegistryBundle>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// VIA signer registry of this gateway's chain
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            gateway.chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry of the source chain
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Project signer registry of the source chain (required when `with_project`)
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &SignerRegistryType::Project.discriminant()?.to_le_bytes(),
+            source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = project_registry.bump
+    )]
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+}
+
+pub fn sync_registry_bundle(
+    ctx: Context<SyncRegistryBundle>,
+    source_chain_id: u64,
+    with_project: bool,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let mut bundle = accounts.registry_bundle.load_mut()?;
+    snapshot_registries(
+        &mut bundle,
+        &accounts.gateway,
+        source_chain_id,
+        with_project,
+        &accounts.via_registry,
+        &accounts.chain_registry,
+        accounts.project_registry.as_ref(),
+    )
+}
+
+fn snapshot_registries(
+    bundle: &mut RegistryBundle,
+    gateway: &MessageGateway,
+    source_chain_id: u64,
+    with_project: bool,
+    via_registry: &SignerRegistry,
+    chain_registry: &SignerRegistry,
+    project_registry: Option<&Account<SignerRegistry>>,
+) -> Result<()> {
+    require!(
+        with_project == project_registry.is_some(),
+        GatewayError::InvalidRegistryBundle
+    );
+    
+    bundle.snapshot(
+        gateway.registry_epoch,
+        source_chain_id,
+        via_registry,
+        chain_registry,
+        project_registry.map(|registry| (registry.key(), &**registry)),
+    )?;
+    
+    msg!(
+        "Synced registry bundle for chain {} (project layer: {}) at registry epoch {}",
+        source_chain_id,
+        with_project,
+        gateway.registry_epoch
+    );
+    
+    Ok(())
+}
\ No newline at end of file
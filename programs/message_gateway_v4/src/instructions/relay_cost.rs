@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+use crate::utils::cost::{self, RelayCostEstimate};
+
+/// Expected compute units, transaction sizes, fees and TxId PDA rent of relaying a message
+/// Read-only: simulate it and read the return data, or call `utils::cost` directly off-chain
+#[derive(Accounts)]
+pub struct EstimateRelayCost<'info> {
+    /// Rent the TxId PDA deposit is priced at (an Accounts struct needs a lifetime for the cpi module)
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn estimate_relay_cost(
+    ctx: Context<EstimateRelayCost>,
+    payload_size: u32,
+    signature_count: u8,
+) -> Result<RelayCostEstimate> {
+    cost::estimate_relay_cost(payload_size as usize, signature_count as usize, &ctx.accounts.rent)
+}
\ No newline at end of file
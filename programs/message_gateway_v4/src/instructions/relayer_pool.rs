@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::constants::{GATEWAY_SEED, RELAYER_POOL_SEED};
+use crate::errors::GatewayError;
+use crate::events::RelayerPoolUpdated;
+use crate::state::{MessageGateway, RelayerPool, RentRefundKind, TxIdPDA};
+
+/// Add lamports to a relayer's rent pool, opening the pool on first use
+#[derive(Accounts)]
+pub struct DepositRelayerPool<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + RelayerPool::SIZE,
+        seeds = [RELAYER_POOL_SEED, relayer.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub relayer_pool: Account<'info, RelayerPool>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deposit_relayer_pool(ctx: Context<DepositRelayerPool>, amount: u64) -> Result<()> {
+    require!(amount > 0, GatewayError::InvalidAmount);
+    
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.relayer.to_account_info(),
+                to: ctx.accounts.relayer_pool.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    let relayer_pool = &mut ctx.accounts.relayer_pool;
+    relayer_pool.relayer = ctx.accounts.relayer.key();
+    relayer_pool.balance = relayer_pool
+        .balance
+        .checked_add(amount)
+        .ok_or(GatewayError::InvalidAmount)?;
+    relayer_pool.bump = ctx.bumps.relayer_pool;
+    
+    emit_pool_update(relayer_pool);
+    Ok(())
+}
+
+/// Withdraw lamports from a relayer's rent pool (relayer only)
+/// Rent still lent to open TxId PDAs cannot be withdrawn until they close
+#[derive(Accounts)]
+pub struct WithdrawRelayerPool<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        mut,
+        seeds = [RELAYER_POOL_SEED, relayer.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = relayer_pool.bump,
+        has_one = relayer @ GatewayError::UnauthorizedAuthority
+    )]
+    pub relayer_pool: Account<'info, RelayerPool>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+}
+
+pub fn withdraw_relayer_pool(ctx: Context<WithdrawRelayerPool>, amount: u64) -> Result<()> {
+    let relayer_pool = &mut ctx.accounts.relayer_pool;
+    require!(
+        amount > 0 && amount <= relayer_pool.balance,
+        GatewayError::InvalidAmount
+    );
+    
+    relayer_pool.balance -= amount;
+    move_pool_lamports(
+        &relayer_pool.to_account_info(),
+        &ctx.accounts.relayer.to_account_info(),
+        amount,
+    )?;
+    
+    emit_pool_update(relayer_pool);
+    Ok(())
+}
+
+/// Reimburse the relayer for the rent of the TxId PDA it just created, lending it from
+/// its pool, and mark the PDA so closing it pays the pool back
+pub(crate) fn advance_tx_id_rent(
+    tx_id_pda: &mut Account<TxIdPDA>,
+    relayer_pool: &mut Account<RelayerPool>,
+    relayer: &AccountInfo,
+) -> Result<()> {
+    let rent = tx_id_pda.to_account_info().lamports();
+    relayer_pool.advance_rent(rent)?;
+    move_pool_lamports(&relayer_pool.to_account_info(), relayer, rent)?;
+    tx_id_pda.rent_refund = relayer_pool.key();
+    tx_id_pda.rent_refund_kind = RentRefundKind::Pool;
+    Ok(())
+}
+
+/// Reimburse the relayer for the rent of the TxId PDA it just created from a separate
+/// payer, and mark the PDA so closing it refunds the payer (no-op without a payer, or when
+/// the payer is the relayer itself)
+pub(crate) fn fund_tx_id_rent<'info>(
+    tx_id_pda: &mut Account<'info, TxIdPDA>,
+    payer: Option<&Signer<'info>>,
+    relayer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let Some(payer) = payer.filter(|payer| payer.key() != relayer.key()) else {
+        return Ok(());
+    };
+    
+    let rent = tx_id_pda.to_account_info().lamports();
+    transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: relayer.to_account_info(),
+            },
+        ),
+        rent,
+    )?;
+    tx_id_pda.rent_refund = payer.key();
+    tx_id_pda.rent_refund_kind = RentRefundKind::Payer;
+    Ok(())
+}
+
+/// Return the rent of a TxId PDA about to be closed to the payer that funded it, or to the
+/// pool that advanced it (no-op for rent the relayer paid itself); the payer or pool must
+/// be the one recorded in TX1
+pub(crate) fn settle_tx_id_rent(
+    tx_id_pda: &Account<TxIdPDA>,
+    relayer_pool: Option<&mut Account<RelayerPool>>,
+    payer: Option<&AccountInfo>,
+) -> Result<()> {
+    let tx_id_info = tx_id_pda.to_account_info();
+    let rent = tx_id_info.lamports();
+    
+    match tx_id_pda.rent_refund_kind {
+        RentRefundKind::Relayer => Ok(()),
+        RentRefundKind::Payer => {
+            let payer = payer.ok_or(GatewayError::InvalidRentPayer)?;
+            require_keys_eq!(payer.key(), tx_id_pda.rent_refund, GatewayError::InvalidRentPayer);
+            move_pool_lamports(&tx_id_info, payer, rent)
+        }
+        RentRefundKind::Pool => {
+            let relayer_pool = relayer_pool.ok_or(GatewayError::InvalidRelayerPool)?;
+            require_keys_eq!(
+                relayer_pool.key(),
+                tx_id_pda.rent_refund,
+                GatewayError::InvalidRelayerPool
+            );
+            
+            move_pool_lamports(&tx_id_info, &relayer_pool.to_account_info(), rent)?;
+            relayer_pool.settle_rent(rent)
+        }
+    }
+}
+
+fn emit_pool_update(relayer_pool: &RelayerPool) {
+    emit!(RelayerPoolUpdated {
+        relayer: relayer_pool.relayer,
+        balance: relayer_pool.balance,
+        rent_outstanding: relayer_pool.rent_outstanding,
+    });
+    
+    msg!(
+        "Relayer {} pool: {} ({} lent)",
+        relayer_pool.relayer,
+        relayer_pool.balance,
+        relayer_pool.rent_outstanding
+    );
+}
+
+/// Move lamports out of a program-owned RelayerPool or TxId PDA account
+fn move_pool_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(GatewayError::InvalidAmount)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(GatewayError::InvalidAmount)?;
+    Ok(())
+}
\ No newline at end of file
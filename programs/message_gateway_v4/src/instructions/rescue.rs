@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{RescueCancelled, RescueExecuted, RescueRequested};
+use crate::state::{MessageGateway, RescueRequest};
+use crate::utils::token::transfer_checked_with_hook;
+
+/// Queue an emergency withdrawal from a token escrow (admin only)
+/// The rescue becomes executable after RESCUE_TIMELOCK_SECONDS
+#[derive(Accounts)]
+pub struct RequestRescue<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + RescueRequest::SIZE,
+        seeds = [RESCUE_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub rescue: Account<'info, RescueRequest>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = gateway,
+        associated_token::token_program = token_program
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn request_rescue(ctx: Context<RequestRescue>, amount: u64, reason: String) -> Result<()> {
+    require!(amount > 0, GatewayError::InvalidAmount);
+    require!(amount <= ctx.accounts.escrow.amount, GatewayError::InvalidAmount);
+    require!(
+        reason.len() <= MAX_RESCUE_REASON_LEN,
+        GatewayError::RescueReasonTooLong
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    let rescue = &mut ctx.accounts.rescue;
+    rescue.mint = ctx.accounts.mint.key();
+    rescue.destination = ctx.accounts.destination.key();
+    rescue.amount = amount;
+    rescue.reason = reason;
+    rescue.requested_at = now;
+    rescue.execute_after = now
+        .checked_add(RESCUE_TIMELOCK_SECONDS)
+        .ok_or(GatewayError::InvalidAmount)?;
+    rescue.bump = ctx.bumps.rescue;
+    
+    emit!(RescueRequested {
+        mint: rescue.mint,
+        escrow: ctx.accounts.escrow.key(),
+        destination: rescue.destination,
+        amount,
+        escrow_balance: ctx.accounts.escrow.amount,
+        reason: rescue.reason.clone(),
+        requested_at: now,
+        execute_after: rescue.execute_after,
+        authority: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Rescue requested for mint {}: amount={}", rescue.mint, amount);
+    Ok(())
+}
+
+/// Execute a queued rescue once its timelock has elapsed (admin only)
+/// remaining_accounts: transfer-hook accounts for Token-2022 mints with a hook
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [RESCUE_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = rescue.bump,
+        has_one = mint @ GatewayError::TokenMismatch,
+        has_one = destination @ GatewayError::InvalidTokenRecipient
+    )]
+    pub rescue: Account<'info, RescueRequest>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = gateway,
+        associated_token::token_program = token_program
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn rescue_tokens<'info>(ctx: Context<'_, '_, '_, 'info, RescueTokens<'info>>) -> Result<()> {
+    let rescue = &ctx.accounts.rescue;
+    let now = Clock::get()?.unix_timestamp;
+    require!(rescue.is_executable(now), GatewayError::RescueTimelockActive);
+    
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.destination.to_account_info(),
+        &gateway.to_account_info(),
+        ctx.remaining_accounts,
+        &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]],
+        rescue.amount,
+        ctx.accounts.mint.decimals,
+    )?;
+    
+    ctx.accounts.escrow.reload()?;
+    
+    emit!(RescueExecuted {
+        mint: rescue.mint,
+        escrow: ctx.accounts.escrow.key(),
+        destination: rescue.destination,
+        amount: rescue.amount,
+        remaining_escrow_balance: ctx.accounts.escrow.amount,
+        reason: rescue.reason.clone(),
+        requested_at: rescue.requested_at,
+        executed_at: now,
+        authority: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Rescue executed for mint {}: amount={}", rescue.mint, rescue.amount);
+    Ok(())
+}
+
+/// Cancel a queued rescue (admin only)
+#[derive(Accounts)]
+pub struct CancelRescue<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [RESCUE_SEED, rescue.mint.as_ref(), gateway.instance_seed().as_ref()],
+        bump = rescue.bump
+    )]
+    pub rescue: Account<'info, RescueRequest>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn cancel_rescue(ctx: Context<CancelRescue>) -> Result<()> {
+    let rescue = &ctx.accounts.rescue;
+    
+    emit!(RescueCancelled {
+        mint: rescue.mint,
+        destination: rescue.destination,
+        amount: rescue.amount,
+        authority: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Rescue cancelled for mint {}", rescue.mint);
+    Ok(())
+}
\ No newline at end of file
@@ -1,47 +1,90 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
 
 use crate::constants::*;
 use crate::errors::GatewayError;
-use crate::events::SendRequested;
-use crate::state::MessageGateway;
+use crate::events::{SendFeePaid, SendRequested, ValueLocked};
+use crate::payloads::{ValueTransferPayload, PAYLOAD_KIND_VALUE_TRANSFER};
+use crate::state::{ChainConfig, Channel, FeeConfig, MessageEnvelope, MessageGateway};
+use crate::utils::{
+    address::validate_remote_address,
+    pyth::{load_pyth_price, usd_to_token_amount, LAMPORTS_DECIMALS},
+};
 
 pub fn handler(
     ctx: Context<SendMessage>,
-    tx_id: u128,
-    recipient: Vec<u8>,
-    dest_chain_id: u64,
-    chain_data: Vec<u8>,
+    mut envelope: MessageEnvelope,
     confirmations: u16,
 ) -> Result<()> {
-    let gateway = &mut ctx.accounts.gateway;
+    let gateway = &ctx.accounts.gateway;
     
     // Validate system is enabled
     require!(gateway.system_enabled, GatewayError::SystemDisabled);
     
     // Validate inputs
-    require!(!recipient.is_empty(), GatewayError::EmptyRecipient);
-    require!(!chain_data.is_empty(), GatewayError::EmptyChainData);
+    require!(!envelope.recipient.is_empty(), GatewayError::EmptyRecipient);
+    require!(!envelope.on_chain_data.is_empty(), GatewayError::EmptyChainData);
     
-    // DOS protection: validate data sizes
+    // Envelope must originate from this gateway and the signing sender
+    envelope.validate_chain_ids()?;
     require!(
-        recipient.len() <= MAX_RECIPIENT_SIZE,
-        GatewayError::RecipientTooLong
+        envelope.source_chain_id == gateway.chain_id,
+        GatewayError::InvalidSourceChain
     );
     require!(
-        chain_data.len() <= MAX_ON_CHAIN_DATA_SIZE,
-        GatewayError::OnChainDataTooLarge
+        gateway.allows_route(envelope.source_chain_id, envelope.dest_chain_id),
+        GatewayError::LoopbackDisabled
     );
+    require!(
+        envelope.sender.as_slice() == ctx.accounts.sender.key().as_ref(),
+        GatewayError::SenderMismatch
+    );
+    
+    // Envelope version and DOS size validation
+    envelope.validate()?;
+    
+    // Destination must be a configured chain that accepts the message, and the recipient
+    // a valid address for its chain family
+    let chain_config = ctx
+        .accounts
+        .chain_config
+        .as_ref()
+        .ok_or(GatewayError::UnsupportedChain)?;
+    chain_config.validate_send(&envelope, confirmations)?;
+    validate_remote_address(chain_config.address_format, &envelope.recipient)?;
+    
+    // Sequenced messages must be routed through the sender's channel
+    if envelope.uses_channel() {
+        let channel = ctx
+            .accounts
+            .channel
+            .as_mut()
+            .ok_or(GatewayError::ChannelRequired)?;
+        channel.route_outbound(&envelope, &ctx.accounts.sender.key())?;
+    }
+    
+    // Per-destination lamport fee into the fee vault, then the USD-denominated send fee
+    // converted to lamports at the current Pyth price
+    let fee_paid = charge_lamport_fee(ctx.accounts, &envelope)?
+        .checked_add(charge_send_fee(ctx.accounts, &envelope)?)
+        .ok_or(GatewayError::InvalidAmount)?;
+    
+    // Native value attached to the message is locked in the SOL vault
+    if envelope.message_type == PAYLOAD_KIND_VALUE_TRANSFER {
+        lock_value(ctx.accounts, &envelope)?;
+    }
     
-    // tx_id is provided as parameter
+    // Publish the commitment so relayers can submit commit-only envelopes downstream
+    envelope.off_chain_data_hash = envelope.resolved_off_chain_data_hash()?;
+    
+    let tx_id = envelope.tx_id;
+    let dest_chain_id = envelope.dest_chain_id;
     
     // Emit event for off-chain processing
     emit!(SendRequested {
-        tx_id,
-        sender: ctx.accounts.sender.key().to_bytes(),
-        recipient: recipient.clone(),
-        dest_chain_id,
-        chain_data: chain_data.clone(),
+        envelope,
         confirmations,
+        fee_paid,
         // timestamp: Clock::get()?.unix_timestamp,
     });
     
@@ -49,14 +92,208 @@ pub fn handler(
     Ok(())
 }
 
+/// Charge the destination's FeeConfig fee in lamports, paid into the fee vault; returns
+/// the lamports charged
+/// The FeeConfig address is fixed by its seeds, so senders cannot skip the fee by omitting
+/// it; while nothing is initialized there the route has no lamport fee
+fn charge_lamport_fee(accounts: &SendMessage, envelope: &MessageEnvelope) -> Result<u64> {
+    let fee_config_info = accounts.fee_config.to_account_info();
+    if *fee_config_info.owner != crate::ID {
+        return Ok(0);
+    }
+    let fee_config = FeeConfig::try_deserialize(&mut &fee_config_info.try_borrow_data()?[..])?;
+    let lamports = fee_config.fee_for(envelope.on_chain_data.len())?;
+    if lamports == 0 {
+        return Ok(0);
+    }
+    
+    let (Some(fee_vault), Some(fee_payer), Some(system_program)) = (
+        accounts.fee_vault.as_ref(),
+        accounts.fee_payer.as_ref(),
+        accounts.system_program.as_ref(),
+    ) else {
+        return err!(GatewayError::MissingFeeAccounts);
+    };
+    
+    transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: fee_payer.to_account_info(),
+                to: fee_vault.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+    
+    Ok(lamports)
+}
+
+/// Charge the destination route's USD send fee in lamports, paid into the fee vault;
+/// returns the lamports charged
+/// The handler has already required the destination chain config, so senders cannot skip
+/// the fee by omitting it
+fn charge_send_fee(accounts: &SendMessage, envelope: &MessageEnvelope) -> Result<u64> {
+    let gateway = &accounts.gateway;
+    let fees_configured = gateway.price_feed_id != [0u8; 32];
+    
+    let send_fee_usd = accounts
+        .chain_config
+        .as_ref()
+        .map_or(0, |chain_config| chain_config.send_fee_usd);
+    if send_fee_usd == 0 {
+        return Ok(0);
+    }
+    require!(fees_configured, GatewayError::InvalidPriceFeed);
+    
+    let (Some(price_update), Some(fee_vault), Some(fee_payer), Some(system_program)) = (
+        accounts.price_update.as_ref(),
+        accounts.fee_vault.as_ref(),
+        accounts.fee_payer.as_ref(),
+        accounts.system_program.as_ref(),
+    ) else {
+        return err!(GatewayError::MissingFeeAccounts);
+    };
+    
+    let price = load_pyth_price(
+        price_update,
+        &gateway.price_feed_id,
+        Clock::get()?.unix_timestamp,
+        gateway.max_price_age,
+    )?;
+    let lamports = usd_to_token_amount(send_fee_usd, &price, LAMPORTS_DECIMALS)?;
+    
+    transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: fee_payer.to_account_info(),
+                to: fee_vault.to_account_info(),
+            },
+        ),
+        lamports,
+    )?;
+    
+    emit!(SendFeePaid {
+        payer: fee_payer.key(),
+        dest_chain_id: envelope.dest_chain_id,
+        fee_usd: send_fee_usd,
+        lamports,
+    });
+    
+    Ok(lamports)
+}
+
+/// Move the payload's lamports from the fee payer into the gateway SOL vault
+fn lock_value(accounts: &SendMessage, envelope: &MessageEnvelope) -> Result<()> {
+    let payload = ValueTransferPayload::decode(&envelope.on_chain_data)?;
+    
+    let (Some(sol_vault), Some(fee_payer), Some(system_program)) = (
+        accounts.sol_vault.as_ref(),
+        accounts.fee_payer.as_ref(),
+        accounts.system_program.as_ref(),
+    ) else {
+        return err!(GatewayError::MissingValueAccounts);
+    };
+    
+    transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: fee_payer.to_account_info(),
+                to: sol_vault.to_account_info(),
+            },
+        ),
+        payload.amount,
+    )?;
+    
+    emit!(ValueLocked {
+        tx_id: envelope.tx_id,
+        payer: fee_payer.key(),
+        amount: payload.amount,
+        dest_chain_id: envelope.dest_chain_id,
+        recipient: payload.recipient,
+    });
+    
+    Ok(())
+}
+
 #[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
 pub struct SendMessage<'info> {
     #[account(
         mut,
-        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref()],
-        bump = gateway.bump
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
     )]
     pub gateway: Account<'info, MessageGateway>,
     
     pub sender: Signer<'info>,
+    
+    /// Destination chain config: send limits and recipient address format
+    /// Optional in the accounts only so a missing one fails as UnsupportedChain
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Option<Account<'info, ChainConfig>>,
+    
+    /// Lamport fee schedule of the destination chain, at its fixed address whether or not
+    /// one is set
+    /// CHECK: Address fixed by the seeds; read as a FeeConfig only when the gateway owns it
+    #[account(
+        seeds = [
+            FEE_CONFIG_SEED,
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub fee_config: UncheckedAccount<'info>,
+    
+    /// Optional channel between the sender and the remote recipient application
+    #[account(
+        mut,
+        seeds = [
+            CHANNEL_SEED,
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            sender.key().as_ref(),
+            Channel::remote_app_seed(&envelope.recipient).as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = channel.bump
+    )]
+    pub channel: Option<Account<'info, Channel>>,
+    
+    /// Pyth PriceUpdateV2 account for the gateway's SOL/USD feed (required when a fee applies)
+    /// CHECK: Owner, discriminator, feed id and freshness are validated in load_pyth_price
+    pub price_update: Option<UncheckedAccount<'info>>,
+    
+    /// Pays the send fees and attached value (required when any applies)
+    #[account(mut)]
+    pub fee_payer: Option<Signer<'info>>,
+    
+    /// Gateway SOL vault receiving attached value (required for value transfers)
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump = gateway.sol_vault_bump
+    )]
+    pub sol_vault: Option<SystemAccount<'info>>,
+    
+    /// Gateway fee vault receiving the lamport fee and the USD send fee (required when
+    /// either applies)
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<SystemAccount<'info>>,
+    
+    pub system_program: Option<Program<'info, System>>,
 }
\ No newline at end of file
@@ -1,8 +1,13 @@
 use anchor_lang::prelude::*;
 use crate::{
-    constants::{SIGNER_REGISTRY_SEED, MAX_SIGNERS_PER_REGISTRY},
+    constants::{
+        SIGNER_REGISTRY_SEED, MAX_SIGNERS_PER_REGISTRY, MAX_EXTRA_VERIFIERS, MAX_VERIFIER_ID,
+        MAX_SHADOW_TRIAL_SECONDS,
+    },
     errors::GatewayError,
+    events::RegistryShadowSet,
     state::{MessageGateway, SignerRegistry, SignerRegistryType},
+    utils::logging::verbose_msg,
 };
 
 /// Initialize a signer registry for a specific tier and chain
@@ -15,16 +20,18 @@ pub struct InitializeSignerRegistry<'info> {
         space = SignerRegistry::space(MAX_SIGNERS_PER_REGISTRY),
         seeds = [
             SIGNER_REGISTRY_SEED,
-            &registry_type.discriminant().to_le_bytes(),
-            &chain_id.to_le_bytes()
+            &registry_type.discriminant()?.to_le_bytes(),
+            &chain_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
         ],
         bump
     )]
     pub signer_registry: Account<'info, SignerRegistry>,
     
     #[account(
-        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes()],
+        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes(), gateway.instance_seed().as_ref()],
         bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
         has_one = authority @ GatewayError::UnauthorizedAuthority
     )]
     pub gateway: Account<'info, MessageGateway>,
@@ -42,6 +49,39 @@ pub fn initialize_signer_registry(
     initial_signers: Vec<Pubkey>,
     required_signatures: u8,
 ) -> Result<()> {
+    let signer_count = initial_signers.len();
+    configure_signer_registry(
+        &mut ctx.accounts.signer_registry,
+        registry_type,
+        chain_id,
+        ctx.accounts.authority.key(),
+        initial_signers,
+        required_signatures,
+        ctx.bumps.signer_registry,
+    )?;
+    
+    msg!(
+        "Initialized {:?} signer registry for chain {} with {} signers, requiring {} signatures",
+        registry_type,
+        chain_id,
+        signer_count,
+        required_signatures
+    );
+    
+    Ok(())
+}
+
+/// Validate and write a new registry's signer set, shared with `bootstrap_test_env`
+pub(crate) fn configure_signer_registry(
+    registry: &mut SignerRegistry,
+    registry_type: SignerRegistryType,
+    chain_id: u64,
+    authority: Pubkey,
+    initial_signers: Vec<Pubkey>,
+    required_signatures: u8,
+    bump: u8,
+) -> Result<()> {
+    require!(registry_type.is_valid(), GatewayError::InvalidSignerRegistryType);
     require!(!initial_signers.is_empty(), GatewayError::InsufficientSignatures);
     require!(
         initial_signers.len() <= MAX_SIGNERS_PER_REGISTRY,
@@ -52,23 +92,18 @@ pub fn initialize_signer_registry(
         GatewayError::InvalidThreshold
     );
     
-    let registry = &mut ctx.accounts.signer_registry;
-    registry.registry_type = registry_type.clone();
-    registry.authority = ctx.accounts.authority.key();
-    registry.signers = initial_signers.clone();
+    registry.registry_type = registry_type;
+    registry.authority = authority;
+    registry.signers = initial_signers;
     registry.required_signatures = required_signatures;
     registry.chain_id = chain_id;
     registry.enabled = true;
-    registry.bump = ctx.bumps.signer_registry;
-    
-    msg!(
-        "Initialized {:?} signer registry for chain {} with {} signers, requiring {} signatures",
-        registry_type,
-        chain_id,
-        initial_signers.len(),
-        required_signatures
-    );
-    
+    registry.bump = bump;
+    registry.extra_verifiers = Vec::new();
+    registry.shadow_until = 0;
+    registry.shadow_enforced_signatures = 0;
+    registry.created_slot = Clock::get()?.slot;
+    registry.previous_required_signatures = required_signatures;
     Ok(())
 }
 
@@ -80,8 +115,9 @@ pub struct UpdateSigners<'info> {
         mut,
         seeds = [
             SIGNER_REGISTRY_SEED,
-            &registry_type.discriminant().to_le_bytes(),
-            &chain_id.to_le_bytes()
+            &registry_type.discriminant()?.to_le_bytes(),
+            &chain_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
         ],
         bump = signer_registry.bump,
         has_one = authority @ GatewayError::UnauthorizedAuthority
@@ -89,8 +125,10 @@ pub struct UpdateSigners<'info> {
     pub signer_registry: Account<'info, SignerRegistry>,
     
     #[account(
-        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes()],
+        mut,
+        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes(), gateway.instance_seed().as_ref()],
         bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
         has_one = authority @ GatewayError::UnauthorizedAuthority
     )]
     pub gateway: Account<'info, MessageGateway>,
@@ -117,7 +155,7 @@ pub fn update_signers(
     
     let registry = &mut ctx.accounts.signer_registry;
     
-    msg!(
+    verbose_msg!(
         "Updating {:?} registry: old signers count={}, new signers count={}",
         registry.registry_type,
         registry.signers.len(),
@@ -125,7 +163,7 @@ pub fn update_signers(
     );
     
     registry.signers = new_signers;
-    registry.required_signatures = new_required_signatures;
+    registry.set_required_signatures(new_required_signatures, Clock::get()?.unix_timestamp);
     
     // Validate the new configuration
     registry.validate_threshold()?;
@@ -137,6 +175,8 @@ pub fn update_signers(
         new_required_signatures
     );
     
+    ctx.accounts.gateway.advance_registry_epoch();
+    
     Ok(())
 }
 
@@ -148,8 +188,9 @@ pub struct AddSigner<'info> {
         mut,
         seeds = [
             SIGNER_REGISTRY_SEED,
-            &registry_type.discriminant().to_le_bytes(),
-            &chain_id.to_le_bytes()
+            &registry_type.discriminant()?.to_le_bytes(),
+            &chain_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
         ],
         bump = signer_registry.bump,
         has_one = authority @ GatewayError::UnauthorizedAuthority
@@ -157,8 +198,10 @@ pub struct AddSigner<'info> {
     pub signer_registry: Account<'info, SignerRegistry>,
     
     #[account(
-        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes()],
+        mut,
+        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes(), gateway.instance_seed().as_ref()],
         bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
         has_one = authority @ GatewayError::UnauthorizedAuthority
     )]
     pub gateway: Account<'info, MessageGateway>,
@@ -192,6 +235,8 @@ pub fn add_signer(
         registry.signers.len()
     );
     
+    ctx.accounts.gateway.advance_registry_epoch();
+    
     Ok(())
 }
 
@@ -203,8 +248,9 @@ pub struct RemoveSigner<'info> {
         mut,
         seeds = [
             SIGNER_REGISTRY_SEED,
-            &registry_type.discriminant().to_le_bytes(),
-            &chain_id.to_le_bytes()
+            &registry_type.discriminant()?.to_le_bytes(),
+            &chain_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
         ],
         bump = signer_registry.bump,
         has_one = authority @ GatewayError::UnauthorizedAuthority
@@ -212,8 +258,10 @@ pub struct RemoveSigner<'info> {
     pub signer_registry: Account<'info, SignerRegistry>,
     
     #[account(
-        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes()],
+        mut,
+        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes(), gateway.instance_seed().as_ref()],
         bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
         has_one = authority @ GatewayError::UnauthorizedAuthority
     )]
     pub gateway: Account<'info, MessageGateway>,
@@ -247,6 +295,8 @@ pub fn remove_signer(
         registry.signers.len()
     );
     
+    ctx.accounts.gateway.advance_registry_epoch();
+    
     Ok(())
 }
 
@@ -258,8 +308,9 @@ pub struct UpdateThreshold<'info> {
         mut,
         seeds = [
             SIGNER_REGISTRY_SEED,
-            &registry_type.discriminant().to_le_bytes(),
-            &chain_id.to_le_bytes()
+            &registry_type.discriminant()?.to_le_bytes(),
+            &chain_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
         ],
         bump = signer_registry.bump,
         has_one = authority @ GatewayError::UnauthorizedAuthority
@@ -267,8 +318,10 @@ pub struct UpdateThreshold<'info> {
     pub signer_registry: Account<'info, SignerRegistry>,
     
     #[account(
-        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes()],
+        mut,
+        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes(), gateway.instance_seed().as_ref()],
         bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
         has_one = authority @ GatewayError::UnauthorizedAuthority
     )]
     pub gateway: Account<'info, MessageGateway>,
@@ -291,7 +344,7 @@ pub fn update_threshold(
     );
     
     let old_threshold = registry.required_signatures;
-    registry.required_signatures = new_threshold;
+    registry.set_required_signatures(new_threshold, Clock::get()?.unix_timestamp);
     
     msg!(
         "Updated {:?} registry threshold from {} to {}",
@@ -300,6 +353,8 @@ pub fn update_threshold(
         new_threshold
     );
     
+    ctx.accounts.gateway.advance_registry_epoch();
+    
     Ok(())
 }
 
@@ -311,8 +366,9 @@ pub struct SetRegistryEnabled<'info> {
         mut,
         seeds = [
             SIGNER_REGISTRY_SEED,
-            &registry_type.discriminant().to_le_bytes(),
-            &chain_id.to_le_bytes()
+            &registry_type.discriminant()?.to_le_bytes(),
+            &chain_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
         ],
         bump = signer_registry.bump,
         has_one = authority @ GatewayError::UnauthorizedAuthority
@@ -320,8 +376,10 @@ pub struct SetRegistryEnabled<'info> {
     pub signer_registry: Account<'info, SignerRegistry>,
     
     #[account(
-        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes()],
+        mut,
+        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes(), gateway.instance_seed().as_ref()],
         bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
         has_one = authority @ GatewayError::UnauthorizedAuthority
     )]
     pub gateway: Account<'info, MessageGateway>,
@@ -344,5 +402,155 @@ pub fn set_registry_enabled(
         enabled
     );
     
+    ctx.accounts.gateway.advance_registry_epoch();
+    
+    Ok(())
+}
+
+/// Start or end a shadow trial of a registry's threshold
+#[derive(Accounts)]
+#[instruction(registry_type: SignerRegistryType, chain_id: u64)]
+pub struct SetRegistryShadow<'info> {
+    #[account(
+        mut,
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &registry_type.discriminant()?.to_le_bytes(),
+            &chain_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = signer_registry.bump,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub signer_registry: Account<'info, SignerRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Until `shadow_until` the registry's threshold is evaluated and misses are logged as
+/// `ShadowThresholdMissed`, while only `enforced_signatures` is enforced (e.g. the threshold
+/// before a raise, or 0 for a new registry); a past `shadow_until` (such as 0) ends the trial
+/// A trial may only enforce 0 while the registry is new (see `SignerRegistry::is_new`), and
+/// otherwise no less than the threshold enforced before the last threshold change
+/// Registry bundles carry the trial once re-synced, as any change advances the registry epoch
+pub fn set_registry_shadow(
+    ctx: Context<SetRegistryShadow>,
+    _registry_type: SignerRegistryType,
+    _chain_id: u64,
+    enforced_signatures: u8,
+    shadow_until: i64,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.signer_registry;
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    
+    if shadow_until > now {
+        require!(
+            enforced_signatures >= registry.min_shadow_enforced_signatures(now, clock.slot)
+                && enforced_signatures < registry.required_signatures
+                && shadow_until - now <= MAX_SHADOW_TRIAL_SECONDS,
+            GatewayError::InvalidShadowTrial
+        );
+        registry.shadow_until = shadow_until;
+        registry.shadow_enforced_signatures = enforced_signatures;
+    } else {
+        registry.shadow_until = 0;
+        registry.shadow_enforced_signatures = 0;
+    }
+    
+    msg!(
+        "Set {:?} registry shadow trial: enforcing {} of {} signatures until {}",
+        registry.registry_type,
+        registry.enforced_signatures(now),
+        registry.required_signatures,
+        registry.shadow_until
+    );
+    emit!(RegistryShadowSet {
+        registry_type: registry.registry_type,
+        chain_id: registry.chain_id,
+        required_signatures: registry.required_signatures,
+        enforced_signatures: registry.enforced_signatures(now),
+        shadow_until: registry.shadow_until,
+    });
+    
+    ctx.accounts.gateway.advance_registry_epoch();
+    
+    Ok(())
+}
+
+/// Configure the extra verifier sets a project registry requires (DVN-style stack)
+#[derive(Accounts)]
+#[instruction(registry_type: SignerRegistryType, chain_id: u64)]
+pub struct SetExtraVerifiers<'info> {
+    #[account(
+        mut,
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &registry_type.discriminant()?.to_le_bytes(),
+            &chain_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = signer_registry.bump,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub signer_registry: Account<'info, SignerRegistry>,
+    
+    #[account(
+        mut,
+        seeds = [crate::constants::GATEWAY_SEED, &gateway.chain_id.to_le_bytes(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn set_extra_verifiers(
+    ctx: Context<SetExtraVerifiers>,
+    _registry_type: SignerRegistryType,
+    _chain_id: u64,
+    verifier_ids: Vec<u8>,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.signer_registry;
+    
+    require!(
+        registry.registry_type == SignerRegistryType::Project,
+        GatewayError::InvalidSignerRegistryType
+    );
+    require!(
+        verifier_ids.len() <= MAX_EXTRA_VERIFIERS,
+        GatewayError::TooManyVerifiers
+    );
+    
+    for (i, verifier_id) in verifier_ids.iter().enumerate() {
+        require!(*verifier_id <= MAX_VERIFIER_ID, GatewayError::InvalidSignerRegistryType);
+        require!(
+            !verifier_ids[..i].contains(verifier_id),
+            GatewayError::DuplicateSigner
+        );
+    }
+    
+    msg!(
+        "Updated {:?} registry extra verifiers: {:?} -> {:?}",
+        registry.registry_type,
+        registry.extra_verifiers,
+        verifier_ids
+    );
+    
+    registry.extra_verifiers = verifier_ids;
+    
+    ctx.accounts.gateway.advance_registry_epoch();
+    
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::state::{ChainConfig, MessageGateway, SignerRegistry};
+use crate::utils::snapshot::{snapshot_page, GatewaySnapshot, SnapshotPage};
+
+/// Export the gateway config, chain configs and signer registries as a canonical snapshot
+/// and return page `page` of it (read-only; simulate it once per page)
+/// remaining_accounts: every chain config ascending by chain id, then every signer registry
+/// ascending by (registry type, chain id); `utils::snapshot::assemble_snapshot` checks and
+/// decodes the pages
+#[derive(Accounts)]
+pub struct ExportSnapshot<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+}
+
+pub fn export_snapshot<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExportSnapshot<'info>>,
+    page: u16,
+) -> Result<SnapshotPage> {
+    let mut chain_configs: Vec<ChainConfig> = Vec::new();
+    let mut registries: Vec<SignerRegistry> = Vec::new();
+    
+    for account_info in ctx.remaining_accounts {
+        require_keys_eq!(
+            *account_info.owner,
+            crate::ID,
+            GatewayError::InvalidSnapshotAccount
+        );
+        let data = account_info.try_borrow_data()?;
+        
+        // Strictly ascending keys make the order canonical and rule out duplicates
+        if data.starts_with(ChainConfig::DISCRIMINATOR) {
+            let chain_config = ChainConfig::try_deserialize(&mut &data[..])?;
+            require!(
+                registries.is_empty()
+                    && chain_configs
+                        .last()
+                        .map_or(true, |last| last.chain_id < chain_config.chain_id),
+                GatewayError::InvalidSnapshotAccount
+            );
+            chain_configs.push(chain_config);
+        } else if data.starts_with(SignerRegistry::DISCRIMINATOR) {
+            let registry = SignerRegistry::try_deserialize(&mut &data[..])?;
+            let key = registry_key(&registry)?;
+            let last_key = registries.last().map(registry_key).transpose()?;
+            require!(
+                last_key.map_or(true, |last| last < key),
+                GatewayError::InvalidSnapshotAccount
+            );
+            registries.push(registry);
+        } else {
+            return err!(GatewayError::InvalidSnapshotAccount);
+        }
+    }
+    
+    let snapshot = GatewaySnapshot {
+        program_id: crate::ID,
+        gateway: (*ctx.accounts.gateway).clone(),
+        chain_configs,
+        registries,
+    };
+    let page = snapshot_page(&snapshot.encode()?, page)?;
+    
+    msg!(
+        "Snapshot page {}/{}: {} bytes",
+        page.page + 1,
+        page.page_count,
+        page.snapshot_size
+    );
+    Ok(page)
+}
+
+fn registry_key(registry: &SignerRegistry) -> Result<(u8, u64)> {
+    Ok((registry.registry_type.discriminant()?, registry.chain_id))
+}
\ No newline at end of file
@@ -0,0 +1,483 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{SendRequested, TokenBatchDeposited, TokenBatchReleased};
+use crate::instructions::process_message::{
+    delivery_accounts, extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::instructions::token_bridge::{
+    bridge_envelope, check_inbound_bridge_message, enforce_blocklist, enforce_rate_limit,
+    expected_payload_token, pay_bridge_fee, DepositTokens,
+};
+use crate::instructions::wrapped::mint_bridge_fee;
+use crate::payloads::{BatchTransfer, TokenBatchPayload, PAYLOAD_KIND_TOKEN_BATCH};
+use crate::state::{
+    BridgePeer, ChainConfig, MessageEnvelope, MessageGateway, MessageSignature, RelayerPool,
+    SignerRegistry, TokenConfig, TokenKind, TxIdPDA, WrappedAsset,
+};
+use crate::utils::{
+    address::validate_remote_address,
+    token::{received_amount, transfer_checked_with_hook},
+};
+
+/// Lock SPL tokens in escrow and send one message paying several recipients
+/// Uses the deposit_tokens accounts; each transfer is checked against the token's bounds
+/// and charged its own bridge fee
+/// Tokens with transfer fees are refused, since the escrow must receive the exact total
+pub fn deposit_tokens_batch<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositTokens<'info>>,
+    tx_id: u128,
+    dest_chain_id: u64,
+    transfers: Vec<BatchTransfer>,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(tx_id > 0, GatewayError::InvalidTxId);
+    require!(
+        gateway.allows_route(gateway.chain_id, dest_chain_id),
+        GatewayError::LoopbackDisabled
+    );
+    require!(
+        !transfers.is_empty() && transfers.len() <= MAX_BATCH_TRANSFERS,
+        GatewayError::InvalidBatchSize
+    );
+    
+    // Beneficiaries must be valid addresses for the destination chain family
+    if let Some(chain_config) = ctx.accounts.chain_config.as_ref() {
+        for transfer in &transfers {
+            validate_remote_address(chain_config.address_format, &transfer.recipient)?;
+        }
+    }
+    
+    enforce_blocklist(
+        &ctx.accounts.blocklist,
+        &[
+            ctx.accounts.depositor_token_account.owner,
+            ctx.accounts.depositor.key(),
+            ctx.accounts.mint.key(),
+        ],
+    )?;
+    
+    // Split every transfer like a single deposit; dust below the bridge decimals stays
+    // with the depositor
+    let decimals = ctx.accounts.mint.decimals;
+    let token_config = &ctx.accounts.token_config;
+    let mut legs = Vec::with_capacity(transfers.len());
+    let mut splits = Vec::with_capacity(transfers.len());
+    let mut total = 0u64;
+    let mut fee = 0u64;
+    for transfer in &transfers {
+        token_config.check_outbound(TokenKind::Native, transfer.amount)?;
+        let amount = token_config.truncate_dust(transfer.amount, decimals)?;
+        require!(amount > 0, GatewayError::AmountNotRepresentable);
+    
+        let amounts = token_config.outbound_amounts(amount, decimals)?;
+        total = total.checked_add(amount).ok_or(GatewayError::InvalidAmount)?;
+        fee = fee.checked_add(amounts.fee).ok_or(GatewayError::InvalidAmount)?;
+        legs.push(BatchTransfer {
+            recipient: transfer.recipient.clone(),
+            amount: amounts.wire,
+        });
+        splits.push(amounts);
+    }
+    
+    let source = &ctx.accounts.depositor_token_account;
+    let signer = ctx.accounts.depositor.key();
+    let delegate = if source.owner == signer {
+        None
+    } else {
+        require!(source.delegated_amount >= total, GatewayError::InsufficientDelegation);
+        Some(signer)
+    };
+    
+    let balance_before = ctx.accounts.escrow.amount;
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.depositor_token_account.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.depositor.to_account_info(),
+        ctx.remaining_accounts,
+        &[],
+        total,
+        decimals,
+    )?;
+    
+    ctx.accounts.escrow.reload()?;
+    let received = received_amount(balance_before, ctx.accounts.escrow.amount)?;
+    require!(received == total, GatewayError::BatchTransferFeeUnsupported);
+    for amounts in &splits {
+        ctx.accounts.token_config.record_deposit(amounts)?;
+    }
+    
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    
+    let instance_seed = gateway.instance_seed();
+    pay_bridge_fee(
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow,
+        &ctx.accounts.mint,
+        ctx.accounts.treasury_token_account.as_ref(),
+        gateway,
+        ctx.remaining_accounts,
+        &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]],
+        fee,
+    )?;
+    
+    let mint = ctx.accounts.mint.key();
+    let depositor = ctx.accounts.depositor_token_account.owner;
+    let payload = TokenBatchPayload {
+        token: mint.to_bytes(),
+        sender: depositor.to_bytes().to_vec(),
+        transfers: legs,
+    };
+    
+    let envelope = bridge_envelope(
+        gateway,
+        &ctx.accounts.bridge_peer,
+        tx_id,
+        dest_chain_id,
+        PAYLOAD_KIND_TOKEN_BATCH,
+        payload.encode()?,
+        Vec::new(),
+    )?;
+    
+    emit!(TokenBatchDeposited {
+        tx_id,
+        mint,
+        depositor,
+        delegate,
+        transfers: payload.transfers.len() as u8,
+        amount: total,
+        fee,
+        dest_chain_id,
+    });
+    
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("Token batch deposited: tx_id={}, transfers={}, dest_chain={}", tx_id, payload.transfers.len(), dest_chain_id);
+    Ok(())
+}
+
+/// TX2 for batched token transfers: verifies the message like release_tokens, then pays
+/// every recipient from escrow (native mints) or by minting (wrapped mints) atomically
+/// remaining_accounts: the project's extra verifier registries, the message history accounts
+/// while history is enabled, then one writable token account per transfer (owned by its
+/// recipient, in payload order), then transfer-hook accounts for Token-2022 mints with a hook
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct ReleaseTokenBatch<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Registry entry mapping the payload token to `mint`; required for wrapped mints
+    #[account(
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            wrapped_asset.source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = wrapped_asset.bump,
+        has_one = mint @ GatewayError::TokenMismatch
+    )]
+    pub wrapped_asset: Option<Account<'info, WrappedAsset>>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    /// CHECK: Mint's TokenRateLimit PDA; may be uninitialized (no cap), validated in enforce_rate_limit
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub rate_limit: UncheckedAccount<'info>,
+    
+    /// Gateway escrow; required for native mints
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = gateway,
+        associated_token::token_program = token_program
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Treasury token account receiving the bridge fee; required when a fee applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = gateway.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn release_token_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReleaseTokenBatch<'info>>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    let verifier_count = extra_verifier_count(ctx.accounts.project_registry.as_deref());
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        verifier_count,
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_TOKEN_BATCH)?;
+    let payload = TokenBatchPayload::decode(&envelope.on_chain_data)?;
+    
+    let token_config = &ctx.accounts.token_config;
+    require!(
+        payload.token == expected_payload_token(token_config, ctx.accounts.wrapped_asset.as_deref())?,
+        GatewayError::TokenMismatch
+    );
+    let kind = token_config.kind;
+    token_config.check_path(kind)?;
+    require!(
+        !token_config.settings.claim_delivery,
+        GatewayError::ClaimDeliveryRequired
+    );
+    
+    // Recipient token accounts follow the extra verifier registries and the history
+    // accounts, hook accounts follow them
+    let accounts = delivery_accounts(&ctx.accounts.gateway, ctx.remaining_accounts, verifier_count);
+    require!(
+        accounts.len() >= payload.transfers.len(),
+        GatewayError::InvalidBatchAccount
+    );
+    let (recipient_accounts, hook_accounts) = accounts.split_at(payload.transfers.len());
+    
+    let mint = ctx.accounts.mint.key();
+    let decimals = ctx.accounts.mint.decimals;
+    let mut blocklist_keys = vec![mint];
+    let mut deliveries = Vec::with_capacity(payload.transfers.len());
+    let mut total = 0u64;
+    let mut fee = 0u64;
+    for (transfer, account) in payload.transfers.iter().zip(recipient_accounts) {
+        let recipient = Pubkey::try_from(transfer.recipient.as_slice())
+            .map_err(|_| GatewayError::InvalidRemoteAddress)?;
+        let token_account = InterfaceAccount::<TokenAccount>::try_from(account)
+            .map_err(|_| GatewayError::InvalidBatchAccount)?;
+        require!(
+            account.is_writable && token_account.mint == mint && token_account.owner == recipient,
+            GatewayError::InvalidBatchAccount
+        );
+    
+        let amount = token_config.from_wire_amount(transfer.amount, decimals)?;
+        let leg_fee = token_config.release_fee(amount);
+        total = total.checked_add(amount).ok_or(GatewayError::InvalidAmount)?;
+        fee = fee.checked_add(leg_fee).ok_or(GatewayError::InvalidAmount)?;
+        blocklist_keys.push(recipient);
+        deliveries.push((account, amount - leg_fee));
+    }
+    
+    enforce_blocklist(&ctx.accounts.blocklist, &blocklist_keys)?;
+    enforce_rate_limit(&ctx.accounts.rate_limit, total)?;
+    ctx.accounts.token_config.record_fee(fee)?;
+    
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]];
+    match kind {
+        TokenKind::Native => {
+            let escrow = ctx.accounts.escrow.as_ref().ok_or(GatewayError::MissingEscrow)?;
+            ctx.accounts.token_config.record_release(total)?;
+            pay_bridge_fee(
+                &ctx.accounts.token_program,
+                escrow,
+                &ctx.accounts.mint,
+                ctx.accounts.treasury_token_account.as_ref(),
+                gateway,
+                hook_accounts,
+                signer_seeds,
+                fee,
+            )?;
+            for (account, amount) in deliveries {
+                transfer_checked_with_hook(
+                    &ctx.accounts.token_program.to_account_info(),
+                    &escrow.to_account_info(),
+                    &ctx.accounts.mint.to_account_info(),
+                    account,
+                    &gateway.to_account_info(),
+                    hook_accounts,
+                    signer_seeds,
+                    amount,
+                    decimals,
+                )?;
+            }
+        }
+        TokenKind::Wrapped => {
+            mint_bridge_fee(
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                ctx.accounts.treasury_token_account.as_ref(),
+                gateway,
+                signer_seeds,
+                fee,
+            )?;
+            for (account, amount) in deliveries {
+                mint_to(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        MintTo {
+                            mint: ctx.accounts.mint.to_account_info(),
+                            to: account.clone(),
+                            authority: gateway.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    amount,
+                )?;
+            }
+        }
+        TokenKind::IssuerBurnMint | TokenKind::ProjectCustody => {
+            return err!(GatewayError::TokenKindMismatch)
+        }
+    }
+    
+    emit!(TokenBatchReleased {
+        tx_id: envelope.tx_id,
+        mint,
+        transfers: payload.transfers.len() as u8,
+        amount: total,
+        fee,
+        source_chain_id: envelope.source_chain_id,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Token batch released and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
@@ -0,0 +1,1208 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{
+    BlocklistUpdated, BridgePeerUpdated, EscrowReconciled, RateLimitUpdated, SendRequested, TokenConfigUpdated,
+    TokenTransferDeadLettered, TokensDeposited, TokensReleased,
+};
+use crate::instructions::fast_transfer::check_delivery_account;
+use crate::instructions::process_message::{
+    delivery_accounts, extra_verifier_count, verify_inbound_message, DeliveryTail,
+};
+use crate::payloads::{TokenTransferPayload, VersionedTokenTransfer, PAYLOAD_KIND_TOKEN_TRANSFER};
+use crate::state::{
+    Blocklist, BridgePeer, ChainConfig, DeadLetter, FeeConfig, MessageEnvelope, MessageGateway, MessageSignature, RelayerPool,
+    SignerRegistry, TokenConfig, TokenKind, TokenRateLimit, TokenSettings, TxIdPDA, WrappedAsset,
+};
+use crate::utils::{
+    address::validate_remote_address,
+    token::{received_amount, transfer_checked_with_hook},
+};
+
+/// Register the token bridge contract of a remote chain (admin only)
+#[derive(Accounts)]
+#[instruction(chain_id: u64)]
+pub struct SetBridgePeer<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + BridgePeer::SIZE,
+        seeds = [BRIDGE_PEER_SEED, chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_bridge_peer(
+    ctx: Context<SetBridgePeer>,
+    chain_id: u64,
+    remote_bridge: Vec<u8>,
+) -> Result<()> {
+    require!(chain_id > 0, GatewayError::InvalidChainId);
+    require!(!remote_bridge.is_empty(), GatewayError::EmptyRecipient);
+    require!(remote_bridge.len() <= MAX_SENDER_SIZE, GatewayError::SenderTooLong);
+    
+    let bridge_peer = &mut ctx.accounts.bridge_peer;
+    bridge_peer.chain_id = chain_id;
+    bridge_peer.remote_bridge = remote_bridge.clone();
+    bridge_peer.bump = ctx.bumps.bridge_peer;
+    
+    emit!(BridgePeerUpdated {
+        chain_id,
+        remote_bridge,
+    });
+    
+    msg!("Bridge peer set for chain {}", chain_id);
+    Ok(())
+}
+
+/// Register or update a mint's bridge configuration (admin only)
+/// The mint is classified as wrapped when the gateway PDA is its mint authority
+#[derive(Accounts)]
+pub struct SetTokenConfig<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TokenConfig::SIZE,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_token_config(
+    ctx: Context<SetTokenConfig>,
+    settings: TokenSettings,
+    governance: Pubkey,
+) -> Result<()> {
+    settings.validate()?;
+    
+    let token_config = &mut ctx.accounts.token_config;
+    let kind = if ctx.accounts.mint.mint_authority == COption::Some(ctx.accounts.gateway.key()) {
+        TokenKind::Wrapped
+    } else if token_config.issuer_program != Pubkey::default() {
+        TokenKind::IssuerBurnMint
+    } else if token_config.kind == TokenKind::ProjectCustody {
+        // Custody stays with the attached extension until it is detached
+        TokenKind::ProjectCustody
+    } else {
+        TokenKind::Native
+    };
+    
+    token_config.mint = ctx.accounts.mint.key();
+    token_config.kind = kind;
+    token_config.governance = governance;
+    token_config.settings = settings;
+    token_config.bump = ctx.bumps.token_config;
+    token_config.decimal_scale(ctx.accounts.mint.decimals)?;
+    
+    emit_token_config_updated(token_config, ctx.accounts.authority.key());
+    
+    msg!("Token config for mint {}: kind={:?}", token_config.mint, kind);
+    Ok(())
+}
+
+/// Update a configured mint's settings (gateway authority or the token's governance)
+#[derive(Accounts)]
+pub struct UpdateTokenConfig<'info> {
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, token_config.mint.as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump,
+        constraint = token_config.can_manage(&manager.key(), &gateway.authority)
+            @ GatewayError::UnauthorizedAuthority
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(address = token_config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub manager: Signer<'info>,
+}
+
+pub fn update_token_config(ctx: Context<UpdateTokenConfig>, settings: TokenSettings) -> Result<()> {
+    settings.validate()?;
+    
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.settings = settings;
+    token_config.decimal_scale(ctx.accounts.mint.decimals)?;
+    
+    emit_token_config_updated(token_config, ctx.accounts.manager.key());
+    
+    msg!("Token config for mint {} updated: paused={}", token_config.mint, settings.paused);
+    Ok(())
+}
+
+fn emit_token_config_updated(token_config: &TokenConfig, updated_by: Pubkey) {
+    emit!(TokenConfigUpdated {
+        mint: token_config.mint,
+        kind: token_config.kind,
+        governance: token_config.governance,
+        settings: token_config.settings,
+        updated_by,
+    });
+}
+
+/// Add a wallet or mint to the compliance blocklist, or remove it (admin only)
+#[derive(Accounts)]
+pub struct SetBlocklistEntry<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + Blocklist::SIZE,
+        seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_blocklist_entry(
+    ctx: Context<SetBlocklistEntry>,
+    entry: Pubkey,
+    blocked: bool,
+) -> Result<()> {
+    let blocklist = &mut ctx.accounts.blocklist;
+    if blocked {
+        blocklist.block(entry)?;
+    } else {
+        blocklist.unblock(&entry)?;
+    }
+    blocklist.bump = ctx.bumps.blocklist;
+    
+    emit!(BlocklistUpdated {
+        entry,
+        blocked,
+        updated_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Blocklist entry {}: blocked={}", entry, blocked);
+    Ok(())
+}
+
+/// Compare a mint's escrow balance with the liabilities recorded in its TokenConfig
+/// Permissionless: monitors can run it at any time to detect insolvency on-chain
+#[derive(Accounts)]
+pub struct ReconcileEscrow<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = gateway,
+        associated_token::token_program = token_program
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn reconcile_escrow(ctx: Context<ReconcileEscrow>) -> Result<()> {
+    let token_config = &ctx.accounts.token_config;
+    let required = token_config.escrowed_amount;
+    let escrow_balance = ctx.accounts.escrow.amount;
+    let surplus = escrow_balance.saturating_sub(required);
+    let shortfall = required.saturating_sub(escrow_balance);
+    
+    emit!(EscrowReconciled {
+        mint: token_config.mint,
+        escrow: ctx.accounts.escrow.key(),
+        escrow_balance,
+        escrowed_amount: required,
+        surplus,
+        shortfall,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!(
+        "Escrow for mint {}: balance={}, liability={}, shortfall={}",
+        token_config.mint,
+        escrow_balance,
+        required,
+        shortfall
+    );
+    Ok(())
+}
+
+/// Set the inbound volume cap for a mint (admin only)
+#[derive(Accounts)]
+pub struct SetRateLimit<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + TokenRateLimit::SIZE,
+        seeds = [RATE_LIMIT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub rate_limit: Account<'info, TokenRateLimit>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_rate_limit(
+    ctx: Context<SetRateLimit>,
+    window_seconds: u64,
+    max_amount: u64,
+) -> Result<()> {
+    require!(
+        window_seconds > 0 && window_seconds <= i64::MAX as u64,
+        GatewayError::InvalidRateLimit
+    );
+    
+    let rate_limit = &mut ctx.accounts.rate_limit;
+    
+    // A new window length invalidates the recorded volume
+    if rate_limit.window_seconds != window_seconds {
+        rate_limit.window_start = Clock::get()?.unix_timestamp;
+        rate_limit.current_volume = 0;
+        rate_limit.previous_volume = 0;
+    }
+    rate_limit.mint = ctx.accounts.mint.key();
+    rate_limit.window_seconds = window_seconds;
+    rate_limit.max_amount = max_amount;
+    rate_limit.bump = ctx.bumps.rate_limit;
+    
+    emit!(RateLimitUpdated {
+        mint: rate_limit.mint,
+        window_seconds,
+        max_amount,
+    });
+    
+    msg!(
+        "Rate limit for mint {}: {} per {}s",
+        rate_limit.mint,
+        max_amount,
+        window_seconds
+    );
+    Ok(())
+}
+
+/// Lock SPL Token or Token-2022 tokens in the gateway escrow and send a token-transfer message
+/// The escrow is the gateway PDA's associated token account for the mint
+/// The signer is the token account owner, or a relayer/program spending the owner's
+/// delegate approval; either way the owner is the transfer's sender
+/// remaining_accounts: transfer-hook accounts for Token-2022 mints with a hook
+#[derive(Accounts)]
+#[instruction(tx_id: u128, dest_chain_id: u64)]
+pub struct DepositTokens<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// Token bridge on the destination chain
+    #[account(
+        seeds = [BRIDGE_PEER_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// Optional destination chain config validating the recipient address format
+    #[account(
+        seeds = [CHAIN_CONFIG_SEED, dest_chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Option<Account<'info, ChainConfig>>,
+    
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+        constraint = depositor_token_account.owner == depositor.key()
+            || depositor_token_account.delegate == COption::Some(depositor.key())
+            @ GatewayError::UnauthorizedDepositor
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = mint,
+        associated_token::authority = gateway,
+        associated_token::token_program = token_program
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Treasury token account receiving the bridge fee; required when a fee applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = gateway.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Token account owner or its approved delegate; pays for the escrow account
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn deposit_tokens<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositTokens<'info>>,
+    tx_id: u128,
+    dest_chain_id: u64,
+    recipient: Vec<u8>,
+    amount: u64,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(tx_id > 0, GatewayError::InvalidTxId);
+    require!(
+        gateway.allows_route(gateway.chain_id, dest_chain_id),
+        GatewayError::LoopbackDisabled
+    );
+    
+    // Beneficiary must be a valid address for the destination chain family
+    if let Some(chain_config) = ctx.accounts.chain_config.as_ref() {
+        validate_remote_address(chain_config.address_format, &recipient)?;
+    }
+    
+    // Both the token owner and a delegate submitting for it must be allowed
+    enforce_blocklist(
+        &ctx.accounts.blocklist,
+        &[
+            ctx.accounts.depositor_token_account.owner,
+            ctx.accounts.depositor.key(),
+            ctx.accounts.mint.key(),
+        ],
+    )?;
+    ctx.accounts.token_config.check_outbound(TokenKind::Native, amount)?;
+    
+    // Digits below the bridge decimals stay with the depositor
+    let decimals = ctx.accounts.mint.decimals;
+    let amount = ctx.accounts.token_config.truncate_dust(amount, decimals)?;
+    require!(amount > 0, GatewayError::AmountNotRepresentable);
+    
+    // A delegate spends the owner's approval; surface an exhausted one before the CPI
+    let source = &ctx.accounts.depositor_token_account;
+    let signer = ctx.accounts.depositor.key();
+    let delegate = if source.owner == signer {
+        None
+    } else {
+        require!(source.delegated_amount >= amount, GatewayError::InsufficientDelegation);
+        Some(signer)
+    };
+    
+    let balance_before = ctx.accounts.escrow.amount;
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.depositor_token_account.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.depositor.to_account_info(),
+        ctx.remaining_accounts,
+        &[],
+        amount,
+        decimals,
+    )?;
+    
+    // Bridge only what the escrow actually received (transfer fees, fee-on-transfer
+    // hooks), never the instruction amount; the bridge fee and any dust those fees
+    // leave go to the treasury
+    ctx.accounts.escrow.reload()?;
+    let received = received_amount(balance_before, ctx.accounts.escrow.amount)?;
+    let amounts = ctx.accounts.token_config.outbound_amounts(received, decimals)?;
+    ctx.accounts.token_config.record_deposit(&amounts)?;
+    
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    
+    let instance_seed = gateway.instance_seed();
+    pay_bridge_fee(
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow,
+        &ctx.accounts.mint,
+        ctx.accounts.treasury_token_account.as_ref(),
+        gateway,
+        ctx.remaining_accounts,
+        &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]],
+        amounts.fee,
+    )?;
+    
+    let mint = ctx.accounts.mint.key();
+    let depositor = ctx.accounts.depositor_token_account.owner;
+    let payload = TokenTransferPayload {
+        token: mint.to_bytes(),
+        amount: amounts.wire,
+        sender: depositor.to_bytes().to_vec(),
+        recipient: recipient.clone(),
+    };
+    
+    let envelope = token_transfer_envelope(
+        &ctx.accounts.gateway,
+        &ctx.accounts.bridge_peer,
+        tx_id,
+        dest_chain_id,
+        &payload,
+    )?;
+    
+    emit!(TokensDeposited {
+        tx_id,
+        mint,
+        depositor,
+        delegate,
+        amount,
+        received,
+        fee: amounts.fee,
+        dest_chain_id,
+        recipient,
+    });
+    
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("Tokens deposited: tx_id={}, bridged={}, dest_chain={}", tx_id, amounts.bridged, dest_chain_id);
+    Ok(())
+}
+
+/// TX2 for inbound token transfers: verifies the message like process_message,
+/// then releases the tokens from escrow to the beneficiary
+/// remaining_accounts: the project's extra verifier registries, the message history accounts
+/// while history is enabled, then transfer-hook accounts
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct ReleaseTokens<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// Source chain fee config for the relayer refund, with the fee vault below
+    #[account(
+        seeds = [
+            FEE_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = fee_config.bump
+    )]
+    pub fee_config: Option<Box<Account<'info, FeeConfig>>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<SystemAccount<'info>>,
+    
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    /// CHECK: Mint's TokenRateLimit PDA; may be uninitialized (no cap), validated in enforce_rate_limit
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub rate_limit: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = gateway,
+        associated_token::token_program = token_program
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Treasury token account receiving the bridge fee; required when a fee applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = gateway.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Beneficiary token account; its owner must match the payload recipient, or it must
+    /// be the LP repayment account when the transfer was fronted
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Transfer's FastFill PDA; may be uninitialized (not fronted), validated in settle_fast_fill
+    #[account(
+        mut,
+        seeds = [
+            FAST_FILL_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub fast_fill: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn release_tokens<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReleaseTokens<'info>>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    let verifier_count = extra_verifier_count(ctx.accounts.project_registry.as_deref());
+    let relayer_refund = DeliveryTail {
+        gateway: &ctx.accounts.gateway,
+        tx_id_pda: &ctx.accounts.tx_id_pda,
+        relayer_pool: ctx.accounts.relayer_pool.as_deref_mut(),
+        payer: ctx.accounts.payer.as_deref(),
+        fee_config: ctx.accounts.fee_config.as_deref().map(|config| &**config),
+        fee_vault: ctx.accounts.fee_vault.as_ref(),
+        fee_vault_bump: ctx.bumps.fee_vault,
+        relayer: &ctx.accounts.relayer,
+        system_program: &ctx.accounts.system_program,
+    }
+    .finalize_delivery(
+        ctx.remaining_accounts,
+        verifier_count,
+        &envelope,
+        envelope.on_chain_data.len(),
+    )?;
+    
+    let payload = decode_inbound_transfer(&envelope, &ctx.accounts.bridge_peer)?;
+    let mint = ctx.accounts.mint.key();
+    require!(
+        payload.token == mint.to_bytes(),
+        GatewayError::TokenMismatch
+    );
+    
+    let recipient = payload.recipient_pubkey()?;
+    enforce_blocklist(&ctx.accounts.blocklist, &[recipient, mint])?;
+    check_delivery_account(
+        &ctx.accounts.fast_fill,
+        &envelope,
+        &ctx.accounts.recipient_token_account,
+        recipient,
+        &ctx.accounts.token_config,
+    )?;
+    
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.check_path(TokenKind::Native)?;
+    let amount = token_config.from_wire_amount(payload.amount, ctx.accounts.mint.decimals)?;
+    enforce_rate_limit(&ctx.accounts.rate_limit, amount)?;
+    token_config.record_release(amount)?;
+    let fee = token_config.release_fee(amount);
+    token_config.record_fee(fee)?;
+    
+    // Transfer-hook accounts follow the extra verifier registries and the history accounts
+    let hook_accounts =
+        delivery_accounts(&ctx.accounts.gateway, ctx.remaining_accounts, verifier_count);
+    
+    // The gateway PDA owns the escrow and signs the release
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]];
+    pay_bridge_fee(
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow,
+        &ctx.accounts.mint,
+        ctx.accounts.treasury_token_account.as_ref(),
+        gateway,
+        hook_accounts,
+        signer_seeds,
+        fee,
+    )?;
+    
+    let balance_before = ctx.accounts.recipient_token_account.amount;
+    transfer_checked_with_hook(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.escrow.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.recipient_token_account.to_account_info(),
+        &gateway.to_account_info(),
+        hook_accounts,
+        signer_seeds,
+        amount - fee,
+        ctx.accounts.mint.decimals,
+    )?;
+    
+    ctx.accounts.recipient_token_account.reload()?;
+    let received = received_amount(balance_before, ctx.accounts.recipient_token_account.amount)?;
+    
+    emit!(TokensReleased {
+        tx_id: envelope.tx_id,
+        mint,
+        recipient,
+        amount,
+        fee,
+        received,
+        source_chain_id: envelope.source_chain_id,
+        relayer: ctx.accounts.relayer.key(),
+        relayer_refund,
+    });
+    
+    msg!("Tokens released and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
+
+/// TX2 for inbound token transfers whose payload version this program cannot decode:
+/// verifies the message like release_tokens, then parks it in a DeadLetter PDA and closes
+/// the TxId PDA instead of leaving it unprocessable
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct DeadLetterTokenTransfer<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// Source chain fee config for the relayer refund, with the fee vault below
+    #[account(
+        seeds = [
+            FEE_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = fee_config.bump
+    )]
+    pub fee_config: Option<Box<Account<'info, FeeConfig>>>,
+    
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub fee_vault: Option<SystemAccount<'info>>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + DeadLetter::SIZE,
+        seeds = [
+            DEAD_LETTER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub dead_letter: Account<'info, DeadLetter>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn dead_letter_token_transfer(
+    ctx: Context<DeadLetterTokenTransfer>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    let relayer_refund = DeliveryTail {
+        gateway: &ctx.accounts.gateway,
+        tx_id_pda: &ctx.accounts.tx_id_pda,
+        relayer_pool: ctx.accounts.relayer_pool.as_deref_mut(),
+        payer: ctx.accounts.payer.as_deref(),
+        fee_config: ctx.accounts.fee_config.as_deref().map(|config| &**config),
+        fee_vault: ctx.accounts.fee_vault.as_ref(),
+        fee_vault_bump: ctx.bumps.fee_vault,
+        relayer: &ctx.accounts.relayer,
+        system_program: &ctx.accounts.system_program,
+    }
+    .finalize_delivery(
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+        envelope.on_chain_data.len(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_TOKEN_TRANSFER)?;
+    
+    // Decodable transfers must go through release_tokens or mint_wrapped
+    let payload_version = match TokenTransferPayload::parse(&envelope.on_chain_data)? {
+        VersionedTokenTransfer::Supported(_) => return err!(GatewayError::PayloadVersionSupported),
+        VersionedTokenTransfer::Unsupported { version } => version,
+    };
+    
+    let dead_letter = &mut ctx.accounts.dead_letter;
+    dead_letter.source_chain_id = envelope.source_chain_id;
+    dead_letter.tx_id = envelope.tx_id;
+    dead_letter.payload_version = payload_version;
+    dead_letter.on_chain_data = envelope.on_chain_data;
+    dead_letter.relayer = ctx.accounts.relayer.key();
+    dead_letter.dead_lettered_at = Clock::get()?.unix_timestamp;
+    dead_letter.bump = ctx.bumps.dead_letter;
+    
+    emit!(TokenTransferDeadLettered {
+        tx_id: envelope.tx_id,
+        source_chain_id: envelope.source_chain_id,
+        payload_version,
+        relayer: dead_letter.relayer,
+        relayer_refund,
+    });
+    
+    msg!(
+        "Token transfer v{} dead-lettered and TxId PDA closed for tx_id={}",
+        payload_version,
+        envelope.tx_id
+    );
+    Ok(())
+}
+
+/// Build the outbound token-transfer envelope addressed to the destination bridge peer
+pub(crate) fn token_transfer_envelope(
+    gateway: &MessageGateway,
+    bridge_peer: &BridgePeer,
+    tx_id: u128,
+    dest_chain_id: u64,
+    payload: &TokenTransferPayload,
+) -> Result<MessageEnvelope> {
+    bridge_envelope(
+        gateway,
+        bridge_peer,
+        tx_id,
+        dest_chain_id,
+        PAYLOAD_KIND_TOKEN_TRANSFER,
+        payload.encode()?,
+        Vec::new(),
+    )
+}
+
+/// Build an outbound bridge-module envelope addressed to the destination bridge peer
+/// The gateway program is the bridge's identity on the remote side
+pub(crate) fn bridge_envelope(
+    gateway: &MessageGateway,
+    bridge_peer: &BridgePeer,
+    tx_id: u128,
+    dest_chain_id: u64,
+    message_type: u8,
+    on_chain_data: Vec<u8>,
+    off_chain_data: Vec<u8>,
+) -> Result<MessageEnvelope> {
+    let mut envelope = MessageEnvelope {
+        version: MessageEnvelope::CURRENT_VERSION,
+        tx_id,
+        source_chain_id: gateway.chain_id,
+        dest_chain_id,
+        sender: crate::ID.to_bytes().to_vec(),
+        recipient: bridge_peer.remote_bridge.clone(),
+        on_chain_data,
+        off_chain_data,
+        off_chain_data_hash: [0u8; 32],
+        message_type,
+        deadline: 0,
+        sequence: 0,
+        protocol_version: PROTOCOL_VERSION,
+        da_pointer: Vec::new(),
+    };
+    envelope.validate()?;
+    envelope.off_chain_data_hash = envelope.resolved_off_chain_data_hash()?;
+    Ok(envelope)
+}
+
+/// Check a verified inbound envelope is a token transfer from the source chain's bridge peer
+pub(crate) fn decode_inbound_transfer(
+    envelope: &MessageEnvelope,
+    bridge_peer: &BridgePeer,
+) -> Result<TokenTransferPayload> {
+    check_inbound_bridge_message(envelope, bridge_peer, PAYLOAD_KIND_TOKEN_TRANSFER)?;
+    TokenTransferPayload::decode(&envelope.on_chain_data)
+}
+
+/// Check a verified inbound envelope has the expected bridge message type, is not
+/// channel-sequenced, is addressed to the gateway program and was sent by the source
+/// chain's bridge peer
+pub(crate) fn check_inbound_bridge_message(
+    envelope: &MessageEnvelope,
+    bridge_peer: &BridgePeer,
+    message_type: u8,
+) -> Result<()> {
+    require!(envelope.message_type == message_type, GatewayError::InvalidPayload);
+    require!(!envelope.uses_channel(), GatewayError::InvalidSequence);
+    require!(
+        envelope.recipient.as_slice() == crate::ID.as_ref(),
+        GatewayError::InvalidPayload
+    );
+    require!(
+        envelope.sender == bridge_peer.remote_bridge,
+        GatewayError::UntrustedBridgePeer
+    );
+    Ok(())
+}
+
+/// Payload token identifier expected for a configured mint: the mint address for native
+/// mints, the source-chain asset for wrapped ones (whose registry entry must be supplied)
+pub(crate) fn expected_payload_token(
+    token_config: &TokenConfig,
+    wrapped_asset: Option<&WrappedAsset>,
+) -> Result<[u8; 32]> {
+    match token_config.kind {
+        TokenKind::Native => Ok(token_config.mint.to_bytes()),
+        TokenKind::Wrapped => {
+            let wrapped_asset = wrapped_asset.ok_or(GatewayError::TokenMismatch)?;
+            require_keys_eq!(wrapped_asset.mint, token_config.mint, GatewayError::TokenMismatch);
+            require!(!wrapped_asset.delisted, GatewayError::WrappedAssetDelisted);
+            Ok(wrapped_asset.source_token)
+        }
+        // Issuer-routed and project-custody mints only arrive through complete_issuer_transfer
+        // and release_via_custody_extension
+        TokenKind::IssuerBurnMint | TokenKind::ProjectCustody => {
+            err!(GatewayError::TokenKindMismatch)
+        }
+    }
+}
+
+/// Move a bridge fee from a gateway-owned token account to the treasury (no-op when zero)
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_bridge_fee<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    treasury_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    gateway: &Account<'info, MessageGateway>,
+    hook_accounts: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+    fee: u64,
+) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+    
+    let treasury_token_account = treasury_token_account.ok_or(GatewayError::MissingTreasuryAccount)?;
+    transfer_checked_with_hook(
+        &token_program.to_account_info(),
+        &from.to_account_info(),
+        &mint.to_account_info(),
+        &treasury_token_account.to_account_info(),
+        &gateway.to_account_info(),
+        hook_accounts,
+        signer_seeds,
+        fee,
+        mint.decimals,
+    )
+}
+
+/// Whether any of `keys` is on the blocklist; an uninitialized blocklist PDA is empty
+pub(crate) fn any_blocked<'info>(blocklist: &AccountInfo<'info>, keys: &[Pubkey]) -> Result<bool> {
+    if blocklist.data_is_empty() {
+        return Ok(false);
+    }
+    require_keys_eq!(*blocklist.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    
+    let list = Blocklist::try_deserialize(&mut &blocklist.try_borrow_data()?[..])?;
+    Ok(keys.iter().any(|key| list.is_blocked(key)))
+}
+
+/// Fail if any of `keys` (wallets or the mint of a token operation) is blocked
+pub(crate) fn enforce_blocklist<'info>(blocklist: &AccountInfo<'info>, keys: &[Pubkey]) -> Result<()> {
+    require!(!any_blocked(blocklist, keys)?, GatewayError::AddressBlocked);
+    Ok(())
+}
+
+/// Count an inbound transfer against the mint's volume cap
+/// The rate-limit PDA is always passed, so a configured cap cannot be skipped;
+/// an uninitialized PDA means the mint is unlimited
+pub(crate) fn enforce_rate_limit<'info>(rate_limit: &AccountInfo<'info>, amount: u64) -> Result<()> {
+    if rate_limit.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*rate_limit.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    
+    let mut limit = TokenRateLimit::try_deserialize(&mut &rate_limit.try_borrow_data()?[..])?;
+    limit.consume(amount, Clock::get()?.unix_timestamp)?;
+    limit.try_serialize(&mut &mut rate_limit.try_borrow_mut_data()?[..])
+}
\ No newline at end of file
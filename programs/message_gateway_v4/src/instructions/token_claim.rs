@@ -0,0 +1,637 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{SendRequested, TokenClaimed, TokenTransferParked, TokenTransferReturned};
+use crate::instructions::process_message::{
+    extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::instructions::token_bridge::{
+    any_blocked, check_inbound_bridge_message, decode_inbound_transfer, enforce_blocklist,
+    enforce_rate_limit, expected_payload_token, pay_bridge_fee, token_transfer_envelope,
+};
+use crate::instructions::wrapped::mint_bridge_fee;
+use crate::payloads::{TokenReturnPayload, TokenTransferPayload, PAYLOAD_KIND_TOKEN_RETURN};
+use crate::state::{
+    BridgePeer, ChainConfig, MessageEnvelope, MessageGateway, MessageSignature, RelayerPool,
+    SignerRegistry, TokenClaim, TokenConfig, TokenKind, TxIdPDA, WrappedAsset,
+};
+use crate::utils::token::transfer_checked_with_hook;
+
+/// TX2 for inbound token transfers that cannot be delivered: verifies the message like
+/// release_tokens, then parks it in a TokenClaim PDA and closes the TxId PDA
+/// Only allowed while the mint is paused or in claim-delivery mode, the recipient or mint
+/// is blocklisted, or the recipient's associated token account is missing
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct ParkTokenTransfer<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    /// Registry entry mapping the payload token to `mint`; required for wrapped mints
+    #[account(
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            wrapped_asset.source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = wrapped_asset.bump,
+        has_one = mint @ GatewayError::TokenMismatch
+    )]
+    pub wrapped_asset: Option<Account<'info, WrappedAsset>>,
+    
+    /// CHECK: Recipient's associated token account for the mint; address checked in the handler
+    pub recipient_token_account: UncheckedAccount<'info>,
+    
+    /// CHECK: FastFill PDA of the transfer; must be uninitialized (not fronted)
+    #[account(
+        seeds = [
+            FAST_FILL_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub fast_fill: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + TokenClaim::SIZE,
+        seeds = [
+            TOKEN_CLAIM_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub token_claim: Account<'info, TokenClaim>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn park_token_transfer(
+    ctx: Context<ParkTokenTransfer>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    let payload = decode_inbound_transfer(&envelope, &ctx.accounts.bridge_peer)?;
+    let mint = ctx.accounts.mint.key();
+    let token_config = &ctx.accounts.token_config;
+    require!(
+        payload.token == expected_payload_token(token_config, ctx.accounts.wrapped_asset.as_deref())?,
+        GatewayError::TokenMismatch
+    );
+    
+    // A fronted transfer was already paid out; the LP is repaid through TX2 instead
+    require!(ctx.accounts.fast_fill.data_is_empty(), GatewayError::FastFillExists);
+    
+    // Parking is a fallback for transfers release_tokens/mint_wrapped cannot deliver
+    let recipient = payload.recipient_pubkey()?;
+    let expected_account = get_associated_token_address_with_program_id(
+        &recipient,
+        &mint,
+        &ctx.accounts.token_program.key(),
+    );
+    require_keys_eq!(
+        ctx.accounts.recipient_token_account.key(),
+        expected_account,
+        GatewayError::InvalidTokenRecipient
+    );
+    let paused = token_config.settings.paused;
+    let blocked = any_blocked(&ctx.accounts.blocklist, &[recipient, mint])?;
+    require!(
+        paused
+            || blocked
+            || token_config.settings.claim_delivery
+            || ctx.accounts.recipient_token_account.data_is_empty(),
+        GatewayError::ReleaseNotBlocked
+    );
+    
+    let amount = token_config.from_wire_amount(payload.amount, ctx.accounts.mint.decimals)?;
+    let kind = token_config.kind;
+    
+    let token_claim = &mut ctx.accounts.token_claim;
+    token_claim.source_chain_id = envelope.source_chain_id;
+    token_claim.tx_id = envelope.tx_id;
+    token_claim.mint = mint;
+    token_claim.kind = kind;
+    token_claim.recipient = recipient;
+    token_claim.sender = payload.sender;
+    token_claim.token = payload.token;
+    token_claim.amount = amount;
+    token_claim.wire_amount = payload.amount;
+    token_claim.relayer = ctx.accounts.relayer.key();
+    token_claim.parked_at = Clock::get()?.unix_timestamp;
+    token_claim.bump = ctx.bumps.token_claim;
+    
+    emit!(TokenTransferParked {
+        tx_id: envelope.tx_id,
+        source_chain_id: envelope.source_chain_id,
+        mint,
+        recipient,
+        amount,
+        paused,
+        blocked,
+        relayer: token_claim.relayer,
+    });
+    
+    msg!("Token transfer parked and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
+
+/// Deliver a parked token transfer to its recipient (recipient only)
+/// The recipient's associated token account is created if missing
+/// remaining_accounts: transfer-hook accounts for Token-2022 mints with a hook
+#[derive(Accounts)]
+pub struct ClaimTokenTransfer<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TOKEN_CLAIM_SEED,
+            token_claim.source_chain_id.to_le_bytes().as_ref(),
+            &token_claim.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = token_claim.bump,
+        has_one = recipient @ GatewayError::InvalidTokenRecipient,
+        has_one = mint @ GatewayError::TokenMismatch,
+        has_one = relayer @ GatewayError::ClaimMismatch
+    )]
+    pub token_claim: Account<'info, TokenClaim>,
+    
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    /// CHECK: Mint's TokenRateLimit PDA; may be uninitialized (no cap), validated in enforce_rate_limit
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub rate_limit: UncheckedAccount<'info>,
+    
+    /// Gateway escrow; required for native mints
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = gateway,
+        associated_token::token_program = token_program
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Treasury token account receiving the bridge fee; required when a fee applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = gateway.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+    
+    /// CHECK: Relayer that paid for the claim account; receives its rent
+    #[account(mut)]
+    pub relayer: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_token_transfer<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimTokenTransfer<'info>>,
+) -> Result<()> {
+    require!(ctx.accounts.gateway.system_enabled, GatewayError::SystemDisabled);
+    
+    let token_claim = &ctx.accounts.token_claim;
+    let amount = token_claim.amount;
+    enforce_blocklist(&ctx.accounts.blocklist, &[token_claim.recipient, token_claim.mint])?;
+    ctx.accounts.token_config.check_path(token_claim.kind)?;
+    enforce_rate_limit(&ctx.accounts.rate_limit, amount)?;
+    let fee = ctx.accounts.token_config.release_fee(amount);
+    ctx.accounts.token_config.record_fee(fee)?;
+    
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]];
+    match token_claim.kind {
+        TokenKind::Native => {
+            let escrow = ctx.accounts.escrow.as_ref().ok_or(GatewayError::MissingEscrow)?;
+            ctx.accounts.token_config.record_release(amount)?;
+            pay_bridge_fee(
+                &ctx.accounts.token_program,
+                escrow,
+                &ctx.accounts.mint,
+                ctx.accounts.treasury_token_account.as_ref(),
+                gateway,
+                ctx.remaining_accounts,
+                signer_seeds,
+                fee,
+            )?;
+            transfer_checked_with_hook(
+                &ctx.accounts.token_program.to_account_info(),
+                &escrow.to_account_info(),
+                &ctx.accounts.mint.to_account_info(),
+                &ctx.accounts.recipient_token_account.to_account_info(),
+                &gateway.to_account_info(),
+                ctx.remaining_accounts,
+                signer_seeds,
+                amount - fee,
+                ctx.accounts.mint.decimals,
+            )?;
+        }
+        TokenKind::Wrapped => {
+            mint_bridge_fee(
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                ctx.accounts.treasury_token_account.as_ref(),
+                gateway,
+                signer_seeds,
+                fee,
+            )?;
+            mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.recipient_token_account.to_account_info(),
+                        authority: gateway.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount - fee,
+            )?;
+        }
+        TokenKind::IssuerBurnMint | TokenKind::ProjectCustody => {
+            return err!(GatewayError::TokenKindMismatch)
+        }
+    }
+    
+    emit!(TokenClaimed {
+        tx_id: token_claim.tx_id,
+        source_chain_id: token_claim.source_chain_id,
+        mint: token_claim.mint,
+        recipient: token_claim.recipient,
+        amount,
+        fee,
+    });
+    
+    msg!("Parked token transfer claimed for tx_id={}", token_claim.tx_id);
+    Ok(())
+}
+
+/// TX2 for return requests: verifies a PAYLOAD_KIND_TOKEN_RETURN message from the parked
+/// transfer's source bridge, closes the claim and sends the tokens back to the original sender
+/// Native tokens stay in escrow (still owed to the source chain); wrapped tokens were never minted
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct ReturnTokenTransfer<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA of the return request, closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender and receives the return
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// Parked transfer being returned; checked against the return payload
+    #[account(
+        mut,
+        close = claim_relayer,
+        seeds = [
+            TOKEN_CLAIM_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &token_claim.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = token_claim.bump,
+        constraint = token_claim.relayer == claim_relayer.key() @ GatewayError::ClaimMismatch
+    )]
+    pub token_claim: Account<'info, TokenClaim>,
+    
+    /// CHECK: Relayer that paid for the claim account; receives its rent
+    #[account(mut)]
+    pub claim_relayer: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn return_token_transfer(
+    ctx: Context<ReturnTokenTransfer>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+    confirmations: u16,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_TOKEN_RETURN)?;
+    let request = TokenReturnPayload::decode(&envelope.on_chain_data)?;
+    
+    let token_claim = &ctx.accounts.token_claim;
+    require!(request.tx_id == token_claim.tx_id, GatewayError::ClaimMismatch);
+    
+    let payload = TokenTransferPayload {
+        token: token_claim.token,
+        amount: token_claim.wire_amount,
+        sender: token_claim.recipient.to_bytes().to_vec(),
+        recipient: token_claim.sender.clone(),
+    };
+    let return_envelope = token_transfer_envelope(
+        &ctx.accounts.gateway,
+        &ctx.accounts.bridge_peer,
+        request.return_tx_id,
+        token_claim.source_chain_id,
+        &payload,
+    )?;
+    
+    emit!(TokenTransferReturned {
+        tx_id: token_claim.tx_id,
+        return_tx_id: request.return_tx_id,
+        source_chain_id: token_claim.source_chain_id,
+        mint: token_claim.mint,
+        sender: token_claim.sender.clone(),
+        wire_amount: token_claim.wire_amount,
+    });
+    
+    emit!(SendRequested {
+        envelope: return_envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!(
+        "Parked token transfer tx_id={} returned as tx_id={}",
+        token_claim.tx_id,
+        request.return_tx_id
+    );
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::ValueReleased;
+use crate::instructions::process_message::{
+    extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::instructions::token_bridge::check_inbound_bridge_message;
+use crate::payloads::{ValueTransferPayload, PAYLOAD_KIND_VALUE_TRANSFER};
+use crate::state::{
+    BridgePeer, ChainConfig, MessageEnvelope, MessageGateway, MessageSignature, RelayerPool,
+    SignerRegistry, TxIdPDA,
+};
+
+/// TX2 for inbound native value transfers: verifies the message like process_message,
+/// then pays the lamports out of the gateway SOL vault to the beneficiary
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct ReleaseValue<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Value bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// Gateway SOL vault holding lamports of in-flight value transfers
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, gateway.instance_seed().as_ref()],
+        bump = gateway.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+    
+    /// CHECK: Beneficiary; must match the payload recipient
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn release_value(
+    ctx: Context<ReleaseValue>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(
+        &envelope,
+        &ctx.accounts.bridge_peer,
+        PAYLOAD_KIND_VALUE_TRANSFER,
+    )?;
+    
+    let payload = ValueTransferPayload::decode(&envelope.on_chain_data)?;
+    let recipient = payload.recipient_pubkey()?;
+    require_keys_eq!(
+        ctx.accounts.recipient.key(),
+        recipient,
+        GatewayError::InvalidTokenRecipient
+    );
+    
+    // The vault is a system account and must stay rent exempt
+    let vault = &ctx.accounts.sol_vault;
+    let available = vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(payload.amount <= available, GatewayError::InsufficientVaultBalance);
+    
+    let instance_seed = ctx.accounts.gateway.instance_seed();
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: vault.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
+            },
+            &[&[SOL_VAULT_SEED, &instance_seed, &[ctx.accounts.gateway.sol_vault_bump]]],
+        ),
+        payload.amount,
+    )?;
+    
+    emit!(ValueReleased {
+        tx_id: envelope.tx_id,
+        recipient,
+        amount: payload.amount,
+        source_chain_id: envelope.source_chain_id,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Value released and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::HashVerified;
+use crate::state::{ChainConfig, MessageEnvelope, MessageGateway};
+use crate::utils::hash::{
+    self, create_cross_chain_hash, create_instance_message_hash_for_signing, HashConsistency,
+};
+
+/// Recompute an envelope's hashes on-chain and compare against a counterpart implementation
+/// Read-only: lets EVM and validator teams prove byte-for-byte parity with the gateway
+/// Also the context of `verify_hash_consistency`
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct VerifyHash<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+}
+
+pub fn verify_hash(
+    ctx: Context<VerifyHash>,
+    envelope: MessageEnvelope,
+    expected_cross_chain_hash: [u8; 32],
+    expected_signing_hash: [u8; 32],
+) -> Result<()> {
+    let hash_scheme = ChainConfig::route_hash_scheme(ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref());
+    
+    let cross_chain_hash = create_cross_chain_hash(hash_scheme, &envelope)?;
+    // Signed by validators for the destination gateway instance's domain
+    let signing_hash = create_instance_message_hash_for_signing(
+        hash_scheme,
+        envelope.dest_chain_id,
+        ctx.accounts.gateway.instance_id,
+        &envelope,
+    )?;
+    
+    emit!(HashVerified {
+        tx_id: envelope.tx_id,
+        cross_chain_hash,
+        signing_hash,
+    });
+    
+    require!(
+        cross_chain_hash == expected_cross_chain_hash && signing_hash == expected_signing_hash,
+        GatewayError::MessageHashMismatch
+    );
+    
+    msg!("Hash verified for tx_id={}", envelope.tx_id);
+    Ok(())
+}
+
+/// Recompute an envelope's hashes and return them with whether `cross_chain_hash` matches
+/// Read-only and never fails on a mismatch: simulate it and decode the return data as
+/// `utils::hash::HashConsistency` to see exactly what the deployed program computes
+pub fn verify_hash_consistency(
+    ctx: Context<VerifyHash>,
+    envelope: MessageEnvelope,
+    cross_chain_hash: [u8; 32],
+) -> Result<HashConsistency> {
+    let hash_scheme = ChainConfig::route_hash_scheme(ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref());
+    
+    let consistent = hash::verify_hash_consistency(&cross_chain_hash, hash_scheme, &envelope)?;
+    let result = HashConsistency {
+        cross_chain_hash: create_cross_chain_hash(hash_scheme, &envelope)?,
+        signing_hash: create_instance_message_hash_for_signing(
+            hash_scheme,
+            envelope.dest_chain_id,
+            ctx.accounts.gateway.instance_id,
+            &envelope,
+        )?,
+        consistent,
+    };
+    
+    msg!("Hash consistency for tx_id={}: {}", envelope.tx_id, consistent);
+    Ok(result)
+}
\ No newline at end of file
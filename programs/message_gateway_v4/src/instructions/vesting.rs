@@ -0,0 +1,415 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{mint_to, Mint, MintTo, TokenAccount, TokenInterface};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{VestedTokensClaimed, VestingScheduleCreated};
+use crate::instructions::process_message::{
+    extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::instructions::token_bridge::{
+    check_inbound_bridge_message, enforce_blocklist, enforce_rate_limit, expected_payload_token,
+    pay_bridge_fee,
+};
+use crate::instructions::wrapped::mint_bridge_fee;
+use crate::payloads::{TokenVestingPayload, PAYLOAD_KIND_TOKEN_VESTING};
+use crate::state::{
+    BridgePeer, ChainConfig, MessageEnvelope, MessageGateway, MessageSignature, RelayerPool,
+    SignerRegistry, TokenConfig, TokenKind, TxIdPDA, VestingSchedule, WrappedAsset,
+};
+use crate::utils::token::transfer_checked_with_hook;
+
+/// TX2 for vesting grants: verifies the message like release_tokens, then records a
+/// VestingSchedule PDA for the recipient instead of paying out
+/// Native tokens stay in escrow until claimed; wrapped tokens are minted on claim
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct CreateVestingSchedule<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// Registry entry mapping the payload token to `mint`; required for wrapped mints
+    #[account(
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            wrapped_asset.source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = wrapped_asset.bump,
+        has_one = mint @ GatewayError::TokenMismatch
+    )]
+    pub wrapped_asset: Option<Account<'info, WrappedAsset>>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + VestingSchedule::SIZE,
+        seeds = [
+            VESTING_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_vesting_schedule(
+    ctx: Context<CreateVestingSchedule>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_TOKEN_VESTING)?;
+    let payload = TokenVestingPayload::decode(&envelope.on_chain_data)?;
+    
+    let token_config = &ctx.accounts.token_config;
+    require!(
+        payload.token == expected_payload_token(token_config, ctx.accounts.wrapped_asset.as_deref())?,
+        GatewayError::TokenMismatch
+    );
+    
+    // Nothing moves yet, so pause and blocklist are enforced on each claim instead
+    let recipient = payload.recipient_pubkey()?;
+    let amount = token_config.from_wire_amount(payload.amount, ctx.accounts.mint.decimals)?;
+    
+    let schedule = &mut ctx.accounts.vesting_schedule;
+    schedule.source_chain_id = envelope.source_chain_id;
+    schedule.tx_id = envelope.tx_id;
+    schedule.mint = ctx.accounts.mint.key();
+    schedule.kind = token_config.kind;
+    schedule.recipient = recipient;
+    schedule.amount = amount;
+    schedule.claimed = 0;
+    schedule.start_time = payload.start_time;
+    schedule.cliff_seconds = payload.cliff_seconds;
+    schedule.duration_seconds = payload.duration_seconds;
+    schedule.period_seconds = payload.period_seconds;
+    schedule.relayer = ctx.accounts.relayer.key();
+    schedule.bump = ctx.bumps.vesting_schedule;
+    
+    emit!(VestingScheduleCreated {
+        tx_id: envelope.tx_id,
+        source_chain_id: envelope.source_chain_id,
+        mint: schedule.mint,
+        recipient,
+        amount,
+        start_time: schedule.start_time,
+        cliff_seconds: schedule.cliff_seconds,
+        duration_seconds: schedule.duration_seconds,
+        period_seconds: schedule.period_seconds,
+    });
+    
+    msg!("Vesting schedule created and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
+
+/// Deliver everything vested so far to the schedule's recipient (recipient only)
+/// The recipient's associated token account is created if missing; the schedule is closed
+/// to the relayer that paid for it once the whole grant is claimed
+/// remaining_accounts: transfer-hook accounts for Token-2022 mints with a hook
+#[derive(Accounts)]
+pub struct ClaimVestedTokens<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        mut,
+        seeds = [
+            VESTING_SEED,
+            vesting_schedule.source_chain_id.to_le_bytes().as_ref(),
+            &vesting_schedule.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = vesting_schedule.bump,
+        has_one = recipient @ GatewayError::InvalidTokenRecipient,
+        has_one = mint @ GatewayError::TokenMismatch,
+        has_one = relayer @ GatewayError::ClaimMismatch
+    )]
+    pub vesting_schedule: Account<'info, VestingSchedule>,
+    
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    /// CHECK: Mint's TokenRateLimit PDA; may be uninitialized (no cap), validated in enforce_rate_limit
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub rate_limit: UncheckedAccount<'info>,
+    
+    /// Gateway escrow; required for native mints
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = gateway,
+        associated_token::token_program = token_program
+    )]
+    pub escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Treasury token account receiving the bridge fee; required when a fee applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = gateway.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+    
+    /// CHECK: Relayer that paid for the schedule account; receives its rent on completion
+    #[account(mut)]
+    pub relayer: UncheckedAccount<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_vested_tokens<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimVestedTokens<'info>>,
+) -> Result<()> {
+    require!(ctx.accounts.gateway.system_enabled, GatewayError::SystemDisabled);
+    
+    let schedule = &mut ctx.accounts.vesting_schedule;
+    enforce_blocklist(&ctx.accounts.blocklist, &[schedule.recipient, schedule.mint])?;
+    ctx.accounts.token_config.check_path(schedule.kind)?;
+    let amount = schedule.claim(Clock::get()?.unix_timestamp)?;
+    enforce_rate_limit(&ctx.accounts.rate_limit, amount)?;
+    let fee = ctx.accounts.token_config.release_fee(amount);
+    ctx.accounts.token_config.record_fee(fee)?;
+    
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]];
+    match schedule.kind {
+        TokenKind::Native => {
+            let escrow = ctx.accounts.escrow.as_ref().ok_or(GatewayError::MissingEscrow)?;
+            ctx.accounts.token_config.record_release(amount)?;
+            pay_bridge_fee(
+                &ctx.accounts.token_program,
+                escrow,
+                &ctx.accounts.mint,
+                ctx.accounts.treasury_token_account.as_ref(),
+                gateway,
+                ctx.remaining_accounts,
+                signer_seeds,
+                fee,
+            )?;
+            transfer_checked_with_hook(
+                &ctx.accounts.token_program.to_account_info(),
+                &escrow.to_account_info(),
+                &ctx.accounts.mint.to_account_info(),
+                &ctx.accounts.recipient_token_account.to_account_info(),
+                &gateway.to_account_info(),
+                ctx.remaining_accounts,
+                signer_seeds,
+                amount - fee,
+                ctx.accounts.mint.decimals,
+            )?;
+        }
+        TokenKind::Wrapped => {
+            mint_bridge_fee(
+                &ctx.accounts.token_program,
+                &ctx.accounts.mint,
+                ctx.accounts.treasury_token_account.as_ref(),
+                gateway,
+                signer_seeds,
+                fee,
+            )?;
+            mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.recipient_token_account.to_account_info(),
+                        authority: gateway.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount - fee,
+            )?;
+        }
+        TokenKind::IssuerBurnMint | TokenKind::ProjectCustody => {
+            return err!(GatewayError::TokenKindMismatch)
+        }
+    }
+    
+    let schedule = &ctx.accounts.vesting_schedule;
+    let complete = schedule.is_complete();
+    emit!(VestedTokensClaimed {
+        tx_id: schedule.tx_id,
+        source_chain_id: schedule.source_chain_id,
+        mint: schedule.mint,
+        recipient: schedule.recipient,
+        amount,
+        fee,
+        claimed: schedule.claimed,
+        complete,
+    });
+    
+    msg!("Vested tokens claimed for tx_id={}: {}", schedule.tx_id, amount);
+    if complete {
+        let relayer = ctx.accounts.relayer.to_account_info();
+        ctx.accounts.vesting_schedule.close(relayer)?;
+    }
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,1057 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::{
+    create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
+    Metadata,
+};
+use anchor_spl::token_interface::{
+    burn, mint_to, transfer_checked, Burn, Mint, MintTo, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::events::{
+    SendRequested, WrappedAssetCreated, WrappedAssetDelisted, WrappedAssetListingCancelled,
+    WrappedAssetListingProposed, WrappedAssetMetadataSet, WrappedTokensBurned,
+    WrappedTokensMinted,
+};
+use crate::instructions::fast_transfer::check_delivery_account;
+use crate::instructions::process_message::{
+    extra_verifier_count, record_history, verify_inbound_message,
+};
+use crate::instructions::relayer_pool::settle_tx_id_rent;
+use crate::instructions::token_bridge::{
+    check_inbound_bridge_message, decode_inbound_transfer, enforce_blocklist, enforce_rate_limit,
+    token_transfer_envelope,
+};
+use crate::payloads::{AssetMetadataPayload, TokenTransferPayload, PAYLOAD_KIND_ASSET_METADATA};
+use crate::state::{
+    BridgePeer, ChainConfig, MessageEnvelope, MessageGateway, MessageSignature, OutboundAmounts,
+    RelayerPool, SignerRegistry, TokenConfig, TokenKind, TokenSettings, TxIdPDA, WrappedAsset,
+    WrappedAssetListing,
+};
+use crate::utils::address::validate_remote_address;
+
+/// Propose listing a foreign asset as a wrapped mint (admin only)
+/// The mint can be created once WRAPPED_LISTING_TIMELOCK_SECONDS have passed
+#[derive(Accounts)]
+#[instruction(source_chain_id: u64, source_token: [u8; 32])]
+pub struct ProposeWrappedAsset<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + WrappedAssetListing::SIZE,
+        seeds = [
+            WRAPPED_LISTING_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub listing: Account<'info, WrappedAssetListing>,
+    
+    /// CHECK: Wrapped asset registry entry; must not exist yet
+    #[account(
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump,
+        constraint = wrapped_asset.data_is_empty() @ GatewayError::WrappedAssetListed
+    )]
+    pub wrapped_asset: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_wrapped_asset(
+    ctx: Context<ProposeWrappedAsset>,
+    source_chain_id: u64,
+    source_token: [u8; 32],
+    decimals: u8,
+) -> Result<()> {
+    require!(source_chain_id > 0, GatewayError::InvalidChainId);
+    require!(
+        source_chain_id != ctx.accounts.gateway.chain_id,
+        GatewayError::InvalidSourceChain
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    let listing = &mut ctx.accounts.listing;
+    listing.source_chain_id = source_chain_id;
+    listing.source_token = source_token;
+    listing.decimals = decimals;
+    listing.proposed_by = ctx.accounts.authority.key();
+    listing.proposed_at = now;
+    listing.execute_after = now
+        .checked_add(WRAPPED_LISTING_TIMELOCK_SECONDS)
+        .ok_or(GatewayError::InvalidAmount)?;
+    listing.bump = ctx.bumps.listing;
+    
+    emit!(WrappedAssetListingProposed {
+        source_chain_id,
+        source_token,
+        decimals,
+        execute_after: listing.execute_after,
+        proposed_by: listing.proposed_by,
+    });
+    
+    msg!("Wrapped asset listing proposed for chain {}", source_chain_id);
+    Ok(())
+}
+
+/// Cancel a pending wrapped-asset listing (admin only)
+#[derive(Accounts)]
+pub struct CancelWrappedAssetListing<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            WRAPPED_LISTING_SEED,
+            listing.source_chain_id.to_le_bytes().as_ref(),
+            listing.source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = listing.bump
+    )]
+    pub listing: Account<'info, WrappedAssetListing>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn cancel_wrapped_asset_listing(ctx: Context<CancelWrappedAssetListing>) -> Result<()> {
+    let listing = &ctx.accounts.listing;
+    
+    emit!(WrappedAssetListingCancelled {
+        source_chain_id: listing.source_chain_id,
+        source_token: listing.source_token,
+        cancelled_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Wrapped asset listing cancelled for chain {}", listing.source_chain_id);
+    Ok(())
+}
+
+/// Create the wrapped mint of a proposed listing once its timelock has elapsed
+/// Anyone may execute the listing; the payer funds the new accounts
+/// The mint is created under whichever token program is passed (SPL Token or Token-2022)
+/// with the source decimals capped at MAX_WRAPPED_DECIMALS
+#[derive(Accounts)]
+#[instruction(source_chain_id: u64, source_token: [u8; 32])]
+pub struct CreateWrappedMint<'info> {
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [
+            WRAPPED_LISTING_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = listing.bump,
+        constraint = listing.proposed_by == proposer.key() @ GatewayError::UnauthorizedAuthority
+    )]
+    pub listing: Account<'info, WrappedAssetListing>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + WrappedAsset::SIZE,
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub wrapped_asset: Account<'info, WrappedAsset>,
+    
+    #[account(
+        init,
+        payer = payer,
+        seeds = [
+            WRAPPED_MINT_SEED,
+            source_chain_id.to_le_bytes().as_ref(),
+            source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump,
+        mint::decimals = listing.decimals.min(MAX_WRAPPED_DECIMALS),
+        mint::authority = gateway,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TokenConfig::SIZE,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// CHECK: Authority that proposed the listing; receives the listing's rent
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_wrapped_mint(
+    ctx: Context<CreateWrappedMint>,
+    source_chain_id: u64,
+    source_token: [u8; 32],
+) -> Result<()> {
+    let listing = &ctx.accounts.listing;
+    require!(
+        listing.is_executable(Clock::get()?.unix_timestamp),
+        GatewayError::ListingTimelockActive
+    );
+    let decimals = listing.decimals;
+    
+    let wrapped_asset = &mut ctx.accounts.wrapped_asset;
+    wrapped_asset.source_chain_id = source_chain_id;
+    wrapped_asset.source_token = source_token;
+    wrapped_asset.mint = ctx.accounts.mint.key();
+    wrapped_asset.bump = ctx.bumps.wrapped_asset;
+    wrapped_asset.delisted = false;
+    
+    init_wrapped_token_config(
+        &mut ctx.accounts.token_config,
+        wrapped_asset.mint,
+        decimals,
+        ctx.bumps.token_config,
+    );
+    
+    emit!(WrappedAssetCreated {
+        source_chain_id,
+        source_token,
+        mint: wrapped_asset.mint,
+        decimals,
+    });
+    
+    msg!("Wrapped mint {} created for chain {}", wrapped_asset.mint, source_chain_id);
+    Ok(())
+}
+
+/// Delist a wrapped asset (admin only)
+/// Inbound transfers of the asset are refused from then on; holders can still burn it
+/// back to the source chain
+#[derive(Accounts)]
+pub struct DelistWrappedAsset<'info> {
+    #[account(
+        mut,
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            wrapped_asset.source_chain_id.to_le_bytes().as_ref(),
+            wrapped_asset.source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = wrapped_asset.bump,
+        constraint = !wrapped_asset.delisted @ GatewayError::WrappedAssetDelisted
+    )]
+    pub wrapped_asset: Account<'info, WrappedAsset>,
+    
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion,
+        has_one = authority @ GatewayError::UnauthorizedAuthority
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    pub authority: Signer<'info>,
+}
+
+pub fn delist_wrapped_asset(ctx: Context<DelistWrappedAsset>) -> Result<()> {
+    let wrapped_asset = &mut ctx.accounts.wrapped_asset;
+    wrapped_asset.delisted = true;
+    
+    emit!(WrappedAssetDelisted {
+        source_chain_id: wrapped_asset.source_chain_id,
+        source_token: wrapped_asset.source_token,
+        mint: wrapped_asset.mint,
+        delisted_by: ctx.accounts.authority.key(),
+    });
+    
+    msg!("Wrapped asset {} delisted", wrapped_asset.mint);
+    Ok(())
+}
+
+/// TX2 for validator-attested asset metadata: creates the wrapped mint with the
+/// source asset's decimals (capped at MAX_WRAPPED_DECIMALS) and a Metaplex metadata
+/// account carrying its name and symbol
+/// `source_token` and `decimals` must match the attested payload
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope, signatures: Vec<MessageSignature>, source_token: [u8; 32], decimals: u8)]
+pub struct AttestWrappedAsset<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + WrappedAsset::SIZE,
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub wrapped_asset: Account<'info, WrappedAsset>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        seeds = [
+            WRAPPED_MINT_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump,
+        mint::decimals = decimals.min(MAX_WRAPPED_DECIMALS),
+        mint::authority = gateway,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + TokenConfig::SIZE,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub token_config: Box<Account<'info, TokenConfig>>,
+    
+    /// CHECK: Metaplex metadata PDA of the wrapped mint, created by CPI
+    #[account(
+        mut,
+        seeds = [b"metadata", token_metadata_program.key().as_ref(), mint.key().as_ref()],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub metadata: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub token_metadata_program: Program<'info, Metadata>,
+    
+    pub system_program: Program<'info, System>,
+    
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn attest_wrapped_asset(
+    ctx: Context<AttestWrappedAsset>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+    source_token: [u8; 32],
+    decimals: u8,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    check_inbound_bridge_message(&envelope, &ctx.accounts.bridge_peer, PAYLOAD_KIND_ASSET_METADATA)?;
+    let asset = AssetMetadataPayload::decode(&envelope.on_chain_data)?;
+    require!(
+        asset.token == source_token && asset.decimals == decimals,
+        GatewayError::TokenMismatch
+    );
+    
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.metadata.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                mint_authority: gateway.to_account_info(),
+                payer: ctx.accounts.relayer.to_account_info(),
+                update_authority: gateway.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]],
+        ),
+        DataV2 {
+            name: asset.name.clone(),
+            symbol: asset.symbol.clone(),
+            uri: asset.uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )?;
+    
+    let wrapped_asset = &mut ctx.accounts.wrapped_asset;
+    wrapped_asset.source_chain_id = envelope.source_chain_id;
+    wrapped_asset.source_token = source_token;
+    wrapped_asset.mint = ctx.accounts.mint.key();
+    wrapped_asset.bump = ctx.bumps.wrapped_asset;
+    wrapped_asset.delisted = false;
+    
+    init_wrapped_token_config(
+        &mut ctx.accounts.token_config,
+        wrapped_asset.mint,
+        decimals,
+        ctx.bumps.token_config,
+    );
+    
+    emit!(WrappedAssetCreated {
+        source_chain_id: wrapped_asset.source_chain_id,
+        source_token,
+        mint: wrapped_asset.mint,
+        decimals,
+    });
+    
+    emit!(WrappedAssetMetadataSet {
+        mint: wrapped_asset.mint,
+        name: asset.name,
+        symbol: asset.symbol,
+        uri: asset.uri,
+    });
+    
+    msg!("Wrapped mint {} attested and TxId PDA closed for tx_id={}", wrapped_asset.mint, envelope.tx_id);
+    Ok(())
+}
+
+/// Burn wrapped tokens and send them back to their source chain
+#[derive(Accounts)]
+pub struct BurnWrapped<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    #[account(
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            wrapped_asset.source_chain_id.to_le_bytes().as_ref(),
+            wrapped_asset.source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = wrapped_asset.bump,
+        has_one = mint @ GatewayError::TokenMismatch
+    )]
+    pub wrapped_asset: Account<'info, WrappedAsset>,
+    
+    /// Token bridge on the asset's source chain
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            wrapped_asset.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// Optional source chain config validating the recipient address format
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            wrapped_asset.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_config.bump
+    )]
+    pub chain_config: Option<Account<'info, ChainConfig>>,
+    
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = holder,
+        token::token_program = token_program
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// Treasury token account receiving the bridge fee; required when a fee applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = gateway.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub holder: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn burn_wrapped(
+    ctx: Context<BurnWrapped>,
+    tx_id: u128,
+    recipient: Vec<u8>,
+    amount: u64,
+    confirmations: u16,
+) -> Result<()> {
+    let gateway = &ctx.accounts.gateway;
+    let wrapped_asset = &ctx.accounts.wrapped_asset;
+    let dest_chain_id = wrapped_asset.source_chain_id;
+    
+    require!(gateway.system_enabled, GatewayError::SystemDisabled);
+    require!(tx_id > 0, GatewayError::InvalidTxId);
+    
+    // Beneficiary must be a valid address for the source chain family
+    if let Some(chain_config) = ctx.accounts.chain_config.as_ref() {
+        validate_remote_address(chain_config.address_format, &recipient)?;
+    }
+    
+    enforce_blocklist(
+        &ctx.accounts.blocklist,
+        &[ctx.accounts.holder.key(), ctx.accounts.mint.key()],
+    )?;
+    
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.check_outbound(TokenKind::Wrapped, amount)?;
+    let decimals = ctx.accounts.mint.decimals;
+    let OutboundAmounts { bridged, fee, wire } = token_config.outbound_amounts(amount, decimals)?;
+    
+    let holder = ctx.accounts.holder.key();
+    let payload = TokenTransferPayload {
+        token: wrapped_asset.source_token,
+        amount: wire,
+        sender: holder.to_bytes().to_vec(),
+        recipient: recipient.clone(),
+    };
+    
+    // The fee and any scaling dust go to the treasury rather than being burned
+    if fee > 0 {
+        let treasury_token_account = ctx
+            .accounts
+            .treasury_token_account
+            .as_ref()
+            .ok_or(GatewayError::MissingTreasuryAccount)?;
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.holder_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.holder.to_account_info(),
+                },
+            ),
+            fee,
+            decimals,
+        )?;
+        token_config.record_fee(fee)?;
+    }
+    
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.holder_token_account.to_account_info(),
+                authority: ctx.accounts.holder.to_account_info(),
+            },
+        ),
+        bridged,
+    )?;
+    
+    let envelope = token_transfer_envelope(
+        gateway,
+        &ctx.accounts.bridge_peer,
+        tx_id,
+        dest_chain_id,
+        &payload,
+    )?;
+    
+    emit!(WrappedTokensBurned {
+        tx_id,
+        mint: wrapped_asset.mint,
+        holder,
+        amount: bridged,
+        fee,
+        dest_chain_id,
+        recipient,
+    });
+    
+    emit!(SendRequested {
+        envelope,
+        confirmations,
+        fee_paid: 0,
+    });
+    
+    msg!("Wrapped tokens burned: tx_id={}, amount={}, dest_chain={}", tx_id, bridged, dest_chain_id);
+    Ok(())
+}
+
+/// TX2 for inbound transfers of foreign assets: verifies the message like
+/// process_message, then mints the wrapped token to the beneficiary
+#[derive(Accounts)]
+#[instruction(envelope: MessageEnvelope)]
+pub struct MintWrapped<'info> {
+    #[account(
+        seeds = [GATEWAY_SEED, gateway.chain_id.to_le_bytes().as_ref(), gateway.instance_seed().as_ref()],
+        bump = gateway.bump,
+        constraint = gateway.is_compatible() @ GatewayError::IncompatibleVersion
+    )]
+    pub gateway: Account<'info, MessageGateway>,
+    
+    /// TxId PDA that will be closed atomically
+    #[account(
+        mut,
+        close = relayer,
+        seeds = [
+            TX_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = tx_id_pda.bump
+    )]
+    pub tx_id_pda: Account<'info, TxIdPDA>,
+    
+    /// Token bridge on the source chain; must be the envelope sender
+    #[account(
+        seeds = [
+            BRIDGE_PEER_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = bridge_peer.bump
+    )]
+    pub bridge_peer: Account<'info, BridgePeer>,
+    
+    /// CHECK: Source chain's ChainConfig PDA; may be uninitialized (keccak256/LE route), loaded by ChainConfig::load_route
+    #[account(
+        seeds = [
+            CHAIN_CONFIG_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub chain_config: UncheckedAccount<'info>,
+    
+    /// VIA signer registry for VIA-level validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::VIA.discriminant()?.to_le_bytes(),
+            envelope.dest_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = via_registry.bump
+    )]
+    pub via_registry: Account<'info, SignerRegistry>,
+    
+    /// Chain signer registry for source chain validation
+    #[account(
+        seeds = [
+            SIGNER_REGISTRY_SEED,
+            &crate::state::SignerRegistryType::Chain.discriminant()?.to_le_bytes(),
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = chain_registry.bump
+    )]
+    pub chain_registry: Account<'info, SignerRegistry>,
+    
+    /// Optional project signer registry for application-level validation
+    pub project_registry: Option<Account<'info, SignerRegistry>>,
+    
+    /// Relayer pool that advanced the TxId PDA rent, if TX1 was pooled
+    #[account(mut)]
+    pub relayer_pool: Option<Box<Account<'info, RelayerPool>>>,
+    
+    /// Wallet that funded the TxId PDA rent, if TX1 named one
+    #[account(mut)]
+    pub payer: Option<SystemAccount<'info>>,
+    
+    /// Registry entry of the transferred asset; checked against the payload
+    #[account(
+        seeds = [
+            WRAPPED_ASSET_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            wrapped_asset.source_token.as_ref(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump = wrapped_asset.bump,
+        has_one = mint @ GatewayError::TokenMismatch
+    )]
+    pub wrapped_asset: Account<'info, WrappedAsset>,
+    
+    #[account(
+        mut,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [TOKEN_CONFIG_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump = token_config.bump
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: Compliance blocklist PDA; may be uninitialized (empty), validated in enforce_blocklist
+    #[account(seeds = [BLOCKLIST_SEED, gateway.instance_seed().as_ref()], bump)]
+    pub blocklist: UncheckedAccount<'info>,
+    
+    /// CHECK: Mint's TokenRateLimit PDA; may be uninitialized (no cap), validated in enforce_rate_limit
+    #[account(
+        mut,
+        seeds = [RATE_LIMIT_SEED, mint.key().as_ref(), gateway.instance_seed().as_ref()],
+        bump
+    )]
+    pub rate_limit: UncheckedAccount<'info>,
+    
+    /// Treasury token account receiving the bridge fee; required when a fee applies
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = gateway.treasury,
+        token::token_program = token_program
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    /// Beneficiary token account; its owner must match the payload recipient, or it must
+    /// be the LP repayment account when the transfer was fronted
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: Transfer's FastFill PDA; may be uninitialized (not fronted), validated in settle_fast_fill
+    #[account(
+        mut,
+        seeds = [
+            FAST_FILL_SEED,
+            envelope.source_chain_id.to_le_bytes().as_ref(),
+            &envelope.tx_id.to_le_bytes(),
+            gateway.instance_seed().as_ref()
+        ],
+        bump
+    )]
+    pub fast_fill: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn mint_wrapped(
+    ctx: Context<MintWrapped>,
+    envelope: MessageEnvelope,
+    signatures: Vec<MessageSignature>,
+) -> Result<()> {
+    verify_inbound_message(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ChainConfig::load_route(&ctx.accounts.chain_config)?.as_ref(),
+        &ctx.accounts.via_registry,
+        &ctx.accounts.chain_registry,
+        ctx.accounts.project_registry.as_deref(),
+        ctx.remaining_accounts,
+        &ctx.accounts.instructions,
+        &envelope,
+        &signatures,
+    )?;
+    
+    record_history(
+        &ctx.accounts.gateway,
+        &ctx.accounts.tx_id_pda,
+        ctx.remaining_accounts,
+        extra_verifier_count(ctx.accounts.project_registry.as_deref()),
+        &envelope,
+    )?;
+    
+    settle_tx_id_rent(
+        &ctx.accounts.tx_id_pda,
+        ctx.accounts.relayer_pool.as_deref_mut(),
+        ctx.accounts.payer.as_deref(),
+    )?;
+    
+    let payload = decode_inbound_transfer(&envelope, &ctx.accounts.bridge_peer)?;
+    require!(
+        payload.token == ctx.accounts.wrapped_asset.source_token,
+        GatewayError::TokenMismatch
+    );
+    require!(!ctx.accounts.wrapped_asset.delisted, GatewayError::WrappedAssetDelisted);
+    
+    let recipient = payload.recipient_pubkey()?;
+    enforce_blocklist(&ctx.accounts.blocklist, &[recipient, ctx.accounts.mint.key()])?;
+    check_delivery_account(
+        &ctx.accounts.fast_fill,
+        &envelope,
+        &ctx.accounts.recipient_token_account,
+        recipient,
+        &ctx.accounts.token_config,
+    )?;
+    
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.check_path(TokenKind::Wrapped)?;
+    let amount = token_config.from_wire_amount(payload.amount, ctx.accounts.mint.decimals)?;
+    enforce_rate_limit(&ctx.accounts.rate_limit, amount)?;
+    let fee = token_config.release_fee(amount);
+    token_config.record_fee(fee)?;
+    
+    // The gateway PDA holds mint authority over wrapped mints
+    let gateway = &ctx.accounts.gateway;
+    let chain_id_bytes = gateway.chain_id.to_le_bytes();
+    let instance_seed = gateway.instance_seed();
+    let signer_seeds: &[&[&[u8]]] = &[&[GATEWAY_SEED, chain_id_bytes.as_ref(), instance_seed.as_ref(), &[gateway.bump]]];
+    mint_bridge_fee(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        ctx.accounts.treasury_token_account.as_ref(),
+        gateway,
+        signer_seeds,
+        fee,
+    )?;
+    
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: gateway.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount - fee,
+    )?;
+    
+    emit!(WrappedTokensMinted {
+        tx_id: envelope.tx_id,
+        mint: ctx.accounts.mint.key(),
+        recipient,
+        amount,
+        fee,
+        source_chain_id: envelope.source_chain_id,
+        relayer: ctx.accounts.relayer.key(),
+    });
+    
+    msg!("Wrapped tokens minted and TxId PDA closed for tx_id={}", envelope.tx_id);
+    Ok(())
+}
+
+/// Mint a bridge fee in the wrapped token to the treasury
+pub(crate) fn mint_bridge_fee<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    treasury_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    gateway: &Account<'info, MessageGateway>,
+    signer_seeds: &[&[&[u8]]],
+    fee: u64,
+) -> Result<()> {
+    if fee == 0 {
+        return Ok(());
+    }
+    
+    let treasury_token_account = treasury_token_account.ok_or(GatewayError::MissingTreasuryAccount)?;
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: mint.to_account_info(),
+                to: treasury_token_account.to_account_info(),
+                authority: gateway.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        fee,
+    )
+}
+
+/// Configure a freshly created wrapped mint: unpaused, unbounded and fee-free, with the
+/// source asset's decimals as its remote decimals so payload amounts stay in the
+/// wrapped mint's (capped) decimals
+fn init_wrapped_token_config(token_config: &mut TokenConfig, mint: Pubkey, decimals: u8, bump: u8) {
+    token_config.mint = mint;
+    token_config.kind = TokenKind::Wrapped;
+    token_config.governance = Pubkey::default();
+    token_config.settings = TokenSettings {
+        remote_decimals: decimals,
+        ..TokenSettings::default()
+    };
+    token_config.escrowed_amount = 0;
+    token_config.fees_accrued = 0;
+    token_config.bump = bump;
+}
\ No newline at end of file
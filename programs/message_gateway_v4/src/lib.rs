@@ -4,7 +4,11 @@ pub mod constants;
 pub mod errors;
 pub mod events;
 pub mod instructions;
+pub mod payloads;
 pub mod state;
+pub mod test_vectors;
+#[cfg(all(feature = "txbuilder", not(target_os = "solana")))]
+pub mod txbuilder;
 pub mod utils;
 
 use instructions::*;
@@ -27,66 +31,148 @@ pub mod message_gateway_v4 {
         instructions::initialize::handler(ctx, chain_id)
     }
 
+    /// Initialize an additional gateway instance for a chain
+    pub fn initialize_gateway_instance(
+        ctx: Context<InitializeGatewayInstance>,
+        chain_id: u64,
+        instance_id: u16,
+    ) -> Result<()> {
+        instructions::initialize::initialize_instance(ctx, chain_id, instance_id)
+    }
+
     /// Send a cross-chain message
     pub fn send_message(
         ctx: Context<SendMessage>,
-        tx_id: u128,
-        recipient: Vec<u8>,
-        dest_chain_id: u64,
-        chain_data: Vec<u8>,
+        envelope: crate::state::MessageEnvelope,
         confirmations: u16,
     ) -> Result<()> {
-        instructions::send_message::handler(ctx, tx_id, recipient, dest_chain_id, chain_data, confirmations)
+        instructions::send_message::handler(ctx, envelope, confirmations)
     }
 
     /// TX1: Create TxId PDA for replay protection
     pub fn create_tx_pda(
         ctx: Context<CreateTxPda>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::create_tx_pda::handler(ctx, envelope, signatures)
+    }
+
+    /// TX1 with compact-encoded message arguments
+    pub fn create_tx_pda_compact(
+        ctx: Context<CreateTxPdaCompact>,
+        source_chain_id: u64,
         tx_id: u128,
+        message: Vec<u8>,
+    ) -> Result<()> {
+        instructions::create_tx_pda::handler_compact(ctx, source_chain_id, tx_id, message)
+    }
+
+    /// Open a buffer for a payload too large for one transaction
+    pub fn create_message_buffer(
+        ctx: Context<CreateMessageBuffer>,
         source_chain_id: u64,
-        dest_chain_id: u64,
-        sender: Vec<u8>,
-        recipient: Vec<u8>,
-        on_chain_data: Vec<u8>,
-        off_chain_data: Vec<u8>,
-        signatures: Vec<crate::state::MessageSignature>,
+        tx_id: u128,
+        total_size: u32,
+        payload_hash: [u8; 32],
     ) -> Result<()> {
-        instructions::create_tx_pda::handler(
+        instructions::message_buffer::create_message_buffer(
             ctx,
-            tx_id,
             source_chain_id,
-            dest_chain_id,
-            sender,
-            recipient,
-            on_chain_data,
-            off_chain_data,
-            signatures,
+            tx_id,
+            total_size,
+            payload_hash,
         )
     }
 
+    /// Append the next payload chunk to a message buffer
+    pub fn create_message_chunk(
+        ctx: Context<CreateMessageChunk>,
+        source_chain_id: u64,
+        tx_id: u128,
+        offset: u32,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        instructions::message_buffer::create_message_chunk(ctx, source_chain_id, tx_id, offset, chunk)
+    }
+
+    /// Close an abandoned message buffer
+    pub fn close_message_buffer(
+        ctx: Context<CloseMessageBuffer>,
+        source_chain_id: u64,
+        tx_id: u128,
+    ) -> Result<()> {
+        instructions::message_buffer::close_message_buffer(ctx, source_chain_id, tx_id)
+    }
+
+    /// TX1 for a chunked message, reading the payload from its completed buffer
+    pub fn create_tx_pda_buffered(
+        ctx: Context<CreateTxPdaBuffered>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::create_tx_pda::handler_buffered(ctx, envelope, signatures)
+    }
+
+    /// TX1 with the TxId PDA rent advanced from the relayer's pool
+    pub fn create_tx_pda_pooled(
+        ctx: Context<CreateTxPdaPooled>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::create_tx_pda::handler_pooled(ctx, envelope, signatures)
+    }
+
     /// TX2: Process message with atomic PDA closure
     pub fn process_message(
         ctx: Context<ProcessMessage>,
-        tx_id: u128,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::process_message::handler(ctx, envelope, signatures)
+    }
+
+    /// TX2 with compact-encoded message arguments
+    pub fn process_message_compact(
+        ctx: Context<ProcessMessageCompact>,
         source_chain_id: u64,
-        dest_chain_id: u64,
-        sender: Vec<u8>,
-        recipient: Vec<u8>,
-        on_chain_data: Vec<u8>,
-        off_chain_data: Vec<u8>,
+        tx_id: u128,
+        message: Vec<u8>,
+    ) -> Result<()> {
+        instructions::process_message::handler_compact(ctx, source_chain_id, tx_id, message)
+    }
+
+    /// Slim TX2: process a message whose payload TX1 already verified, without re-sending it
+    pub fn process_message_by_hash(
+        ctx: Context<ProcessMessage>,
+        envelope: crate::state::MessageEnvelope,
         signatures: Vec<crate::state::MessageSignature>,
     ) -> Result<()> {
-        instructions::process_message::handler(
-            ctx,
-            tx_id,
-            source_chain_id,
-            dest_chain_id,
-            sender,
-            recipient,
-            on_chain_data,
-            off_chain_data,
-            signatures,
-        )
+        instructions::process_message::handler_by_hash(ctx, envelope, signatures)
+    }
+
+    /// TX2 validated against a registry bundle instead of the three registry accounts
+    pub fn process_message_bundled(
+        ctx: Context<ProcessMessageBundled>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::process_message::handler_bundled(ctx, envelope, signatures)
+    }
+
+    /// Close expired TxId PDAs passed in remaining_accounts, refunding their relayers
+    pub fn gc_batch<'info>(ctx: Context<'_, '_, 'info, 'info, GcBatch<'info>>) -> Result<()> {
+        instructions::gc_batch::gc_batch(ctx)
+    }
+
+    /// Add lamports to a relayer's TxId PDA rent pool
+    pub fn deposit_relayer_pool(ctx: Context<DepositRelayerPool>, amount: u64) -> Result<()> {
+        instructions::relayer_pool::deposit_relayer_pool(ctx, amount)
+    }
+
+    /// Withdraw lamports not lent to open TxId PDAs from a relayer's rent pool
+    pub fn withdraw_relayer_pool(ctx: Context<WithdrawRelayerPool>, amount: u64) -> Result<()> {
+        instructions::relayer_pool::withdraw_relayer_pool(ctx, amount)
     }
 
     /// Update system enabled status (admin only)
@@ -122,6 +208,28 @@ pub mod message_gateway_v4 {
         )
     }
 
+    /// Test-only: gateway, source-chain counter and VIA/Chain(/Project) registries in one call
+    #[cfg(feature = "test-env")]
+    pub fn bootstrap_test_env(
+        ctx: Context<BootstrapTestEnv>,
+        chain_id: u64,
+        source_chain_id: u64,
+        via_signers: Vec<Pubkey>,
+        chain_signers: Vec<Pubkey>,
+        project_signers: Vec<Pubkey>,
+        required_signatures: u8,
+    ) -> Result<()> {
+        instructions::bootstrap::handler(
+            ctx,
+            chain_id,
+            source_chain_id,
+            via_signers,
+            chain_signers,
+            project_signers,
+            required_signatures,
+        )
+    }
+
     /// Update signers in an existing registry
     pub fn update_signers(
         ctx: Context<UpdateSigners>,
@@ -178,4 +286,769 @@ pub mod message_gateway_v4 {
     ) -> Result<()> {
         instructions::signer_registry::set_registry_enabled(ctx, registry_type, chain_id, enabled)
     }
+
+    /// Start or end a shadow trial of a registry's threshold
+    pub fn set_registry_shadow(
+        ctx: Context<SetRegistryShadow>,
+        registry_type: crate::state::SignerRegistryType,
+        chain_id: u64,
+        enforced_signatures: u8,
+        shadow_until: i64,
+    ) -> Result<()> {
+        instructions::signer_registry::set_registry_shadow(
+            ctx,
+            registry_type,
+            chain_id,
+            enforced_signatures,
+            shadow_until,
+        )
+    }
+
+    /// Configure the extra verifier sets a project registry requires
+    pub fn set_extra_verifiers(
+        ctx: Context<SetExtraVerifiers>,
+        registry_type: crate::state::SignerRegistryType,
+        chain_id: u64,
+        verifier_ids: Vec<u8>,
+    ) -> Result<()> {
+        instructions::signer_registry::set_extra_verifiers(ctx, registry_type, chain_id, verifier_ids)
+    }
+
+    /// Snapshot a route's signer registries into a registry bundle (permissionless)
+    pub fn create_registry_bundle(
+        ctx: Context<CreateRegistryBundle>,
+        source_chain_id: u64,
+        with_project: bool,
+    ) -> Result<()> {
+        instructions::registry_bundle::create_registry_bundle(ctx, source_chain_id, with_project)
+    }
+
+    /// Start recording processed messages in a concurrent Merkle tree (admin only)
+    pub fn set_message_history_tree(
+        ctx: Context<SetMessageHistoryTree>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        instructions::message_history::set_message_history_tree(ctx, max_depth, max_buffer_size)
+    }
+
+    /// Re-sync a registry bundle after a signer registry change (permissionless)
+    pub fn sync_registry_bundle(
+        ctx: Context<SyncRegistryBundle>,
+        source_chain_id: u64,
+        with_project: bool,
+    ) -> Result<()> {
+        instructions::registry_bundle::sync_registry_bundle(ctx, source_chain_id, with_project)
+    }
+
+    /// Initialize the configuration for a counterpart chain (admin only)
+    pub fn initialize_chain_config(
+        ctx: Context<InitializeChainConfig>,
+        chain_id: u64,
+        hash_algorithm: crate::state::HashAlgorithm,
+        integer_encoding: crate::state::IntegerEncoding,
+    ) -> Result<()> {
+        instructions::chain_config::initialize_chain_config(ctx, chain_id, hash_algorithm, integer_encoding)
+    }
+
+    /// Update the hash algorithm used for a counterpart chain route (admin only)
+    pub fn set_hash_algorithm(
+        ctx: Context<SetHashAlgorithm>,
+        chain_id: u64,
+        hash_algorithm: crate::state::HashAlgorithm,
+    ) -> Result<()> {
+        instructions::chain_config::set_hash_algorithm(ctx, chain_id, hash_algorithm)
+    }
+
+    /// Update the hash pre-image integer byte order for a counterpart chain (admin only)
+    pub fn set_integer_encoding(
+        ctx: Context<SetIntegerEncoding>,
+        chain_id: u64,
+        integer_encoding: crate::state::IntegerEncoding,
+    ) -> Result<()> {
+        instructions::chain_config::set_integer_encoding(ctx, chain_id, integer_encoding)
+    }
+
+    /// Map a CAIP-2 identifier (e.g. "eip155:1") to a chain (admin only)
+    pub fn set_chain_alias(
+        ctx: Context<SetChainAlias>,
+        chain_id: u64,
+        caip2_id: String,
+    ) -> Result<()> {
+        instructions::chain_config::set_chain_alias(ctx, chain_id, caip2_id)
+    }
+
+    /// Remove the CAIP-2 mapping for a chain (admin only)
+    pub fn clear_chain_alias(
+        ctx: Context<ClearChainAlias>,
+        chain_id: u64,
+    ) -> Result<()> {
+        instructions::chain_config::clear_chain_alias(ctx, chain_id)
+    }
+
+    /// Open an IBC-style channel between a local and a remote application
+    pub fn open_channel(
+        ctx: Context<OpenChannel>,
+        remote_chain_id: u64,
+        remote_app: Vec<u8>,
+        ordering: crate::state::ChannelOrdering,
+        require_project_registry: bool,
+    ) -> Result<()> {
+        instructions::channel::open_channel(
+            ctx,
+            remote_chain_id,
+            remote_app,
+            ordering,
+            require_project_registry,
+        )
+    }
+
+    /// Close a channel (local application only)
+    pub fn close_channel(ctx: Context<CloseChannel>) -> Result<()> {
+        instructions::channel::close_channel(ctx)
+    }
+
+    /// Update the accepted envelope protocol version range (admin only)
+    pub fn set_protocol_versions(
+        ctx: Context<SetProtocolVersions>,
+        min_protocol_version: u8,
+        max_protocol_version: u8,
+    ) -> Result<()> {
+        instructions::admin::set_protocol_versions(ctx, min_protocol_version, max_protocol_version)
+    }
+
+    /// Send a cross-chain query; the response is delivered to `callback_program`
+    pub fn send_query(
+        ctx: Context<SendQuery>,
+        envelope: crate::state::MessageEnvelope,
+        callback_program: Pubkey,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::query::send_query(ctx, envelope, callback_program, confirmations)
+    }
+
+    /// TX2 for query responses: verify and invoke the requester's callback
+    pub fn fulfill_query<'info>(
+        ctx: Context<'_, '_, '_, 'info, FulfillQuery<'info>>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::query::fulfill_query(ctx, envelope, signatures)
+    }
+
+    /// Cancel an unanswered query (requester only)
+    pub fn cancel_query(ctx: Context<CancelQuery>) -> Result<()> {
+        instructions::query::cancel_query(ctx)
+    }
+
+    /// TX2 for program deliveries: verify and invoke the recipient's on_message_received
+    pub fn deliver_message<'info>(
+        ctx: Context<'_, '_, '_, 'info, DeliverMessage<'info>>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::delivery::deliver_message(ctx, envelope, signatures)
+    }
+
+    /// Set the address family and remote gateway for a counterpart chain (admin only)
+    pub fn set_remote_address_config(
+        ctx: Context<SetRemoteAddressConfig>,
+        chain_id: u64,
+        address_format: crate::state::AddressFormat,
+        remote_gateway: Vec<u8>,
+    ) -> Result<()> {
+        instructions::chain_config::set_remote_address_config(
+            ctx,
+            chain_id,
+            address_format,
+            remote_gateway,
+        )
+    }
+
+    /// Recompute an envelope's hashes and compare with expected values (read-only)
+    pub fn verify_hash(
+        ctx: Context<VerifyHash>,
+        envelope: crate::state::MessageEnvelope,
+        expected_cross_chain_hash: [u8; 32],
+        expected_signing_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::verify_hash::verify_hash(
+            ctx,
+            envelope,
+            expected_cross_chain_hash,
+            expected_signing_hash,
+        )
+    }
+
+    /// Recompute an envelope's hashes and return them via return data (read-only)
+    pub fn verify_hash_consistency(
+        ctx: Context<VerifyHash>,
+        envelope: crate::state::MessageEnvelope,
+        cross_chain_hash: [u8; 32],
+    ) -> Result<crate::utils::hash::HashConsistency> {
+        instructions::verify_hash::verify_hash_consistency(ctx, envelope, cross_chain_hash)
+    }
+
+    /// Estimate compute units, transaction sizes, fees and rent of a relay (read-only)
+    pub fn estimate_relay_cost(
+        ctx: Context<EstimateRelayCost>,
+        payload_size: u32,
+        signature_count: u8,
+    ) -> Result<crate::utils::cost::RelayCostEstimate> {
+        instructions::relay_cost::estimate_relay_cost(ctx, payload_size, signature_count)
+    }
+
+    /// Check cross-account invariants and emit a report (permissionless, read-only)
+    pub fn assert_invariants<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AssertInvariants<'info>>,
+    ) -> Result<()> {
+        instructions::invariants::assert_invariants(ctx)
+    }
+
+    /// Return one page of the canonical governance state snapshot (read-only)
+    pub fn export_snapshot<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExportSnapshot<'info>>,
+        page: u16,
+    ) -> Result<crate::utils::snapshot::SnapshotPage> {
+        instructions::snapshot::export_snapshot(ctx, page)
+    }
+
+    /// Toggle loopback (same-chain) test mode (admin only)
+    pub fn set_loopback_enabled(ctx: Context<SetLoopbackEnabled>, enabled: bool) -> Result<()> {
+        instructions::admin::set_loopback_enabled(ctx, enabled)
+    }
+
+    /// Toggle full TX2 signature verification past the layer thresholds (admin only)
+    pub fn set_full_signature_verification(
+        ctx: Context<SetFullSignatureVerification>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::admin::set_full_signature_verification(ctx, enabled)
+    }
+
+    /// Toggle strict Ed25519 precompile checks (admin only)
+    pub fn set_strict_precompile_checks(
+        ctx: Context<SetStrictPrecompileChecks>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::admin::set_strict_precompile_checks(ctx, enabled)
+    }
+
+    /// Set the USD send fee for a counterpart chain (admin only)
+    pub fn set_send_fee(ctx: Context<SetSendFee>, chain_id: u64, send_fee_usd: u64) -> Result<()> {
+        instructions::chain_config::set_send_fee(ctx, chain_id, send_fee_usd)
+    }
+
+    /// Enable or disable a counterpart chain as a send_message destination and set its
+    /// send limits (admin only)
+    pub fn set_destination_config(
+        ctx: Context<SetDestinationConfig>,
+        chain_id: u64,
+        enabled: bool,
+        min_confirmations: u16,
+        max_confirmations: u16,
+        max_payload_size: u32,
+    ) -> Result<()> {
+        instructions::chain_config::set_destination_config(
+            ctx,
+            chain_id,
+            enabled,
+            min_confirmations,
+            max_confirmations,
+            max_payload_size,
+        )
+    }
+
+    /// Set the lamport fee schedule for messages to a destination chain (admin only)
+    pub fn set_fee_config(
+        ctx: Context<SetFeeConfig>,
+        dest_chain_id: u64,
+        base_fee: u64,
+        fee_per_byte: u64,
+    ) -> Result<()> {
+        instructions::fee_config::set_fee_config(ctx, dest_chain_id, base_fee, fee_per_byte)
+    }
+
+    /// Set the fee vault refund paid to relayers of messages from a chain (admin only)
+    pub fn set_relayer_refund(
+        ctx: Context<SetRelayerRefund>,
+        chain_id: u64,
+        relayer_refund: u64,
+        relayer_fee_share_bps: u16,
+    ) -> Result<()> {
+        instructions::fee_config::set_relayer_refund(ctx, chain_id, relayer_refund, relayer_fee_share_bps)
+    }
+
+    /// Withdraw accumulated send fees from the fee vault (admin only)
+    pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
+        instructions::fee_config::withdraw_fees(ctx, amount)
+    }
+
+    /// Set the Pyth SOL/USD feed used for USD send fees (admin only)
+    pub fn set_price_feed(
+        ctx: Context<SetPriceFeed>,
+        price_feed_id: [u8; 32],
+        max_price_age: u64,
+    ) -> Result<()> {
+        instructions::admin::set_price_feed(ctx, price_feed_id, max_price_age)
+    }
+
+    /// Set the wallet receiving token bridge fees (admin only)
+    pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+        instructions::admin::set_treasury(ctx, treasury)
+    }
+
+    /// Map a V3 source chain onto a V4 route (admin only)
+    pub fn set_v3_mapping(
+        ctx: Context<SetV3Mapping>,
+        v3_chain_id: u64,
+        v4_chain_id: u64,
+        message_type: u8,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::legacy::set_v3_mapping(ctx, v3_chain_id, v4_chain_id, message_type, enabled)
+    }
+
+    /// Accept a V3-format message and re-wrap it as a V4 envelope
+    pub fn translate_v3_message(
+        ctx: Context<TranslateV3Message>,
+        message: crate::state::LegacyV3Message,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::legacy::translate_v3_message(ctx, message, signatures)
+    }
+
+    /// Register the token bridge contract of a remote chain (admin only)
+    pub fn set_bridge_peer(
+        ctx: Context<SetBridgePeer>,
+        chain_id: u64,
+        remote_bridge: Vec<u8>,
+    ) -> Result<()> {
+        instructions::token_bridge::set_bridge_peer(ctx, chain_id, remote_bridge)
+    }
+
+    /// Register or update a mint's bridge configuration and governance (admin only)
+    pub fn set_token_config(
+        ctx: Context<SetTokenConfig>,
+        settings: crate::state::TokenSettings,
+        governance: Pubkey,
+    ) -> Result<()> {
+        instructions::token_bridge::set_token_config(ctx, settings, governance)
+    }
+
+    /// Update a mint's bridge settings (authority or the token's governance)
+    pub fn update_token_config(
+        ctx: Context<UpdateTokenConfig>,
+        settings: crate::state::TokenSettings,
+    ) -> Result<()> {
+        instructions::token_bridge::update_token_config(ctx, settings)
+    }
+
+    /// Add a wallet or mint to the compliance blocklist, or remove it (admin only)
+    pub fn set_blocklist_entry(
+        ctx: Context<SetBlocklistEntry>,
+        entry: Pubkey,
+        blocked: bool,
+    ) -> Result<()> {
+        instructions::token_bridge::set_blocklist_entry(ctx, entry, blocked)
+    }
+
+    /// Report a mint's escrow balance against its recorded liabilities
+    pub fn reconcile_escrow(ctx: Context<ReconcileEscrow>) -> Result<()> {
+        instructions::token_bridge::reconcile_escrow(ctx)
+    }
+
+    /// Set the inbound volume cap for a mint (admin only)
+    pub fn set_rate_limit(
+        ctx: Context<SetRateLimit>,
+        window_seconds: u64,
+        max_amount: u64,
+    ) -> Result<()> {
+        instructions::token_bridge::set_rate_limit(ctx, window_seconds, max_amount)
+    }
+
+    /// Lock SPL tokens in escrow and send a token-transfer message
+    pub fn deposit_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositTokens<'info>>,
+        tx_id: u128,
+        dest_chain_id: u64,
+        recipient: Vec<u8>,
+        amount: u64,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::token_bridge::deposit_tokens(ctx, tx_id, dest_chain_id, recipient, amount, confirmations)
+    }
+
+    /// TX2 for token transfers: verify and release tokens from escrow
+    pub fn release_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReleaseTokens<'info>>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::token_bridge::release_tokens(ctx, envelope, signatures)
+    }
+
+    /// Attach a project's escrow/mint program as a native mint's custody
+    pub fn attach_custody_extension(ctx: Context<AttachCustodyExtension>) -> Result<()> {
+        instructions::custody_extension::attach_custody_extension(ctx)
+    }
+
+    /// Detach a mint's custody extension and return it to the built-in escrow
+    pub fn detach_custody_extension(ctx: Context<DetachCustodyExtension>) -> Result<()> {
+        instructions::custody_extension::detach_custody_extension(ctx)
+    }
+
+    /// Lock tokens through the mint's custody extension and send them cross-chain
+    pub fn deposit_via_custody_extension<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositViaCustodyExtension<'info>>,
+        tx_id: u128,
+        dest_chain_id: u64,
+        recipient: Vec<u8>,
+        amount: u64,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::custody_extension::deposit_via_custody_extension(
+            ctx,
+            tx_id,
+            dest_chain_id,
+            recipient,
+            amount,
+            confirmations,
+        )
+    }
+
+    /// TX2 for project-custody mints: verify and release through the custody extension
+    pub fn release_via_custody_extension<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReleaseViaCustodyExtension<'info>>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::custody_extension::release_via_custody_extension(ctx, envelope, signatures)
+    }
+
+    /// Route a mint through its issuer's burn/mint program, or back to escrow (admin only)
+    pub fn set_issuer_route(ctx: Context<SetIssuerRoute>, issuer_program: Pubkey) -> Result<()> {
+        instructions::issuer_transfer::set_issuer_route(ctx, issuer_program)
+    }
+
+    /// Burn issuer-routed tokens through the issuer's program and send them cross-chain
+    #[allow(clippy::too_many_arguments)]
+    pub fn deposit_for_issuer_burn<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositForIssuerBurn<'info>>,
+        tx_id: u128,
+        dest_chain_id: u64,
+        recipient: Vec<u8>,
+        amount: u64,
+        issuer_data: Vec<u8>,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::issuer_transfer::deposit_for_issuer_burn(
+            ctx,
+            tx_id,
+            dest_chain_id,
+            recipient,
+            amount,
+            issuer_data,
+            confirmations,
+        )
+    }
+
+    /// TX2 for issuer-routed transfers: verify and mint through the issuer's program
+    pub fn complete_issuer_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, CompleteIssuerTransfer<'info>>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+        issuer_data: Vec<u8>,
+    ) -> Result<()> {
+        instructions::issuer_transfer::complete_issuer_transfer(ctx, envelope, signatures, issuer_data)
+    }
+
+    /// Lock SPL tokens in escrow and send one message paying several recipients
+    pub fn deposit_tokens_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositTokens<'info>>,
+        tx_id: u128,
+        dest_chain_id: u64,
+        transfers: Vec<crate::payloads::BatchTransfer>,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::token_batch::deposit_tokens_batch(ctx, tx_id, dest_chain_id, transfers, confirmations)
+    }
+
+    /// TX2 for batched token transfers: verify and pay every recipient atomically
+    pub fn release_token_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReleaseTokenBatch<'info>>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::token_batch::release_token_batch(ctx, envelope, signatures)
+    }
+
+    /// TX2 for token transfers with an unsupported payload version: park the message
+    pub fn dead_letter_token_transfer(
+        ctx: Context<DeadLetterTokenTransfer>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::token_bridge::dead_letter_token_transfer(ctx, envelope, signatures)
+    }
+
+    /// TX2 for undeliverable token transfers: park them as a claim
+    pub fn park_token_transfer(
+        ctx: Context<ParkTokenTransfer>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::token_claim::park_token_transfer(ctx, envelope, signatures)
+    }
+
+    /// Deliver a parked token transfer (recipient only)
+    pub fn claim_token_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimTokenTransfer<'info>>,
+    ) -> Result<()> {
+        instructions::token_claim::claim_token_transfer(ctx)
+    }
+
+    /// TX2 for return requests: send a parked token transfer back to its sender
+    pub fn return_token_transfer(
+        ctx: Context<ReturnTokenTransfer>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::token_claim::return_token_transfer(ctx, envelope, signatures, confirmations)
+    }
+
+    /// TX2 for vesting grants: record a vesting schedule for the recipient
+    pub fn create_vesting_schedule(
+        ctx: Context<CreateVestingSchedule>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::vesting::create_vesting_schedule(ctx, envelope, signatures)
+    }
+
+    /// Claim tokens vested so far (recipient only)
+    pub fn claim_vested_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimVestedTokens<'info>>,
+    ) -> Result<()> {
+        instructions::vesting::claim_vested_tokens(ctx)
+    }
+
+    /// Add lamports to a liquidity provider's bond
+    pub fn deposit_lp_bond(ctx: Context<DepositLpBond>, amount: u64) -> Result<()> {
+        instructions::fast_transfer::deposit_lp_bond(ctx, amount)
+    }
+
+    /// Withdraw lamports from a liquidity provider's bond (no open fills)
+    pub fn withdraw_lp_bond(ctx: Context<WithdrawLpBond>, amount: u64) -> Result<()> {
+        instructions::fast_transfer::withdraw_lp_bond(ctx, amount)
+    }
+
+    /// Front an inbound token transfer after TX1; TX2 repays the LP
+    pub fn front_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, FrontTransfer<'info>>,
+        envelope: crate::state::MessageEnvelope,
+        fee: u64,
+    ) -> Result<()> {
+        instructions::fast_transfer::front_transfer(ctx, envelope, fee)
+    }
+
+    /// Revoke a fronted message and slash the LP's bond (admin only)
+    pub fn revoke_fast_fill(ctx: Context<RevokeFastFill>, slash_amount: u64) -> Result<()> {
+        instructions::fast_transfer::revoke_fast_fill(ctx, slash_amount)
+    }
+
+    /// Close a settled or revoked fast fill (LP only)
+    pub fn close_fast_fill(ctx: Context<CloseFastFill>) -> Result<()> {
+        instructions::fast_transfer::close_fast_fill(ctx)
+    }
+
+    /// Propose listing a foreign asset as a wrapped mint (admin only, timelocked)
+    pub fn propose_wrapped_asset(
+        ctx: Context<ProposeWrappedAsset>,
+        source_chain_id: u64,
+        source_token: [u8; 32],
+        decimals: u8,
+    ) -> Result<()> {
+        instructions::wrapped::propose_wrapped_asset(ctx, source_chain_id, source_token, decimals)
+    }
+
+    /// Cancel a pending wrapped-asset listing (admin only)
+    pub fn cancel_wrapped_asset_listing(ctx: Context<CancelWrappedAssetListing>) -> Result<()> {
+        instructions::wrapped::cancel_wrapped_asset_listing(ctx)
+    }
+
+    /// Create the wrapped mint of a listing whose timelock has elapsed
+    pub fn create_wrapped_mint(
+        ctx: Context<CreateWrappedMint>,
+        source_chain_id: u64,
+        source_token: [u8; 32],
+    ) -> Result<()> {
+        instructions::wrapped::create_wrapped_mint(ctx, source_chain_id, source_token)
+    }
+
+    /// Delist a wrapped asset, refusing further inbound transfers (admin only)
+    pub fn delist_wrapped_asset(ctx: Context<DelistWrappedAsset>) -> Result<()> {
+        instructions::wrapped::delist_wrapped_asset(ctx)
+    }
+
+    /// TX2 for attested asset metadata: create a named wrapped mint
+    pub fn attest_wrapped_asset(
+        ctx: Context<AttestWrappedAsset>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+        source_token: [u8; 32],
+        decimals: u8,
+    ) -> Result<()> {
+        instructions::wrapped::attest_wrapped_asset(ctx, envelope, signatures, source_token, decimals)
+    }
+
+    /// Burn wrapped tokens and send them back to their source chain
+    pub fn burn_wrapped(
+        ctx: Context<BurnWrapped>,
+        tx_id: u128,
+        recipient: Vec<u8>,
+        amount: u64,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::wrapped::burn_wrapped(ctx, tx_id, recipient, amount, confirmations)
+    }
+
+    /// TX2 for foreign-asset transfers: verify and mint wrapped tokens
+    pub fn mint_wrapped(
+        ctx: Context<MintWrapped>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::wrapped::mint_wrapped(ctx, envelope, signatures)
+    }
+
+    /// TX2 for native value transfers: verify and release SOL from the vault
+    pub fn release_value(
+        ctx: Context<ReleaseValue>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::value_transfer::release_value(ctx, envelope, signatures)
+    }
+
+    /// Queue a timelocked emergency withdrawal from a token escrow (admin only)
+    pub fn request_rescue(ctx: Context<RequestRescue>, amount: u64, reason: String) -> Result<()> {
+        instructions::rescue::request_rescue(ctx, amount, reason)
+    }
+
+    /// Execute a queued rescue after its timelock (admin only)
+    pub fn rescue_tokens<'info>(ctx: Context<'_, '_, '_, 'info, RescueTokens<'info>>) -> Result<()> {
+        instructions::rescue::rescue_tokens(ctx)
+    }
+
+    /// Cancel a queued rescue (admin only)
+    pub fn cancel_rescue(ctx: Context<CancelRescue>) -> Result<()> {
+        instructions::rescue::cancel_rescue(ctx)
+    }
+
+    /// Allow a Metaplex collection to bridge out (admin only)
+    pub fn register_nft_collection(ctx: Context<RegisterNftCollection>) -> Result<()> {
+        instructions::nft_bridge::register_nft_collection(ctx)
+    }
+
+    /// Lock a Solana-native NFT in escrow and send it to a remote chain
+    pub fn lock_nft(
+        ctx: Context<LockNft>,
+        tx_id: u128,
+        dest_chain_id: u64,
+        recipient: Vec<u8>,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::nft_bridge::lock_nft(ctx, tx_id, dest_chain_id, recipient, confirmations)
+    }
+
+    /// TX2 for returning Solana-native NFTs: verify and release from escrow
+    pub fn unlock_nft(
+        ctx: Context<UnlockNft>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+    ) -> Result<()> {
+        instructions::nft_bridge::unlock_nft(ctx, envelope, signatures)
+    }
+
+    /// TX2 for foreign NFTs: verify and mint the wrapped NFT
+    pub fn mint_wrapped_nft(
+        ctx: Context<MintWrappedNft>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+        collection_hash: [u8; 32],
+        token_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::nft_bridge::mint_wrapped_nft(ctx, envelope, signatures, collection_hash, token_id)
+    }
+
+    /// Burn a wrapped NFT and send it back to its source chain
+    pub fn burn_wrapped_nft(
+        ctx: Context<BurnWrappedNft>,
+        tx_id: u128,
+        recipient: Vec<u8>,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::nft_bridge::burn_wrapped_nft(ctx, tx_id, recipient, confirmations)
+    }
+
+    /// Register a Bubblegum tree for wrapped compressed NFTs (admin only)
+    pub fn register_compressed_nft_tree(ctx: Context<RegisterCompressedNftTree>) -> Result<()> {
+        instructions::compressed_nft::register_compressed_nft_tree(ctx)
+    }
+
+    /// Transfer a Solana-native compressed NFT to the gateway and send it to a remote chain
+    #[allow(clippy::too_many_arguments)]
+    pub fn lock_compressed_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, LockCompressedNft<'info>>,
+        tx_id: u128,
+        dest_chain_id: u64,
+        recipient: Vec<u8>,
+        metadata: crate::utils::MetadataArgs,
+        leaf: crate::utils::CompressedLeaf,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::compressed_nft::lock_compressed_nft(
+            ctx,
+            tx_id,
+            dest_chain_id,
+            recipient,
+            metadata,
+            leaf,
+            confirmations,
+        )
+    }
+
+    /// TX2 for returning Solana-native compressed NFTs: verify and transfer from the gateway
+    pub fn unlock_compressed_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnlockCompressedNft<'info>>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+        leaf: crate::utils::CompressedLeaf,
+    ) -> Result<()> {
+        instructions::compressed_nft::unlock_compressed_nft(ctx, envelope, signatures, leaf)
+    }
+
+    /// TX2 for foreign NFTs delivered as compressed NFTs: verify and mint a leaf
+    pub fn mint_wrapped_compressed_nft(
+        ctx: Context<MintWrappedCompressedNft>,
+        envelope: crate::state::MessageEnvelope,
+        signatures: Vec<crate::state::MessageSignature>,
+        collection_hash: [u8; 32],
+        token_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::compressed_nft::mint_wrapped_compressed_nft(ctx, envelope, signatures, collection_hash, token_id)
+    }
+
+    /// Burn a wrapped compressed NFT and send it back to its source chain
+    pub fn burn_wrapped_compressed_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, BurnWrappedCompressedNft<'info>>,
+        tx_id: u128,
+        recipient: Vec<u8>,
+        leaf: crate::utils::CompressedLeaf,
+        confirmations: u16,
+    ) -> Result<()> {
+        instructions::compressed_nft::burn_wrapped_compressed_nft(ctx, tx_id, recipient, leaf, confirmations)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash;
+
+use crate::constants::{MAX_ON_CHAIN_DATA_SIZE, MAX_RECIPIENT_SIZE};
+use crate::errors::GatewayError;
+
+/// Standard "generic call" payload carried in `on_chain_data`
+/// One format for arbitrary execution bridges: what to call, which entry point,
+/// with which arguments and how much execution budget to give it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct GenericCallPayload {
+    /// Target program (32 bytes) or contract (20 bytes for EVM) on the destination chain
+    pub target: Vec<u8>,
+    
+    /// Entry point: 4-byte EVM function selector or 8-byte Anchor discriminator
+    pub selector: Vec<u8>,
+    
+    /// Encoded arguments (ABI for EVM, Borsh for Solana)
+    pub args: Vec<u8>,
+    
+    /// Gas limit (EVM) or compute unit limit (Solana) hint for the executor (0 = default)
+    pub gas_limit: u64,
+}
+
+impl GenericCallPayload {
+    /// Current payload format version
+    pub const VERSION: u8 = 1;
+    
+    /// EVM function selector length
+    pub const EVM_SELECTOR_LEN: usize = 4;
+    
+    /// Anchor instruction discriminator length
+    pub const ANCHOR_DISCRIMINATOR_LEN: usize = 8;
+    
+    /// Build a call to an Anchor instruction, deriving its discriminator from the name
+    pub fn anchor(program_id: Pubkey, instruction_name: &str, args: Vec<u8>, gas_limit: u64) -> Self {
+        Self {
+            target: program_id.to_bytes().to_vec(),
+            selector: Self::anchor_discriminator(instruction_name).to_vec(),
+            args,
+            gas_limit,
+        }
+    }
+    
+    /// Build a call to an EVM contract function
+    pub fn evm(contract: [u8; 20], selector: [u8; 4], args: Vec<u8>, gas_limit: u64) -> Self {
+        Self {
+            target: contract.to_vec(),
+            selector: selector.to_vec(),
+            args,
+            gas_limit,
+        }
+    }
+    
+    /// Anchor discriminator: sha256("global:<instruction_name>")[..8]
+    pub fn anchor_discriminator(instruction_name: &str) -> [u8; 8] {
+        let digest = hash::hashv(&[b"global:", instruction_name.as_bytes()]).to_bytes();
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&digest[..8]);
+        discriminator
+    }
+    
+    /// Validate field sizes
+    pub fn validate(&self) -> Result<()> {
+        require!(!self.target.is_empty(), GatewayError::EmptyRecipient);
+        require!(
+            self.target.len() <= MAX_RECIPIENT_SIZE,
+            GatewayError::RecipientTooLong
+        );
+        require!(
+            self.selector.len() == Self::EVM_SELECTOR_LEN
+                || self.selector.len() == Self::ANCHOR_DISCRIMINATOR_LEN,
+            GatewayError::InvalidPayload
+        );
+        require!(
+            self.args.len() <= MAX_ON_CHAIN_DATA_SIZE,
+            GatewayError::OnChainDataTooLarge
+        );
+        Ok(())
+    }
+    
+    /// Target as a Solana program ID (delivery side)
+    pub fn target_program(&self) -> Result<Pubkey> {
+        Pubkey::try_from(self.target.as_slice()).map_err(|_| GatewayError::InvalidPayload.into())
+    }
+    
+    /// Instruction data for the target: `selector || args`
+    pub fn call_data(&self) -> Vec<u8> {
+        [self.selector.as_slice(), self.args.as_slice()].concat()
+    }
+    
+    /// Encode as `version (u8) || borsh(payload)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Decode from `on_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        require!(*version == Self::VERSION, GatewayError::UnsupportedPayloadVersion);
+        
+        let payload = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        
+        payload.validate()?;
+        Ok(payload)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,224 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::GatewayError;
+use crate::state::MessageEnvelope;
+
+/// ABI word size
+const WORD: usize = 32;
+
+/// CCIP `Client.EVMTokenAmount`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct CcipTokenAmount {
+    /// Token address as a 32-byte ABI word (EVM addresses are left-padded)
+    pub token: [u8; 32],
+    
+    /// Token amount (ABI-encoded as uint256)
+    pub amount: u128,
+}
+
+/// CCIP `Client.Any2EVMMessage`, as received by `ccipReceive`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Any2EvmMessage {
+    /// Message identifier (the gateway uses the cross-chain hash)
+    pub message_id: [u8; 32],
+    
+    /// Source chain selector (the gateway's source chain ID)
+    pub source_chain_selector: u64,
+    
+    /// ABI-encoded sender address
+    pub sender: Vec<u8>,
+    
+    /// Application payload
+    pub data: Vec<u8>,
+    
+    /// Tokens delivered with the message
+    pub dest_token_amounts: Vec<CcipTokenAmount>,
+}
+
+/// CCIP `Client.EVM2AnyMessage`, as passed to `ccipSend`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct Evm2AnyMessage {
+    /// ABI-encoded receiver address
+    pub receiver: Vec<u8>,
+    
+    /// Application payload
+    pub data: Vec<u8>,
+    
+    /// Tokens sent with the message
+    pub token_amounts: Vec<CcipTokenAmount>,
+    
+    /// Fee token address as a 32-byte ABI word (zero = native)
+    pub fee_token: [u8; 32],
+    
+    /// Encoded extra arguments (gas limit, ...)
+    pub extra_args: Vec<u8>,
+}
+
+impl Any2EvmMessage {
+    /// Build the CCIP view of a gateway message so CCIP receivers handle it unchanged
+    pub fn from_envelope(envelope: &MessageEnvelope, message_id: [u8; 32]) -> Self {
+        Self {
+            message_id,
+            source_chain_selector: envelope.source_chain_id,
+            sender: envelope.sender.clone(),
+            data: envelope.on_chain_data.clone(),
+            dest_token_amounts: vec![],
+        }
+    }
+    
+    /// Encode as `abi.encode(Client.Any2EVMMessage)`
+    pub fn abi_encode(&self) -> Vec<u8> {
+        encode_tuple(&[
+            AbiField::Word(self.message_id),
+            AbiField::Word(uint_word(self.source_chain_selector as u128)),
+            AbiField::Bytes(&self.sender),
+            AbiField::Bytes(&self.data),
+            AbiField::TokenAmounts(&self.dest_token_amounts),
+        ])
+    }
+    
+    /// Decode `abi.encode(Client.Any2EVMMessage)`
+    pub fn abi_decode(data: &[u8]) -> Result<Self> {
+        let tuple = read_tuple(data)?;
+        Ok(Self {
+            message_id: read_word(tuple, 0)?,
+            source_chain_selector: read_uint(tuple, 1, 8)? as u64,
+            sender: read_bytes(tuple, 2)?,
+            data: read_bytes(tuple, 3)?,
+            dest_token_amounts: read_token_amounts(tuple, 4)?,
+        })
+    }
+}
+
+impl Evm2AnyMessage {
+    /// Encode as `abi.encode(Client.EVM2AnyMessage)`
+    pub fn abi_encode(&self) -> Vec<u8> {
+        encode_tuple(&[
+            AbiField::Bytes(&self.receiver),
+            AbiField::Bytes(&self.data),
+            AbiField::TokenAmounts(&self.token_amounts),
+            AbiField::Word(self.fee_token),
+            AbiField::Bytes(&self.extra_args),
+        ])
+    }
+    
+    /// Decode `abi.encode(Client.EVM2AnyMessage)`
+    pub fn abi_decode(data: &[u8]) -> Result<Self> {
+        let tuple = read_tuple(data)?;
+        Ok(Self {
+            receiver: read_bytes(tuple, 0)?,
+            data: read_bytes(tuple, 1)?,
+            token_amounts: read_token_amounts(tuple, 2)?,
+            fee_token: read_word(tuple, 3)?,
+            extra_args: read_bytes(tuple, 4)?,
+        })
+    }
+}
+
+enum AbiField<'a> {
+    Word([u8; 32]),
+    Bytes(&'a [u8]),
+    TokenAmounts(&'a [CcipTokenAmount]),
+}
+
+fn uint_word(value: u128) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[16..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// ABI-encode a single struct argument: outer offset, head words, then dynamic tails
+fn encode_tuple(fields: &[AbiField]) -> Vec<u8> {
+    let mut head = Vec::with_capacity(fields.len() * WORD);
+    let mut tail = Vec::new();
+    
+    for field in fields {
+        match field {
+            AbiField::Word(word) => head.extend_from_slice(word),
+            AbiField::Bytes(bytes) => {
+                head.extend_from_slice(&uint_word((fields.len() * WORD + tail.len()) as u128));
+                tail.extend_from_slice(&uint_word(bytes.len() as u128));
+                tail.extend_from_slice(bytes);
+                tail.resize(tail.len() + (WORD - bytes.len() % WORD) % WORD, 0);
+            }
+            AbiField::TokenAmounts(amounts) => {
+                head.extend_from_slice(&uint_word((fields.len() * WORD + tail.len()) as u128));
+                tail.extend_from_slice(&uint_word(amounts.len() as u128));
+                for amount in amounts.iter() {
+                    tail.extend_from_slice(&amount.token);
+                    tail.extend_from_slice(&uint_word(amount.amount));
+                }
+            }
+        }
+    }
+    
+    let mut encoded = uint_word(WORD as u128).to_vec();
+    encoded.extend_from_slice(&head);
+    encoded.extend_from_slice(&tail);
+    encoded
+}
+
+fn word_at(data: &[u8], offset: usize) -> Result<&[u8]> {
+    let end = offset.checked_add(WORD).ok_or(GatewayError::InvalidPayload)?;
+    data.get(offset..end).ok_or_else(|| GatewayError::InvalidPayload.into())
+}
+
+/// Read a word as an unsigned integer that must fit in `max_bytes`
+fn uint_at(data: &[u8], offset: usize, max_bytes: usize) -> Result<u128> {
+    let word = word_at(data, offset)?;
+    require!(
+        word[..WORD - max_bytes].iter().all(|b| *b == 0),
+        GatewayError::InvalidPayload
+    );
+    let mut value = [0u8; 16];
+    value[16 - max_bytes..].copy_from_slice(&word[WORD - max_bytes..]);
+    Ok(u128::from_be_bytes(value))
+}
+
+fn read_tuple(data: &[u8]) -> Result<&[u8]> {
+    let offset = uint_at(data, 0, 4)? as usize;
+    data.get(offset..).ok_or_else(|| GatewayError::InvalidPayload.into())
+}
+
+fn read_word(tuple: &[u8], index: usize) -> Result<[u8; 32]> {
+    let mut word = [0u8; 32];
+    word.copy_from_slice(word_at(tuple, index * WORD)?);
+    Ok(word)
+}
+
+fn read_uint(tuple: &[u8], index: usize, max_bytes: usize) -> Result<u128> {
+    uint_at(tuple, index * WORD, max_bytes)
+}
+
+/// Resolve a dynamic field: returns its length word and the data following it
+fn read_dynamic(tuple: &[u8], index: usize) -> Result<(usize, &[u8])> {
+    let offset = read_uint(tuple, index, 4)? as usize;
+    let len = uint_at(tuple, offset, 4)? as usize;
+    let body = tuple
+        .get(offset + WORD..)
+        .ok_or(GatewayError::InvalidPayload)?;
+    Ok((len, body))
+}
+
+fn read_bytes(tuple: &[u8], index: usize) -> Result<Vec<u8>> {
+    let (len, body) = read_dynamic(tuple, index)?;
+    let bytes = body.get(..len).ok_or(GatewayError::InvalidPayload)?;
+    Ok(bytes.to_vec())
+}
+
+fn read_token_amounts(tuple: &[u8], index: usize) -> Result<Vec<CcipTokenAmount>> {
+    let (len, body) = read_dynamic(tuple, index)?;
+    let size = len.checked_mul(2 * WORD).ok_or(GatewayError::InvalidPayload)?;
+    require!(body.len() >= size, GatewayError::InvalidPayload);
+    
+    (0..len)
+        .map(|i| {
+            let mut token = [0u8; 32];
+            token.copy_from_slice(word_at(body, i * 2 * WORD)?);
+            Ok(CcipTokenAmount {
+                token,
+                amount: uint_at(body, i * 2 * WORD + WORD, 16)?,
+            })
+        })
+        .collect()
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+//! Program deliveries use the shared receiver interface, so the gateway's CPI and the
+//! recipients' `on_message_received` decode one ABI (see the `via-receiver-interface` crate)
+
+pub use via_receiver_interface::{
+    ViaMessage, DELIVERY_AUTHORITY_INDEX, ON_MESSAGE_RECEIVED_DISCRIMINATOR,
+};
\ No newline at end of file
@@ -0,0 +1,45 @@
+pub mod call;
+pub mod ccip;
+pub mod delivery;
+pub mod nft;
+pub mod query;
+pub mod token;
+pub mod value;
+
+pub use call::*;
+pub use ccip::*;
+pub use delivery::*;
+pub use nft::*;
+pub use query::*;
+pub use token::*;
+pub use value::*;
+
+/// Application payload kinds carried in `MessageEnvelope::message_type`
+pub const PAYLOAD_KIND_GENERIC: u8 = 0;
+pub const PAYLOAD_KIND_NFT_TRANSFER: u8 = 1;
+/// `on_chain_data` is `abi.encode(Client.EVM2AnyMessage)` (CCIP-compatible mode)
+pub const PAYLOAD_KIND_CCIP: u8 = 2;
+/// `on_chain_data` is an application-defined query for the remote chain
+pub const PAYLOAD_KIND_QUERY_REQUEST: u8 = 3;
+/// `on_chain_data` is a `QueryResponsePayload`
+pub const PAYLOAD_KIND_QUERY_RESPONSE: u8 = 4;
+/// `on_chain_data` is a `GenericCallPayload`
+pub const PAYLOAD_KIND_GENERIC_CALL: u8 = 5;
+/// `on_chain_data` is a `TokenTransferPayload`
+pub const PAYLOAD_KIND_TOKEN_TRANSFER: u8 = 6;
+/// `on_chain_data` is a `ValueTransferPayload`; lamports travel through the SOL vault
+pub const PAYLOAD_KIND_VALUE_TRANSFER: u8 = 7;
+/// `on_chain_data` is an `AssetMetadataPayload` describing a foreign asset to wrap
+pub const PAYLOAD_KIND_ASSET_METADATA: u8 = 8;
+/// `on_chain_data` is a `TokenReturnPayload` asking to send a parked transfer back
+pub const PAYLOAD_KIND_TOKEN_RETURN: u8 = 9;
+/// `on_chain_data` is a `TokenBatchPayload` of several transfers delivered atomically
+pub const PAYLOAD_KIND_TOKEN_BATCH: u8 = 10;
+/// `on_chain_data` is a `TokenVestingPayload` delivered into a vesting schedule
+pub const PAYLOAD_KIND_TOKEN_VESTING: u8 = 11;
+/// `on_chain_data` is a `TokenTransferPayload` for an issuer-routed mint, burned at the
+/// source and minted at the destination by the issuer's attestation program
+pub const PAYLOAD_KIND_ISSUER_TRANSFER: u8 = 12;
+/// `on_chain_data` is application data delivered by `deliver_message` through a CPI into
+/// the recipient program's `on_message_received`
+pub const PAYLOAD_KIND_PROGRAM_DELIVERY: u8 = 13;
\ No newline at end of file
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::constants::{MAX_RECIPIENT_SIZE, MAX_SENDER_SIZE};
+use crate::errors::GatewayError;
+
+/// Standard NFT-transfer payload carried in `on_chain_data`
+/// Shared schema so NFT bridges built on the gateway interoperate
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct NftTransferPayload {
+    /// Collection address on the source chain (EVM contract, Solana collection mint, ...)
+    pub collection: Vec<u8>,
+    
+    /// Token identifier (uint256 big-endian for EVM, mint address for Solana)
+    pub token_id: [u8; 32],
+    
+    /// Hash of the token metadata URI, letting the destination verify relayed metadata
+    pub metadata_uri_hash: [u8; 32],
+    
+    /// Recipient address on the destination chain
+    pub recipient: Vec<u8>,
+}
+
+impl NftTransferPayload {
+    /// Current payload format version
+    pub const VERSION: u8 = 1;
+    
+    /// Validate field sizes
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.collection.is_empty() && self.collection.len() <= MAX_SENDER_SIZE,
+            GatewayError::InvalidPayload
+        );
+        require!(!self.recipient.is_empty(), GatewayError::EmptyRecipient);
+        require!(
+            self.recipient.len() <= MAX_RECIPIENT_SIZE,
+            GatewayError::RecipientTooLong
+        );
+        Ok(())
+    }
+    
+    /// Encode as `version (u8) || borsh(payload)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Decode from `on_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        require!(*version == Self::VERSION, GatewayError::UnsupportedPayloadVersion);
+        
+        let payload = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        
+        payload.validate()?;
+        Ok(payload)
+    }
+}
+
+
+/// NFT metadata relayed in `off_chain_data` alongside an `NftTransferPayload`
+/// Bound to the payload by `metadata_uri_hash = keccak256(uri)`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct NftMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl NftMetadata {
+    /// Current metadata format version
+    pub const VERSION: u8 = 1;
+    
+    /// Metaplex field limits
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_SYMBOL_LEN: usize = 10;
+    pub const MAX_URI_LEN: usize = 200;
+    
+    /// Validate field lengths against Metaplex limits
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.name.len() <= Self::MAX_NAME_LEN
+                && self.symbol.len() <= Self::MAX_SYMBOL_LEN
+                && self.uri.len() <= Self::MAX_URI_LEN,
+            GatewayError::InvalidPayload
+        );
+        Ok(())
+    }
+    
+    /// keccak256 of the URI, as committed in `NftTransferPayload::metadata_uri_hash`
+    pub fn uri_hash(&self) -> [u8; 32] {
+        keccak::hash(self.uri.as_bytes()).to_bytes()
+    }
+    
+    /// Encode as `version (u8) || borsh(metadata)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Decode from `off_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        require!(*version == Self::VERSION, GatewayError::UnsupportedPayloadVersion);
+        
+        let metadata = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        
+        metadata.validate()?;
+        Ok(metadata)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::GatewayError;
+
+/// Anchor-style discriminator of the callback instruction:
+/// sha256("global:via_query_callback")[..8]
+pub const QUERY_CALLBACK_DISCRIMINATOR: [u8; 8] = [202, 134, 207, 105, 203, 206, 76, 24];
+
+/// Query response carried in `on_chain_data` of a `PAYLOAD_KIND_QUERY_RESPONSE` message
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct QueryResponsePayload {
+    /// Identifier of the query being answered (tx_id of the request)
+    pub query_id: u128,
+    
+    /// Result bytes produced by the remote chain
+    pub result: Vec<u8>,
+}
+
+impl QueryResponsePayload {
+    /// Current payload format version
+    pub const VERSION: u8 = 1;
+    
+    /// Encode as `version (u8) || borsh(payload)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Decode from `on_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        require!(*version == Self::VERSION, GatewayError::UnsupportedPayloadVersion);
+        
+        let payload = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        Ok(payload)
+    }
+}
+
+/// Arguments passed to the requester's callback program
+/// Instruction data is `QUERY_CALLBACK_DISCRIMINATOR || borsh(QueryCallback)`; the
+/// PendingQuery PDA signs the CPI so the callback can authenticate the gateway
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct QueryCallback {
+    pub query_id: u128,
+    pub source_chain_id: u64,
+    pub result: Vec<u8>,
+}
+
+impl QueryCallback {
+    /// Build callback instruction data
+    pub fn instruction_data(&self) -> Result<Vec<u8>> {
+        let mut data = QUERY_CALLBACK_DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(data)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,365 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_BATCH_TRANSFERS, MAX_RECIPIENT_SIZE, MAX_SENDER_SIZE};
+use crate::errors::GatewayError;
+use crate::payloads::NftMetadata;
+
+/// Token-transfer payload carried in `on_chain_data` of a `PAYLOAD_KIND_TOKEN_TRANSFER` message
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct TokenTransferPayload {
+    /// Token identifier (SPL mint address for Solana-native tokens)
+    pub token: [u8; 32],
+    
+    /// Amount in the token's base units
+    pub amount: u64,
+    
+    /// Depositor address on the source chain
+    pub sender: Vec<u8>,
+    
+    /// Beneficiary address on the destination chain (wallet, not token account, on Solana)
+    pub recipient: Vec<u8>,
+}
+
+/// Token-transfer payload parsed by its leading format version byte
+#[derive(Clone, Debug, PartialEq)]
+pub enum VersionedTokenTransfer {
+    /// A version this program decodes
+    Supported(TokenTransferPayload),
+    /// A version introduced by remote bridges that this program does not know yet
+    Unsupported { version: u8 },
+}
+
+impl TokenTransferPayload {
+    /// Current payload format version
+    pub const VERSION: u8 = 1;
+    
+    /// Validate amount and field sizes
+    pub fn validate(&self) -> Result<()> {
+        require!(self.amount > 0, GatewayError::InvalidAmount);
+        require!(
+            !self.sender.is_empty() && self.sender.len() <= MAX_SENDER_SIZE,
+            GatewayError::InvalidPayload
+        );
+        require!(!self.recipient.is_empty(), GatewayError::EmptyRecipient);
+        require!(
+            self.recipient.len() <= MAX_RECIPIENT_SIZE,
+            GatewayError::RecipientTooLong
+        );
+        Ok(())
+    }
+    
+    /// Encode as `version (u8) || borsh(payload)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Parse `on_chain_data` by version
+    /// Unknown versions are reported rather than failed so the message can be dead-lettered;
+    /// a malformed body of a known version is still an error
+    pub fn parse(data: &[u8]) -> Result<VersionedTokenTransfer> {
+        let (version, body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        match *version {
+            Self::VERSION => Ok(VersionedTokenTransfer::Supported(Self::decode_v1(body)?)),
+            version => Ok(VersionedTokenTransfer::Unsupported { version }),
+        }
+    }
+    
+    /// Decode from `on_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        match Self::parse(data)? {
+            VersionedTokenTransfer::Supported(payload) => Ok(payload),
+            VersionedTokenTransfer::Unsupported { .. } => {
+                err!(GatewayError::UnsupportedPayloadVersion)
+            }
+        }
+    }
+    
+    /// Decode a version 1 body: `borsh(payload)` with no trailing bytes
+    fn decode_v1(mut body: &[u8]) -> Result<Self> {
+        let payload = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        
+        payload.validate()?;
+        Ok(payload)
+    }
+    
+    /// Solana beneficiary of an inbound transfer
+    pub fn recipient_pubkey(&self) -> Result<Pubkey> {
+        Pubkey::try_from(self.recipient.as_slice())
+            .map_err(|_| GatewayError::InvalidRemoteAddress.into())
+    }
+}
+
+/// One recipient/amount pair of a batched token transfer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct BatchTransfer {
+    /// Beneficiary address on the destination chain (wallet, not token account, on Solana)
+    pub recipient: Vec<u8>,
+    
+    /// Amount in the token's base units
+    pub amount: u64,
+}
+
+/// Several transfers of one token from one sender, carried in `on_chain_data` of a
+/// `PAYLOAD_KIND_TOKEN_BATCH` message; delivery is all-or-nothing
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct TokenBatchPayload {
+    /// Token identifier (SPL mint address for Solana-native tokens)
+    pub token: [u8; 32],
+    
+    /// Depositor address on the source chain
+    pub sender: Vec<u8>,
+    
+    /// Recipient/amount pairs, delivered in order
+    pub transfers: Vec<BatchTransfer>,
+}
+
+impl TokenBatchPayload {
+    /// Current payload format version
+    pub const VERSION: u8 = 1;
+    
+    /// Validate the batch size, amounts and field sizes
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.sender.is_empty() && self.sender.len() <= MAX_SENDER_SIZE,
+            GatewayError::InvalidPayload
+        );
+        require!(
+            !self.transfers.is_empty() && self.transfers.len() <= MAX_BATCH_TRANSFERS,
+            GatewayError::InvalidBatchSize
+        );
+        for transfer in &self.transfers {
+            require!(transfer.amount > 0, GatewayError::InvalidAmount);
+            require!(!transfer.recipient.is_empty(), GatewayError::EmptyRecipient);
+            require!(
+                transfer.recipient.len() <= MAX_RECIPIENT_SIZE,
+                GatewayError::RecipientTooLong
+            );
+        }
+        self.total_amount()?;
+        Ok(())
+    }
+    
+    /// Sum of the batch's amounts
+    pub fn total_amount(&self) -> Result<u64> {
+        self.transfers.iter().try_fold(0u64, |total, transfer| {
+            total
+                .checked_add(transfer.amount)
+                .ok_or_else(|| error!(GatewayError::InvalidAmount))
+        })
+    }
+    
+    /// Encode as `version (u8) || borsh(payload)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Decode from `on_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        require!(*version == Self::VERSION, GatewayError::UnsupportedPayloadVersion);
+        
+        let payload = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        
+        payload.validate()?;
+        Ok(payload)
+    }
+}
+
+/// Token grant released to the recipient over time, carried in `on_chain_data` of a
+/// `PAYLOAD_KIND_TOKEN_VESTING` message; delivery creates a vesting schedule instead of
+/// paying the recipient
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct TokenVestingPayload {
+    /// Token identifier (SPL mint address for Solana-native tokens)
+    pub token: [u8; 32],
+    
+    /// Total granted amount, in bridge decimals
+    pub amount: u64,
+    
+    /// Grantor address on the source chain
+    pub sender: Vec<u8>,
+    
+    /// Beneficiary wallet on the destination chain
+    pub recipient: Vec<u8>,
+    
+    /// Unix timestamp vesting starts from
+    pub start_time: i64,
+    
+    /// Seconds after `start_time` before anything unlocks
+    pub cliff_seconds: u64,
+    
+    /// Seconds after `start_time` until the whole grant is unlocked
+    pub duration_seconds: u64,
+    
+    /// Unlock step: tokens vest in equal chunks every `period_seconds`
+    pub period_seconds: u64,
+}
+
+impl TokenVestingPayload {
+    /// Current payload format version
+    pub const VERSION: u8 = 1;
+    
+    /// Validate amount, field sizes and schedule shape
+    pub fn validate(&self) -> Result<()> {
+        require!(self.amount > 0, GatewayError::InvalidAmount);
+        require!(
+            !self.sender.is_empty() && self.sender.len() <= MAX_SENDER_SIZE,
+            GatewayError::InvalidPayload
+        );
+        require!(!self.recipient.is_empty(), GatewayError::EmptyRecipient);
+        require!(
+            self.recipient.len() <= MAX_RECIPIENT_SIZE,
+            GatewayError::RecipientTooLong
+        );
+        require!(
+            self.duration_seconds > 0
+                && self.duration_seconds <= i64::MAX as u64
+                && self.period_seconds > 0
+                && self.period_seconds <= self.duration_seconds
+                && self.cliff_seconds <= self.duration_seconds,
+            GatewayError::InvalidVestingSchedule
+        );
+        Ok(())
+    }
+    
+    /// Encode as `version (u8) || borsh(payload)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Decode from `on_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        require!(*version == Self::VERSION, GatewayError::UnsupportedPayloadVersion);
+        
+        let payload = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        
+        payload.validate()?;
+        Ok(payload)
+    }
+    
+    /// Solana beneficiary of an inbound grant
+    pub fn recipient_pubkey(&self) -> Result<Pubkey> {
+        Pubkey::try_from(self.recipient.as_slice())
+            .map_err(|_| GatewayError::InvalidRemoteAddress.into())
+    }
+}
+
+/// Request from the source chain's bridge, on behalf of the original sender, to send a
+/// parked inbound transfer back; carried in `on_chain_data` of a `PAYLOAD_KIND_TOKEN_RETURN` message
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct TokenReturnPayload {
+    /// Transaction identifier of the parked transfer
+    pub tx_id: u128,
+    
+    /// Transaction identifier for the outbound return transfer
+    pub return_tx_id: u128,
+}
+
+impl TokenReturnPayload {
+    /// Current payload format version
+    pub const VERSION: u8 = 1;
+    
+    /// Validate transaction identifiers
+    pub fn validate(&self) -> Result<()> {
+        require!(self.tx_id > 0 && self.return_tx_id > 0, GatewayError::InvalidTxId);
+        Ok(())
+    }
+    
+    /// Encode as `version (u8) || borsh(payload)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Decode from `on_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        require!(*version == Self::VERSION, GatewayError::UnsupportedPayloadVersion);
+        
+        let payload = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        
+        payload.validate()?;
+        Ok(payload)
+    }
+}
+
+/// Asset description attested by validators in `on_chain_data` of a
+/// `PAYLOAD_KIND_ASSET_METADATA` message, used to create a named wrapped mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct AssetMetadataPayload {
+    /// Asset identifier on the source chain (EVM addresses are left-padded)
+    pub token: [u8; 32],
+    
+    /// Decimals of the asset on the source chain
+    pub decimals: u8,
+    
+    pub name: String,
+    
+    pub symbol: String,
+    
+    /// Optional off-chain metadata URI (empty if none)
+    pub uri: String,
+}
+
+impl AssetMetadataPayload {
+    /// Current payload format version
+    pub const VERSION: u8 = 1;
+    
+    /// Validate field lengths against Metaplex limits
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.name.is_empty()
+                && self.name.len() <= NftMetadata::MAX_NAME_LEN
+                && self.symbol.len() <= NftMetadata::MAX_SYMBOL_LEN
+                && self.uri.len() <= NftMetadata::MAX_URI_LEN,
+            GatewayError::InvalidPayload
+        );
+        Ok(())
+    }
+    
+    /// Encode as `version (u8) || borsh(payload)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Decode from `on_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        require!(*version == Self::VERSION, GatewayError::UnsupportedPayloadVersion);
+        
+        let payload = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        
+        payload.validate()?;
+        Ok(payload)
+    }
+}
\ No newline at end of file
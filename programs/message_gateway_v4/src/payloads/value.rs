@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_ON_CHAIN_DATA_SIZE, MAX_RECIPIENT_SIZE};
+use crate::errors::GatewayError;
+
+/// Native value transfer carried in `on_chain_data` of a `PAYLOAD_KIND_VALUE_TRANSFER` message
+/// Lamports are held in the gateway SOL vault while the message is in flight
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct ValueTransferPayload {
+    /// Amount in the source chain's native base units (lamports on Solana)
+    pub amount: u64,
+    
+    /// Beneficiary address on the destination chain
+    pub recipient: Vec<u8>,
+    
+    /// Application data delivered alongside the value
+    pub data: Vec<u8>,
+}
+
+impl ValueTransferPayload {
+    /// Current payload format version
+    pub const VERSION: u8 = 1;
+    
+    /// Validate amount and field sizes
+    pub fn validate(&self) -> Result<()> {
+        require!(self.amount > 0, GatewayError::InvalidAmount);
+        require!(!self.recipient.is_empty(), GatewayError::EmptyRecipient);
+        require!(
+            self.recipient.len() <= MAX_RECIPIENT_SIZE,
+            GatewayError::RecipientTooLong
+        );
+        require!(
+            self.data.len() <= MAX_ON_CHAIN_DATA_SIZE,
+            GatewayError::OnChainDataTooLarge
+        );
+        Ok(())
+    }
+    
+    /// Encode as `version (u8) || borsh(payload)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        self.validate()?;
+        
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(encoded)
+    }
+    
+    /// Decode from `on_chain_data`, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidPayload)?;
+        require!(*version == Self::VERSION, GatewayError::UnsupportedPayloadVersion);
+        
+        let payload = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidPayload)?;
+        require!(body.is_empty(), GatewayError::InvalidPayload);
+        
+        payload.validate()?;
+        Ok(payload)
+    }
+    
+    /// Solana beneficiary of an inbound transfer
+    pub fn recipient_pubkey(&self) -> Result<Pubkey> {
+        Pubkey::try_from(self.recipient.as_slice())
+            .map_err(|_| GatewayError::InvalidRemoteAddress.into())
+    }
+}
\ No newline at end of file
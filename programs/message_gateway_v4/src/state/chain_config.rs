@@ -0,0 +1,166 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_CAIP2_ID_LEN, MAX_SENDER_SIZE};
+use crate::errors::GatewayError;
+use crate::state::MessageEnvelope;
+
+/// Per-chain configuration for a counterpart chain route
+/// Selects how messages to/from that chain are hashed for signing; send_message only
+/// accepts destinations that have one, within its send limits
+#[account]
+pub struct ChainConfig {
+    /// Counterpart chain identifier
+    pub chain_id: u64,
+    
+    /// Hash algorithm used for the signed message hash on this route
+    pub hash_algorithm: HashAlgorithm,
+    
+    /// Byte order of integers in the hash pre-image (big-endian for EVM/Cosmos parity)
+    pub integer_encoding: IntegerEncoding,
+    
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// Namespaced CAIP-2 identifier (e.g. "eip155:1"), empty if unset
+    pub caip2_id: String,
+    
+    /// Address family of the counterpart chain, used to validate senders and recipients
+    pub address_format: AddressFormat,
+    
+    /// Gateway address on the counterpart chain (canonical form), empty if unset
+    pub remote_gateway: Vec<u8>,
+    
+    /// Send fee for messages to this chain in micro-USD (0 = free)
+    /// Converted to lamports with the gateway's Pyth SOL/USD feed at send time
+    pub send_fee_usd: u64,
+    
+    /// Whether send_message accepts messages to this chain
+    pub enabled: bool,
+    
+    /// Inclusive range of confirmations senders may request for this chain
+    pub min_confirmations: u16,
+    pub max_confirmations: u16,
+    
+    /// Largest on-chain data accepted for messages to this chain, in bytes
+    pub max_payload_size: u32,
+}
+
+impl ChainConfig {
+    pub const SIZE: usize = 8   // chain_id
+        + 1                     // hash_algorithm
+        + 1                     // integer_encoding
+        + 1                     // bump
+        + 4 + MAX_CAIP2_ID_LEN  // caip2_id
+        + 1                     // address_format
+        + 4 + MAX_SENDER_SIZE   // remote_gateway
+        + 8                     // send_fee_usd
+        + 1                     // enabled
+        + 2                     // min_confirmations
+        + 2                     // max_confirmations
+        + 4;                    // max_payload_size
+    
+    /// Hash scheme for this route
+    pub fn hash_scheme(&self) -> HashScheme {
+        HashScheme {
+            algorithm: self.hash_algorithm,
+            integer_encoding: self.integer_encoding,
+        }
+    }
+    
+    /// Hash scheme for a route, falling back to keccak256/little-endian when unconfigured
+    pub fn route_hash_scheme(config: Option<&ChainConfig>) -> HashScheme {
+        config.map(|c| c.hash_scheme()).unwrap_or_default()
+    }
+    
+    /// Load a route's config from its ChainConfig PDA, which inbound instructions always take
+    /// so a configured chain's hash scheme and sender check cannot be skipped by leaving the
+    /// account out; None while the chain has no config (the PDA is uninitialized)
+    pub fn load_route(account: &AccountInfo) -> Result<Option<ChainConfig>> {
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+        require_keys_eq!(*account.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+        
+        ChainConfig::try_deserialize(&mut &account.try_borrow_data()?[..]).map(Some)
+    }
+    
+    /// Check an outbound message and its requested confirmations against the send limits
+    pub fn validate_send(&self, envelope: &MessageEnvelope, confirmations: u16) -> Result<()> {
+        require!(self.enabled, GatewayError::DestinationChainDisabled);
+        require!(
+            (self.min_confirmations..=self.max_confirmations).contains(&confirmations),
+            GatewayError::ConfirmationsOutOfRange
+        );
+        require!(
+            envelope.on_chain_data.len() <= self.max_payload_size as usize,
+            GatewayError::PayloadTooLargeForDestination
+        );
+        Ok(())
+    }
+}
+
+/// Reverse mapping from a CAIP-2 identifier to the raw u64 chain_id
+/// One PDA per identifier, so two chain_ids can never claim the same namespace
+#[account]
+pub struct ChainAlias {
+    /// Raw chain identifier used by the gateway
+    pub chain_id: u64,
+    
+    /// Namespaced CAIP-2 identifier
+    pub caip2_id: String,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ChainAlias {
+    pub const SIZE: usize = 8   // chain_id
+        + 4 + MAX_CAIP2_ID_LEN  // caip2_id
+        + 1;                    // bump
+}
+
+/// Hash algorithm for the message pre-image
+/// Some counterpart chains natively verify sha256 or blake3 rather than keccak
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum HashAlgorithm {
+    /// Ethereum-compatible keccak256 (default)
+    #[default]
+    Keccak256,
+    /// SHA-256
+    Sha256,
+    /// BLAKE3
+    Blake3,
+}
+
+/// Byte order for integers in the hash pre-image
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum IntegerEncoding {
+    /// Solana native format (default)
+    #[default]
+    LittleEndian,
+    /// EVM abi.encodePacked / Cosmos compatible format
+    BigEndian,
+}
+
+/// Address family of a counterpart chain
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum AddressFormat {
+    /// Unvalidated bytes up to the size limit (default)
+    #[default]
+    Raw,
+    /// 20-byte EVM address (hex)
+    Evm,
+    /// 20- or 32-byte Cosmos address (bech32)
+    Cosmos,
+    /// 32-byte Solana/SVM address (base58)
+    Svm,
+    /// 32-byte Sui/Aptos address (hex)
+    Move,
+}
+
+/// Complete hashing configuration for a chain route
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct HashScheme {
+    pub algorithm: HashAlgorithm,
+    pub integer_encoding: IntegerEncoding,
+}
\ No newline at end of file
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::constants::MAX_SENDER_SIZE;
+use crate::errors::GatewayError;
+use crate::state::MessageEnvelope;
+
+/// IBC-style channel binding a local application to a remote application
+/// Gives each app pair its own sequence space, ordering mode and trust settings
+/// instead of sharing a single firehose per chain pair
+#[account]
+pub struct Channel {
+    /// Remote chain identifier
+    pub remote_chain_id: u64,
+    
+    /// Local application (Solana program or account) that owns the channel
+    pub local_app: Pubkey,
+    
+    /// Application address on the remote chain
+    pub remote_app: Vec<u8>,
+    
+    /// Delivery ordering mode
+    pub ordering: ChannelOrdering,
+    
+    /// Next sequence number expected for outbound messages
+    pub next_sequence_send: u64,
+    
+    /// Next sequence number expected for inbound messages (ordered channels)
+    pub next_sequence_recv: u64,
+    
+    /// Trust setting: inbound messages must also pass the project layer
+    pub require_project_registry: bool,
+    
+    /// Whether the channel accepts messages
+    pub open: bool,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Channel {
+    pub const SIZE: usize = 8       // remote_chain_id
+        + 32                        // local_app
+        + 4 + MAX_SENDER_SIZE       // remote_app
+        + 1                         // ordering
+        + 8                         // next_sequence_send
+        + 8                         // next_sequence_recv
+        + 1                         // require_project_registry
+        + 1                         // open
+        + 1;                        // bump
+    
+    /// First sequence number on a fresh channel (0 marks un-channeled messages)
+    pub const INITIAL_SEQUENCE: u64 = 1;
+    
+    /// 32-byte PDA seed for a remote application address (addresses can exceed 32 bytes)
+    pub fn remote_app_seed(remote_app: &[u8]) -> [u8; 32] {
+        keccak::hash(remote_app).to_bytes()
+    }
+    
+    /// Check an outbound envelope against the channel and advance the send sequence
+    pub fn route_outbound(&mut self, envelope: &MessageEnvelope, sender: &Pubkey) -> Result<()> {
+        require!(self.open, GatewayError::ChannelClosed);
+        require!(
+            self.remote_chain_id == envelope.dest_chain_id
+                && self.local_app == *sender
+                && self.remote_app == envelope.recipient,
+            GatewayError::ChannelMismatch
+        );
+        require!(
+            envelope.sequence == self.next_sequence_send,
+            GatewayError::InvalidSequence
+        );
+        
+        self.next_sequence_send = self
+            .next_sequence_send
+            .checked_add(1)
+            .ok_or(GatewayError::InvalidSequence)?;
+        Ok(())
+    }
+    
+    /// Check an inbound envelope against the channel's binding, trust and ordering rules
+    pub fn route_inbound(&mut self, envelope: &MessageEnvelope, has_project_layer: bool) -> Result<()> {
+        require!(self.open, GatewayError::ChannelClosed);
+        require!(
+            self.remote_chain_id == envelope.source_chain_id
+                && self.remote_app == envelope.sender
+                && self.local_app.as_ref() == envelope.recipient.as_slice(),
+            GatewayError::ChannelMismatch
+        );
+        
+        if self.require_project_registry {
+            require!(has_project_layer, GatewayError::ChannelTrustNotMet);
+        }
+        
+        match self.ordering {
+            ChannelOrdering::Ordered => {
+                require!(
+                    envelope.sequence == self.next_sequence_recv,
+                    GatewayError::InvalidSequence
+                );
+                self.next_sequence_recv = self
+                    .next_sequence_recv
+                    .checked_add(1)
+                    .ok_or(GatewayError::InvalidSequence)?;
+            }
+            ChannelOrdering::Unordered => {
+                // Track the high-water mark only; TxId PDAs already prevent replays
+                if envelope.sequence >= self.next_sequence_recv {
+                    self.next_sequence_recv = envelope.sequence.saturating_add(1);
+                }
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+/// Channel delivery ordering
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChannelOrdering {
+    /// Messages must be processed in strict sequence order
+    Ordered,
+    /// Messages may be processed in any order (replay protection via TxId PDA)
+    Unordered,
+}
@@ -1,4 +1,8 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::constants::{RECENT_TX_FILTER_BYTES, RECENT_TX_FILTER_CAPACITY, RECENT_TX_FILTER_HASHES};
+use crate::errors::GatewayError;
 
 /// Counter PDA tracking message processing per source chain
 /// Allows out-of-order message processing while detecting gaps
@@ -10,6 +14,12 @@ pub struct CounterPDA {
     /// Highest transaction ID seen from this chain
     pub highest_tx_id_seen: u128,
     
+    /// Bloom filter of tx_ids that recently passed TX1, checked before signature work
+    pub recent_tx_filter: [u8; RECENT_TX_FILTER_BYTES],
+    
+    /// tx_ids recorded since the filter was last cleared
+    pub recent_tx_count: u16,
+    
     /// PDA bump seed
     pub bump: u8,
 }
@@ -17,5 +27,48 @@ pub struct CounterPDA {
 impl CounterPDA {
     pub const SIZE: usize = 8   // source_chain_id
         + 16                    // highest_tx_id_seen (u128)
+        + RECENT_TX_FILTER_BYTES // recent_tx_filter
+        + 2                     // recent_tx_count
         + 1;                    // bump
+    
+    /// Check the counter was initialized for `source_chain_id`
+    pub fn check_source_chain(&self, source_chain_id: u64) -> Result<()> {
+        require!(self.source_chain_id != 0, GatewayError::CounterNotInitialized);
+        require!(
+            self.source_chain_id == source_chain_id,
+            GatewayError::CounterChainMismatch
+        );
+        Ok(())
+    }
+    
+    /// Whether `tx_id` may have passed TX1 since the filter was last cleared
+    /// False positives are possible (and clear with the filter); false negatives are not
+    pub fn maybe_recent(&self, tx_id: u128) -> bool {
+        Self::filter_bits(tx_id)
+            .iter()
+            .all(|&bit| self.recent_tx_filter[bit / 8] & (1 << (bit % 8)) != 0)
+    }
+    
+    /// Record `tx_id`, clearing the filter first once it holds its capacity
+    pub fn record_recent(&mut self, tx_id: u128) {
+        if self.recent_tx_count >= RECENT_TX_FILTER_CAPACITY {
+            self.recent_tx_filter = [0u8; RECENT_TX_FILTER_BYTES];
+            self.recent_tx_count = 0;
+        }
+        for bit in Self::filter_bits(tx_id) {
+            self.recent_tx_filter[bit / 8] |= 1 << (bit % 8);
+        }
+        self.recent_tx_count += 1;
+    }
+    
+    /// Filter bit positions of `tx_id`: consecutive u16 words of keccak256(tx_id)
+    fn filter_bits(tx_id: u128) -> [usize; RECENT_TX_FILTER_HASHES] {
+        let digest = keccak::hash(&tx_id.to_le_bytes()).to_bytes();
+        let mut bits = [0usize; RECENT_TX_FILTER_HASHES];
+        for (i, bit) in bits.iter_mut().enumerate() {
+            let word = u16::from_le_bytes([digest[2 * i], digest[2 * i + 1]]) as usize;
+            *bit = word % (RECENT_TX_FILTER_BYTES * 8);
+        }
+        bits
+    }
 }
\ No newline at end of file
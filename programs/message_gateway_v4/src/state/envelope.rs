@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+
+/// Versioned cross-chain message envelope
+/// Single Borsh struct shared by send, TX1, TX2 and hashing so new fields
+/// only need to be added here and in the hash pre-image
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct MessageEnvelope {
+    /// Envelope format version
+    pub version: u8,
+    
+    /// Transaction ID from source chain
+    pub tx_id: u128,
+    
+    /// Chain the message originates from
+    pub source_chain_id: u64,
+    
+    /// Chain the message is delivered to
+    pub dest_chain_id: u64,
+    
+    /// Sender address on the source chain
+    pub sender: Vec<u8>,
+    
+    /// Recipient address on the destination chain
+    pub recipient: Vec<u8>,
+    
+    /// Payload delivered on-chain
+    pub on_chain_data: Vec<u8>,
+    
+    /// Payload for off-chain consumers (signed, not executed)
+    /// May be left empty when only `off_chain_data_hash` is submitted on-chain
+    pub off_chain_data: Vec<u8>,
+    
+    /// keccak256 commitment to `off_chain_data` (zero = derive from `off_chain_data`)
+    pub off_chain_data_hash: [u8; 32],
+    
+    /// Application-level message type (0 = generic)
+    pub message_type: u8,
+    
+    /// Unix timestamp after which the message can no longer be processed (0 = none)
+    pub deadline: i64,
+    
+    /// Channel sequence number (0 = not routed through a channel)
+    pub sequence: u64,
+    
+    /// Protocol version of the validator software that produced the message
+    pub protocol_version: u8,
+    
+    /// Data-availability pointer for bulky payloads stored off-chain
+    /// (IPFS CID or Arweave transaction ID bytes, empty = none)
+    pub da_pointer: Vec<u8>,
+}
+
+impl MessageEnvelope {
+    /// Current envelope format version
+    pub const CURRENT_VERSION: u8 = 1;
+    
+    /// Generic message type
+    pub const MESSAGE_TYPE_GENERIC: u8 = 0;
+    
+    /// Validate version and field sizes (DOS protection)
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.version == Self::CURRENT_VERSION,
+            GatewayError::UnsupportedEnvelopeVersion
+        );
+        require!(self.sender.len() <= MAX_SENDER_SIZE, GatewayError::SenderTooLong);
+        require!(self.recipient.len() <= MAX_RECIPIENT_SIZE, GatewayError::RecipientTooLong);
+        require!(
+            self.on_chain_data.len() <= MAX_ON_CHAIN_DATA_SIZE,
+            GatewayError::OnChainDataTooLarge
+        );
+        require!(
+            self.off_chain_data.len() <= MAX_OFF_CHAIN_DATA_SIZE,
+            GatewayError::OffChainDataTooLarge
+        );
+        require!(
+            self.da_pointer.len() <= MAX_DA_POINTER_SIZE,
+            GatewayError::DaPointerTooLong
+        );
+        Ok(())
+    }
+    
+    /// Both chain ids must be set; 0 is never a chain (it marks uninitialized counters)
+    /// Whether the route itself is allowed is the gateway's call (`allows_route`)
+    pub fn validate_chain_ids(&self) -> Result<()> {
+        require!(
+            self.source_chain_id != 0 && self.dest_chain_id != 0,
+            GatewayError::InvalidChainId
+        );
+        Ok(())
+    }
+    
+    /// Commitment to the off-chain payload that validators sign over
+    /// Commit-only envelopes carry just the hash; if the data is also present it must match
+    pub fn resolved_off_chain_data_hash(&self) -> Result<[u8; 32]> {
+        if self.off_chain_data_hash == [0u8; 32] {
+            return Ok(keccak::hash(&self.off_chain_data).to_bytes());
+        }
+        
+        if !self.off_chain_data.is_empty() {
+            require!(
+                keccak::hash(&self.off_chain_data).to_bytes() == self.off_chain_data_hash,
+                GatewayError::OffChainDataHashMismatch
+            );
+        }
+        
+        Ok(self.off_chain_data_hash)
+    }
+    
+    /// Whether the message is routed through a channel
+    pub fn uses_channel(&self) -> bool {
+        self.sequence != 0
+    }
+    
+    /// Whether the deadline has passed at the given unix timestamp
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.deadline != 0 && now > self.deadline
+    }
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::errors::GatewayError;
+use crate::state::MessageEnvelope;
+
+/// Liquidity provider allowed to front inbound token transfers before TX2
+/// The account holds the LP's SOL bond on top of its rent; the bond can be slashed when a
+/// fronted message is revoked
+#[account]
+pub struct LiquidityProvider {
+    /// LP wallet
+    pub owner: Pubkey,
+    
+    /// Bonded lamports held by this account
+    pub bond: u64,
+    
+    /// Fills not yet closed; the bond cannot be withdrawn while any are open
+    pub open_fills: u32,
+    
+    /// Total lamports slashed from this LP
+    pub slashed: u64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl LiquidityProvider {
+    pub const SIZE: usize = 32      // owner
+        + 8                         // bond
+        + 4                         // open_fills
+        + 8                         // slashed
+        + 1;                        // bump
+}
+
+/// Lifecycle of a fronted transfer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FastFillStatus {
+    /// Fronted, waiting for TX2
+    Pending,
+    /// TX2 delivered the transfer to the LP
+    Repaid,
+    /// TX2 carried a different envelope than the one fronted; the LP is not repaid
+    Forfeited,
+    /// The authority revoked the message; the LP was slashed and TX2 is blocked
+    Revoked,
+}
+
+impl FastFillStatus {
+    /// Whether TX2 settled the fill, so it no longer guards the message
+    pub fn is_settled(&self) -> bool {
+        matches!(self, FastFillStatus::Repaid | FastFillStatus::Forfeited)
+    }
+}
+
+/// Inbound token transfer fronted by a liquidity provider after TX1
+/// When TX2 lands with the same envelope, the transfer is delivered to the LP instead of
+/// the recipient
+#[account]
+pub struct FastFill {
+    /// Source chain of the transfer
+    pub source_chain_id: u64,
+    
+    /// Transaction identifier of the transfer
+    pub tx_id: u128,
+    
+    /// keccak256 of the borsh-encoded envelope the LP fronted
+    pub envelope_digest: [u8; 32],
+    
+    /// LP wallet
+    pub liquidity_provider: Pubkey,
+    
+    /// LP token account repaid by TX2
+    pub repayment_account: Pubkey,
+    
+    /// Fronted mint
+    pub mint: Pubkey,
+    
+    /// Recipient that was paid
+    pub recipient: Pubkey,
+    
+    /// Transfer amount in the mint's base units (repaid to the LP less any release fee)
+    pub amount: u64,
+    
+    /// LP fee withheld from the fronted amount
+    pub fee: u64,
+    
+    /// Fill lifecycle
+    pub status: FastFillStatus,
+    
+    /// Unix timestamp of the fill
+    pub filled_at: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl FastFill {
+    pub const SIZE: usize = 8       // source_chain_id
+        + 16                        // tx_id
+        + 32                        // envelope_digest
+        + 32                        // liquidity_provider
+        + 32                        // repayment_account
+        + 32                        // mint
+        + 32                        // recipient
+        + 8                         // amount
+        + 8                         // fee
+        + 1                         // status
+        + 8                         // filled_at
+        + 1;                        // bump
+    
+    /// Digest binding a fill to the exact envelope that was fronted
+    pub fn envelope_digest(envelope: &MessageEnvelope) -> Result<[u8; 32]> {
+        let mut encoded = Vec::new();
+        envelope
+            .serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        Ok(keccak::hash(&encoded).to_bytes())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::GatewayError;
+
+/// Lamport fee schedule for messages to and from one remote chain
+/// send_message charges `base_fee + fee_per_byte * on_chain_data.len()` into the fee vault,
+/// on top of the destination's USD send fee; process_message refunds relayers of messages
+/// from the chain out of the vault, so the accumulated fees fund relaying
+#[account]
+pub struct FeeConfig {
+    /// Remote chain identifier: destination of charged sends, source of refunded messages
+    pub dest_chain_id: u64,
+    
+    /// Flat fee per message in lamports
+    pub base_fee: u64,
+    
+    /// Fee per byte of on-chain data in lamports
+    pub fee_per_byte: u64,
+    
+    /// Flat refund per processed message from this chain in lamports
+    pub relayer_refund: u64,
+    
+    /// Share of the send fee a message of the same size would pay, refunded on top of
+    /// `relayer_refund` (basis points)
+    pub relayer_fee_share_bps: u16,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl FeeConfig {
+    pub const SIZE: usize = 8   // dest_chain_id
+        + 8                     // base_fee
+        + 8                     // fee_per_byte
+        + 8                     // relayer_refund
+        + 2                     // relayer_fee_share_bps
+        + 1;                    // bump
+    
+    /// Fee in lamports for a message carrying `payload_size` bytes of on-chain data
+    pub fn fee_for(&self, payload_size: usize) -> Result<u64> {
+        self.fee_per_byte
+            .checked_mul(payload_size as u64)
+            .and_then(|per_byte| per_byte.checked_add(self.base_fee))
+            .ok_or(error!(GatewayError::InvalidAmount))
+    }
+    
+    /// Refund in lamports owed to the relayer of a message carrying `payload_size` bytes
+    /// of on-chain data from this chain
+    pub fn relayer_refund_for(&self, payload_size: usize) -> Result<u64> {
+        let share = self.fee_for(payload_size)? as u128 * self.relayer_fee_share_bps as u128
+            / BPS_DENOMINATOR as u128;
+        self.relayer_refund
+            .checked_add(share as u64)
+            .ok_or(error!(GatewayError::InvalidAmount))
+    }
+}
\ No newline at end of file
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{DEFAULT_INSTANCE_ID, MIN_COMPATIBLE_PROGRAM_VERSION, PROGRAM_VERSION};
+
 /// Main gateway account storing configuration and state
 #[account]
 pub struct MessageGateway {
@@ -14,11 +16,116 @@ pub struct MessageGateway {
     
     /// PDA bump seed
     pub bump: u8,
+    
+    /// Oldest envelope protocol version accepted by process_message
+    pub min_protocol_version: u8,
+    
+    /// Newest envelope protocol version accepted by process_message
+    pub max_protocol_version: u8,
+    
+    /// Loopback test mode: accept messages whose source and destination are this gateway
+    pub loopback_enabled: bool,
+    
+    /// Pyth SOL/USD price feed id used to convert USD send fees
+    pub price_feed_id: [u8; 32],
+    
+    /// Maximum accepted age of a price update in seconds
+    pub max_price_age: u64,
+    
+    /// Wallet receiving token bridge fees in its token accounts
+    pub treasury: Pubkey,
+    
+    /// Advanced by every signer registry change; registry bundles synced at an older
+    /// epoch are rejected until re-synced
+    pub registry_epoch: u64,
+    
+    /// Concurrent Merkle tree recording processed messages (default = history disabled)
+    pub message_history_tree: Pubkey,
+    
+    /// Bump of the history authority PDA that owns the tree
+    pub message_history_bump: u8,
+    
+    /// Canonical bump of the SOL vault PDA, cached so constraints and signers skip the
+    /// `find_program_address` search
+    pub sol_vault_bump: u8,
+    
+    /// Verify every signature of a TX2 instead of stopping once all layer thresholds are
+    /// met, e.g. to keep invalid attestations provable as slashing evidence
+    pub full_signature_verification: bool,
+    
+    /// Also compare the signature bytes of each matched Ed25519 precompile entry with the
+    /// attestation, on top of its key and message
+    pub strict_precompile_checks: bool,
+    
+    /// PROGRAM_VERSION of the build that created the gateway; instructions refuse a
+    /// gateway outside this build's compatible range instead of misreading its layout
+    pub program_version: u16,
+    
+    /// Instance of this gateway among those sharing its chain id under the program (blue/
+    /// green, canary, per environment); the gateway, its signer registries, counters and
+    /// registry bundles are seeded with it, and validators sign for it
+    pub instance_id: u16,
 }
 
 impl MessageGateway {
     pub const SIZE: usize = 32  // authority
         + 8                     // chain_id
         + 1                     // system_enabled
-        + 1;                    // bump
+        + 1                     // bump
+        + 1                     // min_protocol_version
+        + 1                     // max_protocol_version
+        + 1                     // loopback_enabled
+        + 32                    // price_feed_id
+        + 8                     // max_price_age
+        + 32                    // treasury
+        + 8                     // registry_epoch
+        + 32                    // message_history_tree
+        + 1                     // message_history_bump
+        + 1                     // sol_vault_bump
+        + 1                     // full_signature_verification
+        + 1                     // strict_precompile_checks
+        + 2                     // program_version
+        + 2;                    // instance_id
+    
+    /// Whether a route between the two chains is allowed on this gateway
+    /// Solana-to-Solana routes between different chain_ids are ordinary routes;
+    /// same-chain (loopback) routes need loopback mode
+    pub fn allows_route(&self, source_chain_id: u64, dest_chain_id: u64) -> bool {
+        source_chain_id != dest_chain_id || self.loopback_enabled
+    }
+    
+    /// Invalidate every registry bundle after a signer registry change
+    pub fn advance_registry_epoch(&mut self) {
+        self.registry_epoch = self.registry_epoch.wrapping_add(1);
+    }
+    
+    /// Whether processed messages must be appended to the history tree
+    pub fn message_history_enabled(&self) -> bool {
+        self.message_history_tree != Pubkey::default()
+    }
+    
+    /// Whether this build can read the gateway's account layout
+    pub fn is_compatible(&self) -> bool {
+        (MIN_COMPATIBLE_PROGRAM_VERSION..=PROGRAM_VERSION).contains(&self.program_version)
+    }
+    
+    /// Seed suffix of the PDAs scoped to this gateway instance
+    pub fn instance_seed(&self) -> Vec<u8> {
+        instance_seed(self.instance_id)
+    }
+    
+    /// Whether a protocol version falls inside the supported range
+    pub fn supports_protocol_version(&self, protocol_version: u8) -> bool {
+        (self.min_protocol_version..=self.max_protocol_version).contains(&protocol_version)
+    }
+}
+
+/// Seed suffix scoping a PDA to gateway instance `instance_id`: empty for the default
+/// instance, whose addresses are the ones derived before instances existed
+pub fn instance_seed(instance_id: u16) -> Vec<u8> {
+    if instance_id == DEFAULT_INSTANCE_ID {
+        Vec::new()
+    } else {
+        instance_id.to_le_bytes().to_vec()
+    }
 }
\ No newline at end of file
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::GatewayError;
+use crate::state::MessageEnvelope;
+
+/// Message as produced by Via V3 gateways and signed by V3 validators
+/// (no envelope version, no domain separation)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct LegacyV3Message {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub dest_chain_id: u64,
+    pub sender: Vec<u8>,
+    pub recipient: Vec<u8>,
+    pub on_chain_data: Vec<u8>,
+    pub off_chain_data: Vec<u8>,
+}
+
+impl LegacyV3Message {
+    /// Validate field sizes (DOS protection)
+    pub fn validate(&self) -> Result<()> {
+        require!(self.sender.len() <= MAX_SENDER_SIZE, GatewayError::SenderTooLong);
+        require!(self.recipient.len() <= MAX_RECIPIENT_SIZE, GatewayError::RecipientTooLong);
+        require!(
+            self.on_chain_data.len() <= MAX_ON_CHAIN_DATA_SIZE,
+            GatewayError::OnChainDataTooLarge
+        );
+        require!(
+            self.off_chain_data.len() <= MAX_OFF_CHAIN_DATA_SIZE,
+            GatewayError::OffChainDataTooLarge
+        );
+        Ok(())
+    }
+    
+    /// Re-wrap into a V4 envelope using the authority-configured route mapping
+    pub fn to_envelope(&self, mapping: &V3RouteMapping) -> MessageEnvelope {
+        MessageEnvelope {
+            version: MessageEnvelope::CURRENT_VERSION,
+            tx_id: self.tx_id,
+            source_chain_id: mapping.v4_chain_id,
+            dest_chain_id: self.dest_chain_id,
+            sender: self.sender.clone(),
+            recipient: self.recipient.clone(),
+            on_chain_data: self.on_chain_data.clone(),
+            off_chain_data: self.off_chain_data.clone(),
+            off_chain_data_hash: [0u8; 32],
+            message_type: mapping.message_type,
+            deadline: 0,
+            sequence: 0,
+            protocol_version: PROTOCOL_VERSION,
+            da_pointer: Vec::new(),
+        }
+    }
+}
+
+/// Authority-configured mapping of a V3 source chain onto a V4 route
+/// Lets chains migrate one at a time: V3 attestations are accepted only for mapped chains
+#[account]
+pub struct V3RouteMapping {
+    /// Chain identifier used by the V3 gateway
+    pub v3_chain_id: u64,
+    
+    /// Chain identifier the message is delivered under in V4 (selects Chain registry)
+    pub v4_chain_id: u64,
+    
+    /// V4 message type assigned to translated messages
+    pub message_type: u8,
+    
+    /// Whether V3 messages from this chain are still accepted
+    pub enabled: bool,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl V3RouteMapping {
+    pub const SIZE: usize = 8   // v3_chain_id
+        + 8                     // v4_chain_id
+        + 1                     // message_type
+        + 1                     // enabled
+        + 1;                    // bump
+}
+
+/// Permanent replay marker for a translated V3 message
+#[account]
+pub struct V3Receipt {
+    /// Transaction ID from the V3 source chain
+    pub tx_id: u128,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl V3Receipt {
+    pub const SIZE: usize = 16  // tx_id (u128)
+        + 1;                    // bump
+}
\ No newline at end of file
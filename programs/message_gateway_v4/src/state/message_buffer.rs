@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::errors::GatewayError;
+
+/// Payload of an inbound message too large for one transaction, assembled from chunks
+/// Created by create_message_buffer, filled by create_message_chunk and closed by
+/// create_tx_pda_buffered (or close_message_buffer if abandoned)
+#[account]
+pub struct MessageBuffer {
+    /// Relayer that opened the buffer; the only account allowed to write or close it
+    pub relayer: Pubkey,
+    
+    /// Source chain of the buffered message
+    pub source_chain_id: u64,
+    
+    /// Transaction ID of the buffered message
+    pub tx_id: u128,
+    
+    /// keccak256 of the complete payload
+    pub payload_hash: [u8; 32],
+    
+    /// Payload length once every chunk has arrived
+    pub total_size: u32,
+    
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// Chunks received so far, in order
+    pub data: Vec<u8>,
+}
+
+impl MessageBuffer {
+    /// Size without the payload bytes
+    pub const BASE_SIZE: usize = 32  // relayer
+        + 8                          // source_chain_id
+        + 16                         // tx_id (u128)
+        + 32                         // payload_hash
+        + 4                          // total_size
+        + 1                          // bump
+        + 4;                         // data length prefix
+    
+    /// Account size for a payload of `total_size` bytes
+    pub fn size(total_size: u32) -> usize {
+        Self::BASE_SIZE + total_size as usize
+    }
+    
+    /// Append the chunk starting at `offset`
+    /// Chunks must arrive in order, so a resent chunk is rejected instead of duplicated
+    pub fn append_chunk(&mut self, offset: u32, chunk: &[u8]) -> Result<()> {
+        require!(
+            offset as usize == self.data.len() && !chunk.is_empty(),
+            GatewayError::InvalidMessageChunk
+        );
+        require!(
+            self.data.len() + chunk.len() <= self.total_size as usize,
+            GatewayError::InvalidMessageChunk
+        );
+        self.data.extend_from_slice(chunk);
+        Ok(())
+    }
+    
+    /// The complete payload, once every chunk has arrived and it matches `payload_hash`
+    pub fn payload(&self) -> Result<&[u8]> {
+        require!(
+            self.data.len() == self.total_size as usize
+                && keccak::hash(&self.data).to_bytes() == self.payload_hash,
+            GatewayError::MessageBufferIncomplete
+        );
+        Ok(&self.data)
+    }
+}
\ No newline at end of file
@@ -1,9 +1,35 @@
+pub mod chain_config;
+pub mod channel;
 pub mod counter;
+pub mod envelope;
+pub mod fast_transfer;
+pub mod fee_config;
 pub mod gateway;
+pub mod legacy;
+pub mod message_buffer;
+pub mod nft_bridge;
+pub mod query;
+pub mod registry_bundle;
+pub mod relayer_pool;
 pub mod signer_registry;
+pub mod token_bridge;
 pub mod tx_id;
+pub mod vesting;
 
+pub use chain_config::*;
+pub use channel::*;
 pub use counter::*;
+pub use envelope::*;
+pub use fast_transfer::*;
+pub use fee_config::*;
 pub use gateway::*;
+pub use legacy::*;
+pub use message_buffer::*;
+pub use nft_bridge::*;
+pub use query::*;
+pub use registry_bundle::*;
+pub use relayer_pool::*;
 pub use signer_registry::*;
-pub use tx_id::*;
\ No newline at end of file
+pub use token_bridge::*;
+pub use tx_id::*;
+pub use vesting::*;
\ No newline at end of file
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_SENDER_SIZE;
+
+/// Metaplex collection whose verified members may be bridged out of Solana
+#[account]
+pub struct NftCollection {
+    /// Collection NFT mint
+    pub collection_mint: Pubkey,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl NftCollection {
+    pub const SIZE: usize = 32      // collection_mint
+        + 1;                        // bump
+}
+
+/// Registry entry for a wrapped NFT representing a foreign NFT on Solana
+/// The wrapped mint has 0 decimals and the gateway keeps its supply at most one
+#[account]
+pub struct WrappedNft {
+    /// Chain the NFT is native to
+    pub source_chain_id: u64,
+    
+    /// Collection address on the source chain
+    pub source_collection: Vec<u8>,
+    
+    /// Token identifier on the source chain
+    pub token_id: [u8; 32],
+    
+    /// keccak256 of the relayed metadata URI
+    pub metadata_uri_hash: [u8; 32],
+    
+    /// Wrapped mint (PDA of this program)
+    pub mint: Pubkey,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl WrappedNft {
+    pub const SIZE: usize = 8       // source_chain_id
+        + 4 + MAX_SENDER_SIZE       // source_collection
+        + 32                        // token_id
+        + 32                        // metadata_uri_hash
+        + 32                        // mint
+        + 1;                        // bump
+}
+
+/// Bubblegum Merkle tree the gateway mints wrapped compressed NFTs into (admin-registered)
+/// The gateway PDA must be the tree's creator or delegate
+#[account]
+pub struct CompressedNftTree {
+    /// Merkle tree account (owned by the account-compression program)
+    pub merkle_tree: Pubkey,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl CompressedNftTree {
+    pub const SIZE: usize = 32      // merkle_tree
+        + 1;                        // bump
+}
+
+/// Registry entry for a foreign NFT represented on Solana by a compressed NFT
+/// At most one leaf exists per foreign NFT; it is re-minted when the NFT returns again
+#[account]
+pub struct WrappedCompressedNft {
+    /// Chain the NFT is native to
+    pub source_chain_id: u64,
+    
+    /// Collection address on the source chain
+    pub source_collection: Vec<u8>,
+    
+    /// keccak256 of `source_collection` (PDA seed)
+    pub collection_hash: [u8; 32],
+    
+    /// Token identifier on the source chain
+    pub token_id: [u8; 32],
+    
+    /// keccak256 of the relayed metadata URI
+    pub metadata_uri_hash: [u8; 32],
+    
+    /// Tree holding the current leaf
+    pub merkle_tree: Pubkey,
+    
+    /// Bubblegum asset id of the current leaf
+    pub asset_id: Pubkey,
+    
+    /// Whether a leaf is currently minted (false after it is burned to go home)
+    pub minted: bool,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl WrappedCompressedNft {
+    pub const SIZE: usize = 8       // source_chain_id
+        + 4 + MAX_SENDER_SIZE       // source_collection
+        + 32                        // collection_hash
+        + 32                        // token_id
+        + 32                        // metadata_uri_hash
+        + 32                        // merkle_tree
+        + 32                        // asset_id
+        + 1                         // minted
+        + 1;                        // bump
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+/// Outstanding cross-chain query awaiting a validator-signed response
+/// Created by send_query, closed by fulfill_query or cancel_query
+#[account]
+pub struct PendingQuery {
+    /// Requesting application (program PDA or wallet); receives the response
+    pub requester: Pubkey,
+    
+    /// Query identifier (tx_id of the request envelope)
+    pub query_id: u128,
+    
+    /// Chain the query was sent to; responses must originate there
+    pub dest_chain_id: u64,
+    
+    /// keccak256 of the remote address queried; responses must be sent by it
+    pub responder_hash: [u8; 32],
+    
+    /// Program invoked with the response
+    pub callback_program: Pubkey,
+    
+    /// Account that paid rent and is refunded on close
+    pub payer: Pubkey,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl PendingQuery {
+    pub const SIZE: usize = 32  // requester
+        + 16                    // query_id (u128)
+        + 8                     // dest_chain_id
+        + 32                    // responder_hash
+        + 32                    // callback_program
+        + 32                    // payer
+        + 1;                    // bump
+    
+    /// Hash binding a remote responder address
+    pub fn responder_hash(responder: &[u8]) -> [u8; 32] {
+        keccak::hash(responder).to_bytes()
+    }
+}
\ No newline at end of file
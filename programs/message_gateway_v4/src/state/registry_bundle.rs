@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_EXTRA_VERIFIERS, MAX_SIGNERS_PER_REGISTRY};
+use crate::errors::GatewayError;
+use crate::state::SignerRegistry;
+
+/// Zero-copy snapshot of the VIA, Chain and (optional) Project registries of one inbound
+/// route, letting process_message_bundled replace three registry accounts with one
+/// Valid only while `registry_epoch` matches the gateway's; any registry change
+/// advances the gateway epoch and the bundle must be re-synced before it is used again
+#[account(zero_copy)]
+pub struct RegistryBundle {
+    /// Gateway registry epoch at the last sync
+    pub registry_epoch: u64,
+    
+    /// Source chain whose Chain (and Project) registries are snapshotted
+    pub source_chain_id: u64,
+    
+    /// Project registry the project layer was copied from (default if none)
+    pub project_registry: Pubkey,
+    
+    /// VIA registry of this gateway's chain
+    pub via: BundledRegistry,
+    
+    /// Chain registry of the source chain
+    pub chain: BundledRegistry,
+    
+    /// Project registry of the source chain (unused when `has_project` is 0)
+    pub project: BundledRegistry,
+    
+    /// Extra verifier ids the project registry requires, in its configured order
+    pub extra_verifiers: [u8; MAX_EXTRA_VERIFIERS],
+    
+    /// Number of valid entries in `extra_verifiers`
+    pub extra_verifier_count: u8,
+    
+    /// Whether the project layer is part of the bundle (1) or not (0)
+    pub has_project: u8,
+    
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// Explicit tail padding to the struct's 8-byte alignment (bytemuck::Pod forbids implicit padding)
+    pub padding: [u8; 1],
+}
+
+// Every field is accounted for, so the layout has no implicit padding; changing
+// MAX_SIGNERS_PER_REGISTRY or MAX_EXTRA_VERIFIERS requires resizing `padding`
+const _: () = assert!(
+    std::mem::size_of::<BundledRegistry>() == 32 * MAX_SIGNERS_PER_REGISTRY + 8 + 5 + 3
+);
+const _: () = assert!(
+    std::mem::size_of::<RegistryBundle>()
+        == 8 + 8 + 32 + 3 * std::mem::size_of::<BundledRegistry>() + MAX_EXTRA_VERIFIERS + 3 + 1
+);
+
+/// Membership, threshold and shadow trial of one signer registry, copied into a bundle
+#[zero_copy]
+pub struct BundledRegistry {
+    pub signers: [Pubkey; MAX_SIGNERS_PER_REGISTRY],
+    /// End of the source registry's shadow trial (unix timestamp, 0 = none)
+    pub shadow_until: i64,
+    pub signer_count: u8,
+    pub required_signatures: u8,
+    pub enabled: u8,
+    /// `SignerRegistryType` discriminant of the source registry
+    pub registry_type: u8,
+    /// Threshold enforced until `shadow_until`
+    pub shadow_enforced_signatures: u8,
+    /// Explicit tail padding to the struct's 8-byte alignment
+    pub padding: [u8; 3],
+}
+
+impl RegistryBundle {
+    pub const SIZE: usize = 8 + std::mem::size_of::<RegistryBundle>();
+    
+    /// Overwrite the bundle with the current state of the route's registries
+    pub fn snapshot(
+        &mut self,
+        registry_epoch: u64,
+        source_chain_id: u64,
+        via_registry: &SignerRegistry,
+        chain_registry: &SignerRegistry,
+        project_registry: Option<(Pubkey, &SignerRegistry)>,
+    ) -> Result<()> {
+        self.registry_epoch = registry_epoch;
+        self.source_chain_id = source_chain_id;
+        self.via = BundledRegistry::from_registry(via_registry)?;
+        self.chain = BundledRegistry::from_registry(chain_registry)?;
+        
+        self.extra_verifiers = [0; MAX_EXTRA_VERIFIERS];
+        match project_registry {
+            Some((address, registry)) => {
+                require!(
+                    registry.extra_verifiers.len() <= MAX_EXTRA_VERIFIERS,
+                    GatewayError::TooManyVerifiers
+                );
+                self.project_registry = address;
+                self.project = BundledRegistry::from_registry(registry)?;
+                self.extra_verifiers[..registry.extra_verifiers.len()]
+                    .copy_from_slice(&registry.extra_verifiers);
+                self.extra_verifier_count = registry.extra_verifiers.len() as u8;
+                self.has_project = 1;
+            }
+            None => {
+                self.project_registry = Pubkey::default();
+                self.project = BundledRegistry::default();
+                self.extra_verifier_count = 0;
+                self.has_project = 0;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Whether the snapshot is still current for the gateway's registry epoch
+    pub fn is_current(&self, registry_epoch: u64) -> bool {
+        self.registry_epoch == registry_epoch
+    }
+    
+    /// Project layer of the bundle, if it has one
+    pub fn project_layer(&self) -> Option<&BundledRegistry> {
+        (self.has_project != 0).then_some(&self.project)
+    }
+    
+    /// Extra verifier ids the bundled project layer requires
+    pub fn extra_verifier_ids(&self) -> &[u8] {
+        &self.extra_verifiers[..self.extra_verifier_count as usize]
+    }
+}
+
+impl BundledRegistry {
+    /// Copy the membership, threshold, shadow trial and enabled flag of a registry
+    pub fn from_registry(registry: &SignerRegistry) -> Result<Self> {
+        require!(
+            registry.signers.len() <= MAX_SIGNERS_PER_REGISTRY,
+            GatewayError::TooManySignatures
+        );
+        
+        let mut bundled = Self::default();
+        bundled.signers[..registry.signers.len()].copy_from_slice(&registry.signers);
+        bundled.signer_count = registry.signers.len() as u8;
+        bundled.required_signatures = registry.required_signatures;
+        bundled.enabled = registry.enabled as u8;
+        bundled.registry_type = registry.registry_type.discriminant()?;
+        bundled.shadow_until = registry.shadow_until;
+        bundled.shadow_enforced_signatures = registry.shadow_enforced_signatures;
+        Ok(bundled)
+    }
+    
+    /// Signers copied from the source registry
+    pub fn signers(&self) -> &[Pubkey] {
+        &self.signers[..self.signer_count as usize]
+    }
+}
+
+impl Default for BundledRegistry {
+    fn default() -> Self {
+        Self {
+            signers: [Pubkey::default(); MAX_SIGNERS_PER_REGISTRY],
+            shadow_until: 0,
+            signer_count: 0,
+            required_signatures: 0,
+            enabled: 0,
+            registry_type: 0,
+            shadow_enforced_signatures: 0,
+            padding: [0; 3],
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::GatewayError;
+
+/// Lamports a relayer pre-deposits to pay TxId PDA rent
+/// create_tx_pda_pooled reimburses the relayer's TX1 rent from the pool; the rent comes
+/// back to the pool when process_message (or gc_batch) closes the PDA
+/// The account holds `balance` on top of its own rent
+#[account]
+pub struct RelayerPool {
+    /// Relayer owning the pool; the only account allowed to withdraw
+    pub relayer: Pubkey,
+    
+    /// Deposited lamports not currently lent to a TxId PDA
+    pub balance: u64,
+    
+    /// Lamports lent to TxId PDAs that are still open
+    pub rent_outstanding: u64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RelayerPool {
+    pub const SIZE: usize = 32      // relayer
+        + 8                         // balance
+        + 8                         // rent_outstanding
+        + 1;                        // bump
+    
+    /// Lend `rent` from the balance to a new TxId PDA
+    pub fn advance_rent(&mut self, rent: u64) -> Result<()> {
+        require!(rent <= self.balance, GatewayError::RelayerPoolInsufficient);
+        self.balance -= rent;
+        self.rent_outstanding = self
+            .rent_outstanding
+            .checked_add(rent)
+            .ok_or(GatewayError::InvalidAmount)?;
+        Ok(())
+    }
+    
+    /// Credit back the rent of a closed TxId PDA
+    pub fn settle_rent(&mut self, rent: u64) -> Result<()> {
+        self.balance = self.balance.checked_add(rent).ok_or(GatewayError::InvalidAmount)?;
+        self.rent_outstanding = self.rent_outstanding.saturating_sub(rent);
+        Ok(())
+    }
+}
\ No newline at end of file
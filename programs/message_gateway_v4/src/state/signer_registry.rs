@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{MAX_EXTRA_VERIFIERS, MAX_VERIFIER_ID, MIN_THRESHOLD};
+
 /// Signer registry for managing authorized signers in three-layer security model
 #[account]
 pub struct SignerRegistry {
@@ -23,6 +25,25 @@ pub struct SignerRegistry {
     
     /// PDA bump seed
     pub bump: u8,
+    
+    /// Additional verifier sets (Verifier registry ids) whose thresholds must also
+    /// be met for messages validated against this registry (project registries only)
+    pub extra_verifiers: Vec<u8>,
+    
+    /// End of a shadow trial (unix timestamp, 0 = none): until then `required_signatures`
+    /// is evaluated and misses are logged, but only `shadow_enforced_signatures` is enforced
+    pub shadow_until: i64,
+    
+    /// Threshold enforced during a shadow trial, e.g. the one before a raise (0 for a new
+    /// registry that is not enforced yet)
+    pub shadow_enforced_signatures: u8,
+    
+    /// Slot the registry was initialized in; only then is it new enough for a trial that
+    /// enforces no signatures at all
+    pub created_slot: u64,
+    
+    /// Threshold enforced before the last threshold change, the floor of a trial of a raise
+    pub previous_required_signatures: u8,
 }
 
 impl SignerRegistry {
@@ -36,7 +57,12 @@ impl SignerRegistry {
         1 +                         // required_signatures
         8 +                         // chain_id
         1 +                         // enabled
-        1                           // bump
+        1 +                         // bump
+        4 + MAX_EXTRA_VERIFIERS +   // extra_verifiers vec
+        8 +                         // shadow_until
+        1 +                         // shadow_enforced_signatures
+        8 +                         // created_slot
+        1                           // previous_required_signatures
     }
     
     /// Default maximum signers per registry
@@ -50,6 +76,38 @@ impl SignerRegistry {
         self.enabled && self.signers.contains(signer)
     }
     
+    /// Signatures enforced at `now`: `required_signatures`, or the trial threshold while a
+    /// shadow trial runs
+    pub fn enforced_signatures(&self, now: i64) -> u8 {
+        if now < self.shadow_until {
+            self.shadow_enforced_signatures.min(self.required_signatures)
+        } else {
+            self.required_signatures
+        }
+    }
+    
+    /// Whether the registry has never been enforced at (`now`, `slot`): it was created in this
+    /// slot, or the trial it started with, enforcing no signatures, is still running
+    pub fn is_new(&self, now: i64, slot: u64) -> bool {
+        slot == self.created_slot || (now < self.shadow_until && self.shadow_enforced_signatures == 0)
+    }
+    
+    /// Lowest threshold a shadow trial may enforce: 0 for a new registry, otherwise
+    /// MIN_THRESHOLD and at least the threshold enforced before the last change
+    pub fn min_shadow_enforced_signatures(&self, now: i64, slot: u64) -> u8 {
+        if self.is_new(now, slot) {
+            0
+        } else {
+            self.previous_required_signatures.max(MIN_THRESHOLD)
+        }
+    }
+    
+    /// Change the threshold, keeping the one enforced until `now` as the floor for trials
+    pub fn set_required_signatures(&mut self, required_signatures: u8, now: i64) {
+        self.previous_required_signatures = self.enforced_signatures(now);
+        self.required_signatures = required_signatures;
+    }
+    
     /// Validate threshold requirements
     pub fn validate_threshold(&self) -> Result<()> {
         require!(
@@ -65,7 +123,7 @@ impl SignerRegistry {
 }
 
 /// Type of signer registry for three-layer security
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
 pub enum SignerRegistryType {
     /// Via Labs core signers - highest authority level
     VIA,
@@ -73,15 +131,25 @@ pub enum SignerRegistryType {
     Chain,
     /// Project-specific signers - application level
     Project,
+    /// Independent verifier set opted into by projects (LayerZero DVN style)
+    Verifier(u8),
 }
 
 impl SignerRegistryType {
     /// Get discriminant value for PDA seeds
-    pub fn discriminant(&self) -> u8 {
+    /// Verifier ids above MAX_VERIFIER_ID have no seed of their own and are rejected
+    pub fn discriminant(&self) -> Result<u8> {
         match self {
-            SignerRegistryType::VIA => 0,
-            SignerRegistryType::Chain => 1,
-            SignerRegistryType::Project => 2,
+            SignerRegistryType::VIA => Ok(0),
+            SignerRegistryType::Chain => Ok(1),
+            SignerRegistryType::Project => Ok(2),
+            SignerRegistryType::Verifier(id) => {
+                require!(
+                    *id <= MAX_VERIFIER_ID,
+                    crate::errors::GatewayError::InvalidSignerRegistryType
+                );
+                Ok(3 + id)
+            }
         }
     }
     
@@ -91,7 +159,15 @@ impl SignerRegistryType {
             0 => Some(SignerRegistryType::VIA),
             1 => Some(SignerRegistryType::Chain),
             2 => Some(SignerRegistryType::Project),
-            _ => None,
+            id => (id - 3 <= MAX_VERIFIER_ID).then_some(SignerRegistryType::Verifier(id - 3)),
+        }
+    }
+    
+    /// Verifier ids must stay within the single-byte seed space
+    pub fn is_valid(&self) -> bool {
+        match self {
+            SignerRegistryType::Verifier(id) => *id <= MAX_VERIFIER_ID,
+            _ => true,
         }
     }
 }
@@ -99,15 +175,29 @@ impl SignerRegistryType {
 /// Message signature - Ethereum-style simple format
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct MessageSignature {
-    /// Ed25519 signature (64 bytes)
+    /// Ed25519 signature, or secp256k1 compact signature (r || s), 64 bytes
     pub signature: [u8; 64],
     
-    /// Signer public key - layer determined by registry membership
+    /// Signer identity - layer determined by registry membership
+    /// Ed25519: the public key; secp256k1: keccak256 of the 64-byte uncompressed key
     pub signer: Pubkey,
+    
+    /// Curve the attestation was produced with
+    pub scheme: SignatureScheme,
 }
 
 impl MessageSignature {
-    pub const SIZE: usize = 64 + 32;  // signature + pubkey
+    pub const SIZE: usize = 64 + 32 + 2;  // signature + pubkey + scheme
+}
+
+/// Signature scheme of a single attestation
+/// A message's signature vector may mix schemes freely
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SignatureScheme {
+    /// Verified through an Ed25519 precompile instruction in the same transaction
+    Ed25519,
+    /// Verified with the secp256k1_recover syscall
+    Secp256k1 { recovery_id: u8 },
 }
 
 /// Security layer for signature validation
@@ -138,6 +228,9 @@ pub struct ValidationResult {
     pub via_signatures: u8,
     pub chain_signatures: u8,
     pub project_signatures: u8,
+    /// Signatures counted per extra verifier set, in the project's configured order
+    /// (unused slots stay zero)
+    pub verifier_signatures: [u8; MAX_EXTRA_VERIFIERS],
     pub total_valid: u8,
 }
 
@@ -147,6 +240,7 @@ impl ValidationResult {
             via_signatures: 0,
             chain_signatures: 0,
             project_signatures: 0,
+            verifier_signatures: [0; MAX_EXTRA_VERIFIERS],
             total_valid: 0,
         }
     }
@@ -0,0 +1,642 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    BPS_DENOMINATOR, MAX_BLOCKLIST_ENTRIES, MAX_ON_CHAIN_DATA_SIZE, MAX_RESCUE_REASON_LEN,
+    MAX_SENDER_SIZE,
+};
+use crate::errors::GatewayError;
+
+/// Token bridge contract registered for a remote chain
+/// Inbound token messages are only honoured when sent by this address, and
+/// outbound deposits are addressed to it
+#[account]
+pub struct BridgePeer {
+    /// Remote chain identifier
+    pub chain_id: u64,
+    
+    /// Token bridge address on the remote chain (canonical form)
+    pub remote_bridge: Vec<u8>,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl BridgePeer {
+    pub const SIZE: usize = 8       // chain_id
+        + 4 + MAX_SENDER_SIZE       // remote_bridge
+        + 1;                        // bump
+}
+
+/// Registry entry mapping a foreign asset to its wrapped SPL mint on Solana
+/// The gateway PDA holds mint authority over every wrapped mint
+#[account]
+pub struct WrappedAsset {
+    /// Chain the asset is native to
+    pub source_chain_id: u64,
+    
+    /// Asset identifier on the source chain (EVM addresses are left-padded)
+    pub source_token: [u8; 32],
+    
+    /// Wrapped SPL mint (PDA of this program)
+    pub mint: Pubkey,
+    
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// Delisted by the authority: inbound transfers are refused, holders can still burn
+    pub delisted: bool,
+}
+
+impl WrappedAsset {
+    pub const SIZE: usize = 8       // source_chain_id
+        + 32                        // source_token
+        + 32                        // mint
+        + 1                         // bump
+        + 1;                        // delisted
+}
+
+/// Pending wrapped-asset listing proposed by the authority
+/// The wrapped mint can only be created after `execute_after`, giving observers time to
+/// spot and cancel a listing of a fake asset
+#[account]
+pub struct WrappedAssetListing {
+    /// Chain the asset is native to
+    pub source_chain_id: u64,
+    
+    /// Asset identifier on the source chain
+    pub source_token: [u8; 32],
+    
+    /// Decimals of the source asset
+    pub decimals: u8,
+    
+    /// Authority that proposed the listing
+    pub proposed_by: Pubkey,
+    
+    /// Unix timestamp of the proposal
+    pub proposed_at: i64,
+    
+    /// Earliest unix timestamp at which the mint can be created
+    pub execute_after: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl WrappedAssetListing {
+    pub const SIZE: usize = 8       // source_chain_id
+        + 32                        // source_token
+        + 1                         // decimals
+        + 32                        // proposed_by
+        + 8                         // proposed_at
+        + 8                         // execute_after
+        + 1;                        // bump
+    
+    /// Whether the timelock has elapsed
+    pub fn is_executable(&self, now: i64) -> bool {
+        now >= self.execute_after
+    }
+}
+
+/// Verified token message whose payload version this program cannot decode
+/// Parking it consumes the TxId PDA instead of leaving the message stuck, and keeps the
+/// raw payload for a program upgrade that understands the version
+#[account]
+pub struct DeadLetter {
+    /// Source chain of the message
+    pub source_chain_id: u64,
+    
+    /// Transaction identifier of the message
+    pub tx_id: u128,
+    
+    /// Unsupported payload version
+    pub payload_version: u8,
+    
+    /// Raw `on_chain_data` of the message
+    pub on_chain_data: Vec<u8>,
+    
+    /// Relayer that parked the message
+    pub relayer: Pubkey,
+    
+    /// Unix timestamp the message was parked
+    pub dead_lettered_at: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl DeadLetter {
+    pub const SIZE: usize = 8       // source_chain_id
+        + 16                        // tx_id
+        + 1                         // payload_version
+        + 4 + MAX_ON_CHAIN_DATA_SIZE // on_chain_data
+        + 32                        // relayer
+        + 8                         // dead_lettered_at
+        + 1;                        // bump
+}
+
+/// Inbound token transfer that could not be delivered (mint paused or recipient token
+/// account missing), parked instead of leaving the message stuck
+/// The recipient claims it later, or the source chain's bridge sends it back to the sender
+#[account]
+pub struct TokenClaim {
+    /// Source chain of the transfer
+    pub source_chain_id: u64,
+    
+    /// Transaction identifier of the transfer
+    pub tx_id: u128,
+    
+    /// Mint delivered on claim (escrowed or wrapped)
+    pub mint: Pubkey,
+    
+    /// Whether the claim releases from escrow or mints
+    pub kind: TokenKind,
+    
+    /// Intended Solana beneficiary
+    pub recipient: Pubkey,
+    
+    /// Original sender on the source chain, refunded on return
+    pub sender: Vec<u8>,
+    
+    /// Token identifier as carried in the payload
+    pub token: [u8; 32],
+    
+    /// Amount in the mint's base units
+    pub amount: u64,
+    
+    /// Payload amount, in bridge decimals
+    pub wire_amount: u64,
+    
+    /// Relayer that paid for the claim account and receives its rent back
+    pub relayer: Pubkey,
+    
+    /// Unix timestamp the transfer was parked
+    pub parked_at: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl TokenClaim {
+    pub const SIZE: usize = 8       // source_chain_id
+        + 16                        // tx_id
+        + 32                        // mint
+        + 1                         // kind
+        + 32                        // recipient
+        + 4 + MAX_SENDER_SIZE       // sender
+        + 32                        // token
+        + 8                         // amount
+        + 8                         // wire_amount
+        + 32                        // relayer
+        + 8                         // parked_at
+        + 1;                        // bump
+}
+
+/// Pending emergency withdrawal from a token escrow
+/// Executable only after `execute_after`, giving observers time to react
+#[account]
+pub struct RescueRequest {
+    /// Escrowed mint being rescued
+    pub mint: Pubkey,
+    
+    /// Token account receiving the rescued tokens
+    pub destination: Pubkey,
+    
+    /// Amount to move out of escrow
+    pub amount: u64,
+    
+    /// Operator-supplied justification, echoed in events
+    pub reason: String,
+    
+    /// Unix timestamp of the request
+    pub requested_at: i64,
+    
+    /// Earliest unix timestamp at which the rescue can execute
+    pub execute_after: i64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl RescueRequest {
+    pub const SIZE: usize = 32      // mint
+        + 32                        // destination
+        + 8                         // amount
+        + 4 + MAX_RESCUE_REASON_LEN // reason
+        + 8                         // requested_at
+        + 8                         // execute_after
+        + 1;                        // bump
+    
+    /// Whether the timelock has elapsed
+    pub fn is_executable(&self, now: i64) -> bool {
+        now >= self.execute_after
+    }
+}
+
+/// Authority-set volume cap for a mint on the inbound (release and mint) paths
+/// Volume is tracked over a rolling window approximated from the current and previous
+/// fixed windows, weighting the previous one by how much of it still overlaps
+#[account]
+pub struct TokenRateLimit {
+    /// Mint the limit applies to (escrowed or wrapped)
+    pub mint: Pubkey,
+    
+    /// Rolling window length in seconds
+    pub window_seconds: u64,
+    
+    /// Maximum volume per window in base units (0 = transfers paused)
+    pub max_amount: u64,
+    
+    /// Start of the current fixed window
+    pub window_start: i64,
+    
+    /// Volume bridged in the current fixed window
+    pub current_volume: u64,
+    
+    /// Volume bridged in the previous fixed window
+    pub previous_volume: u64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl TokenRateLimit {
+    pub const SIZE: usize = 32      // mint
+        + 8                         // window_seconds
+        + 8                         // max_amount
+        + 8                         // window_start
+        + 8                         // current_volume
+        + 8                         // previous_volume
+        + 1;                        // bump
+    
+    /// Roll the fixed windows forward to `now`
+    fn advance(&mut self, now: i64) {
+        let window = self.window_seconds as i64;
+        let elapsed = now.saturating_sub(self.window_start);
+        if elapsed < window {
+            return;
+        }
+        
+        self.previous_volume = if elapsed < 2 * window { self.current_volume } else { 0 };
+        self.current_volume = 0;
+        self.window_start = now - elapsed % window;
+    }
+    
+    /// Volume counted against the cap at `now`
+    pub fn rolling_volume(&self, now: i64) -> u64 {
+        let window = self.window_seconds as u128;
+        let elapsed = now.saturating_sub(self.window_start).clamp(0, self.window_seconds as i64) as u128;
+        let previous_weight = window - elapsed;
+        let previous = (self.previous_volume as u128 * previous_weight / window) as u64;
+        previous.saturating_add(self.current_volume)
+    }
+    
+    /// Record `amount` of volume, failing if it would exceed the cap
+    pub fn consume(&mut self, amount: u64, now: i64) -> Result<()> {
+        self.advance(now);
+        
+        let volume = self
+            .rolling_volume(now)
+            .checked_add(amount)
+            .ok_or(GatewayError::RateLimitExceeded)?;
+        require!(volume <= self.max_amount, GatewayError::RateLimitExceeded);
+        
+        self.current_volume = self.current_volume.saturating_add(amount);
+        Ok(())
+    }
+}
+
+/// Compliance blocklist for token operations, managed by the authority
+/// Entries are wallets or mints: a listed wallet cannot deposit, burn, receive or claim,
+/// and a listed mint cannot be bridged in either direction
+#[account]
+pub struct Blocklist {
+    /// Blocked wallets and mints
+    pub entries: Vec<Pubkey>,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Blocklist {
+    pub const SIZE: usize = 4 + (32 * MAX_BLOCKLIST_ENTRIES) // entries
+        + 1;                        // bump
+    
+    pub fn is_blocked(&self, key: &Pubkey) -> bool {
+        self.entries.contains(key)
+    }
+    
+    /// Add `key` to the list
+    pub fn block(&mut self, key: Pubkey) -> Result<()> {
+        require!(!self.is_blocked(&key), GatewayError::InvalidBlocklistEntry);
+        require!(
+            self.entries.len() < MAX_BLOCKLIST_ENTRIES,
+            GatewayError::BlocklistFull
+        );
+        self.entries.push(key);
+        Ok(())
+    }
+    
+    /// Remove `key` from the list
+    pub fn unblock(&mut self, key: &Pubkey) -> Result<()> {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry == key)
+            .ok_or(GatewayError::InvalidBlocklistEntry)?;
+        self.entries.swap_remove(index);
+        Ok(())
+    }
+}
+
+/// Whether a mint is escrowed (Solana-native), minted by the gateway (wrapped foreign asset),
+/// burned and minted natively by its issuer or held by a project's custody extension
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenKind {
+    /// Solana-native mint bridged by lock/unlock
+    Native,
+    /// Wrapped foreign asset bridged by mint/burn; the gateway PDA is its mint authority
+    Wrapped,
+    /// Issuer-native asset (e.g. a stablecoin) bridged by burn/mint through the issuer's
+    /// attestation program; the gateway never holds or mints it
+    IssuerBurnMint,
+    /// Locked and released by a project's own escrow/mint program (see CustodyExtension)
+    ProjectCustody,
+}
+
+/// Project-owned escrow/mint program the gateway CPIs into for a mint's token legs,
+/// replacing the built-in escrow
+/// Bound to the project signer registry whose authority attached it; inbound releases
+/// must pass that registry's signature layer
+#[account]
+pub struct CustodyExtension {
+    /// Mint whose custody is delegated
+    pub mint: Pubkey,
+    
+    /// Project signer registry that owns the extension
+    pub project_registry: Pubkey,
+    
+    /// Extension program implementing `custody_lock` and `custody_release`
+    pub program: Pubkey,
+    
+    /// PDA bump seed
+    pub bump: u8,
+    
+    /// Canonical bump of the mint's custody authority PDA
+    pub authority_bump: u8,
+}
+
+impl CustodyExtension {
+    pub const SIZE: usize = 32      // mint
+        + 32                        // project_registry
+        + 32                        // program
+        + 1                         // bump
+        + 1;                        // authority_bump
+}
+
+/// Operational settings of a TokenConfig, managed by the authority or the token's governance
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TokenSettings {
+    /// Block every bridge instruction for the mint
+    pub paused: bool,
+    
+    /// Smallest outbound transfer in base units
+    pub min_transfer: u64,
+    
+    /// Largest outbound transfer in base units (0 = no maximum)
+    pub max_transfer: u64,
+    
+    /// Bridge fee on the transferred amount, in basis points
+    pub fee_bps: u16,
+    
+    /// Smallest bridge fee in base units, applied when the bps fee is lower
+    pub min_fee: u64,
+    
+    /// Largest bridge fee in base units (0 = no maximum)
+    pub max_fee: u64,
+    
+    /// Charge the bridge fee on inbound releases and mints instead of outbound deposits
+    pub fee_on_release: bool,
+    
+    /// Decimals of the token's representation on remote chains
+    /// Payload amounts use the smaller of these and the mint's decimals
+    pub remote_decimals: u8,
+    
+    /// Rebasing/elastic-supply token: deposits are blocked because escrow balances
+    /// drift away from the amounts bridged
+    pub rebasing: bool,
+    
+    /// Claim-based delivery: inbound transfers are parked as claims the recipient signs
+    /// to redeem, instead of being pushed to a token account
+    pub claim_delivery: bool,
+}
+
+impl TokenSettings {
+    /// Check the settings are internally consistent
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.fee_bps as u64 <= BPS_DENOMINATOR,
+            GatewayError::InvalidTokenConfig
+        );
+        require!(
+            self.max_transfer == 0 || self.min_transfer <= self.max_transfer,
+            GatewayError::InvalidTokenConfig
+        );
+        require!(
+            self.max_fee == 0 || self.min_fee <= self.max_fee,
+            GatewayError::InvalidTokenConfig
+        );
+        Ok(())
+    }
+}
+
+/// Split of an outbound transfer into the part bridged and the part kept by the gateway
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OutboundAmounts {
+    /// Amount leaving Solana, in the mint's base units
+    pub bridged: u64,
+    
+    /// Bridge fee plus scaling dust, kept by the gateway
+    pub fee: u64,
+    
+    /// Payload amount, in bridge decimals
+    pub wire: u64,
+}
+
+/// Per-mint configuration consulted by every token bridge instruction, plus escrow
+/// accounting for native mints
+/// A mint must be configured before it can be bridged in either direction
+#[account]
+pub struct TokenConfig {
+    /// Configured mint (escrowed or wrapped)
+    pub mint: Pubkey,
+    
+    /// Native (lock/unlock), wrapped (mint/burn), issuer (burn/mint) or project custody
+    /// classification
+    pub kind: TokenKind,
+    
+    /// Issuer attestation program executing burns and mints for `IssuerBurnMint` mints
+    /// (default pubkey = not issuer-routed)
+    pub issuer_program: Pubkey,
+    
+    /// Token-specific governance allowed to update the settings alongside the
+    /// gateway authority (default pubkey = authority only)
+    pub governance: Pubkey,
+    
+    /// Pause flag, transfer bounds, fee and decimal mapping
+    pub settings: TokenSettings,
+    
+    /// Amount currently owed to remote chains, measured as the escrow balance deltas
+    /// of deposits (net of bridge fees) minus releases
+    pub escrowed_amount: u64,
+    
+    /// Total bridge fees paid to the treasury in the token
+    pub fees_accrued: u64,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl TokenConfig {
+    pub const SIZE: usize = 32      // mint
+        + 1                         // kind
+        + 32                        // issuer_program
+        + 32                        // governance
+        + 1                         // settings.paused
+        + 8                         // settings.min_transfer
+        + 8                         // settings.max_transfer
+        + 2                         // settings.fee_bps
+        + 8                         // settings.min_fee
+        + 8                         // settings.max_fee
+        + 1                         // settings.fee_on_release
+        + 1                         // settings.remote_decimals
+        + 1                         // settings.rebasing
+        + 1                         // settings.claim_delivery
+        + 8                         // escrowed_amount
+        + 8                         // fees_accrued
+        + 1;                        // bump
+    
+    /// Whether `key` may update the settings
+    pub fn can_manage(&self, key: &Pubkey, authority: &Pubkey) -> bool {
+        key == authority || (self.governance != Pubkey::default() && *key == self.governance)
+    }
+    
+    /// Check an outbound transfer of `amount` against the mint's classification,
+    /// pause flag and transfer bounds
+    pub fn check_outbound(&self, kind: TokenKind, amount: u64) -> Result<()> {
+        self.check_path(kind)?;
+        require!(amount > 0, GatewayError::InvalidAmount);
+        require!(
+            amount >= self.settings.min_transfer,
+            GatewayError::TransferBelowMinimum
+        );
+        require!(
+            self.settings.max_transfer == 0 || amount <= self.settings.max_transfer,
+            GatewayError::TransferAboveMaximum
+        );
+        Ok(())
+    }
+    
+    /// Check the mint is bridged by the path of `kind` and is not paused
+    /// Inbound transfers only take this check: the tokens already left the source chain
+    pub fn check_path(&self, kind: TokenKind) -> Result<()> {
+        require!(self.kind == kind, GatewayError::TokenKindMismatch);
+        require!(!self.settings.paused, GatewayError::TokenPaused);
+        Ok(())
+    }
+    
+    /// Bridge fee on a transfer of `amount`: the bps fee bounded by the absolute minimum
+    /// and maximum, never more than the amount itself
+    pub fn bridge_fee(&self, amount: u64) -> u64 {
+        let settings = &self.settings;
+        let mut fee = (amount as u128 * settings.fee_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        fee = fee.max(settings.min_fee);
+        if settings.max_fee > 0 {
+            fee = fee.min(settings.max_fee);
+        }
+        fee.min(amount)
+    }
+    
+    /// Bridge fee withheld from an outbound transfer of `amount`
+    pub fn outbound_fee(&self, amount: u64) -> u64 {
+        if self.settings.fee_on_release { 0 } else { self.bridge_fee(amount) }
+    }
+    
+    /// Bridge fee withheld from an inbound release or mint of `amount`
+    pub fn release_fee(&self, amount: u64) -> u64 {
+        if self.settings.fee_on_release { self.bridge_fee(amount) } else { 0 }
+    }
+    
+    /// Factor between the mint's base units and payload amounts
+    /// Payload amounts use min(local, remote) decimals, so inbound amounts only ever scale up
+    /// and never need rounding on Solana
+    pub fn decimal_scale(&self, local_decimals: u8) -> Result<u64> {
+        let shift = local_decimals.saturating_sub(self.settings.remote_decimals);
+        10u64
+            .checked_pow(shift as u32)
+            .ok_or_else(|| error!(GatewayError::InvalidTokenConfig))
+    }
+    
+    /// Drop the low digits of `amount` that payload amounts cannot represent
+    pub fn truncate_dust(&self, amount: u64, local_decimals: u8) -> Result<u64> {
+        let scale = self.decimal_scale(local_decimals)?;
+        Ok(amount - amount % scale)
+    }
+    
+    /// Convert a local amount to a payload amount; the amount must carry no dust
+    pub fn to_wire_amount(&self, amount: u64, local_decimals: u8) -> Result<u64> {
+        let scale = self.decimal_scale(local_decimals)?;
+        require!(amount % scale == 0, GatewayError::AmountNotRepresentable);
+        Ok(amount / scale)
+    }
+    
+    /// Convert an inbound payload amount to the mint's base units
+    pub fn from_wire_amount(&self, wire: u64, local_decimals: u8) -> Result<u64> {
+        wire.checked_mul(self.decimal_scale(local_decimals)?)
+            .ok_or_else(|| error!(GatewayError::AmountNotRepresentable))
+    }
+    
+    /// Split an outbound `amount` into the bridged part and the fee, with any dust left
+    /// after scaling added to the fee
+    pub fn outbound_amounts(&self, amount: u64, local_decimals: u8) -> Result<OutboundAmounts> {
+        let after_fee = amount - self.outbound_fee(amount);
+        let bridged = self.truncate_dust(after_fee, local_decimals)?;
+        let wire = self.to_wire_amount(bridged, local_decimals)?;
+        require!(wire > 0, GatewayError::InvalidAmount);
+        
+        Ok(OutboundAmounts {
+            bridged,
+            fee: amount - bridged,
+            wire,
+        })
+    }
+    
+    /// Record a withheld bridge fee
+    pub fn record_fee(&mut self, fee: u64) -> Result<()> {
+        self.fees_accrued = self
+            .fees_accrued
+            .checked_add(fee)
+            .ok_or(GatewayError::EscrowAccountingMismatch)?;
+        Ok(())
+    }
+    
+    /// Record tokens received into escrow: the bridged part becomes a liability and the
+    /// fee is paid on to the treasury
+    pub fn record_deposit(&mut self, amounts: &OutboundAmounts) -> Result<()> {
+        require!(!self.settings.rebasing, GatewayError::RebasingTokenBlocked);
+        self.record_fee(amounts.fee)?;
+        self.escrowed_amount = self
+            .escrowed_amount
+            .checked_add(amounts.bridged)
+            .ok_or(GatewayError::EscrowAccountingMismatch)?;
+        Ok(())
+    }
+    
+    /// Record tokens leaving escrow; releases can never exceed what was locked
+    pub fn record_release(&mut self, amount: u64) -> Result<()> {
+        self.escrowed_amount = self
+            .escrowed_amount
+            .checked_sub(amount)
+            .ok_or(GatewayError::EscrowAccountingMismatch)?;
+        Ok(())
+    }
+}
@@ -1,17 +1,86 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{MAX_SIGNATURES_PER_MESSAGE, TX_ID_PDA_TTL_SECONDS};
+
 /// TxId PDA for two-transaction replay protection
-/// Created in TX1, closed in TX2 (rent reclaimed)
+/// Created in TX1, closed in TX2 (rent reclaimed), or by gc_batch once expired
+/// The tx_id is not stored: the seeds (source_chain_id, tx_id) already bind it, and every
+/// TX2 re-derives the address from the envelope
 #[account]
 pub struct TxIdPDA {
-    /// Transaction ID from source chain
-    pub tx_id: u128,
+    /// Signing hash whose signatures TX1 verified
+    pub message_hash: [u8; 32],
+    
+    /// Digest of the envelope without `on_chain_data`, letting process_message_by_hash
+    /// accept the envelope without re-sending its payload
+    pub envelope_digest: [u8; 32],
+    
+    /// Per signature, how many instructions before create_tx_pda TX1 found its Ed25519
+    /// precompile (0 = none); TX2 checks the same position before scanning
+    pub ed25519_offsets: [u8; MAX_SIGNATURES_PER_MESSAGE],
+    
+    /// Account the rent goes back to when the PDA closes, as `rent_refund_kind` says
+    pub rent_refund: Pubkey,
+    
+    /// Whether `rent_refund` is the relayer that sent TX1, the relayer pool that advanced the
+    /// rent, or a wallet that funded it in place of the relayer
+    pub rent_refund_kind: RentRefundKind,
+    
+    /// Unix timestamp after which TX2 can no longer be expected and gc_batch may close the PDA
+    pub expires_at: i64,
     
     /// PDA bump seed
     pub bump: u8,
 }
 
+/// Who paid a TxId PDA's rent, and so who closing it refunds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RentRefundKind {
+    /// The relayer that sent TX1 paid it
+    Relayer,
+    /// The relayer's pool advanced it; closing the PDA credits the pool
+    Pool,
+    /// A separate wallet funded it; closing the PDA refunds that wallet
+    Payer,
+}
+
 impl TxIdPDA {
-    pub const SIZE: usize = 16  // tx_id (u128)
+    pub const SIZE: usize = 32  // message_hash
+        + 32                    // envelope_digest
+        + MAX_SIGNATURES_PER_MESSAGE // ed25519_offsets
+        + 32                    // rent_refund
+        + 1                     // rent_refund_kind
+        + 8                     // expires_at
         + 1;                    // bump
+    
+    /// Expiry of a TxId PDA created at `now` for a message with `deadline` (0 = none):
+    /// TX1 plus TX_ID_PDA_TTL_SECONDS, or the deadline if that comes first
+    pub fn expiry(now: i64, deadline: i64) -> i64 {
+        let ttl_expiry = now.saturating_add(TX_ID_PDA_TTL_SECONDS);
+        if deadline == 0 {
+            ttl_expiry
+        } else {
+            ttl_expiry.min(deadline)
+        }
+    }
+    
+    /// Whether TX1 already recorded this PDA (a fresh account holds no message hash)
+    pub fn is_recorded(&self) -> bool {
+        self.message_hash != [0u8; 32]
+    }
+    
+    /// Relayer pool that advanced the rent, if any
+    pub fn rent_pool(&self) -> Option<Pubkey> {
+        (self.rent_refund_kind == RentRefundKind::Pool).then_some(self.rent_refund)
+    }
+    
+    /// Wallet that funded the rent in place of the relayer, if any
+    pub fn rent_payer(&self) -> Option<Pubkey> {
+        (self.rent_refund_kind == RentRefundKind::Payer).then_some(self.rent_refund)
+    }
+    
+    /// Whether the PDA may be garbage-collected at `now`
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
 }
\ No newline at end of file
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::GatewayError;
+use crate::state::TokenKind;
+
+/// Cross-chain token grant delivered by a `PAYLOAD_KIND_TOKEN_VESTING` message
+/// Tokens unlock in equal chunks every `period_seconds` after the cliff and are claimed by
+/// the recipient; native tokens stay in escrow as a liability until claimed, wrapped tokens
+/// are minted on claim
+#[account]
+pub struct VestingSchedule {
+    /// Source chain of the grant
+    pub source_chain_id: u64,
+    
+    /// Transaction identifier of the grant
+    pub tx_id: u128,
+    
+    /// Mint delivered on claim (escrowed or wrapped)
+    pub mint: Pubkey,
+    
+    /// Whether claims release from escrow or mint
+    pub kind: TokenKind,
+    
+    /// Solana beneficiary
+    pub recipient: Pubkey,
+    
+    /// Total granted amount in the mint's base units
+    pub amount: u64,
+    
+    /// Amount claimed so far
+    pub claimed: u64,
+    
+    /// Unix timestamp vesting starts from
+    pub start_time: i64,
+    
+    /// Seconds after `start_time` before anything unlocks
+    pub cliff_seconds: u64,
+    
+    /// Seconds after `start_time` until the whole grant is unlocked
+    pub duration_seconds: u64,
+    
+    /// Unlock step in seconds
+    pub period_seconds: u64,
+    
+    /// Relayer that paid for the schedule account and receives its rent back
+    pub relayer: Pubkey,
+    
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl VestingSchedule {
+    pub const SIZE: usize = 8       // source_chain_id
+        + 16                        // tx_id
+        + 32                        // mint
+        + 1                         // kind
+        + 32                        // recipient
+        + 8                         // amount
+        + 8                         // claimed
+        + 8                         // start_time
+        + 8                         // cliff_seconds
+        + 8                         // duration_seconds
+        + 8                         // period_seconds
+        + 32                        // relayer
+        + 1;                        // bump
+    
+    /// Amount unlocked at `now`, counting whole periods only
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if now <= self.start_time {
+            return 0;
+        }
+        
+        let elapsed = now.abs_diff(self.start_time);
+        if elapsed < self.cliff_seconds {
+            return 0;
+        }
+        if elapsed >= self.duration_seconds || self.period_seconds == 0 {
+            return self.amount;
+        }
+        
+        let unlocked_seconds = elapsed - elapsed % self.period_seconds;
+        (self.amount as u128 * unlocked_seconds as u128 / self.duration_seconds as u128) as u64
+    }
+    
+    /// Vested amount not yet claimed
+    pub fn claimable(&self, now: i64) -> u64 {
+        self.vested_amount(now).saturating_sub(self.claimed)
+    }
+    
+    /// Record a claim of everything vested at `now`, returning the amount
+    pub fn claim(&mut self, now: i64) -> Result<u64> {
+        let amount = self.claimable(now);
+        require!(amount > 0, GatewayError::NothingVested);
+        self.claimed += amount;
+        Ok(amount)
+    }
+    
+    /// Whether the whole grant has been claimed
+    pub fn is_complete(&self) -> bool {
+        self.claimed >= self.amount
+    }
+}
\ No newline at end of file
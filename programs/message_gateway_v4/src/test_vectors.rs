@@ -0,0 +1,198 @@
+//! Regression snapshots of the hash pipeline (inputs -> hashes)
+//!
+//! Every vector uses keccak256 and the deployed program ID for domain separation.
+//! The big-endian vectors follow the layout of the EVM gateway's
+//! `abi.encodePacked(uint8 version, uint128 txId, uint64 sourceChainId, uint64 destChainId,
+//! uint32(sender.length), sender, uint32(recipient.length), recipient,
+//! uint32(onChainData.length), onChainData, keccak256(offChainData),
+//! uint8 messageType, int64 deadline, uint64 sequence, uint8 protocolVersion,
+//! uint32(daPointer.length), daPointer)`.
+//! The expected hashes were recorded from this program's own encoder, so they catch
+//! regressions but do not prove parity with the Solidity contracts; check a counterpart
+//! against its own implementation, or submit an envelope to the `verify_hash` instruction
+//! to compare on-chain.
+
+use crate::constants::PROTOCOL_VERSION;
+use crate::state::{HashAlgorithm, HashScheme, IntegerEncoding, MessageEnvelope};
+
+/// A single snapshot; hashes are lowercase hex
+pub struct HashVector {
+    pub name: &'static str,
+    pub integer_encoding: IntegerEncoding,
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub dest_chain_id: u64,
+    pub sender: &'static [u8],
+    pub recipient: &'static [u8],
+    pub on_chain_data: &'static [u8],
+    pub off_chain_data: &'static [u8],
+    pub message_type: u8,
+    pub deadline: i64,
+    pub sequence: u64,
+    pub protocol_version: u8,
+    pub da_pointer: &'static [u8],
+    pub preimage: &'static str,
+    pub cross_chain_hash: &'static str,
+    pub signing_hash: &'static str,
+}
+
+impl HashVector {
+    /// Envelope the vector was generated from
+    pub fn envelope(&self) -> MessageEnvelope {
+        MessageEnvelope {
+            version: MessageEnvelope::CURRENT_VERSION,
+            tx_id: self.tx_id,
+            source_chain_id: self.source_chain_id,
+            dest_chain_id: self.dest_chain_id,
+            sender: self.sender.to_vec(),
+            recipient: self.recipient.to_vec(),
+            on_chain_data: self.on_chain_data.to_vec(),
+            off_chain_data: self.off_chain_data.to_vec(),
+            off_chain_data_hash: [0u8; 32],
+            message_type: self.message_type,
+            deadline: self.deadline,
+            sequence: self.sequence,
+            protocol_version: self.protocol_version,
+            da_pointer: self.da_pointer.to_vec(),
+        }
+    }
+    
+    /// Hash scheme the vector was generated with
+    pub fn hash_scheme(&self) -> HashScheme {
+        HashScheme {
+            algorithm: HashAlgorithm::Keccak256,
+            integer_encoding: self.integer_encoding,
+        }
+    }
+}
+
+pub const HASH_VECTORS: &[HashVector] = &[
+    HashVector {
+        name: "minimal_le",
+        integer_encoding: IntegerEncoding::LittleEndian,
+        tx_id: 1,
+        source_chain_id: 2,
+        dest_chain_id: 1,
+        sender: &[0x11; 20],
+        recipient: &[0x22; 32],
+        on_chain_data: b"hello",
+        off_chain_data: &[],
+        message_type: 0,
+        deadline: 0,
+        sequence: 0,
+        protocol_version: PROTOCOL_VERSION,
+        da_pointer: &[],
+        preimage: "0101000000000000000000000000000000020000000000000001000000000000\
+                   0014000000111111111111111111111111111111111111111120000000222222\
+                   2222222222222222222222222222222222222222222222222222222222050000\
+                   0068656c6c6fc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfa\
+                   d8045d85a47000000000000000000000000000000000000400000000",
+        cross_chain_hash: "4b5e983226ee3ec6d7a4c84a977639d89b6fe0e958ec68b25115d7dac57f509d",
+        signing_hash: "42079f0c83d86c8a69372e05c3b8a34dafc3002538e27a87d8e47bee8c5a11fe",
+    },
+    HashVector {
+        name: "minimal_be",
+        integer_encoding: IntegerEncoding::BigEndian,
+        tx_id: 1,
+        source_chain_id: 2,
+        dest_chain_id: 1,
+        sender: &[0x11; 20],
+        recipient: &[0x22; 32],
+        on_chain_data: b"hello",
+        off_chain_data: &[],
+        message_type: 0,
+        deadline: 0,
+        sequence: 0,
+        protocol_version: PROTOCOL_VERSION,
+        da_pointer: &[],
+        preimage: "0100000000000000000000000000000001000000000000000200000000000000\
+                   0100000014111111111111111111111111111111111111111100000020222222\
+                   2222222222222222222222222222222222222222222222222222222222000000\
+                   0568656c6c6fc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfa\
+                   d8045d85a47000000000000000000000000000000000000400000000",
+        cross_chain_hash: "d2e491b7fa2c3125203c27163cdced6407a7390e4271c4b4dae939a7baadd75d",
+        signing_hash: "ae5c2e6fdc3dc3fd441ce365ec88d89f61269cf6950deba2af1fe42967b48094",
+    },
+    HashVector {
+        name: "all_fields_le",
+        integer_encoding: IntegerEncoding::LittleEndian,
+        tx_id: 0x0102030405060708090a0b0c0d0e0f10,
+        source_chain_id: 137,
+        dest_chain_id: 900,
+        sender: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b,
+            0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13,
+        ],
+        recipient: &[
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2a, 0x2b,
+            0x2c, 0x2d, 0x2e, 0x2f, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+        ],
+        on_chain_data: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b,
+            0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20, 0x21, 0x22, 0x23,
+            0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x3b,
+            0x3c, 0x3d, 0x3e, 0x3f,
+        ],
+        off_chain_data: &[
+            0xaa, 0xbb, 0xcc,
+        ],
+        message_type: 3,
+        deadline: 1_700_000_000,
+        sequence: 7,
+        protocol_version: PROTOCOL_VERSION,
+        da_pointer: b"ar://tx",
+        preimage: "01100f0e0d0c0b0a090807060504030201890000000000000084030000000000\
+                   0014000000000102030405060708090a0b0c0d0e0f1011121320000000202122\
+                   232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f400000\
+                   00000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e\
+                   1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e\
+                   3fccad3f5300e77cf5347e3c6200a08bd8cf71f94a0b347bcb39486b17b88a8a\
+                   710300f15365000000000700000000000000040700000061723a2f2f7478",
+        cross_chain_hash: "a3629a6b70463b3582b3f768c4a6a3f7fb63e05faf344215de55e869c03acbe0",
+        signing_hash: "18014b4998a3f90a64dafef077460910e33f4e0510bb6cedf5c2242053f067c5",
+    },
+    HashVector {
+        name: "all_fields_be",
+        integer_encoding: IntegerEncoding::BigEndian,
+        tx_id: 0x0102030405060708090a0b0c0d0e0f10,
+        source_chain_id: 137,
+        dest_chain_id: 900,
+        sender: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b,
+            0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13,
+        ],
+        recipient: &[
+            0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2a, 0x2b,
+            0x2c, 0x2d, 0x2e, 0x2f, 0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37,
+            0x38, 0x39, 0x3a, 0x3b, 0x3c, 0x3d, 0x3e, 0x3f,
+        ],
+        on_chain_data: &[
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b,
+            0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b, 0x1c, 0x1d, 0x1e, 0x1f, 0x20, 0x21, 0x22, 0x23,
+            0x24, 0x25, 0x26, 0x27, 0x28, 0x29, 0x2a, 0x2b, 0x2c, 0x2d, 0x2e, 0x2f,
+            0x30, 0x31, 0x32, 0x33, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x3b,
+            0x3c, 0x3d, 0x3e, 0x3f,
+        ],
+        off_chain_data: &[
+            0xaa, 0xbb, 0xcc,
+        ],
+        message_type: 3,
+        deadline: 1_700_000_000,
+        sequence: 7,
+        protocol_version: PROTOCOL_VERSION,
+        da_pointer: b"ar://tx",
+        preimage: "010102030405060708090a0b0c0d0e0f10000000000000008900000000000003\
+                   8400000014000102030405060708090a0b0c0d0e0f1011121300000020202122\
+                   232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f000000\
+                   40000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e\
+                   1f202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e\
+                   3fccad3f5300e77cf5347e3c6200a08bd8cf71f94a0b347bcb39486b17b88a8a\
+                   7103000000006553f1000000000000000007040000000761723a2f2f7478",
+        cross_chain_hash: "74c1f98baca459990916bd4b1abe80266726870c24b5cd4ae7b2cd290b554900",
+        signing_hash: "3540097286d474dd765b2d539eddbd2d23679c0a4751050f84a2601782d2c3d6",
+    },
+];
\ No newline at end of file
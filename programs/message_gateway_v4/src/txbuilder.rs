@@ -0,0 +1,403 @@
+//! Off-chain assembly of TX1/TX2 for relayers (feature `txbuilder`)
+//!
+//! Builds the Ed25519 precompile instructions in the layout the gateway parses, the
+//! compute-budget instructions, the gateway instructions with their derived PDAs, and
+//! v0 messages that resolve accounts through address lookup tables
+//! The returned `VersionedMessage` is signed by the relayer with its own key management
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    address_lookup_table::AddressLookupTableAccount,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, CompileError, VersionedMessage},
+    sysvar,
+};
+use anchor_lang::{InstructionData, ToAccountMetas};
+
+use crate::constants::{
+    CHAIN_CONFIG_SEED, CHANNEL_SEED, COUNTER_SEED, DEFAULT_INSTANCE_ID, DELIVERY_AUTHORITY_SEED,
+    FEE_CONFIG_SEED, FEE_VAULT_SEED, GATEWAY_SEED, MESSAGE_HISTORY_SEED, SIGNER_REGISTRY_SEED, TX_SEED,
+};
+use crate::state::{
+    instance_seed, Channel, MessageEnvelope, MessageSignature, SignatureScheme, SignerRegistryType,
+};
+use crate::utils::bubblegum::{ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+use crate::utils::ed25519::ed25519_instruction;
+
+/// Compute budget program
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// ComputeBudgetInstruction tags
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// Compute budget requested by a relayer transaction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComputeBudget {
+    /// Compute unit limit (0 keeps the runtime default)
+    pub unit_limit: u32,
+    /// Priority fee in micro-lamports per compute unit (0 adds no priority fee)
+    pub unit_price_micro_lamports: u64,
+}
+
+impl ComputeBudget {
+    /// Compute-budget instructions to place at the start of the transaction
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = Vec::with_capacity(2);
+        if self.unit_limit != 0 {
+            let mut data = vec![SET_COMPUTE_UNIT_LIMIT];
+            data.extend_from_slice(&self.unit_limit.to_le_bytes());
+            instructions.push(Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, Vec::new()));
+        }
+        if self.unit_price_micro_lamports != 0 {
+            let mut data = vec![SET_COMPUTE_UNIT_PRICE];
+            data.extend_from_slice(&self.unit_price_micro_lamports.to_le_bytes());
+            instructions.push(Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, Vec::new()));
+        }
+        instructions
+    }
+}
+
+/// Ed25519 precompile instruction verifying one attestation over the message hash
+pub fn ed25519_verify_instruction(
+    signer: &Pubkey,
+    signature: &[u8; 64],
+    message_hash: &[u8; 32],
+) -> Instruction {
+    ed25519_instruction(&[(*signer, *signature)], message_hash)
+}
+
+/// One precompile instruction per Ed25519 attestation (secp256k1 attestations are
+/// verified in-program and need none)
+pub fn ed25519_verify_instructions(
+    signatures: &[MessageSignature],
+    message_hash: &[u8; 32],
+) -> Vec<Instruction> {
+    signatures
+        .iter()
+        .filter(|signature| signature.scheme == SignatureScheme::Ed25519)
+        .map(|signature| ed25519_verify_instruction(&signature.signer, &signature.signature, message_hash))
+        .collect()
+}
+
+/// Accounts of an inbound route that are not derived from the envelope alone
+#[derive(Clone, Debug, Default)]
+pub struct InboundRoute {
+    /// Chain id of the destination gateway
+    pub gateway_chain_id: u64,
+    /// Instance of the destination gateway (DEFAULT_INSTANCE_ID unless blue/green or canary)
+    pub instance_id: u16,
+    /// Project registry validating the message, if any
+    pub project_registry: Option<Pubkey>,
+    /// Extra verifier registries the project requires, in its configured order
+    pub extra_verifier_registries: Vec<Pubkey>,
+    /// Gateway's message history tree while history is enabled
+    pub message_history_tree: Option<Pubkey>,
+    /// Relayer pool that advanced the TxId PDA rent (TX1 sent with create_tx_pda_pooled)
+    pub relayer_pool: Option<Pubkey>,
+    /// Wallet that funded the TxId PDA rent in place of the relayer (TX1 sent with a payer)
+    pub rent_payer: Option<Pubkey>,
+    /// Whether to claim the source chain's relayer refund from the fee vault
+    /// (process_message and deliver_message; requires the chain's FeeConfig to exist)
+    pub claim_refund: bool,
+}
+
+fn find_address(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::ID).0
+}
+
+fn gateway_address(chain_id: u64, instance_id: u16) -> Pubkey {
+    find_address(&[GATEWAY_SEED, &chain_id.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+fn signer_registry_address(
+    registry_type: SignerRegistryType,
+    chain_id: u64,
+    instance_id: u16,
+) -> Pubkey {
+    find_address(&[
+        SIGNER_REGISTRY_SEED,
+        &registry_type.discriminant().expect("fixed registry discriminant").to_le_bytes(),
+        &chain_id.to_le_bytes(),
+        &instance_seed(instance_id),
+    ])
+}
+
+fn tx_id_address(envelope: &MessageEnvelope, instance_id: u16) -> Pubkey {
+    find_address(&[
+        TX_SEED,
+        &envelope.source_chain_id.to_le_bytes(),
+        &envelope.tx_id.to_le_bytes(),
+        &instance_seed(instance_id),
+    ])
+}
+
+/// ChainConfig PDA of the source chain, passed whether or not the chain has a config
+fn chain_config_address(source_chain_id: u64, instance_id: u16) -> Pubkey {
+    find_address(&[CHAIN_CONFIG_SEED, &source_chain_id.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+/// Channel of the envelope's route, when it carries a sequence
+fn channel_address(envelope: &MessageEnvelope, instance_id: u16) -> Option<Pubkey> {
+    envelope.uses_channel().then(|| {
+        find_address(&[
+            CHANNEL_SEED,
+            &envelope.source_chain_id.to_le_bytes(),
+            envelope.recipient.as_slice(),
+            &Channel::remote_app_seed(&envelope.sender),
+            &instance_seed(instance_id),
+        ])
+    })
+}
+
+/// Fee config of the source chain and the fee vault, when the route claims the relayer refund
+fn refund_addresses(route: &InboundRoute, envelope: &MessageEnvelope) -> (Option<Pubkey>, Option<Pubkey>) {
+    if !route.claim_refund {
+        return (None, None);
+    }
+    let instance_seed = instance_seed(route.instance_id);
+    (
+        Some(find_address(&[FEE_CONFIG_SEED, &envelope.source_chain_id.to_le_bytes(), &instance_seed])),
+        Some(find_address(&[FEE_VAULT_SEED, &instance_seed])),
+    )
+}
+
+/// remaining_accounts of a TX2: the extra verifier registries, then the message history
+/// accounts when the route records history
+/// App-specific TX2s (release_tokens, fulfill_query, ...) append their own accounts after these
+pub fn route_remaining_accounts(route: &InboundRoute) -> Vec<AccountMeta> {
+    let mut metas: Vec<AccountMeta> = route
+        .extra_verifier_registries
+        .iter()
+        .map(|registry| AccountMeta::new_readonly(*registry, false))
+        .collect();
+    if let Some(merkle_tree) = route.message_history_tree {
+        metas.extend([
+            AccountMeta::new(merkle_tree, false),
+            AccountMeta::new_readonly(find_address(&[MESSAGE_HISTORY_SEED, &instance_seed(route.instance_id)]), false),
+            AccountMeta::new_readonly(ACCOUNT_COMPRESSION_PROGRAM_ID, false),
+            AccountMeta::new_readonly(NOOP_PROGRAM_ID, false),
+        ]);
+    }
+    metas
+}
+
+/// TX1 gateway instruction (`create_tx_pda`)
+pub fn create_tx_pda_instruction(
+    relayer: &Pubkey,
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+) -> Instruction {
+    tx1_instruction(relayer, None, DEFAULT_INSTANCE_ID, envelope, signatures)
+}
+
+/// TX1 gateway instruction (`create_tx_pda`) for gateway instance `instance_id`; the
+/// signatures must cover that instance's signing hash
+pub fn create_tx_pda_instruction_for_instance(
+    relayer: &Pubkey,
+    instance_id: u16,
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+) -> Instruction {
+    tx1_instruction(relayer, None, instance_id, envelope, signatures)
+}
+
+/// TX1 gateway instruction with the TxId PDA rent funded by `payer`, which must sign TX1
+/// too (typically as its fee payer); TX2 then names it in `InboundRoute::rent_payer`
+pub fn create_tx_pda_instruction_with_payer(
+    relayer: &Pubkey,
+    payer: &Pubkey,
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+) -> Instruction {
+    tx1_instruction(relayer, Some(*payer), DEFAULT_INSTANCE_ID, envelope, signatures)
+}
+
+fn tx1_instruction(
+    relayer: &Pubkey,
+    payer: Option<Pubkey>,
+    instance_id: u16,
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+) -> Instruction {
+    let accounts = crate::accounts::CreateTxPda {
+        tx_id_pda: tx_id_address(envelope, instance_id),
+        gateway: gateway_address(envelope.dest_chain_id, instance_id),
+        counter_pda: find_address(&[
+            COUNTER_SEED,
+            &envelope.source_chain_id.to_le_bytes(),
+            &instance_seed(instance_id),
+        ]),
+        chain_config: chain_config_address(envelope.source_chain_id, instance_id),
+        payer,
+        relayer: *relayer,
+        instructions: sysvar::instructions::ID,
+        system_program: anchor_lang::system_program::ID,
+    };
+    let data = crate::instruction::CreateTxPda {
+        envelope: envelope.clone(),
+        signatures: signatures.to_vec(),
+    };
+    
+    Instruction::new_with_bytes(crate::ID, &data.data(), accounts.to_account_metas(None))
+}
+
+/// TX2 gateway instruction (`process_message`); the channel is derived from the
+/// envelope when it carries a sequence
+pub fn process_message_instruction(
+    relayer: &Pubkey,
+    route: &InboundRoute,
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+) -> Instruction {
+    let (fee_config, fee_vault) = refund_addresses(route, envelope);
+    let accounts = crate::accounts::ProcessMessage {
+        gateway: gateway_address(route.gateway_chain_id, route.instance_id),
+        tx_id_pda: tx_id_address(envelope, route.instance_id),
+        chain_config: chain_config_address(envelope.source_chain_id, route.instance_id),
+        via_registry: signer_registry_address(
+            SignerRegistryType::VIA,
+            envelope.dest_chain_id,
+            route.instance_id,
+        ),
+        chain_registry: signer_registry_address(
+            SignerRegistryType::Chain,
+            envelope.source_chain_id,
+            route.instance_id,
+        ),
+        channel: channel_address(envelope, route.instance_id),
+        project_registry: route.project_registry,
+        relayer_pool: route.relayer_pool,
+        payer: route.rent_payer,
+        fee_config,
+        fee_vault,
+        relayer: *relayer,
+        instructions: sysvar::instructions::ID,
+        system_program: anchor_lang::system_program::ID,
+    };
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(route_remaining_accounts(route));
+    let data = crate::instruction::ProcessMessage {
+        envelope: envelope.clone(),
+        signatures: signatures.to_vec(),
+    };
+    
+    Instruction::new_with_bytes(crate::ID, &data.data(), metas)
+}
+
+/// TX2 gateway instruction for a program delivery (`deliver_message`) into the program
+/// encoded in `envelope.recipient`; `recipient_accounts` are passed through to its
+/// `on_message_received` after the delivery authority
+pub fn deliver_message_instruction(
+    relayer: &Pubkey,
+    route: &InboundRoute,
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+    recipient_accounts: &[AccountMeta],
+) -> Instruction {
+    let recipient_program = Pubkey::try_from(envelope.recipient.as_slice()).unwrap_or_default();
+    let (fee_config, fee_vault) = refund_addresses(route, envelope);
+    let accounts = crate::accounts::DeliverMessage {
+        gateway: gateway_address(route.gateway_chain_id, route.instance_id),
+        tx_id_pda: tx_id_address(envelope, route.instance_id),
+        chain_config: chain_config_address(envelope.source_chain_id, route.instance_id),
+        via_registry: signer_registry_address(
+            SignerRegistryType::VIA,
+            envelope.dest_chain_id,
+            route.instance_id,
+        ),
+        chain_registry: signer_registry_address(
+            SignerRegistryType::Chain,
+            envelope.source_chain_id,
+            route.instance_id,
+        ),
+        channel: channel_address(envelope, route.instance_id),
+        project_registry: route.project_registry,
+        relayer_pool: route.relayer_pool,
+        payer: route.rent_payer,
+        fee_config,
+        fee_vault,
+        recipient_program,
+        delivery_authority: find_address(&[DELIVERY_AUTHORITY_SEED, recipient_program.as_ref()]),
+        relayer: *relayer,
+        instructions: sysvar::instructions::ID,
+        system_program: anchor_lang::system_program::ID,
+    };
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(route_remaining_accounts(route));
+    metas.extend_from_slice(recipient_accounts);
+    let data = crate::instruction::DeliverMessage {
+        envelope: envelope.clone(),
+        signatures: signatures.to_vec(),
+    };
+    
+    Instruction::new_with_bytes(crate::ID, &data.data(), metas)
+}
+
+/// Compile instructions into a v0 message, resolving accounts through the lookup tables
+pub fn compile_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> std::result::Result<VersionedMessage, CompileError> {
+    v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+        .map(VersionedMessage::V0)
+}
+
+/// Order shared by TX1 and TX2: compute budget, Ed25519 precompiles, gateway instruction
+/// (the gateway only searches instructions before its own for precompile matches)
+fn with_verification(
+    budget: &ComputeBudget,
+    signatures: &[MessageSignature],
+    message_hash: &[u8; 32],
+    gateway_instruction: Instruction,
+) -> Vec<Instruction> {
+    let mut instructions = budget.instructions();
+    instructions.extend(ed25519_verify_instructions(signatures, message_hash));
+    instructions.push(gateway_instruction);
+    instructions
+}
+
+/// Complete TX1 as a v0 message
+/// `message_hash` is the route's signing hash (`create_message_hash_for_signing`)
+pub fn build_tx1(
+    relayer: &Pubkey,
+    budget: &ComputeBudget,
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+    message_hash: &[u8; 32],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> std::result::Result<VersionedMessage, CompileError> {
+    let instructions = with_verification(
+        budget,
+        signatures,
+        message_hash,
+        create_tx_pda_instruction(relayer, envelope, signatures),
+    );
+    compile_message(relayer, &instructions, lookup_tables, recent_blockhash)
+}
+
+/// Complete TX2 as a v0 message
+#[allow(clippy::too_many_arguments)]
+pub fn build_tx2(
+    relayer: &Pubkey,
+    budget: &ComputeBudget,
+    route: &InboundRoute,
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+    message_hash: &[u8; 32],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> std::result::Result<VersionedMessage, CompileError> {
+    let instructions = with_verification(
+        budget,
+        signatures,
+        message_hash,
+        process_message_instruction(relayer, route, envelope, signatures),
+    );
+    compile_message(relayer, &instructions, lookup_tables, recent_blockhash)
+}
\ No newline at end of file
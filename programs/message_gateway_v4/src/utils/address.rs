@@ -0,0 +1,323 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_RECIPIENT_SIZE;
+use crate::errors::GatewayError;
+use crate::state::AddressFormat;
+
+/// EVM address length
+pub const EVM_ADDRESS_LEN: usize = 20;
+
+/// 32-byte account addresses (Solana/SVM, Sui, Aptos, Cosmos contracts)
+pub const WORD_ADDRESS_LEN: usize = 32;
+
+/// Maximum address string length accepted by the parsers
+pub const MAX_ADDRESS_STRING_LEN: usize = 128;
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32_GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+/// Validate a canonical remote address for its chain family
+pub fn validate_remote_address(format: AddressFormat, address: &[u8]) -> Result<()> {
+    require!(!address.is_empty(), GatewayError::InvalidRemoteAddress);
+    
+    let valid = match format {
+        AddressFormat::Raw => address.len() <= MAX_RECIPIENT_SIZE,
+        AddressFormat::Evm => address.len() == EVM_ADDRESS_LEN,
+        // Bech32 payload: 20-byte accounts or 32-byte contracts
+        AddressFormat::Cosmos => {
+            address.len() == EVM_ADDRESS_LEN || address.len() == WORD_ADDRESS_LEN
+        }
+        AddressFormat::Svm | AddressFormat::Move => address.len() == WORD_ADDRESS_LEN,
+    };
+    require!(valid, GatewayError::InvalidRemoteAddress);
+    
+    Ok(())
+}
+
+/// Deserialize a remote address into its canonical form
+/// Accepts the padded forms other gateways commonly emit: EVM addresses as
+/// left-padded 32-byte words, and short Move addresses (e.g. `0x1`) left-padded to 32 bytes
+pub fn decode_remote_address(format: AddressFormat, raw: &[u8]) -> Result<Vec<u8>> {
+    let canonical = match format {
+        AddressFormat::Evm if raw.len() == WORD_ADDRESS_LEN => {
+            let (padding, address) = raw.split_at(WORD_ADDRESS_LEN - EVM_ADDRESS_LEN);
+            require!(
+                padding.iter().all(|b| *b == 0),
+                GatewayError::InvalidRemoteAddress
+            );
+            address.to_vec()
+        }
+        AddressFormat::Move if !raw.is_empty() && raw.len() < WORD_ADDRESS_LEN => {
+            let mut address = vec![0u8; WORD_ADDRESS_LEN - raw.len()];
+            address.extend_from_slice(raw);
+            address
+        }
+        _ => raw.to_vec(),
+    };
+    
+    validate_remote_address(format, &canonical)?;
+    Ok(canonical)
+}
+
+/// Serialize a canonical remote address for the wire, optionally as a 32-byte word
+/// (the `bytes32` form used by EVM gateways)
+pub fn encode_remote_address(format: AddressFormat, address: &[u8], as_word: bool) -> Result<Vec<u8>> {
+    validate_remote_address(format, address)?;
+    
+    if as_word && address.len() < WORD_ADDRESS_LEN {
+        let mut word = vec![0u8; WORD_ADDRESS_LEN - address.len()];
+        word.extend_from_slice(address);
+        return Ok(word);
+    }
+    
+    Ok(address.to_vec())
+}
+
+/// Parse an address string in the chain family's native notation into canonical bytes
+/// Cosmos addresses must use `bech32_hrp` (e.g. "cosmos", "osmo")
+pub fn parse_address(format: AddressFormat, address: &str, bech32_hrp: &str) -> Result<Vec<u8>> {
+    let raw = match format {
+        AddressFormat::Evm | AddressFormat::Move => parse_hex(address)?,
+        AddressFormat::Svm => parse_base58(address)?,
+        AddressFormat::Cosmos => {
+            let (hrp, data) = parse_bech32(address)?;
+            require!(hrp == bech32_hrp, GatewayError::InvalidRemoteAddress);
+            data
+        }
+        AddressFormat::Raw => {
+            if address.starts_with("0x") {
+                parse_hex(address)?
+            } else {
+                parse_base58(address)?
+            }
+        }
+    };
+    
+    decode_remote_address(format, &raw)
+}
+
+/// Format canonical address bytes in the chain family's native notation
+pub fn format_address(format: AddressFormat, address: &[u8], bech32_hrp: &str) -> Result<String> {
+    validate_remote_address(format, address)?;
+    
+    match format {
+        AddressFormat::Evm | AddressFormat::Move | AddressFormat::Raw => Ok(format_hex(address)),
+        AddressFormat::Svm => Ok(format_base58(address)),
+        AddressFormat::Cosmos => format_bech32(bech32_hrp, address),
+    }
+}
+
+/// Parse a `0x`-prefixed hex string (either case, even length)
+pub fn parse_hex(value: &str) -> Result<Vec<u8>> {
+    let digits = value
+        .strip_prefix("0x")
+        .ok_or(GatewayError::InvalidRemoteAddress)?
+        .as_bytes();
+    require!(
+        digits.len() % 2 == 0 && digits.len() <= MAX_ADDRESS_STRING_LEN,
+        GatewayError::InvalidRemoteAddress
+    );
+    
+    digits
+        .chunks(2)
+        .map(|pair| Ok((hex_value(pair[0])? << 4) | hex_value(pair[1])?))
+        .collect()
+}
+
+/// Format bytes as a lowercase `0x`-prefixed hex string
+pub fn format_hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(2 + bytes.len() * 2);
+    out.push_str("0x");
+    for byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0x0f) as usize] as char);
+    }
+    out
+}
+
+fn hex_value(digit: u8) -> Result<u8> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => err!(GatewayError::InvalidRemoteAddress),
+    }
+}
+
+/// Parse a Bitcoin-alphabet base58 string
+pub fn parse_base58(value: &str) -> Result<Vec<u8>> {
+    require!(
+        !value.is_empty() && value.len() <= MAX_ADDRESS_STRING_LEN,
+        GatewayError::InvalidRemoteAddress
+    );
+    
+    // Big-endian base-256 accumulator
+    let mut bytes: Vec<u8> = Vec::with_capacity(value.len());
+    for c in value.bytes() {
+        let mut carry = BASE58_ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or(GatewayError::InvalidRemoteAddress)? as u32;
+        for byte in bytes.iter_mut().rev() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, carry as u8);
+            carry >>= 8;
+        }
+    }
+    
+    // Each leading '1' encodes a leading zero byte
+    let zeros = value.bytes().take_while(|&c| c == b'1').count();
+    let mut decoded = vec![0u8; zeros];
+    decoded.extend_from_slice(&bytes);
+    Ok(decoded)
+}
+
+/// Format bytes as a Bitcoin-alphabet base58 string
+pub fn format_base58(bytes: &[u8]) -> String {
+    // Little-endian base-58 digits
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    let mut out = String::with_capacity(zeros + digits.len());
+    out.extend(std::iter::repeat('1').take(zeros));
+    out.extend(digits.iter().rev().map(|&d| BASE58_ALPHABET[d as usize] as char));
+    out
+}
+
+/// Parse a BIP-173 bech32 string into its human-readable part and data bytes
+pub fn parse_bech32(value: &str) -> Result<(String, Vec<u8>)> {
+    require!(
+        value.len() <= MAX_ADDRESS_STRING_LEN,
+        GatewayError::InvalidRemoteAddress
+    );
+    
+    // Mixed case is invalid; the checksum is computed over the lowercase form
+    let has_lower = value.bytes().any(|c| c.is_ascii_lowercase());
+    let has_upper = value.bytes().any(|c| c.is_ascii_uppercase());
+    require!(!(has_lower && has_upper), GatewayError::InvalidRemoteAddress);
+    let value = value.to_ascii_lowercase();
+    
+    let separator = value.rfind('1').ok_or(GatewayError::InvalidRemoteAddress)?;
+    let (hrp, data) = (&value[..separator], &value[separator + 1..]);
+    require!(
+        !hrp.is_empty() && data.len() >= 6,
+        GatewayError::InvalidRemoteAddress
+    );
+    require!(
+        hrp.bytes().all(|c| (33..=126).contains(&c)),
+        GatewayError::InvalidRemoteAddress
+    );
+    
+    let values = data
+        .bytes()
+        .map(|c| {
+            BECH32_CHARSET
+                .iter()
+                .position(|&a| a == c)
+                .map(|v| v as u8)
+                .ok_or_else(|| GatewayError::InvalidRemoteAddress.into())
+        })
+        .collect::<Result<Vec<u8>>>()?;
+    
+    let mut checked = bech32_hrp_expand(hrp);
+    checked.extend_from_slice(&values);
+    require!(bech32_polymod(&checked) == 1, GatewayError::InvalidRemoteAddress);
+    
+    let payload = convert_bits(&values[..values.len() - 6], 5, 8, false)?;
+    Ok((hrp.to_string(), payload))
+}
+
+/// Format data bytes as a BIP-173 bech32 string with the given human-readable part
+pub fn format_bech32(hrp: &str, data: &[u8]) -> Result<String> {
+    require!(
+        !hrp.is_empty() && hrp.bytes().all(|c| (33..=126).contains(&c) && !c.is_ascii_uppercase()),
+        GatewayError::InvalidRemoteAddress
+    );
+    
+    let values = convert_bits(data, 8, 5, true)?;
+    let mut checked = bech32_hrp_expand(hrp);
+    checked.extend_from_slice(&values);
+    checked.extend_from_slice(&[0u8; 6]);
+    let polymod = bech32_polymod(&checked) ^ 1;
+    
+    let mut out = String::with_capacity(hrp.len() + 1 + values.len() + 6);
+    out.push_str(hrp);
+    out.push('1');
+    for v in values.iter() {
+        out.push(BECH32_CHARSET[*v as usize] as char);
+    }
+    for i in 0..6 {
+        out.push(BECH32_CHARSET[((polymod >> (5 * (5 - i))) & 31) as usize] as char);
+    }
+    Ok(out)
+}
+
+fn bech32_polymod(values: &[u8]) -> u32 {
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ v as u32;
+        for (i, generator) in BECH32_GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn bech32_hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut expanded: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+    expanded.push(0);
+    expanded.extend(hrp.bytes().map(|c| c & 31));
+    expanded
+}
+
+/// Regroup bits between 8-bit bytes and 5-bit bech32 values
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Result<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value = (1u32 << to) - 1;
+    let max_acc = (1u32 << (from + to - 1)) - 1;
+    let mut out = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    
+    for &value in data {
+        require!((value as u32) >> from == 0, GatewayError::InvalidRemoteAddress);
+        acc = ((acc << from) | value as u32) & max_acc;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            out.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+    
+    if pad {
+        if bits > 0 {
+            out.push(((acc << (to - bits)) & max_value) as u8);
+        }
+    } else {
+        require!(
+            bits < from && (acc << (to - bits)) & max_value == 0,
+            GatewayError::InvalidRemoteAddress
+        );
+    }
+    
+    Ok(out)
+}
\ No newline at end of file
@@ -0,0 +1,316 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    keccak,
+    program::invoke_signed,
+};
+
+use crate::errors::GatewayError;
+
+/// Metaplex Bubblegum program (compressed NFTs)
+pub const BUBBLEGUM_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY");
+
+/// SPL account-compression program owning Bubblegum's Merkle trees
+pub const ACCOUNT_COMPRESSION_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// SPL noop program Bubblegum logs leaf changes through
+pub const NOOP_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+/// Anchor discriminators of the Bubblegum instructions used: sha256("global:<name>")[..8]
+const TRANSFER_DISCRIMINATOR: [u8; 8] = [163, 52, 200, 231, 140, 3, 69, 186];
+const BURN_DISCRIMINATOR: [u8; 8] = [116, 110, 29, 56, 107, 219, 42, 93];
+const MINT_V1_DISCRIMINATOR: [u8; 8] = [145, 98, 192, 118, 184, 147, 118, 104];
+
+/// Offset of `num_minted` in Bubblegum's TreeConfig: after the discriminator,
+/// tree_creator (32), tree_delegate (32) and total_mint_capacity (u64)
+const TREE_CONFIG_NUM_MINTED_OFFSET: usize = 8 + 32 + 32 + 8;
+
+/// Bubblegum `TokenStandard`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TokenStandard {
+    NonFungible,
+    FungibleAsset,
+    Fungible,
+    NonFungibleEdition,
+}
+
+/// Bubblegum `Collection`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+/// Bubblegum `UseMethod`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum UseMethod {
+    Burn,
+    Multiple,
+    Single,
+}
+
+/// Bubblegum `Uses`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Uses {
+    pub use_method: UseMethod,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+/// Bubblegum `TokenProgramVersion`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub enum TokenProgramVersion {
+    Original,
+    Token2022,
+}
+
+/// Bubblegum `Creator`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+/// Bubblegum `MetadataArgs`: the metadata a compressed NFT's leaf commits to
+/// Mirrors Bubblegum's borsh layout so the leaf hashes can be recomputed here
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct MetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub primary_sale_happened: bool,
+    pub is_mutable: bool,
+    pub edition_nonce: Option<u8>,
+    pub token_standard: Option<TokenStandard>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+    pub token_program_version: TokenProgramVersion,
+    pub creators: Vec<Creator>,
+}
+
+impl MetadataArgs {
+    /// Leaf `data_hash`: keccak256(keccak256(borsh(metadata)) || seller_fee_basis_points)
+    pub fn data_hash(&self) -> Result<[u8; 32]> {
+        let mut encoded = Vec::new();
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        let metadata_hash = keccak::hash(&encoded);
+        Ok(keccak::hashv(&[
+            metadata_hash.as_ref(),
+            &self.seller_fee_basis_points.to_le_bytes(),
+        ])
+        .to_bytes())
+    }
+    
+    /// Leaf `creator_hash`: keccak256 over each creator's address, verified flag and share
+    pub fn creator_hash(&self) -> [u8; 32] {
+        let creators: Vec<[u8; 34]> = self
+            .creators
+            .iter()
+            .map(|creator| {
+                let mut data = [0u8; 34];
+                data[..32].copy_from_slice(creator.address.as_ref());
+                data[32] = creator.verified as u8;
+                data[33] = creator.share;
+                data
+            })
+            .collect();
+        let slices: Vec<&[u8]> = creators.iter().map(|data| data.as_slice()).collect();
+        keccak::hashv(&slices).to_bytes()
+    }
+}
+
+/// Compressed NFT leaf being transferred or burned, proven against `root` by the proof
+/// accounts passed alongside
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct CompressedLeaf {
+    pub root: [u8; 32],
+    pub data_hash: [u8; 32],
+    pub creator_hash: [u8; 32],
+    pub nonce: u64,
+    pub index: u32,
+}
+
+/// Asset id of the leaf minted with `nonce` into `merkle_tree`
+pub fn compressed_asset_id(merkle_tree: &Pubkey, nonce: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"asset", merkle_tree.as_ref(), &nonce.to_le_bytes()],
+        &BUBBLEGUM_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Nonce the next leaf minted into the tree will get (the tree's `num_minted`)
+pub fn next_leaf_nonce(tree_config: &AccountInfo) -> Result<u64> {
+    require_keys_eq!(*tree_config.owner, BUBBLEGUM_PROGRAM_ID, GatewayError::InvalidCompressedNftTree);
+    
+    let data = tree_config.try_borrow_data()?;
+    let bytes = data
+        .get(TREE_CONFIG_NUM_MINTED_OFFSET..TREE_CONFIG_NUM_MINTED_OFFSET + 8)
+        .ok_or(GatewayError::InvalidCompressedNftTree)?;
+    Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Tree and program accounts shared by every Bubblegum CPI
+pub struct BubblegumCpi<'a, 'info> {
+    pub bubblegum_program: &'a AccountInfo<'info>,
+    pub tree_config: &'a AccountInfo<'info>,
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub log_wrapper: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> BubblegumCpi<'_, 'info> {
+    /// Move a leaf to `new_leaf_owner`; `leaf_owner` signs (directly or via `signer_seeds`)
+    pub fn transfer(
+        &self,
+        leaf_owner: &AccountInfo<'info>,
+        leaf_delegate: &AccountInfo<'info>,
+        new_leaf_owner: &AccountInfo<'info>,
+        leaf: &CompressedLeaf,
+        proof: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.tree_config.key(), false),
+            AccountMeta::new_readonly(leaf_owner.key(), true),
+            AccountMeta::new_readonly(leaf_delegate.key(), false),
+            AccountMeta::new_readonly(new_leaf_owner.key(), false),
+            AccountMeta::new(self.merkle_tree.key(), false),
+        ];
+        let mut account_infos = vec![
+            self.tree_config.clone(),
+            leaf_owner.clone(),
+            leaf_delegate.clone(),
+            new_leaf_owner.clone(),
+            self.merkle_tree.clone(),
+        ];
+        self.invoke_leaf_instruction(
+            TRANSFER_DISCRIMINATOR,
+            &mut accounts,
+            &mut account_infos,
+            leaf,
+            proof,
+            signer_seeds,
+        )
+    }
+    
+    /// Burn a leaf; `leaf_owner` signs
+    pub fn burn(
+        &self,
+        leaf_owner: &AccountInfo<'info>,
+        leaf_delegate: &AccountInfo<'info>,
+        leaf: &CompressedLeaf,
+        proof: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut accounts = vec![
+            AccountMeta::new_readonly(self.tree_config.key(), false),
+            AccountMeta::new_readonly(leaf_owner.key(), true),
+            AccountMeta::new_readonly(leaf_delegate.key(), false),
+            AccountMeta::new(self.merkle_tree.key(), false),
+        ];
+        let mut account_infos = vec![
+            self.tree_config.clone(),
+            leaf_owner.clone(),
+            leaf_delegate.clone(),
+            self.merkle_tree.clone(),
+        ];
+        self.invoke_leaf_instruction(
+            BURN_DISCRIMINATOR,
+            &mut accounts,
+            &mut account_infos,
+            leaf,
+            proof,
+            &[],
+        )
+    }
+    
+    /// Mint a leaf owned by `leaf_owner`; `tree_delegate` (tree creator or delegate)
+    /// signs via `signer_seeds`
+    pub fn mint_v1(
+        &self,
+        leaf_owner: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        tree_delegate: &AccountInfo<'info>,
+        metadata: &MetadataArgs,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut data = MINT_V1_DISCRIMINATOR.to_vec();
+        metadata
+            .serialize(&mut data)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        
+        let instruction = Instruction {
+            program_id: BUBBLEGUM_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.tree_config.key(), false),
+                AccountMeta::new_readonly(leaf_owner.key(), false),
+                AccountMeta::new_readonly(leaf_owner.key(), false),
+                AccountMeta::new(self.merkle_tree.key(), false),
+                AccountMeta::new(payer.key(), true),
+                AccountMeta::new_readonly(tree_delegate.key(), true),
+                AccountMeta::new_readonly(self.log_wrapper.key(), false),
+                AccountMeta::new_readonly(self.compression_program.key(), false),
+                AccountMeta::new_readonly(self.system_program.key(), false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &instruction,
+            &[
+                self.tree_config.clone(),
+                leaf_owner.clone(),
+                self.merkle_tree.clone(),
+                payer.clone(),
+                tree_delegate.clone(),
+                self.log_wrapper.clone(),
+                self.compression_program.clone(),
+                self.system_program.clone(),
+                self.bubblegum_program.clone(),
+            ],
+            signer_seeds,
+        )?;
+        Ok(())
+    }
+    
+    /// Append the program accounts, leaf arguments and proof path, then invoke
+    fn invoke_leaf_instruction(
+        &self,
+        discriminator: [u8; 8],
+        accounts: &mut Vec<AccountMeta>,
+        account_infos: &mut Vec<AccountInfo<'info>>,
+        leaf: &CompressedLeaf,
+        proof: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        accounts.push(AccountMeta::new_readonly(self.log_wrapper.key(), false));
+        accounts.push(AccountMeta::new_readonly(self.compression_program.key(), false));
+        accounts.push(AccountMeta::new_readonly(self.system_program.key(), false));
+        account_infos.push(self.log_wrapper.clone());
+        account_infos.push(self.compression_program.clone());
+        account_infos.push(self.system_program.clone());
+        for node in proof {
+            accounts.push(AccountMeta::new_readonly(node.key(), false));
+            account_infos.push(node.clone());
+        }
+        account_infos.push(self.bubblegum_program.clone());
+        
+        let mut data = discriminator.to_vec();
+        leaf.serialize(&mut data)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        
+        let instruction = Instruction {
+            program_id: BUBBLEGUM_PROGRAM_ID,
+            accounts: std::mem::take(accounts),
+            data,
+        };
+        invoke_signed(&instruction, account_infos, signer_seeds)?;
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::constants::{
+    MAX_CAIP2_NAMESPACE_LEN, MAX_CAIP2_REFERENCE_LEN, MIN_CAIP2_NAMESPACE_LEN,
+};
+use crate::errors::GatewayError;
+
+/// Well-known CAIP-2 namespaces
+pub const CAIP2_NAMESPACE_EIP155: &str = "eip155";
+pub const CAIP2_NAMESPACE_SOLANA: &str = "solana";
+pub const CAIP2_NAMESPACE_COSMOS: &str = "cosmos";
+
+/// Split a CAIP-2 chain identifier (`namespace:reference`) into its parts
+/// Validates the character sets and lengths from the CAIP-2 specification
+pub fn parse_caip2(caip2_id: &str) -> Result<(&str, &str)> {
+    let (namespace, reference) = caip2_id
+        .split_once(':')
+        .ok_or(GatewayError::InvalidCaip2Id)?;
+    
+    // namespace: [-a-z0-9]{3,8}
+    require!(
+        namespace.len() >= MIN_CAIP2_NAMESPACE_LEN && namespace.len() <= MAX_CAIP2_NAMESPACE_LEN,
+        GatewayError::InvalidCaip2Id
+    );
+    require!(
+        namespace
+            .bytes()
+            .all(|b| b == b'-' || b.is_ascii_lowercase() || b.is_ascii_digit()),
+        GatewayError::InvalidCaip2Id
+    );
+    
+    // reference: [-_a-zA-Z0-9]{1,32}
+    require!(
+        !reference.is_empty() && reference.len() <= MAX_CAIP2_REFERENCE_LEN,
+        GatewayError::InvalidCaip2Id
+    );
+    require!(
+        reference
+            .bytes()
+            .all(|b| b == b'-' || b == b'_' || b.is_ascii_alphanumeric()),
+        GatewayError::InvalidCaip2Id
+    );
+    
+    Ok((namespace, reference))
+}
+
+/// Validate a CAIP-2 chain identifier
+pub fn validate_caip2(caip2_id: &str) -> Result<()> {
+    parse_caip2(caip2_id).map(|_| ())
+}
+
+/// Format a CAIP-2 chain identifier from its parts
+pub fn format_caip2(namespace: &str, reference: &str) -> Result<String> {
+    let caip2_id = format!("{}:{}", namespace, reference);
+    validate_caip2(&caip2_id)?;
+    Ok(caip2_id)
+}
+
+/// Format an EVM chain as `eip155:<chain id>`
+pub fn format_eip155(evm_chain_id: u64) -> String {
+    format!("{}:{}", CAIP2_NAMESPACE_EIP155, evm_chain_id)
+}
+
+/// 32-byte PDA seed for a CAIP-2 identifier (ids can exceed the 32-byte seed limit)
+pub fn caip2_seed(caip2_id: &str) -> [u8; 32] {
+    keccak::hash(caip2_id.as_bytes()).to_bytes()
+}
@@ -0,0 +1,278 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_SIGNATURES_PER_MESSAGE;
+use crate::errors::GatewayError;
+use crate::state::{MessageEnvelope, MessageSignature, SignatureScheme};
+use crate::utils::signature::SignatureSource;
+
+/// Compact (non-Borsh) encoding of TX1/TX2 message arguments
+/// Lengths, chain ids, deadlines and sequences are LEB128 varints, and optional fields
+/// are omitted unless flagged, saving 50-100 bytes against Borsh on a typical message
+/// `source_chain_id` and `tx_id` travel as separate Borsh arguments (they seed the TxId PDA)
+///
+/// Layout: flags u8 | version u8 | dest_chain_id | sender | recipient | on_chain_data |
+/// [off_chain_data] | [off_chain_data_hash 32] | message_type u8 | [deadline] | [sequence] |
+/// protocol_version u8 | [da_pointer] | signature count | signatures
+/// Each signature: scheme u8 (0 Ed25519, 1 secp256k1 followed by its recovery id u8) |
+/// signature 64 | signer 32
+const FLAG_OFF_CHAIN_DATA: u8 = 1 << 0;
+const FLAG_OFF_CHAIN_DATA_HASH: u8 = 1 << 1;
+const FLAG_DEADLINE: u8 = 1 << 2;
+const FLAG_SEQUENCE: u8 = 1 << 3;
+const FLAG_DA_POINTER: u8 = 1 << 4;
+const KNOWN_FLAGS: u8 =
+    FLAG_OFF_CHAIN_DATA | FLAG_OFF_CHAIN_DATA_HASH | FLAG_DEADLINE | FLAG_SEQUENCE | FLAG_DA_POINTER;
+
+const SCHEME_ED25519: u8 = 0;
+const SCHEME_SECP256K1: u8 = 1;
+
+/// Encode an envelope and its signatures in the compact layout
+pub fn encode_compact_message(
+    envelope: &MessageEnvelope,
+    signatures: &[MessageSignature],
+) -> Vec<u8> {
+    let mut flags = 0u8;
+    if !envelope.off_chain_data.is_empty() {
+        flags |= FLAG_OFF_CHAIN_DATA;
+    }
+    if envelope.off_chain_data_hash != [0u8; 32] {
+        flags |= FLAG_OFF_CHAIN_DATA_HASH;
+    }
+    if envelope.deadline != 0 {
+        flags |= FLAG_DEADLINE;
+    }
+    if envelope.sequence != 0 {
+        flags |= FLAG_SEQUENCE;
+    }
+    if !envelope.da_pointer.is_empty() {
+        flags |= FLAG_DA_POINTER;
+    }
+    
+    let mut out = vec![flags, envelope.version];
+    write_varint(&mut out, envelope.dest_chain_id);
+    write_bytes(&mut out, &envelope.sender);
+    write_bytes(&mut out, &envelope.recipient);
+    write_bytes(&mut out, &envelope.on_chain_data);
+    if flags & FLAG_OFF_CHAIN_DATA != 0 {
+        write_bytes(&mut out, &envelope.off_chain_data);
+    }
+    if flags & FLAG_OFF_CHAIN_DATA_HASH != 0 {
+        out.extend_from_slice(&envelope.off_chain_data_hash);
+    }
+    out.push(envelope.message_type);
+    if flags & FLAG_DEADLINE != 0 {
+        write_varint(&mut out, envelope.deadline as u64);
+    }
+    if flags & FLAG_SEQUENCE != 0 {
+        write_varint(&mut out, envelope.sequence);
+    }
+    out.push(envelope.protocol_version);
+    if flags & FLAG_DA_POINTER != 0 {
+        write_bytes(&mut out, &envelope.da_pointer);
+    }
+    
+    write_varint(&mut out, signatures.len() as u64);
+    for signature in signatures {
+        match signature.scheme {
+            SignatureScheme::Ed25519 => out.push(SCHEME_ED25519),
+            SignatureScheme::Secp256k1 { recovery_id } => {
+                out.push(SCHEME_SECP256K1);
+                out.push(recovery_id);
+            }
+        }
+        out.extend_from_slice(&signature.signature);
+        out.extend_from_slice(signature.signer.as_ref());
+    }
+    out
+}
+
+/// Decode the compact layout; the whole input must be consumed
+/// Field sizes are left to `MessageEnvelope::validate`, as for Borsh arguments
+pub fn decode_compact_message(
+    source_chain_id: u64,
+    tx_id: u128,
+    data: &[u8],
+) -> Result<(MessageEnvelope, Vec<MessageSignature>)> {
+    let (envelope, compact_signatures) = decode_compact_message_lazy(source_chain_id, tx_id, data)?;
+    let compact_signatures: &dyn SignatureSource = &compact_signatures;
+    let signatures = compact_signatures.iter().collect::<Result<Vec<_>>>()?;
+    Ok((envelope, signatures))
+}
+
+/// Decode the compact layout, leaving the signatures in the instruction data
+/// Their framing is checked here; each one is decoded when validation reaches it
+pub fn decode_compact_message_lazy(
+    source_chain_id: u64,
+    tx_id: u128,
+    data: &[u8],
+) -> Result<(MessageEnvelope, CompactSignatures<'_>)> {
+    let mut reader = CompactReader { data };
+    
+    let flags = reader.read_u8()?;
+    require!(flags & !KNOWN_FLAGS == 0, GatewayError::InvalidCompactEncoding);
+    
+    let version = reader.read_u8()?;
+    let dest_chain_id = reader.read_varint()?;
+    let sender = reader.read_bytes()?;
+    let recipient = reader.read_bytes()?;
+    let on_chain_data = reader.read_bytes()?;
+    let off_chain_data = if flags & FLAG_OFF_CHAIN_DATA != 0 {
+        reader.read_bytes()?
+    } else {
+        Vec::new()
+    };
+    let off_chain_data_hash = if flags & FLAG_OFF_CHAIN_DATA_HASH != 0 {
+        reader.read_array::<32>()?
+    } else {
+        [0u8; 32]
+    };
+    let message_type = reader.read_u8()?;
+    let deadline = if flags & FLAG_DEADLINE != 0 {
+        reader.read_varint()? as i64
+    } else {
+        0
+    };
+    let sequence = if flags & FLAG_SEQUENCE != 0 {
+        reader.read_varint()?
+    } else {
+        0
+    };
+    let protocol_version = reader.read_u8()?;
+    let da_pointer = if flags & FLAG_DA_POINTER != 0 {
+        reader.read_bytes()?
+    } else {
+        Vec::new()
+    };
+    
+    let count = reader.read_varint()? as usize;
+    require!(
+        count <= MAX_SIGNATURES_PER_MESSAGE,
+        GatewayError::InvalidCompactEncoding
+    );
+    let mut signatures = CompactSignatures {
+        data: reader.data,
+        offsets: [0u16; MAX_SIGNATURES_PER_MESSAGE],
+        len: count,
+    };
+    for offset in signatures.offsets.iter_mut().take(count) {
+        *offset = (signatures.data.len() - reader.data.len()) as u16;
+        read_signature(&mut reader)?;
+    }
+    require!(reader.data.is_empty(), GatewayError::InvalidCompactEncoding);
+    
+    let envelope = MessageEnvelope {
+        version,
+        tx_id,
+        source_chain_id,
+        dest_chain_id,
+        sender,
+        recipient,
+        on_chain_data,
+        off_chain_data,
+        off_chain_data_hash,
+        message_type,
+        deadline,
+        sequence,
+        protocol_version,
+        da_pointer,
+    };
+    Ok((envelope, signatures))
+}
+
+/// Signatures of a compact message, borrowed from the instruction data
+/// Only their offsets are kept; `SignatureSource::get` decodes one signature at a time
+pub struct CompactSignatures<'a> {
+    data: &'a [u8],
+    offsets: [u16; MAX_SIGNATURES_PER_MESSAGE],
+    len: usize,
+}
+
+impl SignatureSource for CompactSignatures<'_> {
+    fn len(&self) -> usize {
+        self.len
+    }
+    
+    fn get(&self, index: usize) -> Result<MessageSignature> {
+        require!(index < self.len, GatewayError::InvalidCompactEncoding);
+        let mut reader = CompactReader {
+            data: &self.data[self.offsets[index] as usize..],
+        };
+        read_signature(&mut reader)
+    }
+}
+
+/// One signature: scheme u8 [recovery id u8] | signature 64 | signer 32
+fn read_signature(reader: &mut CompactReader) -> Result<MessageSignature> {
+    let scheme = match reader.read_u8()? {
+        SCHEME_ED25519 => SignatureScheme::Ed25519,
+        SCHEME_SECP256K1 => SignatureScheme::Secp256k1 {
+            recovery_id: reader.read_u8()?,
+        },
+        _ => return err!(GatewayError::InvalidCompactEncoding),
+    };
+    Ok(MessageSignature {
+        signature: reader.read_array::<64>()?,
+        signer: Pubkey::new_from_array(reader.read_array::<32>()?),
+        scheme,
+    })
+}
+
+/// LEB128 unsigned varint
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Varint length followed by the bytes
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+struct CompactReader<'a> {
+    data: &'a [u8],
+}
+
+impl CompactReader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8]> {
+        require!(self.data.len() >= len, GatewayError::InvalidCompactEncoding);
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+    
+    fn read_u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+    
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+    
+    /// At most ten bytes; bits beyond 64 are rejected
+    fn read_varint(&mut self) -> Result<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_u8()?;
+            let bits = (byte & 0x7f) as u64;
+            require!(
+                shift < 63 || bits <= 1,
+                GatewayError::InvalidCompactEncoding
+            );
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        err!(GatewayError::InvalidCompactEncoding)
+    }
+    
+    fn read_bytes(&mut self) -> Result<Vec<u8>> {
+        let len = self.read_varint()?;
+        let len = usize::try_from(len).map_err(|_| GatewayError::InvalidCompactEncoding)?;
+        Ok(self.take(len)?.to_vec())
+    }
+}
\ No newline at end of file
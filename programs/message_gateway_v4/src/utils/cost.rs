@@ -0,0 +1,165 @@
+//! Relay cost model: compute units, transaction size, fees and rent of a TX1/TX2 pair
+//!
+//! Lets relayers size compute budgets and fees before sending, for a payload size and an
+//! Ed25519 attestation count, assuming the transaction layout `txbuilder` produces (legacy
+//! transactions, one precompile instruction per attestation, both compute-budget
+//! instructions, an EVM sender, a 32-byte recipient and no off-chain data or DA pointer)
+//! Compute units are calibrated against `profile` builds and err on the high side
+
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_ON_CHAIN_DATA_SIZE;
+use crate::errors::GatewayError;
+use crate::state::TxIdPDA;
+use crate::utils::signature::validate_signature_count;
+
+/// TX1 compute: account creation, counter update and envelope validation
+pub const TX1_BASE_COMPUTE_UNITS: u32 = 30_000;
+/// TX1 compute per attestation: matching it to its precompile instruction
+pub const TX1_COMPUTE_UNITS_PER_SIGNATURE: u32 = 3_000;
+/// TX2 compute: registry loading, envelope checks, routing and PDA closure
+pub const TX2_BASE_COMPUTE_UNITS: u32 = 45_000;
+/// TX2 compute per attestation: precompile lookup, duplicate and membership checks
+pub const TX2_COMPUTE_UNITS_PER_SIGNATURE: u32 = 13_000;
+/// Compute per payload byte, per transaction: deserialization and hashing
+pub const COMPUTE_UNITS_PER_PAYLOAD_BYTE: u32 = 3;
+/// Headroom added to an estimate to get the compute unit limit to request
+pub const COMPUTE_UNIT_MARGIN_PERCENT: u32 = 20;
+
+/// Base fee per transaction signature, precompile signatures included
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Largest serialized transaction the network accepts
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Assumed envelope address sizes
+const SENDER_SIZE: usize = 20;
+const RECIPIENT_SIZE: usize = 32;
+
+/// Borsh size of an envelope without its variable-length fields
+const ENVELOPE_FIXED_SIZE: usize = 1 + 16 + 8 + 8 + 4 + 4 + 4 + 4 + 32 + 1 + 8 + 8 + 1 + 4;
+/// Borsh size of an Ed25519 MessageSignature (signature, signer, scheme tag)
+const SIGNATURE_ARG_SIZE: usize = 64 + 32 + 1;
+/// Data of one single-signature Ed25519 precompile instruction
+const ED25519_INSTRUCTION_DATA_SIZE: usize = 16 + 64 + 32 + 32;
+/// Data of the compute unit limit and price instructions
+const COMPUTE_BUDGET_DATA_SIZES: [usize; 2] = [5, 9];
+
+/// Distinct accounts (relayer, gateway PDAs, sysvar, programs) and account metas of the
+/// gateway instruction of each transaction
+const TX1_KEYS: usize = 9;
+const TX1_ACCOUNT_METAS: usize = 8;
+const TX2_KEYS: usize = 10;
+const TX2_ACCOUNT_METAS: usize = 12;
+
+/// Expected cost of relaying one message
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RelayCostEstimate {
+    /// Expected compute units of create_tx_pda / process_message
+    pub tx1_compute_units: u32,
+    pub tx2_compute_units: u32,
+    
+    /// Serialized transaction sizes in bytes (at most MAX_TRANSACTION_SIZE to be sendable)
+    pub tx1_size: u32,
+    pub tx2_size: u32,
+    
+    /// Base fees at LAMPORTS_PER_SIGNATURE, without priority fees
+    pub tx1_fee_lamports: u64,
+    pub tx2_fee_lamports: u64,
+    
+    /// Rent the relayer advances in TX1 and gets back in TX2
+    pub tx_id_pda_rent_lamports: u64,
+}
+
+impl RelayCostEstimate {
+    /// Compute unit limit to request for an estimate, with COMPUTE_UNIT_MARGIN_PERCENT headroom
+    pub fn compute_unit_limit(compute_units: u32) -> u32 {
+        compute_units.saturating_mul(100 + COMPUTE_UNIT_MARGIN_PERCENT) / 100
+    }
+    
+    /// Whether both transactions fit in a packet without address lookup tables
+    pub fn fits_legacy_transactions(&self) -> bool {
+        self.tx1_size as usize <= MAX_TRANSACTION_SIZE && self.tx2_size as usize <= MAX_TRANSACTION_SIZE
+    }
+}
+
+/// Estimate the cost of relaying a message with `payload_size` bytes of on-chain data and
+/// `signature_count` Ed25519 attestations
+pub fn estimate_relay_cost(
+    payload_size: usize,
+    signature_count: usize,
+    rent: &Rent,
+) -> Result<RelayCostEstimate> {
+    require!(
+        payload_size <= MAX_ON_CHAIN_DATA_SIZE,
+        GatewayError::OnChainDataTooLarge
+    );
+    validate_signature_count(signature_count)?;
+    
+    let payload_compute_units = COMPUTE_UNITS_PER_PAYLOAD_BYTE * payload_size as u32;
+    let signatures = signature_count as u32;
+    
+    // Both transactions carry the same arguments; the precompile instructions repeat in TX2
+    let gateway_data = 8
+        + ENVELOPE_FIXED_SIZE
+        + SENDER_SIZE
+        + RECIPIENT_SIZE
+        + payload_size
+        + 4
+        + signature_count * SIGNATURE_ARG_SIZE;
+    let fee = LAMPORTS_PER_SIGNATURE * (1 + signature_count as u64);
+    
+    Ok(RelayCostEstimate {
+        tx1_compute_units: TX1_BASE_COMPUTE_UNITS
+            + TX1_COMPUTE_UNITS_PER_SIGNATURE * signatures
+            + payload_compute_units,
+        tx2_compute_units: TX2_BASE_COMPUTE_UNITS
+            + TX2_COMPUTE_UNITS_PER_SIGNATURE * signatures
+            + payload_compute_units,
+        tx1_size: transaction_size(TX1_KEYS, TX1_ACCOUNT_METAS, gateway_data, signature_count) as u32,
+        tx2_size: transaction_size(TX2_KEYS, TX2_ACCOUNT_METAS, gateway_data, signature_count) as u32,
+        tx1_fee_lamports: fee,
+        tx2_fee_lamports: fee,
+        tx_id_pda_rent_lamports: rent.minimum_balance(8 + TxIdPDA::SIZE),
+    })
+}
+
+/// Serialized size of a legacy transaction signed by the relayer alone, holding the
+/// compute-budget instructions, `ed25519_instructions` precompile instructions and the
+/// gateway instruction
+fn transaction_size(
+    keys: usize,
+    account_metas: usize,
+    gateway_data: usize,
+    ed25519_instructions: usize,
+) -> usize {
+    let signatures = compact_len(1) + 64;
+    let header = 3 + compact_len(keys) + 32 * keys + 32;
+    let compute_budget: usize = COMPUTE_BUDGET_DATA_SIZES
+        .iter()
+        .map(|&data| instruction_size(0, data))
+        .sum();
+    let precompiles = ed25519_instructions * instruction_size(0, ED25519_INSTRUCTION_DATA_SIZE);
+    let gateway = instruction_size(account_metas, gateway_data);
+    
+    signatures
+        + header
+        + compact_len(COMPUTE_BUDGET_DATA_SIZES.len() + ed25519_instructions + 1)
+        + compute_budget
+        + precompiles
+        + gateway
+}
+
+/// Compiled instruction: program index, account indices and data, each list length-prefixed
+fn instruction_size(account_metas: usize, data: usize) -> usize {
+    1 + compact_len(account_metas) + account_metas + compact_len(data) + data
+}
+
+/// Bytes of a compact-u16 length prefix
+fn compact_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
\ No newline at end of file
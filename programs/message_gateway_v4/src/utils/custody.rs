@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+
+use crate::errors::GatewayError;
+
+/// Anchor discriminators of the custody extension interface: sha256("global:<name>")[..8]
+/// Extensions written with Anchor implement `custody_lock` and `custody_release` instructions
+const CUSTODY_LOCK_DISCRIMINATOR: [u8; 8] = [98, 7, 25, 194, 177, 158, 70, 50];
+const CUSTODY_RELEASE_DISCRIMINATOR: [u8; 8] = [160, 81, 39, 223, 160, 173, 182, 187];
+
+/// Arguments of `custody_lock`: take `amount` from the depositor for a transfer to `dest_chain_id`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct CustodyLockArgs {
+    pub tx_id: u128,
+    pub dest_chain_id: u64,
+    pub amount: u64,
+}
+
+/// Arguments of `custody_release`: credit `amount` to the recipient for a verified inbound transfer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct CustodyReleaseArgs {
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub amount: u64,
+}
+
+/// Accounts shared by every custody extension CPI
+/// `custody_authority` is the gateway's per-mint PDA and always signs, so an extension
+/// can tell gateway-authorized calls apart from direct ones
+pub struct CustodyExtensionCpi<'a, 'info> {
+    pub program: &'a AccountInfo<'info>,
+    pub custody_authority: &'a AccountInfo<'info>,
+    pub mint: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> CustodyExtensionCpi<'_, 'info> {
+    /// `custody_lock` accounts: custody authority, mint, depositor token account, depositor
+    /// (signer), token program, then the extension's own accounts
+    pub fn lock(
+        &self,
+        depositor_token_account: &AccountInfo<'info>,
+        depositor: &AccountInfo<'info>,
+        args: CustodyLockArgs,
+        extension_accounts: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut accounts = vec![
+            AccountMeta::new(depositor_token_account.key(), false),
+            AccountMeta::new_readonly(depositor.key(), true),
+        ];
+        let mut account_infos = vec![depositor_token_account.clone(), depositor.clone()];
+        self.invoke(
+            CUSTODY_LOCK_DISCRIMINATOR,
+            &args,
+            &mut accounts,
+            &mut account_infos,
+            extension_accounts,
+            signer_seeds,
+        )
+    }
+    
+    /// `custody_release` accounts: custody authority, mint, recipient token account, token
+    /// program, then the extension's own accounts
+    pub fn release(
+        &self,
+        recipient_token_account: &AccountInfo<'info>,
+        args: CustodyReleaseArgs,
+        extension_accounts: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut accounts = vec![AccountMeta::new(recipient_token_account.key(), false)];
+        let mut account_infos = vec![recipient_token_account.clone()];
+        self.invoke(
+            CUSTODY_RELEASE_DISCRIMINATOR,
+            &args,
+            &mut accounts,
+            &mut account_infos,
+            extension_accounts,
+            signer_seeds,
+        )
+    }
+    
+    /// Prepend the authority and mint, append the token program and extension accounts, then invoke
+    fn invoke<T: AnchorSerialize>(
+        &self,
+        discriminator: [u8; 8],
+        args: &T,
+        accounts: &mut Vec<AccountMeta>,
+        account_infos: &mut Vec<AccountInfo<'info>>,
+        extension_accounts: &[AccountInfo<'info>],
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let mut metas = vec![
+            AccountMeta::new_readonly(self.custody_authority.key(), true),
+            AccountMeta::new(self.mint.key(), false),
+        ];
+        metas.append(accounts);
+        metas.push(AccountMeta::new_readonly(self.token_program.key(), false));
+        
+        let mut infos = vec![self.custody_authority.clone(), self.mint.clone()];
+        infos.append(account_infos);
+        infos.push(self.token_program.clone());
+        
+        for account in extension_accounts {
+            metas.push(if account.is_writable {
+                AccountMeta::new(account.key(), account.is_signer)
+            } else {
+                AccountMeta::new_readonly(account.key(), account.is_signer)
+            });
+            infos.push(account.clone());
+        }
+        infos.push(self.program.clone());
+        
+        let mut data = discriminator.to_vec();
+        args.serialize(&mut data)
+            .map_err(|_| GatewayError::InvalidPayload)?;
+        
+        let instruction = Instruction {
+            program_id: self.program.key(),
+            accounts: metas,
+            data,
+        };
+        invoke_signed(&instruction, &infos, signer_seeds)?;
+        Ok(())
+    }
+}
\ No newline at end of file
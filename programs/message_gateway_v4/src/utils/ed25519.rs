@@ -0,0 +1,67 @@
+//! Ed25519 precompile instruction layout
+//!
+//! The gateway reads attestations from the precompile's offsets table (see
+//! `signature::parse_ed25519_instruction`); relayers and tests build the instructions here
+//! so the layout they submit is the one the verifier parses
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
+
+use crate::state::{MessageSignature, SignatureScheme};
+
+/// Signature count, padding byte, then one offsets entry per signature
+pub const ED25519_OFFSETS_START: usize = 2;
+pub const ED25519_OFFSETS_ENTRY_SIZE: usize = 14;
+
+/// `*_instruction_index` value referring to the precompile instruction itself
+pub const ED25519_CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Precompile instruction verifying every `(signer, signature)` pair over one message hash
+/// Layout: offsets table, each entry's signature and key, then the message hash once,
+/// shared by every entry; with a single pair it is the 144-byte one-attestation layout
+/// `entries` must hold between 1 and 255 pairs
+pub fn ed25519_instruction(entries: &[(Pubkey, [u8; 64])], message_hash: &[u8; 32]) -> Instruction {
+    let table_end = ED25519_OFFSETS_START + entries.len() * ED25519_OFFSETS_ENTRY_SIZE;
+    let message_offset = table_end + entries.len() * 96;
+    
+    let mut data = Vec::with_capacity(message_offset + 32);
+    data.push(entries.len() as u8);
+    data.push(0); // padding
+    for index in 0..entries.len() {
+        let signature_offset = table_end + index * 96;
+        for field in [
+            signature_offset as u16,
+            ED25519_CURRENT_INSTRUCTION,
+            (signature_offset + 64) as u16,
+            ED25519_CURRENT_INSTRUCTION,
+            message_offset as u16,
+            32,
+            ED25519_CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+    }
+    for (signer, signature) in entries {
+        data.extend_from_slice(signature);
+        data.extend_from_slice(signer.as_ref());
+    }
+    data.extend_from_slice(message_hash);
+    
+    Instruction::new_with_bytes(ed25519_program::ID, &data, Vec::new())
+}
+
+/// One precompile instruction covering every Ed25519 attestation of a message (None if
+/// it has none; secp256k1 attestations are verified in-program)
+/// Saves at least 34 bytes per extra attestation over one instruction each, the difference that lets
+/// larger signer sets fit a legacy transaction
+pub fn ed25519_packed_instruction(
+    signatures: &[MessageSignature],
+    message_hash: &[u8; 32],
+) -> Option<Instruction> {
+    let entries: Vec<(Pubkey, [u8; 64])> = signatures
+        .iter()
+        .filter(|signature| signature.scheme == SignatureScheme::Ed25519)
+        .map(|signature| (signature.signer, signature.signature))
+        .collect();
+    (!entries.is_empty()).then(|| ed25519_instruction(&entries, message_hash))
+}
\ No newline at end of file
@@ -1,59 +1,163 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::{blake3, hash as sha256, keccak};
+use crate::constants::DEFAULT_INSTANCE_ID;
 use crate::errors::GatewayError;
+use crate::utils::{logging::verbose_msg, profile::checkpoint};
+use crate::state::{HashAlgorithm, HashScheme, IntegerEncoding, LegacyV3Message, MessageEnvelope};
+
+/// Hash a sequence of byte slices with the route's configured algorithm
+pub fn hash_with_algorithm(algorithm: HashAlgorithm, parts: &[&[u8]]) -> [u8; 32] {
+    match algorithm {
+        HashAlgorithm::Keccak256 => keccak::hashv(parts).to_bytes(),
+        HashAlgorithm::Sha256 => sha256::hashv(parts).to_bytes(),
+        HashAlgorithm::Blake3 => blake3::hashv(parts).to_bytes(),
+    }
+}
+
+/// Build the cross-chain hash pre-image from a message envelope
+/// Integers use the route's byte order (little endian is Solana native format,
+/// big endian matches EVM abi.encodePacked)
+pub fn encode_cross_chain_preimage(
+    integer_encoding: IntegerEncoding,
+    envelope: &MessageEnvelope,
+) -> Result<Vec<u8>> {
+    with_cross_chain_preimage(integer_encoding, envelope, &envelope.on_chain_data, |parts| {
+        parts.concat()
+    })
+}
+
+/// Digest of every envelope field except `on_chain_data` (keccak256 over the little-endian
+/// pre-image with an empty payload), recorded in TX1 for process_message_by_hash
+pub fn create_envelope_digest(envelope: &MessageEnvelope) -> Result<[u8; 32]> {
+    with_cross_chain_preimage(IntegerEncoding::LittleEndian, envelope, &[], |parts| {
+        keccak::hashv(parts).to_bytes()
+    })
+}
+
+/// Hand the cross-chain pre-image to `f` as ordered slices, without concatenating it
+/// Fixed-width fields are encoded into stack arrays and variable fields are borrowed
+/// from the envelope, so hashing the slices with `hashv` needs no heap buffer
+/// `on_chain_data` stands in for the envelope's payload
+fn with_cross_chain_preimage<R>(
+    integer_encoding: IntegerEncoding,
+    envelope: &MessageEnvelope,
+    on_chain_data: &[u8],
+    f: impl FnOnce(&[&[u8]]) -> R,
+) -> Result<R> {
+    // Validate version and input sizes to prevent hash collisions
+    envelope.validate()?;
+    
+    // u128 tx_id (16 bytes)
+    let tx_id = match integer_encoding {
+        IntegerEncoding::LittleEndian => envelope.tx_id.to_le_bytes(),
+        IntegerEncoding::BigEndian => envelope.tx_id.to_be_bytes(),
+    };
+    let off_chain_data_hash = envelope.resolved_off_chain_data_hash()?;
+    
+    Ok(f(&[
+        // u8 envelope version
+        &[envelope.version],
+        &tx_id,
+        // u64 source_chain_id and dest_chain_id (8 bytes each)
+        &encode_u64(integer_encoding, envelope.source_chain_id),
+        &encode_u64(integer_encoding, envelope.dest_chain_id),
+        // Length-prefixed bytes (u32 length + data) - Solana style encoding
+        &encode_length(integer_encoding, &envelope.sender),
+        &envelope.sender,
+        &encode_length(integer_encoding, &envelope.recipient),
+        &envelope.recipient,
+        &encode_length(integer_encoding, on_chain_data),
+        on_chain_data,
+        // bytes32 keccak256(off_chain_data) - full data and commit-only envelopes hash identically
+        &off_chain_data_hash,
+        // u8 message_type and i64 deadline (8 bytes)
+        &[envelope.message_type],
+        &encode_u64(integer_encoding, envelope.deadline as u64),
+        // u64 channel sequence (8 bytes)
+        &encode_u64(integer_encoding, envelope.sequence),
+        // u8 protocol_version
+        &[envelope.protocol_version],
+        // Length-prefixed data-availability pointer (empty = none)
+        &encode_length(integer_encoding, &envelope.da_pointer),
+        &envelope.da_pointer,
+    ]))
+}
 
 /// Cross-chain compatible message hash generation
 /// Creates destination-specific hashes that are consistent across chains
+/// Off-chain tooling without Anchor uses the `via-gateway-core` crate, kept byte-identical
+/// to this by `tests/core_parity.rs`
 pub fn create_cross_chain_hash(
-    tx_id: u128,
-    source_chain_id: u64,
-    dest_chain_id: u64,
-    sender: &[u8],
-    recipient: &[u8],
-    on_chain_data: &[u8],
-    off_chain_data: &[u8],
+    hash_scheme: HashScheme,
+    envelope: &MessageEnvelope,
 ) -> Result<[u8; 32]> {
-    // Validate input sizes to prevent hash collisions
-    require!(sender.len() <= 64, GatewayError::SenderTooLong);
-    require!(recipient.len() <= 64, GatewayError::RecipientTooLong);
-    require!(on_chain_data.len() <= 1024, GatewayError::OnChainDataTooLarge);
-    require!(off_chain_data.len() <= 1024, GatewayError::OffChainDataTooLarge);
+    create_cross_chain_hash_with_payload(hash_scheme, envelope, &envelope.on_chain_data)
+}
 
-    let mut encoded = Vec::new();
-    
-    // u128 tx_id (16 bytes, little endian) - Solana native format
-    encoded.extend_from_slice(&tx_id.to_le_bytes());
-    
-    // u64 source_chain_id (8 bytes, little endian)
-    encoded.extend_from_slice(&source_chain_id.to_le_bytes());
-    
-    // u64 dest_chain_id (8 bytes, little endian)
-    encoded.extend_from_slice(&dest_chain_id.to_le_bytes());
-    
-    // Length-prefixed bytes (u32 length + data) - Solana style encoding
-    encode_length_prefixed(&mut encoded, sender);
-    encode_length_prefixed(&mut encoded, recipient);
-    encode_length_prefixed(&mut encoded, on_chain_data);
-    encode_length_prefixed(&mut encoded, off_chain_data);
-    
-    // Use Solana's keccak256 syscall for consistency
-    let hash = keccak::hash(&encoded);
+/// Cross-chain hash with `on_chain_data` standing in for the envelope's payload
+/// (chunked messages keep their payload in a MessageBuffer)
+pub fn create_cross_chain_hash_with_payload(
+    hash_scheme: HashScheme,
+    envelope: &MessageEnvelope,
+    on_chain_data: &[u8],
+) -> Result<[u8; 32]> {
+    // Use Solana's hashing syscalls (keccak256 by default) over the borrowed fields
+    let hash = with_cross_chain_preimage(
+        hash_scheme.integer_encoding,
+        envelope,
+        on_chain_data,
+        |parts| hash_with_algorithm(hash_scheme.algorithm, parts),
+    )?;
     
-    msg!(
-        "Generated hash for tx_id={}, source_chain={}, dest_chain={}, hash={:?}",
-        tx_id,
-        source_chain_id,
-        dest_chain_id,
-        hash.to_bytes()
+    verbose_msg!(
+        "Generated {:?} hash for tx_id={}, source_chain={}, dest_chain={}, hash={:?}",
+        hash_scheme,
+        envelope.tx_id,
+        envelope.source_chain_id,
+        envelope.dest_chain_id,
+        hash
     );
     
-    Ok(hash.to_bytes())
+    Ok(hash)
+}
+
+/// Hash signed by Via V3 validators: keccak256 over little-endian integers and
+/// length-prefixed fields, without envelope version or domain separation
+pub fn create_legacy_v3_hash(message: &LegacyV3Message) -> Result<[u8; 32]> {
+    message.validate()?;
+    
+    let encoding = IntegerEncoding::LittleEndian;
+    Ok(keccak::hashv(&[
+        &message.tx_id.to_le_bytes(),
+        &encode_u64(encoding, message.source_chain_id),
+        &encode_u64(encoding, message.dest_chain_id),
+        &encode_length(encoding, &message.sender),
+        &message.sender,
+        &encode_length(encoding, &message.recipient),
+        &message.recipient,
+        &encode_length(encoding, &message.on_chain_data),
+        &message.on_chain_data,
+        &encode_length(encoding, &message.off_chain_data),
+        &message.off_chain_data,
+    ])
+    .to_bytes())
 }
 
-/// Encode data with length prefix (u32 length + data bytes)
-fn encode_length_prefixed(buffer: &mut Vec<u8>, data: &[u8]) {
-    buffer.extend_from_slice(&(data.len() as u32).to_le_bytes());
-    buffer.extend_from_slice(data);
+/// Encode a u64 in the route's byte order
+fn encode_u64(integer_encoding: IntegerEncoding, value: u64) -> [u8; 8] {
+    match integer_encoding {
+        IntegerEncoding::LittleEndian => value.to_le_bytes(),
+        IntegerEncoding::BigEndian => value.to_be_bytes(),
+    }
+}
+
+/// Encode the u32 length prefix of a variable-length field in the route's byte order
+fn encode_length(integer_encoding: IntegerEncoding, data: &[u8]) -> [u8; 4] {
+    let length = data.len() as u32;
+    match integer_encoding {
+        IntegerEncoding::LittleEndian => length.to_le_bytes(),
+        IntegerEncoding::BigEndian => length.to_be_bytes(),
+    }
 }
 
 /// Validate message hash format
@@ -67,49 +171,109 @@ pub fn validate_message_hash(hash: &[u8; 32]) -> Result<()> {
     Ok(())
 }
 
+/// Domain separator binding signatures to a single gateway deployment
+/// H(tag || program_id || gateway_chain_id (u64) || instance_id (u16))
+/// The instance id is omitted for the default instance, whose domain is unchanged
+/// The layout is `via_gateway_core::create_domain_separator_with`; only the hasher is ours
+pub fn create_domain_separator(
+    hash_scheme: HashScheme,
+    program_id: &Pubkey,
+    gateway_chain_id: u64,
+    instance_id: u16,
+) -> [u8; 32] {
+    via_gateway_core::create_domain_separator_with(
+        |parts| hash_with_algorithm(hash_scheme.algorithm, parts),
+        core_integer_encoding(hash_scheme.integer_encoding),
+        &program_id.to_bytes(),
+        gateway_chain_id,
+        instance_id,
+    )
+}
+
+/// The core crate's counterpart of a route's integer encoding
+fn core_integer_encoding(integer_encoding: IntegerEncoding) -> via_gateway_core::IntegerEncoding {
+    match integer_encoding {
+        IntegerEncoding::LittleEndian => via_gateway_core::IntegerEncoding::LittleEndian,
+        IntegerEncoding::BigEndian => via_gateway_core::IntegerEncoding::BigEndian,
+    }
+}
+
 /// Create message hash for signature verification
-/// This function creates the exact hash that off-chain validators sign
+/// This function creates the exact hash that off-chain validators sign:
+/// H(0x1901 || domain_separator || cross_chain_hash), H selected per chain route
+/// Signs for the default gateway instance on `gateway_chain_id`
 pub fn create_message_hash_for_signing(
-    tx_id: u128,
-    source_chain_id: u64,
-    dest_chain_id: u64,
-    sender: &[u8],
-    recipient: &[u8],
+    hash_scheme: HashScheme,
+    gateway_chain_id: u64,
+    envelope: &MessageEnvelope,
+) -> Result<[u8; 32]> {
+    create_instance_message_hash_for_signing(
+        hash_scheme,
+        gateway_chain_id,
+        DEFAULT_INSTANCE_ID,
+        envelope,
+    )
+}
+
+/// Signing hash for gateway instance `instance_id` on `gateway_chain_id`
+pub fn create_instance_message_hash_for_signing(
+    hash_scheme: HashScheme,
+    gateway_chain_id: u64,
+    instance_id: u16,
+    envelope: &MessageEnvelope,
+) -> Result<[u8; 32]> {
+    create_message_hash_for_signing_with_payload(
+        hash_scheme,
+        gateway_chain_id,
+        instance_id,
+        envelope,
+        &envelope.on_chain_data,
+    )
+}
+
+/// Signing hash with `on_chain_data` standing in for the envelope's payload
+pub fn create_message_hash_for_signing_with_payload(
+    hash_scheme: HashScheme,
+    gateway_chain_id: u64,
+    instance_id: u16,
+    envelope: &MessageEnvelope,
     on_chain_data: &[u8],
-    off_chain_data: &[u8],
 ) -> Result<[u8; 32]> {
     // This should match the hash format used by off-chain validators
-    create_cross_chain_hash(
-        tx_id,
-        source_chain_id,
-        dest_chain_id,
-        sender,
-        recipient,
-        on_chain_data,
-        off_chain_data,
-    )
+    let message_hash = create_cross_chain_hash_with_payload(hash_scheme, envelope, on_chain_data)?;
+    
+    // Domain separation prevents replay across programs, networks, protocol versions and
+    // gateway instances
+    let domain_separator =
+        create_domain_separator(hash_scheme, &crate::ID, gateway_chain_id, instance_id);
+    
+    let signing_hash = via_gateway_core::create_signing_hash_with(
+        |parts| hash_with_algorithm(hash_scheme.algorithm, parts),
+        &domain_separator,
+        &message_hash,
+    );
+    checkpoint("profile: message hashed");
+    Ok(signing_hash)
+}
+
+/// Hashes the gateway computes for an envelope, returned by the `verify_hash_consistency`
+/// instruction so counterpart implementations can diff them against their own
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashConsistency {
+    pub cross_chain_hash: [u8; 32],
+    /// Signing hash for the destination gateway's domain
+    pub signing_hash: [u8; 32],
+    /// Whether the caller's cross-chain hash matches `cross_chain_hash`
+    pub consistent: bool,
 }
 
 /// Verify message hash matches expected format
 pub fn verify_hash_consistency(
     hash: &[u8; 32],
-    tx_id: u128,
-    source_chain_id: u64,
-    dest_chain_id: u64,
-    sender: &[u8],
-    recipient: &[u8],
-    on_chain_data: &[u8],
-    off_chain_data: &[u8],
+    hash_scheme: HashScheme,
+    envelope: &MessageEnvelope,
 ) -> Result<bool> {
-    let calculated_hash = create_cross_chain_hash(
-        tx_id,
-        source_chain_id,
-        dest_chain_id,
-        sender,
-        recipient,
-        on_chain_data,
-        off_chain_data,
-    )?;
+    let calculated_hash = create_cross_chain_hash(hash_scheme, envelope)?;
     
     Ok(hash == &calculated_hash)
 }
\ No newline at end of file
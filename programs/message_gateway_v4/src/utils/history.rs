@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    keccak,
+    program::invoke_signed,
+};
+
+use crate::constants::MESSAGE_HISTORY_SEED;
+use crate::errors::GatewayError;
+use crate::state::{MessageEnvelope, MessageGateway};
+use crate::utils::bubblegum::{ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+
+/// Anchor discriminators of the account-compression instructions used: sha256("global:<name>")[..8]
+const INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+const APPEND_DISCRIMINATOR: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+
+/// Number of history accounts a delivery path consumes from remaining_accounts while history
+/// is enabled: [merkle_tree, history_authority, account-compression program, noop program]
+pub const MESSAGE_HISTORY_ACCOUNTS: usize = 4;
+
+/// History leaf of a processed message: keccak256(source_chain_id LE || tx_id LE || message_hash)
+/// `message_hash` is the signing hash TX1 verified and stored in the TxId PDA
+pub fn message_history_leaf(source_chain_id: u64, tx_id: u128, message_hash: &[u8; 32]) -> [u8; 32] {
+    keccak::hashv(&[
+        &source_chain_id.to_le_bytes(),
+        &tx_id.to_le_bytes(),
+        message_hash,
+    ])
+    .to_bytes()
+}
+
+/// Concurrent Merkle tree owned by the account-compression program, with the gateway's
+/// history authority PDA as tree authority
+pub struct MessageHistoryCpi<'a, 'info> {
+    pub merkle_tree: &'a AccountInfo<'info>,
+    pub history_authority: &'a AccountInfo<'info>,
+    pub compression_program: &'a AccountInfo<'info>,
+    pub noop_program: &'a AccountInfo<'info>,
+}
+
+impl<'info> MessageHistoryCpi<'_, 'info> {
+    /// Initialize an allocated tree account as an empty concurrent Merkle tree
+    /// `instance_seed` and `bump` derive the history authority of the gateway instance
+    pub fn init_empty(&self, max_depth: u32, max_buffer_size: u32, instance_seed: &[u8], bump: u8) -> Result<()> {
+        let mut data = INIT_EMPTY_MERKLE_TREE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&max_depth.to_le_bytes());
+        data.extend_from_slice(&max_buffer_size.to_le_bytes());
+        self.invoke(data, instance_seed, bump)
+    }
+    
+    /// Append one leaf to the tree
+    pub fn append(&self, leaf: &[u8; 32], instance_seed: &[u8], bump: u8) -> Result<()> {
+        let mut data = APPEND_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(leaf);
+        self.invoke(data, instance_seed, bump)
+    }
+    
+    fn invoke(&self, data: Vec<u8>, instance_seed: &[u8], bump: u8) -> Result<()> {
+        let instruction = Instruction {
+            program_id: ACCOUNT_COMPRESSION_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.merkle_tree.key(), false),
+                AccountMeta::new_readonly(self.history_authority.key(), true),
+                AccountMeta::new_readonly(self.noop_program.key(), false),
+            ],
+            data,
+        };
+        invoke_signed(
+            &instruction,
+            &[
+                self.merkle_tree.clone(),
+                self.history_authority.clone(),
+                self.noop_program.clone(),
+                self.compression_program.clone(),
+            ],
+            &[&[MESSAGE_HISTORY_SEED, instance_seed, &[bump]]],
+        )?;
+        Ok(())
+    }
+}
+
+/// Append a processed message to the gateway's history tree, if history is enabled
+/// The history accounts follow the extra verifier registries in remaining_accounts:
+/// [merkle_tree, history_authority, account-compression program, noop program]
+pub fn record_processed_message<'info>(
+    gateway: &MessageGateway,
+    history_accounts: &[AccountInfo<'info>],
+    envelope: &MessageEnvelope,
+    message_hash: &[u8; 32],
+) -> Result<()> {
+    if !gateway.message_history_enabled() {
+        return Ok(());
+    }
+    
+    let [merkle_tree, history_authority, compression_program, noop_program, ..] = history_accounts else {
+        return err!(GatewayError::MissingMessageHistory);
+    };
+    require_keys_eq!(
+        merkle_tree.key(),
+        gateway.message_history_tree,
+        GatewayError::MissingMessageHistory
+    );
+    require_keys_eq!(
+        compression_program.key(),
+        ACCOUNT_COMPRESSION_PROGRAM_ID,
+        GatewayError::MissingMessageHistory
+    );
+    require_keys_eq!(noop_program.key(), NOOP_PROGRAM_ID, GatewayError::MissingMessageHistory);
+    
+    // The authority only signs through the history PDA seeds, so a substituted account
+    // fails the CPI signer check
+    let history = MessageHistoryCpi {
+        merkle_tree,
+        history_authority,
+        compression_program,
+        noop_program,
+    };
+    let leaf = message_history_leaf(envelope.source_chain_id, envelope.tx_id, message_hash);
+    history.append(&leaf, &gateway.instance_seed(), gateway.message_history_bump)
+}
\ No newline at end of file
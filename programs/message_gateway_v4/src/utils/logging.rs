@@ -0,0 +1,17 @@
+//! Diagnostic logging (feature `verbose-logs`)
+//!
+//! Release builds log one summary line per instruction; per-signature diagnostics, hash
+//! dumps and intermediate validation counts go through `verbose_msg!`, which compiles to
+//! nothing without the feature
+
+/// `msg!` that only logs with the `verbose-logs` feature
+/// The arguments are still type-checked, so disabling the feature cannot hide a broken log
+macro_rules! verbose_msg {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "verbose-logs") {
+            anchor_lang::prelude::msg!($($arg)*);
+        }
+    };
+}
+
+pub(crate) use verbose_msg;
\ No newline at end of file
@@ -1,5 +1,31 @@
+pub mod address;
+pub mod bubblegum;
+pub mod caip;
+pub mod compact;
+pub mod cost;
+pub mod custody;
+pub mod ed25519;
 pub mod hash;
+pub mod history;
+pub mod logging;
+pub mod profile;
+pub mod pyth;
 pub mod signature;
+pub mod snapshot;
+pub mod token;
+pub mod verification;
 
+pub use address::*;
+pub use bubblegum::*;
+pub use caip::*;
+pub use compact::*;
+pub use cost::*;
+pub use custody::*;
+pub use ed25519::*;
 pub use hash::*;
-pub use signature::*;
\ No newline at end of file
+pub use history::*;
+pub use pyth::*;
+pub use signature::*;
+pub use snapshot::*;
+pub use token::*;
+pub use verification::*;
\ No newline at end of file
@@ -0,0 +1,17 @@
+//! Compute-unit checkpoints (feature `profile`)
+//!
+//! Each checkpoint logs its label followed by the compute units remaining, so operators
+//! can attribute budget usage between two checkpoints from the transaction logs
+//! Without the feature a checkpoint compiles to nothing
+
+/// Log `label` and the compute units remaining
+#[inline(always)]
+pub fn checkpoint(label: &str) {
+    #[cfg(feature = "profile")]
+    {
+        anchor_lang::solana_program::log::sol_log(label);
+        anchor_lang::solana_program::log::sol_log_compute_units();
+    }
+    #[cfg(not(feature = "profile"))]
+    let _ = label;
+}
\ No newline at end of file
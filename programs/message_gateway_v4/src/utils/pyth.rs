@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::errors::GatewayError;
+
+/// Pyth Solana receiver program (owner of PriceUpdateV2 accounts)
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey =
+    anchor_lang::solana_program::pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Anchor discriminator of `PriceUpdateV2`: sha256("account:PriceUpdateV2")[..8]
+const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Fixed-point scale of USD fee amounts (micro-USD)
+pub const USD_DECIMALS: u32 = 6;
+
+/// Native SOL decimals
+pub const LAMPORTS_DECIMALS: u32 = 9;
+
+/// Price read from a Pyth PriceUpdateV2 account: `price * 10^exponent` USD per token
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PythPrice {
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Load a fully verified, fresh price for `feed_id` from a PriceUpdateV2 account
+/// Parsed directly (no SDK dependency). Layout after the discriminator:
+/// write_authority (32), verification_level (enum: Partial{u8} | Full),
+/// feed_id (32), price (i64), conf (u64), exponent (i32), publish_time (i64), ...
+pub fn load_pyth_price(
+    price_update: &AccountInfo,
+    feed_id: &[u8; 32],
+    now: i64,
+    max_age_secs: u64,
+) -> Result<PythPrice> {
+    require_keys_eq!(
+        *price_update.owner,
+        PYTH_RECEIVER_PROGRAM_ID,
+        GatewayError::InvalidPriceFeed
+    );
+    
+    let data = price_update.try_borrow_data()?;
+    require!(
+        data.len() >= 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+        GatewayError::InvalidPriceFeed
+    );
+    
+    // Only accept updates verified by the full Wormhole guardian set
+    let mut offset = 8 + 32;
+    match data.get(offset) {
+        Some(1) => offset += 1,
+        _ => return err!(GatewayError::InvalidPriceFeed),
+    }
+    
+    let read = |offset: usize, len: usize| -> Result<&[u8]> {
+        data.get(offset..offset + len)
+            .ok_or_else(|| GatewayError::InvalidPriceFeed.into())
+    };
+    
+    require!(read(offset, 32)? == feed_id, GatewayError::InvalidPriceFeed);
+    offset += 32;
+    
+    let price = i64::from_le_bytes(read(offset, 8)?.try_into().unwrap());
+    let conf = u64::from_le_bytes(read(offset + 8, 8)?.try_into().unwrap());
+    let exponent = i32::from_le_bytes(read(offset + 16, 4)?.try_into().unwrap());
+    let publish_time = i64::from_le_bytes(read(offset + 20, 8)?.try_into().unwrap());
+    
+    require!(price > 0, GatewayError::InvalidPriceFeed);
+    require!(
+        now.saturating_sub(publish_time) <= max_age_secs as i64,
+        GatewayError::StalePrice
+    );
+    
+    Ok(PythPrice {
+        price,
+        conf,
+        exponent,
+        publish_time,
+    })
+}
+
+/// Convert a micro-USD amount into base units of a token priced by `price`
+/// amount = usd_micros * 10^token_decimals / (price * 10^exponent * 10^USD_DECIMALS), rounded up
+pub fn usd_to_token_amount(usd_micros: u64, price: &PythPrice, token_decimals: u32) -> Result<u64> {
+    // Split the powers of ten between numerator and denominator by the exponent's sign
+    let (num_exp, den_exp) = if price.exponent < 0 {
+        (token_decimals + price.exponent.unsigned_abs(), USD_DECIMALS)
+    } else {
+        (token_decimals, USD_DECIMALS + price.exponent as u32)
+    };
+    
+    let pow10 = |exp: u32| 10u128.checked_pow(exp).ok_or(GatewayError::FeeCalculationOverflow);
+    let numerator = (usd_micros as u128)
+        .checked_mul(pow10(num_exp)?)
+        .ok_or(GatewayError::FeeCalculationOverflow)?;
+    let denominator = (price.price as u128)
+        .checked_mul(pow10(den_exp)?)
+        .ok_or(GatewayError::FeeCalculationOverflow)?;
+    
+    let amount = numerator
+        .checked_add(denominator - 1)
+        .ok_or(GatewayError::FeeCalculationOverflow)?
+        / denominator;
+    
+    u64::try_from(amount).map_err(|_| GatewayError::FeeCalculationOverflow.into())
+}
\ No newline at end of file
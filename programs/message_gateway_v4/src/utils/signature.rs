@@ -2,22 +2,92 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
     ed25519_program,
     instruction::Instruction,
+    keccak,
+    secp256k1_recover::secp256k1_recover,
     sysvar::instructions::{self, load_instruction_at_checked}
 };
 use crate::{
     errors::GatewayError,
-    state::{MessageSignature, SignerRegistry, ValidationResult},
-    constants::{MAX_SIGNATURES_PER_MESSAGE, MIN_SIGNATURES_REQUIRED},
-    utils::hash::validate_message_hash,
+    events::ShadowThresholdMissed,
+    state::{MessageGateway, MessageSignature, SignatureScheme, SignerRegistry, ValidationResult},
+    constants::{MAX_SIGNATURES_PER_MESSAGE, MAX_VERIFICATION_MODULES, MIN_SIGNATURES_REQUIRED},
+    utils::{
+        ed25519::{ED25519_CURRENT_INSTRUCTION, ED25519_OFFSETS_ENTRY_SIZE, ED25519_OFFSETS_START},
+        hash::validate_message_hash, logging::verbose_msg, profile::checkpoint,
+        verification::VerificationModule,
+    },
 };
 
+/// Attestations of one message, handed to validation one at a time
+/// Borsh arguments arrive as a Vec; compact arguments stay in the instruction data and are
+/// decoded on demand (`utils::compact::CompactSignatures`)
+pub trait SignatureSource {
+    /// Number of attestations
+    fn len(&self) -> usize;
+    
+    /// Attestation at `index`
+    fn get(&self, index: usize) -> Result<MessageSignature>;
+    
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl SignatureSource for Vec<MessageSignature> {
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+    
+    fn get(&self, index: usize) -> Result<MessageSignature> {
+        self.as_slice()
+            .get(index)
+            .cloned()
+            .ok_or_else(|| GatewayError::InvalidSignatureFormat.into())
+    }
+}
+
+impl dyn SignatureSource + '_ {
+    /// Attestations in order
+    pub fn iter(&self) -> impl Iterator<Item = Result<MessageSignature>> + '_ {
+        (0..self.len()).map(move |index| self.get(index))
+    }
+}
+
+/// How thoroughly TX2 checks a message's attestations (gateway settings)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VerificationPolicy {
+    /// Verify every signature instead of stopping once all thresholds are met
+    pub full_verification: bool,
+    
+    /// Also compare the signature bytes a precompile instruction verified with the
+    /// attestation's (see `parse_ed25519_instruction`)
+    pub strict_precompile_checks: bool,
+    
+    /// Unix time registry shadow trials are evaluated at
+    pub now: i64,
+}
+
+impl VerificationPolicy {
+    pub fn for_gateway(gateway: &MessageGateway, now: i64) -> Self {
+        Self {
+            full_verification: gateway.full_signature_verification,
+            strict_precompile_checks: gateway.strict_precompile_checks,
+            now,
+        }
+    }
+}
+
 /// Verify Ed25519 signature using Solana's Ed25519 program
 /// This function checks if a valid Ed25519 instruction exists in the same transaction
+/// `ed25519_offset` is how many instructions before the current one TX1 found the matching
+/// precompile (0 = unknown); that position is checked before scanning the transaction
 pub fn verify_ed25519_signature(
     signature: &[u8; 64],
     signer: &Pubkey,
     message_hash: &[u8; 32],
     ix_sysvar_account: &AccountInfo,
+    ed25519_offset: u8,
+    strict: bool,
 ) -> Result<bool> {
     // Validate inputs
     require!(
@@ -31,196 +101,413 @@ pub fn verify_ed25519_signature(
     let current_index = instructions::load_current_index_checked(ix_sysvar_account)
         .map_err(|_| GatewayError::Ed25519VerificationFailed)?;
     
+    // Relayers lay out TX1 and TX2 alike, so the position TX1 matched usually matches here
+    let hinted_index = current_index
+        .checked_sub(ed25519_offset as u16)
+        .filter(|_| ed25519_offset != 0);
+    if let Some(hinted_index) = hinted_index {
+        if let Ok(ix) = load_instruction_at_checked(hinted_index as usize, ix_sysvar_account) {
+            if ix.program_id == ed25519_program::ID
+                && parse_ed25519_instruction(&ix, signature, signer, message_hash, strict)
+                    == Some(true)
+            {
+                return Ok(true);
+            }
+        }
+    }
+    
     // Look for Ed25519 instruction in this transaction
     for i in 0..current_index {
         if let Ok(ix) = load_instruction_at_checked(i as usize, ix_sysvar_account) {
             if ix.program_id == ed25519_program::ID {
-                if let Some(is_valid) = parse_ed25519_instruction(&ix, signature, signer, message_hash) {
-                    if is_valid {
-                        return Ok(true);
-                    }
-                    // Continue loop if this Ed25519 instruction doesn't match our signature
+                let is_valid = parse_ed25519_instruction(&ix, signature, signer, message_hash, strict)
+                    .ok_or(GatewayError::Ed25519InstructionMalformed)?;
+                if is_valid {
+                    return Ok(true);
                 }
+                // Continue loop if this Ed25519 instruction doesn't match our signature
             }
         }
     }
     
-    msg!("No matching Ed25519 instruction found for signature verification");
+    verbose_msg!("No matching Ed25519 instruction found for signature verification");
     Ok(false)
 }
 
-/// Parse Ed25519 instruction data to verify it matches our signature
-fn parse_ed25519_instruction(
+/// Verify a secp256k1 attestation by recovering the public key
+/// The signer identity is keccak256 of the recovered 64-byte uncompressed key
+/// (its last 20 bytes are the signer's Ethereum address)
+pub fn verify_secp256k1_signature(
+    signature: &[u8; 64],
+    recovery_id: u8,
+    signer: &Pubkey,
+    message_hash: &[u8; 32],
+) -> Result<bool> {
+    validate_message_hash(message_hash)?;
+    
+    let recovered = match secp256k1_recover(message_hash, recovery_id, signature) {
+        Ok(pubkey) => pubkey,
+        Err(_) => {
+            verbose_msg!("secp256k1 recovery failed");
+            return Ok(false);
+        }
+    };
+    
+    Ok(secp256k1_signer_id(&recovered.to_bytes()) == *signer)
+}
+
+/// Registry identity for a secp256k1 key: keccak256(uncompressed_pubkey[64])
+pub fn secp256k1_signer_id(uncompressed_pubkey: &[u8; 64]) -> Pubkey {
+    Pubkey::new_from_array(keccak::hash(uncompressed_pubkey).to_bytes())
+}
+
+/// Verify a single attestation with the scheme it is tagged with
+/// `ed25519_offset` and `strict` are only used by Ed25519 attestations (see
+/// `verify_ed25519_signature`)
+pub fn verify_message_signature(
+    signature: &MessageSignature,
+    message_hash: &[u8; 32],
+    ix_sysvar_account: &AccountInfo,
+    ed25519_offset: u8,
+    strict: bool,
+) -> Result<bool> {
+    match signature.scheme {
+        SignatureScheme::Ed25519 => verify_ed25519_signature(
+            &signature.signature,
+            &signature.signer,
+            message_hash,
+            ix_sysvar_account,
+            ed25519_offset,
+            strict,
+        ),
+        SignatureScheme::Secp256k1 { recovery_id } => verify_secp256k1_signature(
+            &signature.signature,
+            recovery_id,
+            &signature.signer,
+            message_hash,
+        ),
+    }
+}
+
+/// Whether an Ed25519 precompile instruction verified `expected_signer`'s signature over
+/// `expected_message` (None if its offsets table is malformed)
+/// The offsets table is parsed entry by entry, and only entries whose signature, key and
+/// message all sit inside the precompile instruction are considered, so the bytes compared
+/// are exactly the ones the runtime verified
+/// The runtime already checked the signature, so a matching key and message prove the
+/// attestation and the signature bytes need no comparison; `strict` compares them anyway
+pub fn parse_ed25519_instruction(
     ix: &Instruction,
     expected_signature: &[u8; 64],
     expected_signer: &Pubkey,
     expected_message: &[u8; 32],
+    strict: bool,
 ) -> Option<bool> {
-    // Ed25519 instruction format:
-    // [0..16]   - signature offset info
-    // [16..80]  - 64-byte signature
-    // [80..112] - 32-byte pubkey
-    // [112..]   - message data
-    
-    if ix.data.len() < 112 + expected_message.len() {
-        return Some(false);
-    }
+    let data = ix.data.as_slice();
+    let count = *data.first()? as usize;
     
-    let ix_signature = &ix.data[16..80];
-    let ix_pubkey = &ix.data[80..112];
-    let ix_message = &ix.data[112..];
-    
-    // Verify all components match
-    let signature_matches = ix_signature == expected_signature;
-    let pubkey_matches = ix_pubkey == expected_signer.as_ref();
-    let message_matches = ix_message == expected_message;
-    
-    msg!(
-        "Ed25519 instruction verification: sig={}, pk={}, msg={}",
-        signature_matches,
-        pubkey_matches,
-        message_matches
-    );
+    for entry in 0..count {
+        let start = ED25519_OFFSETS_START + entry * ED25519_OFFSETS_ENTRY_SIZE;
+        let offsets = data.get(start..start + ED25519_OFFSETS_ENTRY_SIZE)?;
+        let field = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+        
+        // [signature_offset, signature_ix, pubkey_offset, pubkey_ix, message_offset,
+        //  message_size, message_ix]
+        if field(2) != ED25519_CURRENT_INSTRUCTION
+            || field(6) != ED25519_CURRENT_INSTRUCTION
+            || field(12) != ED25519_CURRENT_INSTRUCTION
+        {
+            continue;
+        }
+        
+        let pubkey_offset = field(4) as usize;
+        let message_offset = field(8) as usize;
+        let ix_pubkey = data.get(pubkey_offset..pubkey_offset + 32)?;
+        let ix_message = data.get(message_offset..message_offset + field(10) as usize)?;
+        if ix_pubkey != expected_signer.as_ref() || ix_message != expected_message {
+            continue;
+        }
+        
+        if strict {
+            let signature_offset = field(0) as usize;
+            let ix_signature = data.get(signature_offset..signature_offset + 64)?;
+            if ix_signature != expected_signature {
+                continue;
+            }
+        }
+        return Some(true);
+    }
     
-    Some(signature_matches && pubkey_matches && message_matches)
+    Some(false)
 }
 
 /// Validate three-layer signatures according to Via Labs security model
+/// VIA, Chain and (optional) Project layers plus any extra verifier sets the
+/// project opted into are each treated as an independent verification module
 pub fn validate_three_layer_signatures(
-    signatures: &[MessageSignature],
+    signatures: &dyn SignatureSource,
     message_hash: &[u8; 32],
     via_registry: &SignerRegistry,
     chain_registry: &SignerRegistry,
     project_registry: Option<&SignerRegistry>,
+    extra_verifiers: &[SignerRegistry],
+    policy: VerificationPolicy,
     ix_sysvar_account: &AccountInfo,
 ) -> Result<ValidationResult> {
-    // Input validation
+    validate_layered_signatures(
+        signatures,
+        message_hash,
+        via_registry,
+        chain_registry,
+        project_registry.map(|registry| registry as &dyn VerificationModule),
+        extra_verifiers,
+        &[0u8; MAX_SIGNATURES_PER_MESSAGE],
+        policy,
+        ix_sysvar_account,
+    )
+}
+
+/// Three-layer validation over any layer representation, e.g. live registries or
+/// the snapshots of a registry bundle
+/// `ed25519_offsets` are the per-signature precompile positions TX1 recorded
+/// `policy` selects full verification and strict precompile checks
+#[allow(clippy::too_many_arguments)]
+pub fn validate_layered_signatures(
+    signatures: &dyn SignatureSource,
+    message_hash: &[u8; 32],
+    via_registry: &dyn VerificationModule,
+    chain_registry: &dyn VerificationModule,
+    project_registry: Option<&dyn VerificationModule>,
+    extra_verifiers: &[SignerRegistry],
+    ed25519_offsets: &[u8; MAX_SIGNATURES_PER_MESSAGE],
+    policy: VerificationPolicy,
+    ix_sysvar_account: &AccountInfo,
+) -> Result<ValidationResult> {
+    // Assemble the verification stack without allocating: [VIA, Chain, Project?, Verifier...]
     require!(
-        !signatures.is_empty() && signatures.len() <= MAX_SIGNATURES_PER_MESSAGE,
-        GatewayError::TooManySignatures
+        extra_verifiers.len() <= MAX_VERIFICATION_MODULES - 3,
+        GatewayError::TooManyVerifiers
+    );
+    let mut modules: [&dyn VerificationModule; MAX_VERIFICATION_MODULES] =
+        [via_registry; MAX_VERIFICATION_MODULES];
+    modules[1] = chain_registry;
+    let mut depth = 2;
+    if let Some(proj_registry) = project_registry {
+        modules[depth] = proj_registry;
+        depth += 1;
+    }
+    let first_extra = depth;
+    for verifier in extra_verifiers {
+        modules[depth] = verifier;
+        depth += 1;
+    }
+    
+    let (counts, verified) = validate_verification_modules(
+        signatures,
+        message_hash,
+        &modules[..depth],
+        ed25519_offsets,
+        policy,
+        ix_sysvar_account,
+    )?;
+    
+    let mut validation_result = ValidationResult::new();
+    validation_result.via_signatures = counts[0];
+    validation_result.chain_signatures = counts[1];
+    if project_registry.is_some() {
+        validation_result.project_signatures = counts[2];
+    }
+    validation_result.verifier_signatures[..depth - first_extra]
+        .copy_from_slice(&counts[first_extra..depth]);
+    validation_result.total_valid = verified;
+    
+    verbose_msg!(
+        "Signature validation completed: VIA={}, Chain={}, Project={}, Verifiers={:?}, Total={}",
+        validation_result.via_signatures,
+        validation_result.chain_signatures,
+        validation_result.project_signatures,
+        &validation_result.verifier_signatures[..extra_verifiers.len()],
+        validation_result.total_valid
     );
     
+    Ok(validation_result)
+}
+
+/// Fixed-capacity set of the signers seen in one message, used for duplicate detection
+/// Lives on the stack and never scans more than MAX_SIGNATURES_PER_MESSAGE entries, so
+/// the validator loop's worst-case cost does not depend on the input
+pub struct SignerSet {
+    signers: [Pubkey; MAX_SIGNATURES_PER_MESSAGE],
+    len: usize,
+}
+
+impl SignerSet {
+    pub fn new() -> Self {
+        Self {
+            signers: [Pubkey::default(); MAX_SIGNATURES_PER_MESSAGE],
+            len: 0,
+        }
+    }
+    
+    /// Signers recorded so far, in insertion order
+    pub fn signers(&self) -> &[Pubkey] {
+        &self.signers[..self.len]
+    }
+    
+    /// Record a signer; fails on a repeat or once the set is full
+    pub fn insert(&mut self, signer: &Pubkey) -> Result<()> {
+        require!(!self.signers().contains(signer), GatewayError::DuplicateSigner);
+        require!(
+            self.len < MAX_SIGNATURES_PER_MESSAGE,
+            GatewayError::TooManySignatures
+        );
+        self.signers[self.len] = *signer;
+        self.len += 1;
+        Ok(())
+    }
+}
+
+impl Default for SignerSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Bounds on the number of attestations a message may carry
+pub fn validate_signature_count(count: usize) -> Result<()> {
     require!(
-        signatures.len() >= MIN_SIGNATURES_REQUIRED,
+        count != 0 && count <= MAX_SIGNATURES_PER_MESSAGE,
+        GatewayError::TooManySignatures
+    );
+    require!(
+        count >= MIN_SIGNATURES_REQUIRED,
         GatewayError::TooFewSignatures
     );
-    
+    Ok(())
+}
+
+/// Validate signatures against a stack of up to MAX_VERIFICATION_MODULES verification modules
+/// Returns the number of valid signatures counted for each module, in stack order, and the
+/// number of signatures verified
+/// Signatures are fetched one at a time and bookkeeping uses fixed-size arrays, so validation
+/// does not allocate
+/// Unless `policy.full_verification` is set, validation stops as soon as every module's
+/// threshold is met; the signatures left over are neither verified nor counted
+pub fn validate_verification_modules(
+    signatures: &dyn SignatureSource,
+    message_hash: &[u8; 32],
+    modules: &[&dyn VerificationModule],
+    ed25519_offsets: &[u8; MAX_SIGNATURES_PER_MESSAGE],
+    policy: VerificationPolicy,
+    ix_sysvar_account: &AccountInfo,
+) -> Result<([u8; MAX_VERIFICATION_MODULES], u8)> {
+    // Input validation
+    validate_signature_count(signatures.len())?;
     validate_message_hash(message_hash)?;
+    require!(
+        modules.len() <= MAX_VERIFICATION_MODULES,
+        GatewayError::TooManyVerifiers
+    );
     
-    // Check that registries are enabled
-    require!(via_registry.enabled, GatewayError::SignerRegistryDisabled);
-    require!(chain_registry.enabled, GatewayError::SignerRegistryDisabled);
-    
-    if let Some(proj_registry) = project_registry {
-        require!(proj_registry.enabled, GatewayError::SignerRegistryDisabled);
+    // Check that every module is enabled
+    for module in modules {
+        require!(module.is_enabled(), GatewayError::SignerRegistryDisabled);
     }
     
-    let mut validation_result = ValidationResult::new();
-    let mut used_signers = Vec::new();
+    let mut counts = [0u8; MAX_VERIFICATION_MODULES];
+    let mut verified = 0u8;
+    let mut seen_signers = SignerSet::new();
     
     // Validate each signature
-    for signature in signatures {
+    for (signature, &ed25519_offset) in signatures.iter().zip(ed25519_offsets) {
+        let signature = signature?;
+        
         // Prevent signer reuse
-        require!(
-            !used_signers.contains(&signature.signer),
-            GatewayError::DuplicateSigner
-        );
-        used_signers.push(signature.signer);
+        seen_signers.insert(&signature.signer)?;
         
-        // Verify Ed25519 signature
-        let is_valid_signature = verify_ed25519_signature(
-            &signature.signature,
-            &signature.signer,
+        // Verify the attestation with its tagged scheme
+        let is_valid_signature = verify_message_signature(
+            &signature,
             message_hash,
             ix_sysvar_account,
+            ed25519_offset,
+            policy.strict_precompile_checks,
         )?;
         
         if !is_valid_signature {
-            msg!("Invalid Ed25519 signature from signer: {}", signature.signer);
+            verbose_msg!("Invalid {:?} signature from signer: {}", signature.scheme, signature.signer);
             return Err(GatewayError::InvalidSignature.into());
         }
         
-        // Ethereum-style implicit layer detection: check membership across all registries
-        let is_via_signer = via_registry.is_signer(&signature.signer);
-        let is_chain_signer = chain_registry.is_signer(&signature.signer);
-        let is_project_signer = if let Some(proj_registry) = project_registry {
-            proj_registry.is_signer(&signature.signer)
-        } else {
-            false
-        };
+        // Ethereum-style implicit layer detection: check membership across all modules
+        let mut is_member_of_any = false;
+        for (count, module) in counts.iter_mut().zip(modules) {
+            if module.is_member(&signature.signer) {
+                *count += 1;
+                is_member_of_any = true;
+            }
+        }
         
-        // Require signer to belong to at least one registry
-        if !is_via_signer && !is_chain_signer && !is_project_signer {
-            msg!(
+        // Require signer to belong to at least one module
+        if !is_member_of_any {
+            verbose_msg!(
                 "Unauthorized signer {} - not found in any registry",
                 signature.signer
             );
             return Err(GatewayError::UnauthorizedSigner.into());
         }
+        verified += 1;
+        checkpoint("profile: signature verified");
         
-        // Increment counters based on registry memberships
-        validation_result.increment_for_signer(is_via_signer, is_chain_signer, is_project_signer);
-        
-        msg!(
-            "Valid signature from {} (VIA: {}, Chain: {}, Project: {})",
-            signature.signer,
-            is_via_signer,
-            is_chain_signer,
-            is_project_signer
-        );
+        // Further signatures cannot change the outcome once every threshold is met
+        if !policy.full_verification && thresholds_met(&counts, modules) {
+            break;
+        }
     }
     
-    // Check threshold requirements for each layer
-    validate_signature_thresholds(&validation_result, via_registry, chain_registry, project_registry)?;
-    
-    msg!(
-        "Signature validation completed: VIA={}, Chain={}, Project={}, Total={}",
-        validation_result.via_signatures,
-        validation_result.chain_signatures,
-        validation_result.project_signatures,
-        validation_result.total_valid
-    );
-    
-    Ok(validation_result)
-}
-
-/// Validate that signature thresholds are met for all required layers
-fn validate_signature_thresholds(
-    validation_result: &ValidationResult,
-    via_registry: &SignerRegistry,
-    chain_registry: &SignerRegistry,
-    project_registry: Option<&SignerRegistry>,
-) -> Result<()> {
-    // VIA layer threshold
-    require!(
-        validation_result.via_signatures >= via_registry.required_signatures,
-        GatewayError::InsufficientVIASignatures
-    );
-    
-    // Chain layer threshold
-    require!(
-        validation_result.chain_signatures >= chain_registry.required_signatures,
-        GatewayError::InsufficientChainSignatures
-    );
-    
-    // Project layer threshold (if registry exists)
-    if let Some(proj_registry) = project_registry {
+    // Check threshold requirements for each module; a threshold on shadow trial is still
+    // evaluated, and a miss is reported instead of failing the message
+    for (layer, (count, module)) in counts.iter().zip(modules).enumerate() {
         require!(
-            validation_result.project_signatures >= proj_registry.required_signatures,
-            GatewayError::InsufficientProjectSignatures
+            *count >= module.enforced_signatures(policy.now),
+            module.threshold_error()
         );
+        if *count < module.required_signatures() {
+            msg!(
+                "Shadow threshold missed: layer {} has {} of {} signatures",
+                layer,
+                count,
+                module.required_signatures()
+            );
+            emit!(ShadowThresholdMissed {
+                message_hash: *message_hash,
+                layer: layer as u8,
+                signatures: *count,
+                required_signatures: module.required_signatures(),
+            });
+        }
     }
+    checkpoint("profile: thresholds checked");
     
-    Ok(())
+    Ok((counts, verified))
+}
+
+/// Whether the per-module signature counts meet every module's threshold
+pub fn thresholds_met(counts: &[u8], modules: &[&dyn VerificationModule]) -> bool {
+    counts
+        .iter()
+        .zip(modules)
+        .all(|(count, module)| *count >= module.required_signatures())
 }
 
 /// Simplified signature validation for TX1 (create_tx_pda)
 /// Only requires basic validation, full validation happens in TX2
+/// Returns the Ed25519 precompile offsets found for each signature, recorded for TX2
 pub fn validate_signatures_tx1(
-    signatures: &[MessageSignature],
+    signatures: &dyn SignatureSource,
     message_hash: &[u8; 32],
     ix_sysvar_account: &AccountInfo,
-) -> Result<()> {
+) -> Result<[u8; MAX_SIGNATURES_PER_MESSAGE]> {
     // Basic validation only for TX1
     require!(
         !signatures.is_empty() && signatures.len() <= MAX_SIGNATURES_PER_MESSAGE,
@@ -229,25 +516,80 @@ pub fn validate_signatures_tx1(
     
     validate_message_hash(message_hash)?;
     
-    // Just verify that at least one signature is cryptographically valid
-    let mut valid_signature_found = false;
+    // Just verify that at least one signature is cryptographically valid: a matched
+    // Ed25519 precompile is enough, secp256k1 keys are only recovered otherwise
+    let ed25519_offsets = match_ed25519_instructions(signatures, message_hash, ix_sysvar_account)?;
+    checkpoint("profile: signature verified");
+    let mut valid_signature_found = ed25519_offsets.iter().any(|&offset| offset != 0);
     
-    for signature in signatures {
-        if verify_ed25519_signature(
-            &signature.signature,
-            &signature.signer,
-            message_hash,
-            ix_sysvar_account,
-        )? {
-            valid_signature_found = true;
+    for signature in signatures.iter() {
+        if valid_signature_found {
             break;
         }
+        let signature = signature?;
+        if let SignatureScheme::Secp256k1 { .. } = signature.scheme {
+            valid_signature_found =
+                verify_message_signature(&signature, message_hash, ix_sysvar_account, 0, false)?;
+            checkpoint("profile: signature verified");
+        }
     }
     
     require!(valid_signature_found, GatewayError::InvalidSignature);
     
-    msg!("TX1 signature validation passed with {} signatures", signatures.len());
-    Ok(())
+    verbose_msg!("TX1 signature validation passed with {} signatures", signatures.len());
+    Ok(ed25519_offsets)
+}
+
+/// Match every Ed25519 attestation to a precompile instruction in a single pass over the
+/// instructions before the current one
+/// One precompile instruction may verify several attestations
+/// TX1 matches without strict precompile checks; TX2 re-checks each match under the
+/// gateway's policy
+/// Returns, per signature, how many instructions back its precompile sits (0 = no match)
+/// A precompile whose offsets table cannot be parsed fails with Ed25519InstructionMalformed
+pub fn match_ed25519_instructions(
+    signatures: &dyn SignatureSource,
+    message_hash: &[u8; 32],
+    ix_sysvar_account: &AccountInfo,
+) -> Result<[u8; MAX_SIGNATURES_PER_MESSAGE]> {
+    let current_index = instructions::load_current_index_checked(ix_sysvar_account)
+        .map_err(|_| GatewayError::Ed25519VerificationFailed)?;
+    
+    let mut ed25519_offsets = [0u8; MAX_SIGNATURES_PER_MESSAGE];
+    for i in 0..current_index {
+        // Offsets beyond u8 cannot be recorded (far above any real transaction's length)
+        let Ok(offset) = u8::try_from(current_index - i) else {
+            continue;
+        };
+        let Ok(ix) = load_instruction_at_checked(i as usize, ix_sysvar_account) else {
+            continue;
+        };
+        if ix.program_id != ed25519_program::ID {
+            continue;
+        }
+        for (index, slot) in ed25519_offsets.iter_mut().enumerate().take(signatures.len()) {
+            if *slot != 0 {
+                continue;
+            }
+            let signature = signatures.get(index)?;
+            if signature.scheme != SignatureScheme::Ed25519 {
+                continue;
+            }
+            let is_match = parse_ed25519_instruction(
+                &ix,
+                &signature.signature,
+                &signature.signer,
+                message_hash,
+                false,
+            )
+            .ok_or(GatewayError::Ed25519InstructionMalformed)?;
+            if is_match {
+                *slot = offset;
+            }
+        }
+    }
+    
+    Ok(ed25519_offsets)
 }
 
 /// Helper function to create message signature struct
@@ -258,5 +600,6 @@ pub fn create_message_signature(
     MessageSignature {
         signature: signature_bytes,
         signer: signer_pubkey,
+        scheme: SignatureScheme::Ed25519,
     }
 }
\ No newline at end of file
@@ -0,0 +1,113 @@
+//! Governance state snapshots: the gateway config, chain configs and signer registries as one
+//! canonical blob
+//!
+//! `export_snapshot` builds the blob on-chain and returns it in SNAPSHOT_PAGE_SIZE pages of
+//! return data. Chain configs are ordered by chain id and registries by (type, chain id), so
+//! two exports of the same state are byte-identical; every page carries the blob's size and
+//! keccak256, so pages of different states are never stitched together and a migration to a
+//! new program id can be checked against the hash
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::errors::GatewayError;
+use crate::state::{ChainConfig, MessageGateway, SignerRegistry};
+
+/// Blob bytes per page; with the page header this stays under the 1024-byte return data limit
+pub const SNAPSHOT_PAGE_SIZE: usize = 960;
+
+/// Governance state of one gateway, as read from the program that exported it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GatewaySnapshot {
+    /// Program the state was exported from (bumps in the accounts belong to its PDAs)
+    pub program_id: Pubkey,
+    
+    pub gateway: MessageGateway,
+    
+    /// Ascending by chain id
+    pub chain_configs: Vec<ChainConfig>,
+    
+    /// Ascending by (registry type, chain id)
+    pub registries: Vec<SignerRegistry>,
+}
+
+impl GatewaySnapshot {
+    /// Current snapshot format version
+    pub const VERSION: u8 = 1;
+    
+    /// Encode as `version (u8) || borsh(snapshot)`
+    pub fn encode(&self) -> Result<Vec<u8>> {
+        let mut encoded = vec![Self::VERSION];
+        self.serialize(&mut encoded)
+            .map_err(|_| GatewayError::InvalidSnapshotPage)?;
+        Ok(encoded)
+    }
+    
+    /// Decode a blob, rejecting unknown versions and trailing bytes
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        let (version, mut body) = data.split_first().ok_or(GatewayError::InvalidSnapshotPage)?;
+        require!(*version == Self::VERSION, GatewayError::InvalidSnapshotPage);
+        
+        let snapshot = Self::deserialize(&mut body).map_err(|_| GatewayError::InvalidSnapshotPage)?;
+        require!(body.is_empty(), GatewayError::InvalidSnapshotPage);
+        Ok(snapshot)
+    }
+}
+
+/// One page of a snapshot blob, returned by `export_snapshot`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SnapshotPage {
+    pub page: u16,
+    pub page_count: u16,
+    
+    /// Size and keccak256 of the whole blob
+    pub snapshot_size: u32,
+    pub snapshot_hash: [u8; 32],
+    
+    pub data: Vec<u8>,
+}
+
+/// Page `page` of `blob`
+pub fn snapshot_page(blob: &[u8], page: u16) -> Result<SnapshotPage> {
+    let page_count = blob.len().div_ceil(SNAPSHOT_PAGE_SIZE).max(1);
+    require!((page as usize) < page_count, GatewayError::InvalidSnapshotPage);
+    
+    let start = page as usize * SNAPSHOT_PAGE_SIZE;
+    let end = (start + SNAPSHOT_PAGE_SIZE).min(blob.len());
+    Ok(SnapshotPage {
+        page,
+        page_count: u16::try_from(page_count).map_err(|_| GatewayError::InvalidSnapshotPage)?,
+        snapshot_size: blob.len() as u32,
+        snapshot_hash: keccak::hash(blob).to_bytes(),
+        data: blob[start..end].to_vec(),
+    })
+}
+
+/// Reassemble pages 0..page_count of one export and decode the snapshot, checking that they
+/// all describe the same blob and that it matches its hash
+pub fn assemble_snapshot(pages: &[SnapshotPage]) -> Result<GatewaySnapshot> {
+    let first = pages.first().ok_or(GatewayError::InvalidSnapshotPage)?;
+    require!(
+        pages.len() == first.page_count as usize,
+        GatewayError::InvalidSnapshotPage
+    );
+    
+    let mut blob = Vec::with_capacity(first.snapshot_size as usize);
+    for (index, page) in pages.iter().enumerate() {
+        require!(
+            page.page as usize == index
+                && page.page_count == first.page_count
+                && page.snapshot_size == first.snapshot_size
+                && page.snapshot_hash == first.snapshot_hash,
+            GatewayError::InvalidSnapshotPage
+        );
+        blob.extend_from_slice(&page.data);
+    }
+    
+    require!(
+        blob.len() == first.snapshot_size as usize
+            && keccak::hash(&blob).to_bytes() == first.snapshot_hash,
+        GatewayError::InvalidSnapshotPage
+    );
+    GatewaySnapshot::decode(&blob)
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::AccountMeta, program::invoke_signed};
+use anchor_spl::token_2022::spl_token_2022;
+
+/// `transfer_checked` for SPL Token and Token-2022 mints
+/// `hook_accounts` are appended to the instruction so Token-2022 can invoke the mint's
+/// transfer hook: the hook program, its validation account and the resolved extra metas
+/// (empty for mints without a transfer hook)
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_with_hook<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    hook_accounts: &[AccountInfo<'info>],
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let mut instruction = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        from.key,
+        mint.key,
+        to.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+    
+    let mut account_infos = vec![
+        from.clone(),
+        mint.clone(),
+        to.clone(),
+        authority.clone(),
+    ];
+    for account in hook_accounts {
+        instruction.accounts.push(if account.is_writable {
+            AccountMeta::new(account.key(), account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.key(), account.is_signer)
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(token_program.clone());
+    
+    invoke_signed(&instruction, &account_infos, signer_seeds)?;
+    Ok(())
+}
+
+/// Amount actually credited by a transfer, measured on the receiving account
+/// Accounts for Token-2022 transfer fees withheld from the destination
+pub fn received_amount(balance_before: u64, balance_after: u64) -> Result<u64> {
+    balance_after
+        .checked_sub(balance_before)
+        .ok_or_else(|| error!(crate::errors::GatewayError::InvalidAmount))
+}
\ No newline at end of file
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::SIGNER_REGISTRY_SEED,
+    errors::GatewayError,
+    state::{BundledRegistry, SignerRegistry, SignerRegistryType},
+};
+
+/// Pluggable verification module (LayerZero DVN style)
+/// Each module is an independent verifier set with its own membership and threshold;
+/// a message is valid only if every module in the stack meets its threshold
+pub trait VerificationModule {
+    /// Whether the module is active
+    fn is_enabled(&self) -> bool;
+    
+    /// Whether the signer belongs to this verifier set
+    fn is_member(&self, signer: &Pubkey) -> bool;
+    
+    /// Signatures required from this verifier set
+    fn required_signatures(&self) -> u8;
+    
+    /// Signatures enforced at `now`; below `required_signatures` while the module's
+    /// threshold is on shadow trial, where a miss is only reported
+    fn enforced_signatures(&self, _now: i64) -> u8 {
+        self.required_signatures()
+    }
+    
+    /// Error returned when the threshold is not met
+    fn threshold_error(&self) -> GatewayError;
+}
+
+impl VerificationModule for SignerRegistry {
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+    
+    fn is_member(&self, signer: &Pubkey) -> bool {
+        self.is_signer(signer)
+    }
+    
+    fn required_signatures(&self) -> u8 {
+        self.required_signatures
+    }
+    
+    fn enforced_signatures(&self, now: i64) -> u8 {
+        SignerRegistry::enforced_signatures(self, now)
+    }
+    
+    fn threshold_error(&self) -> GatewayError {
+        match self.registry_type {
+            SignerRegistryType::VIA => GatewayError::InsufficientVIASignatures,
+            SignerRegistryType::Chain => GatewayError::InsufficientChainSignatures,
+            SignerRegistryType::Project => GatewayError::InsufficientProjectSignatures,
+            SignerRegistryType::Verifier(_) => GatewayError::InsufficientVerifierSignatures,
+        }
+    }
+}
+
+impl VerificationModule for BundledRegistry {
+    fn is_enabled(&self) -> bool {
+        self.enabled != 0
+    }
+    
+    fn is_member(&self, signer: &Pubkey) -> bool {
+        self.is_enabled() && self.signers().contains(signer)
+    }
+    
+    fn required_signatures(&self) -> u8 {
+        self.required_signatures
+    }
+    
+    fn enforced_signatures(&self, now: i64) -> u8 {
+        if now < self.shadow_until {
+            self.shadow_enforced_signatures.min(self.required_signatures)
+        } else {
+            self.required_signatures
+        }
+    }
+    
+    fn threshold_error(&self) -> GatewayError {
+        match SignerRegistryType::from_discriminant(self.registry_type) {
+            Some(SignerRegistryType::VIA) => GatewayError::InsufficientVIASignatures,
+            Some(SignerRegistryType::Chain) => GatewayError::InsufficientChainSignatures,
+            Some(SignerRegistryType::Project) => GatewayError::InsufficientProjectSignatures,
+            _ => GatewayError::InsufficientVerifierSignatures,
+        }
+    }
+}
+
+/// Load the extra verifier registries a project requires from remaining accounts
+/// Accounts must be passed in the same order as `verifier_ids` and belong to the gateway
+/// instance `instance_seed` (see `MessageGateway::instance_seed`)
+pub fn load_verifier_registries(
+    remaining_accounts: &[AccountInfo],
+    verifier_ids: &[u8],
+    chain_id: u64,
+    instance_seed: &[u8],
+) -> Result<Vec<SignerRegistry>> {
+    require!(
+        remaining_accounts.len() >= verifier_ids.len(),
+        GatewayError::RegistryNotFound
+    );
+    
+    let mut registries = Vec::with_capacity(verifier_ids.len());
+    
+    for (account_info, &verifier_id) in remaining_accounts.iter().zip(verifier_ids) {
+        // Not created yet (or closed): owned by the system program, or holding no registry
+        require_keys_eq!(
+            *account_info.owner,
+            crate::ID,
+            GatewayError::RegistryNotFound
+        );
+        let registry = SignerRegistry::try_deserialize(&mut &account_info.try_borrow_data()?[..])
+            .map_err(|_| GatewayError::RegistryNotFound)?;
+        let registry_type = SignerRegistryType::Verifier(verifier_id);
+        
+        require!(
+            registry.registry_type == registry_type && registry.chain_id == chain_id,
+            GatewayError::MissingVerifierRegistry
+        );
+        
+        // Verify the account is the canonical registry PDA for this verifier, chain and instance
+        let expected = Pubkey::create_program_address(
+            &[
+                SIGNER_REGISTRY_SEED,
+                &registry_type.discriminant()?.to_le_bytes(),
+                &chain_id.to_le_bytes(),
+                instance_seed,
+                &[registry.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| GatewayError::MissingVerifierRegistry)?;
+        require_keys_eq!(
+            account_info.key(),
+            expected,
+            GatewayError::MissingVerifierRegistry
+        );
+        
+        registries.push(registry);
+    }
+    
+    Ok(registries)
+}
@@ -0,0 +1,35 @@
+//! Host size of the TX2 account contexts
+//!
+//! Large accounts are boxed so each costs a pointer in the accounts struct; this keeps an
+//! unboxed account from slipping back in. `size_of` is measured on the host, so it says
+//! nothing about the SBF stack frame of `try_accounts`: frame overflows are reported by
+//! `cargo build-sbf` ("Stack offset of N exceeded max offset of 4096") and must be
+//! checked there
+
+use std::mem::size_of;
+
+use message_gateway_v4::instructions::process_message::{
+    ProcessMessage, ProcessMessageBundled, ProcessMessageCompact,
+};
+
+/// Bytes the accounts struct may occupy on the host
+const ACCOUNTS_SIZE_LIMIT: usize = 512;
+
+#[test]
+fn process_message_accounts_stay_boxed() {
+    assert!(
+        size_of::<ProcessMessage>() <= ACCOUNTS_SIZE_LIMIT,
+        "ProcessMessage is {} bytes",
+        size_of::<ProcessMessage>()
+    );
+    assert!(
+        size_of::<ProcessMessageCompact>() <= ACCOUNTS_SIZE_LIMIT,
+        "ProcessMessageCompact is {} bytes",
+        size_of::<ProcessMessageCompact>()
+    );
+    assert!(
+        size_of::<ProcessMessageBundled>() <= ACCOUNTS_SIZE_LIMIT,
+        "ProcessMessageBundled is {} bytes",
+        size_of::<ProcessMessageBundled>()
+    );
+}
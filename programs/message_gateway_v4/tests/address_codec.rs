@@ -0,0 +1,62 @@
+//! Address string parsing/formatting vectors for each chain family
+
+use message_gateway_v4::state::AddressFormat;
+use message_gateway_v4::utils::address::{
+    format_address, format_base58, format_bech32, parse_address, parse_base58, parse_bech32,
+    parse_hex,
+};
+
+#[test]
+fn bech32_round_trip() {
+    let address = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu";
+    let (hrp, data) = parse_bech32(address).unwrap();
+    assert_eq!(hrp, "cosmos");
+    assert_eq!(data, (1..=20).collect::<Vec<u8>>());
+    assert_eq!(format_bech32("cosmos", &data).unwrap(), address);
+    
+    // BIP-173 test vectors: valid, bad checksum, mixed case
+    assert_eq!(parse_bech32("A12UEL5L").unwrap().0, "a");
+    assert!(parse_bech32("a12uel5m").is_err());
+    assert!(parse_bech32("A12uEL5L").is_err());
+}
+
+#[test]
+fn base58_round_trip() {
+    let address = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+    let bytes = parse_base58(address).unwrap();
+    assert_eq!(bytes.len(), 32);
+    assert_eq!(format_base58(&bytes), address);
+    
+    assert_eq!(parse_base58("11111111111111111111111111111111").unwrap(), vec![0u8; 32]);
+    assert!(parse_base58("0OIl").is_err());
+}
+
+#[test]
+fn hex_parsing() {
+    assert_eq!(parse_hex("0xAbCd").unwrap(), vec![0xab, 0xcd]);
+    assert!(parse_hex("abcd").is_err());
+    assert!(parse_hex("0xabc").is_err());
+}
+
+#[test]
+fn chain_family_addresses() {
+    let evm = "0x1111111111111111111111111111111111111111";
+    let bytes = parse_address(AddressFormat::Evm, evm, "").unwrap();
+    assert_eq!(bytes, vec![0x11; 20]);
+    assert_eq!(format_address(AddressFormat::Evm, &bytes, "").unwrap(), evm);
+    
+    // Short Move addresses are left-padded to 32 bytes
+    let framework = parse_address(AddressFormat::Move, "0x1", "");
+    assert!(framework.is_err());
+    let framework = parse_address(AddressFormat::Move, "0x01", "").unwrap();
+    assert_eq!(framework.len(), 32);
+    assert_eq!(framework[31], 1);
+    
+    // Cosmos addresses must carry the configured prefix
+    let cosmos = "cosmos1qypqxpq9qcrsszg2pvxq6rs0zqg3yyc5lzv7xu";
+    assert!(parse_address(AddressFormat::Cosmos, cosmos, "cosmos").is_ok());
+    assert!(parse_address(AddressFormat::Cosmos, cosmos, "osmo").is_err());
+    
+    // Wrong length for the family
+    assert!(parse_address(AddressFormat::Evm, "0x11", "").is_err());
+}
\ No newline at end of file
@@ -0,0 +1,30 @@
+//! Compliance blocklist entries for token operations
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::constants::MAX_BLOCKLIST_ENTRIES;
+use message_gateway_v4::state::Blocklist;
+
+#[test]
+fn blocks_and_unblocks_entries() {
+    let mut list = Blocklist { entries: vec![], bump: 0 };
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    list.block(wallet).unwrap();
+    list.block(mint).unwrap();
+    assert!(list.is_blocked(&wallet) && list.is_blocked(&mint));
+    
+    // Duplicates and unknown entries are rejected rather than silently ignored
+    assert!(list.block(wallet).is_err());
+    list.unblock(&wallet).unwrap();
+    assert!(!list.is_blocked(&wallet));
+    assert!(list.unblock(&wallet).is_err());
+}
+
+#[test]
+fn capacity_is_bounded() {
+    let mut list = Blocklist { entries: vec![], bump: 0 };
+    for _ in 0..MAX_BLOCKLIST_ENTRIES {
+        list.block(Pubkey::new_unique()).unwrap();
+    }
+    assert!(list.block(Pubkey::new_unique()).is_err());
+}
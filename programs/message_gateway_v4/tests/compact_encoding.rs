@@ -0,0 +1,89 @@
+//! Compact (varint) encoding of TX1/TX2 message arguments
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorSerialize;
+use message_gateway_v4::state::{MessageSignature, SignatureScheme};
+use message_gateway_v4::test_vectors::HASH_VECTORS;
+use message_gateway_v4::utils::compact::{
+    decode_compact_message, decode_compact_message_lazy, encode_compact_message,
+};
+use message_gateway_v4::utils::signature::SignatureSource;
+
+fn signatures() -> Vec<MessageSignature> {
+    vec![
+        MessageSignature {
+            signature: [1u8; 64],
+            signer: Pubkey::new_unique(),
+            scheme: SignatureScheme::Ed25519,
+        },
+        MessageSignature {
+            signature: [2u8; 64],
+            signer: Pubkey::new_unique(),
+            scheme: SignatureScheme::Secp256k1 { recovery_id: 1 },
+        },
+    ]
+}
+
+#[test]
+fn round_trips_and_beats_borsh() {
+    let signatures = signatures();
+    for v in HASH_VECTORS {
+        let envelope = v.envelope();
+        let encoded = encode_compact_message(&envelope, &signatures);
+        
+        let (decoded, decoded_signatures) =
+            decode_compact_message(envelope.source_chain_id, envelope.tx_id, &encoded).unwrap();
+        assert_eq!(decoded, envelope, "envelope mismatch ({})", v.name);
+        assert_eq!(decoded_signatures.len(), signatures.len());
+        for (decoded, original) in decoded_signatures.iter().zip(&signatures) {
+            assert_eq!(decoded.signature, original.signature);
+            assert_eq!(decoded.signer, original.signer);
+            assert_eq!(decoded.scheme, original.scheme);
+        }
+        
+        let mut borsh = Vec::new();
+        envelope.serialize(&mut borsh).unwrap();
+        signatures.serialize(&mut borsh).unwrap();
+        // source_chain_id and tx_id still travel as 24 Borsh bytes
+        assert!(encoded.len() + 24 + 50 <= borsh.len(), "no saving ({})", v.name);
+    }
+}
+
+#[test]
+fn rejects_malformed_input() {
+    let envelope = HASH_VECTORS[0].envelope();
+    let encoded = encode_compact_message(&envelope, &signatures());
+    let decode = |data: &[u8]| decode_compact_message(envelope.source_chain_id, envelope.tx_id, data);
+    
+    // Truncated and trailing bytes
+    assert!(decode(&encoded[..encoded.len() - 1]).is_err());
+    let mut trailing = encoded.clone();
+    trailing.push(0);
+    assert!(decode(&trailing).is_err());
+    
+    // Unknown flag bits
+    let mut flagged = encoded.clone();
+    flagged[0] |= 0x80;
+    assert!(decode(&flagged).is_err());
+    
+    assert!(decode(&[]).is_err());
+}
+
+#[test]
+fn lazy_signatures_match_eager_decode() {
+    let signatures = signatures();
+    let envelope = HASH_VECTORS[0].envelope();
+    let encoded = encode_compact_message(&envelope, &signatures);
+    
+    let (decoded, lazy) =
+        decode_compact_message_lazy(envelope.source_chain_id, envelope.tx_id, &encoded).unwrap();
+    assert_eq!(decoded, envelope);
+    assert_eq!(lazy.len(), signatures.len());
+    for (index, original) in signatures.iter().enumerate() {
+        let decoded = lazy.get(index).unwrap();
+        assert_eq!(decoded.signature, original.signature);
+        assert_eq!(decoded.signer, original.signer);
+        assert_eq!(decoded.scheme, original.scheme);
+    }
+    assert!(lazy.get(signatures.len()).is_err());
+}
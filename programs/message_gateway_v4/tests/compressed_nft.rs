@@ -0,0 +1,67 @@
+//! Bubblegum leaf hashing and asset ids for compressed NFT bridging
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::utils::{
+    compressed_asset_id, Collection, Creator, MetadataArgs, TokenProgramVersion, TokenStandard,
+    BUBBLEGUM_PROGRAM_ID,
+};
+
+fn metadata() -> MetadataArgs {
+    MetadataArgs {
+        name: "Gateway #1".to_string(),
+        symbol: "GW".to_string(),
+        uri: "https://example.com/1.json".to_string(),
+        seller_fee_basis_points: 500,
+        primary_sale_happened: false,
+        is_mutable: true,
+        edition_nonce: None,
+        token_standard: Some(TokenStandard::NonFungible),
+        collection: Some(Collection { verified: true, key: Pubkey::new_unique() }),
+        uses: None,
+        token_program_version: TokenProgramVersion::Original,
+        creators: vec![Creator { address: Pubkey::new_unique(), verified: true, share: 100 }],
+    }
+}
+
+#[test]
+fn data_hash_commits_to_every_field() {
+    let original = metadata();
+    let hash = original.data_hash().unwrap();
+    assert_eq!(hash, original.clone().data_hash().unwrap());
+    
+    let unverified = MetadataArgs {
+        collection: original.collection.map(|collection| Collection { verified: false, ..collection }),
+        ..original.clone()
+    };
+    assert_ne!(unverified.data_hash().unwrap(), hash);
+    
+    let royalty = MetadataArgs { seller_fee_basis_points: 0, ..original };
+    assert_ne!(royalty.data_hash().unwrap(), hash);
+}
+
+#[test]
+fn creator_hash_commits_to_verification_and_share() {
+    let original = metadata();
+    let creator = original.creators[0];
+    let hash = original.creator_hash();
+    
+    let unverified = MetadataArgs {
+        creators: vec![Creator { verified: false, ..creator }],
+        ..original.clone()
+    };
+    assert_ne!(unverified.creator_hash(), hash);
+    
+    let no_creators = MetadataArgs { creators: Vec::new(), ..original };
+    assert_ne!(no_creators.creator_hash(), hash);
+}
+
+#[test]
+fn asset_id_is_the_bubblegum_pda() {
+    let tree = Pubkey::new_unique();
+    let (expected, _) = Pubkey::find_program_address(
+        &[b"asset", tree.as_ref(), &7u64.to_le_bytes()],
+        &BUBBLEGUM_PROGRAM_ID,
+    );
+    assert_eq!(compressed_asset_id(&tree, 7), expected);
+    assert_ne!(compressed_asset_id(&tree, 8), expected);
+}
\ No newline at end of file
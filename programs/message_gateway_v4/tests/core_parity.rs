@@ -0,0 +1,122 @@
+//! `via-gateway-core` hashes byte-identically to the program over every scheme
+
+use message_gateway_v4::state::{HashAlgorithm, HashScheme, IntegerEncoding, MessageEnvelope};
+use message_gateway_v4::test_vectors::HASH_VECTORS;
+use message_gateway_v4::utils::hash::{
+    create_cross_chain_hash, create_instance_message_hash_for_signing,
+    create_message_hash_for_signing, encode_cross_chain_preimage,
+};
+use via_gateway_core as gateway_core;
+
+fn core_envelope(envelope: &MessageEnvelope) -> gateway_core::Envelope {
+    gateway_core::Envelope {
+        version: envelope.version,
+        tx_id: envelope.tx_id,
+        source_chain_id: envelope.source_chain_id,
+        dest_chain_id: envelope.dest_chain_id,
+        sender: envelope.sender.clone(),
+        recipient: envelope.recipient.clone(),
+        on_chain_data: envelope.on_chain_data.clone(),
+        off_chain_data: envelope.off_chain_data.clone(),
+        off_chain_data_hash: envelope.off_chain_data_hash,
+        message_type: envelope.message_type,
+        deadline: envelope.deadline,
+        sequence: envelope.sequence,
+        protocol_version: envelope.protocol_version,
+        da_pointer: envelope.da_pointer.clone(),
+    }
+}
+
+fn core_scheme(scheme: HashScheme) -> gateway_core::HashScheme {
+    gateway_core::HashScheme {
+        algorithm: match scheme.algorithm {
+            HashAlgorithm::Keccak256 => gateway_core::HashAlgorithm::Keccak256,
+            HashAlgorithm::Sha256 => gateway_core::HashAlgorithm::Sha256,
+            HashAlgorithm::Blake3 => gateway_core::HashAlgorithm::Blake3,
+        },
+        integer_encoding: match scheme.integer_encoding {
+            IntegerEncoding::LittleEndian => gateway_core::IntegerEncoding::LittleEndian,
+            IntegerEncoding::BigEndian => gateway_core::IntegerEncoding::BigEndian,
+        },
+    }
+}
+
+#[test]
+fn matches_program_for_every_vector_and_scheme() {
+    for v in HASH_VECTORS {
+        let envelope = v.envelope();
+        let ours = core_envelope(&envelope);
+        
+        let preimage = encode_cross_chain_preimage(v.integer_encoding, &envelope).unwrap();
+        assert_eq!(ours.encode_preimage(core_scheme(v.hash_scheme()).integer_encoding).unwrap(), preimage);
+        
+        for algorithm in [HashAlgorithm::Keccak256, HashAlgorithm::Sha256, HashAlgorithm::Blake3] {
+            let scheme = HashScheme { algorithm, integer_encoding: v.integer_encoding };
+            assert_eq!(
+                gateway_core::create_cross_chain_hash(core_scheme(scheme), &ours).unwrap(),
+                create_cross_chain_hash(scheme, &envelope).unwrap(),
+                "cross-chain hash mismatch ({}, {:?})",
+                v.name,
+                algorithm
+            );
+            assert_eq!(
+                gateway_core::create_message_hash_for_signing(
+                    core_scheme(scheme),
+                    &message_gateway_v4::ID.to_bytes(),
+                    envelope.dest_chain_id,
+                    &ours,
+                )
+                .unwrap(),
+                create_message_hash_for_signing(scheme, envelope.dest_chain_id, &envelope).unwrap(),
+                "signing hash mismatch ({}, {:?})",
+                v.name,
+                algorithm
+            );
+        }
+    }
+}
+
+#[test]
+fn instance_signing_hashes_match_program() {
+    for v in HASH_VECTORS {
+        let envelope = v.envelope();
+        let ours = core_envelope(&envelope);
+        let scheme = v.hash_scheme();
+        
+        let default_instance = create_message_hash_for_signing(scheme, envelope.dest_chain_id, &envelope).unwrap();
+        let instance = create_instance_message_hash_for_signing(scheme, envelope.dest_chain_id, 7, &envelope).unwrap();
+        assert_ne!(instance, default_instance, "{}", v.name);
+        assert_eq!(
+            gateway_core::create_instance_message_hash_for_signing(
+                core_scheme(scheme),
+                &message_gateway_v4::ID.to_bytes(),
+                envelope.dest_chain_id,
+                7,
+                &ours,
+            )
+            .unwrap(),
+            instance,
+            "instance signing hash mismatch ({})",
+            v.name
+        );
+    }
+}
+
+#[test]
+fn rejects_what_the_program_rejects() {
+    let mut envelope = HASH_VECTORS[0].envelope();
+    envelope.sender = vec![0; 65];
+    assert!(create_cross_chain_hash(HASH_VECTORS[0].hash_scheme(), &envelope).is_err());
+    assert_eq!(
+        gateway_core::create_cross_chain_hash(gateway_core::HashScheme::default(), &core_envelope(&envelope)),
+        Err(gateway_core::CoreError::SenderTooLong)
+    );
+    
+    let mut envelope = HASH_VECTORS[0].envelope();
+    envelope.off_chain_data = vec![1];
+    envelope.off_chain_data_hash = [2; 32];
+    assert_eq!(
+        core_envelope(&envelope).resolved_off_chain_data_hash(),
+        Err(gateway_core::CoreError::OffChainDataHashMismatch)
+    );
+}
@@ -0,0 +1,83 @@
+//! The generated `cpi` module, as another program calling the gateway sees it
+#![cfg(feature = "cpi")]
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::system_program;
+use anchor_lang::ToAccountMetas;
+use message_gateway_v4::{accounts, cpi, ID};
+
+struct Account {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl Account {
+    fn new(is_signer: bool, is_writable: bool) -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner: system_program::ID,
+            lamports: 0,
+            data: Vec::new(),
+            is_signer,
+            is_writable,
+        }
+    }
+    
+    fn info(&mut self) -> AccountInfo<'_> {
+        AccountInfo::new(
+            &self.key,
+            self.is_signer,
+            self.is_writable,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+#[test]
+fn send_message_cpi_accounts_match_the_client_accounts() {
+    // A program sender signs as its own PDA and pays nothing when no fee applies
+    let mut gateway = Account::new(false, true);
+    let mut sender = Account::new(true, false);
+    let mut chain_config = Account::new(false, false);
+    let mut fee_config = Account::new(false, false);
+    let (gateway_key, sender_key, chain_config_key, fee_config_key) =
+        (gateway.key, sender.key, chain_config.key, fee_config.key);
+    
+    let cpi_accounts = cpi::accounts::SendMessage {
+        gateway: gateway.info(),
+        sender: sender.info(),
+        chain_config: Some(chain_config.info()),
+        fee_config: fee_config.info(),
+        channel: None,
+        price_update: None,
+        fee_payer: None,
+        sol_vault: None,
+        fee_vault: None,
+        system_program: None,
+    };
+    let client_accounts = accounts::SendMessage {
+        gateway: gateway_key,
+        sender: sender_key,
+        chain_config: Some(chain_config_key),
+        fee_config: fee_config_key,
+        channel: None,
+        price_update: None,
+        fee_payer: None,
+        sol_vault: None,
+        fee_vault: None,
+        system_program: None,
+    };
+    
+    let metas = cpi_accounts.to_account_metas(None);
+    assert_eq!(metas, client_accounts.to_account_metas(None));
+    // Absent optional accounts are passed as the gateway's program id
+    assert_eq!(metas.iter().filter(|meta| meta.pubkey == ID).count(), 6);
+}
@@ -0,0 +1,150 @@
+//! Matching attestations against Ed25519 precompile instructions through their offsets table
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
+use message_gateway_v4::state::{MessageSignature, SignatureScheme};
+use message_gateway_v4::utils::ed25519::{ed25519_instruction, ed25519_packed_instruction};
+use message_gateway_v4::utils::signature::parse_ed25519_instruction;
+
+const HERE: u16 = u16::MAX;
+
+struct Entry {
+    signature: [u8; 64],
+    signer: Pubkey,
+    message: [u8; 32],
+}
+
+fn entry(seed: u8) -> Entry {
+    Entry {
+        signature: [seed; 64],
+        signer: Pubkey::new_unique(),
+        message: [seed.wrapping_add(1); 32],
+    }
+}
+
+/// Precompile instruction laid out like the runtime's helper: offsets table, then per entry
+/// the key, signature and message; `instruction_index` is written into every entry
+fn precompile(entries: &[Entry], instruction_index: u16) -> Instruction {
+    let mut data = vec![entries.len() as u8, 0];
+    let mut offset = 2 + 14 * entries.len();
+    let mut payload = Vec::new();
+    for entry in entries {
+        let (pubkey, signature, message) = (offset, offset + 32, offset + 96);
+        for field in [
+            signature,
+            instruction_index as usize,
+            pubkey,
+            instruction_index as usize,
+            message,
+            32,
+            instruction_index as usize,
+        ] {
+            data.extend_from_slice(&(field as u16).to_le_bytes());
+        }
+        payload.extend_from_slice(entry.signer.as_ref());
+        payload.extend_from_slice(&entry.signature);
+        payload.extend_from_slice(&entry.message);
+        offset += 128;
+    }
+    data.extend_from_slice(&payload);
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: Vec::new(),
+        data,
+    }
+}
+
+fn matches(ix: &Instruction, entry: &Entry, strict: bool) -> Option<bool> {
+    parse_ed25519_instruction(ix, &entry.signature, &entry.signer, &entry.message, strict)
+}
+
+#[test]
+fn matches_key_and_message_at_parsed_offsets() {
+    // The key comes first here, so fixed positions would read the wrong bytes
+    let verified = entry(1);
+    let ix = precompile(std::slice::from_ref(&verified), HERE);
+    assert_eq!(matches(&ix, &verified, false), Some(true));
+    assert_eq!(matches(&ix, &verified, true), Some(true));
+    
+    let other_signer = Entry { signer: Pubkey::new_unique(), ..verified };
+    assert_eq!(matches(&ix, &other_signer, false), Some(false));
+}
+
+#[test]
+fn signature_bytes_are_only_compared_in_strict_mode() {
+    let verified = entry(2);
+    let ix = precompile(std::slice::from_ref(&verified), HERE);
+    let attestation = Entry {
+        signature: [9u8; 64],
+        signer: verified.signer,
+        message: verified.message,
+    };
+    assert_eq!(matches(&ix, &attestation, false), Some(true));
+    assert_eq!(matches(&ix, &attestation, true), Some(false));
+}
+
+#[test]
+fn message_must_match_in_either_mode() {
+    let verified = entry(3);
+    let ix = precompile(std::slice::from_ref(&verified), HERE);
+    let attestation = Entry { message: [0xee; 32], ..verified };
+    assert_eq!(matches(&ix, &attestation, false), Some(false));
+    assert_eq!(matches(&ix, &attestation, true), Some(false));
+}
+
+#[test]
+fn ignores_entries_reading_other_instructions() {
+    let verified = entry(4);
+    let ix = precompile(std::slice::from_ref(&verified), 0);
+    assert_eq!(matches(&ix, &verified, false), Some(false));
+}
+
+#[test]
+fn finds_attestation_in_multi_signature_instruction() {
+    let entries = [entry(5), entry(6), entry(7)];
+    let ix = precompile(&entries, HERE);
+    for attestation in &entries {
+        assert_eq!(matches(&ix, attestation, true), Some(true));
+    }
+}
+
+#[test]
+fn rejects_truncated_offsets_table() {
+    let verified = entry(8);
+    let mut ix = precompile(std::slice::from_ref(&verified), HERE);
+    ix.data[0] = 2;
+    ix.data.truncate(2 + 14 + 10);
+    assert_eq!(matches(&ix, &verified, false), None);
+    
+    ix.data.clear();
+    assert_eq!(matches(&ix, &verified, false), None);
+}
+
+#[test]
+fn packed_instruction_verifies_every_entry() {
+    let message = [3u8; 32];
+    let entries: Vec<(Pubkey, [u8; 64])> = (0..4).map(|i| (Pubkey::new_unique(), [i; 64])).collect();
+    let ix = ed25519_instruction(&entries, &message);
+    
+    // Offsets table, signature and key per entry, one shared message
+    assert_eq!(ix.data.len(), 2 + 4 * 14 + 4 * 96 + 32);
+    for (signer, signature) in &entries {
+        assert_eq!(parse_ed25519_instruction(&ix, signature, signer, &message, true), Some(true));
+    }
+    let stranger = Pubkey::new_unique();
+    assert_eq!(parse_ed25519_instruction(&ix, &[0; 64], &stranger, &message, false), Some(false));
+}
+
+#[test]
+fn packed_instruction_covers_only_ed25519_attestations() {
+    let attestation = |scheme| MessageSignature { signature: [1u8; 64], signer: Pubkey::new_unique(), scheme };
+    let signatures = [
+        attestation(SignatureScheme::Ed25519),
+        attestation(SignatureScheme::Secp256k1 { recovery_id: 0 }),
+        attestation(SignatureScheme::Ed25519),
+    ];
+    let ix = ed25519_packed_instruction(&signatures, &[7u8; 32]).unwrap();
+    assert_eq!(ix.data[0], 2);
+    
+    assert!(ed25519_packed_instruction(&signatures[1..2], &[7u8; 32]).is_none());
+}
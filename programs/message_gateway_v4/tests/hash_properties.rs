@@ -0,0 +1,200 @@
+//! Property tests of the cross-chain hash pre-image over arbitrary envelopes
+
+use anchor_lang::solana_program::keccak;
+use message_gateway_v4::constants::{
+    MAX_DA_POINTER_SIZE, MAX_OFF_CHAIN_DATA_SIZE, MAX_ON_CHAIN_DATA_SIZE, MAX_RECIPIENT_SIZE, MAX_SENDER_SIZE,
+};
+use message_gateway_v4::state::{HashAlgorithm, HashScheme, IntegerEncoding, MessageEnvelope};
+use message_gateway_v4::test_vectors::HASH_VECTORS;
+use message_gateway_v4::utils::hash::{create_cross_chain_hash, encode_cross_chain_preimage};
+use proptest::prelude::*;
+
+fn bytes(max: usize) -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..=max)
+}
+
+fn encoding() -> impl Strategy<Value = IntegerEncoding> {
+    prop_oneof![Just(IntegerEncoding::LittleEndian), Just(IntegerEncoding::BigEndian)]
+}
+
+prop_compose! {
+    fn envelope()(
+        tx_id in any::<u128>(),
+        source_chain_id in any::<u64>(),
+        dest_chain_id in any::<u64>(),
+        sender in bytes(MAX_SENDER_SIZE),
+        recipient in bytes(MAX_RECIPIENT_SIZE),
+        on_chain_data in bytes(MAX_ON_CHAIN_DATA_SIZE),
+        off_chain_data in bytes(MAX_OFF_CHAIN_DATA_SIZE),
+        message_type in any::<u8>(),
+        deadline in any::<i64>(),
+        sequence in any::<u64>(),
+        protocol_version in any::<u8>(),
+        da_pointer in bytes(MAX_DA_POINTER_SIZE),
+    ) -> MessageEnvelope {
+        MessageEnvelope {
+            version: MessageEnvelope::CURRENT_VERSION,
+            tx_id,
+            source_chain_id,
+            dest_chain_id,
+            sender,
+            recipient,
+            on_chain_data,
+            off_chain_data,
+            off_chain_data_hash: [0; 32],
+            message_type,
+            deadline,
+            sequence,
+            protocol_version,
+            da_pointer,
+        }
+    }
+}
+
+/// Reads a pre-image back field by field
+struct Reader<'a> {
+    data: &'a [u8],
+    encoding: IntegerEncoding,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> [u8; N] {
+        let (head, rest) = self.data.split_at(N);
+        self.data = rest;
+        head.try_into().unwrap()
+    }
+    
+    fn int<const N: usize>(&mut self) -> [u8; N] {
+        let mut bytes = self.take::<N>();
+        if self.encoding == IntegerEncoding::LittleEndian {
+            bytes.reverse();
+        }
+        bytes
+    }
+    
+    fn prefixed(&mut self) -> Vec<u8> {
+        let length = u32::from_be_bytes(self.int()) as usize;
+        let (field, rest) = self.data.split_at(length);
+        self.data = rest;
+        field.to_vec()
+    }
+}
+
+/// Envelope recovered from its pre-image, with the off-chain payload as its commitment
+fn decode(encoding: IntegerEncoding, preimage: &[u8]) -> MessageEnvelope {
+    let mut reader = Reader { data: preimage, encoding };
+    let envelope = MessageEnvelope {
+        version: reader.take::<1>()[0],
+        tx_id: u128::from_be_bytes(reader.int()),
+        source_chain_id: u64::from_be_bytes(reader.int()),
+        dest_chain_id: u64::from_be_bytes(reader.int()),
+        sender: reader.prefixed(),
+        recipient: reader.prefixed(),
+        on_chain_data: reader.prefixed(),
+        off_chain_data: Vec::new(),
+        off_chain_data_hash: reader.take(),
+        message_type: reader.take::<1>()[0],
+        deadline: i64::from_be_bytes(reader.int()),
+        sequence: u64::from_be_bytes(reader.int()),
+        protocol_version: reader.take::<1>()[0],
+        da_pointer: reader.prefixed(),
+    };
+    assert!(reader.data.is_empty(), "trailing pre-image bytes");
+    envelope
+}
+
+/// Rust model of the `abi.encodePacked` layout documented for the EVM gateway (see
+/// `test_vectors`), written out independently of the program's encoder
+/// It checks the encoder against that documented layout, not against the Solidity contract
+fn packed_layout_model(envelope: &MessageEnvelope) -> Vec<u8> {
+    let packed_bytes = |data: &[u8]| [&(data.len() as u32).to_be_bytes()[..], data].concat();
+    [
+        vec![envelope.version],
+        envelope.tx_id.to_be_bytes().to_vec(),
+        envelope.source_chain_id.to_be_bytes().to_vec(),
+        envelope.dest_chain_id.to_be_bytes().to_vec(),
+        packed_bytes(&envelope.sender),
+        packed_bytes(&envelope.recipient),
+        packed_bytes(&envelope.on_chain_data),
+        keccak::hash(&envelope.off_chain_data).to_bytes().to_vec(),
+        vec![envelope.message_type],
+        envelope.deadline.to_be_bytes().to_vec(),
+        envelope.sequence.to_be_bytes().to_vec(),
+        vec![envelope.protocol_version],
+        packed_bytes(&envelope.da_pointer),
+    ]
+    .concat()
+}
+
+#[test]
+fn packed_layout_model_reproduces_snapshots() {
+    for v in HASH_VECTORS.iter().filter(|v| v.integer_encoding == IntegerEncoding::BigEndian) {
+        let preimage = encode_cross_chain_preimage(IntegerEncoding::BigEndian, &v.envelope()).unwrap();
+        assert_eq!(packed_layout_model(&v.envelope()), preimage, "model mismatch ({})", v.name);
+    }
+}
+
+proptest! {
+    /// Every field is recoverable from the pre-image, so distinct envelopes never share one
+    #[test]
+    fn preimage_decodes_back_to_the_envelope(envelope in envelope(), encoding in encoding()) {
+        let preimage = encode_cross_chain_preimage(encoding, &envelope).unwrap();
+        let mut expected = envelope.clone();
+        expected.off_chain_data_hash = envelope.resolved_off_chain_data_hash().unwrap();
+        expected.off_chain_data.clear();
+        prop_assert_eq!(decode(encoding, &preimage), expected);
+    }
+    
+    /// Moving bytes across the boundary of two adjacent variable-length fields changes the hash
+    #[test]
+    fn shifting_field_boundaries_changes_the_hash(
+        envelope in envelope(),
+        encoding in encoding(),
+        shift in 1usize..=16,
+    ) {
+        let scheme = HashScheme { algorithm: HashAlgorithm::Keccak256, integer_encoding: encoding };
+        let original = create_cross_chain_hash(scheme, &envelope).unwrap();
+        
+        let mut shifted = envelope.clone();
+        let moved = shifted.sender.split_off(shifted.sender.len().saturating_sub(shift));
+        prop_assume!(!moved.is_empty() && envelope.recipient.len() + moved.len() <= MAX_RECIPIENT_SIZE);
+        shifted.recipient.splice(0..0, moved);
+        prop_assert_ne!(create_cross_chain_hash(scheme, &shifted).unwrap(), original);
+        
+        let mut shifted = envelope.clone();
+        let moved = shifted.recipient.split_off(shifted.recipient.len().saturating_sub(shift));
+        prop_assume!(!moved.is_empty() && envelope.on_chain_data.len() + moved.len() <= MAX_ON_CHAIN_DATA_SIZE);
+        shifted.on_chain_data.splice(0..0, moved);
+        prop_assert_ne!(create_cross_chain_hash(scheme, &shifted).unwrap(), original);
+    }
+    
+    /// Big-endian pre-images follow the documented `abi.encodePacked` layout, byte for byte
+    #[test]
+    fn big_endian_preimage_matches_packed_layout_model(envelope in envelope()) {
+        let preimage = encode_cross_chain_preimage(IntegerEncoding::BigEndian, &envelope).unwrap();
+        prop_assert_eq!(preimage, packed_layout_model(&envelope));
+    }
+    
+    /// The layout does not move with the protocol version: only its own byte differs, and
+    /// envelope versions other than the current one are rejected rather than re-encoded
+    #[test]
+    fn layout_is_stable_across_versions(
+        envelope in envelope(),
+        encoding in encoding(),
+        protocol_version in any::<u8>(),
+        version in any::<u8>(),
+    ) {
+        let preimage = encode_cross_chain_preimage(encoding, &envelope).unwrap();
+        let other = MessageEnvelope { protocol_version, ..envelope.clone() };
+        let other_preimage = encode_cross_chain_preimage(encoding, &other).unwrap();
+        prop_assert_eq!(preimage.len(), other_preimage.len());
+        
+        let at = preimage.len() - 4 - envelope.da_pointer.len() - 1;
+        let differing: Vec<usize> = (0..preimage.len()).filter(|&i| preimage[i] != other_preimage[i]).collect();
+        let expected: Vec<usize> = if protocol_version == envelope.protocol_version { vec![] } else { vec![at] };
+        prop_assert_eq!(differing, expected);
+        
+        prop_assume!(version != MessageEnvelope::CURRENT_VERSION);
+        prop_assert!(encode_cross_chain_preimage(encoding, &MessageEnvelope { version, ..envelope }).is_err());
+    }
+}
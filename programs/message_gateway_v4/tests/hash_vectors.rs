@@ -0,0 +1,77 @@
+//! Regression snapshots of the hash pipeline (`message_gateway_v4::test_vectors`)
+//! The expected hashes were recorded from this encoder; they pin its output, not EVM parity
+
+use anchor_lang::solana_program::keccak;
+use message_gateway_v4::test_vectors::{HashVector, HASH_VECTORS};
+use message_gateway_v4::utils::hash::{
+    create_cross_chain_hash, create_envelope_digest, create_message_hash_for_signing,
+    encode_cross_chain_preimage,
+};
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn check(v: &HashVector) {
+    let envelope = v.envelope();
+    let scheme = v.hash_scheme();
+    
+    let encoded = encode_cross_chain_preimage(v.integer_encoding, &envelope).unwrap();
+    assert_eq!(encoded, hex(v.preimage), "pre-image mismatch ({})", v.name);
+    
+    let cross_chain_hash = create_cross_chain_hash(scheme, &envelope).unwrap();
+    assert_eq!(cross_chain_hash.to_vec(), hex(v.cross_chain_hash), "hash mismatch ({})", v.name);
+    
+    let signed = create_message_hash_for_signing(scheme, envelope.dest_chain_id, &envelope).unwrap();
+    assert_eq!(signed.to_vec(), hex(v.signing_hash), "signing hash mismatch ({})", v.name);
+}
+
+#[test]
+fn hashes_match_recorded_snapshots() {
+    for v in HASH_VECTORS {
+        check(v);
+    }
+}
+
+#[test]
+fn encodings_produce_distinct_hashes() {
+    for pair in HASH_VECTORS.chunks(2) {
+        assert_ne!(pair[0].integer_encoding, pair[1].integer_encoding);
+        assert_ne!(pair[0].cross_chain_hash, pair[1].cross_chain_hash);
+        assert_ne!(pair[0].signing_hash, pair[1].signing_hash);
+    }
+}
+
+#[test]
+fn commit_only_off_chain_data_hashes_identically() {
+    for v in HASH_VECTORS {
+        let mut envelope = v.envelope();
+        envelope.off_chain_data_hash = keccak::hash(v.off_chain_data).to_bytes();
+        envelope.off_chain_data.clear();
+        
+        let hash = create_cross_chain_hash(v.hash_scheme(), &envelope).unwrap();
+        assert_eq!(hash.to_vec(), hex(v.cross_chain_hash), "commit-only mismatch ({})", v.name);
+        
+        // Data that contradicts the commitment is rejected
+        envelope.off_chain_data = vec![0xff];
+        assert!(create_cross_chain_hash(v.hash_scheme(), &envelope).is_err());
+    }
+}
+
+#[test]
+fn envelope_digest_omits_only_the_payload() {
+    for v in HASH_VECTORS {
+        let envelope = v.envelope();
+        let digest = create_envelope_digest(&envelope).unwrap();
+        
+        let mut slim = v.envelope();
+        slim.on_chain_data.clear();
+        assert_eq!(create_envelope_digest(&slim).unwrap(), digest, "slim mismatch ({})", v.name);
+        
+        slim.deadline += 1;
+        assert_ne!(create_envelope_digest(&slim).unwrap(), digest);
+    }
+}
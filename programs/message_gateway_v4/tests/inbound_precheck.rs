@@ -0,0 +1,73 @@
+//! Ordering of the cheap TX2 checks that run before hashing, syscalls and sysvar scans
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::constants::{DEFAULT_INSTANCE_ID, MAX_ON_CHAIN_DATA_SIZE, PROGRAM_VERSION};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::instructions::process_message::precheck_inbound;
+use message_gateway_v4::state::{MessageEnvelope, MessageGateway};
+use message_gateway_v4::test_vectors::HASH_VECTORS;
+
+fn envelope() -> MessageEnvelope {
+    HASH_VECTORS[0].envelope()
+}
+
+fn gateway(envelope: &MessageEnvelope) -> MessageGateway {
+    MessageGateway {
+        authority: Pubkey::default(),
+        chain_id: envelope.dest_chain_id,
+        system_enabled: true,
+        bump: 0,
+        min_protocol_version: envelope.protocol_version,
+        max_protocol_version: envelope.protocol_version,
+        loopback_enabled: false,
+        price_feed_id: [0u8; 32],
+        max_price_age: 0,
+        treasury: Pubkey::default(),
+        registry_epoch: 0,
+        message_history_tree: Pubkey::default(),
+        message_history_bump: 0,
+        sol_vault_bump: 0,
+        full_signature_verification: false,
+        strict_precompile_checks: false,
+        program_version: PROGRAM_VERSION,
+        instance_id: DEFAULT_INSTANCE_ID,
+    }
+}
+
+#[test]
+fn accepts_well_formed_message() {
+    let envelope = envelope();
+    assert!(precheck_inbound(&gateway(&envelope), &envelope, 2).is_ok());
+}
+
+#[test]
+fn dest_chain_mismatch_is_reported_before_payload_checks() {
+    // An oversized payload would only be caught by the later size validation
+    let mut envelope = envelope();
+    envelope.on_chain_data = vec![0u8; MAX_ON_CHAIN_DATA_SIZE + 1];
+    let gateway = gateway(&envelope);
+    
+    let other_gateway = MessageGateway { chain_id: envelope.dest_chain_id + 1, ..gateway.clone() };
+    let err = precheck_inbound(&other_gateway, &envelope, 2).unwrap_err();
+    assert_eq!(err, GatewayError::InvalidDestChain.into());
+    let err = precheck_inbound(&gateway, &envelope, 2).unwrap_err();
+    assert_eq!(err, GatewayError::OnChainDataTooLarge.into());
+}
+
+#[test]
+fn disabled_system_rejects_first() {
+    let envelope = envelope();
+    let gateway = MessageGateway { system_enabled: false, ..gateway(&envelope) };
+    let err = precheck_inbound(&gateway, &envelope, 0).unwrap_err();
+    assert_eq!(err, GatewayError::SystemDisabled.into());
+}
+
+#[test]
+fn signature_count_is_checked_without_signatures() {
+    let envelope = envelope();
+    let gateway = gateway(&envelope);
+    let err = precheck_inbound(&gateway, &envelope, 1).unwrap_err();
+    assert_eq!(err, GatewayError::TooFewSignatures.into());
+    let err = precheck_inbound(&gateway, &envelope, 9).unwrap_err();
+    assert_eq!(err, GatewayError::TooManySignatures.into());
+}
@@ -0,0 +1,94 @@
+//! Chunked payload assembly in MessageBuffer and the signing hash of buffered messages
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::keccak;
+use message_gateway_v4::constants::{DEFAULT_INSTANCE_ID, MAX_ON_CHAIN_DATA_SIZE};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::state::MessageBuffer;
+use message_gateway_v4::test_vectors::HASH_VECTORS;
+use message_gateway_v4::utils::hash::{
+    create_message_hash_for_signing, create_message_hash_for_signing_with_payload,
+};
+
+fn buffer(payload: &[u8]) -> MessageBuffer {
+    MessageBuffer {
+        relayer: Pubkey::new_unique(),
+        source_chain_id: 1,
+        tx_id: 7,
+        payload_hash: keccak::hash(payload).to_bytes(),
+        total_size: payload.len() as u32,
+        bump: 0,
+        data: Vec::new(),
+    }
+}
+
+#[test]
+fn assembles_chunks_in_order() {
+    let payload: Vec<u8> = (0..3000u32).map(|i| i as u8).collect();
+    let mut buffer = buffer(&payload);
+    
+    for (i, chunk) in payload.chunks(900).enumerate() {
+        assert_eq!(buffer.payload().unwrap_err(), GatewayError::MessageBufferIncomplete.into());
+        buffer.append_chunk((i * 900) as u32, chunk).unwrap();
+    }
+    assert_eq!(buffer.payload().unwrap(), payload.as_slice());
+}
+
+#[test]
+fn rejects_resent_skipped_and_overflowing_chunks() {
+    let payload = [5u8; 100];
+    let mut buffer = buffer(&payload);
+    buffer.append_chunk(0, &payload[..60]).unwrap();
+    
+    let err = buffer.append_chunk(0, &payload[..60]).unwrap_err();
+    assert_eq!(err, GatewayError::InvalidMessageChunk.into());
+    let err = buffer.append_chunk(70, &payload[70..]).unwrap_err();
+    assert_eq!(err, GatewayError::InvalidMessageChunk.into());
+    let err = buffer.append_chunk(60, &[5u8; 41]).unwrap_err();
+    assert_eq!(err, GatewayError::InvalidMessageChunk.into());
+    let err = buffer.append_chunk(60, &[]).unwrap_err();
+    assert_eq!(err, GatewayError::InvalidMessageChunk.into());
+}
+
+#[test]
+fn complete_payload_must_match_hash() {
+    let mut buffer = buffer(&[1u8; 10]);
+    buffer.append_chunk(0, &[2u8; 10]).unwrap();
+    assert_eq!(buffer.payload().unwrap_err(), GatewayError::MessageBufferIncomplete.into());
+}
+
+#[test]
+fn buffered_payload_hashes_like_inline_payload() {
+    let v = &HASH_VECTORS[0];
+    let envelope = v.envelope();
+    let inline = create_message_hash_for_signing(v.hash_scheme(), envelope.dest_chain_id, &envelope).unwrap();
+    
+    let mut slim = envelope.clone();
+    slim.on_chain_data = Vec::new();
+    let buffered = create_message_hash_for_signing_with_payload(
+        v.hash_scheme(),
+        envelope.dest_chain_id,
+        DEFAULT_INSTANCE_ID,
+        &slim,
+        &envelope.on_chain_data,
+    )
+    .unwrap();
+    assert_eq!(buffered, inline);
+}
+
+#[test]
+fn buffered_payload_may_exceed_inline_limit() {
+    let v = &HASH_VECTORS[0];
+    let mut envelope = v.envelope();
+    let payload = vec![9u8; MAX_ON_CHAIN_DATA_SIZE * 4];
+    envelope.on_chain_data = Vec::new();
+    
+    assert!(create_message_hash_for_signing_with_payload(
+        v.hash_scheme(),
+        envelope.dest_chain_id,
+        DEFAULT_INSTANCE_ID,
+        &envelope,
+        &payload,
+    )
+    .is_ok());
+}
@@ -0,0 +1,23 @@
+//! Leaves recorded in the processed-message history tree
+
+use message_gateway_v4::utils::history::message_history_leaf;
+
+fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+#[test]
+fn leaf_commits_to_route_tx_id_and_hash() {
+    let leaf = message_history_leaf(2, 1, &[7u8; 32]);
+    assert_eq!(
+        leaf.to_vec(),
+        hex("ae8bbe81294204c6cfefae576713ef71754568d84473a08b3b225cd44f0fb1d3")
+    );
+    
+    // The same tx_id and hash from another source chain is a different leaf
+    assert_ne!(message_history_leaf(3, 1, &[7u8; 32]), leaf);
+    assert_ne!(message_history_leaf(2, 2, &[7u8; 32]), leaf);
+}
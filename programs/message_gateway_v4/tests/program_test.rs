@@ -0,0 +1,267 @@
+//! End-to-end flow against the real runtime (`anchor build`, then run with `--features program-test`)
+#![cfg(feature = "program-test")]
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{instruction::Instruction, system_program};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use message_gateway_v4::constants::{
+    CHAIN_CONFIG_SEED, COUNTER_SEED, FEE_CONFIG_SEED, GATEWAY_SEED, PROTOCOL_VERSION, SIGNER_REGISTRY_SEED, TX_SEED,
+};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::state::{
+    HashAlgorithm, HashScheme, IntegerEncoding, MessageEnvelope, MessageSignature, SignatureScheme, SignerRegistryType,
+};
+use message_gateway_v4::txbuilder::{build_tx1, build_tx2, ComputeBudget, InboundRoute};
+use message_gateway_v4::utils::hash::create_message_hash_for_signing;
+use message_gateway_v4::{accounts, instruction, ID};
+use solana_program_test::{BanksClient, BanksClientError, ProgramTest};
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError, VersionedTransaction};
+
+const GATEWAY_CHAIN: u64 = 1;
+const SOURCE_CHAIN: u64 = 2;
+const BUDGET: ComputeBudget = ComputeBudget { unit_limit: 1_400_000, unit_price_micro_lamports: 0 };
+
+struct Env {
+    banks: BanksClient,
+    payer: Keypair,
+    validators: [Keypair; 3],
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &ID).0
+}
+
+fn gateway() -> Pubkey {
+    pda(&[GATEWAY_SEED, &GATEWAY_CHAIN.to_le_bytes()])
+}
+
+fn registry(registry_type: SignerRegistryType, chain_id: u64) -> Pubkey {
+    pda(&[SIGNER_REGISTRY_SEED, &registry_type.discriminant().unwrap().to_le_bytes(), &chain_id.to_le_bytes()])
+}
+
+fn tx_id_pda(envelope: &MessageEnvelope) -> Pubkey {
+    pda(&[TX_SEED, &envelope.source_chain_id.to_le_bytes(), &envelope.tx_id.to_le_bytes()])
+}
+
+fn build(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    Instruction::new_with_bytes(ID, &args.data(), accounts.to_account_metas(None))
+}
+
+/// Custom error code a failed transaction returned
+fn error_code(result: Result<(), BanksClientError>) -> u32 {
+    match result.expect_err("transaction should fail").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        other => panic!("unexpected failure: {other:?}"),
+    }
+}
+
+fn envelope(tx_id: u128, source_chain_id: u64, dest_chain_id: u64, sender: Vec<u8>) -> MessageEnvelope {
+    MessageEnvelope {
+        version: MessageEnvelope::CURRENT_VERSION,
+        tx_id,
+        source_chain_id,
+        dest_chain_id,
+        sender,
+        recipient: Pubkey::new_unique().to_bytes().to_vec(),
+        on_chain_data: b"program-test".to_vec(),
+        off_chain_data: Vec::new(),
+        off_chain_data_hash: [0; 32],
+        message_type: MessageEnvelope::MESSAGE_TYPE_GENERIC,
+        deadline: 0,
+        sequence: 0,
+        protocol_version: PROTOCOL_VERSION,
+        da_pointer: Vec::new(),
+    }
+}
+
+impl Env {
+    /// Gateway on GATEWAY_CHAIN plus VIA {0, 1}, Chain {0, 1} and Project {1, 2} registries,
+    /// each requiring two signatures
+    async fn new() -> Self {
+        let (banks, payer, _) = ProgramTest::new("message_gateway_v4", ID, None).start().await;
+        let mut env = Self {
+            banks,
+            payer,
+            validators: [Keypair::new(), Keypair::new(), Keypair::new()],
+        };
+        
+        let authority = env.payer.pubkey();
+        let mut setup = vec![build(
+            accounts::InitializeGateway { gateway: gateway(), authority, system_program: system_program::ID },
+            instruction::InitializeGateway { chain_id: GATEWAY_CHAIN },
+        )];
+        for (registry_type, chain_id, members) in [
+            (SignerRegistryType::VIA, GATEWAY_CHAIN, [0, 1]),
+            (SignerRegistryType::Chain, SOURCE_CHAIN, [0, 1]),
+            (SignerRegistryType::Project, SOURCE_CHAIN, [1, 2]),
+        ] {
+            setup.push(build(
+                accounts::InitializeSignerRegistry {
+                    signer_registry: registry(registry_type, chain_id),
+                    gateway: gateway(),
+                    authority,
+                    system_program: system_program::ID,
+                },
+                instruction::InitializeSignerRegistry {
+                    registry_type,
+                    chain_id,
+                    initial_signers: members.map(|i| env.validators[i].pubkey()).to_vec(),
+                    required_signatures: 2,
+                },
+            ));
+        }
+        env.send(&setup).await.unwrap();
+        env
+    }
+    
+    async fn send(&mut self, instructions: &[Instruction]) -> Result<(), BanksClientError> {
+        let blockhash = self.banks.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(instructions, Some(&self.payer.pubkey()), &[&self.payer], blockhash);
+        self.banks.process_transaction(transaction).await
+    }
+    
+    /// Inbound envelope from SOURCE_CHAIN, attested by the validators at `signers`
+    fn attested(&self, tx_id: u128, signers: &[usize]) -> (MessageEnvelope, Vec<MessageSignature>, [u8; 32]) {
+        let envelope = envelope(tx_id, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]);
+        let message_hash = create_message_hash_for_signing(HashScheme::default(), GATEWAY_CHAIN, &envelope).unwrap();
+        let signatures = signers
+            .iter()
+            .map(|&i| MessageSignature {
+                signature: self.validators[i].sign_message(&message_hash).as_ref().try_into().unwrap(),
+                signer: self.validators[i].pubkey(),
+                scheme: SignatureScheme::Ed25519,
+            })
+            .collect();
+        (envelope, signatures, message_hash)
+    }
+    
+    async fn tx1(&mut self, envelope: &MessageEnvelope, signatures: &[MessageSignature], hash: &[u8; 32]) -> Result<(), BanksClientError> {
+        let relayer = self.payer.pubkey();
+        let blockhash = self.banks.get_latest_blockhash().await?;
+        let message = build_tx1(&relayer, &BUDGET, envelope, signatures, hash, &[], blockhash).unwrap();
+        self.process(message).await
+    }
+    
+    async fn tx2(&mut self, envelope: &MessageEnvelope, signatures: &[MessageSignature], hash: &[u8; 32], project: bool) -> Result<(), BanksClientError> {
+        let relayer = self.payer.pubkey();
+        let route = InboundRoute {
+            gateway_chain_id: GATEWAY_CHAIN,
+            project_registry: project.then(|| registry(SignerRegistryType::Project, SOURCE_CHAIN)),
+            ..Default::default()
+        };
+        let blockhash = self.banks.get_latest_blockhash().await?;
+        let message = build_tx2(&relayer, &BUDGET, &route, envelope, signatures, hash, &[], blockhash).unwrap();
+        self.process(message).await
+    }
+    
+    async fn process(&mut self, message: solana_sdk::message::VersionedMessage) -> Result<(), BanksClientError> {
+        let transaction = VersionedTransaction::try_new(message, &[&self.payer]).unwrap();
+        self.banks.process_transaction(transaction).await
+    }
+    
+    async fn exists(&mut self, address: Pubkey) -> bool {
+        self.banks.get_account(address).await.unwrap().is_some()
+    }
+    
+    fn set_system_enabled(&self, enabled: bool) -> Instruction {
+        build(
+            accounts::SetSystemEnabled { gateway: gateway(), authority: self.payer.pubkey() },
+            instruction::SetSystemEnabled { enabled },
+        )
+    }
+}
+
+#[tokio::test]
+async fn relays_message_through_three_layers() {
+    let mut env = Env::new().await;
+    let (envelope, signatures, hash) = env.attested(1, &[0, 1, 2]);
+    
+    env.tx1(&envelope, &signatures, &hash).await.unwrap();
+    assert!(env.exists(tx_id_pda(&envelope)).await);
+    assert!(env.exists(pda(&[COUNTER_SEED, &SOURCE_CHAIN.to_le_bytes()])).await);
+    
+    env.tx2(&envelope, &signatures, &hash, true).await.unwrap();
+    assert!(!env.exists(tx_id_pda(&envelope)).await);
+}
+
+#[tokio::test]
+async fn rejects_replayed_tx_id() {
+    let mut env = Env::new().await;
+    let (envelope, signatures, hash) = env.attested(2, &[0, 1]);
+    env.tx1(&envelope, &signatures, &hash).await.unwrap();
+    env.tx2(&envelope, &signatures, &hash, false).await.unwrap();
+    
+    // TX2 again finds no TxId PDA, and TX1 again trips the counter's recent filter
+    assert!(env.tx2(&envelope, &signatures, &hash, false).await.is_err());
+    let result = env.tx1(&envelope, &signatures, &hash).await;
+    assert_eq!(error_code(result), GatewayError::RecentTxIdReplay.into());
+}
+
+#[tokio::test]
+async fn project_layer_enforces_its_threshold() {
+    let mut env = Env::new().await;
+    // Validators 0 and 1 satisfy VIA and Chain but only one project signer
+    let (envelope, signatures, hash) = env.attested(3, &[0, 1]);
+    env.tx1(&envelope, &signatures, &hash).await.unwrap();
+    
+    let result = env.tx2(&envelope, &signatures, &hash, true).await;
+    assert_eq!(error_code(result), GatewayError::InsufficientProjectSignatures.into());
+    assert!(env.exists(tx_id_pda(&envelope)).await);
+}
+
+#[tokio::test]
+async fn tx1_requires_matching_precompile_instructions() {
+    let mut env = Env::new().await;
+    let (envelope, signatures, _) = env.attested(4, &[0, 1]);
+    
+    // Precompiles over a different hash verify, but prove nothing about this message
+    assert!(env.tx1(&envelope, &signatures, &[7u8; 32]).await.is_err());
+    assert!(!env.exists(tx_id_pda(&envelope)).await);
+}
+
+#[tokio::test]
+async fn pause_stops_sending_until_unpaused() {
+    let mut env = Env::new().await;
+    let sender = env.payer.pubkey();
+    let chain_config = pda(&[CHAIN_CONFIG_SEED, &SOURCE_CHAIN.to_le_bytes()]);
+    let configure = build(
+        accounts::InitializeChainConfig {
+            chain_config,
+            gateway: gateway(),
+            authority: sender,
+            system_program: system_program::ID,
+        },
+        instruction::InitializeChainConfig {
+            chain_id: SOURCE_CHAIN,
+            hash_algorithm: HashAlgorithm::Keccak256,
+            integer_encoding: IntegerEncoding::LittleEndian,
+        },
+    );
+    env.send(&[configure]).await.unwrap();
+    let send = |tx_id: u128| {
+        let envelope = envelope(tx_id, GATEWAY_CHAIN, SOURCE_CHAIN, sender.to_bytes().to_vec());
+        build(
+            accounts::SendMessage {
+                gateway: gateway(),
+                sender,
+                chain_config: Some(chain_config),
+                fee_config: pda(&[FEE_CONFIG_SEED, &SOURCE_CHAIN.to_le_bytes()]),
+                channel: None,
+                price_update: None,
+                fee_payer: None,
+                sol_vault: None,
+                fee_vault: None,
+                system_program: None,
+            },
+            instruction::SendMessage { envelope, confirmations: 1 },
+        )
+    };
+    
+    env.send(&[env.set_system_enabled(false)]).await.unwrap();
+    assert_eq!(error_code(env.send(&[send(10)]).await), GatewayError::SystemDisabled.into());
+    
+    env.send(&[env.set_system_enabled(true)]).await.unwrap();
+    env.send(&[send(11)]).await.unwrap();
+}
@@ -0,0 +1,53 @@
+//! Rolling-window volume accounting for per-token rate limits
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::state::TokenRateLimit;
+
+fn limit(window_seconds: u64, max_amount: u64) -> TokenRateLimit {
+    TokenRateLimit {
+        mint: Pubkey::default(),
+        window_seconds,
+        max_amount,
+        window_start: 0,
+        current_volume: 0,
+        previous_volume: 0,
+        bump: 0,
+    }
+}
+
+#[test]
+fn caps_volume_within_window() {
+    let mut limit = limit(100, 1_000);
+    assert!(limit.consume(600, 10).is_ok());
+    assert!(limit.consume(400, 50).is_ok());
+    assert!(limit.consume(1, 99).is_err());
+    assert_eq!(limit.current_volume, 1_000);
+}
+
+#[test]
+fn previous_window_decays_linearly() {
+    let mut limit = limit(100, 1_000);
+    limit.consume(1_000, 50).unwrap();
+    
+    // 20s into the next window, 80% of the previous window still counts
+    assert_eq!(limit.rolling_volume(100), 1_000);
+    assert!(limit.consume(201, 120).is_err());
+    assert!(limit.consume(200, 120).is_ok());
+    assert_eq!(limit.rolling_volume(120), 1_000);
+}
+
+#[test]
+fn idle_windows_reset_volume() {
+    let mut limit = limit(100, 1_000);
+    limit.consume(1_000, 0).unwrap();
+    
+    assert!(limit.consume(1_000, 250).is_ok());
+    assert_eq!(limit.previous_volume, 0);
+    assert_eq!(limit.window_start, 200);
+}
+
+#[test]
+fn zero_cap_pauses_transfers() {
+    let mut limit = limit(100, 0);
+    assert!(limit.consume(1, 0).is_err());
+}
\ No newline at end of file
@@ -0,0 +1,75 @@
+//! `via-receiver-interface` matches the ABI `deliver_message` calls recipients with
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use message_gateway_v4::constants::DELIVERY_AUTHORITY_SEED;
+use message_gateway_v4::payloads::GenericCallPayload;
+use via_receiver_interface::{
+    delivery_authority, unpack_delivery, ReceiverError, ViaMessage, GATEWAY_PROGRAM_ID,
+    ON_MESSAGE_RECEIVED_DISCRIMINATOR,
+};
+
+fn message() -> ViaMessage {
+    ViaMessage {
+        tx_id: 7,
+        source_chain_id: 137,
+        sender: vec![0xab; 20],
+        on_chain_data: b"payload".to_vec(),
+    }
+}
+
+#[test]
+fn interface_constants_match_the_program() {
+    assert_eq!(GATEWAY_PROGRAM_ID, message_gateway_v4::ID);
+    assert_eq!(
+        ON_MESSAGE_RECEIVED_DISCRIMINATOR,
+        GenericCallPayload::anchor_discriminator("on_message_received")
+    );
+    
+    let recipient = Pubkey::new_unique();
+    assert_eq!(
+        delivery_authority(&message_gateway_v4::ID, &recipient).0,
+        Pubkey::find_program_address(&[DELIVERY_AUTHORITY_SEED, recipient.as_ref()], &message_gateway_v4::ID).0
+    );
+}
+
+#[test]
+fn recipients_accept_only_gateway_signed_deliveries() {
+    let program_id = Pubkey::new_unique();
+    let authority = delivery_authority(&GATEWAY_PROGRAM_ID, &program_id).0;
+    let passed = Pubkey::new_unique();
+    let data = message().instruction_data().unwrap();
+    
+    let (mut authority_lamports, mut passed_lamports) = (0, 0);
+    let (mut authority_data, mut passed_data) = (Vec::new(), Vec::new());
+    let mut accounts = vec![
+        AccountInfo::new(&authority, true, false, &mut authority_lamports, &mut authority_data, &GATEWAY_PROGRAM_ID, false, 0),
+        AccountInfo::new(&passed, false, true, &mut passed_lamports, &mut passed_data, &program_id, false, 0),
+    ];
+    
+    let (decoded, passed_through) = unpack_delivery(&program_id, &GATEWAY_PROGRAM_ID, &accounts, &data).unwrap();
+    assert_eq!(decoded, message());
+    assert_eq!(passed_through.len(), 1);
+    assert_eq!(*passed_through[0].key, passed);
+    
+    // Another recipient's authority, an unsigned authority and foreign data are rejected
+    let other = Pubkey::new_unique();
+    assert_eq!(
+        unpack_delivery(&other, &GATEWAY_PROGRAM_ID, &accounts, &data).unwrap_err(),
+        ReceiverError::InvalidDeliveryAuthority
+    );
+    assert_eq!(
+        unpack_delivery(&program_id, &GATEWAY_PROGRAM_ID, &accounts, &data[1..]).unwrap_err(),
+        ReceiverError::InvalidDiscriminator
+    );
+    let mut trailing = data.clone();
+    trailing.push(0);
+    assert_eq!(
+        unpack_delivery(&program_id, &GATEWAY_PROGRAM_ID, &accounts, &trailing).unwrap_err(),
+        ReceiverError::InvalidMessage
+    );
+    accounts[0].is_signer = false;
+    assert_eq!(
+        unpack_delivery(&program_id, &GATEWAY_PROGRAM_ID, &accounts, &data).unwrap_err(),
+        ReceiverError::MissingDeliveryAuthority
+    );
+}
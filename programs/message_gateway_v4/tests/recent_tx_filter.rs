@@ -0,0 +1,54 @@
+//! CounterPDA bloom filter of tx_ids that recently passed TX1
+
+use message_gateway_v4::constants::{RECENT_TX_FILTER_BYTES, RECENT_TX_FILTER_CAPACITY};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::state::CounterPDA;
+
+fn counter() -> CounterPDA {
+    CounterPDA {
+        source_chain_id: 2,
+        highest_tx_id_seen: 0,
+        recent_tx_filter: [0u8; RECENT_TX_FILTER_BYTES],
+        recent_tx_count: 0,
+        bump: 0,
+    }
+}
+
+#[test]
+fn recorded_ids_are_recent() {
+    let mut counter = counter();
+    assert!(!counter.maybe_recent(7));
+    
+    counter.record_recent(7);
+    assert!(counter.maybe_recent(7));
+    assert!(!counter.maybe_recent(8));
+    assert_eq!(counter.recent_tx_count, 1);
+}
+
+#[test]
+fn filter_clears_at_capacity() {
+    let mut counter = counter();
+    for tx_id in 1..=RECENT_TX_FILTER_CAPACITY as u128 {
+        counter.record_recent(tx_id);
+    }
+    assert!((1..=RECENT_TX_FILTER_CAPACITY as u128).all(|tx_id| counter.maybe_recent(tx_id)));
+    
+    // Ids just past the window are rarely false positives
+    let false_positives = (1_000..2_000u128).filter(|&tx_id| counter.maybe_recent(tx_id)).count();
+    assert!(false_positives <= 2);
+    
+    counter.record_recent(1_000_000);
+    assert_eq!(counter.recent_tx_count, 1);
+    assert!(!counter.maybe_recent(1));
+    assert!(counter.maybe_recent(1_000_000));
+}
+
+#[test]
+fn source_chain_check_tells_uninitialized_from_foreign_counters() {
+    let counter = counter();
+    assert!(counter.check_source_chain(2).is_ok());
+    assert_eq!(counter.check_source_chain(3).unwrap_err(), GatewayError::CounterChainMismatch.into());
+    
+    let blank = CounterPDA { source_chain_id: 0, ..counter };
+    assert_eq!(blank.check_source_chain(2).unwrap_err(), GatewayError::CounterNotInitialized.into());
+}
@@ -0,0 +1,70 @@
+//! Registry bundle snapshots of the VIA / Chain / Project layers
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::state::{BundledRegistry, SignerRegistry, SignerRegistryType};
+use message_gateway_v4::utils::verification::VerificationModule;
+
+fn registry(registry_type: SignerRegistryType, signers: Vec<Pubkey>, enabled: bool) -> SignerRegistry {
+    SignerRegistry {
+        registry_type,
+        authority: Pubkey::default(),
+        signers,
+        required_signatures: 2,
+        chain_id: 1,
+        enabled,
+        bump: 0,
+        extra_verifiers: Vec::new(),
+        shadow_until: 0,
+        shadow_enforced_signatures: 0,
+        created_slot: 0,
+        previous_required_signatures: 2,
+    }
+}
+
+#[test]
+fn snapshot_matches_live_registry() {
+    let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let live = registry(SignerRegistryType::Chain, signers.clone(), true);
+    let bundled = BundledRegistry::from_registry(&live).unwrap();
+    
+    assert_eq!(bundled.signers(), signers.as_slice());
+    assert_eq!(bundled.required_signatures(), live.required_signatures());
+    assert!(bundled.is_member(&signers[2]));
+    assert!(!bundled.is_member(&Pubkey::default()));
+    assert_eq!(bundled.threshold_error() as u32, live.threshold_error() as u32);
+}
+
+#[test]
+fn disabled_snapshot_has_no_members() {
+    let signer = Pubkey::new_unique();
+    let bundled = BundledRegistry::from_registry(
+        &registry(SignerRegistryType::VIA, vec![signer], false),
+    )
+    .unwrap();
+    assert!(!bundled.is_enabled());
+    assert!(!bundled.is_member(&signer));
+}
+
+#[test]
+fn rejects_oversized_registry() {
+    let signers = (0..11).map(|_| Pubkey::new_unique()).collect();
+    let live = registry(SignerRegistryType::Project, signers, true);
+    assert!(BundledRegistry::from_registry(&live).is_err());
+}
+
+#[test]
+fn snapshot_carries_shadow_trial() {
+    let signers: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+    let live = SignerRegistry {
+        shadow_until: 1_000,
+        shadow_enforced_signatures: 1,
+        ..registry(SignerRegistryType::Chain, signers, true)
+    };
+    let bundled = BundledRegistry::from_registry(&live).unwrap();
+    
+    for now in [0, 999, 1_000, 2_000] {
+        assert_eq!(bundled.enforced_signatures(now), live.enforced_signatures(now));
+    }
+    assert_eq!(bundled.enforced_signatures(999), 1);
+    assert_eq!(bundled.enforced_signatures(1_000), 2);
+}
@@ -0,0 +1,93 @@
+//! Relay cost model used by relayers to size compute budgets and fees
+
+use anchor_lang::prelude::Rent;
+use message_gateway_v4::constants::{MAX_ON_CHAIN_DATA_SIZE, MAX_SIGNATURES_PER_MESSAGE};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::state::TxIdPDA;
+use message_gateway_v4::utils::cost::{estimate_relay_cost, RelayCostEstimate, LAMPORTS_PER_SIGNATURE};
+
+#[test]
+fn grows_with_payload_and_signatures() {
+    let rent = Rent::default();
+    let small = estimate_relay_cost(0, 2, &rent).unwrap();
+    let large_payload = estimate_relay_cost(512, 2, &rent).unwrap();
+    let more_signatures = estimate_relay_cost(0, 4, &rent).unwrap();
+    
+    assert!(large_payload.tx1_compute_units > small.tx1_compute_units);
+    assert!(large_payload.tx2_size > small.tx2_size);
+    assert!(more_signatures.tx2_compute_units > small.tx2_compute_units);
+    assert_eq!(more_signatures.tx1_fee_lamports, 5 * LAMPORTS_PER_SIGNATURE);
+    assert_eq!(small.tx_id_pda_rent_lamports, rent.minimum_balance(8 + TxIdPDA::SIZE));
+}
+
+#[test]
+fn rejects_out_of_range_inputs() {
+    let rent = Rent::default();
+    let err = estimate_relay_cost(MAX_ON_CHAIN_DATA_SIZE + 1, 2, &rent).unwrap_err();
+    assert_eq!(err, GatewayError::OnChainDataTooLarge.into());
+    let err = estimate_relay_cost(0, 1, &rent).unwrap_err();
+    assert_eq!(err, GatewayError::TooFewSignatures.into());
+    let err = estimate_relay_cost(0, MAX_SIGNATURES_PER_MESSAGE + 1, &rent).unwrap_err();
+    assert_eq!(err, GatewayError::TooManySignatures.into());
+}
+
+#[test]
+fn large_messages_need_lookup_tables() {
+    let rent = Rent::default();
+    assert!(estimate_relay_cost(0, 2, &rent).unwrap().fits_legacy_transactions());
+    assert!(!estimate_relay_cost(MAX_ON_CHAIN_DATA_SIZE, MAX_SIGNATURES_PER_MESSAGE, &rent)
+        .unwrap()
+        .fits_legacy_transactions());
+}
+
+#[test]
+fn limit_adds_margin() {
+    assert_eq!(RelayCostEstimate::compute_unit_limit(100_000), 120_000);
+}
+
+/// The size model must match what `txbuilder` actually assembles
+#[cfg(feature = "txbuilder")]
+#[test]
+fn sizes_match_assembled_transactions() {
+    use anchor_lang::prelude::Pubkey;
+    use anchor_lang::solana_program::{instruction::Instruction, message::Message};
+    use message_gateway_v4::state::{MessageSignature, SignatureScheme};
+    use message_gateway_v4::test_vectors::HASH_VECTORS;
+    use message_gateway_v4::txbuilder::{
+        create_tx_pda_instruction, ed25519_verify_instructions, process_message_instruction,
+        ComputeBudget, InboundRoute,
+    };
+    
+    let relayer = Pubkey::new_unique();
+    let budget = ComputeBudget { unit_limit: 400_000, unit_price_micro_lamports: 1 };
+    let size = |gateway_ix: Instruction, signatures: &[MessageSignature]| {
+        let mut instructions = budget.instructions();
+        instructions.extend(ed25519_verify_instructions(signatures, &[7u8; 32]));
+        instructions.push(gateway_ix);
+        1 + 64 + Message::new(&instructions, Some(&relayer)).serialize().len()
+    };
+    
+    for (payload_size, signature_count) in [(0, 2), (300, 3), (700, 2)] {
+        let mut envelope = HASH_VECTORS[0].envelope();
+        envelope.sender = vec![0xab; 20];
+        envelope.recipient = vec![0xcd; 32];
+        envelope.on_chain_data = vec![1u8; payload_size];
+        envelope.off_chain_data = Vec::new();
+        envelope.da_pointer = Vec::new();
+        envelope.sequence = 0;
+        let signatures: Vec<MessageSignature> = (0..signature_count)
+            .map(|_| MessageSignature {
+                signature: [3u8; 64],
+                signer: Pubkey::new_unique(),
+                scheme: SignatureScheme::Ed25519,
+            })
+            .collect();
+        let route = InboundRoute { gateway_chain_id: envelope.dest_chain_id, ..Default::default() };
+        
+        let estimate = estimate_relay_cost(payload_size, signature_count, &Rent::default()).unwrap();
+        let tx1 = create_tx_pda_instruction(&relayer, &envelope, &signatures);
+        let tx2 = process_message_instruction(&relayer, &route, &envelope, &signatures);
+        assert_eq!(estimate.tx1_size as usize, size(tx1, &signatures));
+        assert_eq!(estimate.tx2_size as usize, size(tx2, &signatures));
+    }
+}
@@ -0,0 +1,44 @@
+//! Relayer pool accounting of TxId PDA rent
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::state::RelayerPool;
+
+const RENT: u64 = 1_900_000;
+
+fn pool(balance: u64) -> RelayerPool {
+    RelayerPool {
+        relayer: Pubkey::new_unique(),
+        balance,
+        rent_outstanding: 0,
+        bump: 0,
+    }
+}
+
+#[test]
+fn advance_lends_rent_and_settle_returns_it() {
+    let mut pool = pool(3 * RENT);
+    pool.advance_rent(RENT).unwrap();
+    pool.advance_rent(RENT).unwrap();
+    assert_eq!((pool.balance, pool.rent_outstanding), (RENT, 2 * RENT));
+    
+    pool.settle_rent(RENT).unwrap();
+    assert_eq!((pool.balance, pool.rent_outstanding), (2 * RENT, RENT));
+}
+
+#[test]
+fn advance_requires_balance() {
+    let mut pool = pool(RENT - 1);
+    let err = pool.advance_rent(RENT).unwrap_err();
+    assert_eq!(err, GatewayError::RelayerPoolInsufficient.into());
+    assert_eq!((pool.balance, pool.rent_outstanding), (RENT - 1, 0));
+}
+
+#[test]
+fn settle_tolerates_rent_growth() {
+    // A PDA may hold more than the rent advanced (e.g. lamports sent to it directly)
+    let mut pool = pool(RENT);
+    pool.advance_rent(RENT).unwrap();
+    pool.settle_rent(RENT + 5).unwrap();
+    assert_eq!((pool.balance, pool.rent_outstanding), (RENT + 5, 0));
+}
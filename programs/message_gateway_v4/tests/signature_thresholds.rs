@@ -0,0 +1,97 @@
+//! Threshold check that lets TX2 stop verifying signatures early
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::state::{SignerRegistry, SignerRegistryType};
+use message_gateway_v4::utils::signature::thresholds_met;
+use message_gateway_v4::utils::verification::VerificationModule;
+
+fn registry(registry_type: SignerRegistryType, required_signatures: u8) -> SignerRegistry {
+    SignerRegistry {
+        registry_type,
+        authority: Pubkey::default(),
+        signers: (0..4).map(|_| Pubkey::new_unique()).collect(),
+        required_signatures,
+        chain_id: 1,
+        enabled: true,
+        bump: 0,
+        extra_verifiers: Vec::new(),
+        shadow_until: 0,
+        shadow_enforced_signatures: 0,
+        created_slot: 0,
+        previous_required_signatures: required_signatures,
+    }
+}
+
+#[test]
+fn met_only_once_every_layer_is_met() {
+    let via = registry(SignerRegistryType::VIA, 2);
+    let chain = registry(SignerRegistryType::Chain, 1);
+    let modules: [&dyn VerificationModule; 2] = [&via, &chain];
+    
+    assert!(!thresholds_met(&[0, 0], &modules));
+    assert!(!thresholds_met(&[2, 0], &modules));
+    assert!(!thresholds_met(&[1, 1], &modules));
+    assert!(thresholds_met(&[2, 1], &modules));
+    assert!(thresholds_met(&[3, 2], &modules));
+}
+
+#[test]
+fn ignores_counts_past_the_module_stack() {
+    // Counts are kept for MAX_VERIFICATION_MODULES slots; unused slots must not matter
+    let via = registry(SignerRegistryType::VIA, 1);
+    let project = registry(SignerRegistryType::Project, 1);
+    let modules: [&dyn VerificationModule; 2] = [&via, &project];
+    
+    assert!(thresholds_met(&[1, 1, 0, 0, 0], &modules));
+}
+
+#[test]
+fn shadow_trial_enforces_the_lower_threshold_until_it_ends() {
+    let mut chain = registry(SignerRegistryType::Chain, 3);
+    chain.shadow_until = 100;
+    chain.shadow_enforced_signatures = 2;
+    
+    assert_eq!(chain.enforced_signatures(99), 2);
+    assert_eq!(chain.enforced_signatures(100), 3);
+    
+    // A threshold lowered during the trial below the trial threshold wins
+    chain.required_signatures = 1;
+    assert_eq!(chain.enforced_signatures(99), 1);
+}
+
+#[test]
+fn shadow_trials_keep_the_threshold_enforced_before_a_raise() {
+    let mut chain = registry(SignerRegistryType::Chain, 2);
+    chain.created_slot = 10;
+    
+    // Only a registry trialled from its creation slot may enforce nothing
+    assert_eq!(chain.min_shadow_enforced_signatures(0, 10), 0);
+    assert_eq!(chain.min_shadow_enforced_signatures(0, 11), 2);
+    chain.shadow_until = 100;
+    assert!(chain.is_new(99, 11));
+    assert!(!chain.is_new(100, 11));
+    
+    // Raising the threshold after the first trial ended keeps the old one as the floor
+    chain.shadow_until = 0;
+    chain.set_required_signatures(3, 200);
+    assert_eq!(chain.min_shadow_enforced_signatures(200, 11), 2);
+    
+    // A raise during a trial keeps the trial's threshold as the floor
+    chain.shadow_until = 300;
+    chain.shadow_enforced_signatures = 1;
+    chain.set_required_signatures(4, 250);
+    assert_eq!(chain.previous_required_signatures, 1);
+    assert_eq!(chain.min_shadow_enforced_signatures(250, 11), 1);
+}
+
+#[test]
+fn early_stop_waits_for_the_threshold_on_trial() {
+    // Verification goes on until the trial threshold is met, so misses are measured
+    let mut via = registry(SignerRegistryType::VIA, 2);
+    via.shadow_until = 100;
+    via.shadow_enforced_signatures = 1;
+    let modules: [&dyn VerificationModule; 1] = [&via];
+    
+    assert!(!thresholds_met(&[1], &modules));
+    assert!(thresholds_met(&[2], &modules));
+}
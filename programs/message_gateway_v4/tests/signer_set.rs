@@ -0,0 +1,32 @@
+//! Fixed-capacity duplicate-signer detection used by the validator loop
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::constants::MAX_SIGNATURES_PER_MESSAGE;
+use message_gateway_v4::utils::signature::SignerSet;
+
+#[test]
+fn rejects_repeated_signer() {
+    let signer = Pubkey::new_unique();
+    let mut set = SignerSet::new();
+    assert!(set.insert(&signer).is_ok());
+    assert!(set.insert(&Pubkey::new_unique()).is_ok());
+    assert!(set.insert(&signer).is_err());
+    assert_eq!(set.signers().len(), 2);
+}
+
+#[test]
+fn default_key_is_not_treated_as_seen() {
+    // Unused slots hold the default key; it only counts once actually inserted
+    let mut set = SignerSet::new();
+    assert!(set.insert(&Pubkey::default()).is_ok());
+    assert!(set.insert(&Pubkey::default()).is_err());
+}
+
+#[test]
+fn capacity_is_bounded() {
+    let mut set = SignerSet::new();
+    for _ in 0..MAX_SIGNATURES_PER_MESSAGE {
+        assert!(set.insert(&Pubkey::new_unique()).is_ok());
+    }
+    assert!(set.insert(&Pubkey::new_unique()).is_err());
+}
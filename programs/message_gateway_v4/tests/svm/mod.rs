@@ -0,0 +1,865 @@
+//! LiteSVM harness shared by the `svm_*` suites: the built program in an in-process runtime,
+//! a gateway with VIA {0, 1}, Chain {2, 3} and Project {3, 4} registries (two signatures
+//! each), SOURCE_CHAIN configured as a destination, and builders for every transaction the
+//! suites send
+#![allow(dead_code)]
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{instruction::Instruction, system_program, sysvar};
+use anchor_lang::{AccountDeserialize, AccountSerialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address, spl_associated_token_account};
+use anchor_spl::token::spl_token;
+use litesvm::LiteSVM;
+use message_gateway_v4::constants::{
+    BLOCKLIST_SEED, BRIDGE_PEER_SEED, CHAIN_CONFIG_SEED, COUNTER_SEED, DEFAULT_INSTANCE_ID, FAST_FILL_SEED,
+    FEE_CONFIG_SEED, FEE_VAULT_SEED, GATEWAY_SEED, MESSAGE_HISTORY_SEED, PROTOCOL_VERSION, RATE_LIMIT_SEED,
+    RELAYER_POOL_SEED, SIGNER_REGISTRY_SEED, SOL_VAULT_SEED, TOKEN_CLAIM_SEED, TOKEN_CONFIG_SEED, TX_SEED,
+};
+use message_gateway_v4::payloads::{TokenTransferPayload, PAYLOAD_KIND_TOKEN_TRANSFER};
+use message_gateway_v4::state::{
+    instance_seed, HashAlgorithm, HashScheme, IntegerEncoding, MessageEnvelope, MessageSignature, SignatureScheme,
+    SignerRegistryType, TokenSettings,
+};
+use message_gateway_v4::txbuilder::{
+    build_tx1, build_tx2, compile_message, create_tx_pda_instruction_for_instance, create_tx_pda_instruction_with_payer,
+    ed25519_verify_instructions, route_remaining_accounts, ComputeBudget, InboundRoute,
+};
+use message_gateway_v4::utils::bubblegum::{ACCOUNT_COMPRESSION_PROGRAM_ID, NOOP_PROGRAM_ID};
+use message_gateway_v4::utils::hash::create_instance_message_hash_for_signing;
+use message_gateway_v4::{accounts, instruction, ID};
+use solana_sdk::account::Account;
+use solana_sdk::instruction::InstructionError;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError, VersionedTransaction};
+
+pub const GATEWAY_CHAIN: u64 = 1;
+pub const SOURCE_CHAIN: u64 = 2;
+pub const BUDGET: ComputeBudget = ComputeBudget { unit_limit: 1_400_000, unit_price_micro_lamports: 0 };
+
+/// Signers attesting every layer, project included
+pub const ALL_LAYERS: &[usize] = &[0, 1, 2, 3, 4];
+
+/// Decimals of the suites' mints, bridged 1:1 by `token_settings`
+pub const DECIMALS: u8 = 6;
+
+/// Token bridge of SOURCE_CHAIN, registered by `set_bridge_peer`
+pub const REMOTE_BRIDGE: [u8; 20] = [0xbb; 20];
+
+/// Message history tree shape: 32 leaves and 8 concurrent changes
+const HISTORY_DEPTH: usize = 5;
+const HISTORY_BUFFER: usize = 8;
+
+/// Size of the account-compression header preceding the tree, which starts with its
+/// sequence number
+const HISTORY_HEADER_SIZE: usize = 56;
+
+pub type TxResult = Result<(), TransactionError>;
+
+pub struct Svm {
+    pub svm: LiteSVM,
+    pub authority: Keypair,
+    pub validators: Vec<Keypair>,
+}
+
+/// Inbound message with its attestations
+pub struct Attested {
+    pub envelope: MessageEnvelope,
+    pub signatures: Vec<MessageSignature>,
+    pub hash: [u8; 32],
+}
+
+pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &ID).0
+}
+
+pub fn gateway() -> Pubkey {
+    pda(&[GATEWAY_SEED, &GATEWAY_CHAIN.to_le_bytes()])
+}
+
+pub fn registry(registry_type: SignerRegistryType, chain_id: u64) -> Pubkey {
+    pda(&[SIGNER_REGISTRY_SEED, &registry_type.discriminant().unwrap().to_le_bytes(), &chain_id.to_le_bytes()])
+}
+
+/// Gateway instance `instance_id` on GATEWAY_CHAIN (`gateway()` for the default instance)
+pub fn instance_gateway(instance_id: u16) -> Pubkey {
+    pda(&[GATEWAY_SEED, &GATEWAY_CHAIN.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+pub fn instance_registry(registry_type: SignerRegistryType, chain_id: u64, instance_id: u16) -> Pubkey {
+    pda(&[
+        SIGNER_REGISTRY_SEED,
+        &registry_type.discriminant().unwrap().to_le_bytes(),
+        &chain_id.to_le_bytes(),
+        &instance_seed(instance_id),
+    ])
+}
+
+pub fn tx_id_pda(envelope: &MessageEnvelope) -> Pubkey {
+    pda(&[TX_SEED, &envelope.source_chain_id.to_le_bytes(), &envelope.tx_id.to_le_bytes()])
+}
+
+/// TxId PDA of a message delivered to gateway instance `instance_id`
+pub fn instance_tx_id_pda(envelope: &MessageEnvelope, instance_id: u16) -> Pubkey {
+    pda(&[
+        TX_SEED,
+        &envelope.source_chain_id.to_le_bytes(),
+        &envelope.tx_id.to_le_bytes(),
+        &instance_seed(instance_id),
+    ])
+}
+
+pub fn chain_config(chain_id: u64) -> Pubkey {
+    pda(&[CHAIN_CONFIG_SEED, &chain_id.to_le_bytes()])
+}
+
+/// ChainConfig PDA of gateway instance `instance_id`
+pub fn instance_chain_config(chain_id: u64, instance_id: u16) -> Pubkey {
+    pda(&[CHAIN_CONFIG_SEED, &chain_id.to_le_bytes(), &instance_seed(instance_id)])
+}
+
+pub fn fee_config(dest_chain_id: u64) -> Pubkey {
+    pda(&[FEE_CONFIG_SEED, &dest_chain_id.to_le_bytes()])
+}
+
+pub fn fee_vault() -> Pubkey {
+    pda(&[FEE_VAULT_SEED])
+}
+
+pub fn relayer_pool(relayer: &Pubkey) -> Pubkey {
+    pda(&[RELAYER_POOL_SEED, relayer.as_ref()])
+}
+
+pub fn bridge_peer(chain_id: u64) -> Pubkey {
+    pda(&[BRIDGE_PEER_SEED, &chain_id.to_le_bytes()])
+}
+
+pub fn token_config(mint: &Pubkey) -> Pubkey {
+    pda(&[TOKEN_CONFIG_SEED, mint.as_ref()])
+}
+
+pub fn rate_limit(mint: &Pubkey) -> Pubkey {
+    pda(&[RATE_LIMIT_SEED, mint.as_ref()])
+}
+
+pub fn blocklist() -> Pubkey {
+    pda(&[BLOCKLIST_SEED])
+}
+
+pub fn fast_fill(envelope: &MessageEnvelope) -> Pubkey {
+    pda(&[FAST_FILL_SEED, &envelope.source_chain_id.to_le_bytes(), &envelope.tx_id.to_le_bytes()])
+}
+
+pub fn sol_vault() -> Pubkey {
+    pda(&[SOL_VAULT_SEED])
+}
+
+/// Gateway escrow of `mint`: the gateway PDA's associated token account
+pub fn escrow(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&gateway(), mint)
+}
+
+pub fn build(accounts: impl ToAccountMetas, args: impl InstructionData) -> Instruction {
+    Instruction::new_with_bytes(ID, &args.data(), accounts.to_account_metas(None))
+}
+
+/// Gateway error a failed transaction returned
+pub fn error_code(result: TxResult) -> u32 {
+    match result.expect_err("transaction should fail") {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        other => panic!("unexpected failure: {other:?}"),
+    }
+}
+
+pub fn envelope(tx_id: u128, source_chain_id: u64, dest_chain_id: u64, sender: Vec<u8>) -> MessageEnvelope {
+    MessageEnvelope {
+        version: MessageEnvelope::CURRENT_VERSION,
+        tx_id,
+        source_chain_id,
+        dest_chain_id,
+        sender,
+        recipient: vec![0xcd; 32],
+        on_chain_data: b"litesvm".to_vec(),
+        off_chain_data: Vec::new(),
+        off_chain_data_hash: [0; 32],
+        message_type: MessageEnvelope::MESSAGE_TYPE_GENERIC,
+        deadline: 0,
+        sequence: 0,
+        protocol_version: PROTOCOL_VERSION,
+        da_pointer: Vec::new(),
+    }
+}
+
+/// Inbound bridge-module message from SOURCE_CHAIN's bridge peer to the gateway program
+pub fn bridge_envelope(tx_id: u128, message_type: u8, on_chain_data: Vec<u8>) -> MessageEnvelope {
+    MessageEnvelope {
+        recipient: ID.to_bytes().to_vec(),
+        on_chain_data,
+        message_type,
+        ..envelope(tx_id, SOURCE_CHAIN, GATEWAY_CHAIN, REMOTE_BRIDGE.to_vec())
+    }
+}
+
+/// Settings bridging DECIMALS mints 1:1 without fees or transfer bounds
+pub fn token_settings() -> TokenSettings {
+    TokenSettings { remote_decimals: DECIMALS, ..Default::default() }
+}
+
+/// Per-message PDA a token delivery creates under `seed`
+pub fn message_pda(seed: &[u8], envelope: &MessageEnvelope) -> Pubkey {
+    pda(&[seed, &envelope.source_chain_id.to_le_bytes(), &envelope.tx_id.to_le_bytes()])
+}
+
+/// Inbound transfer of `amount` of `mint` to `recipient` from SOURCE_CHAIN's bridge peer
+pub fn token_transfer(tx_id: u128, mint: &Pubkey, recipient: &Pubkey, amount: u64) -> MessageEnvelope {
+    let payload = TokenTransferPayload {
+        token: mint.to_bytes(),
+        amount,
+        sender: vec![0xaa; 20],
+        recipient: recipient.to_bytes().to_vec(),
+    };
+    bridge_envelope(tx_id, PAYLOAD_KIND_TOKEN_TRANSFER, payload.encode().unwrap())
+}
+
+impl Svm {
+    pub fn new() -> Self {
+        let mut env = Self::bare();
+        let mut setup = vec![build(
+            accounts::InitializeGateway {
+                gateway: gateway(),
+                authority: env.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::InitializeGateway { chain_id: GATEWAY_CHAIN },
+        )];
+        for (registry_type, chain_id, members) in [
+            (SignerRegistryType::VIA, GATEWAY_CHAIN, [0, 1]),
+            (SignerRegistryType::Chain, SOURCE_CHAIN, [2, 3]),
+            (SignerRegistryType::Project, SOURCE_CHAIN, [3, 4]),
+        ] {
+            let signers = members.map(|i| env.validators[i].pubkey()).to_vec();
+            setup.push(env.init_registry(registry_type, chain_id, signers, 2));
+        }
+        setup.push(build(
+            accounts::InitializeChainConfig {
+                chain_config: chain_config(SOURCE_CHAIN),
+                gateway: gateway(),
+                authority: env.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::InitializeChainConfig {
+                chain_id: SOURCE_CHAIN,
+                hash_algorithm: HashAlgorithm::Keccak256,
+                integer_encoding: IntegerEncoding::LittleEndian,
+            },
+        ));
+        env.send(&setup).unwrap();
+        env
+    }
+    
+    /// The built program and a funded authority, before any setup
+    pub fn bare() -> Self {
+        let program = std::env::var("SBF_OUT_DIR")
+            .unwrap_or_else(|_| concat!(env!("CARGO_MANIFEST_DIR"), "/../../target/deploy").to_string());
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(ID, format!("{program}/message_gateway_v4.so"))
+            .expect("build the program first (anchor build)");
+        
+        let authority = Keypair::new();
+        svm.airdrop(&authority.pubkey(), 100_000_000_000).unwrap();
+        Self {
+            svm,
+            authority,
+            validators: (0..5).map(|_| Keypair::new()).collect(),
+        }
+    }
+    
+    /// Send `instructions` signed by the authority, under a fresh blockhash so identical
+    /// transactions can be resent
+    pub fn send(&mut self, instructions: &[Instruction]) -> TxResult {
+        self.send_as(&self.authority.insecure_clone(), instructions)
+    }
+    
+    pub fn send_as(&mut self, signer: &Keypair, instructions: &[Instruction]) -> TxResult {
+        self.svm.expire_blockhash();
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signer.pubkey()),
+            &[signer],
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(transaction).map(|_| ()).map_err(|failed| failed.err)
+    }
+    
+    /// Program logs of `instructions` sent by the authority, which must succeed
+    pub fn send_logs(&mut self, instructions: &[Instruction]) -> Vec<String> {
+        self.svm.expire_blockhash();
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.authority.pubkey()),
+            &[&self.authority],
+            self.svm.latest_blockhash(),
+        );
+        self.svm.send_transaction(transaction).expect("transaction should succeed").logs
+    }
+    
+    /// Return data of `instruction` simulated as the authority, which must succeed
+    pub fn simulate_return(&mut self, instruction: Instruction) -> Vec<u8> {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.authority.pubkey()),
+            &[&self.authority],
+            self.svm.latest_blockhash(),
+        );
+        let simulated = self.svm.simulate_transaction(transaction).expect("simulation should succeed");
+        simulated.meta.return_data.data
+    }
+    
+    fn process(&mut self, message: VersionedMessage) -> TxResult {
+        let transaction = VersionedTransaction::try_new(message, &[&self.authority]).unwrap();
+        self.svm.send_transaction(transaction).map(|_| ()).map_err(|failed| failed.err)
+    }
+    
+    /// Rewrite a program account's state in place, bypassing the program
+    pub fn edit<T: AccountSerialize + AccountDeserialize>(&mut self, address: &Pubkey, edit: impl FnOnce(&mut T)) {
+        let mut account = self.svm.get_account(address).expect("account exists");
+        let mut state = T::try_deserialize(&mut &account.data[..]).unwrap();
+        edit(&mut state);
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        self.svm.set_account(*address, account).unwrap();
+    }
+    
+    pub fn exists(&self, address: &Pubkey) -> bool {
+        self.svm.get_account(address).is_some_and(|account| account.lamports > 0)
+    }
+    
+    /// Inbound envelope from SOURCE_CHAIN attested by the validators at `signers`
+    pub fn attest(&self, envelope: MessageEnvelope, signers: &[usize]) -> Attested {
+        self.attest_for_instance(envelope, signers, DEFAULT_INSTANCE_ID)
+    }
+    
+    /// Inbound envelope attested for gateway instance `instance_id`
+    pub fn attest_for_instance(&self, envelope: MessageEnvelope, signers: &[usize], instance_id: u16) -> Attested {
+        let hash = create_instance_message_hash_for_signing(
+            HashScheme::default(),
+            envelope.dest_chain_id,
+            instance_id,
+            &envelope,
+        )
+        .unwrap();
+        let signatures = signers
+            .iter()
+            .map(|&i| MessageSignature {
+                signature: self.validators[i].sign_message(&hash).as_ref().try_into().unwrap(),
+                signer: self.validators[i].pubkey(),
+                scheme: SignatureScheme::Ed25519,
+            })
+            .collect();
+        Attested { envelope, signatures, hash }
+    }
+    
+    pub fn inbound(&self, tx_id: u128, signers: &[usize]) -> Attested {
+        self.attest(envelope(tx_id, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]), signers)
+    }
+    
+    pub fn tx1(&mut self, message: &Attested) -> TxResult {
+        self.svm.expire_blockhash();
+        let relayer = self.authority.pubkey();
+        let compiled = build_tx1(
+            &relayer,
+            &BUDGET,
+            &message.envelope,
+            &message.signatures,
+            &message.hash,
+            &[],
+            self.svm.latest_blockhash(),
+        )
+        .unwrap();
+        self.process(compiled)
+    }
+    
+    /// TX1 on gateway instance `instance_id`
+    pub fn tx1_for_instance(&mut self, message: &Attested, instance_id: u16) -> TxResult {
+        self.svm.expire_blockhash();
+        let relayer = self.authority.pubkey();
+        let mut instructions = BUDGET.instructions();
+        instructions.extend(ed25519_verify_instructions(&message.signatures, &message.hash));
+        instructions.push(create_tx_pda_instruction_for_instance(
+            &relayer,
+            instance_id,
+            &message.envelope,
+            &message.signatures,
+        ));
+        let compiled = compile_message(&relayer, &instructions, &[], self.svm.latest_blockhash()).unwrap();
+        self.process(compiled)
+    }
+    
+    /// TX1 with the TxId PDA rent advanced from the relayer's pool
+    pub fn tx1_pooled(&mut self, message: &Attested) -> TxResult {
+        self.svm.expire_blockhash();
+        let relayer = self.authority.pubkey();
+        let mut instructions = BUDGET.instructions();
+        instructions.extend(ed25519_verify_instructions(&message.signatures, &message.hash));
+        instructions.push(build(
+            accounts::CreateTxPdaPooled {
+                tx_id_pda: tx_id_pda(&message.envelope),
+                gateway: gateway(),
+                counter_pda: pda(&[COUNTER_SEED, &message.envelope.source_chain_id.to_le_bytes()]),
+                relayer_pool: relayer_pool(&relayer),
+                chain_config: chain_config(message.envelope.source_chain_id),
+                relayer,
+                instructions: sysvar::instructions::ID,
+                system_program: system_program::ID,
+            },
+            instruction::CreateTxPdaPooled {
+                envelope: message.envelope.clone(),
+                signatures: message.signatures.clone(),
+            },
+        ));
+        let compiled = compile_message(&relayer, &instructions, &[], self.svm.latest_blockhash()).unwrap();
+        self.process(compiled)
+    }
+    
+    /// TX1 with the TxId PDA rent and the fee paid by `payer` instead of the relayer
+    pub fn tx1_funded(&mut self, message: &Attested, payer: &Keypair) -> TxResult {
+        self.svm.expire_blockhash();
+        let relayer = self.authority.pubkey();
+        let mut instructions = BUDGET.instructions();
+        instructions.extend(ed25519_verify_instructions(&message.signatures, &message.hash));
+        instructions.push(create_tx_pda_instruction_with_payer(
+            &relayer,
+            &payer.pubkey(),
+            &message.envelope,
+            &message.signatures,
+        ));
+        let compiled = compile_message(&payer.pubkey(), &instructions, &[], self.svm.latest_blockhash()).unwrap();
+        let transaction = VersionedTransaction::try_new(compiled, &[payer, &self.authority]).unwrap();
+        self.svm.send_transaction(transaction).map(|_| ()).map_err(|failed| failed.err)
+    }
+    
+    pub fn tx2(&mut self, message: &Attested, route: InboundRoute) -> TxResult {
+        self.svm.expire_blockhash();
+        let relayer = self.authority.pubkey();
+        let route = InboundRoute { gateway_chain_id: GATEWAY_CHAIN, ..route };
+        let compiled = build_tx2(
+            &relayer,
+            &BUDGET,
+            &route,
+            &message.envelope,
+            &message.signatures,
+            &message.hash,
+            &[],
+            self.svm.latest_blockhash(),
+        )
+        .unwrap();
+        self.process(compiled)
+    }
+    
+    /// TX2 sending `gateway_instruction` (e.g. `deliver_message`) after the message's
+    /// precompile instructions
+    pub fn tx2_with(&mut self, message: &Attested, gateway_instruction: Instruction) -> TxResult {
+        self.svm.expire_blockhash();
+        let relayer = self.authority.pubkey();
+        let mut instructions = BUDGET.instructions();
+        instructions.extend(ed25519_verify_instructions(&message.signatures, &message.hash));
+        instructions.push(gateway_instruction);
+        let compiled = compile_message(&relayer, &instructions, &[], self.svm.latest_blockhash()).unwrap();
+        self.process(compiled)
+    }
+    
+    /// Route validated by the project registry as well
+    pub fn with_project() -> InboundRoute {
+        InboundRoute {
+            project_registry: Some(registry(SignerRegistryType::Project, SOURCE_CHAIN)),
+            ..Default::default()
+        }
+    }
+    
+    pub fn init_registry(
+        &self,
+        registry_type: SignerRegistryType,
+        chain_id: u64,
+        initial_signers: Vec<Pubkey>,
+        required_signatures: u8,
+    ) -> Instruction {
+        build(
+            accounts::InitializeSignerRegistry {
+                signer_registry: registry(registry_type, chain_id),
+                gateway: gateway(),
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::InitializeSignerRegistry {
+                registry_type,
+                chain_id,
+                initial_signers,
+                required_signatures,
+            },
+        )
+    }
+    
+    /// send_message naming the destination's chain config when one exists
+    pub fn send_message(&self, sender: &Pubkey, envelope: MessageEnvelope) -> Instruction {
+        let destination = chain_config(envelope.dest_chain_id);
+        build(
+            accounts::SendMessage {
+                gateway: gateway(),
+                sender: *sender,
+                chain_config: self.exists(&destination).then_some(destination),
+                fee_config: fee_config(envelope.dest_chain_id),
+                channel: None,
+                price_update: None,
+                fee_payer: None,
+                sol_vault: None,
+                fee_vault: None,
+                system_program: None,
+            },
+            instruction::SendMessage { envelope, confirmations: 1 },
+        )
+    }
+    
+    /// Valid outbound envelope from the authority
+    pub fn outbound(&self, tx_id: u128) -> MessageEnvelope {
+        envelope(tx_id, GATEWAY_CHAIN, SOURCE_CHAIN, self.authority.pubkey().to_bytes().to_vec())
+    }
+    
+    pub fn deposit_relayer_pool(&self, amount: u64) -> Instruction {
+        let relayer = self.authority.pubkey();
+        build(
+            accounts::DepositRelayerPool {
+                gateway: gateway(),
+                relayer_pool: relayer_pool(&relayer),
+                relayer,
+                system_program: system_program::ID,
+            },
+            instruction::DepositRelayerPool { amount },
+        )
+    }
+    
+    /// Lamport send fee for messages to SOURCE_CHAIN
+    pub fn set_fee_config(&self, base_fee: u64, fee_per_byte: u64) -> Instruction {
+        build(
+            accounts::SetFeeConfig {
+                fee_config: fee_config(SOURCE_CHAIN),
+                fee_vault: fee_vault(),
+                gateway: gateway(),
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::SetFeeConfig { dest_chain_id: SOURCE_CHAIN, base_fee, fee_per_byte },
+        )
+    }
+    
+    /// Relayer refund for messages from SOURCE_CHAIN
+    pub fn set_relayer_refund(&self, relayer_refund: u64, relayer_fee_share_bps: u16) -> Instruction {
+        build(
+            accounts::SetRelayerRefund {
+                fee_config: fee_config(SOURCE_CHAIN),
+                gateway: gateway(),
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::SetRelayerRefund { chain_id: SOURCE_CHAIN, relayer_refund, relayer_fee_share_bps },
+        )
+    }
+    
+    pub fn set_system_enabled(&self, enabled: bool) -> Instruction {
+        build(
+            accounts::SetSystemEnabled { gateway: gateway(), authority: self.authority.pubkey() },
+            instruction::SetSystemEnabled { enabled },
+        )
+    }
+    
+    /// New SPL Token mint with DECIMALS decimals, minted by the authority
+    pub fn create_mint(&mut self) -> Pubkey {
+        let mint = Pubkey::new_unique();
+        let mut data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::Some(self.authority.pubkey()),
+            supply: 0,
+            decimals: DECIMALS,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut data);
+        let account = Account {
+            lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: spl_token::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm.set_account(mint, account).unwrap();
+        mint
+    }
+    
+    /// `owner`'s associated token account for `mint`, created if missing, after minting
+    /// `amount` into it
+    pub fn token_account(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let authority = self.authority.pubkey();
+        let mut instructions = vec![spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+            &authority,
+            owner,
+            mint,
+            &spl_token::ID,
+        )];
+        if amount > 0 {
+            let account = get_associated_token_address(owner, mint);
+            instructions.push(spl_token::instruction::mint_to(&spl_token::ID, mint, &account, &authority, &[], amount).unwrap());
+        }
+        self.send(&instructions).unwrap();
+        get_associated_token_address(owner, mint)
+    }
+    
+    pub fn token_balance(&self, account: &Pubkey) -> u64 {
+        let account = self.svm.get_account(account).expect("token account exists");
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+    
+    /// Register REMOTE_BRIDGE as the token bridge of SOURCE_CHAIN
+    pub fn set_bridge_peer(&self) -> Instruction {
+        build(
+            accounts::SetBridgePeer {
+                bridge_peer: bridge_peer(SOURCE_CHAIN),
+                gateway: gateway(),
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::SetBridgePeer { chain_id: SOURCE_CHAIN, remote_bridge: REMOTE_BRIDGE.to_vec() },
+        )
+    }
+    
+    pub fn set_token_config(&self, mint: &Pubkey, settings: TokenSettings) -> Instruction {
+        build(
+            accounts::SetTokenConfig {
+                token_config: token_config(mint),
+                gateway: gateway(),
+                mint: *mint,
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::SetTokenConfig { settings, governance: Pubkey::default() },
+        )
+    }
+    
+    pub fn set_blocklist_entry(&self, entry: Pubkey, blocked: bool) -> Instruction {
+        build(
+            accounts::SetBlocklistEntry {
+                blocklist: blocklist(),
+                gateway: gateway(),
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::SetBlocklistEntry { entry, blocked },
+        )
+    }
+    
+    /// Inbound volume cap of `max_amount` per `window_seconds` for `mint`
+    pub fn set_rate_limit(&self, mint: &Pubkey, window_seconds: u64, max_amount: u64) -> Instruction {
+        build(
+            accounts::SetRateLimit {
+                rate_limit: rate_limit(mint),
+                gateway: gateway(),
+                mint: *mint,
+                authority: self.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::SetRateLimit { window_seconds, max_amount },
+        )
+    }
+    
+    /// deposit_tokens of `amount` from `depositor`'s associated token account to SOURCE_CHAIN
+    pub fn deposit_tokens(&self, depositor: &Pubkey, mint: &Pubkey, tx_id: u128, amount: u64) -> Instruction {
+        build(
+            accounts::DepositTokens {
+                gateway: gateway(),
+                bridge_peer: bridge_peer(SOURCE_CHAIN),
+                chain_config: None,
+                mint: *mint,
+                token_config: token_config(mint),
+                blocklist: blocklist(),
+                depositor_token_account: get_associated_token_address(depositor, mint),
+                escrow: escrow(mint),
+                treasury_token_account: None,
+                depositor: *depositor,
+                token_program: spl_token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::DepositTokens {
+                tx_id,
+                dest_chain_id: SOURCE_CHAIN,
+                recipient: vec![0xcd; 20],
+                amount,
+                confirmations: 1,
+            },
+        )
+    }
+    
+    /// release_tokens paying `message` out of escrow into `recipient_token_account`
+    pub fn release_tokens(
+        &self,
+        message: &Attested,
+        mint: &Pubkey,
+        recipient_token_account: &Pubkey,
+        route: &InboundRoute,
+    ) -> Instruction {
+        let mut release = build(
+            accounts::ReleaseTokens {
+                gateway: gateway(),
+                tx_id_pda: tx_id_pda(&message.envelope),
+                bridge_peer: bridge_peer(SOURCE_CHAIN),
+                chain_config: chain_config(message.envelope.source_chain_id),
+                via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+                chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+                project_registry: route.project_registry,
+                relayer_pool: route.relayer_pool,
+                payer: route.rent_payer,
+                fee_config: route.claim_refund.then(|| fee_config(SOURCE_CHAIN)),
+                fee_vault: route.claim_refund.then(fee_vault),
+                mint: *mint,
+                token_config: token_config(mint),
+                blocklist: blocklist(),
+                rate_limit: rate_limit(mint),
+                escrow: escrow(mint),
+                treasury_token_account: None,
+                recipient_token_account: *recipient_token_account,
+                fast_fill: fast_fill(&message.envelope),
+                relayer: self.authority.pubkey(),
+                instructions: sysvar::instructions::ID,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::ReleaseTokens {
+                envelope: message.envelope.clone(),
+                signatures: message.signatures.clone(),
+            },
+        );
+        release.accounts.extend(route_remaining_accounts(route));
+        release
+    }
+    
+    /// park_token_transfer parking `message` as a claim for `recipient`
+    pub fn park_token_transfer(
+        &self,
+        message: &Attested,
+        mint: &Pubkey,
+        recipient: &Pubkey,
+        route: &InboundRoute,
+    ) -> Instruction {
+        let mut park = build(
+            accounts::ParkTokenTransfer {
+                gateway: gateway(),
+                tx_id_pda: tx_id_pda(&message.envelope),
+                bridge_peer: bridge_peer(SOURCE_CHAIN),
+                chain_config: chain_config(message.envelope.source_chain_id),
+                via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+                chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+                project_registry: route.project_registry,
+                relayer_pool: route.relayer_pool,
+                payer: route.rent_payer,
+                mint: *mint,
+                token_config: token_config(mint),
+                blocklist: blocklist(),
+                wrapped_asset: None,
+                recipient_token_account: get_associated_token_address(recipient, mint),
+                fast_fill: fast_fill(&message.envelope),
+                token_claim: message_pda(TOKEN_CLAIM_SEED, &message.envelope),
+                relayer: self.authority.pubkey(),
+                instructions: sysvar::instructions::ID,
+                token_program: spl_token::ID,
+                system_program: system_program::ID,
+            },
+            instruction::ParkTokenTransfer {
+                envelope: message.envelope.clone(),
+                signatures: message.signatures.clone(),
+            },
+        );
+        park.accounts.extend(route_remaining_accounts(route));
+        park
+    }
+    
+    /// release_value paying `message` out of the SOL vault to `recipient`
+    pub fn release_value(&self, message: &Attested, recipient: &Pubkey, route: &InboundRoute) -> Instruction {
+        let mut release = build(
+            accounts::ReleaseValue {
+                gateway: gateway(),
+                tx_id_pda: tx_id_pda(&message.envelope),
+                bridge_peer: bridge_peer(SOURCE_CHAIN),
+                chain_config: chain_config(message.envelope.source_chain_id),
+                via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+                chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+                project_registry: route.project_registry,
+                relayer_pool: route.relayer_pool,
+                payer: route.rent_payer,
+                sol_vault: sol_vault(),
+                recipient: *recipient,
+                relayer: self.authority.pubkey(),
+                instructions: sysvar::instructions::ID,
+                system_program: system_program::ID,
+            },
+            instruction::ReleaseValue {
+                envelope: message.envelope.clone(),
+                signatures: message.signatures.clone(),
+            },
+        );
+        release.accounts.extend(route_remaining_accounts(route));
+        release
+    }
+    
+    /// Load the account-compression and noop programs and record processed messages in
+    /// a fresh history tree, which is returned
+    /// Neither program is built here: dump both into tests/fixtures first, e.g.
+    /// `solana program dump -um cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK
+    /// tests/fixtures/spl_account_compression.so` and likewise
+    /// `noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV` into tests/fixtures/spl_noop.so
+    pub fn enable_message_history(&mut self) -> Pubkey {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+        for (program_id, name) in [(ACCOUNT_COMPRESSION_PROGRAM_ID, "spl_account_compression"), (NOOP_PROGRAM_ID, "spl_noop")] {
+            self.svm
+                .add_program_from_file(program_id, format!("{fixtures}/{name}.so"))
+                .expect("dump the program into tests/fixtures first");
+        }
+        
+        // Header, then sequence number, active index and buffer size, the change log
+        // buffer and the rightmost proof (no canopy)
+        let change_log_size = 32 + 32 * HISTORY_DEPTH + 8;
+        let size = HISTORY_HEADER_SIZE + 24 + HISTORY_BUFFER * change_log_size + 32 * HISTORY_DEPTH + 40;
+        let tree = Pubkey::new_unique();
+        let account = Account {
+            lamports: self.svm.minimum_balance_for_rent_exemption(size),
+            data: vec![0; size],
+            owner: ACCOUNT_COMPRESSION_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm.set_account(tree, account).unwrap();
+        
+        let set_tree = build(
+            accounts::SetMessageHistoryTree {
+                gateway: gateway(),
+                merkle_tree: tree,
+                history_authority: pda(&[MESSAGE_HISTORY_SEED]),
+                compression_program: ACCOUNT_COMPRESSION_PROGRAM_ID,
+                noop_program: NOOP_PROGRAM_ID,
+                authority: self.authority.pubkey(),
+            },
+            instruction::SetMessageHistoryTree {
+                max_depth: HISTORY_DEPTH as u32,
+                max_buffer_size: HISTORY_BUFFER as u32,
+            },
+        );
+        self.send(&[set_tree]).unwrap();
+        tree
+    }
+    
+    /// Leaves appended to a history tree: its sequence number, which only appends advance
+    pub fn history_leaf_count(&self, tree: &Pubkey) -> u64 {
+        let data = self.svm.get_account(tree).expect("history tree exists").data;
+        u64::from_le_bytes(data[HISTORY_HEADER_SIZE..HISTORY_HEADER_SIZE + 8].try_into().unwrap())
+    }
+}
@@ -0,0 +1,233 @@
+//! Registry and admin instructions rejecting bad input, against the built program in LiteSVM
+#![cfg(feature = "program-test")]
+
+mod svm;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::{AnchorDeserialize, InstructionData};
+use message_gateway_v4::constants::{MAX_SIGNERS_PER_REGISTRY, MAX_VERIFIER_ID, PROGRAM_VERSION};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::state::{MessageGateway, SignerRegistry, SignerRegistryType};
+use message_gateway_v4::utils::snapshot::{assemble_snapshot, SnapshotPage};
+use message_gateway_v4::{accounts, instruction};
+use solana_sdk::signature::{Keypair, Signer};
+use svm::{build, error_code, gateway, registry, Svm, GATEWAY_CHAIN, SOURCE_CHAIN};
+
+const NEW_CHAIN: u64 = 9;
+
+fn add_signer(env: &Svm, new_signer: Pubkey) -> Instruction {
+    build(
+        accounts::AddSigner {
+            signer_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+        },
+        instruction::AddSigner { registry_type: SignerRegistryType::Chain, chain_id: SOURCE_CHAIN, new_signer },
+    )
+}
+
+fn remove_signer(env: &Svm, signer_to_remove: Pubkey) -> Instruction {
+    build(
+        accounts::RemoveSigner {
+            signer_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+        },
+        instruction::RemoveSigner { registry_type: SignerRegistryType::Chain, chain_id: SOURCE_CHAIN, signer_to_remove },
+    )
+}
+
+fn update_threshold(env: &Svm, new_threshold: u8) -> Instruction {
+    build(
+        accounts::UpdateThreshold {
+            signer_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+        },
+        instruction::UpdateThreshold { registry_type: SignerRegistryType::Chain, chain_id: SOURCE_CHAIN, new_threshold },
+    )
+}
+
+fn set_protocol_versions(env: &Svm, min: u8, max: u8) -> Instruction {
+    build(
+        accounts::SetProtocolVersions { gateway: gateway(), authority: env.authority.pubkey() },
+        instruction::SetProtocolVersions { min_protocol_version: min, max_protocol_version: max },
+    )
+}
+
+#[test]
+fn initialize_registry_rejects_bad_signer_sets() {
+    let mut env = Svm::new();
+    let keys = |n: usize| (0..n).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+    let cases = [
+        (keys(0), 1, GatewayError::InsufficientSignatures),
+        (keys(MAX_SIGNERS_PER_REGISTRY + 1), 2, GatewayError::TooManySignatures),
+        (keys(3), 0, GatewayError::InvalidThreshold),
+        (keys(3), 4, GatewayError::InvalidThreshold),
+    ];
+    
+    for (signers, required, expected) in cases {
+        let ix = env.init_registry(SignerRegistryType::Chain, NEW_CHAIN, signers, required);
+        assert_eq!(error_code(env.send(&[ix])), expected.into());
+    }
+}
+
+#[test]
+fn verifier_ids_past_the_seed_space_are_rejected() {
+    let mut env = Svm::new();
+    assert!(SignerRegistryType::Verifier(MAX_VERIFIER_ID + 1).discriminant().is_err());
+    
+    // Verifier(u8::MAX) used to clamp onto the last verifier's registry address
+    let last = env.init_registry(SignerRegistryType::Verifier(MAX_VERIFIER_ID), NEW_CHAIN, vec![Pubkey::new_unique()], 1);
+    let aliased = Instruction {
+        data: instruction::InitializeSignerRegistry {
+            registry_type: SignerRegistryType::Verifier(u8::MAX),
+            chain_id: NEW_CHAIN,
+            initial_signers: vec![Pubkey::new_unique()],
+            required_signatures: 1,
+        }
+        .data(),
+        ..last.clone()
+    };
+    assert_eq!(error_code(env.send(&[aliased])), GatewayError::InvalidSignerRegistryType.into());
+    env.send(&[last]).unwrap();
+}
+
+#[test]
+fn membership_changes_keep_registry_consistent() {
+    let mut env = Svm::new();
+    let member = env.validators[2].pubkey();
+    
+    assert_eq!(error_code(env.send(&[add_signer(&env, member)])), GatewayError::DuplicateSigner.into());
+    assert_eq!(
+        error_code(env.send(&[remove_signer(&env, Pubkey::new_unique())])),
+        GatewayError::UnauthorizedSigner.into()
+    );
+    // Two members with a threshold of two: removing either would strand the registry
+    assert_eq!(error_code(env.send(&[remove_signer(&env, member)])), GatewayError::ThresholdTooHigh.into());
+}
+
+#[test]
+fn threshold_must_fit_the_signer_set() {
+    let mut env = Svm::new();
+    assert_eq!(error_code(env.send(&[update_threshold(&env, 0)])), GatewayError::InvalidThreshold.into());
+    assert_eq!(error_code(env.send(&[update_threshold(&env, 3)])), GatewayError::ThresholdTooHigh.into());
+    env.send(&[update_threshold(&env, 1)]).unwrap();
+}
+
+#[test]
+fn protocol_version_range_must_be_ordered() {
+    let mut env = Svm::new();
+    for (min, max) in [(0, 4), (5, 4)] {
+        let result = env.send(&[set_protocol_versions(&env, min, max)]);
+        assert_eq!(error_code(result), GatewayError::InvalidProtocolVersionRange.into());
+    }
+    env.send(&[set_protocol_versions(&env, 4, 5)]).unwrap();
+}
+
+#[test]
+fn admin_instructions_require_the_gateway_authority() {
+    let mut env = Svm::new();
+    let intruder = Keypair::new();
+    env.svm.airdrop(&intruder.pubkey(), 1_000_000_000).unwrap();
+    
+    let ix = build(
+        accounts::SetSystemEnabled { gateway: gateway(), authority: intruder.pubkey() },
+        instruction::SetSystemEnabled { enabled: false },
+    );
+    assert_eq!(error_code(env.send_as(&intruder, &[ix])), GatewayError::UnauthorizedAuthority.into());
+}
+
+#[test]
+fn assert_invariants_reports_violations_without_failing() {
+    let mut env = Svm::new();
+    let audit = |audited: &[Pubkey]| {
+        let mut ix = build(accounts::AssertInvariants { gateway: gateway() }, instruction::AssertInvariants {});
+        ix.accounts.extend(audited.iter().map(|account| AccountMeta::new_readonly(*account, false)));
+        ix
+    };
+    let reported = |logs: Vec<String>, summary: &str| logs.iter().any(|log| log.contains(summary));
+    let registries = [
+        registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+        registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+        registry(SignerRegistryType::Project, SOURCE_CHAIN),
+    ];
+    
+    // The gateway config plus the address and threshold of each registry
+    assert!(reported(env.send_logs(&[audit(&registries)]), "Invariants: 7 checks, 0 violations"));
+    
+    // A threshold its signers cannot reach is reported, not raised
+    env.edit(&registries[1], |chain_registry: &mut SignerRegistry| chain_registry.required_signatures = 3);
+    assert!(reported(env.send_logs(&[audit(&registries)]), "Invariants: 7 checks, 1 violations"));
+    
+    // Accounts it cannot audit fail the instruction
+    let authority = env.authority.pubkey();
+    assert_eq!(
+        error_code(env.send(&[audit(&[authority])])),
+        GatewayError::InvalidInvariantAccount.into()
+    );
+}
+
+#[test]
+fn instructions_refuse_a_gateway_from_an_incompatible_version() {
+    let mut env = Svm::new();
+    env.edit(&gateway(), |gateway: &mut MessageGateway| gateway.program_version = PROGRAM_VERSION + 1);
+    assert_eq!(
+        error_code(env.send(&[env.set_system_enabled(false)])),
+        GatewayError::IncompatibleVersion.into()
+    );
+    
+    env.edit(&gateway(), |gateway: &mut MessageGateway| gateway.program_version = PROGRAM_VERSION);
+    env.send(&[env.set_system_enabled(false)]).unwrap();
+}
+
+#[test]
+fn export_snapshot_pages_reassemble_into_the_governance_state() {
+    let mut env = Svm::new();
+    let export = |exported: &[Pubkey], page: u16| {
+        let mut ix = build(accounts::ExportSnapshot { gateway: gateway() }, instruction::ExportSnapshot { page });
+        ix.accounts.extend(exported.iter().map(|account| AccountMeta::new_readonly(*account, false)));
+        ix
+    };
+    let registries = [
+        registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+        registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+        registry(SignerRegistryType::Project, SOURCE_CHAIN),
+    ];
+    
+    let first = SnapshotPage::deserialize(&mut &env.simulate_return(export(&registries, 0))[..]).unwrap();
+    let mut pages = vec![first.clone()];
+    for page in 1..first.page_count {
+        pages.push(SnapshotPage::deserialize(&mut &env.simulate_return(export(&registries, page))[..]).unwrap());
+    }
+    let snapshot = assemble_snapshot(&pages).unwrap();
+    assert_eq!(snapshot.program_id, message_gateway_v4::ID);
+    assert_eq!(snapshot.gateway.chain_id, GATEWAY_CHAIN);
+    assert!(snapshot.chain_configs.is_empty());
+    let exported: Vec<_> = snapshot.registries.iter().map(|r| (r.registry_type, r.chain_id, r.signers.len())).collect();
+    assert_eq!(
+        exported,
+        [
+            (SignerRegistryType::VIA, GATEWAY_CHAIN, 2),
+            (SignerRegistryType::Chain, SOURCE_CHAIN, 2),
+            (SignerRegistryType::Project, SOURCE_CHAIN, 2),
+        ]
+    );
+    
+    // A page altered in transit no longer matches the snapshot hash
+    let mut tampered = pages.clone();
+    tampered[0].data[1] ^= 1;
+    assert!(assemble_snapshot(&tampered).is_err());
+    
+    // Out-of-order or duplicated accounts would make the blob non-canonical
+    let reordered = [registries[1], registries[0]];
+    assert_eq!(
+        error_code(env.send(&[export(&reordered, 0)])),
+        GatewayError::InvalidSnapshotAccount.into()
+    );
+    assert_eq!(
+        error_code(env.send(&[export(&registries, first.page_count)])),
+        GatewayError::InvalidSnapshotPage.into()
+    );
+}
@@ -0,0 +1,79 @@
+//! bootstrap_test_env against the built program in LiteSVM
+//! (`anchor build -- --features test-env` first)
+#![cfg(all(feature = "program-test", feature = "test-env"))]
+
+mod svm;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{instruction::Instruction, system_program};
+use message_gateway_v4::constants::COUNTER_SEED;
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::state::SignerRegistryType;
+use message_gateway_v4::txbuilder::InboundRoute;
+use message_gateway_v4::{accounts, instruction};
+use solana_sdk::signature::Signer;
+use svm::{build, error_code, gateway, pda, registry, Svm, ALL_LAYERS, GATEWAY_CHAIN, SOURCE_CHAIN};
+
+fn bootstrap(env: &Svm, with_project: bool, project_signers: Vec<Pubkey>) -> Instruction {
+    let members = |indices: &[usize]| indices.iter().map(|&i| env.validators[i].pubkey()).collect();
+    build(
+        accounts::BootstrapTestEnv {
+            gateway: gateway(),
+            counter_pda: pda(&[COUNTER_SEED, &SOURCE_CHAIN.to_le_bytes()]),
+            via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+            chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            project_registry: with_project.then(|| registry(SignerRegistryType::Project, SOURCE_CHAIN)),
+            authority: env.authority.pubkey(),
+            system_program: system_program::ID,
+        },
+        instruction::BootstrapTestEnv {
+            chain_id: GATEWAY_CHAIN,
+            source_chain_id: SOURCE_CHAIN,
+            via_signers: members(&[0, 1]),
+            chain_signers: members(&[2, 3]),
+            project_signers,
+            required_signatures: 2,
+        },
+    )
+}
+
+#[test]
+fn bootstrapped_gateway_relays_three_layer_messages() {
+    let mut env = Svm::bare();
+    let project_signers = vec![env.validators[3].pubkey(), env.validators[4].pubkey()];
+    env.send(&[bootstrap(&env, true, project_signers.clone())]).unwrap();
+    for address in [
+        gateway(),
+        pda(&[COUNTER_SEED, &SOURCE_CHAIN.to_le_bytes()]),
+        registry(SignerRegistryType::Project, SOURCE_CHAIN),
+    ] {
+        assert!(env.exists(&address));
+    }
+    
+    let message = env.inbound(1, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    env.tx2(&message, Svm::with_project()).unwrap();
+    
+    // Every account uses init, so a second bootstrap cannot overwrite the first
+    assert!(env.send(&[bootstrap(&env, true, project_signers)]).is_err());
+}
+
+#[test]
+fn project_registry_is_optional_but_must_match_its_signers() {
+    let mut env = Svm::bare();
+    let project_signers = vec![env.validators[3].pubkey()];
+    assert_eq!(
+        error_code(env.send(&[bootstrap(&env, false, project_signers)])),
+        GatewayError::InvalidBootstrapProject.into()
+    );
+    assert_eq!(
+        error_code(env.send(&[bootstrap(&env, true, Vec::new())])),
+        GatewayError::InvalidBootstrapProject.into()
+    );
+    
+    env.send(&[bootstrap(&env, false, Vec::new())]).unwrap();
+    assert!(!env.exists(&registry(SignerRegistryType::Project, SOURCE_CHAIN)));
+    let message = env.inbound(1, &[0, 1, 2, 3]);
+    env.tx1(&message).unwrap();
+    env.tx2(&message, InboundRoute::default()).unwrap();
+}
@@ -0,0 +1,651 @@
+//! Token and native value bridge against the built program in LiteSVM: balances moved by
+//! each bridge flow and the errors that stop them
+#![cfg(feature = "program-test")]
+
+mod svm;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::spl_token;
+use message_gateway_v4::constants::{
+    RESCUE_SEED, RESCUE_TIMELOCK_SECONDS, TOKEN_CLAIM_SEED, WRAPPED_ASSET_SEED, WRAPPED_LISTING_SEED,
+    WRAPPED_LISTING_TIMELOCK_SECONDS, WRAPPED_MINT_SEED,
+};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::payloads::{
+    TokenReturnPayload, TokenTransferPayload, ValueTransferPayload, PAYLOAD_KIND_TOKEN_RETURN,
+    PAYLOAD_KIND_TOKEN_TRANSFER, PAYLOAD_KIND_VALUE_TRANSFER,
+};
+use message_gateway_v4::state::{MessageEnvelope, SignerRegistryType, TokenSettings};
+use message_gateway_v4::txbuilder::InboundRoute;
+use message_gateway_v4::{accounts, instruction};
+use solana_sdk::signature::{Keypair, Signer};
+use svm::{
+    blocklist, bridge_envelope, bridge_peer, build, chain_config, error_code, escrow, fast_fill, fee_config, fee_vault,
+    gateway, message_pda, pda, rate_limit, registry, sol_vault, token_config, token_settings, token_transfer, tx_id_pda,
+    Attested, Svm, ALL_LAYERS, DECIMALS, GATEWAY_CHAIN, SOURCE_CHAIN,
+};
+
+/// SOURCE_CHAIN asset bridged in as a wrapped mint
+const SOURCE_TOKEN: [u8; 32] = [0x5a; 32];
+
+/// Mint bridged to SOURCE_CHAIN with `locked` tokens deposited into escrow by the authority
+fn bridged_mint(env: &mut Svm, locked: u64) -> Pubkey {
+    let mint = env.create_mint();
+    let authority = env.authority.pubkey();
+    env.send(&[env.set_bridge_peer(), env.set_token_config(&mint, token_settings())]).unwrap();
+    env.token_account(&mint, &authority, locked);
+    env.send(&[env.deposit_tokens(&authority, &mint, 1, locked)]).unwrap();
+    mint
+}
+
+/// Advance the cluster clock by `seconds`
+fn warp(env: &mut Svm, seconds: i64) {
+    let mut clock = env.svm.get_sysvar::<Clock>();
+    clock.unix_timestamp += seconds;
+    env.svm.set_sysvar(&clock);
+}
+
+fn supply(env: &Svm, mint: &Pubkey) -> u64 {
+    let account = env.svm.get_account(mint).expect("mint exists");
+    spl_token::state::Mint::unpack(&account.data).unwrap().supply
+}
+
+/// Funded wallet holding `amount` of `mint`
+fn holder(env: &mut Svm, mint: &Pubkey, amount: u64) -> Keypair {
+    let wallet = Keypair::new();
+    env.svm.airdrop(&wallet.pubkey(), 1_000_000_000).unwrap();
+    env.token_account(mint, &wallet.pubkey(), amount);
+    wallet
+}
+
+#[test]
+fn deposits_lock_into_escrow_and_releases_pay_out_of_it() {
+    let mut env = Svm::new();
+    let mint = env.create_mint();
+    env.send(&[env.set_bridge_peer(), env.set_token_config(&mint, token_settings())]).unwrap();
+    
+    let depositor = holder(&mut env, &mint, 1_000);
+    let source = env.token_account(&mint, &depositor.pubkey(), 0);
+    env.send_as(&depositor, &[env.deposit_tokens(&depositor.pubkey(), &mint, 1, 600)]).unwrap();
+    assert_eq!(env.token_balance(&source), 400);
+    assert_eq!(env.token_balance(&escrow(&mint)), 600);
+    
+    let beneficiary = Keypair::new().pubkey();
+    let beneficiary_account = env.token_account(&mint, &beneficiary, 0);
+    let message = env.attest(token_transfer(1, &mint, &beneficiary, 250), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    env.tx2_with(&message, env.release_tokens(&message, &mint, &beneficiary_account, &InboundRoute::default()))
+        .unwrap();
+    assert_eq!(env.token_balance(&beneficiary_account), 250);
+    assert_eq!(env.token_balance(&escrow(&mint)), 350);
+    assert!(!env.exists(&tx_id_pda(&message.envelope)));
+}
+
+#[test]
+fn releases_pay_the_claimed_relayer_refund() {
+    let mut env = Svm::new();
+    let mint = bridged_mint(&mut env, 600);
+    env.send(&[env.set_fee_config(10_000, 0), env.set_relayer_refund(20_000, 0)]).unwrap();
+    let rent_exempt = env.svm.get_balance(&fee_vault()).unwrap();
+    env.svm.airdrop(&fee_vault(), 30_000).unwrap();
+    
+    let beneficiary = Keypair::new().pubkey();
+    let beneficiary_account = env.token_account(&mint, &beneficiary, 0);
+    let message = env.attest(token_transfer(1, &mint, &beneficiary, 250), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    let claim = InboundRoute { claim_refund: true, ..Default::default() };
+    env.tx2_with(&message, env.release_tokens(&message, &mint, &beneficiary_account, &claim)).unwrap();
+    assert_eq!(env.token_balance(&beneficiary_account), 250);
+    assert_eq!(env.svm.get_balance(&fee_vault()).unwrap(), rent_exempt + 10_000);
+}
+
+#[test]
+fn blocked_wallets_and_mints_neither_lock_nor_release() {
+    let mut env = Svm::new();
+    let mint = bridged_mint(&mut env, 1_000);
+    let authority = env.authority.pubkey();
+    
+    // A blocked beneficiary receives nothing; the message stays pending
+    let beneficiary = Keypair::new().pubkey();
+    let beneficiary_account = env.token_account(&mint, &beneficiary, 0);
+    env.send(&[env.set_blocklist_entry(beneficiary, true)]).unwrap();
+    let message = env.attest(token_transfer(1, &mint, &beneficiary, 100), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    let release = env.release_tokens(&message, &mint, &beneficiary_account, &InboundRoute::default());
+    assert_eq!(error_code(env.tx2_with(&message, release.clone())), GatewayError::AddressBlocked.into());
+    assert_eq!(env.token_balance(&beneficiary_account), 0);
+    assert!(env.exists(&tx_id_pda(&message.envelope)));
+    
+    // A blocked depositor locks nothing
+    let depositor = holder(&mut env, &mint, 100);
+    env.send(&[env.set_blocklist_entry(depositor.pubkey(), true)]).unwrap();
+    assert_eq!(
+        error_code(env.send_as(&depositor, &[env.deposit_tokens(&depositor.pubkey(), &mint, 2, 100)])),
+        GatewayError::AddressBlocked.into()
+    );
+    
+    // A blocked mint is bridged in neither direction, whoever sends or receives it
+    env.send(&[env.set_blocklist_entry(beneficiary, false), env.set_blocklist_entry(mint, true)]).unwrap();
+    env.token_account(&mint, &authority, 100);
+    assert_eq!(
+        error_code(env.send(&[env.deposit_tokens(&authority, &mint, 3, 100)])),
+        GatewayError::AddressBlocked.into()
+    );
+    assert_eq!(error_code(env.tx2_with(&message, release.clone())), GatewayError::AddressBlocked.into());
+    assert_eq!(env.token_balance(&escrow(&mint)), 1_000);
+    
+    // Lifting the block lets the pending release through
+    env.send(&[env.set_blocklist_entry(mint, false)]).unwrap();
+    env.tx2_with(&message, release).unwrap();
+    assert_eq!(env.token_balance(&beneficiary_account), 100);
+    assert_eq!(env.token_balance(&escrow(&mint)), 900);
+}
+
+#[test]
+fn releases_beyond_the_rate_limit_wait_for_headroom() {
+    let mut env = Svm::new();
+    let mint = bridged_mint(&mut env, 1_000);
+    env.send(&[env.set_rate_limit(&mint, 3_600, 300)]).unwrap();
+    let beneficiary = Keypair::new().pubkey();
+    let beneficiary_account = env.token_account(&mint, &beneficiary, 0);
+    
+    let first = env.attest(token_transfer(1, &mint, &beneficiary, 250), ALL_LAYERS);
+    env.tx1(&first).unwrap();
+    env.tx2_with(&first, env.release_tokens(&first, &mint, &beneficiary_account, &InboundRoute::default()))
+        .unwrap();
+    assert_eq!(env.token_balance(&beneficiary_account), 250);
+    
+    // 250 + 100 exceeds the 300 cap within the window
+    let second = env.attest(token_transfer(2, &mint, &beneficiary, 100), ALL_LAYERS);
+    env.tx1(&second).unwrap();
+    let release = env.release_tokens(&second, &mint, &beneficiary_account, &InboundRoute::default());
+    assert_eq!(error_code(env.tx2_with(&second, release.clone())), GatewayError::RateLimitExceeded.into());
+    assert_eq!(env.token_balance(&beneficiary_account), 250);
+    assert_eq!(env.token_balance(&escrow(&mint)), 750);
+    
+    // Raising the cap keeps the window's volume and admits the held transfer
+    env.send(&[env.set_rate_limit(&mint, 3_600, 400)]).unwrap();
+    env.tx2_with(&second, release).unwrap();
+    assert_eq!(env.token_balance(&beneficiary_account), 350);
+    assert_eq!(env.token_balance(&escrow(&mint)), 650);
+}
+
+#[test]
+fn neither_leg_moves_more_than_the_balance_behind_it() {
+    let mut env = Svm::new();
+    let mint = bridged_mint(&mut env, 500);
+    let authority = env.authority.pubkey();
+    
+    // The depositor's token balance bounds a lock
+    env.token_account(&mint, &authority, 100);
+    assert_eq!(
+        error_code(env.send(&[env.deposit_tokens(&authority, &mint, 2, 101)])),
+        spl_token::error::TokenError::InsufficientFunds as u32
+    );
+    assert_eq!(env.token_balance(&escrow(&mint)), 500);
+    
+    // The locked amount bounds a release
+    let beneficiary = Keypair::new().pubkey();
+    let beneficiary_account = env.token_account(&mint, &beneficiary, 0);
+    let message = env.attest(token_transfer(1, &mint, &beneficiary, 501), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    assert_eq!(
+        error_code(
+            env.tx2_with(&message, env.release_tokens(&message, &mint, &beneficiary_account, &InboundRoute::default()))
+        ),
+        GatewayError::EscrowAccountingMismatch.into()
+    );
+    assert_eq!(env.token_balance(&beneficiary_account), 0);
+    assert_eq!(env.token_balance(&escrow(&mint)), 500);
+}
+
+fn wrapped_asset() -> Pubkey {
+    pda(&[WRAPPED_ASSET_SEED, &SOURCE_CHAIN.to_le_bytes(), &SOURCE_TOKEN])
+}
+
+fn wrapped_mint() -> Pubkey {
+    pda(&[WRAPPED_MINT_SEED, &SOURCE_CHAIN.to_le_bytes(), &SOURCE_TOKEN])
+}
+
+fn propose_wrapped_asset(env: &Svm) -> Instruction {
+    build(
+        accounts::ProposeWrappedAsset {
+            listing: pda(&[WRAPPED_LISTING_SEED, &SOURCE_CHAIN.to_le_bytes(), &SOURCE_TOKEN]),
+            wrapped_asset: wrapped_asset(),
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+            system_program: system_program::ID,
+        },
+        instruction::ProposeWrappedAsset { source_chain_id: SOURCE_CHAIN, source_token: SOURCE_TOKEN, decimals: DECIMALS },
+    )
+}
+
+fn create_wrapped_mint(env: &Svm) -> Instruction {
+    build(
+        accounts::CreateWrappedMint {
+            listing: pda(&[WRAPPED_LISTING_SEED, &SOURCE_CHAIN.to_le_bytes(), &SOURCE_TOKEN]),
+            wrapped_asset: wrapped_asset(),
+            mint: wrapped_mint(),
+            token_config: token_config(&wrapped_mint()),
+            gateway: gateway(),
+            proposer: env.authority.pubkey(),
+            payer: env.authority.pubkey(),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateWrappedMint { source_chain_id: SOURCE_CHAIN, source_token: SOURCE_TOKEN },
+    )
+}
+
+fn delist_wrapped_asset(env: &Svm) -> Instruction {
+    build(
+        accounts::DelistWrappedAsset {
+            wrapped_asset: wrapped_asset(),
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+        },
+        instruction::DelistWrappedAsset {},
+    )
+}
+
+/// Inbound transfer of `amount` of SOURCE_TOKEN to `recipient`
+fn wrapped_transfer(tx_id: u128, recipient: &Pubkey, amount: u64) -> MessageEnvelope {
+    let payload = TokenTransferPayload {
+        token: SOURCE_TOKEN,
+        amount,
+        sender: vec![0xaa; 20],
+        recipient: recipient.to_bytes().to_vec(),
+    };
+    bridge_envelope(tx_id, PAYLOAD_KIND_TOKEN_TRANSFER, payload.encode().unwrap())
+}
+
+fn mint_wrapped(env: &Svm, message: &Attested, recipient_token_account: &Pubkey) -> Instruction {
+    let mint = wrapped_mint();
+    build(
+        accounts::MintWrapped {
+            gateway: gateway(),
+            tx_id_pda: tx_id_pda(&message.envelope),
+            bridge_peer: bridge_peer(SOURCE_CHAIN),
+            chain_config: chain_config(message.envelope.source_chain_id),
+            via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+            chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            project_registry: None,
+            relayer_pool: None,
+            payer: None,
+            wrapped_asset: wrapped_asset(),
+            mint,
+            token_config: token_config(&mint),
+            blocklist: blocklist(),
+            rate_limit: rate_limit(&mint),
+            treasury_token_account: None,
+            recipient_token_account: *recipient_token_account,
+            fast_fill: fast_fill(&message.envelope),
+            relayer: env.authority.pubkey(),
+            instructions: sysvar::instructions::ID,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::MintWrapped {
+            envelope: message.envelope.clone(),
+            signatures: message.signatures.clone(),
+        },
+    )
+}
+
+/// burn_wrapped of `amount` from `holder`'s associated token account back to SOURCE_CHAIN
+fn burn_wrapped(holder: &Pubkey, tx_id: u128, amount: u64) -> Instruction {
+    let mint = wrapped_mint();
+    build(
+        accounts::BurnWrapped {
+            gateway: gateway(),
+            wrapped_asset: wrapped_asset(),
+            bridge_peer: bridge_peer(SOURCE_CHAIN),
+            chain_config: None,
+            mint,
+            token_config: token_config(&mint),
+            blocklist: blocklist(),
+            holder_token_account: get_associated_token_address(holder, &mint),
+            treasury_token_account: None,
+            holder: *holder,
+            token_program: spl_token::ID,
+        },
+        instruction::BurnWrapped { tx_id, recipient: vec![0xcd; 20], amount, confirmations: 1 },
+    )
+}
+
+#[test]
+fn wrapped_assets_mint_inbound_and_burn_outbound() {
+    let mut env = Svm::new();
+    env.send(&[env.set_bridge_peer(), propose_wrapped_asset(&env)]).unwrap();
+    assert_eq!(error_code(env.send(&[create_wrapped_mint(&env)])), GatewayError::ListingTimelockActive.into());
+    warp(&mut env, WRAPPED_LISTING_TIMELOCK_SECONDS);
+    env.send(&[create_wrapped_mint(&env)]).unwrap();
+    let mint = wrapped_mint();
+    
+    // Inbound transfers mint to the beneficiary; outbound ones burn from the holder
+    let wallet = holder(&mut env, &mint, 0);
+    let wallet_account = env.token_account(&mint, &wallet.pubkey(), 0);
+    let message = env.attest(wrapped_transfer(1, &wallet.pubkey(), 700), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    env.tx2_with(&message, mint_wrapped(&env, &message, &wallet_account)).unwrap();
+    assert_eq!(env.token_balance(&wallet_account), 700);
+    assert_eq!(supply(&env, &mint), 700);
+    
+    env.send_as(&wallet, &[burn_wrapped(&wallet.pubkey(), 1, 300)]).unwrap();
+    assert_eq!(env.token_balance(&wallet_account), 400);
+    assert_eq!(supply(&env, &mint), 400);
+    assert_eq!(
+        error_code(env.send_as(&wallet, &[burn_wrapped(&wallet.pubkey(), 2, 401)])),
+        spl_token::error::TokenError::InsufficientFunds as u32
+    );
+    
+    // A delisted asset mints nothing more, but holders can still burn it home
+    env.send(&[delist_wrapped_asset(&env)]).unwrap();
+    let message = env.attest(wrapped_transfer(2, &wallet.pubkey(), 100), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    assert_eq!(
+        error_code(env.tx2_with(&message, mint_wrapped(&env, &message, &wallet_account))),
+        GatewayError::WrappedAssetDelisted.into()
+    );
+    env.send_as(&wallet, &[burn_wrapped(&wallet.pubkey(), 3, 400)]).unwrap();
+    assert_eq!(env.token_balance(&wallet_account), 0);
+    assert_eq!(supply(&env, &mint), 0);
+}
+
+/// send_message locking the value of `envelope` from the authority into the SOL vault
+fn send_value(env: &Svm, envelope: MessageEnvelope) -> Instruction {
+    let authority = env.authority.pubkey();
+    build(
+        accounts::SendMessage {
+            gateway: gateway(),
+            sender: authority,
+            chain_config: Some(chain_config(SOURCE_CHAIN)),
+            fee_config: fee_config(SOURCE_CHAIN),
+            channel: None,
+            price_update: None,
+            fee_payer: Some(authority),
+            sol_vault: Some(sol_vault()),
+            fee_vault: None,
+            system_program: Some(system_program::ID),
+        },
+        instruction::SendMessage { envelope, confirmations: 1 },
+    )
+}
+
+/// Attested value transfer of `amount` lamports from SOURCE_CHAIN to `beneficiary`
+fn value_transfer(env: &Svm, tx_id: u128, beneficiary: &Pubkey, amount: u64) -> Attested {
+    let payload = ValueTransferPayload {
+        amount,
+        recipient: beneficiary.to_bytes().to_vec(),
+        data: Vec::new(),
+    };
+    env.attest(bridge_envelope(tx_id, PAYLOAD_KIND_VALUE_TRANSFER, payload.encode().unwrap()), ALL_LAYERS)
+}
+
+#[test]
+fn value_locked_on_send_is_released_inbound() {
+    let mut env = Svm::new();
+    let authority = env.authority.pubkey();
+    env.send(&[env.set_bridge_peer()]).unwrap();
+    let value = ValueTransferPayload { amount: 50_000_000, recipient: vec![0xcd; 20], data: Vec::new() };
+    let outbound = MessageEnvelope {
+        message_type: PAYLOAD_KIND_VALUE_TRANSFER,
+        on_chain_data: value.encode().unwrap(),
+        ..env.outbound(1)
+    };
+    
+    // Value is never sent without the accounts that lock it
+    let result = env.send(&[env.send_message(&authority, outbound.clone())]);
+    assert_eq!(error_code(result), GatewayError::MissingValueAccounts.into());
+    assert!(!env.exists(&sol_vault()));
+    
+    let sender_before = env.svm.get_balance(&authority).unwrap();
+    env.send(&[send_value(&env, outbound)]).unwrap();
+    assert_eq!(env.svm.get_balance(&sol_vault()).unwrap(), 50_000_000);
+    assert!(env.svm.get_balance(&authority).unwrap() <= sender_before - 50_000_000);
+    
+    // Releases pay only the payload's beneficiary
+    let beneficiary = Pubkey::new_unique();
+    let message = value_transfer(&env, 2, &beneficiary, 20_000_000);
+    env.tx1(&message).unwrap();
+    let misdirected = env.release_value(&message, &Pubkey::new_unique(), &InboundRoute::default());
+    assert_eq!(error_code(env.tx2_with(&message, misdirected)), GatewayError::InvalidTokenRecipient.into());
+    env.tx2_with(&message, env.release_value(&message, &beneficiary, &InboundRoute::default())).unwrap();
+    assert_eq!(env.svm.get_balance(&beneficiary).unwrap(), 20_000_000);
+    assert_eq!(env.svm.get_balance(&sol_vault()).unwrap(), 30_000_000);
+    
+    // and never take the vault below its rent-exempt minimum
+    let rent = env.svm.minimum_balance_for_rent_exemption(0);
+    let message = value_transfer(&env, 3, &beneficiary, 30_000_000);
+    env.tx1(&message).unwrap();
+    let result = env.tx2_with(&message, env.release_value(&message, &beneficiary, &InboundRoute::default()));
+    assert_eq!(error_code(result), GatewayError::InsufficientVaultBalance.into());
+    
+    let message = value_transfer(&env, 4, &beneficiary, 30_000_000 - rent);
+    env.tx1(&message).unwrap();
+    env.tx2_with(&message, env.release_value(&message, &beneficiary, &InboundRoute::default())).unwrap();
+    assert_eq!(env.svm.get_balance(&beneficiary).unwrap(), 50_000_000 - rent);
+    assert_eq!(env.svm.get_balance(&sol_vault()).unwrap(), rent);
+}
+
+fn request_rescue(env: &Svm, mint: &Pubkey, destination: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::RequestRescue {
+            rescue: pda(&[RESCUE_SEED, mint.as_ref()]),
+            gateway: gateway(),
+            mint: *mint,
+            escrow: escrow(mint),
+            destination: *destination,
+            authority: env.authority.pubkey(),
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::RequestRescue { amount, reason: "stuck deposit".to_string() },
+    )
+}
+
+fn rescue_tokens(authority: &Pubkey, mint: &Pubkey, destination: &Pubkey) -> Instruction {
+    build(
+        accounts::RescueTokens {
+            rescue: pda(&[RESCUE_SEED, mint.as_ref()]),
+            gateway: gateway(),
+            mint: *mint,
+            escrow: escrow(mint),
+            destination: *destination,
+            authority: *authority,
+            token_program: spl_token::ID,
+        },
+        instruction::RescueTokens {},
+    )
+}
+
+fn cancel_rescue(env: &Svm, mint: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelRescue {
+            rescue: pda(&[RESCUE_SEED, mint.as_ref()]),
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+        },
+        instruction::CancelRescue {},
+    )
+}
+
+#[test]
+fn rescues_drain_escrow_only_after_their_timelock() {
+    let mut env = Svm::new();
+    let authority = env.authority.pubkey();
+    let mint = bridged_mint(&mut env, 1_000);
+    let treasury = env.token_account(&mint, &Pubkey::new_unique(), 0);
+    let rescue = pda(&[RESCUE_SEED, mint.as_ref()]);
+    
+    let result = env.send(&[request_rescue(&env, &mint, &treasury, 1_001)]);
+    assert_eq!(error_code(result), GatewayError::InvalidAmount.into());
+    
+    // A cancelled rescue moves nothing
+    env.send(&[request_rescue(&env, &mint, &treasury, 600)]).unwrap();
+    env.send(&[cancel_rescue(&env, &mint)]).unwrap();
+    assert!(!env.exists(&rescue));
+    
+    env.send(&[request_rescue(&env, &mint, &treasury, 600)]).unwrap();
+    let result = env.send(&[rescue_tokens(&authority, &mint, &treasury)]);
+    assert_eq!(error_code(result), GatewayError::RescueTimelockActive.into());
+    warp(&mut env, RESCUE_TIMELOCK_SECONDS - 1);
+    let result = env.send(&[rescue_tokens(&authority, &mint, &treasury)]);
+    assert_eq!(error_code(result), GatewayError::RescueTimelockActive.into());
+    assert_eq!(env.token_balance(&escrow(&mint)), 1_000);
+    assert_eq!(env.token_balance(&treasury), 0);
+    
+    // Only the authority executes it, once the timelock has elapsed
+    warp(&mut env, 1);
+    let intruder = Keypair::new();
+    env.svm.airdrop(&intruder.pubkey(), 1_000_000_000).unwrap();
+    let result = env.send_as(&intruder, &[rescue_tokens(&intruder.pubkey(), &mint, &treasury)]);
+    assert_eq!(error_code(result), GatewayError::UnauthorizedAuthority.into());
+    
+    env.send(&[rescue_tokens(&authority, &mint, &treasury)]).unwrap();
+    assert_eq!(env.token_balance(&escrow(&mint)), 400);
+    assert_eq!(env.token_balance(&treasury), 600);
+    assert!(!env.exists(&rescue));
+}
+
+/// claim_token_transfer redeeming the native transfer parked for `recipient`
+fn claim_token_transfer(env: &Svm, recipient: &Pubkey, mint: &Pubkey, parked: &MessageEnvelope) -> Instruction {
+    build(
+        accounts::ClaimTokenTransfer {
+            gateway: gateway(),
+            token_claim: message_pda(TOKEN_CLAIM_SEED, parked),
+            mint: *mint,
+            token_config: token_config(mint),
+            blocklist: blocklist(),
+            rate_limit: rate_limit(mint),
+            escrow: Some(escrow(mint)),
+            treasury_token_account: None,
+            recipient_token_account: get_associated_token_address(recipient, mint),
+            recipient: *recipient,
+            relayer: env.authority.pubkey(),
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimTokenTransfer {},
+    )
+}
+
+/// Request from SOURCE_CHAIN's bridge to return the transfer parked as `tx_id`
+fn return_request(env: &Svm, request_tx_id: u128, tx_id: u128, return_tx_id: u128) -> Attested {
+    let payload = TokenReturnPayload { tx_id, return_tx_id };
+    env.attest(bridge_envelope(request_tx_id, PAYLOAD_KIND_TOKEN_RETURN, payload.encode().unwrap()), ALL_LAYERS)
+}
+
+fn return_token_transfer(env: &Svm, message: &Attested, parked: &MessageEnvelope) -> Instruction {
+    let relayer = env.authority.pubkey();
+    build(
+        accounts::ReturnTokenTransfer {
+            gateway: gateway(),
+            tx_id_pda: tx_id_pda(&message.envelope),
+            bridge_peer: bridge_peer(SOURCE_CHAIN),
+            chain_config: chain_config(message.envelope.source_chain_id),
+            via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+            chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            project_registry: None,
+            relayer_pool: None,
+            payer: None,
+            token_claim: message_pda(TOKEN_CLAIM_SEED, parked),
+            claim_relayer: relayer,
+            relayer,
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ReturnTokenTransfer {
+            envelope: message.envelope.clone(),
+            signatures: message.signatures.clone(),
+            confirmations: 1,
+        },
+    )
+}
+
+#[test]
+fn undeliverable_transfers_park_until_claimed_or_returned() {
+    let mut env = Svm::new();
+    let mint = bridged_mint(&mut env, 1_000);
+    let route = InboundRoute::default();
+    
+    // Deliverable transfers are released, never parked
+    let funded = holder(&mut env, &mint, 0);
+    let deliverable = env.attest(token_transfer(2, &mint, &funded.pubkey(), 100), ALL_LAYERS);
+    env.tx1(&deliverable).unwrap();
+    let result = env.tx2_with(&deliverable, env.park_token_transfer(&deliverable, &mint, &funded.pubkey(), &route));
+    assert_eq!(error_code(result), GatewayError::ReleaseNotBlocked.into());
+    
+    // A recipient without a token account is left a claim to redeem later
+    let unfunded = Keypair::new();
+    env.svm.airdrop(&unfunded.pubkey(), 1_000_000_000).unwrap();
+    let parked = env.attest(token_transfer(3, &mint, &unfunded.pubkey(), 400), ALL_LAYERS);
+    env.tx1(&parked).unwrap();
+    env.tx2_with(&parked, env.park_token_transfer(&parked, &mint, &unfunded.pubkey(), &route)).unwrap();
+    assert!(env.exists(&message_pda(TOKEN_CLAIM_SEED, &parked.envelope)));
+    assert_eq!(env.token_balance(&escrow(&mint)), 1_000);
+    
+    env.send_as(&unfunded, &[claim_token_transfer(&env, &unfunded.pubkey(), &mint, &parked.envelope)]).unwrap();
+    assert_eq!(env.token_balance(&get_associated_token_address(&unfunded.pubkey(), &mint)), 400);
+    assert_eq!(env.token_balance(&escrow(&mint)), 600);
+    assert!(!env.exists(&message_pda(TOKEN_CLAIM_SEED, &parked.envelope)));
+    
+    // Transfers parked while the mint is paused go back to their sender on request
+    let paused = TokenSettings { paused: true, ..token_settings() };
+    env.send(&[env.set_token_config(&mint, paused)]).unwrap();
+    let parked = env.attest(token_transfer(4, &mint, &funded.pubkey(), 300), ALL_LAYERS);
+    env.tx1(&parked).unwrap();
+    env.tx2_with(&parked, env.park_token_transfer(&parked, &mint, &funded.pubkey(), &route)).unwrap();
+    
+    let misdirected = return_request(&env, 5, 2, 9);
+    env.tx1(&misdirected).unwrap();
+    let result = env.tx2_with(&misdirected, return_token_transfer(&env, &misdirected, &parked.envelope));
+    assert_eq!(error_code(result), GatewayError::ClaimMismatch.into());
+    
+    let request = return_request(&env, 6, 4, 10);
+    env.tx1(&request).unwrap();
+    env.tx2_with(&request, return_token_transfer(&env, &request, &parked.envelope)).unwrap();
+    assert!(!env.exists(&message_pda(TOKEN_CLAIM_SEED, &parked.envelope)));
+    assert_eq!(env.token_balance(&get_associated_token_address(&funded.pubkey(), &mint)), 0);
+    assert_eq!(env.token_balance(&escrow(&mint)), 600);
+}
+
+#[test]
+fn claim_delivery_pays_only_the_signing_recipient() {
+    let mut env = Svm::new();
+    let mint = bridged_mint(&mut env, 1_000);
+    let route = InboundRoute::default();
+    let claim_delivery = TokenSettings { claim_delivery: true, ..token_settings() };
+    env.send(&[env.set_token_config(&mint, claim_delivery)]).unwrap();
+    let recipient = holder(&mut env, &mint, 0);
+    let recipient_account = get_associated_token_address(&recipient.pubkey(), &mint);
+    
+    // Nothing is pushed, even to an existing token account
+    let message = env.attest(token_transfer(2, &mint, &recipient.pubkey(), 400), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    let result = env.tx2_with(&message, env.release_tokens(&message, &mint, &recipient_account, &route));
+    assert_eq!(error_code(result), GatewayError::ClaimDeliveryRequired.into());
+    env.tx2_with(&message, env.park_token_transfer(&message, &mint, &recipient.pubkey(), &route)).unwrap();
+    
+    // Redeeming takes the recipient's own signature, while it is not blocked
+    let intruder = holder(&mut env, &mint, 0);
+    let stolen = claim_token_transfer(&env, &intruder.pubkey(), &mint, &message.envelope);
+    assert_eq!(error_code(env.send_as(&intruder, &[stolen])), GatewayError::InvalidTokenRecipient.into());
+    
+    env.send(&[env.set_blocklist_entry(recipient.pubkey(), true)]).unwrap();
+    let claim = claim_token_transfer(&env, &recipient.pubkey(), &mint, &message.envelope);
+    assert_eq!(error_code(env.send_as(&recipient, &[claim.clone()])), GatewayError::AddressBlocked.into());
+    assert_eq!(env.token_balance(&recipient_account), 0);
+    
+    env.send(&[env.set_blocklist_entry(recipient.pubkey(), false)]).unwrap();
+    env.send_as(&recipient, &[claim]).unwrap();
+    assert_eq!(env.token_balance(&recipient_account), 400);
+    assert_eq!(env.token_balance(&escrow(&mint)), 600);
+    assert!(!env.exists(&message_pda(TOKEN_CLAIM_SEED, &message.envelope)));
+}
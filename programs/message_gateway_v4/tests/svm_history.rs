@@ -0,0 +1,248 @@
+//! Message history against the built program in LiteSVM: every TX2 that closes a TxId
+//! PDA appends exactly one leaf to the history tree
+#![cfg(feature = "program-test")]
+
+mod svm;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use message_gateway_v4::constants::{DEAD_LETTER_SEED, QUERY_SEED, TOKEN_CLAIM_SEED, VESTING_SEED};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::payloads::{
+    QueryResponsePayload, TokenVestingPayload, ValueTransferPayload, PAYLOAD_KIND_PROGRAM_DELIVERY,
+    PAYLOAD_KIND_QUERY_REQUEST, PAYLOAD_KIND_QUERY_RESPONSE, PAYLOAD_KIND_TOKEN_TRANSFER,
+    PAYLOAD_KIND_TOKEN_VESTING, PAYLOAD_KIND_VALUE_TRANSFER,
+};
+use message_gateway_v4::state::{MessageEnvelope, SignerRegistryType};
+use message_gateway_v4::txbuilder::{deliver_message_instruction, route_remaining_accounts, InboundRoute};
+use message_gateway_v4::utils::bubblegum::NOOP_PROGRAM_ID;
+use message_gateway_v4::{accounts, instruction};
+use solana_sdk::signature::{Keypair, Signer};
+use svm::{
+    bridge_envelope, bridge_peer, build, chain_config, envelope, error_code, fee_config, fee_vault, gateway,
+    message_pda, pda, registry, sol_vault, token_config, token_settings, token_transfer, tx_id_pda, Attested, Svm,
+    ALL_LAYERS, GATEWAY_CHAIN, SOURCE_CHAIN,
+};
+
+fn dead_letter_token_transfer(env: &Svm, message: &Attested, route: &InboundRoute) -> Instruction {
+    let mut dead_letter = build(
+        accounts::DeadLetterTokenTransfer {
+            gateway: gateway(),
+            tx_id_pda: tx_id_pda(&message.envelope),
+            bridge_peer: bridge_peer(SOURCE_CHAIN),
+            chain_config: chain_config(message.envelope.source_chain_id),
+            via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+            chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            project_registry: None,
+            relayer_pool: None,
+            payer: None,
+            fee_config: route.claim_refund.then(|| fee_config(SOURCE_CHAIN)),
+            fee_vault: route.claim_refund.then(fee_vault),
+            dead_letter: message_pda(DEAD_LETTER_SEED, &message.envelope),
+            relayer: env.authority.pubkey(),
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::DeadLetterTokenTransfer {
+            envelope: message.envelope.clone(),
+            signatures: message.signatures.clone(),
+        },
+    );
+    dead_letter.accounts.extend(route_remaining_accounts(route));
+    dead_letter
+}
+
+fn create_vesting_schedule(env: &Svm, message: &Attested, mint: &Pubkey, route: &InboundRoute) -> Instruction {
+    let mut vesting = build(
+        accounts::CreateVestingSchedule {
+            gateway: gateway(),
+            tx_id_pda: tx_id_pda(&message.envelope),
+            bridge_peer: bridge_peer(SOURCE_CHAIN),
+            chain_config: chain_config(message.envelope.source_chain_id),
+            via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+            chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            project_registry: None,
+            relayer_pool: None,
+            payer: None,
+            mint: *mint,
+            token_config: token_config(mint),
+            wrapped_asset: None,
+            vesting_schedule: message_pda(VESTING_SEED, &message.envelope),
+            relayer: env.authority.pubkey(),
+            instructions: sysvar::instructions::ID,
+            token_program: spl_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::CreateVestingSchedule {
+            envelope: message.envelope.clone(),
+            signatures: message.signatures.clone(),
+        },
+    );
+    vesting.accounts.extend(route_remaining_accounts(route));
+    vesting
+}
+
+/// Query `query_id` from the authority to SOURCE_CHAIN, answered into the noop program
+fn send_query(env: &Svm, query_id: u128) -> Instruction {
+    let requester = env.authority.pubkey();
+    let request = MessageEnvelope {
+        recipient: vec![0xee; 20],
+        message_type: PAYLOAD_KIND_QUERY_REQUEST,
+        ..envelope(query_id, GATEWAY_CHAIN, SOURCE_CHAIN, requester.to_bytes().to_vec())
+    };
+    build(
+        accounts::SendQuery {
+            gateway: gateway(),
+            query: pda(&[QUERY_SEED, requester.as_ref(), &query_id.to_le_bytes()]),
+            requester,
+            payer: requester,
+            system_program: system_program::ID,
+        },
+        instruction::SendQuery { envelope: request, callback_program: NOOP_PROGRAM_ID, confirmations: 1 },
+    )
+}
+
+fn fulfill_query(env: &Svm, message: &Attested, query_id: u128, route: &InboundRoute) -> Instruction {
+    let requester = env.authority.pubkey();
+    let mut fulfill = build(
+        accounts::FulfillQuery {
+            gateway: gateway(),
+            tx_id_pda: tx_id_pda(&message.envelope),
+            query: pda(&[QUERY_SEED, requester.as_ref(), &query_id.to_le_bytes()]),
+            payer: requester,
+            callback_program: NOOP_PROGRAM_ID,
+            chain_config: chain_config(message.envelope.source_chain_id),
+            via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+            chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            project_registry: None,
+            relayer_pool: None,
+            rent_payer: None,
+            relayer: requester,
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::FulfillQuery {
+            envelope: message.envelope.clone(),
+            signatures: message.signatures.clone(),
+        },
+    );
+    fulfill.accounts.extend(route_remaining_accounts(route));
+    fulfill
+}
+
+#[test]
+fn every_closing_tx2_appends_one_history_leaf() {
+    let mut env = Svm::new();
+    let tree = env.enable_message_history();
+    let route = InboundRoute {
+        gateway_chain_id: GATEWAY_CHAIN,
+        message_history_tree: Some(tree),
+        ..Default::default()
+    };
+    let relayer = env.authority.pubkey();
+    assert_eq!(env.history_leaf_count(&tree), 0);
+    
+    // Token bridge with 1_000 tokens locked in escrow
+    let mint = env.create_mint();
+    env.send(&[env.set_bridge_peer(), env.set_token_config(&mint, token_settings())]).unwrap();
+    env.token_account(&mint, &relayer, 1_000);
+    env.send(&[env.deposit_tokens(&relayer, &mint, 1, 1_000)]).unwrap();
+    
+    // process_message, refused without the history accounts
+    let message = env.inbound(1, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    assert_eq!(
+        error_code(env.tx2(&message, InboundRoute::default())),
+        GatewayError::MissingMessageHistory.into()
+    );
+    env.tx2(&message, route.clone()).unwrap();
+    assert_eq!(env.history_leaf_count(&tree), 1);
+    
+    // deliver_message into the noop program
+    let delivery = MessageEnvelope {
+        recipient: NOOP_PROGRAM_ID.to_bytes().to_vec(),
+        message_type: PAYLOAD_KIND_PROGRAM_DELIVERY,
+        ..envelope(2, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20])
+    };
+    let message = env.attest(delivery, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    let deliver = deliver_message_instruction(&relayer, &route, &message.envelope, &message.signatures, &[]);
+    env.tx2_with(&message, deliver).unwrap();
+    assert_eq!(env.history_leaf_count(&tree), 2);
+    
+    // release_tokens; a rejected release appends nothing
+    let beneficiary = Keypair::new().pubkey();
+    let beneficiary_account = env.token_account(&mint, &beneficiary, 0);
+    let message = env.attest(token_transfer(3, &mint, &beneficiary, 400), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    let misdirected = env.release_tokens(&message, &mint, &get_associated_token_address(&relayer, &mint), &route);
+    assert_eq!(error_code(env.tx2_with(&message, misdirected)), GatewayError::InvalidTokenRecipient.into());
+    assert_eq!(env.history_leaf_count(&tree), 2);
+    env.tx2_with(&message, env.release_tokens(&message, &mint, &beneficiary_account, &route)).unwrap();
+    assert_eq!(env.token_balance(&beneficiary_account), 400);
+    assert_eq!(env.history_leaf_count(&tree), 3);
+    
+    // park_token_transfer, for a recipient without a token account
+    let unfunded = Keypair::new().pubkey();
+    let message = env.attest(token_transfer(4, &mint, &unfunded, 100), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    env.tx2_with(&message, env.park_token_transfer(&message, &mint, &unfunded, &route)).unwrap();
+    assert!(env.exists(&message_pda(TOKEN_CLAIM_SEED, &message.envelope)));
+    assert_eq!(env.history_leaf_count(&tree), 4);
+    
+    // dead_letter_token_transfer, for a payload version this program cannot decode
+    let message = env.attest(bridge_envelope(5, PAYLOAD_KIND_TOKEN_TRANSFER, vec![2, 0, 0]), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    env.tx2_with(&message, dead_letter_token_transfer(&env, &message, &route)).unwrap();
+    assert!(env.exists(&message_pda(DEAD_LETTER_SEED, &message.envelope)));
+    assert_eq!(env.history_leaf_count(&tree), 5);
+    
+    // create_vesting_schedule
+    let grant = TokenVestingPayload {
+        token: mint.to_bytes(),
+        amount: 100,
+        sender: vec![0xaa; 20],
+        recipient: beneficiary.to_bytes().to_vec(),
+        start_time: 0,
+        cliff_seconds: 0,
+        duration_seconds: 100,
+        period_seconds: 10,
+    };
+    let message = env.attest(bridge_envelope(6, PAYLOAD_KIND_TOKEN_VESTING, grant.encode().unwrap()), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    env.tx2_with(&message, create_vesting_schedule(&env, &message, &mint, &route)).unwrap();
+    assert!(env.exists(&message_pda(VESTING_SEED, &message.envelope)));
+    assert_eq!(env.history_leaf_count(&tree), 6);
+    
+    // release_value out of a funded SOL vault
+    env.svm.airdrop(&sol_vault(), 1_000_000_000).unwrap();
+    let value = ValueTransferPayload {
+        amount: 10_000_000,
+        recipient: beneficiary.to_bytes().to_vec(),
+        data: Vec::new(),
+    };
+    let message = env.attest(bridge_envelope(7, PAYLOAD_KIND_VALUE_TRANSFER, value.encode().unwrap()), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    env.tx2_with(&message, env.release_value(&message, &beneficiary, &route)).unwrap();
+    assert_eq!(env.svm.get_balance(&beneficiary).unwrap(), 10_000_000);
+    assert_eq!(env.history_leaf_count(&tree), 7);
+    
+    // fulfill_query, answering a query sent from this gateway
+    let query_id = 100;
+    env.send(&[send_query(&env, query_id)]).unwrap();
+    let answer = QueryResponsePayload { query_id, result: b"answer".to_vec() };
+    let response = MessageEnvelope {
+        recipient: relayer.to_bytes().to_vec(),
+        on_chain_data: answer.encode().unwrap(),
+        message_type: PAYLOAD_KIND_QUERY_RESPONSE,
+        ..envelope(8, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xee; 20])
+    };
+    let message = env.attest(response, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    env.tx2_with(&message, fulfill_query(&env, &message, query_id, &route)).unwrap();
+    assert!(!env.exists(&pda(&[QUERY_SEED, relayer.as_ref(), &query_id.to_le_bytes()])));
+    assert_eq!(env.history_leaf_count(&tree), 8);
+}
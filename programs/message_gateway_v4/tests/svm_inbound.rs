@@ -0,0 +1,615 @@
+//! TX1, TX2, relayer pool and gc_batch rejections, against the built program in LiteSVM
+#![cfg(feature = "program-test")]
+
+mod svm;
+
+use anchor_lang::solana_program::clock::Clock;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::AnchorDeserialize;
+use message_gateway_v4::constants::{COUNTER_SEED, DEFAULT_INSTANCE_ID, MAX_SHADOW_TRIAL_SECONDS, PROTOCOL_VERSION};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::payloads::PAYLOAD_KIND_PROGRAM_DELIVERY;
+use message_gateway_v4::state::{
+    instance_seed, HashAlgorithm, HashScheme, IntegerEncoding, MessageEnvelope, SignerRegistryType,
+};
+use message_gateway_v4::txbuilder::{deliver_message_instruction, process_message_instruction, InboundRoute};
+use message_gateway_v4::utils::compact::encode_compact_message;
+use message_gateway_v4::utils::hash::{create_cross_chain_hash, HashConsistency};
+use message_gateway_v4::{accounts, instruction, ID};
+use solana_sdk::signature::{Keypair, Signer};
+use svm::{
+    build, chain_config, envelope, error_code, fee_config, fee_vault, gateway, instance_chain_config, instance_gateway,
+    instance_registry, instance_tx_id_pda, pda, registry, relayer_pool, tx_id_pda, Svm, ALL_LAYERS, GATEWAY_CHAIN,
+    SOURCE_CHAIN,
+};
+
+fn gc_batch(env: &Svm, remaining: Vec<AccountMeta>) -> Instruction {
+    let mut ix = build(accounts::GcBatch { keeper: env.authority.pubkey() }, instruction::GcBatch {});
+    ix.accounts.extend(remaining);
+    ix
+}
+
+/// process_message_compact claiming the source chain's relayer refund
+fn process_message_compact(env: &Svm, message: &svm::Attested) -> Instruction {
+    let envelope = &message.envelope;
+    build(
+        accounts::ProcessMessageCompact {
+            gateway: gateway(),
+            tx_id_pda: tx_id_pda(envelope),
+            chain_config: chain_config(message.envelope.source_chain_id),
+            via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+            chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            channel: None,
+            project_registry: None,
+            relayer_pool: None,
+            payer: None,
+            fee_config: Some(fee_config(SOURCE_CHAIN)),
+            fee_vault: Some(fee_vault()),
+            relayer: env.authority.pubkey(),
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        },
+        instruction::ProcessMessageCompact {
+            source_chain_id: envelope.source_chain_id,
+            tx_id: envelope.tx_id,
+            message: encode_compact_message(envelope, &message.signatures),
+        },
+    )
+}
+
+fn initialize_instance(env: &Svm, instance_id: u16) -> Instruction {
+    build(
+        accounts::InitializeGatewayInstance {
+            gateway: instance_gateway(instance_id),
+            authority: env.authority.pubkey(),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeGatewayInstance { chain_id: GATEWAY_CHAIN, instance_id },
+    )
+}
+
+#[test]
+fn tx1_rejects_unverified_and_replayed_messages() {
+    let mut env = Svm::new();
+    
+    let mut unsigned = env.inbound(1, &[0, 1]);
+    unsigned.signatures.clear();
+    assert_eq!(error_code(env.tx1(&unsigned)), GatewayError::TooManySignatures.into());
+    
+    // Precompiles over another hash verify but prove nothing about this message
+    let mut forged = env.inbound(2, &[0, 1]);
+    forged.hash = [7; 32];
+    assert_eq!(error_code(env.tx1(&forged)), GatewayError::InvalidSignature.into());
+    
+    let message = env.inbound(3, &[0, 1, 2, 3]);
+    env.tx1(&message).unwrap();
+    // A second TX1 while the TxId PDA is still open
+    assert_eq!(error_code(env.tx1(&message)), GatewayError::AlreadyProcessed.into());
+    env.tx2(&message, InboundRoute::default()).unwrap();
+    assert_eq!(error_code(env.tx1(&message)), GatewayError::RecentTxIdReplay.into());
+    
+    let zero_chain = env.attest(envelope(4, 0, GATEWAY_CHAIN, vec![0xab; 20]), &[0, 1]);
+    assert_eq!(error_code(env.tx1(&zero_chain)), GatewayError::InvalidChainId.into());
+}
+
+#[test]
+fn tx1_rejects_unroutable_envelopes_before_taking_rent() {
+    let mut env = Svm::new();
+    // The pooled path names this gateway whatever the envelope's destination
+    env.send(&[env.deposit_relayer_pool(1_000_000_000)]).unwrap();
+    let cases: [(fn(&mut MessageEnvelope), GatewayError); 2] = [
+        (|e| e.dest_chain_id = 5, GatewayError::InvalidDestChain),
+        (|e| e.source_chain_id = GATEWAY_CHAIN, GatewayError::LoopbackDisabled),
+    ];
+    
+    for (tx_id, (mutate, expected)) in cases.into_iter().enumerate() {
+        let mut envelope = envelope(10 + tx_id as u128, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]);
+        mutate(&mut envelope);
+        let message = env.attest(envelope, ALL_LAYERS);
+        assert_eq!(error_code(env.tx1_pooled(&message)), expected.into(), "case {tx_id}");
+        assert!(!env.exists(&tx_id_pda(&message.envelope)), "case {tx_id}");
+    }
+}
+
+#[test]
+fn tx2_rejects_unsupported_protocol_versions() {
+    let mut env = Svm::new();
+    let mut envelope = envelope(12, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]);
+    envelope.protocol_version += 1;
+    let message = env.attest(envelope, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    assert_eq!(
+        error_code(env.tx2(&message, InboundRoute::default())),
+        GatewayError::UnsupportedProtocolVersion.into()
+    );
+}
+
+#[test]
+fn tx2_accepts_messages_attested_under_the_previous_protocol_version() {
+    let mut env = Svm::new();
+    let supported = build(
+        accounts::SetProtocolVersions { gateway: gateway(), authority: env.authority.pubkey() },
+        instruction::SetProtocolVersions {
+            min_protocol_version: PROTOCOL_VERSION - 1,
+            max_protocol_version: PROTOCOL_VERSION,
+        },
+    );
+    env.send(&[supported]).unwrap();
+    
+    let mut envelope = envelope(13, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]);
+    envelope.protocol_version -= 1;
+    let message = env.attest(envelope, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    env.tx2(&message, InboundRoute::default()).unwrap();
+    assert!(!env.exists(&tx_id_pda(&message.envelope)));
+}
+
+#[test]
+fn tx1_and_tx2_reject_while_paused() {
+    let mut env = Svm::new();
+    let message = env.inbound(20, ALL_LAYERS);
+    let pending = env.inbound(21, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    
+    env.send(&[env.set_system_enabled(false)]).unwrap();
+    assert_eq!(error_code(env.tx1(&pending)), GatewayError::SystemDisabled.into());
+    assert_eq!(error_code(env.tx2(&message, InboundRoute::default())), GatewayError::SystemDisabled.into());
+    env.send(&[env.set_system_enabled(true)]).unwrap();
+    env.tx2(&message, InboundRoute::default()).unwrap();
+    env.tx1(&pending).unwrap();
+}
+
+#[test]
+fn tx2_rejects_a_single_attestation() {
+    let mut env = Svm::new();
+    let mut message = env.inbound(30, &[0, 1]);
+    env.tx1(&message).unwrap();
+    
+    message.signatures.truncate(1);
+    assert_eq!(error_code(env.tx2(&message, InboundRoute::default())), GatewayError::TooFewSignatures.into());
+}
+
+#[test]
+fn tx2_rejects_messages_past_their_deadline() {
+    let mut env = Svm::new();
+    let mut clock = env.svm.get_sysvar::<Clock>();
+    let mut envelope = envelope(40, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]);
+    envelope.deadline = clock.unix_timestamp + 60;
+    let message = env.attest(envelope, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    
+    clock.unix_timestamp += 61;
+    env.svm.set_sysvar(&clock);
+    assert_eq!(error_code(env.tx2(&message, InboundRoute::default())), GatewayError::MessageExpired.into());
+}
+
+#[test]
+fn tx2_rejects_through_a_disabled_registry() {
+    let mut env = Svm::new();
+    let message = env.inbound(50, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    
+    let disable = build(
+        accounts::SetRegistryEnabled {
+            signer_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+        },
+        instruction::SetRegistryEnabled {
+            registry_type: SignerRegistryType::Chain,
+            chain_id: SOURCE_CHAIN,
+            enabled: false,
+        },
+    );
+    env.send(&[disable]).unwrap();
+    assert_eq!(
+        error_code(env.tx2(&message, InboundRoute::default())),
+        GatewayError::SignerRegistryDisabled.into()
+    );
+}
+
+#[test]
+fn each_layer_enforces_its_threshold() {
+    let mut env = Svm::new();
+    let cases = [
+        (&[2, 3][..], InboundRoute::default(), GatewayError::InsufficientVIASignatures),
+        (&[0, 1][..], InboundRoute::default(), GatewayError::InsufficientChainSignatures),
+        (&[0, 1, 2, 3][..], Svm::with_project(), GatewayError::InsufficientProjectSignatures),
+    ];
+    
+    for (tx_id, (signers, route, expected)) in cases.into_iter().enumerate() {
+        let message = env.inbound(60 + tx_id as u128, signers);
+        env.tx1(&message).unwrap();
+        assert_eq!(error_code(env.tx2(&message, route)), expected.into(), "case {tx_id}");
+        assert!(env.exists(&tx_id_pda(&message.envelope)));
+    }
+}
+
+#[test]
+fn shadow_trial_evaluates_a_raised_threshold_without_enforcing_it() {
+    let mut env = Svm::new();
+    let shadow = |env: &Svm, enforced_signatures: u8, shadow_until: i64| {
+        build(
+            accounts::SetRegistryShadow {
+                signer_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+                gateway: gateway(),
+                authority: env.authority.pubkey(),
+            },
+            instruction::SetRegistryShadow {
+                registry_type: SignerRegistryType::Chain,
+                chain_id: SOURCE_CHAIN,
+                enforced_signatures,
+                shadow_until,
+            },
+        )
+    };
+    let update_threshold = |env: &Svm, new_threshold: u8| {
+        build(
+            accounts::UpdateThreshold {
+                signer_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+                gateway: gateway(),
+                authority: env.authority.pubkey(),
+            },
+            instruction::UpdateThreshold { registry_type: SignerRegistryType::Chain, chain_id: SOURCE_CHAIN, new_threshold },
+        )
+    };
+    let mut clock = env.svm.get_sysvar::<Clock>();
+    let now = clock.unix_timestamp;
+    
+    // Still in its creation slot the registry is new, so a trial may enforce nothing
+    env.send(&[shadow(&env, 0, now + 60)]).unwrap();
+    env.send(&[shadow(&env, 0, 0)]).unwrap();
+    clock.slot += 1;
+    env.svm.set_sysvar(&clock);
+    
+    // Raise the threshold from 1 to 2: a trial must relax it, but not below 1, and not for
+    // longer than MAX_SHADOW_TRIAL_SECONDS
+    env.send(&[update_threshold(&env, 1)]).unwrap();
+    env.send(&[update_threshold(&env, 2)]).unwrap();
+    for (enforced_signatures, shadow_until) in [(0, now + 60), (2, now + 60), (1, now + MAX_SHADOW_TRIAL_SECONDS + 1)] {
+        assert_eq!(
+            error_code(env.send(&[shadow(&env, enforced_signatures, shadow_until)])),
+            GatewayError::InvalidShadowTrial.into()
+        );
+    }
+    
+    // A single Chain signature misses the threshold of 2, which the trial only reports
+    env.send(&[shadow(&env, 1, now + 60)]).unwrap();
+    let delivered = env.inbound(65, &[0, 1, 2]);
+    env.tx1(&delivered).unwrap();
+    env.tx2(&delivered, InboundRoute::default()).unwrap();
+    
+    // Once the trial is over the threshold is enforced again
+    let rejected = env.inbound(66, &[0, 1, 2]);
+    env.tx1(&rejected).unwrap();
+    clock.unix_timestamp = now + 60;
+    env.svm.set_sysvar(&clock);
+    assert_eq!(
+        error_code(env.tx2(&rejected, InboundRoute::default())),
+        GatewayError::InsufficientChainSignatures.into()
+    );
+}
+
+#[test]
+fn relayer_pool_must_cover_and_reclaim_rent() {
+    let mut env = Svm::new();
+    assert_eq!(error_code(env.send(&[env.deposit_relayer_pool(0)])), GatewayError::InvalidAmount.into());
+    
+    let message = env.inbound(70, ALL_LAYERS);
+    env.send(&[env.deposit_relayer_pool(1)]).unwrap();
+    assert_eq!(error_code(env.tx1_pooled(&message)), GatewayError::RelayerPoolInsufficient.into());
+    
+    // TX2 of a pooled TxId PDA must return the rent to the pool that advanced it
+    env.send(&[env.deposit_relayer_pool(1_000_000_000)]).unwrap();
+    env.tx1_pooled(&message).unwrap();
+    assert_eq!(
+        error_code(env.tx2(&message, InboundRoute::default())),
+        GatewayError::InvalidRelayerPool.into()
+    );
+    let pool = relayer_pool(&env.authority.pubkey());
+    env.tx2(&message, InboundRoute { relayer_pool: Some(pool), ..Default::default() }).unwrap();
+}
+
+#[test]
+fn a_separate_payer_funds_and_reclaims_tx_id_rent() {
+    let mut env = Svm::new();
+    let treasury = Keypair::new();
+    env.svm.airdrop(&treasury.pubkey(), 1_000_000_000).unwrap();
+    let relayer = env.authority.pubkey();
+    // Opens the source chain counter, whose rent the relayer still pays
+    let opening = env.inbound(79, ALL_LAYERS);
+    env.tx1(&opening).unwrap();
+    
+    // The treasury pays the fee and the TxId PDA rent; the relayer key spends nothing
+    let message = env.inbound(80, ALL_LAYERS);
+    let relayer_balance = env.svm.get_balance(&relayer).unwrap();
+    env.tx1_funded(&message, &treasury).unwrap();
+    assert_eq!(env.svm.get_balance(&relayer).unwrap(), relayer_balance);
+    
+    // TX2 must name the payer TX1 recorded, which gets the rent back
+    assert_eq!(
+        error_code(env.tx2(&message, InboundRoute::default())),
+        GatewayError::InvalidRentPayer.into()
+    );
+    let treasury_balance = env.svm.get_balance(&treasury.pubkey()).unwrap();
+    let rent = env.svm.get_balance(&tx_id_pda(&message.envelope)).unwrap();
+    env.tx2(&message, InboundRoute { rent_payer: Some(treasury.pubkey()), ..Default::default() }).unwrap();
+    assert_eq!(env.svm.get_balance(&treasury.pubkey()).unwrap(), treasury_balance + rent);
+}
+
+#[test]
+fn claimed_relayer_refunds_are_paid_from_the_fee_vault_up_to_its_balance() {
+    let mut env = Svm::new();
+    assert_eq!(
+        error_code(env.send(&[env.set_relayer_refund(0, 10_001)])),
+        GatewayError::InvalidRelayerRefund.into()
+    );
+    
+    // 20_000 flat plus half of the 10_000 send fee
+    env.send(&[env.set_fee_config(10_000, 0), env.set_relayer_refund(20_000, 5_000)]).unwrap();
+    let rent_exempt = env.svm.get_balance(&fee_vault()).unwrap();
+    env.svm.airdrop(&fee_vault(), 30_000).unwrap();
+    let claim = InboundRoute { claim_refund: true, ..Default::default() };
+    
+    // Unclaimed, nothing is paid
+    let unclaimed = env.inbound(85, ALL_LAYERS);
+    env.tx1(&unclaimed).unwrap();
+    env.tx2(&unclaimed, InboundRoute::default()).unwrap();
+    assert_eq!(env.svm.get_balance(&fee_vault()).unwrap(), rent_exempt + 30_000);
+    
+    let first = env.inbound(86, ALL_LAYERS);
+    env.tx1(&first).unwrap();
+    env.tx2(&first, claim.clone()).unwrap();
+    assert_eq!(env.svm.get_balance(&fee_vault()).unwrap(), rent_exempt + 5_000);
+    
+    // The compact TX2 claims it too, up to what the vault holds above rent exemption
+    let compact = env.inbound(89, ALL_LAYERS);
+    env.tx1(&compact).unwrap();
+    env.tx2_with(&compact, process_message_compact(&env, &compact)).unwrap();
+    assert_eq!(env.svm.get_balance(&fee_vault()).unwrap(), rent_exempt);
+    
+    // An empty vault pays nothing, without failing TX2
+    for tx_id in [87, 88] {
+        let message = env.inbound(tx_id, ALL_LAYERS);
+        env.tx1(&message).unwrap();
+        env.tx2(&message, claim.clone()).unwrap();
+        assert_eq!(env.svm.get_balance(&fee_vault()).unwrap(), rent_exempt);
+    }
+}
+
+#[test]
+fn gc_batch_only_closes_expired_tx_id_pdas() {
+    let mut env = Svm::new();
+    assert_eq!(error_code(env.send(&[gc_batch(&env, Vec::new())])), GatewayError::InvalidGcBatch.into());
+    
+    let message = env.inbound(80, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    let pair = vec![
+        AccountMeta::new(tx_id_pda(&message.envelope), false),
+        AccountMeta::new(env.authority.pubkey(), false),
+    ];
+    assert_eq!(error_code(env.send(&[gc_batch(&env, pair)])), GatewayError::TxIdPdaNotExpired.into());
+}
+
+#[test]
+fn verify_hash_consistency_returns_the_hashes_the_program_computes() {
+    let mut env = Svm::new();
+    let message = env.inbound(90, ALL_LAYERS);
+    let cross_chain_hash = create_cross_chain_hash(HashScheme::default(), &message.envelope).unwrap();
+    let verify = |cross_chain_hash: [u8; 32]| {
+        build(
+            accounts::VerifyHash { gateway: gateway(), chain_config: chain_config(SOURCE_CHAIN) },
+            instruction::VerifyHashConsistency { envelope: message.envelope.clone(), cross_chain_hash },
+        )
+    };
+    
+    let result = HashConsistency::deserialize(&mut &env.simulate_return(verify(cross_chain_hash))[..]).unwrap();
+    assert_eq!(
+        result,
+        HashConsistency { cross_chain_hash, signing_hash: message.hash, consistent: true }
+    );
+    
+    // A diverging counterpart hash is reported, not raised, alongside the gateway's own
+    let result = HashConsistency::deserialize(&mut &env.simulate_return(verify([0; 32]))[..]).unwrap();
+    assert_eq!(
+        result,
+        HashConsistency { cross_chain_hash, signing_hash: message.hash, consistent: false }
+    );
+}
+
+/// Gateway instance `instance_id` with its own VIA {0, 1} and Chain {2, 3} registries
+fn initialize_instance_with_registries(env: &Svm, instance_id: u16) -> Vec<Instruction> {
+    let mut setup = vec![initialize_instance(env, instance_id)];
+    for (registry_type, chain_id, members) in [
+        (SignerRegistryType::VIA, GATEWAY_CHAIN, [0, 1]),
+        (SignerRegistryType::Chain, SOURCE_CHAIN, [2, 3]),
+    ] {
+        setup.push(build(
+            accounts::InitializeSignerRegistry {
+                signer_registry: instance_registry(registry_type, chain_id, instance_id),
+                gateway: instance_gateway(instance_id),
+                authority: env.authority.pubkey(),
+                system_program: system_program::ID,
+            },
+            instruction::InitializeSignerRegistry {
+                registry_type,
+                chain_id,
+                initial_signers: members.map(|i| env.validators[i].pubkey()).to_vec(),
+                required_signatures: 2,
+            },
+        ));
+    }
+    setup
+}
+
+#[test]
+fn gateway_instances_validate_with_their_own_registries_and_signing_domain() {
+    const CANARY: u16 = 1;
+    
+    // The default instance is the one initialize_gateway creates
+    let mut bare = Svm::bare();
+    assert_eq!(
+        error_code(bare.send(&[initialize_instance(&bare, DEFAULT_INSTANCE_ID)])),
+        GatewayError::InvalidGatewayInstance.into()
+    );
+    
+    let mut env = Svm::new();
+    env.send(&initialize_instance_with_registries(&env, CANARY)).unwrap();
+    
+    // Attestations for the default instance prove nothing to the canary
+    let default_signed = env.inbound(1, &[0, 1, 2, 3]);
+    assert_eq!(
+        error_code(env.tx1_for_instance(&default_signed, CANARY)),
+        GatewayError::InvalidSignature.into()
+    );
+    
+    let message = env.attest_for_instance(envelope(1, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]), &[0, 1, 2, 3], CANARY);
+    env.tx1_for_instance(&message, CANARY).unwrap();
+    env.tx2(&message, InboundRoute { instance_id: CANARY, ..Default::default() }).unwrap();
+    
+    // The canary counts the source chain's tx_ids on its own counter
+    assert!(env.exists(&pda(&[COUNTER_SEED, &SOURCE_CHAIN.to_le_bytes(), &instance_seed(CANARY)])));
+    assert!(!env.exists(&pda(&[COUNTER_SEED, &SOURCE_CHAIN.to_le_bytes()])));
+    
+    // The default instance keeps its registries and domain alongside it
+    let message = env.inbound(2, &[0, 1, 2, 3]);
+    env.tx1(&message).unwrap();
+    env.tx2(&message, InboundRoute::default()).unwrap();
+}
+
+#[test]
+fn gateway_instances_keep_their_own_chain_configs() {
+    const CANARY: u16 = 2;
+    
+    let mut env = Svm::new();
+    env.send(&initialize_instance_with_registries(&env, CANARY)).unwrap();
+    
+    // The canary moves SOURCE_CHAIN to SHA-256 on its own PDA
+    let canary_config = instance_chain_config(SOURCE_CHAIN, CANARY);
+    assert_ne!(canary_config, chain_config(SOURCE_CHAIN));
+    env.send(&[build(
+        accounts::InitializeChainConfig {
+            chain_config: canary_config,
+            gateway: instance_gateway(CANARY),
+            authority: env.authority.pubkey(),
+            system_program: system_program::ID,
+        },
+        instruction::InitializeChainConfig {
+            chain_id: SOURCE_CHAIN,
+            hash_algorithm: HashAlgorithm::Sha256,
+            integer_encoding: IntegerEncoding::LittleEndian,
+        },
+    )])
+    .unwrap();
+    
+    // The default instance keeps hashing the route with keccak256
+    let default_message = env.inbound(80, ALL_LAYERS);
+    env.tx1(&default_message).unwrap();
+    env.tx2(&default_message, InboundRoute::default()).unwrap();
+    
+    // while keccak256 attestations no longer match the canary's route
+    let canary_message =
+        env.attest_for_instance(envelope(80, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]), &[0, 1, 2, 3], CANARY);
+    assert_eq!(error_code(env.tx1_for_instance(&canary_message, CANARY)), GatewayError::InvalidSignature.into());
+}
+
+#[test]
+fn gateway_instances_keep_separate_replay_slots() {
+    const CANARY: u16 = 1;
+    
+    let mut env = Svm::new();
+    env.send(&initialize_instance_with_registries(&env, CANARY)).unwrap();
+    let relayer = env.authority.pubkey();
+    
+    let canary_message = env.attest_for_instance(envelope(1, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]), &[0, 1, 2, 3], CANARY);
+    env.tx1_for_instance(&canary_message, CANARY).unwrap();
+    let canary_tx_id_pda = instance_tx_id_pda(&canary_message.envelope, CANARY);
+    assert!(env.exists(&canary_tx_id_pda));
+    assert!(!env.exists(&tx_id_pda(&canary_message.envelope)));
+    
+    // The default instance cannot complete a message on the canary's TxId PDA
+    let mut cross_instance = process_message_instruction(
+        &relayer,
+        &InboundRoute { gateway_chain_id: GATEWAY_CHAIN, ..Default::default() },
+        &canary_message.envelope,
+        &canary_message.signatures,
+    );
+    cross_instance.accounts[1].pubkey = canary_tx_id_pda;
+    assert_eq!(
+        error_code(env.tx2_with(&canary_message, cross_instance)),
+        anchor_lang::error::ErrorCode::ConstraintSeeds as u32
+    );
+    
+    // The same tx_id has its own slot on the default instance, and neither TX2 consumes the other's
+    let default_message = env.inbound(1, &[0, 1, 2, 3]);
+    env.tx1(&default_message).unwrap();
+    env.tx2(&default_message, InboundRoute::default()).unwrap();
+    assert!(env.exists(&canary_tx_id_pda));
+    
+    env.tx2(&canary_message, InboundRoute { instance_id: CANARY, ..Default::default() }).unwrap();
+    assert!(!env.exists(&canary_tx_id_pda));
+}
+
+#[test]
+fn tx2_requires_the_source_chain_config() {
+    let mut env = Svm::new();
+    let relayer = env.authority.pubkey();
+    let message = env.inbound(70, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    
+    // Leaving the configured chain's PDA out (the program id stands for an absent account)
+    // or passing another chain's would skip its hash scheme and sender check
+    let route = InboundRoute { gateway_chain_id: GATEWAY_CHAIN, ..Default::default() };
+    for substitute in [ID, chain_config(GATEWAY_CHAIN)] {
+        let mut tx2 = process_message_instruction(&relayer, &route, &message.envelope, &message.signatures);
+        tx2.accounts[2].pubkey = substitute;
+        assert_eq!(
+            error_code(env.tx2_with(&message, tx2)),
+            anchor_lang::error::ErrorCode::ConstraintSeeds as u32
+        );
+    }
+    env.tx2(&message, InboundRoute::default()).unwrap();
+}
+
+#[test]
+fn program_deliveries_invoke_the_recipient_through_deliver_message_only() {
+    let mut env = Svm::new();
+    let relayer = env.authority.pubkey();
+    let route = InboundRoute { gateway_chain_id: GATEWAY_CHAIN, ..Default::default() };
+    let deliver = |message: &svm::Attested| {
+        deliver_message_instruction(&relayer, &route, &message.envelope, &message.signatures, &[])
+    };
+    
+    // The gateway itself stands in for a recipient program
+    let mut delivery = envelope(60, SOURCE_CHAIN, GATEWAY_CHAIN, vec![0xab; 20]);
+    delivery.recipient = ID.to_bytes().to_vec();
+    delivery.message_type = PAYLOAD_KIND_PROGRAM_DELIVERY;
+    let message = env.attest(delivery.clone(), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    
+    // process_message would skip the CPI
+    assert_eq!(error_code(env.tx2(&message, InboundRoute::default())), GatewayError::InvalidPayload.into());
+    
+    // The recipient program account must be the envelope recipient (the last account
+    // without recipient accounts; absent optional accounts are the program id too)
+    let mut substituted = deliver(&message);
+    substituted.accounts.iter_mut().rev().find(|meta| meta.pubkey == ID).unwrap().pubkey = system_program::ID;
+    assert_eq!(error_code(env.tx2_with(&message, substituted)), GatewayError::InvalidRecipientProgram.into());
+    
+    // The verified message reaches the recipient's on_message_received, which the gateway
+    // does not implement, and the failed CPI leaves the TxId PDA in place
+    assert_eq!(
+        error_code(env.tx2_with(&message, deliver(&message))),
+        anchor_lang::error::ErrorCode::InstructionFallbackNotFound as u32
+    );
+    assert!(env.exists(&tx_id_pda(&message.envelope)));
+    
+    // Other message kinds keep going through process_message
+    delivery.tx_id = 61;
+    delivery.message_type = MessageEnvelope::MESSAGE_TYPE_GENERIC;
+    let message = env.attest(delivery, ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    assert_eq!(error_code(env.tx2_with(&message, deliver(&message))), GatewayError::InvalidPayload.into());
+    env.tx2(&message, InboundRoute::default()).unwrap();
+}
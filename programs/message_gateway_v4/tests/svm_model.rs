@@ -0,0 +1,194 @@
+//! Model-based test: random sequences of sends, TX1s, TX2s, threshold edits and pauses
+//! against the built program in LiteSVM, each step checked against a reference model
+#![cfg(feature = "program-test")]
+
+mod svm;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::AccountDeserialize;
+use message_gateway_v4::constants::COUNTER_SEED;
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::state::{CounterPDA, SignerRegistryType};
+use message_gateway_v4::txbuilder::InboundRoute;
+use message_gateway_v4::{accounts, instruction};
+use proptest::prelude::*;
+use solana_sdk::signature::Signer;
+use svm::{
+    build, error_code, gateway, pda, registry, tx_id_pda, Attested, Svm, TxResult, ALL_LAYERS, SOURCE_CHAIN,
+};
+
+/// Inbound messages the sequences draw from; odd ones carry a single Chain attestation
+const MESSAGES: usize = 6;
+
+#[derive(Clone, Copy, Debug)]
+enum Step {
+    Send,
+    Tx1(usize),
+    Tx2(usize),
+    ChainThreshold(u8),
+    SetSystemEnabled(bool),
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        1 => Just(Step::Send),
+        3 => (0..MESSAGES).prop_map(Step::Tx1),
+        3 => (0..MESSAGES).prop_map(Step::Tx2),
+        1 => (1..=2u8).prop_map(Step::ChainThreshold),
+        1 => any::<bool>().prop_map(Step::SetSystemEnabled),
+    ]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Inbound {
+    Pending,
+    Recorded,
+    Delivered,
+}
+
+/// What the gateway should hold: pause flag, Chain threshold and each message's progress
+struct Model {
+    enabled: bool,
+    chain_threshold: u8,
+    messages: [Inbound; MESSAGES],
+}
+
+impl Model {
+    fn new() -> Self {
+        Self {
+            enabled: true,
+            chain_threshold: 2,
+            messages: [Inbound::Pending; MESSAGES],
+        }
+    }
+    
+    fn chain_signatures(index: usize) -> u8 {
+        if index % 2 == 0 {
+            2
+        } else {
+            1
+        }
+    }
+    
+    /// Apply `step`, returning the error code it must fail with (None = success)
+    fn apply(&mut self, step: Step) -> Option<u32> {
+        let fail = |error: GatewayError| -> Option<u32> { Some(error.into()) };
+        match step {
+            Step::Send if !self.enabled => fail(GatewayError::SystemDisabled),
+            Step::Send => None,
+            Step::Tx1(_) if !self.enabled => fail(GatewayError::SystemDisabled),
+            Step::Tx1(index) => match self.messages[index] {
+                Inbound::Recorded => fail(GatewayError::AlreadyProcessed),
+                // TX2 closed the PDA; the recent tx_id filter still remembers it
+                Inbound::Delivered => fail(GatewayError::RecentTxIdReplay),
+                Inbound::Pending => {
+                    self.messages[index] = Inbound::Recorded;
+                    None
+                }
+            },
+            // Without a TxId PDA, TX2 fails its account constraints before any check
+            Step::Tx2(index) if self.messages[index] != Inbound::Recorded => {
+                Some(ErrorCode::AccountNotInitialized.into())
+            }
+            Step::Tx2(_) if !self.enabled => fail(GatewayError::SystemDisabled),
+            Step::Tx2(index) if Self::chain_signatures(index) < self.chain_threshold => {
+                fail(GatewayError::InsufficientChainSignatures)
+            }
+            Step::Tx2(index) => {
+                self.messages[index] = Inbound::Delivered;
+                None
+            }
+            Step::ChainThreshold(threshold) => {
+                self.chain_threshold = threshold;
+                None
+            }
+            Step::SetSystemEnabled(enabled) => {
+                self.enabled = enabled;
+                None
+            }
+        }
+    }
+    
+    /// Highest tx_id that passed TX1, which the source chain counter must hold
+    fn highest_tx_id_seen(&self, messages: &[Attested]) -> Option<u128> {
+        messages
+            .iter()
+            .zip(self.messages)
+            .filter(|(_, progress)| *progress != Inbound::Pending)
+            .map(|(message, _)| message.envelope.tx_id)
+            .max()
+    }
+}
+
+fn set_chain_threshold(env: &Svm, new_threshold: u8) -> Instruction {
+    build(
+        accounts::UpdateThreshold {
+            signer_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+        },
+        instruction::UpdateThreshold {
+            registry_type: SignerRegistryType::Chain,
+            chain_id: SOURCE_CHAIN,
+            new_threshold,
+        },
+    )
+}
+
+fn run(env: &mut Svm, messages: &[Attested], step: Step, nonce: u128) -> TxResult {
+    let sender = env.authority.pubkey();
+    match step {
+        Step::Send => env.send(&[env.send_message(&sender, env.outbound(nonce + 1))]),
+        Step::Tx1(index) => env.tx1(&messages[index]),
+        Step::Tx2(index) => env.tx2(&messages[index], InboundRoute::default()),
+        Step::ChainThreshold(threshold) => env.send(&[set_chain_threshold(env, threshold)]),
+        Step::SetSystemEnabled(enabled) => env.send(&[env.set_system_enabled(enabled)]),
+    }
+}
+
+/// TxId PDAs open exactly for recorded messages, and the counter exists once a TX1 passed
+/// and holds the highest tx_id seen
+fn check_state(env: &Svm, model: &Model, messages: &[Attested]) {
+    for (index, message) in messages.iter().enumerate() {
+        let recorded = model.messages[index] == Inbound::Recorded;
+        assert_eq!(env.exists(&tx_id_pda(&message.envelope)), recorded, "message {index}");
+    }
+    
+    let counter = pda(&[COUNTER_SEED, &SOURCE_CHAIN.to_le_bytes()]);
+    match model.highest_tx_id_seen(messages) {
+        None => assert!(!env.exists(&counter), "counter opened without a TX1"),
+        Some(highest) => {
+            let account = env.svm.get_account(&counter).expect("counter opened by the first TX1");
+            let counter = CounterPDA::try_deserialize(&mut &account.data[..]).unwrap();
+            assert_eq!(counter.source_chain_id, SOURCE_CHAIN);
+            assert_eq!(counter.highest_tx_id_seen, highest);
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+    
+    #[test]
+    fn gateway_follows_the_model(steps in prop::collection::vec(step(), 1..40)) {
+        let mut env = Svm::new();
+        let messages: Vec<Attested> = (0..MESSAGES)
+            .map(|index| {
+                let signers: &[usize] = if index % 2 == 0 { ALL_LAYERS } else { &[0, 1, 2] };
+                env.inbound(index as u128 + 1, signers)
+            })
+            .collect();
+        let mut model = Model::new();
+        
+        for (nonce, step) in steps.into_iter().enumerate() {
+            let expected = model.apply(step);
+            let result = run(&mut env, &messages, step, nonce as u128);
+            match expected {
+                None => prop_assert!(result.is_ok(), "step {nonce} {step:?}: {result:?}"),
+                Some(code) => prop_assert_eq!(error_code(result), code, "step {} {:?}", nonce, step),
+            }
+            check_state(&env, &model, &messages);
+        }
+    }
+}
@@ -0,0 +1,318 @@
+//! Metaplex NFT bridge against the built program in LiteSVM: native NFTs locked into and
+//! unlocked from escrow, foreign NFTs minted as wrapped NFTs and burned back
+#![cfg(feature = "program-test")]
+
+mod svm;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::{keccak, system_program, sysvar};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::metadata::{self, mpl_token_metadata::types::Key};
+use anchor_spl::token::spl_token;
+use message_gateway_v4::constants::{NFT_COLLECTION_SEED, WRAPPED_NFT_MINT_SEED, WRAPPED_NFT_SEED};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::payloads::{NftMetadata, NftTransferPayload, PAYLOAD_KIND_NFT_TRANSFER};
+use message_gateway_v4::state::{MessageEnvelope, SignerRegistryType};
+use message_gateway_v4::{accounts, instruction};
+use solana_sdk::account::Account;
+use solana_sdk::signature::{Keypair, Signer};
+use svm::{
+    bridge_envelope, bridge_peer, build, chain_config, error_code, escrow, gateway, pda, registry, tx_id_pda, Attested,
+    Svm, ALL_LAYERS, GATEWAY_CHAIN, SOURCE_CHAIN,
+};
+
+/// SOURCE_CHAIN collection and token id of the foreign NFT bridged in
+const FOREIGN_COLLECTION: [u8; 20] = [0x77; 20];
+const FOREIGN_TOKEN_ID: [u8; 32] = [0x01; 32];
+
+/// Load the Metaplex token metadata program, which is not built here: dump it into
+/// tests/fixtures first, e.g. `solana program dump -um
+/// metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s tests/fixtures/mpl_token_metadata.so`
+fn enable_token_metadata(env: &mut Svm) {
+    let fixture = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/mpl_token_metadata.so");
+    env.svm
+        .add_program_from_file(metadata::ID, fixture)
+        .expect("dump the program into tests/fixtures first");
+}
+
+fn metadata_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"metadata", metadata::ID.as_ref(), mint.as_ref()], &metadata::ID).0
+}
+
+fn nft_metadata() -> NftMetadata {
+    NftMetadata {
+        name: "Bridged".to_string(),
+        symbol: "VIA".to_string(),
+        uri: "https://example.com/nft.json".to_string(),
+    }
+}
+
+/// Metaplex metadata of `mint` naming `collection`, in the borsh layout Metaplex stores
+fn metadata_account(mint: &Pubkey, collection: &Pubkey, verified: bool) -> Vec<u8> {
+    let metadata = nft_metadata();
+    let mut data = vec![Key::MetadataV1 as u8];
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.extend_from_slice(mint.as_ref());
+    for field in [&metadata.name, &metadata.symbol, &metadata.uri] {
+        data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        data.extend_from_slice(field.as_bytes());
+    }
+    // No royalties or creators, primary sale pending, mutable, no edition nonce
+    data.extend_from_slice(&[0, 0, 0, 0, 1, 0]);
+    // Non-fungible, then the collection
+    data.extend_from_slice(&[1, 0, 1, verified as u8]);
+    data.extend_from_slice(collection.as_ref());
+    // No uses, collection details or programmable config
+    data.extend_from_slice(&[0, 0, 0]);
+    data
+}
+
+/// Solana-native NFT held by the authority, a member of `collection` that Metaplex has
+/// `verified` or not
+fn native_nft(env: &mut Svm, collection: &Pubkey, verified: bool) -> Pubkey {
+    let mint = Pubkey::new_unique();
+    let authority = env.authority.pubkey();
+    let mut data = vec![0; spl_token::state::Mint::LEN];
+    spl_token::state::Mint {
+        mint_authority: COption::Some(authority),
+        supply: 0,
+        decimals: 0,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    for (address, data, owner) in [
+        (mint, data, spl_token::ID),
+        (metadata_pda(&mint), metadata_account(&mint, collection, verified), metadata::ID),
+    ] {
+        let account = Account {
+            lamports: env.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        };
+        env.svm.set_account(address, account).unwrap();
+    }
+    env.token_account(&mint, &authority, 1);
+    mint
+}
+
+/// NFT transfer from SOURCE_CHAIN's bridge peer of `token_id` in `collection` to `recipient`,
+/// relaying the NFT's metadata
+fn nft_transfer(tx_id: u128, collection: &[u8], token_id: [u8; 32], recipient: &Pubkey) -> MessageEnvelope {
+    let payload = NftTransferPayload {
+        collection: collection.to_vec(),
+        token_id,
+        metadata_uri_hash: nft_metadata().uri_hash(),
+        recipient: recipient.to_bytes().to_vec(),
+    };
+    MessageEnvelope {
+        off_chain_data: nft_metadata().encode().unwrap(),
+        ..bridge_envelope(tx_id, PAYLOAD_KIND_NFT_TRANSFER, payload.encode().unwrap())
+    }
+}
+
+fn register_nft_collection(env: &Svm, collection: &Pubkey) -> Instruction {
+    build(
+        accounts::RegisterNftCollection {
+            nft_collection: pda(&[NFT_COLLECTION_SEED, collection.as_ref()]),
+            collection_mint: *collection,
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+            system_program: system_program::ID,
+        },
+        instruction::RegisterNftCollection {},
+    )
+}
+
+/// lock_nft of the authority's `mint` to SOURCE_CHAIN
+fn lock_nft(env: &Svm, collection: &Pubkey, mint: &Pubkey, tx_id: u128) -> Instruction {
+    let owner = env.authority.pubkey();
+    build(
+        accounts::LockNft {
+            gateway: gateway(),
+            bridge_peer: bridge_peer(SOURCE_CHAIN),
+            chain_config: None,
+            nft_collection: pda(&[NFT_COLLECTION_SEED, collection.as_ref()]),
+            mint: *mint,
+            metadata: metadata_pda(mint),
+            owner_token_account: get_associated_token_address(&owner, mint),
+            escrow: escrow(mint),
+            owner,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: metadata::ID,
+            system_program: system_program::ID,
+        },
+        instruction::LockNft {
+            tx_id,
+            dest_chain_id: SOURCE_CHAIN,
+            recipient: vec![0xcd; 20],
+            confirmations: 1,
+        },
+    )
+}
+
+fn unlock_nft(env: &Svm, message: &Attested, mint: &Pubkey, recipient: &Pubkey) -> Instruction {
+    build(
+        accounts::UnlockNft {
+            gateway: gateway(),
+            tx_id_pda: tx_id_pda(&message.envelope),
+            bridge_peer: bridge_peer(SOURCE_CHAIN),
+            chain_config: chain_config(message.envelope.source_chain_id),
+            via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+            chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            project_registry: None,
+            relayer_pool: None,
+            payer: None,
+            mint: *mint,
+            escrow: escrow(mint),
+            recipient: *recipient,
+            recipient_token_account: get_associated_token_address(recipient, mint),
+            relayer: env.authority.pubkey(),
+            instructions: sysvar::instructions::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        instruction::UnlockNft {
+            envelope: message.envelope.clone(),
+            signatures: message.signatures.clone(),
+        },
+    )
+}
+
+fn wrapped_nft_mint() -> Pubkey {
+    let collection_hash = keccak::hash(&FOREIGN_COLLECTION).to_bytes();
+    pda(&[WRAPPED_NFT_MINT_SEED, &SOURCE_CHAIN.to_le_bytes(), &collection_hash, &FOREIGN_TOKEN_ID])
+}
+
+fn mint_wrapped_nft(env: &Svm, message: &Attested, recipient: &Pubkey) -> Instruction {
+    let mint = wrapped_nft_mint();
+    build(
+        accounts::MintWrappedNft {
+            gateway: gateway(),
+            tx_id_pda: tx_id_pda(&message.envelope),
+            bridge_peer: bridge_peer(SOURCE_CHAIN),
+            chain_config: chain_config(message.envelope.source_chain_id),
+            via_registry: registry(SignerRegistryType::VIA, GATEWAY_CHAIN),
+            chain_registry: registry(SignerRegistryType::Chain, SOURCE_CHAIN),
+            project_registry: None,
+            relayer_pool: None,
+            payer: None,
+            mint,
+            wrapped_nft: pda(&[WRAPPED_NFT_SEED, mint.as_ref()]),
+            metadata: metadata_pda(&mint),
+            recipient: *recipient,
+            recipient_token_account: get_associated_token_address(recipient, &mint),
+            relayer: env.authority.pubkey(),
+            instructions: sysvar::instructions::ID,
+            token_program: spl_token::ID,
+            associated_token_program: associated_token::ID,
+            token_metadata_program: metadata::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        instruction::MintWrappedNft {
+            envelope: message.envelope.clone(),
+            signatures: message.signatures.clone(),
+            collection_hash: keccak::hash(&FOREIGN_COLLECTION).to_bytes(),
+            token_id: FOREIGN_TOKEN_ID,
+        },
+    )
+}
+
+fn burn_wrapped_nft(holder: &Pubkey, tx_id: u128) -> Instruction {
+    let mint = wrapped_nft_mint();
+    build(
+        accounts::BurnWrappedNft {
+            gateway: gateway(),
+            wrapped_nft: pda(&[WRAPPED_NFT_SEED, mint.as_ref()]),
+            bridge_peer: bridge_peer(SOURCE_CHAIN),
+            chain_config: None,
+            mint,
+            holder_token_account: get_associated_token_address(holder, &mint),
+            holder: *holder,
+            token_program: spl_token::ID,
+        },
+        instruction::BurnWrappedNft {
+            tx_id,
+            recipient: vec![0xcd; 20],
+            confirmations: 1,
+        },
+    )
+}
+
+fn supply(env: &Svm, mint: &Pubkey) -> u64 {
+    let account = env.svm.get_account(mint).expect("mint exists");
+    spl_token::state::Mint::unpack(&account.data).unwrap().supply
+}
+
+#[test]
+fn native_nfts_lock_into_escrow_and_unlock_to_the_beneficiary() {
+    let mut env = Svm::new();
+    enable_token_metadata(&mut env);
+    let owner = env.authority.pubkey();
+    let collection = env.create_mint();
+    env.send(&[env.set_bridge_peer(), register_nft_collection(&env, &collection)]).unwrap();
+    
+    // Only verified members of the registered collection leave Solana
+    let unverified = native_nft(&mut env, &collection, false);
+    let result = env.send(&[lock_nft(&env, &collection, &unverified, 1)]);
+    assert_eq!(error_code(result), GatewayError::NftCollectionNotVerified.into());
+    assert_eq!(env.token_balance(&get_associated_token_address(&owner, &unverified)), 1);
+    
+    let nft = native_nft(&mut env, &collection, true);
+    let owner_account = get_associated_token_address(&owner, &nft);
+    env.send(&[lock_nft(&env, &collection, &nft, 1)]).unwrap();
+    assert_eq!(env.token_balance(&owner_account), 0);
+    assert_eq!(env.token_balance(&escrow(&nft)), 1);
+    
+    // Coming home, it is released only to the payload's beneficiary
+    let beneficiary = Pubkey::new_unique();
+    let message = env.attest(nft_transfer(2, collection.as_ref(), nft.to_bytes(), &beneficiary), ALL_LAYERS);
+    env.tx1(&message).unwrap();
+    let misdirected = unlock_nft(&env, &message, &nft, &owner);
+    assert_eq!(error_code(env.tx2_with(&message, misdirected)), GatewayError::InvalidTokenRecipient.into());
+    env.tx2_with(&message, unlock_nft(&env, &message, &nft, &beneficiary)).unwrap();
+    assert_eq!(env.token_balance(&escrow(&nft)), 0);
+    assert_eq!(env.token_balance(&get_associated_token_address(&beneficiary, &nft)), 1);
+}
+
+#[test]
+fn foreign_nfts_mint_one_wrapped_token_at_a_time() {
+    let mut env = Svm::new();
+    enable_token_metadata(&mut env);
+    env.send(&[env.set_bridge_peer()]).unwrap();
+    let holder = Keypair::new();
+    env.svm.airdrop(&holder.pubkey(), 1_000_000_000).unwrap();
+    let mint = wrapped_nft_mint();
+    let holder_account = get_associated_token_address(&holder.pubkey(), &mint);
+    
+    // First arrival creates the mint and its Metaplex metadata
+    let first = env.attest(nft_transfer(1, &FOREIGN_COLLECTION, FOREIGN_TOKEN_ID, &holder.pubkey()), ALL_LAYERS);
+    env.tx1(&first).unwrap();
+    env.tx2_with(&first, mint_wrapped_nft(&env, &first, &holder.pubkey())).unwrap();
+    assert_eq!(env.token_balance(&holder_account), 1);
+    assert_eq!(supply(&env, &mint), 1);
+    assert!(env.exists(&metadata_pda(&mint)));
+    
+    // A second copy cannot arrive while the first is outstanding
+    let second = env.attest(nft_transfer(2, &FOREIGN_COLLECTION, FOREIGN_TOKEN_ID, &holder.pubkey()), ALL_LAYERS);
+    env.tx1(&second).unwrap();
+    let result = env.tx2_with(&second, mint_wrapped_nft(&env, &second, &holder.pubkey()));
+    assert_eq!(error_code(result), GatewayError::NftAlreadyMinted.into());
+    
+    // Burning sends it home, after which it can arrive again
+    env.send_as(&holder, &[burn_wrapped_nft(&holder.pubkey(), 3)]).unwrap();
+    assert_eq!(env.token_balance(&holder_account), 0);
+    assert_eq!(supply(&env, &mint), 0);
+    
+    env.tx2_with(&second, mint_wrapped_nft(&env, &second, &holder.pubkey())).unwrap();
+    assert_eq!(env.token_balance(&holder_account), 1);
+    assert_eq!(supply(&env, &mint), 1);
+}
@@ -0,0 +1,248 @@
+//! send_message rejecting bad outbound envelopes and unconfigured destinations, and charging
+//! lamport and USD fees, against the built program in LiteSVM
+#![cfg(feature = "program-test")]
+
+mod svm;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::{clock::Clock, instruction::Instruction, system_program};
+use anchor_lang::ToAccountMetas;
+use message_gateway_v4::constants::{
+    MAX_DA_POINTER_SIZE, MAX_OFF_CHAIN_DATA_SIZE, MAX_ON_CHAIN_DATA_SIZE, MAX_RECIPIENT_SIZE,
+};
+use message_gateway_v4::errors::GatewayError;
+use message_gateway_v4::state::MessageEnvelope;
+use message_gateway_v4::utils::pyth::PYTH_RECEIVER_PROGRAM_ID;
+use message_gateway_v4::{accounts, instruction};
+use solana_sdk::account::Account;
+use solana_sdk::signature::{Keypair, Signer};
+use svm::{build, chain_config, error_code, fee_config, fee_vault, gateway, Svm, GATEWAY_CHAIN, SOURCE_CHAIN};
+
+fn set_destination_config(
+    env: &Svm,
+    enabled: bool,
+    min_confirmations: u16,
+    max_confirmations: u16,
+    max_payload_size: u32,
+) -> Instruction {
+    build(
+        accounts::SetDestinationConfig {
+            chain_config: chain_config(SOURCE_CHAIN),
+            gateway: gateway(),
+            authority: env.authority.pubkey(),
+        },
+        instruction::SetDestinationConfig {
+            chain_id: SOURCE_CHAIN,
+            enabled,
+            min_confirmations,
+            max_confirmations,
+            max_payload_size,
+        },
+    )
+}
+
+fn set_usd_send_fee(env: &Svm, price_feed_id: [u8; 32], send_fee_usd: u64) -> [Instruction; 2] {
+    let authority = env.authority.pubkey();
+    [
+        build(
+            accounts::SetPriceFeed { gateway: gateway(), authority },
+            instruction::SetPriceFeed { price_feed_id, max_price_age: 60 },
+        ),
+        build(
+            accounts::SetSendFee { chain_config: chain_config(SOURCE_CHAIN), gateway: gateway(), authority },
+            instruction::SetSendFee { chain_id: SOURCE_CHAIN, send_fee_usd },
+        ),
+    ]
+}
+
+/// Fully verified PriceUpdateV2 account publishing `price * 10^exponent` USD for `feed_id` now
+fn price_update(env: &mut Svm, feed_id: [u8; 32], price: i64, exponent: i32) -> Pubkey {
+    let publish_time = env.svm.get_sysvar::<Clock>().unix_timestamp;
+    let mut data = vec![34, 241, 35, 99, 157, 126, 244, 205];
+    data.extend_from_slice(&[0; 32]);
+    data.push(1);
+    data.extend_from_slice(&feed_id);
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&0u64.to_le_bytes());
+    data.extend_from_slice(&exponent.to_le_bytes());
+    data.extend_from_slice(&publish_time.to_le_bytes());
+    
+    let address = Pubkey::new_unique();
+    let account = Account {
+        lamports: env.svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: PYTH_RECEIVER_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    env.svm.set_account(address, account).unwrap();
+    address
+}
+
+fn withdraw_fees(authority: &Pubkey, recipient: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::WithdrawFees {
+            fee_vault: fee_vault(),
+            gateway: gateway(),
+            authority: *authority,
+            recipient: *recipient,
+            system_program: system_program::ID,
+        },
+        instruction::WithdrawFees { amount },
+    )
+}
+
+#[test]
+fn rejects_each_malformed_envelope() {
+    let mut env = Svm::new();
+    let sender = env.authority.pubkey();
+    let cases: [(fn(&mut MessageEnvelope), GatewayError); 11] = [
+        (|e| e.recipient.clear(), GatewayError::EmptyRecipient),
+        (|e| e.on_chain_data.clear(), GatewayError::EmptyChainData),
+        (|e| e.dest_chain_id = 0, GatewayError::InvalidChainId),
+        (|e| e.source_chain_id = 7, GatewayError::InvalidSourceChain),
+        (|e| e.dest_chain_id = GATEWAY_CHAIN, GatewayError::LoopbackDisabled),
+        (|e| e.sender = vec![0xab; 20], GatewayError::SenderMismatch),
+        (|e| e.version = MessageEnvelope::CURRENT_VERSION + 1, GatewayError::UnsupportedEnvelopeVersion),
+        (|e| e.recipient = vec![1; MAX_RECIPIENT_SIZE + 1], GatewayError::RecipientTooLong),
+        (|e| e.on_chain_data = vec![1; MAX_ON_CHAIN_DATA_SIZE + 1], GatewayError::OnChainDataTooLarge),
+        (|e| e.off_chain_data = vec![1; MAX_OFF_CHAIN_DATA_SIZE + 1], GatewayError::OffChainDataTooLarge),
+        (|e| e.da_pointer = vec![1; MAX_DA_POINTER_SIZE + 1], GatewayError::DaPointerTooLong),
+    ];
+    
+    for (tx_id, (mutate, expected)) in cases.into_iter().enumerate() {
+        let mut envelope = env.outbound(tx_id as u128);
+        mutate(&mut envelope);
+        let result = env.send(&[env.send_message(&sender, envelope)]);
+        assert_eq!(error_code(result), expected.into(), "case {tx_id}");
+    }
+    env.send(&[env.send_message(&sender, env.outbound(100))]).unwrap();
+}
+
+#[test]
+fn paused_gateway_sends_nothing() {
+    let mut env = Svm::new();
+    let sender = env.authority.pubkey();
+    env.send(&[env.set_system_enabled(false)]).unwrap();
+    
+    let result = env.send(&[env.send_message(&sender, env.outbound(1))]);
+    assert_eq!(error_code(result), GatewayError::SystemDisabled.into());
+}
+
+#[test]
+fn destinations_must_be_configured_enabled_and_within_limits() {
+    let mut env = Svm::new();
+    let sender = env.authority.pubkey();
+    let mut unconfigured = env.outbound(1);
+    unconfigured.dest_chain_id = 7;
+    let result = env.send(&[env.send_message(&sender, unconfigured)]);
+    assert_eq!(error_code(result), GatewayError::UnsupportedChain.into());
+    
+    for (min, max, payload) in [(2, 1, 1024), (0, 10, 1025)] {
+        let result = env.send(&[set_destination_config(&env, true, min, max, payload)]);
+        assert_eq!(error_code(result), GatewayError::InvalidDestinationConfig.into());
+    }
+    
+    // The harness requests one confirmation and sends 7 bytes of on-chain data
+    let cases = [
+        ((false, 0, 10, 1024), GatewayError::DestinationChainDisabled),
+        ((true, 2, 10, 1024), GatewayError::ConfirmationsOutOfRange),
+        ((true, 0, 0, 1024), GatewayError::ConfirmationsOutOfRange),
+        ((true, 0, 10, 6), GatewayError::PayloadTooLargeForDestination),
+    ];
+    for (tx_id, ((enabled, min, max, payload), expected)) in cases.into_iter().enumerate() {
+        env.send(&[set_destination_config(&env, enabled, min, max, payload)]).unwrap();
+        let result = env.send(&[env.send_message(&sender, env.outbound(tx_id as u128))]);
+        assert_eq!(error_code(result), expected.into(), "case {tx_id}");
+    }
+    
+    env.send(&[set_destination_config(&env, true, 1, 1, 7)]).unwrap();
+    env.send(&[env.send_message(&sender, env.outbound(10))]).unwrap();
+}
+
+#[test]
+fn lamport_fees_are_paid_into_the_fee_vault_and_withdrawn_by_the_admin() {
+    let mut env = Svm::new();
+    let sender = env.authority.pubkey();
+    env.send(&[env.set_fee_config(5_000, 100)]).unwrap();
+    let vault_before = env.svm.get_balance(&fee_vault()).unwrap();
+    
+    // Without the fee accounts the configured fee cannot be skipped
+    let result = env.send(&[env.send_message(&sender, env.outbound(1))]);
+    assert_eq!(error_code(result), GatewayError::MissingFeeAccounts.into());
+    
+    let envelope = env.outbound(1);
+    let fee = 5_000 + 100 * envelope.on_chain_data.len() as u64;
+    let mut send = env.send_message(&sender, envelope);
+    let fee_accounts = accounts::SendMessage {
+        gateway: gateway(),
+        sender,
+        chain_config: Some(chain_config(SOURCE_CHAIN)),
+        fee_config: fee_config(SOURCE_CHAIN),
+        channel: None,
+        price_update: None,
+        fee_payer: Some(sender),
+        sol_vault: None,
+        fee_vault: Some(fee_vault()),
+        system_program: Some(system_program::ID),
+    };
+    send.accounts = fee_accounts.to_account_metas(None);
+    env.send(&[send]).unwrap();
+    assert_eq!(env.svm.get_balance(&fee_vault()).unwrap(), vault_before + fee);
+    
+    // Only the authority withdraws, and never below the vault's rent-exempt minimum
+    let intruder = Keypair::new();
+    env.svm.airdrop(&intruder.pubkey(), 1_000_000_000).unwrap();
+    let result = env.send_as(&intruder, &[withdraw_fees(&intruder.pubkey(), &intruder.pubkey(), fee)]);
+    assert_eq!(error_code(result), GatewayError::UnauthorizedAuthority.into());
+    let result = env.send(&[withdraw_fees(&sender, &intruder.pubkey(), fee + 1)]);
+    assert_eq!(error_code(result), GatewayError::InsufficientFeeVaultBalance.into());
+    
+    let recipient_before = env.svm.get_balance(&intruder.pubkey()).unwrap();
+    env.send(&[withdraw_fees(&sender, &intruder.pubkey(), fee)]).unwrap();
+    assert_eq!(env.svm.get_balance(&intruder.pubkey()).unwrap(), recipient_before + fee);
+    assert_eq!(env.svm.get_balance(&fee_vault()).unwrap(), vault_before);
+    
+    // Zero fees make the route free again
+    env.send(&[env.set_fee_config(0, 0)]).unwrap();
+    env.send(&[env.send_message(&sender, env.outbound(2))]).unwrap();
+}
+
+#[test]
+fn usd_send_fees_are_paid_into_the_fee_vault() {
+    let mut env = Svm::new();
+    let sender = env.authority.pubkey();
+    let feed_id = [0x50; 32];
+    
+    // $2 at $100 per SOL
+    env.send(&set_usd_send_fee(&env, feed_id, 2_000_000)).unwrap();
+    let price = price_update(&mut env, feed_id, 100_000_000, -6);
+    let fee = 20_000_000;
+    let send = |env: &Svm, tx_id: u128, fee_vault: Option<Pubkey>| {
+        let mut send = env.send_message(&sender, env.outbound(tx_id));
+        let fee_accounts = accounts::SendMessage {
+            gateway: gateway(),
+            sender,
+            chain_config: Some(chain_config(SOURCE_CHAIN)),
+            fee_config: fee_config(SOURCE_CHAIN),
+            channel: None,
+            price_update: Some(price),
+            fee_payer: Some(sender),
+            sol_vault: None,
+            fee_vault,
+            system_program: Some(system_program::ID),
+        };
+        send.accounts = fee_accounts.to_account_metas(None);
+        send
+    };
+    
+    // The fee has nowhere to go without the vault
+    let result = env.send(&[send(&env, 1, None)]);
+    assert_eq!(error_code(result), GatewayError::MissingFeeAccounts.into());
+    
+    let vault_before = env.svm.get_balance(&fee_vault()).unwrap();
+    let gateway_before = env.svm.get_balance(&gateway()).unwrap();
+    env.send(&[send(&env, 1, Some(fee_vault()))]).unwrap();
+    assert_eq!(env.svm.get_balance(&fee_vault()).unwrap(), vault_before + fee);
+    assert_eq!(env.svm.get_balance(&gateway()).unwrap(), gateway_before);
+}
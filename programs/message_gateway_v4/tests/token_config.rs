@@ -0,0 +1,164 @@
+//! Per-token bridge settings: classification, pause, bounds, fees and decimal scaling
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::state::{OutboundAmounts, TokenConfig, TokenKind, TokenSettings};
+
+fn config(kind: TokenKind, settings: TokenSettings) -> TokenConfig {
+    TokenConfig {
+        mint: Pubkey::default(),
+        kind,
+        issuer_program: Pubkey::default(),
+        governance: Pubkey::default(),
+        settings,
+        escrowed_amount: 0,
+        fees_accrued: 0,
+        bump: 0,
+    }
+}
+
+#[test]
+fn rejects_wrong_bridge_path() {
+    let token = config(TokenKind::Wrapped, TokenSettings::default());
+    assert!(token.check_path(TokenKind::Wrapped).is_ok());
+    assert!(token.check_path(TokenKind::Native).is_err());
+    assert!(token.check_outbound(TokenKind::Native, 1).is_err());
+    
+    // Issuer-routed mints never take the escrow path
+    let issuer = config(TokenKind::IssuerBurnMint, TokenSettings::default());
+    assert!(issuer.check_outbound(TokenKind::IssuerBurnMint, 1).is_ok());
+    assert!(issuer.check_path(TokenKind::Native).is_err());
+    
+    // Project custody mints leave the built-in escrow entirely
+    let custody = config(TokenKind::ProjectCustody, TokenSettings::default());
+    assert!(custody.check_path(TokenKind::ProjectCustody).is_ok());
+    assert!(custody.check_outbound(TokenKind::Native, 1).is_err());
+}
+
+#[test]
+fn paused_blocks_both_directions() {
+    let settings = TokenSettings { paused: true, ..TokenSettings::default() };
+    let token = config(TokenKind::Native, settings);
+    assert!(token.check_path(TokenKind::Native).is_err());
+    assert!(token.check_outbound(TokenKind::Native, 1).is_err());
+}
+
+#[test]
+fn enforces_outbound_bounds() {
+    let settings = TokenSettings { min_transfer: 10, max_transfer: 100, ..TokenSettings::default() };
+    let token = config(TokenKind::Native, settings);
+    assert!(token.check_outbound(TokenKind::Native, 9).is_err());
+    assert!(token.check_outbound(TokenKind::Native, 10).is_ok());
+    assert!(token.check_outbound(TokenKind::Native, 100).is_ok());
+    assert!(token.check_outbound(TokenKind::Native, 101).is_err());
+    
+    let unbounded = config(TokenKind::Native, TokenSettings::default());
+    assert!(unbounded.check_outbound(TokenKind::Native, u64::MAX).is_ok());
+    assert!(unbounded.check_outbound(TokenKind::Native, 0).is_err());
+}
+
+#[test]
+fn deposit_withholds_fee() {
+    let settings = TokenSettings { fee_bps: 30, ..TokenSettings::default() };
+    let mut token = config(TokenKind::Native, settings);
+    let amounts = token.outbound_amounts(10_000, 0).unwrap();
+    assert_eq!(amounts, OutboundAmounts { bridged: 9_970, fee: 30, wire: 9_970 });
+    
+    token.record_deposit(&amounts).unwrap();
+    assert_eq!(token.escrowed_amount, 9_970);
+    assert_eq!(token.fees_accrued, 30);
+    
+    // Releases draw on the liability only, never on accrued fees
+    assert!(token.record_release(9_971).is_err());
+    assert!(token.record_release(9_970).is_ok());
+}
+
+#[test]
+fn validates_settings() {
+    assert!(TokenSettings { fee_bps: 10_001, ..TokenSettings::default() }.validate().is_err());
+    assert!(TokenSettings { min_transfer: 5, max_transfer: 4, ..TokenSettings::default() }.validate().is_err());
+    assert!(TokenSettings { min_transfer: 5, max_transfer: 0, ..TokenSettings::default() }.validate().is_ok());
+}
+
+#[test]
+fn governance_shares_management() {
+    let authority = Pubkey::new_unique();
+    let governance = Pubkey::new_unique();
+    let mut token = config(TokenKind::Native, TokenSettings::default());
+    assert!(token.can_manage(&authority, &authority));
+    assert!(!token.can_manage(&Pubkey::default(), &authority));
+    
+    token.governance = governance;
+    assert!(token.can_manage(&governance, &authority));
+    assert!(!token.can_manage(&Pubkey::new_unique(), &authority));
+}
+
+#[test]
+fn scales_down_to_remote_decimals() {
+    // 9-decimal SPL mint bridged to a 6-decimal remote representation
+    let settings = TokenSettings { remote_decimals: 6, ..TokenSettings::default() };
+    let token = config(TokenKind::Native, settings);
+    assert_eq!(token.decimal_scale(9).unwrap(), 1_000);
+    assert_eq!(token.truncate_dust(1_234_567, 9).unwrap(), 1_234_000);
+    assert_eq!(token.to_wire_amount(1_234_000, 9).unwrap(), 1_234);
+    assert!(token.to_wire_amount(1_234_567, 9).is_err());
+    
+    // Round trip is exact once dust is removed
+    assert_eq!(token.from_wire_amount(1_234, 9).unwrap(), 1_234_000);
+}
+
+#[test]
+fn dust_from_fees_is_kept_with_the_fee() {
+    let settings = TokenSettings { fee_bps: 10, remote_decimals: 6, ..TokenSettings::default() };
+    let token = config(TokenKind::Native, settings);
+    let amounts = token.outbound_amounts(1_000_000, 9).unwrap();
+    assert_eq!(amounts, OutboundAmounts { bridged: 999_000, fee: 1_000, wire: 999 });
+    
+    let amounts = token.outbound_amounts(1_234_567, 9).unwrap();
+    assert_eq!(amounts.bridged + amounts.fee, 1_234_567);
+    assert_eq!(amounts.bridged % 1_000, 0);
+    
+    // Nothing representable left to bridge
+    assert!(token.outbound_amounts(999, 9).is_err());
+}
+
+#[test]
+fn wire_uses_smaller_decimals() {
+    // 18-decimal EVM asset wrapped as a 9-decimal mint: payload amounts are local
+    let settings = TokenSettings { remote_decimals: 18, ..TokenSettings::default() };
+    let token = config(TokenKind::Wrapped, settings);
+    assert_eq!(token.decimal_scale(9).unwrap(), 1);
+    assert_eq!(token.from_wire_amount(u64::MAX, 9).unwrap(), u64::MAX);
+    
+    let settings = TokenSettings { remote_decimals: 0, ..TokenSettings::default() };
+    let token = config(TokenKind::Native, settings);
+    assert!(token.from_wire_amount(u64::MAX, 9).is_err());
+    assert!(token.decimal_scale(20).is_err());
+}
+
+#[test]
+fn fee_respects_absolute_bounds() {
+    let settings = TokenSettings { fee_bps: 100, min_fee: 5, max_fee: 50, ..TokenSettings::default() };
+    let token = config(TokenKind::Native, settings);
+    assert_eq!(token.bridge_fee(100), 5);
+    assert_eq!(token.bridge_fee(2_000), 20);
+    assert_eq!(token.bridge_fee(1_000_000), 50);
+    
+    // The minimum never exceeds the transfer itself
+    assert_eq!(token.bridge_fee(3), 3);
+    assert!(token.outbound_amounts(3, 0).is_err());
+    
+    assert!(TokenSettings { min_fee: 6, max_fee: 5, ..TokenSettings::default() }.validate().is_err());
+}
+
+#[test]
+fn fee_is_charged_on_one_side_only() {
+    let settings = TokenSettings { fee_bps: 100, ..TokenSettings::default() };
+    let on_deposit = config(TokenKind::Native, settings);
+    assert_eq!(on_deposit.outbound_fee(1_000), 10);
+    assert_eq!(on_deposit.release_fee(1_000), 0);
+    
+    let on_release = config(TokenKind::Native, TokenSettings { fee_on_release: true, ..settings });
+    assert_eq!(on_release.outbound_fee(1_000), 0);
+    assert_eq!(on_release.release_fee(1_000), 10);
+    assert_eq!(on_release.outbound_amounts(1_000, 0).unwrap().bridged, 1_000);
+}
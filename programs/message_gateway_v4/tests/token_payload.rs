@@ -0,0 +1,67 @@
+//! Versioned token-transfer payload parsing
+
+use message_gateway_v4::payloads::{
+    BatchTransfer, TokenBatchPayload, TokenTransferPayload, VersionedTokenTransfer,
+};
+
+fn payload() -> TokenTransferPayload {
+    TokenTransferPayload {
+        token: [7u8; 32],
+        amount: 1_000,
+        sender: vec![1u8; 20],
+        recipient: vec![2u8; 32],
+    }
+}
+
+#[test]
+fn current_version_round_trips() {
+    let encoded = payload().encode().unwrap();
+    assert_eq!(encoded[0], TokenTransferPayload::VERSION);
+    assert_eq!(
+        TokenTransferPayload::parse(&encoded).unwrap(),
+        VersionedTokenTransfer::Supported(payload())
+    );
+    assert_eq!(TokenTransferPayload::decode(&encoded).unwrap(), payload());
+}
+
+#[test]
+fn unknown_versions_are_reported_not_failed() {
+    let mut encoded = payload().encode().unwrap();
+    encoded[0] = 2;
+    encoded.extend_from_slice(&[0xAA; 16]);
+    
+    assert_eq!(
+        TokenTransferPayload::parse(&encoded).unwrap(),
+        VersionedTokenTransfer::Unsupported { version: 2 }
+    );
+    assert!(TokenTransferPayload::decode(&encoded).is_err());
+}
+
+#[test]
+fn malformed_known_version_is_an_error() {
+    let mut encoded = payload().encode().unwrap();
+    encoded.push(0);
+    assert!(TokenTransferPayload::parse(&encoded).is_err());
+    assert!(TokenTransferPayload::parse(&[]).is_err());
+}
+
+#[test]
+fn batch_round_trips_and_bounds_size() {
+    let transfer = |amount| BatchTransfer { recipient: vec![2u8; 32], amount };
+    let batch = TokenBatchPayload {
+        token: [7u8; 32],
+        sender: vec![1u8; 20],
+        transfers: vec![transfer(10), transfer(20)],
+    };
+    let encoded = batch.encode().unwrap();
+    assert_eq!(encoded[0], TokenBatchPayload::VERSION);
+    assert_eq!(TokenBatchPayload::decode(&encoded).unwrap(), batch);
+    assert_eq!(batch.total_amount().unwrap(), 30);
+    
+    let empty = TokenBatchPayload { transfers: Vec::new(), ..batch.clone() };
+    assert!(empty.encode().is_err());
+    let oversized = TokenBatchPayload { transfers: vec![transfer(1); 17], ..batch.clone() };
+    assert!(oversized.encode().is_err());
+    let overflow = TokenBatchPayload { transfers: vec![transfer(u64::MAX), transfer(1)], ..batch };
+    assert!(overflow.encode().is_err());
+}
@@ -0,0 +1,57 @@
+//! Expiry and rent refund of TxId PDAs closed by gc_batch
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorSerialize;
+use message_gateway_v4::constants::{MAX_SIGNATURES_PER_MESSAGE, TX_ID_PDA_TTL_SECONDS};
+use message_gateway_v4::state::{RentRefundKind, TxIdPDA};
+
+const NOW: i64 = 1_700_000_000;
+
+fn tx_id_pda() -> TxIdPDA {
+    TxIdPDA {
+        message_hash: [0u8; 32],
+        envelope_digest: [0u8; 32],
+        ed25519_offsets: [0u8; MAX_SIGNATURES_PER_MESSAGE],
+        rent_refund: Pubkey::new_unique(),
+        rent_refund_kind: RentRefundKind::Relayer,
+        expires_at: TxIdPDA::expiry(NOW, 0),
+        bump: 0,
+    }
+}
+
+#[test]
+fn expires_after_ttl_without_deadline() {
+    assert_eq!(TxIdPDA::expiry(NOW, 0), NOW + TX_ID_PDA_TTL_SECONDS);
+}
+
+#[test]
+fn earlier_deadline_shortens_expiry() {
+    assert_eq!(TxIdPDA::expiry(NOW, NOW + 60), NOW + 60);
+    assert_eq!(
+        TxIdPDA::expiry(NOW, NOW + 2 * TX_ID_PDA_TTL_SECONDS),
+        NOW + TX_ID_PDA_TTL_SECONDS
+    );
+}
+
+#[test]
+fn is_expired_from_expiry_onwards() {
+    let tx_id_pda = tx_id_pda();
+    assert!(!tx_id_pda.is_expired(NOW));
+    assert!(!tx_id_pda.is_expired(tx_id_pda.expires_at - 1));
+    assert!(tx_id_pda.is_expired(tx_id_pda.expires_at));
+}
+
+#[test]
+fn records_a_single_rent_refund_account() {
+    let relayer_paid = tx_id_pda();
+    assert_eq!((relayer_paid.rent_pool(), relayer_paid.rent_payer()), (None, None));
+    
+    let pool = Pubkey::new_unique();
+    let pooled = TxIdPDA { rent_refund: pool, rent_refund_kind: RentRefundKind::Pool, ..tx_id_pda() };
+    assert_eq!((pooled.rent_pool(), pooled.rent_payer()), (Some(pool), None));
+    
+    let payer = Pubkey::new_unique();
+    let funded = TxIdPDA { rent_refund: payer, rent_refund_kind: RentRefundKind::Payer, ..tx_id_pda() };
+    assert_eq!((funded.rent_pool(), funded.rent_payer()), (None, Some(payer)));
+    assert_eq!(funded.try_to_vec().unwrap().len(), TxIdPDA::SIZE);
+}
@@ -0,0 +1,45 @@
+//! Off-chain TX1/TX2 assembly (run with `--features txbuilder`)
+#![cfg(feature = "txbuilder")]
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::ed25519_program;
+use message_gateway_v4::state::{MessageSignature, SignatureScheme};
+use message_gateway_v4::txbuilder::{ed25519_verify_instruction, ed25519_verify_instructions, ComputeBudget};
+
+#[test]
+fn ed25519_instruction_matches_gateway_layout() {
+    let signer = Pubkey::new_unique();
+    let ix = ed25519_verify_instruction(&signer, &[9u8; 64], &[7u8; 32]);
+    assert_eq!(ix.program_id, ed25519_program::ID);
+    assert_eq!(ix.data.len(), 144);
+    assert_eq!(&ix.data[..2], &[1, 0]);
+    assert_eq!(&ix.data[16..80], &[9u8; 64]);
+    assert_eq!(&ix.data[80..112], signer.as_ref());
+    assert_eq!(&ix.data[112..], &[7u8; 32]);
+    
+    // Offsets point into the precompile's own data
+    assert_eq!(u16::from_le_bytes([ix.data[2], ix.data[3]]), 16);
+    assert_eq!(u16::from_le_bytes([ix.data[6], ix.data[7]]), 80);
+    assert_eq!(u16::from_le_bytes([ix.data[10], ix.data[11]]), 112);
+    assert_eq!(u16::from_le_bytes([ix.data[12], ix.data[13]]), 32);
+}
+
+#[test]
+fn only_ed25519_attestations_need_precompiles() {
+    let attestation = |scheme| MessageSignature { signature: [1u8; 64], signer: Pubkey::new_unique(), scheme };
+    let signatures = [
+        attestation(SignatureScheme::Ed25519),
+        attestation(SignatureScheme::Secp256k1 { recovery_id: 0 }),
+    ];
+    assert_eq!(ed25519_verify_instructions(&signatures, &[7u8; 32]).len(), 1);
+}
+
+#[test]
+fn compute_budget_skips_unset_values() {
+    assert!(ComputeBudget::default().instructions().is_empty());
+    
+    let budget = ComputeBudget { unit_limit: 400_000, unit_price_micro_lamports: 0 };
+    let instructions = budget.instructions();
+    assert_eq!(instructions.len(), 1);
+    assert_eq!(instructions[0].data, [2, 0x80, 0x1a, 0x06, 0x00]);
+}
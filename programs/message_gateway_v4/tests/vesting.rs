@@ -0,0 +1,76 @@
+//! Vesting grants: payload validation and unlock schedule
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_v4::payloads::TokenVestingPayload;
+use message_gateway_v4::state::{TokenKind, VestingSchedule};
+
+fn schedule(amount: u64, cliff: u64, duration: u64, period: u64) -> VestingSchedule {
+    VestingSchedule {
+        source_chain_id: 1,
+        tx_id: 1,
+        mint: Pubkey::default(),
+        kind: TokenKind::Native,
+        recipient: Pubkey::default(),
+        amount,
+        claimed: 0,
+        start_time: 1_000,
+        cliff_seconds: cliff,
+        duration_seconds: duration,
+        period_seconds: period,
+        relayer: Pubkey::default(),
+        bump: 0,
+    }
+}
+
+#[test]
+fn nothing_unlocks_before_the_cliff() {
+    let grant = schedule(1_200, 300, 1_200, 100);
+    assert_eq!(grant.vested_amount(0), 0);
+    assert_eq!(grant.vested_amount(1_000), 0);
+    assert_eq!(grant.vested_amount(1_299), 0);
+    assert_eq!(grant.vested_amount(1_300), 300);
+}
+
+#[test]
+fn unlocks_in_whole_periods() {
+    let grant = schedule(1_200, 0, 1_200, 100);
+    assert_eq!(grant.vested_amount(1_099), 0);
+    assert_eq!(grant.vested_amount(1_100), 100);
+    assert_eq!(grant.vested_amount(1_150), 100);
+    assert_eq!(grant.vested_amount(2_199), 1_100);
+    assert_eq!(grant.vested_amount(2_200), 1_200);
+    assert_eq!(grant.vested_amount(i64::MAX), 1_200);
+}
+
+#[test]
+fn claims_only_what_is_unclaimed() {
+    let mut grant = schedule(u64::MAX, 0, 4, 1);
+    assert!(grant.claim(1_000).is_err());
+    assert_eq!(grant.claim(1_002).unwrap(), u64::MAX / 2);
+    assert!(grant.claim(1_002).is_err());
+    assert!(!grant.is_complete());
+    
+    assert_eq!(grant.claim(1_010).unwrap(), u64::MAX - u64::MAX / 2);
+    assert!(grant.is_complete());
+}
+
+#[test]
+fn payload_rejects_malformed_schedules() {
+    let payload = TokenVestingPayload {
+        token: [7u8; 32],
+        amount: 1_000,
+        sender: vec![1u8; 20],
+        recipient: vec![2u8; 32],
+        start_time: 1_000,
+        cliff_seconds: 100,
+        duration_seconds: 1_000,
+        period_seconds: 10,
+    };
+    let encoded = payload.encode().unwrap();
+    assert_eq!(TokenVestingPayload::decode(&encoded).unwrap(), payload);
+    
+    assert!(TokenVestingPayload { duration_seconds: 0, ..payload.clone() }.validate().is_err());
+    assert!(TokenVestingPayload { period_seconds: 0, ..payload.clone() }.validate().is_err());
+    assert!(TokenVestingPayload { period_seconds: 1_001, ..payload.clone() }.validate().is_err());
+    assert!(TokenVestingPayload { cliff_seconds: 1_001, ..payload }.validate().is_err());
+}
\ No newline at end of file
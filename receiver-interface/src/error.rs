@@ -0,0 +1,42 @@
+use std::fmt;
+
+use solana_program::program_error::ProgramError;
+
+/// Delivery rejected by a recipient before it trusts the message
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReceiverError {
+    /// Instruction data is not an `on_message_received` call
+    InvalidDiscriminator,
+    /// `ViaMessage` does not decode, or bytes follow it
+    InvalidMessage,
+    /// No delivery authority account, or it did not sign
+    MissingDeliveryAuthority,
+    /// The signing account is not the recipient's delivery authority under the gateway
+    InvalidDeliveryAuthority,
+}
+
+impl fmt::Display for ReceiverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ReceiverError::InvalidDiscriminator => "not an on_message_received instruction",
+            ReceiverError::InvalidMessage => "malformed message",
+            ReceiverError::MissingDeliveryAuthority => "delivery authority missing or not a signer",
+            ReceiverError::InvalidDeliveryAuthority => "delivery authority does not belong to the gateway",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for ReceiverError {}
+
+impl From<ReceiverError> for ProgramError {
+    fn from(error: ReceiverError) -> Self {
+        match error {
+            ReceiverError::InvalidDiscriminator | ReceiverError::InvalidMessage => {
+                ProgramError::InvalidInstructionData
+            }
+            ReceiverError::MissingDeliveryAuthority => ProgramError::MissingRequiredSignature,
+            ReceiverError::InvalidDeliveryAuthority => ProgramError::IllegalOwner,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+//! Via Labs V4 message receiver interface
+//!
+//! The gateway's `deliver_message` calls the program named in a program-delivery envelope
+//! (`PAYLOAD_KIND_PROGRAM_DELIVERY`) with a single instruction, `on_message_received`:
+//! - data: `ON_MESSAGE_RECEIVED_DISCRIMINATOR || borsh(ViaMessage)`
+//! - account `DELIVERY_AUTHORITY_INDEX`: the recipient's delivery authority, a gateway PDA
+//!   (`[DELIVERY_AUTHORITY_SEED, recipient_program]`), read-only and signing
+//! - the following accounts: the ones the relayer supplied, passed through unchanged
+//!
+//! Anchor recipients declare `on_message_received(ctx, message: ViaMessage)` with the
+//! delivery authority as their first account and check it with `verify_delivery_authority`;
+//! native recipients decode and check everything with `unpack_delivery`
+//! The gateway builds its CPI from this crate, so both sides share one ABI
+
+pub mod error;
+
+pub use error::ReceiverError;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{account_info::AccountInfo, pubkey, pubkey::Pubkey};
+
+/// Gateway program on the public clusters; localnet deployments pass their own id
+pub const GATEWAY_PROGRAM_ID: Pubkey = pubkey!("2RgmXiQc6d5nys8vCMLKAovNKXneQK9SLkgRtnThex7i");
+
+/// Anchor-style discriminator of `on_message_received`: sha256("global:on_message_received")[..8]
+pub const ON_MESSAGE_RECEIVED_DISCRIMINATOR: [u8; 8] = [19, 136, 4, 5, 129, 186, 177, 183];
+
+/// Seed of a recipient's delivery authority PDA under the gateway program
+pub const DELIVERY_AUTHORITY_SEED: &[u8] = b"delivery_authority";
+
+/// Position of the delivery authority in the `on_message_received` accounts
+pub const DELIVERY_AUTHORITY_INDEX: usize = 0;
+
+/// Verified inbound message delivered to the recipient program
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ViaMessage {
+    /// Source-chain transaction id; unique per source chain
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    
+    /// Sending application on the source chain, in its native address encoding
+    pub sender: Vec<u8>,
+    
+    /// Application payload of the envelope
+    pub on_chain_data: Vec<u8>,
+}
+
+impl ViaMessage {
+    /// `on_message_received` instruction data carrying this message
+    pub fn instruction_data(&self) -> Result<Vec<u8>, ReceiverError> {
+        let mut data = ON_MESSAGE_RECEIVED_DISCRIMINATOR.to_vec();
+        self.serialize(&mut data)
+            .map_err(|_| ReceiverError::InvalidMessage)?;
+        Ok(data)
+    }
+    
+    /// Decode `on_message_received` instruction data, rejecting other instructions and
+    /// trailing bytes
+    pub fn unpack(data: &[u8]) -> Result<Self, ReceiverError> {
+        let body = data
+            .strip_prefix(&ON_MESSAGE_RECEIVED_DISCRIMINATOR)
+            .ok_or(ReceiverError::InvalidDiscriminator)?;
+        Self::try_from_slice(body).map_err(|_| ReceiverError::InvalidMessage)
+    }
+}
+
+/// Delivery authority of `recipient_program` under `gateway_program`, with its bump
+pub fn delivery_authority(gateway_program: &Pubkey, recipient_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DELIVERY_AUTHORITY_SEED, recipient_program.as_ref()], gateway_program)
+}
+
+/// Check that `authority` signed and is `recipient_program`'s delivery authority under
+/// `gateway_program`, i.e. that the gateway itself verified and delivered the message
+pub fn verify_delivery_authority(
+    authority: &AccountInfo,
+    gateway_program: &Pubkey,
+    recipient_program: &Pubkey,
+) -> Result<(), ReceiverError> {
+    if !authority.is_signer {
+        return Err(ReceiverError::MissingDeliveryAuthority);
+    }
+    if *authority.key != delivery_authority(gateway_program, recipient_program).0 {
+        return Err(ReceiverError::InvalidDeliveryAuthority);
+    }
+    Ok(())
+}
+
+/// Authenticate and decode an `on_message_received` call to `program_id` from
+/// `gateway_program`; returns the message and the passed-through accounts
+pub fn unpack_delivery<'a, 'info>(
+    program_id: &Pubkey,
+    gateway_program: &Pubkey,
+    accounts: &'a [AccountInfo<'info>],
+    data: &[u8],
+) -> Result<(ViaMessage, &'a [AccountInfo<'info>]), ReceiverError> {
+    let message = ViaMessage::unpack(data)?;
+    let (authority, passed_through) = accounts
+        .split_first()
+        .ok_or(ReceiverError::MissingDeliveryAuthority)?;
+    verify_delivery_authority(authority, gateway_program, program_id)?;
+    Ok((message, passed_through))
+}
\ No newline at end of file
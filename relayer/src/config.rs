@@ -0,0 +1,66 @@
+use std::env;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use solana_sdk::commitment_config::CommitmentConfig;
+
+use crate::Result;
+
+/// Relayer settings, read from `VIA_RELAYER_*` environment variables
+pub struct Config {
+    /// `VIA_RELAYER_RPC_URL` (default local validator)
+    pub rpc_url: String,
+    pub commitment: CommitmentConfig,
+    /// `VIA_RELAYER_KEYPAIR`: fee payer and relayer identity (default Solana CLI keypair)
+    pub keypair_path: PathBuf,
+    /// `VIA_RELAYER_GATEWAY_CHAIN_ID`: chain id the destination gateway was initialized with
+    pub gateway_chain_id: u64,
+    /// `VIA_RELAYER_FEED`: JSON-lines file of attested inbound messages
+    pub attestation_feed: PathBuf,
+    /// `VIA_RELAYER_POLL_MS` (default 2000)
+    pub poll_interval: Duration,
+    /// `VIA_RELAYER_MAX_RETRIES` per transaction (default 5)
+    pub max_retries: u32,
+    /// `VIA_RELAYER_CU_PRICE`: priority fee in micro-lamports per compute unit (default 0)
+    pub compute_unit_price: u64,
+    /// `VIA_RELAYER_POOL_TOP_UP`: lamports deposited whenever the relayer pool cannot cover
+    /// the next TxId PDA; 0 (default) pays rent directly with create_tx_pda
+    pub pool_top_up: u64,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self> {
+        let home = env::var("HOME").unwrap_or_default();
+        Ok(Self {
+            rpc_url: var("RPC_URL").unwrap_or_else(|| "http://127.0.0.1:8899".to_string()),
+            commitment: CommitmentConfig::confirmed(),
+            keypair_path: var("KEYPAIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from(home).join(".config/solana/id.json")),
+            gateway_chain_id: var("GATEWAY_CHAIN_ID")
+                .ok_or("VIA_RELAYER_GATEWAY_CHAIN_ID is required")?
+                .parse()?,
+            attestation_feed: var("FEED")
+                .map(PathBuf::from)
+                .ok_or("VIA_RELAYER_FEED is required")?,
+            poll_interval: Duration::from_millis(parse_or("POLL_MS", 2000)?),
+            max_retries: parse_or("MAX_RETRIES", 5)?,
+            compute_unit_price: parse_or("CU_PRICE", 0)?,
+            pool_top_up: parse_or("POOL_TOP_UP", 0)?,
+        })
+    }
+}
+
+fn var(name: &str) -> Option<String> {
+    env::var(format!("VIA_RELAYER_{name}")).ok()
+}
+
+fn parse_or<T: std::str::FromStr>(name: &str, default: T) -> Result<T>
+where
+    T::Err: std::error::Error + 'static,
+{
+    match var(name) {
+        Some(value) => Ok(value.parse()?),
+        None => Ok(default),
+    }
+}
\ No newline at end of file
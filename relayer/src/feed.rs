@@ -0,0 +1,204 @@
+//! Attested inbound messages and their JSON form
+//!
+//! One message per line: the envelope (byte fields as 0x-hex), its attestations and the
+//! project accounts of its route
+//!
+//! `{"envelope": {..}, "signatures": [{"signer": "<base58>", "signature": "0x..",
+//!   "recovery_id": null}], "project_registry": null, "extra_verifier_registries": []}`
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use message_gateway_client::state::{MessageEnvelope, MessageSignature, SignatureScheme};
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// Where attested messages come from (a validator API, a queue, a file...)
+pub trait AttestationSource {
+    /// Messages attested since the last poll
+    fn poll(&mut self) -> Result<Vec<AttestedMessage>>;
+}
+
+/// Inbound message ready to relay
+pub struct AttestedMessage {
+    pub envelope: MessageEnvelope,
+    pub signatures: Vec<MessageSignature>,
+    pub project_registry: Option<Pubkey>,
+    pub extra_verifier_registries: Vec<Pubkey>,
+}
+
+/// Envelope with its byte fields as 0x-hex
+#[derive(Serialize, Deserialize)]
+pub struct EnvelopeJson {
+    pub version: u8,
+    pub tx_id: u128,
+    pub source_chain_id: u64,
+    pub dest_chain_id: u64,
+    pub sender: String,
+    pub recipient: String,
+    pub on_chain_data: String,
+    pub off_chain_data: String,
+    pub off_chain_data_hash: String,
+    pub message_type: u8,
+    pub deadline: i64,
+    pub sequence: u64,
+    pub protocol_version: u8,
+    pub da_pointer: String,
+}
+
+#[derive(Deserialize)]
+struct SignatureJson {
+    signer: String,
+    signature: String,
+    /// Set for secp256k1 attestations, absent for Ed25519
+    recovery_id: Option<u8>,
+}
+
+#[derive(Deserialize)]
+struct AttestedMessageJson {
+    envelope: EnvelopeJson,
+    signatures: Vec<SignatureJson>,
+    #[serde(default)]
+    project_registry: Option<String>,
+    #[serde(default)]
+    extra_verifier_registries: Vec<String>,
+}
+
+impl From<&MessageEnvelope> for EnvelopeJson {
+    fn from(envelope: &MessageEnvelope) -> Self {
+        Self {
+            version: envelope.version,
+            tx_id: envelope.tx_id,
+            source_chain_id: envelope.source_chain_id,
+            dest_chain_id: envelope.dest_chain_id,
+            sender: to_hex(&envelope.sender),
+            recipient: to_hex(&envelope.recipient),
+            on_chain_data: to_hex(&envelope.on_chain_data),
+            off_chain_data: to_hex(&envelope.off_chain_data),
+            off_chain_data_hash: to_hex(&envelope.off_chain_data_hash),
+            message_type: envelope.message_type,
+            deadline: envelope.deadline,
+            sequence: envelope.sequence,
+            protocol_version: envelope.protocol_version,
+            da_pointer: to_hex(&envelope.da_pointer),
+        }
+    }
+}
+
+impl TryFrom<EnvelopeJson> for MessageEnvelope {
+    type Error = Box<dyn std::error::Error>;
+    
+    fn try_from(json: EnvelopeJson) -> Result<Self> {
+        Ok(Self {
+            version: json.version,
+            tx_id: json.tx_id,
+            source_chain_id: json.source_chain_id,
+            dest_chain_id: json.dest_chain_id,
+            sender: from_hex(&json.sender)?,
+            recipient: from_hex(&json.recipient)?,
+            on_chain_data: from_hex(&json.on_chain_data)?,
+            off_chain_data: from_hex(&json.off_chain_data)?,
+            off_chain_data_hash: fixed(&json.off_chain_data_hash)?,
+            message_type: json.message_type,
+            deadline: json.deadline,
+            sequence: json.sequence,
+            protocol_version: json.protocol_version,
+            da_pointer: from_hex(&json.da_pointer)?,
+        })
+    }
+}
+
+impl AttestedMessage {
+    /// Parse one feed line
+    pub fn from_json(line: &str) -> Result<Self> {
+        let json: AttestedMessageJson = serde_json::from_str(line)?;
+        let signatures = json
+            .signatures
+            .iter()
+            .map(|signature| {
+                Ok(MessageSignature {
+                    signature: fixed(&signature.signature)?,
+                    signer: Pubkey::from_str(&signature.signer)?,
+                    scheme: match signature.recovery_id {
+                        Some(recovery_id) => SignatureScheme::Secp256k1 { recovery_id },
+                        None => SignatureScheme::Ed25519,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        
+        Ok(Self {
+            envelope: json.envelope.try_into()?,
+            signatures,
+            project_registry: json.project_registry.as_deref().map(Pubkey::from_str).transpose()?,
+            extra_verifier_registries: json
+                .extra_verifier_registries
+                .iter()
+                .map(|key| Pubkey::from_str(key))
+                .collect::<std::result::Result<_, _>>()?,
+        })
+    }
+}
+
+/// Attestation feed appended to as a JSON-lines file; each poll reads the lines added since
+pub struct FileFeed {
+    path: PathBuf,
+    offset: u64,
+}
+
+impl FileFeed {
+    pub fn new(path: &Path) -> Self {
+        Self { path: path.to_path_buf(), offset: 0 }
+    }
+}
+
+impl AttestationSource for FileFeed {
+    fn poll(&mut self) -> Result<Vec<AttestedMessage>> {
+        let Ok(mut file) = File::open(&self.path) else {
+            return Ok(Vec::new());
+        };
+        file.seek(SeekFrom::Start(self.offset))?;
+        
+        let mut messages = Vec::new();
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+        // A line without its newline is still being written and is read on the next poll
+        while reader.read_line(&mut line)? > 0 && line.ends_with('\n') {
+            self.offset += line.len() as u64;
+            if !line.trim().is_empty() {
+                match AttestedMessage::from_json(&line) {
+                    Ok(message) => messages.push(message),
+                    Err(error) => eprintln!("skipping malformed feed line: {error}"),
+                }
+            }
+            line.clear();
+        }
+        Ok(messages)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let digits: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!("0x{digits}")
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>> {
+    let digits = s.strip_prefix("0x").unwrap_or(s);
+    if digits.len() % 2 != 0 {
+        return Err(format!("odd-length hex: {s}").into());
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| Ok(u8::from_str_radix(&digits[i..i + 2], 16)?))
+        .collect()
+}
+
+fn fixed<const N: usize>(s: &str) -> Result<[u8; N]> {
+    from_hex(s)?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("expected {N} bytes, got {}", bytes.len()).into())
+}
\ No newline at end of file
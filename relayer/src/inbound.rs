@@ -0,0 +1,204 @@
+//! TX1/TX2 submission for attested inbound messages
+
+use std::thread;
+use std::time::Duration;
+
+use anchor_lang::prelude::{Pubkey, Rent};
+use message_gateway_client::constants::DEFAULT_INSTANCE_ID;
+use message_gateway_client::state::{ChainConfig, FeeConfig, MessageGateway, RelayerPool, TxIdPDA};
+use message_gateway_client::txbuilder::{self, ComputeBudget, InboundRoute};
+use message_gateway_client::utils::cost::{estimate_relay_cost, RelayCostEstimate};
+use message_gateway_client::utils::hash::create_message_hash_for_signing;
+use message_gateway_client::{account, ix, pda};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+
+use crate::config::Config;
+use crate::feed::AttestedMessage;
+use crate::rent::RentLedger;
+use crate::Result;
+
+/// Delay before the first retry; doubled on each further one
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+pub struct InboundRelayer<'a> {
+    client: &'a RpcClient,
+    keypair: &'a Keypair,
+    gateway_chain_id: u64,
+    max_retries: u32,
+    compute_unit_price: u64,
+    pool_top_up: u64,
+    rent: RentLedger,
+}
+
+impl<'a> InboundRelayer<'a> {
+    pub fn new(client: &'a RpcClient, keypair: &'a Keypair, config: &Config) -> Self {
+        Self {
+            client,
+            keypair,
+            gateway_chain_id: config.gateway_chain_id,
+            max_retries: config.max_retries,
+            compute_unit_price: config.compute_unit_price,
+            pool_top_up: config.pool_top_up,
+            rent: RentLedger::default(),
+        }
+    }
+    
+    pub fn rent(&self) -> &RentLedger {
+        &self.rent
+    }
+    
+    /// Relay one message, resuming after whichever step already landed: a TxId PDA that
+    /// exists means TX1 is done, and one that is gone again means TX2 is
+    pub fn relay(&mut self, message: &AttestedMessage) -> Result<()> {
+        let envelope = &message.envelope;
+        let relayer = self.keypair.pubkey();
+        let tx_id_pda = pda::tx_id(envelope.source_chain_id, envelope.tx_id).0;
+        
+        let chain_config: Option<ChainConfig> =
+            account::fetch_optional(self.client, &pda::chain_config(envelope.source_chain_id).0)?;
+        let gateway: MessageGateway = account::fetch(self.client, &pda::gateway(self.gateway_chain_id).0)?;
+        let message_hash = create_message_hash_for_signing(
+            ChainConfig::route_hash_scheme(chain_config.as_ref()),
+            self.gateway_chain_id,
+            envelope,
+        )?;
+        
+        let estimate = estimate_relay_cost(
+            envelope.on_chain_data.len(),
+            message.signatures.len(),
+            &Rent::default(),
+        )?;
+        let budget = |compute_units| ComputeBudget {
+            unit_limit: RelayCostEstimate::compute_unit_limit(compute_units),
+            unit_price_micro_lamports: self.compute_unit_price,
+        };
+        let tx1_budget = budget(estimate.tx1_compute_units);
+        let tx2_budget = budget(estimate.tx2_compute_units);
+        
+        // TX1, unless an earlier attempt already opened the PDA
+        let (rent_pool, rent_payer) = match self.tx_id_pda(&tx_id_pda)? {
+            Some(existing) => (existing.rent_pool(), existing.rent_payer()),
+            None => {
+                let rent = self
+                    .client
+                    .get_minimum_balance_for_rent_exemption(8 + TxIdPDA::SIZE)?;
+                let pooled = self.pool_top_up > 0;
+                if pooled {
+                    self.ensure_pool(rent)?;
+                }
+                
+                self.send_with_retries(
+                    |blockhash| {
+                        let gateway_instruction = if pooled {
+                            ix::create_tx_pda_pooled(&relayer, envelope, &message.signatures)
+                        } else {
+                            txbuilder::create_tx_pda_instruction(&relayer, envelope, &message.signatures)
+                        };
+                        let mut instructions = tx1_budget.instructions();
+                        instructions.extend(txbuilder::ed25519_verify_instructions(&message.signatures, &message_hash));
+                        instructions.push(gateway_instruction);
+                        Ok(txbuilder::compile_message(&relayer, &instructions, &[], blockhash)?)
+                    },
+                    || Ok(self.tx_id_pda(&tx_id_pda)?.is_some()),
+                )?;
+                self.rent.opened(tx_id_pda, rent);
+                (pooled.then(|| pda::relayer_pool(&relayer).0), None)
+            }
+        };
+        
+        // TX2 closes the PDA and refunds its rent to the relayer, the pool or the payer,
+        // and pays the relayer refund when the source chain has one
+        let fee_config: Option<FeeConfig> =
+            account::fetch_optional(self.client, &pda::fee_config(envelope.source_chain_id).0)?;
+        let claim_refund = match fee_config {
+            Some(fee_config) => fee_config.relayer_refund_for(envelope.on_chain_data.len())? > 0,
+            None => false,
+        };
+        let route = InboundRoute {
+            gateway_chain_id: self.gateway_chain_id,
+            instance_id: DEFAULT_INSTANCE_ID,
+            project_registry: message.project_registry,
+            extra_verifier_registries: message.extra_verifier_registries.clone(),
+            message_history_tree: (gateway.message_history_tree != Pubkey::default())
+                .then_some(gateway.message_history_tree),
+            relayer_pool: rent_pool,
+            rent_payer,
+            claim_refund,
+        };
+        self.send_with_retries(
+            |blockhash| {
+                Ok(txbuilder::build_tx2(
+                    &relayer,
+                    &tx2_budget,
+                    &route,
+                    envelope,
+                    &message.signatures,
+                    &message_hash,
+                    &[],
+                    blockhash,
+                )?)
+            },
+            || Ok(self.tx_id_pda(&tx_id_pda)?.is_none()),
+        )?;
+        self.rent.closed(&tx_id_pda);
+        
+        eprintln!(
+            "relayed tx_id={} from chain {}",
+            envelope.tx_id, envelope.source_chain_id
+        );
+        Ok(())
+    }
+    
+    fn tx_id_pda(&self, address: &Pubkey) -> Result<Option<TxIdPDA>> {
+        Ok(account::fetch_optional(self.client, address)?)
+    }
+    
+    /// Top up the relayer pool when it cannot advance the next TxId PDA's rent
+    fn ensure_pool(&mut self, rent: u64) -> Result<()> {
+        let relayer = self.keypair.pubkey();
+        let pool: Option<RelayerPool> = account::fetch_optional(self.client, &pda::relayer_pool(&relayer).0)?;
+        if pool.is_some_and(|pool| pool.balance >= rent) {
+            return Ok(());
+        }
+        
+        let amount = self.pool_top_up.max(rent);
+        let deposit = ix::deposit_relayer_pool(&relayer, self.gateway_chain_id, amount);
+        self.send_with_retries(
+            |blockhash| Ok(txbuilder::compile_message(&relayer, &[deposit.clone()], &[], blockhash)?),
+            || Ok(false),
+        )?;
+        self.rent.deposited += amount;
+        Ok(())
+    }
+    
+    /// Sign and send the message built by `build`, retrying with a fresh blockhash and
+    /// exponential backoff; `landed` is checked first so a step confirmed after a timed
+    /// out attempt is never sent twice
+    fn send_with_retries(
+        &self,
+        build: impl Fn(Hash) -> Result<VersionedMessage>,
+        landed: impl Fn() -> Result<bool>,
+    ) -> Result<()> {
+        let mut delay = RETRY_BASE_DELAY;
+        let mut attempt = 0;
+        loop {
+            if landed()? {
+                return Ok(());
+            }
+            let message = build(self.client.get_latest_blockhash()?)?;
+            let transaction = VersionedTransaction::try_new(message, &[self.keypair])?;
+            match self.client.send_and_confirm_transaction(&transaction) {
+                Ok(_) => return Ok(()),
+                Err(error) if attempt >= self.max_retries => return Err(error.into()),
+                Err(error) => eprintln!("attempt {} failed, retrying: {error}", attempt + 1),
+            }
+            attempt += 1;
+            thread::sleep(delay);
+            delay *= 2;
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+//! Reference relayer for the Via Labs V4 message gateway
+//!
+//! - Watches the gateway for `SendRequested` events and prints each outbound envelope as a
+//!   JSON line for the validators and destination-chain submitters
+//! - Tails a feed of attested inbound messages (`feed`) and relays each one: TX1 with the
+//!   Ed25519 precompiles, then TX2, retrying each step and skipping the ones already landed
+//! - Accounts for the TxId PDA rent it advances, optionally through its relayer pool
+//!
+//! Configuration comes from the environment (see `config`); operators replace the file feed
+//! with their own attestation transport through `feed::AttestationSource`
+
+mod config;
+mod feed;
+mod inbound;
+mod outbound;
+mod rent;
+
+use std::thread;
+
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::signature::read_keypair_file;
+
+use crate::config::Config;
+use crate::feed::{AttestationSource, FileFeed};
+use crate::inbound::InboundRelayer;
+use crate::outbound::OutboundWatcher;
+
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn main() -> Result<()> {
+    let config = Config::from_env()?;
+    let client = RpcClient::new_with_commitment(config.rpc_url.clone(), config.commitment);
+    let keypair = read_keypair_file(&config.keypair_path)?;
+    
+    let mut feed = FileFeed::new(&config.attestation_feed);
+    let mut outbound = OutboundWatcher::new(&client);
+    let mut inbound = InboundRelayer::new(&client, &keypair, &config);
+    
+    loop {
+        for envelope in outbound.poll()? {
+            println!("{}", serde_json::to_string(&envelope)?);
+        }
+        
+        let messages = feed.poll()?;
+        for message in &messages {
+            if let Err(error) = inbound.relay(message) {
+                eprintln!("relay of tx_id={} failed: {error}", message.envelope.tx_id);
+            }
+        }
+        if !messages.is_empty() {
+            eprintln!("{}", inbound.rent());
+        }
+        
+        thread::sleep(config.poll_interval);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,95 @@
+//! `SendRequested` events of the gateway, read from its confirmed transactions
+
+use solana_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient};
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::UiTransactionEncoding;
+
+use message_gateway_client::events::SendRequested;
+use message_gateway_client::{event, ID};
+
+use crate::feed::EnvelopeJson;
+use crate::Result;
+
+/// Signatures requested per RPC page
+const SIGNATURE_PAGE: usize = 1000;
+
+/// Follows the gateway's transactions from the first poll onwards
+pub struct OutboundWatcher<'a> {
+    client: &'a RpcClient,
+    /// Newest transaction already read
+    cursor: Option<Signature>,
+}
+
+impl<'a> OutboundWatcher<'a> {
+    pub fn new(client: &'a RpcClient) -> Self {
+        Self { client, cursor: None }
+    }
+    
+    /// Envelopes sent since the last poll, oldest first
+    /// The first poll only sets the cursor, so a restart does not replay history
+    pub fn poll(&mut self) -> Result<Vec<EnvelopeJson>> {
+        let signatures = self.signatures_since_cursor()?;
+        let Some(newest) = signatures.first() else {
+            return Ok(Vec::new());
+        };
+        let first_poll = self.cursor.is_none();
+        self.cursor = Some(*newest);
+        if first_poll {
+            return Ok(Vec::new());
+        }
+        
+        let mut envelopes = Vec::new();
+        for signature in signatures.iter().rev() {
+            for sent in event::parse_events::<SendRequested>(&self.logs(signature)?) {
+                envelopes.push(EnvelopeJson::from(&sent.envelope));
+            }
+        }
+        Ok(envelopes)
+    }
+    
+    /// Successful gateway transactions newer than the cursor, newest first
+    /// (only the newest page before the first poll)
+    fn signatures_since_cursor(&self) -> Result<Vec<Signature>> {
+        let mut signatures = Vec::new();
+        let mut before = None;
+        loop {
+            let page = self.client.get_signatures_for_address_with_config(
+                &ID,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until: self.cursor,
+                    limit: Some(SIGNATURE_PAGE),
+                    commitment: Some(self.client.commitment()),
+                },
+            )?;
+            let full_page = page.len() == SIGNATURE_PAGE;
+            for status in page {
+                let signature: Signature = status.signature.parse()?;
+                before = Some(signature);
+                if status.err.is_none() {
+                    signatures.push(signature);
+                }
+            }
+            if !full_page || self.cursor.is_none() {
+                return Ok(signatures);
+            }
+        }
+    }
+    
+    fn logs(&self, signature: &Signature) -> Result<Vec<String>> {
+        let transaction = self.client.get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Json),
+                commitment: Some(self.client.commitment()),
+                max_supported_transaction_version: Some(0),
+            },
+        )?;
+        Ok(match transaction.transaction.meta.map(|meta| meta.log_messages) {
+            Some(OptionSerializer::Some(logs)) => logs,
+            _ => Vec::new(),
+        })
+    }
+}
\ No newline at end of file
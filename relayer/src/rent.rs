@@ -0,0 +1,55 @@
+//! TxId PDA rent the relayer has advanced and got back
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+
+/// Rent ledger of the TxId PDAs this relayer opened
+/// TX2 (or gc_batch once the PDA expires) refunds the rent to whoever paid it: the relayer
+/// or its pool
+#[derive(Default)]
+pub struct RentLedger {
+    open: HashMap<Pubkey, u64>,
+    /// Lamports advanced by TX1s since start
+    pub advanced: u64,
+    /// Lamports refunded by TX2s since start
+    pub refunded: u64,
+    /// Lamports deposited into the relayer pool since start
+    pub deposited: u64,
+}
+
+impl RentLedger {
+    /// TX1 opened `tx_id_pda`, advancing `rent`
+    pub fn opened(&mut self, tx_id_pda: Pubkey, rent: u64) {
+        if self.open.insert(tx_id_pda, rent).is_none() {
+            self.advanced += rent;
+        }
+    }
+    
+    /// TX2 closed `tx_id_pda`, refunding its rent
+    pub fn closed(&mut self, tx_id_pda: &Pubkey) {
+        if let Some(rent) = self.open.remove(tx_id_pda) {
+            self.refunded += rent;
+        }
+    }
+    
+    /// Rent locked in PDAs still open (recoverable through TX2 or gc_batch)
+    pub fn outstanding(&self) -> u64 {
+        self.open.values().sum()
+    }
+}
+
+impl fmt::Display for RentLedger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rent: advanced {} refunded {} outstanding {} in {} PDAs, pool deposits {}",
+            self.advanced,
+            self.refunded,
+            self.outstanding(),
+            self.open.len(),
+            self.deposited
+        )
+    }
+}
\ No newline at end of file
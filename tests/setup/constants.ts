@@ -32,6 +32,8 @@ export const PDA_SEEDS = {
   GATEWAY: "gateway",
   TX_ID: "tx",
   COUNTER: "counter",
+  CHAIN_CONFIG: "chain_config",
+  FEE_CONFIG: "fee_config",
 } as const;
 
 /**
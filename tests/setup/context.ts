@@ -14,12 +14,14 @@ import {
   deriveGatewayPDA,
   deriveTxIdPDA,
   deriveCounterPDA,
+  deriveChainConfigPDA,
+  deriveFeeConfigPDA,
   fundAccount,
   createFundedKeypair,
   wait,
   logInfo,
 } from "./helpers";
-import { createValidSignature } from "./signature";
+import { buildEnvelope, createValidSignature } from "./signature";
 import { TEST_CONFIG, CHAIN_IDS, WAIT_TIMES } from "./constants";
 
 /**
@@ -260,11 +262,32 @@ export class TestContext {
       throw new Error("Context not properly setup");
     }
 
+    // send_message only accepts configured destinations
+    const chainConfig = await this.ensureChainConfig(destChainId);
+    const [feeConfig] = deriveFeeConfigPDA(this.program.programId, destChainId);
+    const envelope = buildEnvelope(
+      txId,
+      this.chainId,
+      destChainId,
+      this.authority.publicKey.toBuffer(),
+      recipient,
+      chainData,
+      Buffer.alloc(0)
+    );
+
     const tx = await this.program.methods
-      .sendMessage(txId, recipient, destChainId, chainData, confirmations)
+      .sendMessage(envelope, confirmations)
       .accounts({
         gateway: this.gatewayPDA,
         sender: this.authority.publicKey,
+        chainConfig,
+        feeConfig,
+        channel: null,
+        priceUpdate: null,
+        feePayer: null,
+        solVault: null,
+        feeVault: null,
+        systemProgram: null,
       })
       .signers([this.authority])
       .rpc();
@@ -273,6 +296,31 @@ export class TestContext {
     return tx;
   }
 
+  /**
+   * Chain config of a counterpart chain, initialized with the default route hash
+   * scheme (keccak256, little endian) if it does not exist yet
+   */
+  async ensureChainConfig(chainId: BN): Promise<PublicKey> {
+    const [chainConfig] = deriveChainConfigPDA(this.program.programId, chainId);
+    if (await this.connection.getAccountInfo(chainConfig)) {
+      return chainConfig;
+    }
+
+    await this.program.methods
+      .initializeChainConfig(chainId, { keccak256: {} }, { littleEndian: {} })
+      .accounts({
+        chainConfig,
+        gateway: this.gatewayPDA!,
+        authority: this.authority!.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([this.authority!])
+      .rpc();
+
+    this.metrics.transactionCount++;
+    return chainConfig;
+  }
+
   /**
    * Create TxId PDA (TX1) - Simple version for tests
    */
@@ -387,6 +435,10 @@ export class TestContext {
       this.program.programId,
       sourceChainId
     );
+    const [chainConfig] = deriveChainConfigPDA(
+      this.program.programId,
+      sourceChainId
+    );
 
     // If no signatures provided, generate a valid one for testing
     let validSignatures = signatures;
@@ -408,6 +460,7 @@ export class TestContext {
         {
           signature: Array.from(validSignature.signature),
           signer: validSignature.signer,
+          scheme: { ed25519: {} },
         },
       ];
       ed25519Instructions.push(validSignature.ed25519Instruction);
@@ -420,24 +473,28 @@ export class TestContext {
       }
     }
 
+    const envelope = buildEnvelope(
+      txId,
+      sourceChainId,
+      destChainId,
+      sender,
+      recipient,
+      onChainData,
+      offChainData
+    );
+
     // Build the createTxPda instruction using Anchor
     const createTxPdaInstruction = await this.program.methods
-      .createTxPda(
-        txId,
-        sourceChainId,
-        destChainId,
-        sender,
-        recipient,
-        onChainData,
-        offChainData,
-        validSignatures
-      )
+      .createTxPda(envelope, validSignatures)
       .accounts({
         txIdPda: txIdPDA,
+        gateway: this.gatewayPDA!,
         counterPda: counterPDA,
+        chainConfig,
+        payer: null,
         relayer: relayer.publicKey,
-        systemProgram: SystemProgram.programId,
         instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
       })
       .instruction();
 
@@ -483,6 +540,10 @@ export class TestContext {
       sourceChainId,
       txId
     );
+    const [chainConfig] = deriveChainConfigPDA(
+      this.program.programId,
+      sourceChainId
+    );
 
     // Derive signer registry PDAs
     const [viaRegistry] = PublicKey.findProgramAddressSync(
@@ -548,10 +609,12 @@ export class TestContext {
         {
           signature: Array.from(signature1.signature),
           signer: signature1.signer,
+          scheme: { ed25519: {} },
         },
         {
           signature: Array.from(signature2.signature),
           signer: signature2.signer,
+          scheme: { ed25519: {} },
         },
       ];
 
@@ -629,26 +692,33 @@ export class TestContext {
       }
     }
 
+    const envelope = buildEnvelope(
+      txId,
+      sourceChainId,
+      destChainId,
+      sender,
+      recipient,
+      onChainData,
+      offChainData
+    );
+
     const tx = await this.program.methods
-      .processMessage(
-        txId,
-        sourceChainId,
-        destChainId,
-        sender,
-        recipient,
-        onChainData,
-        offChainData,
-        validSignatures
-      )
+      .processMessage(envelope, validSignatures)
       .accounts({
         gateway: this.gatewayPDA,
         txIdPda: txIdPDA,
+        chainConfig,
         viaRegistry: viaRegistry,
         chainRegistry: chainRegistry,
+        channel: null,
         projectRegistry: projectRegistry,
+        relayerPool: null,
+        payer: null,
+        feeConfig: null,
+        feeVault: null,
         relayer: relayer.publicKey,
-        systemProgram: SystemProgram.programId,
         instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
       })
       .preInstructions(ed25519Instructions)
       .signers([relayer])
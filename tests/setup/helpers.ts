@@ -55,6 +55,26 @@ export function deriveCounterPDA(
   );
 }
 
+export function deriveChainConfigPDA(
+  programId: PublicKey,
+  chainId: BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEEDS.CHAIN_CONFIG), chainId.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+export function deriveFeeConfigPDA(
+  programId: PublicKey,
+  destChainId: BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from(PDA_SEEDS.FEE_CONFIG), destChainId.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
 /**
  * Account Helpers
  */
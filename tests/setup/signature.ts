@@ -3,8 +3,15 @@ import { Keypair, Ed25519Program, PublicKey } from "@solana/web3.js";
 import { keccak256 } from "js-sha3";
 import * as nacl from "tweetnacl";
 
+export const PROTOCOL_VERSION = 4;
+export const ENVELOPE_VERSION = 1;
+export const GATEWAY_PROGRAM_ID = new PublicKey(
+  "2RgmXiQc6d5nys8vCMLKAovNKXneQK9SLkgRtnThex7i"
+);
+
 /**
  * Create the exact same message hash that the Solana program generates
+ * for a MessageEnvelope
  */
 export function createMessageHash(
   txId: BN,
@@ -13,10 +20,18 @@ export function createMessageHash(
   sender: Buffer,
   recipient: Buffer,
   onChainData: Buffer,
-  offChainData: Buffer
+  offChainData: Buffer,
+  messageType: number = 0,
+  deadline: BN = new BN(0),
+  sequence: BN = new BN(0),
+  protocolVersion: number = PROTOCOL_VERSION,
+  daPointer: Buffer = Buffer.alloc(0)
 ): Buffer {
   const encoded: number[] = [];
 
+  // u8 envelope version
+  encoded.push(ENVELOPE_VERSION);
+
   // u128 tx_id (16 bytes, little endian)
   const txIdBytes = txId.toArrayLike(Buffer, "le", 16);
   encoded.push(...Array.from(txIdBytes));
@@ -33,7 +48,22 @@ export function createMessageHash(
   encodeLengthPrefixed(encoded, sender);
   encodeLengthPrefixed(encoded, recipient);
   encodeLengthPrefixed(encoded, onChainData);
-  encodeLengthPrefixed(encoded, offChainData);
+
+  // bytes32 keccak256(off_chain_data) commitment
+  encoded.push(...Array.from(Buffer.from(keccak256(new Uint8Array(offChainData)), "hex")));
+
+  // u8 message_type and i64 deadline (8 bytes, little endian)
+  encoded.push(messageType);
+  encoded.push(...Array.from(deadline.toTwos(64).toArrayLike(Buffer, "le", 8)));
+
+  // u64 channel sequence (8 bytes, little endian, 0 = no channel)
+  encoded.push(...Array.from(sequence.toArrayLike(Buffer, "le", 8)));
+
+  // u8 protocol version
+  encoded.push(protocolVersion);
+
+  // Length-prefixed data-availability pointer (empty = none)
+  encodeLengthPrefixed(encoded, daPointer);
 
   // Use keccak256 (same as Solana's keccak syscall)
   const hash = keccak256(new Uint8Array(encoded));
@@ -41,6 +71,38 @@ export function createMessageHash(
   return Buffer.from(hash, "hex");
 }
 
+/**
+ * MessageEnvelope argument of send_message, create_tx_pda and process_message, with
+ * the fields the harness does not vary left at their defaults (the zero
+ * off_chain_data_hash makes the program derive it from off_chain_data)
+ */
+export function buildEnvelope(
+  txId: BN,
+  sourceChainId: BN,
+  destChainId: BN,
+  sender: Buffer,
+  recipient: Buffer,
+  onChainData: Buffer,
+  offChainData: Buffer
+) {
+  return {
+    version: ENVELOPE_VERSION,
+    txId,
+    sourceChainId,
+    destChainId,
+    sender,
+    recipient,
+    onChainData,
+    offChainData,
+    offChainDataHash: new Array(32).fill(0),
+    messageType: 0,
+    deadline: new BN(0),
+    sequence: new BN(0),
+    protocolVersion: PROTOCOL_VERSION,
+    daPointer: Buffer.alloc(0),
+  };
+}
+
 /**
  * Encode data with length prefix (u32 length + data bytes)
  */
@@ -56,6 +118,38 @@ function encodeLengthPrefixed(buffer: number[], data: Buffer): void {
   buffer.push(...Array.from(data));
 }
 
+/**
+ * Domain separator binding signatures to a single gateway deployment
+ */
+export function createDomainSeparator(
+  programId: PublicKey,
+  gatewayChainId: BN
+): Buffer {
+  const encoded = Buffer.concat([
+    Buffer.from("ViaLabsMessageGateway", "utf8"),
+    programId.toBuffer(),
+    gatewayChainId.toArrayLike(Buffer, "le", 8),
+    Buffer.from([PROTOCOL_VERSION]),
+  ]);
+  return Buffer.from(keccak256(new Uint8Array(encoded)), "hex");
+}
+
+/**
+ * Create the domain-separated hash that validators actually sign
+ */
+export function createSigningHash(
+  programId: PublicKey,
+  gatewayChainId: BN,
+  messageHash: Buffer
+): Buffer {
+  const encoded = Buffer.concat([
+    Buffer.from([0x19, 0x01]),
+    createDomainSeparator(programId, gatewayChainId),
+    messageHash,
+  ]);
+  return Buffer.from(keccak256(new Uint8Array(encoded)), "hex");
+}
+
 /**
  * Create a valid Ed25519 signature for testing
  * Signs the actual message hash that the program will validate
@@ -76,14 +170,19 @@ export function createValidSignature(
   ed25519Instruction: any;
 } {
   // Create the exact message hash that the Solana program will validate
-  const messageHash = createMessageHash(
-    txId,
-    sourceChainId,
+  // (TX1/TX2 use the destination gateway's chain id for the domain)
+  const messageHash = createSigningHash(
+    GATEWAY_PROGRAM_ID,
     destChainId,
-    sender,
-    recipient,
-    onChainData,
-    offChainData
+    createMessageHash(
+      txId,
+      sourceChainId,
+      destChainId,
+      sender,
+      recipient,
+      onChainData,
+      offChainData
+    )
   );
 
   // Sign the actual message hash (32 bytes)
@@ -0,0 +1,358 @@
+import { BN } from "@coral-xyz/anchor";
+import {
+  ComputeBudgetProgram,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+  sendAndConfirmTransaction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  TestContext,
+  createFundedKeypair,
+  createValidSignature,
+  deriveChainConfigPDA,
+  deriveCounterPDA,
+  deriveTxIdPDA,
+  ENVELOPE_VERSION,
+  PROTOCOL_VERSION,
+} from "../setup";
+
+/**
+ * Throughput harness for parallel relaying
+ *
+ * Drives many TX1/TX2 pairs concurrently against a local validator and reports the
+ * achieved pair throughput, per-transaction latency and how TX1s pile up on the
+ * CounterPDA they all write. Every worker pays with its own relayer so the fee payer
+ * is not a shared write lock; the only account written by every TX1 of a source
+ * chain is that chain's CounterPDA, so spreading the load over more source chains
+ * shows what sharding the counter would buy
+ *
+ * Skipped unless THROUGHPUT=1 (see `yarn test:throughput`). Knobs:
+ *   THROUGHPUT_PAIRS          TX1/TX2 pairs to relay (default 200)
+ *   THROUGHPUT_CONCURRENCY    pairs in flight at once, one relayer each (default 32)
+ *   THROUGHPUT_SOURCE_CHAINS  source chains (and so CounterPDAs) to spread over (default 1)
+ */
+const PAIRS = parseInt(process.env.THROUGHPUT_PAIRS || "200");
+const CONCURRENCY = parseInt(process.env.THROUGHPUT_CONCURRENCY || "32");
+const SOURCE_CHAINS = parseInt(process.env.THROUGHPUT_SOURCE_CHAINS || "1");
+
+const COMPUTE_UNIT_LIMIT = 400_000;
+const STATUS_BATCH = 256;
+
+interface PairResult {
+  sourceChainId: BN;
+  tx1?: string;
+  tx2?: string;
+  tx1LatencyMs?: number;
+  tx2LatencyMs?: number;
+  error?: string;
+}
+
+(process.env.THROUGHPUT ? describe : describe.skip)(
+  "Throughput: parallel TX1/TX2 relaying",
+  () => {
+    let context: TestContext;
+    let validators: Keypair[];
+    let relayers: Keypair[];
+    let sourceChainIds: BN[];
+
+    before(async () => {
+      context = new TestContext();
+      await context.setup({ silent: true });
+
+      // Two validators in both layers satisfy MIN_SIGNATURES_REQUIRED
+      validators = [Keypair.generate(), Keypair.generate()];
+      const validatorKeys = validators.map((v) => v.publicKey);
+      await context.initializeSignerRegistry(
+        "VIA",
+        context.chainId,
+        validatorKeys,
+        2
+      );
+
+      sourceChainIds = [];
+      for (let i = 0; i < SOURCE_CHAINS; i++) {
+        const sourceChainId = context.chainId.addn(1 + i);
+        sourceChainIds.push(sourceChainId);
+        await context.initializeSignerRegistry(
+          "Chain",
+          sourceChainId,
+          validatorKeys,
+          2
+        );
+      }
+
+      relayers = await Promise.all(
+        Array.from({ length: CONCURRENCY }, () =>
+          createFundedKeypair(context.connection)
+        )
+      );
+    });
+
+    after(async () => {
+      await context.teardown();
+    });
+
+    it(`relays ${PAIRS} pairs with ${CONCURRENCY} in flight over ${SOURCE_CHAINS} source chain(s)`, async () => {
+      const results: PairResult[] = [];
+      let next = 0;
+
+      const started = Date.now();
+      await Promise.all(
+        relayers.map(async (relayer) => {
+          while (next < PAIRS) {
+            const index = next++;
+            const sourceChainId = sourceChainIds[index % sourceChainIds.length];
+            results.push(
+              await relayPair(
+                context,
+                relayer,
+                validators,
+                sourceChainId,
+                new BN(started).muln(1000).addn(index)
+              )
+            );
+          }
+        })
+      );
+      const elapsedMs = Date.now() - started;
+
+      const completed = results.filter((r) => r.tx2);
+      const failed = results.filter((r) => r.error);
+      const tx1Slots = await landingSlots(
+        context,
+        completed.map((r) => r.tx1!)
+      );
+
+      console.log("\n📊 Throughput:");
+      console.log(`  Pairs completed: ${completed.length}/${PAIRS}`);
+      console.log(`  Elapsed time: ${elapsedMs}ms`);
+      console.log(
+        `  Pair TPS: ${((completed.length * 1000) / elapsedMs).toFixed(1)}`
+      );
+      console.log(
+        `  Transaction TPS: ${((completed.length * 2000) / elapsedMs).toFixed(1)}`
+      );
+      logLatency("TX1", completed.map((r) => r.tx1LatencyMs!));
+      logLatency("TX2", completed.map((r) => r.tx2LatencyMs!));
+      logCounterContention(completed, tx1Slots);
+
+      if (failed.length > 0) {
+        console.log(`  Failures: ${failed.length}`);
+        const reasons = new Map<string, number>();
+        for (const r of failed) {
+          reasons.set(r.error!, (reasons.get(r.error!) || 0) + 1);
+        }
+        for (const [reason, count] of reasons) {
+          console.log(`    ${count}x ${reason}`);
+        }
+      }
+
+      expect(completed.length).to.be.greaterThan(0);
+    });
+  }
+);
+
+/**
+ * Relay one message: TX1 creates the TxId PDA and bumps the source chain's counter,
+ * TX2 verifies it again and closes the PDA
+ * Both transactions carry the same Ed25519 precompile instructions in the same order,
+ * so TX2 finds them at the offsets TX1 recorded
+ */
+async function relayPair(
+  context: TestContext,
+  relayer: Keypair,
+  validators: Keypair[],
+  sourceChainId: BN,
+  txId: BN
+): Promise<PairResult> {
+  const result: PairResult = { sourceChainId };
+  const program = context.program;
+  const destChainId = context.chainId;
+
+  const sender = Buffer.alloc(20, 0xab);
+  const recipient = relayer.publicKey.toBuffer();
+  const onChainData = Buffer.from(`throughput ${txId.toString()}`, "utf8");
+  const offChainData = Buffer.alloc(0);
+
+  const attestations = validators.map((validator) =>
+    createValidSignature(
+      txId,
+      sourceChainId,
+      destChainId,
+      sender,
+      recipient,
+      onChainData,
+      offChainData,
+      validator
+    )
+  );
+  const signatures = attestations.map((a) => ({
+    signature: Array.from(a.signature),
+    signer: a.signer,
+    scheme: { ed25519: {} },
+  }));
+  const preInstructions: TransactionInstruction[] = [
+    ComputeBudgetProgram.setComputeUnitLimit({ units: COMPUTE_UNIT_LIMIT }),
+    ...attestations.map((a) => a.ed25519Instruction),
+  ];
+
+  const envelope = {
+    version: ENVELOPE_VERSION,
+    txId,
+    sourceChainId,
+    destChainId,
+    sender,
+    recipient,
+    onChainData,
+    offChainData,
+    offChainDataHash: new Array(32).fill(0),
+    messageType: 0,
+    deadline: new BN(0),
+    sequence: new BN(0),
+    protocolVersion: PROTOCOL_VERSION,
+    daPointer: Buffer.alloc(0),
+  };
+
+  const [txIdPda] = deriveTxIdPDA(program.programId, sourceChainId, txId);
+  const [counterPda] = deriveCounterPDA(program.programId, sourceChainId);
+  const [chainConfig] = deriveChainConfigPDA(program.programId, sourceChainId);
+  const [viaRegistry] = context.getSignerRegistryPDA("VIA", destChainId);
+  const [chainRegistry] = context.getSignerRegistryPDA("Chain", sourceChainId);
+
+  try {
+    const tx1 = await program.methods
+      .createTxPda(envelope, signatures)
+      .accounts({
+        txIdPda,
+        gateway: context.gatewayPDA!,
+        counterPda,
+        chainConfig,
+        payer: null,
+        relayer: relayer.publicKey,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    let sent = Date.now();
+    result.tx1 = await send(context, relayer, [...preInstructions, tx1]);
+    result.tx1LatencyMs = Date.now() - sent;
+
+    const tx2 = await program.methods
+      .processMessage(envelope, signatures)
+      .accounts({
+        gateway: context.gatewayPDA!,
+        txIdPda,
+        chainConfig,
+        viaRegistry,
+        chainRegistry,
+        channel: null,
+        projectRegistry: null,
+        relayerPool: null,
+        payer: null,
+        feeConfig: null,
+        feeVault: null,
+        relayer: relayer.publicKey,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+    sent = Date.now();
+    result.tx2 = await send(context, relayer, [...preInstructions, tx2]);
+    result.tx2LatencyMs = Date.now() - sent;
+  } catch (error: any) {
+    result.error = (error?.message || String(error)).split("\n")[0];
+  }
+  return result;
+}
+
+/**
+ * Send with the relayer as fee payer (not the provider wallet, which every worker
+ * would otherwise write-lock)
+ */
+async function send(
+  context: TestContext,
+  relayer: Keypair,
+  instructions: TransactionInstruction[]
+): Promise<string> {
+  const transaction = new Transaction().add(...instructions);
+  transaction.feePayer = relayer.publicKey;
+  const signature = await sendAndConfirmTransaction(
+    context.connection,
+    transaction,
+    [relayer],
+    { commitment: "confirmed", skipPreflight: true }
+  );
+  context.metrics.transactionCount++;
+  return signature;
+}
+
+/**
+ * Slot each transaction landed in
+ */
+async function landingSlots(
+  context: TestContext,
+  signatures: string[]
+): Promise<Map<string, number>> {
+  const slots = new Map<string, number>();
+  for (let i = 0; i < signatures.length; i += STATUS_BATCH) {
+    const batch = signatures.slice(i, i + STATUS_BATCH);
+    const { value } = await context.connection.getSignatureStatuses(batch, {
+      searchTransactionHistory: true,
+    });
+    value.forEach((status, j) => {
+      if (status) {
+        slots.set(batch[j], status.slot);
+      }
+    });
+  }
+  return slots;
+}
+
+function logLatency(label: string, latencies: number[]): void {
+  if (latencies.length === 0) return;
+  const sorted = [...latencies].sort((a, b) => a - b);
+  const at = (q: number) =>
+    sorted[Math.min(sorted.length - 1, Math.floor(q * sorted.length))];
+  console.log(
+    `  ${label} latency: p50=${at(0.5)}ms p95=${at(0.95)}ms max=${at(1)}ms`
+  );
+}
+
+/**
+ * TX1s writing the same CounterPDA are serialized within a slot; the more of them
+ * land in one slot, the more the counter's write lock bounds throughput
+ */
+function logCounterContention(
+  completed: PairResult[],
+  tx1Slots: Map<string, number>
+): void {
+  const perCounterSlot = new Map<string, Map<number, number>>();
+  for (const r of completed) {
+    const slot = tx1Slots.get(r.tx1!);
+    if (slot === undefined) continue;
+    const counter = r.sourceChainId.toString();
+    const slots = perCounterSlot.get(counter) || new Map<number, number>();
+    slots.set(slot, (slots.get(slot) || 0) + 1);
+    perCounterSlot.set(counter, slots);
+  }
+
+  console.log("  CounterPDA write locks (TX1s per slot):");
+  for (const [counter, slots] of perCounterSlot) {
+    const writes = [...slots.values()];
+    const total = writes.reduce((a, b) => a + b, 0);
+    const contended = writes.filter((w) => w > 1).reduce((a, b) => a + b, 0);
+    console.log(
+      `    source chain ${counter}: ${total} writes over ${slots.size} slots, ` +
+        `avg ${(total / slots.size).toFixed(2)}/slot, max ${Math.max(
+          ...writes
+        )}/slot, ${((contended * 100) / total).toFixed(
+          1
+        )}% sharing a slot with another write`
+    );
+  }
+}